@@ -0,0 +1,26 @@
+-- Encrypted Log Payloads (Crypto-Shredder)
+-- Persists the AES-256-GCM ciphertext and nonce produced by VeridionKeyStore::log_event.
+-- The DEK needed to decrypt lives in encrypted_log_keys; once it is shredded the
+-- ciphertext stored here becomes permanently unreadable (GDPR Article 17).
+
+CREATE TABLE IF NOT EXISTS encrypted_log_payloads (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    log_id VARCHAR(255) NOT NULL UNIQUE, -- Same value as compliance_records.tx_id
+    ciphertext BYTEA NOT NULL,
+    nonce BYTEA NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_encrypted_log_payloads_log_id ON encrypted_log_payloads(log_id);
+
+-- Permission to decrypt logged payloads (separate from plain compliance.read)
+INSERT INTO permissions (name, resource, action, description) VALUES
+    ('compliance.decrypt', 'compliance', 'decrypt', 'Decrypt logged payloads via the crypto-shredder')
+ON CONFLICT (name) DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id
+FROM roles r, permissions p
+WHERE r.name IN ('admin', 'compliance_officer', 'auditor')
+  AND p.name = 'compliance.decrypt'
+ON CONFLICT DO NOTHING;
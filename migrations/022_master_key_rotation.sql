@@ -0,0 +1,47 @@
+-- Master Key Rotation (Crypto-Shredder)
+-- Every wrapped DEK records which master key version wrapped it, so the master key
+-- can be rotated: the background re-wrap job moves live DEKs to the current version
+-- without touching ciphertext, after which old versions can be retired.
+
+CREATE TABLE IF NOT EXISTS master_key_versions (
+    key_id VARCHAR(64) PRIMARY KEY,
+    kdf VARCHAR(32) NOT NULL, -- HKDF-SHA256, RAW_PADDED (pre-rotation keys)
+    status VARCHAR(20) NOT NULL DEFAULT 'DECRYPT_ONLY', -- ACTIVE, DECRYPT_ONLY, RETIRED
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_seen_at TIMESTAMP, -- Last time a node started with this version configured
+    retired_at TIMESTAMP,
+    retired_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    CONSTRAINT ck_master_key_versions_status CHECK (status IN ('ACTIVE', 'DECRYPT_ONLY', 'RETIRED'))
+);
+
+-- Keys persisted before versioning were wrapped with the raw (zero-padded) master key
+INSERT INTO master_key_versions (key_id, kdf, status) VALUES
+    ('legacy', 'RAW_PADDED', 'DECRYPT_ONLY')
+ON CONFLICT (key_id) DO NOTHING;
+
+ALTER TABLE encrypted_log_keys
+    ADD COLUMN IF NOT EXISTS master_key_id VARCHAR(64) NOT NULL DEFAULT 'legacy',
+    ADD COLUMN IF NOT EXISTS rewrapped_at TIMESTAMP;
+
+CREATE INDEX IF NOT EXISTS idx_encrypted_log_keys_master_key_id
+    ON encrypted_log_keys(master_key_id) WHERE shredded_at IS NULL;
+
+-- Permissions for key management
+INSERT INTO permissions (name, resource, action, description) VALUES
+    ('keys.read', 'keys', 'read', 'View master key versions and re-wrap progress'),
+    ('keys.write', 'keys', 'write', 'Re-wrap DEKs and retire master key versions')
+ON CONFLICT (name) DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id
+FROM roles r, permissions p
+WHERE r.name = 'admin'
+  AND p.name IN ('keys.read', 'keys.write')
+ON CONFLICT DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id
+FROM roles r, permissions p
+WHERE r.name IN ('compliance_officer', 'auditor')
+  AND p.name = 'keys.read'
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,59 @@
+-- Data Subject Keys (Crypto-Shredder, GDPR Article 17)
+-- Optional key hierarchy: master key -> per-subject KEK -> DEK. Destroying a
+-- subject's KEK makes every record logged under it unreadable in one step.
+
+CREATE TABLE IF NOT EXISTS data_subject_keys (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id VARCHAR(255) NOT NULL,
+    master_key_id VARCHAR(64) NOT NULL, -- Master key version wrapping the KEK
+    wrapped_kek BYTEA NOT NULL,
+    key_fingerprint VARCHAR(64) NOT NULL, -- SHA-256 of the plaintext KEK
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    rewrapped_at TIMESTAMP,
+    destroyed_at TIMESTAMP
+);
+
+-- One live KEK per subject; a new one may be created after an erasure
+CREATE UNIQUE INDEX IF NOT EXISTS uq_data_subject_keys_live_user
+    ON data_subject_keys(user_id) WHERE destroyed_at IS NULL;
+CREATE INDEX IF NOT EXISTS idx_data_subject_keys_master_key_id
+    ON data_subject_keys(master_key_id) WHERE destroyed_at IS NULL;
+
+-- DEKs wrapped by a subject KEK have master_key_id = 'subject' and subject_id set
+ALTER TABLE encrypted_log_keys
+    ADD COLUMN IF NOT EXISTS subject_id VARCHAR(255),
+    ADD COLUMN IF NOT EXISTS key_fingerprint VARCHAR(64); -- SHA-256 of the plaintext DEK
+
+CREATE INDEX IF NOT EXISTS idx_encrypted_log_keys_subject_id
+    ON encrypted_log_keys(subject_id) WHERE subject_id IS NOT NULL;
+
+-- Signed erasure certificates
+CREATE TABLE IF NOT EXISTS erasure_certificates (
+    certificate_id VARCHAR(64) PRIMARY KEY,
+    scope VARCHAR(20) NOT NULL, -- RECORD, DATA_SUBJECT
+    subject_id VARCHAR(255),
+    seal_ids TEXT[] NOT NULL DEFAULT '{}',
+    tx_ids TEXT[] NOT NULL DEFAULT '{}',
+    key_fingerprints TEXT[] NOT NULL DEFAULT '{}',
+    erased_at TIMESTAMPTZ NOT NULL,
+    operator VARCHAR(255) NOT NULL,
+    operator_id VARCHAR(255),
+    certificate_hash VARCHAR(64) NOT NULL,
+    signature TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT ck_erasure_certificates_scope CHECK (scope IN ('RECORD', 'DATA_SUBJECT'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_erasure_certificates_subject_id ON erasure_certificates(subject_id);
+
+-- Permission for full data subject erasure
+INSERT INTO permissions (name, resource, action, description) VALUES
+    ('data_subject.erase', 'data_subject', 'erase', 'Erase all records of a data subject (GDPR Article 17)')
+ON CONFLICT (name) DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id
+FROM roles r, permissions p
+WHERE r.name IN ('admin', 'compliance_officer')
+  AND p.name = 'data_subject.erase'
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,53 @@
+-- Jurisdiction Policies (Sovereign Lock)
+-- Per-tenant allow/deny lists, adequacy decisions, cloud region mappings and SCC
+-- exceptions, stored as policy_type = 'JURISDICTION' rows of policy_versions.
+-- company_id NULL is the global policy used by tenants without their own.
+
+CREATE TABLE IF NOT EXISTS policy_versions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    policy_type VARCHAR(100) NOT NULL,
+    policy_name VARCHAR(255),
+    policy_config JSONB NOT NULL DEFAULT '{}',
+    version_number INTEGER NOT NULL DEFAULT 1,
+    is_active BOOLEAN NOT NULL DEFAULT false,
+    company_id UUID,
+    created_by VARCHAR(255),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    activated_at TIMESTAMP,
+    deactivated_at TIMESTAMP
+);
+
+ALTER TABLE policy_versions
+    ADD COLUMN IF NOT EXISTS policy_name VARCHAR(255),
+    ADD COLUMN IF NOT EXISTS policy_config JSONB NOT NULL DEFAULT '{}',
+    ADD COLUMN IF NOT EXISTS company_id UUID,
+    ADD COLUMN IF NOT EXISTS activated_at TIMESTAMP,
+    ADD COLUMN IF NOT EXISTS deactivated_at TIMESTAMP;
+
+-- One active jurisdiction policy per tenant (and one global)
+CREATE UNIQUE INDEX IF NOT EXISTS uq_policy_versions_active_jurisdiction
+    ON policy_versions (COALESCE(company_id, '00000000-0000-0000-0000-000000000000'::uuid))
+    WHERE policy_type = 'JURISDICTION' AND is_active = true;
+
+CREATE INDEX IF NOT EXISTS idx_policy_versions_type_company
+    ON policy_versions(policy_type, company_id, version_number);
+
+-- Permissions used by the policy endpoints
+INSERT INTO permissions (name, resource, action, description) VALUES
+    ('policy.read', 'policy', 'read', 'View policies, versions and simulations'),
+    ('policy.write', 'policy', 'write', 'Publish, activate and roll back policies')
+ON CONFLICT (name) DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id
+FROM roles r, permissions p
+WHERE r.name IN ('admin', 'compliance_officer')
+  AND p.name IN ('policy.read', 'policy.write')
+ON CONFLICT DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id
+FROM roles r, permissions p
+WHERE r.name = 'auditor'
+  AND p.name = 'policy.read'
+ON CONFLICT DO NOTHING;
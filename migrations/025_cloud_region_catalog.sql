@@ -0,0 +1,14 @@
+-- Cloud Region Catalog
+-- Additions and corrections to the built-in AWS/Azure/GCP region table
+-- (src/core/region_catalog.rs). Rows here override built-in entries with the
+-- same region_code and are loaded at startup.
+
+CREATE TABLE IF NOT EXISTS cloud_region_catalog (
+    region_code VARCHAR(100) PRIMARY KEY,
+    provider VARCHAR(50) NOT NULL,
+    country VARCHAR(2) NOT NULL,
+    jurisdiction VARCHAR(20) NOT NULL,
+    location VARCHAR(255),
+    updated_by VARCHAR(255),
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,19 @@
+-- PII Token Vaults
+-- Token -> original value mappings of redacted proxy prompts and log_action
+-- payloads (src/core/pii_redaction.rs). The mapping is encrypted like any other
+-- payload: ciphertext in encrypted_log_payloads and wrapped DEK in
+-- encrypted_log_keys, both under log_id = vault_id. Shredding that key erases
+-- the original values; tokens left in logs and transcripts become meaningless.
+
+CREATE TABLE IF NOT EXISTS pii_token_vaults (
+    vault_id VARCHAR(255) PRIMARY KEY,
+    source VARCHAR(20) NOT NULL, -- LOG_ACTION, PROXY
+    agent_id VARCHAR(255) NOT NULL,
+    user_id VARCHAR(255),
+    tx_id VARCHAR(255), -- compliance_records.tx_id of a redacted log_action payload
+    token_counts JSONB NOT NULL DEFAULT '{}'::jsonb, -- e.g. {"EMAIL": 2, "IBAN": 1}
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_pii_token_vaults_user_id ON pii_token_vaults(user_id);
+CREATE INDEX IF NOT EXISTS idx_pii_token_vaults_tx_id ON pii_token_vaults(tx_id);
@@ -0,0 +1,50 @@
+-- AI Usage Budgets
+-- Per-agent and per-business-function bounds on AI vendor usage (tokens,
+-- requests, estimated EUR cost) enforced by the proxy (src/core/usage_budget.rs).
+-- DORA Article 6 (ICT risk management) and finance cost caps.
+
+CREATE TABLE IF NOT EXISTS usage_budgets (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    scope_type VARCHAR(30) NOT NULL CHECK (scope_type IN ('AGENT', 'BUSINESS_FUNCTION')),
+    scope_value VARCHAR(255) NOT NULL, -- agent_id or business function (e.g. CREDIT_SCORING)
+    period VARCHAR(10) NOT NULL CHECK (period IN ('DAILY', 'MONTHLY')), -- calendar periods in UTC
+    max_tokens BIGINT,
+    max_requests BIGINT,
+    max_cost_eur NUMERIC(14, 2),
+    soft_limit_percent INTEGER NOT NULL DEFAULT 80 CHECK (soft_limit_percent BETWEEN 1 AND 100),
+    enforcement VARCHAR(10) NOT NULL DEFAULT 'HARD' CHECK (enforcement IN ('HARD', 'SOFT')),
+    is_active BOOLEAN NOT NULL DEFAULT true,
+    created_by VARCHAR(255),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (scope_type, scope_value, period)
+);
+
+-- One row per proxied request; tokens are 0 when the vendor reported no usage
+CREATE TABLE IF NOT EXISTS ai_usage_records (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    agent_id VARCHAR(255) NOT NULL,
+    business_function VARCHAR(100),
+    vendor VARCHAR(100) NOT NULL,
+    model VARCHAR(255),
+    target_url TEXT NOT NULL,
+    upstream_status INTEGER NOT NULL,
+    input_tokens BIGINT NOT NULL DEFAULT 0,
+    output_tokens BIGINT NOT NULL DEFAULT 0,
+    total_tokens BIGINT NOT NULL DEFAULT 0,
+    cost_eur NUMERIC(14, 6) NOT NULL DEFAULT 0, -- estimate from the model price table
+    usage_reported BOOLEAN NOT NULL DEFAULT false,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_ai_usage_records_agent ON ai_usage_records(agent_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_ai_usage_records_business_function ON ai_usage_records(business_function, created_at);
+
+-- Alerts already sent, so each budget alerts once per period and level
+CREATE TABLE IF NOT EXISTS usage_budget_alerts (
+    budget_id UUID NOT NULL REFERENCES usage_budgets(id) ON DELETE CASCADE,
+    period_start TIMESTAMPTZ NOT NULL,
+    level VARCHAR(10) NOT NULL, -- SOFT, EXCEEDED
+    sent_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (budget_id, period_start, level)
+);
@@ -0,0 +1,23 @@
+-- Rollout Assignments
+-- Sticky cohort membership for policies rolled out to a percentage of traffic
+-- (src/core/rollout.rs). Each subject keeps the bucket it got on first sight;
+-- in_cohort follows the policy's rollout_percentage.
+
+ALTER TABLE policy_versions
+    ADD COLUMN IF NOT EXISTS rollout_bucket_by VARCHAR(30) NOT NULL DEFAULT 'AGENT', -- AGENT, TENANT, BUSINESS_FUNCTION, USER
+    ADD COLUMN IF NOT EXISTS rollout_salt VARCHAR(64); -- NULL: the policy version id
+
+CREATE TABLE IF NOT EXISTS rollout_assignments (
+    policy_version_id UUID NOT NULL REFERENCES policy_versions(id) ON DELETE CASCADE,
+    bucket_by VARCHAR(30) NOT NULL,
+    subject VARCHAR(255) NOT NULL, -- agent_id, company_id, business function or user_id
+    bucket INTEGER NOT NULL CHECK (bucket >= 0 AND bucket < 10000),
+    in_cohort BOOLEAN NOT NULL,
+    rollout_percentage INTEGER NOT NULL,
+    request_count BIGINT NOT NULL DEFAULT 1,
+    first_seen_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_seen_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (policy_version_id, bucket_by, subject)
+);
+
+CREATE INDEX IF NOT EXISTS idx_rollout_assignments_cohort ON rollout_assignments(policy_version_id, in_cohort);
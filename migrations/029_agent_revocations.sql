@@ -0,0 +1,24 @@
+-- Agent Revocations
+-- Revoked agents shared by all replicas (src/core/agent_revocation.rs). Each
+-- revoke or restore is appended to agent_revocation_events and announced with
+-- NOTIFY agent_revocations, '<agent_id>' so other replicas refresh their cache.
+
+CREATE TABLE IF NOT EXISTS agent_revocations (
+    agent_id VARCHAR(255) PRIMARY KEY,
+    reason TEXT,
+    revoked_by VARCHAR(255),
+    revoked_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    expires_at TIMESTAMPTZ -- NULL: until restored
+);
+
+CREATE TABLE IF NOT EXISTS agent_revocation_events (
+    id BIGSERIAL PRIMARY KEY,
+    agent_id VARCHAR(255) NOT NULL,
+    event_type VARCHAR(20) NOT NULL CHECK (event_type IN ('REVOKED', 'RESTORED')),
+    reason TEXT,
+    actor VARCHAR(255),
+    expires_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_agent_revocation_events_agent ON agent_revocation_events(agent_id, created_at DESC);
@@ -0,0 +1,39 @@
+-- Scoped Lockdowns
+-- Lockdown rules narrower than the global panic button (system_config
+-- is_locked_down): one business function, vendor domain, jurisdiction or tenant.
+-- Every rule carries a justification and an expiry and is linked to the DORA
+-- Lite incident opened for it (src/core/lockdown.rs).
+
+CREATE TABLE IF NOT EXISTS dora_lite_incidents (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    incident_type VARCHAR(100) NOT NULL,
+    description TEXT NOT NULL,
+    detected_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    resolved_at TIMESTAMPTZ,
+    severity VARCHAR(20) NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'OPEN',
+    impact_description TEXT,
+    mitigation_steps TEXT,
+    reported_to_authority BOOLEAN NOT NULL DEFAULT false,
+    reported_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS lockdown_rules (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    scope_type VARCHAR(30) NOT NULL
+        CHECK (scope_type IN ('GLOBAL', 'BUSINESS_FUNCTION', 'VENDOR_DOMAIN', 'JURISDICTION', 'TENANT')),
+    scope_value VARCHAR(255), -- NULL for GLOBAL
+    justification TEXT NOT NULL CHECK (length(trim(justification)) > 0),
+    created_by VARCHAR(255),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    expires_at TIMESTAMPTZ NOT NULL,
+    lifted_at TIMESTAMPTZ,
+    lifted_by VARCHAR(255),
+    lift_reason TEXT,
+    incident_id UUID REFERENCES dora_lite_incidents(id),
+    CHECK (scope_type = 'GLOBAL' OR scope_value IS NOT NULL)
+);
+
+CREATE INDEX IF NOT EXISTS idx_lockdown_rules_active ON lockdown_rules(expires_at) WHERE lifted_at IS NULL;
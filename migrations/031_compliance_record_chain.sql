@@ -0,0 +1,138 @@
+-- Hash-chained compliance_records
+-- Every record stores the hash of the record before it (prev_hash) and its own
+-- hash over its content and prev_hash (record_hash), in insertion order
+-- (chain_seq). The content columns can no longer be updated and rows cannot be
+-- deleted: erasure, rectification and oversight rejection append an entry that
+-- supersedes the original record instead (src/core/audit_chain.rs).
+
+ALTER TABLE compliance_records
+    ADD COLUMN IF NOT EXISTS chain_seq BIGINT,
+    ADD COLUMN IF NOT EXISTS prev_hash VARCHAR(64),
+    ADD COLUMN IF NOT EXISTS record_hash VARCHAR(64),
+    ADD COLUMN IF NOT EXISTS chained_at TIMESTAMPTZ,
+    -- ACTION, TOMBSTONE (erasure), RECTIFICATION or OVERSIGHT
+    ADD COLUMN IF NOT EXISTS entry_type VARCHAR(20) NOT NULL DEFAULT 'ACTION',
+    ADD COLUMN IF NOT EXISTS supersedes_seal_id VARCHAR(255);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_compliance_records_chain_seq ON compliance_records(chain_seq);
+CREATE INDEX IF NOT EXISTS idx_compliance_records_chained_at ON compliance_records(chained_at);
+CREATE INDEX IF NOT EXISTS idx_compliance_records_supersedes ON compliance_records(supersedes_seal_id)
+    WHERE supersedes_seal_id IS NOT NULL;
+
+-- SHA-256 over the chained fields joined by U+001F; must match
+-- audit_chain::record_hash
+CREATE OR REPLACE FUNCTION compliance_record_hash(
+    p_chain_seq BIGINT,
+    p_prev_hash TEXT,
+    p_seal_id TEXT,
+    p_tx_id TEXT,
+    p_agent_id TEXT,
+    p_action_summary TEXT,
+    p_status TEXT,
+    p_payload_hash TEXT,
+    p_user_id TEXT,
+    p_risk_level TEXT,
+    p_timestamp TIMESTAMPTZ,
+    p_entry_type TEXT,
+    p_supersedes_seal_id TEXT
+) RETURNS VARCHAR(64) AS $$
+    SELECT encode(sha256(convert_to(concat_ws(E'\x1f',
+        p_chain_seq::TEXT,
+        p_prev_hash,
+        p_seal_id,
+        p_tx_id,
+        p_agent_id,
+        p_action_summary,
+        p_status,
+        p_payload_hash,
+        COALESCE(p_user_id, ''),
+        COALESCE(p_risk_level, ''),
+        to_char(p_timestamp AT TIME ZONE 'UTC', 'YYYY-MM-DD"T"HH24:MI:SS.US"Z"'),
+        p_entry_type,
+        COALESCE(p_supersedes_seal_id, '')
+    ), 'UTF8')), 'hex')
+$$ LANGUAGE sql STABLE;
+
+-- Chain existing records in timestamp order
+DO $$
+DECLARE
+    rec RECORD;
+    seq BIGINT := 0;
+    last_hash VARCHAR(64) := repeat('0', 64);
+BEGIN
+    IF EXISTS (SELECT 1 FROM compliance_records WHERE chain_seq IS NOT NULL) THEN
+        RETURN;
+    END IF;
+    FOR rec IN SELECT * FROM compliance_records ORDER BY timestamp, created_at, id LOOP
+        seq := seq + 1;
+        UPDATE compliance_records
+        SET chain_seq = seq,
+            prev_hash = last_hash,
+            chained_at = rec.created_at,
+            record_hash = compliance_record_hash(
+                seq, last_hash, rec.seal_id, rec.tx_id, rec.agent_id, rec.action_summary,
+                rec.status, rec.payload_hash, rec.user_id, rec.risk_level, rec.timestamp,
+                rec.entry_type, rec.supersedes_seal_id
+            )
+        WHERE id = rec.id
+        RETURNING record_hash INTO last_hash;
+    END LOOP;
+END $$;
+
+-- Link each new record to the current head of the chain. The advisory lock
+-- serializes inserts until commit so two records never share a predecessor.
+CREATE OR REPLACE FUNCTION chain_compliance_record()
+RETURNS TRIGGER AS $$
+DECLARE
+    head RECORD;
+BEGIN
+    PERFORM pg_advisory_xact_lock(hashtext('compliance_records_chain'));
+    SELECT chain_seq, record_hash INTO head
+    FROM compliance_records
+    WHERE chain_seq IS NOT NULL
+    ORDER BY chain_seq DESC
+    LIMIT 1;
+
+    NEW.chain_seq := COALESCE(head.chain_seq, 0) + 1;
+    NEW.prev_hash := COALESCE(head.record_hash, repeat('0', 64));
+    NEW.chained_at := clock_timestamp();
+    NEW.record_hash := compliance_record_hash(
+        NEW.chain_seq, NEW.prev_hash, NEW.seal_id, NEW.tx_id, NEW.agent_id, NEW.action_summary,
+        NEW.status, NEW.payload_hash, NEW.user_id, NEW.risk_level, NEW.timestamp,
+        NEW.entry_type, NEW.supersedes_seal_id
+    );
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS chain_compliance_records ON compliance_records;
+CREATE TRIGGER chain_compliance_records
+    BEFORE INSERT ON compliance_records
+    FOR EACH ROW
+    EXECUTE FUNCTION chain_compliance_record();
+
+-- Chained content is append-only; oversight status and timestamps stay mutable
+CREATE OR REPLACE FUNCTION protect_chained_compliance_record()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        RAISE EXCEPTION 'compliance_records is append-only (seal_id %)', OLD.seal_id;
+    END IF;
+    IF (NEW.chain_seq, NEW.prev_hash, NEW.record_hash, NEW.chained_at, NEW.seal_id, NEW.tx_id,
+        NEW.agent_id, NEW.action_summary, NEW.status, NEW.payload_hash, NEW.user_id,
+        NEW.risk_level, NEW.timestamp, NEW.entry_type, NEW.supersedes_seal_id)
+       IS DISTINCT FROM
+       (OLD.chain_seq, OLD.prev_hash, OLD.record_hash, OLD.chained_at, OLD.seal_id, OLD.tx_id,
+        OLD.agent_id, OLD.action_summary, OLD.status, OLD.payload_hash, OLD.user_id,
+        OLD.risk_level, OLD.timestamp, OLD.entry_type, OLD.supersedes_seal_id) THEN
+        RAISE EXCEPTION 'chained compliance record % cannot be modified; append a superseding entry', OLD.seal_id;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS protect_compliance_records ON compliance_records;
+CREATE TRIGGER protect_compliance_records
+    BEFORE UPDATE OR DELETE ON compliance_records
+    FOR EACH ROW
+    EXECUTE FUNCTION protect_chained_compliance_record();
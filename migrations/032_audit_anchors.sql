@@ -0,0 +1,36 @@
+-- Merkle anchoring of the compliance record chain
+-- At each interval the records chained since the previous anchor become the
+-- leaves of a Merkle tree; only the root is sealed. Every record keeps its
+-- inclusion proof so it can be verified offline against the sealed root
+-- (src/core/merkle_anchor.rs).
+
+CREATE TABLE IF NOT EXISTS audit_anchors (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- Range of compliance_records.chain_seq covered by this anchor
+    first_chain_seq BIGINT NOT NULL UNIQUE,
+    last_chain_seq BIGINT NOT NULL UNIQUE,
+    leaf_count INTEGER NOT NULL,
+    merkle_root VARCHAR(64) NOT NULL,
+    -- chained_at of the first and last record
+    period_start TIMESTAMPTZ NOT NULL,
+    period_end TIMESTAMPTZ NOT NULL,
+    -- Seal returned by the Privacy Bridge for merkle_root
+    seal_id TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CHECK (last_chain_seq >= first_chain_seq)
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_anchors_period ON audit_anchors(period_start, period_end);
+
+CREATE TABLE IF NOT EXISTS audit_anchor_proofs (
+    chain_seq BIGINT PRIMARY KEY,
+    seal_id VARCHAR(255) NOT NULL,
+    anchor_id UUID NOT NULL REFERENCES audit_anchors(id),
+    leaf_index INTEGER NOT NULL,
+    leaf_hash VARCHAR(64) NOT NULL,
+    -- Sibling hashes from leaf to root: [{"hash": ..., "position": "LEFT"|"RIGHT"}]
+    proof JSONB NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_anchor_proofs_seal_id ON audit_anchor_proofs(seal_id);
+CREATE INDEX IF NOT EXISTS idx_audit_anchor_proofs_anchor ON audit_anchor_proofs(anchor_id);
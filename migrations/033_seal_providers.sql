@@ -0,0 +1,34 @@
+-- Pluggable sealing backends
+-- Each tenant seals with Signicat, an RFC 3161 TSA or a local development key
+-- (src/core/seal_provider.rs). The token of every seal is kept for offline
+-- verification.
+
+CREATE TABLE IF NOT EXISTS seal_provider_settings (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- NULL: default for tenants without their own setting
+    company_id UUID,
+    provider VARCHAR(20) NOT NULL CHECK (provider IN ('SIGNICAT', 'RFC3161', 'LOCAL')),
+    tsa_url TEXT,
+    tsa_policy_oid VARCHAR(100),
+    updated_by VARCHAR(255),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CHECK (provider <> 'RFC3161' OR tsa_url IS NOT NULL)
+);
+
+-- One setting per tenant (and one default)
+CREATE UNIQUE INDEX IF NOT EXISTS idx_seal_provider_settings_company
+    ON seal_provider_settings ((COALESCE(company_id, '00000000-0000-0000-0000-000000000000'::uuid)));
+
+CREATE TABLE IF NOT EXISTS seal_tokens (
+    seal_id VARCHAR(255) PRIMARY KEY,
+    provider VARCHAR(20) NOT NULL,
+    company_id UUID,
+    -- Hex SHA-256 that was sealed
+    sealed_hash VARCHAR(64) NOT NULL,
+    -- DER TimeStampToken (RFC3161), seal JSON (LOCAL) or provider response (SIGNICAT)
+    token BYTEA NOT NULL,
+    sealed_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_seal_tokens_sealed_hash ON seal_tokens(sealed_hash);
@@ -0,0 +1,32 @@
+-- Seal outbox
+-- When a record's seal provider is unreachable, log_action stores the record
+-- with a PENDING_SYNC_LOCAL placeholder seal ID and queues the hash here. A
+-- background job retries with backoff until the provider seals it
+-- (src/core/seal_outbox.rs).
+--
+-- The placeholder stays the record's seal_id: it is part of the chained record
+-- hash and the key other tables reference. The seal obtained later is written
+-- to compliance_records.resolved_seal_id, which is not chained.
+
+CREATE TABLE IF NOT EXISTS seal_outbox (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    placeholder_seal_id VARCHAR(255) NOT NULL UNIQUE,
+    company_id UUID,
+    -- Hex SHA-256 to seal
+    sealed_hash VARCHAR(64) NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'PENDING' CHECK (status IN ('PENDING', 'SEALED')),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    -- Seal returned by the provider once the retry succeeds
+    seal_id VARCHAR(255),
+    sealed_at TIMESTAMPTZ,
+    -- Set when the entry has been reported as past the sealing SLA
+    sla_alerted_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_seal_outbox_due ON seal_outbox(next_attempt_at) WHERE status = 'PENDING';
+CREATE INDEX IF NOT EXISTS idx_seal_outbox_seal_id ON seal_outbox(seal_id);
+
+ALTER TABLE compliance_records ADD COLUMN IF NOT EXISTS resolved_seal_id VARCHAR(255);
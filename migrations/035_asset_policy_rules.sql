@@ -0,0 +1,24 @@
+-- Declarative asset policy rules
+-- Asset policies of type RULES carry a rule set in policy_config, evaluated by
+-- src/core/policy_rules.rs in log_action, the proxy and the policy simulator.
+-- The asset_policies table is created here if an earlier schema did not.
+
+CREATE TABLE IF NOT EXISTS asset_policies (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    policy_name VARCHAR(255) NOT NULL,
+    policy_type VARCHAR(50) NOT NULL,
+    business_function_filter VARCHAR(100),
+    department_filter VARCHAR(100),
+    location_filter VARCHAR(100),
+    risk_profile_filter VARCHAR(50),
+    asset_tags_filter TEXT[],
+    policy_config JSONB NOT NULL DEFAULT '{}'::jsonb,
+    is_active BOOLEAN NOT NULL DEFAULT true,
+    priority INTEGER NOT NULL DEFAULT 100,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    created_by VARCHAR(255)
+);
+
+CREATE INDEX IF NOT EXISTS idx_asset_policies_active_rules
+    ON asset_policies(priority, created_at) WHERE is_active = true AND policy_type = 'RULES';
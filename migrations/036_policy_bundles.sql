@@ -0,0 +1,120 @@
+-- Policy Bundles
+-- Active policies, asset policies, jurisdiction rules and the enforcement mode
+-- are exported as a deterministic directory of JSON files for review in Git
+-- (src/core/policy_bundle.rs). An imported bundle becomes a POLICY_BUNDLE row
+-- of policy_versions that is approved through POST /policies/{id}/approve
+-- before it is applied.
+--
+-- The approval workflow tables and functions used by approve_policy were not
+-- part of the migrations; they are created here if absent.
+
+ALTER TABLE policy_versions
+    ADD COLUMN IF NOT EXISTS requires_approval BOOLEAN NOT NULL DEFAULT false,
+    ADD COLUMN IF NOT EXISTS approval_required_count INTEGER NOT NULL DEFAULT 1,
+    ADD COLUMN IF NOT EXISTS approval_status VARCHAR(20), -- PENDING, APPROVED, REJECTED
+    ADD COLUMN IF NOT EXISTS approval_notes TEXT,
+    ADD COLUMN IF NOT EXISTS enforcement_mode_override VARCHAR(20); -- SHADOW, DRY_RUN, ENFORCING
+
+CREATE TABLE IF NOT EXISTS policy_approvals (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    policy_version_id UUID NOT NULL REFERENCES policy_versions(id) ON DELETE CASCADE,
+    approver_id VARCHAR(255) NOT NULL,
+    action VARCHAR(20) NOT NULL, -- APPROVED, REJECTED
+    notes TEXT,
+    approved_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (policy_version_id, approver_id)
+);
+
+CREATE TABLE IF NOT EXISTS approval_delegations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    delegator_id VARCHAR(255) NOT NULL,
+    delegate_id VARCHAR(255) NOT NULL,
+    resource_type VARCHAR(50) NOT NULL DEFAULT 'POLICY',
+    active BOOLEAN NOT NULL DEFAULT true,
+    expires_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    notes TEXT
+);
+
+CREATE TABLE IF NOT EXISTS policy_activation_history (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    policy_version_id UUID NOT NULL,
+    action VARCHAR(30) NOT NULL, -- ACTIVATED, DEACTIVATED, ROLLED_BACK
+    performed_by VARCHAR(255),
+    previous_version_id UUID,
+    notes TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_policy_activation_history_created ON policy_activation_history(action, created_at);
+
+CREATE TABLE IF NOT EXISTS system_enforcement_mode (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    enforcement_mode VARCHAR(20) NOT NULL, -- SHADOW, DRY_RUN, ENFORCING
+    description TEXT,
+    enabled_by VARCHAR(255),
+    enabled_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    notes TEXT
+);
+
+DO $$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM pg_proc WHERE proname = 'get_system_enforcement_mode') THEN
+        CREATE FUNCTION get_system_enforcement_mode() RETURNS VARCHAR AS $fn$
+            SELECT COALESCE(
+                (SELECT enforcement_mode FROM system_enforcement_mode ORDER BY enabled_at DESC LIMIT 1),
+                'ENFORCING'
+            )::VARCHAR
+        $fn$ LANGUAGE SQL STABLE;
+    END IF;
+
+    -- A user approves for themselves or for an approver who delegated to them
+    IF NOT EXISTS (SELECT 1 FROM pg_proc WHERE proname = 'can_approve_on_behalf') THEN
+        CREATE FUNCTION can_approve_on_behalf(p_user_id VARCHAR, p_approver_id VARCHAR) RETURNS BOOLEAN AS $fn$
+            SELECT p_user_id = p_approver_id OR EXISTS (
+                SELECT 1 FROM approval_delegations
+                WHERE delegator_id = p_approver_id AND delegate_id = p_user_id
+                  AND resource_type = 'POLICY' AND active = true
+                  AND (expires_at IS NULL OR expires_at > CURRENT_TIMESTAMP)
+            )
+        $fn$ LANGUAGE SQL STABLE;
+    END IF;
+
+    -- Enough approvals and no rejection (always true without required approval)
+    IF NOT EXISTS (SELECT 1 FROM pg_proc WHERE proname = 'can_activate_policy') THEN
+        CREATE FUNCTION can_activate_policy(p_policy_id UUID) RETURNS BOOLEAN AS $fn$
+            SELECT NOT pv.requires_approval OR (
+                (SELECT COUNT(*) FROM policy_approvals
+                 WHERE policy_version_id = pv.id AND action = 'APPROVED') >= pv.approval_required_count
+                AND NOT EXISTS (SELECT 1 FROM policy_approvals
+                                WHERE policy_version_id = pv.id AND action = 'REJECTED')
+            )
+            FROM policy_versions pv WHERE pv.id = p_policy_id
+        $fn$ LANGUAGE SQL STABLE;
+    END IF;
+END
+$$;
+
+CREATE TABLE IF NOT EXISTS policy_bundle_imports (
+    -- The POLICY_BUNDLE row of policy_versions that carries the approvals
+    id UUID PRIMARY KEY REFERENCES policy_versions(id) ON DELETE CASCADE,
+    -- SHA-256 over the bundle files
+    digest VARCHAR(64) NOT NULL,
+    files JSONB NOT NULL,
+    -- Digest of the live state the diff was computed against
+    base_digest VARCHAR(64) NOT NULL,
+    diff JSONB NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'PENDING_APPROVAL'
+        CHECK (status IN ('PENDING_APPROVAL', 'APPLIED', 'REJECTED', 'ROLLED_BACK')),
+    notes TEXT,
+    requested_by VARCHAR(255),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    -- Live state replaced by the import, restored by a rollback
+    previous_files JSONB,
+    applied_by VARCHAR(255),
+    applied_at TIMESTAMPTZ,
+    rolled_back_by VARCHAR(255),
+    rolled_back_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_policy_bundle_imports_status ON policy_bundle_imports(status, created_at);
@@ -0,0 +1,32 @@
+-- Policy simulation jobs
+-- Simulations over long replay windows run in the background
+-- (POST /policies/simulate/jobs). A worker claims a pending job, streams the
+-- matching compliance records through the simulator and reports progress here
+-- (src/core/policy_simulator.rs). A job whose worker stopped reporting is
+-- claimed again once its lease runs out.
+
+CREATE TABLE IF NOT EXISTS policy_simulation_jobs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- SimulationRequest as submitted
+    request JSONB NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'PENDING'
+        CHECK (status IN ('PENDING', 'RUNNING', 'COMPLETED', 'FAILED')),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    -- Records matching the filters and sample, counted when the job starts
+    records_total BIGINT,
+    records_processed BIGINT NOT NULL DEFAULT 0,
+    -- SimulationResult once COMPLETED
+    result JSONB,
+    error TEXT,
+    requested_by VARCHAR(255),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    started_at TIMESTAMPTZ,
+    -- Refreshed with every progress report
+    heartbeat_at TIMESTAMPTZ,
+    finished_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_policy_simulation_jobs_status ON policy_simulation_jobs(status, created_at);
+
+-- Agent filters of simulations over long windows
+CREATE INDEX IF NOT EXISTS idx_compliance_records_agent_timestamp ON compliance_records(agent_id, timestamp);
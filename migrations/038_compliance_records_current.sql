@@ -0,0 +1,46 @@
+-- Current state of compliance records
+-- Erasure, rectification and oversight rejection append a superseding entry
+-- instead of changing the original record (migration 031). This view shows each
+-- action record as it stands now: the status of its latest superseding entry
+-- and the content of its latest erasure or rectification. Readers that show
+-- record content use the view; the chain itself is read from compliance_records.
+
+CREATE OR REPLACE VIEW compliance_records_current AS
+SELECT cr.id,
+       cr.timestamp,
+       cr.agent_id,
+       COALESCE(content.action_summary, cr.action_summary) AS action_summary,
+       cr.seal_id,
+       COALESCE(latest.status, cr.status) AS status,
+       cr.user_notified,
+       cr.notification_timestamp,
+       cr.human_oversight_status,
+       cr.risk_level,
+       cr.user_id,
+       cr.tx_id,
+       cr.payload_hash,
+       cr.created_at,
+       cr.updated_at,
+       cr.chain_seq,
+       cr.record_hash,
+       cr.resolved_seal_id,
+       -- TOMBSTONE, RECTIFICATION or OVERSIGHT; NULL while the record is as logged
+       latest.entry_type AS superseded_by,
+       latest.seal_id AS superseding_seal_id
+FROM compliance_records cr
+LEFT JOIN LATERAL (
+    SELECT s.seal_id, s.status, s.entry_type
+    FROM compliance_records s
+    WHERE s.supersedes_seal_id = cr.seal_id
+    ORDER BY s.chain_seq DESC
+    LIMIT 1
+) latest ON true
+LEFT JOIN LATERAL (
+    SELECT s.action_summary
+    FROM compliance_records s
+    WHERE s.supersedes_seal_id = cr.seal_id
+      AND s.entry_type IN ('TOMBSTONE', 'RECTIFICATION')
+    ORDER BY s.chain_seq DESC
+    LIMIT 1
+) content ON true
+WHERE cr.entry_type = 'ACTION';
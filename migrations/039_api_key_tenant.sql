@@ -0,0 +1,7 @@
+-- Tenant of an API key
+-- The forward proxy takes the agent and tenant of a request from its API key;
+-- headers naming another agent or tenant are rejected.
+
+ALTER TABLE api_keys ADD COLUMN IF NOT EXISTS company_id UUID;
+
+CREATE INDEX IF NOT EXISTS idx_api_keys_company_id ON api_keys(company_id);
@@ -1,9 +1,12 @@
+use crate::core::agent_revocation::{AgentRevocation, AgentRevocations};
 use crate::core::crypto_shredder::VeridionKeyStore;
 use crate::core::privacy_bridge::SignicatClient;
+use crate::core::seal_provider::SealService;
+use crate::core::region_catalog::RegionCatalog;
+use crate::core::sovereign_lock::SovereigntyResolver;
 use crate::database::Database;
 use crate::deployment::DeploymentConfig;
 use crate::integration::notifications::NotificationService;
-use dashmap::DashMap;
 use sqlx::PgPool;
 use std::sync::Arc;
 
@@ -13,6 +16,8 @@ pub struct AppState {
     pub key_store: Arc<VeridionKeyStore>,
     /// Signicat client for eIDAS sealing
     pub signicat: Arc<SignicatClient>,
+    /// Seals hashes with each tenant's provider (Signicat, RFC 3161 TSA or local key)
+    pub seals: Arc<SealService>,
     /// Database connection pool
     #[allow(dead_code)]
     pub db: Arc<Database>,
@@ -23,8 +28,12 @@ pub struct AppState {
     pub deployment: DeploymentConfig,
     /// Notification service for GDPR Article 33 and EU AI Act Article 13
     pub notification_service: Arc<NotificationService>,
-    /// Revoked agents map (agent_id -> true if revoked)
-    pub revoked_agents: Arc<DashMap<String, bool>>,
+    /// Revoked agents, persisted and kept in sync across replicas via LISTEN/NOTIFY
+    pub revocations: Arc<AgentRevocations>,
+    /// Data sovereignty verdicts for log_action, the proxy and the policy simulator
+    pub sovereignty: Arc<SovereigntyResolver>,
+    /// Tokenize personal data in log_action payloads and proxied prompts
+    pub pii_redaction_enabled: bool,
 }
 
 impl AppState {
@@ -33,14 +42,43 @@ impl AppState {
         let db = Arc::new(Database::new(database_url).await?);
         let db_pool = db.pool().clone();
 
+        // Rehydrate wrapped DEKs persisted by earlier runs (or other nodes).
+        // Keys missing from the cache are still loaded lazily on read.
+        let key_store = VeridionKeyStore::new();
+        key_store.register_master_keys(&db_pool).await?;
+        let preload_keys = std::env::var("VERIDION_KEYSTORE_PRELOAD")
+            .unwrap_or_else(|_| "true".to_string())
+            .parse::<bool>()
+            .unwrap_or(true);
+        if preload_keys {
+            let loaded = key_store.load_from_db(&db_pool).await?;
+            log::info!("Key store rehydrated with {} wrapped DEKs", loaded);
+        }
+
+        // Built-in cloud regions plus the additions stored in cloud_region_catalog
+        let region_catalog = Arc::new(RegionCatalog::builtin());
+        let overrides = region_catalog.load_overrides(&db_pool).await?;
+        log::info!("Region catalog loaded with {} database overrides", overrides);
+
+        // Revocations survive restarts and are shared with the other replicas
+        let revocations = AgentRevocations::new();
+        let revoked = revocations.load(&db_pool).await?;
+        log::info!("{} agent revocations in force", revoked);
+
+        let signicat = Arc::new(SignicatClient::new());
+        let seals = Arc::new(SealService::from_env(signicat.clone()));
+
         Ok(Self {
-            key_store: Arc::new(VeridionKeyStore::new()),
-            signicat: Arc::new(SignicatClient::new()),
+            key_store: Arc::new(key_store),
+            signicat,
+            seals,
             db: db.clone(),
             db_pool,
             deployment: DeploymentConfig::default(),
             notification_service: Arc::new(NotificationService::new()),
-            revoked_agents: Arc::new(DashMap::new()),
+            revocations: Arc::new(revocations),
+            sovereignty: Arc::new(SovereigntyResolver::from_env().with_region_catalog(region_catalog)),
+            pii_redaction_enabled: crate::core::pii_redaction::enabled_from_env(),
         })
     }
 
@@ -70,21 +108,25 @@ impl AppState {
         Ok(())
     }
 
-    /// Revoke access for a specific agent
-    pub fn revoke_agent(&self, agent_id: &str) {
-        self.revoked_agents.insert(agent_id.to_string(), true);
-        log::warn!("Agent revoked: {}", agent_id);
+    /// Revoke access for a specific agent on all replicas
+    pub async fn revoke_agent(
+        &self,
+        agent_id: &str,
+        reason: Option<&str>,
+        actor: Option<&str>,
+        expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<AgentRevocation, sqlx::Error> {
+        self.revocations.revoke(&self.db_pool, agent_id, reason, actor, expires_at).await
     }
 
-    /// Restore access for a specific agent
-    pub fn restore_agent(&self, agent_id: &str) {
-        self.revoked_agents.remove(agent_id);
-        log::info!("Agent access restored: {}", agent_id);
+    /// Restore access for a specific agent on all replicas (false if it was not revoked)
+    pub async fn restore_agent(&self, agent_id: &str, reason: Option<&str>, actor: Option<&str>) -> Result<bool, sqlx::Error> {
+        self.revocations.restore(&self.db_pool, agent_id, reason, actor).await
     }
 
     /// Check if a specific agent is revoked
     pub fn is_agent_revoked(&self, agent_id: &str) -> bool {
-        self.revoked_agents.contains_key(agent_id)
+        self.revocations.is_revoked(agent_id)
     }
 }
 
@@ -1,6 +1,17 @@
 use sqlx::PgPool;
+use sqlx::postgres::PgListener;
 use std::time::Duration;
+use std::sync::Arc;
 use tokio::time::sleep;
+use crate::core::agent_revocation::{self, AgentRevocations};
+use crate::core::audit_chain::AuditChain;
+use crate::core::lockdown::Lockdowns;
+use crate::core::merkle_anchor::{self, AuditAnchors};
+use crate::core::policy_simulator::SimulationJobs;
+use crate::core::seal_provider::SealService;
+use crate::core::seal_outbox::{self, SealOutbox};
+use crate::core::sovereign_lock::SovereigntyResolver;
+use crate::core::crypto_shredder::VeridionKeyStore;
 use crate::integration::webhooks::WebhookService;
 use crate::compliance_models::WebhookEvent;
 use crate::models::db_models::{WebhookEndpointDb, WebhookDeliveryDb, RetentionAssignmentDb};
@@ -165,17 +176,15 @@ impl BackgroundWorker {
                         .fetch_optional(&self.db_pool)
                         .await;
                         
-                        if let Ok(Some(_tx_id)) = tx_id_result {
-                            // Note: We can't access key_store here, so we'll just mark it
-                            // The actual shredding should be done via the API endpoint
-                            let _ = sqlx::query(
-                                "UPDATE compliance_records 
-                                 SET action_summary = '[RETENTION EXPIRED] Data Automatically Deleted',
-                                     status = 'DELETED (Retention Period)'
-                                 WHERE seal_id = $1"
+                        if let Ok(Some(tx_id)) = tx_id_result {
+                            // Destroy the persisted DEK; nodes drop their cached copy on next read
+                            let _ = VeridionKeyStore::mark_shredded(&self.db_pool, &tx_id).await;
+                            let _ = AuditChain::append_tombstone(
+                                &self.db_pool,
+                                &assignment.record_id,
+                                "DELETED (Retention Period)",
+                                "[RETENTION EXPIRED] Data Automatically Deleted",
                             )
-                            .bind(&assignment.record_id)
-                            .execute(&self.db_pool)
                             .await;
                         }
                     }
@@ -205,6 +214,165 @@ impl BackgroundWorker {
         }
     }
 
+    /// Re-wrap DEKs still under older master key versions (master key rotation)
+    pub async fn process_master_key_rewrap(&self, key_store: Arc<VeridionKeyStore>) {
+        loop {
+            // Check for DEKs to re-wrap every 5 minutes
+            sleep(Duration::from_secs(300)).await;
+
+            match key_store.rewrap_all(&self.db_pool, 500).await {
+                Ok(report) => {
+                    if report.rewrapped > 0 {
+                        println!(
+                            "🔑 Re-wrapped {} DEKs under master key {}",
+                            report.rewrapped,
+                            key_store.current_master_key_id()
+                        );
+                    }
+                    if report.failed > 0 {
+                        eprintln!("{} DEKs could not be re-wrapped (master key unavailable or corrupt)", report.failed);
+                    }
+                }
+                Err(e) => eprintln!("Error re-wrapping DEKs: {}", e),
+            }
+        }
+    }
+
+    /// Apply agent revocations made on other replicas (LISTEN agent_revocations)
+    pub async fn process_revocation_notifications(&self, revocations: Arc<AgentRevocations>) {
+        loop {
+            let mut listener = match PgListener::connect_with(&self.db_pool).await {
+                Ok(listener) => listener,
+                Err(e) => {
+                    eprintln!("Error connecting revocation listener: {}", e);
+                    sleep(Duration::from_secs(5)).await;
+                    continue;
+                }
+            };
+            if let Err(e) = listener.listen(agent_revocation::CHANNEL).await {
+                eprintln!("Error listening for revocations: {}", e);
+                sleep(Duration::from_secs(5)).await;
+                continue;
+            }
+
+            // Catch up on anything missed while not listening
+            if let Err(e) = revocations.load(&self.db_pool).await {
+                eprintln!("Error reloading agent revocations: {}", e);
+            }
+
+            loop {
+                match listener.try_recv().await {
+                    Ok(Some(notification)) => {
+                        if let Err(e) = revocations.refresh(&self.db_pool, notification.payload()).await {
+                            eprintln!("Error refreshing revocation of {}: {}", notification.payload(), e);
+                        }
+                    }
+                    // Connection lost; notifications sent meanwhile are gone, so reload
+                    Ok(None) => {
+                        if let Err(e) = revocations.load(&self.db_pool).await {
+                            eprintln!("Error reloading agent revocations: {}", e);
+                        }
+                    }
+                    Err(e) => {
+                        eprintln!("Revocation listener failed: {}", e);
+                        sleep(Duration::from_secs(5)).await;
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Resolve the DORA Lite incidents of lockdowns that expired
+    pub async fn process_lockdown_expiry(&self) {
+        loop {
+            sleep(Duration::from_secs(60)).await;
+
+            match Lockdowns::resolve_expired(&self.db_pool).await {
+                Ok(0) => {}
+                Ok(resolved) => println!("✅ Resolved {} incident(s) of expired lockdowns", resolved),
+                Err(e) => eprintln!("Error resolving expired lockdowns: {}", e),
+            }
+        }
+    }
+
+    /// Seal a Merkle root over the compliance records chained since the last anchor
+    pub async fn process_audit_anchoring(&self, seals: Arc<SealService>) {
+        loop {
+            sleep(Duration::from_secs(merkle_anchor::anchor_interval_minutes() * 60)).await;
+
+            // A backlog larger than one anchor is worked off in consecutive anchors
+            loop {
+                match AuditAnchors::anchor_pending(&self.db_pool, &seals, merkle_anchor::MAX_LEAVES_PER_ANCHOR).await {
+                    Ok(Some(anchor)) => {
+                        println!(
+                            "⚓ Anchored records {}..{} under Merkle root {}",
+                            anchor.first_chain_seq, anchor.last_chain_seq, anchor.merkle_root
+                        );
+                        if (anchor.leaf_count as i64) < merkle_anchor::MAX_LEAVES_PER_ANCHOR {
+                            break;
+                        }
+                    }
+                    Ok(None) => break,
+                    Err(e) => {
+                        eprintln!("Error anchoring audit records: {}", e);
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Retry queued seals with backoff and alert on records unsealed past the SLA
+    pub async fn process_seal_outbox(&self, seals: Arc<SealService>) {
+        loop {
+            sleep(Duration::from_secs(30)).await;
+
+            match SealOutbox::retry_due(&self.db_pool, &seals, 50).await {
+                Ok((0, 0)) => {}
+                Ok((sealed, failed)) => println!("🔏 Seal outbox: {} sealed, {} still failing", sealed, failed),
+                Err(e) => eprintln!("Error retrying queued seals: {}", e),
+            }
+
+            let sla_minutes = seal_outbox::sla_minutes();
+            let overdue = match SealOutbox::claim_overdue_alerts(&self.db_pool, sla_minutes).await {
+                Ok(overdue) => overdue,
+                Err(e) => {
+                    eprintln!("Error checking seal outbox SLA: {}", e);
+                    continue;
+                }
+            };
+            if !overdue.is_empty() {
+                eprintln!("⚠️ {} record(s) unsealed for more than {} minutes", overdue.len(), sla_minutes);
+                let notification_service = crate::integration::notifications::NotificationService::new();
+                if let Err(e) = notification_service
+                    .send_seal_sla_alert(&self.db_pool, &overdue, sla_minutes, None)
+                    .await
+                {
+                    eprintln!("Error sending seal SLA alert: {}", e);
+                }
+            }
+        }
+    }
+
+    /// Run queued policy simulation jobs
+    pub async fn process_simulation_jobs(&self, resolver: Arc<SovereigntyResolver>) {
+        loop {
+            sleep(Duration::from_secs(5)).await;
+
+            loop {
+                match SimulationJobs::run_next(&self.db_pool, &resolver).await {
+                    Ok(true) => println!("🧪 Policy simulation job finished"),
+                    Ok(false) => break,
+                    Err(e) => {
+                        eprintln!("Error running policy simulation job: {}", e);
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
     /// Refresh materialized views periodically
     pub async fn refresh_materialized_views(&self) {
         loop {
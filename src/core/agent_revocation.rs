@@ -0,0 +1,243 @@
+// Agent Revocation
+// Revoked agents are stored in agent_revocations (with reason, actor and optional
+// expiry) and every revoke or restore is appended to agent_revocation_events.
+// Each replica keeps an in-memory copy for the hot path and refreshes it from
+// Postgres NOTIFY messages on the agent_revocations channel.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::RwLock;
+use utoipa::ToSchema;
+
+/// NOTIFY channel carrying the agent_id of every revoke and restore
+pub const CHANNEL: &str = "agent_revocations";
+
+/// An agent whose access is revoked
+#[derive(Debug, Clone, Serialize, ToSchema, sqlx::FromRow)]
+pub struct AgentRevocation {
+    pub agent_id: String,
+    pub reason: Option<String>,
+    pub revoked_by: Option<String>,
+    pub revoked_at: DateTime<Utc>,
+    /// Access comes back on its own after this time; never when absent
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// One revoke or restore in an agent's history
+#[derive(Debug, Clone, Serialize, ToSchema, sqlx::FromRow)]
+pub struct RevocationEvent {
+    pub id: i64,
+    pub agent_id: String,
+    /// REVOKED or RESTORED
+    pub event_type: String,
+    pub reason: Option<String>,
+    pub actor: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Replica-local view of the revoked agents, kept in sync through LISTEN/NOTIFY
+pub struct AgentRevocations {
+    /// agent_id -> expiry (None: until restored)
+    revoked: RwLock<HashMap<String, Option<DateTime<Utc>>>>,
+}
+
+impl AgentRevocations {
+    pub fn new() -> Self {
+        Self { revoked: RwLock::new(HashMap::new()) }
+    }
+
+    /// Check if an agent is revoked on this replica
+    pub fn is_revoked(&self, agent_id: &str) -> bool {
+        let revoked = self.revoked.read().expect("Failed to acquire lock");
+        match revoked.get(agent_id) {
+            Some(expires_at) => expires_at.map(|expires_at| expires_at > Utc::now()).unwrap_or(true),
+            None => false,
+        }
+    }
+
+    /// Replace the local view with the revocations stored in the database
+    ///
+    /// The new view is built first and swapped in whole, so no agent is ever
+    /// seen as unrevoked while it is being reloaded.
+    pub async fn load(&self, db_pool: &PgPool) -> Result<usize, sqlx::Error> {
+        let active = Self::active(db_pool).await?;
+        let revoked: HashMap<_, _> = active
+            .iter()
+            .map(|revocation| (revocation.agent_id.clone(), revocation.expires_at))
+            .collect();
+        *self.revoked.write().expect("Failed to acquire lock") = revoked;
+        Ok(active.len())
+    }
+
+    /// Mark an agent revoked in the local view
+    fn set(&self, agent_id: &str, expires_at: Option<DateTime<Utc>>) {
+        self.revoked.write().expect("Failed to acquire lock").insert(agent_id.to_string(), expires_at);
+    }
+
+    /// Drop an agent from the local view
+    fn unset(&self, agent_id: &str) {
+        self.revoked.write().expect("Failed to acquire lock").remove(agent_id);
+    }
+
+    /// Re-read one agent after a notification
+    pub async fn refresh(&self, db_pool: &PgPool, agent_id: &str) -> Result<(), sqlx::Error> {
+        let expires_at: Option<Option<DateTime<Utc>>> = sqlx::query_scalar(
+            "SELECT expires_at FROM agent_revocations WHERE agent_id = $1"
+        )
+        .bind(agent_id)
+        .fetch_optional(db_pool)
+        .await?;
+
+        match expires_at {
+            Some(expires_at) => {
+                self.set(agent_id, expires_at);
+            }
+            None => {
+                self.unset(agent_id);
+            }
+        }
+        Ok(())
+    }
+
+    /// Revoke an agent on every replica; revoking again replaces reason and expiry
+    pub async fn revoke(
+        &self,
+        db_pool: &PgPool,
+        agent_id: &str,
+        reason: Option<&str>,
+        actor: Option<&str>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<AgentRevocation, sqlx::Error> {
+        let mut tx = db_pool.begin().await?;
+        let revocation: AgentRevocation = sqlx::query_as(
+            "INSERT INTO agent_revocations (agent_id, reason, revoked_by, expires_at)
+             VALUES ($1, $2, $3, $4)
+             ON CONFLICT (agent_id) DO UPDATE SET
+                reason = EXCLUDED.reason,
+                revoked_by = EXCLUDED.revoked_by,
+                revoked_at = CURRENT_TIMESTAMP,
+                expires_at = EXCLUDED.expires_at
+             RETURNING agent_id, reason, revoked_by, revoked_at, expires_at"
+        )
+        .bind(agent_id)
+        .bind(reason)
+        .bind(actor)
+        .bind(expires_at)
+        .fetch_one(&mut *tx)
+        .await?;
+        Self::record_event(&mut tx, agent_id, "REVOKED", reason, actor, expires_at).await?;
+        tx.commit().await?;
+
+        self.set(agent_id, expires_at);
+        log::warn!("Agent revoked: {}", agent_id);
+        Ok(revocation)
+    }
+
+    /// Restore an agent on every replica; false if it was not revoked
+    pub async fn restore(
+        &self,
+        db_pool: &PgPool,
+        agent_id: &str,
+        reason: Option<&str>,
+        actor: Option<&str>,
+    ) -> Result<bool, sqlx::Error> {
+        let mut tx = db_pool.begin().await?;
+        let removed = sqlx::query("DELETE FROM agent_revocations WHERE agent_id = $1")
+            .bind(agent_id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        if removed == 0 {
+            tx.rollback().await?;
+            self.unset(agent_id);
+            return Ok(false);
+        }
+        Self::record_event(&mut tx, agent_id, "RESTORED", reason, actor, None).await?;
+        tx.commit().await?;
+
+        self.unset(agent_id);
+        log::info!("Agent access restored: {}", agent_id);
+        Ok(true)
+    }
+
+    /// Append to the audit history and notify the other replicas on commit
+    async fn record_event(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        agent_id: &str,
+        event_type: &str,
+        reason: Option<&str>,
+        actor: Option<&str>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO agent_revocation_events (agent_id, event_type, reason, actor, expires_at)
+             VALUES ($1, $2, $3, $4, $5)"
+        )
+        .bind(agent_id)
+        .bind(event_type)
+        .bind(reason)
+        .bind(actor)
+        .bind(expires_at)
+        .execute(&mut **tx)
+        .await?;
+
+        sqlx::query("SELECT pg_notify($1, $2)")
+            .bind(CHANNEL)
+            .bind(agent_id)
+            .execute(&mut **tx)
+            .await?;
+        Ok(())
+    }
+
+    /// Revocations in force (expired ones are left out)
+    pub async fn active(db_pool: &PgPool) -> Result<Vec<AgentRevocation>, sqlx::Error> {
+        sqlx::query_as(
+            "SELECT agent_id, reason, revoked_by, revoked_at, expires_at
+             FROM agent_revocations
+             WHERE expires_at IS NULL OR expires_at > CURRENT_TIMESTAMP
+             ORDER BY revoked_at DESC"
+        )
+        .fetch_all(db_pool)
+        .await
+    }
+
+    /// Revoke and restore events of an agent, newest first
+    pub async fn history(db_pool: &PgPool, agent_id: &str) -> Result<Vec<RevocationEvent>, sqlx::Error> {
+        sqlx::query_as(
+            "SELECT id, agent_id, event_type, reason, actor, expires_at, created_at
+             FROM agent_revocation_events
+             WHERE agent_id = $1
+             ORDER BY created_at DESC, id DESC"
+        )
+        .bind(agent_id)
+        .fetch_all(db_pool)
+        .await
+    }
+}
+
+impl Default for AgentRevocations {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expired_revocations_no_longer_apply() {
+        let revocations = AgentRevocations::new();
+        revocations.set("forever", None);
+        revocations.set("later", Some(Utc::now() + chrono::Duration::minutes(5)));
+        revocations.set("expired", Some(Utc::now() - chrono::Duration::seconds(1)));
+
+        assert!(revocations.is_revoked("forever"));
+        assert!(revocations.is_revoked("later"));
+        assert!(!revocations.is_revoked("expired"));
+        assert!(!revocations.is_revoked("unknown"));
+    }
+}
@@ -93,7 +93,7 @@ impl AIExplainabilityService {
 
         let context: Option<DecisionContext> = sqlx::query_as(
             "SELECT agent_id, action_summary, payload_hash
-             FROM compliance_records
+             FROM compliance_records_current
              WHERE seal_id = $1"
         )
         .bind(seal_id)
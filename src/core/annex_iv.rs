@@ -1,3 +1,4 @@
+use crate::core::region_catalog::{jurisdiction_for, RegionCatalog};
 use printpdf::*;
 use serde::{Deserialize, Serialize};
 use std::fs::File;
@@ -256,7 +257,7 @@ fn escape_xml(s: &str) -> String {
 }
 
 /// Generate a simplified DORA Register report focusing on vendor supply chain
-pub fn generate_dora_register(records: &Vec<ComplianceRecord>, output_path: &str) -> Result<(), String> {
+pub fn generate_dora_register(records: &Vec<ComplianceRecord>, region_catalog: &RegionCatalog, output_path: &str) -> Result<(), String> {
     let (doc, page1, layer1) = PdfDocument::new("Veridion DORA Register", Mm(210.0), Mm(297.0), "Layer 1");
     let current_layer = doc.get_page(page1).get_layer(layer1);
 
@@ -294,16 +295,9 @@ pub fn generate_dora_register(records: &Vec<ComplianceRecord>, output_path: &str
             "AI Model Vendor"
         };
         
-        // Extract region from status
-        let region = if record.status.contains("EU") || record.status.contains("COMPLIANT") {
-            "EU/EEA"
-        } else if record.status.contains("US") || record.status.contains("BLOCKED") {
-            "Non-EU (Blocked)"
-        } else {
-            "EU/EEA"
-        };
+        let region = dora_region_label(record, region_catalog);
         
-        supply_chain.push((agent_id, model_provider.to_string(), region.to_string()));
+        supply_chain.push((agent_id, model_provider.to_string(), region));
     }
     
     // Remove duplicates
@@ -337,6 +331,29 @@ pub fn generate_dora_register(records: &Vec<ComplianceRecord>, output_path: &str
     Ok(())
 }
 
+/// REGION column of the DORA register, e.g. "DE eu-central-1 (EU/EEA)"
+///
+/// Uses the record's target region, resolved through the region catalog. Records
+/// without one fall back to the sovereignty outcome in their status.
+fn dora_region_label(record: &ComplianceRecord, region_catalog: &RegionCatalog) -> String {
+    if let Some(target_region) = record.target_region.as_deref().map(str::trim).filter(|r| !r.is_empty()) {
+        if let Some(cloud_region) = region_catalog.lookup(target_region) {
+            return format!("{} {} ({})", cloud_region.country, cloud_region.region_code, cloud_region.jurisdiction);
+        }
+        if target_region.len() == 2 && target_region.chars().all(|c| c.is_ascii_alphabetic()) {
+            let country = target_region.to_uppercase();
+            return format!("{} ({})", country, jurisdiction_for(&country));
+        }
+        return target_region.to_string();
+    }
+
+    if record.status.contains("US") || record.status.contains("BLOCKED") {
+        "Non-EU (Blocked)".to_string()
+    } else {
+        "EU/EEA".to_string()
+    }
+}
+
 /// Export format enumeration
 #[derive(Debug, Clone, Copy)]
 pub enum ExportFormat {
@@ -4,6 +4,10 @@
 use sqlx::PgPool;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use crate::core::policy_rules::RuleSet;
+
+/// Asset policy type whose policy_config is a declarative rule set
+pub const RULES_POLICY_TYPE: &str = "RULES";
 
 /// Asset context for policy evaluation
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -117,6 +121,28 @@ impl AssetPolicyEngine {
             .collect())
     }
 
+    /// Rule sets of the applicable RULES policies, in priority order
+    ///
+    /// A policy whose rules no longer parse is skipped with a warning; rules are
+    /// validated when the policy is created.
+    pub fn rule_sets(policies: &[PolicyMatch]) -> Vec<RuleSet> {
+        policies
+            .iter()
+            .filter(|p| p.policy_type == RULES_POLICY_TYPE)
+            .filter_map(|p| match RuleSet::parse(&p.policy_config) {
+                Ok(mut set) => {
+                    set.policy_id = Some(p.policy_id);
+                    set.policy_name = Some(p.policy_name.clone());
+                    Some(set)
+                }
+                Err(e) => {
+                    log::warn!("Skipping rules of asset policy {}: {}", p.policy_id, e);
+                    None
+                }
+            })
+            .collect()
+    }
+
     /// Get asset context from agent_id
     pub async fn get_asset_context_from_agent(
         db_pool: &PgPool,
@@ -150,6 +176,46 @@ impl AssetPolicyEngine {
         }
     }
 
+    /// Get asset context and mapped agent_ids from asset_id
+    pub async fn get_asset_agents(
+        db_pool: &PgPool,
+        asset_id: &str,
+    ) -> Result<Option<(AssetContext, Vec<String>)>, String> {
+        let asset: Option<(uuid::Uuid, Option<String>, Option<String>, Option<String>, String, Option<Vec<String>>)> = sqlx::query_as(
+            "SELECT id, business_function, department, location, risk_profile, tags
+             FROM assets
+             WHERE asset_id = $1"
+        )
+        .bind(asset_id)
+        .fetch_optional(db_pool)
+        .await
+        .map_err(|e| format!("Database query failed: {}", e))?;
+
+        let Some((id, business_function, department, location, risk_profile, tags)) = asset else {
+            return Ok(None);
+        };
+
+        let agent_ids: Vec<String> = sqlx::query_scalar(
+            "SELECT agent_id FROM asset_agent_mapping WHERE asset_id = $1 ORDER BY agent_id"
+        )
+        .bind(id)
+        .fetch_all(db_pool)
+        .await
+        .map_err(|e| format!("Database query failed: {}", e))?;
+
+        Ok(Some((
+            AssetContext {
+                asset_id: Some(asset_id.to_string()),
+                business_function,
+                department,
+                location,
+                risk_profile: Some(risk_profile),
+                tags,
+            },
+            agent_ids,
+        )))
+    }
+
     /// Infer business function from action type (fallback when asset not registered)
     pub fn infer_business_function_from_action(action: &str) -> Option<String> {
         let action_lower = action.to_lowercase();
@@ -0,0 +1,524 @@
+// Audit Chain
+// compliance_records form a hash chain: each record stores the hash of its
+// predecessor (prev_hash) and a SHA-256 over its own content and prev_hash
+// (record_hash), numbered by chain_seq. The database assigns all three on insert
+// (migration 031) and refuses updates to chained columns, so erasure,
+// rectification and oversight rejection are appended as superseding entries.
+// Verification recomputes every hash in a range and reports gaps, broken links
+// and modified records. Readers showing record content go through the
+// compliance_records_current view (migration 038), which applies the latest
+// superseding entry to each action record.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use utoipa::ToSchema;
+use crate::models::db_models::ComplianceRecordDb;
+
+/// prev_hash of the first record in the chain
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Records verified per database round trip
+const VERIFY_BATCH: i64 = 5_000;
+
+/// Kind of entry in the chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryType {
+    /// Original record of an action
+    Action,
+    /// Erasure (GDPR Art. 17 or retention expiry) of an action record
+    Tombstone,
+    /// Corrected content of an action record (GDPR Art. 16)
+    Rectification,
+    /// Human oversight decision that changes an action's status
+    Oversight,
+}
+
+impl EntryType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EntryType::Action => "ACTION",
+            EntryType::Tombstone => "TOMBSTONE",
+            EntryType::Rectification => "RECTIFICATION",
+            EntryType::Oversight => "OVERSIGHT",
+        }
+    }
+}
+
+/// The chained columns of a compliance record
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ChainEntry {
+    pub chain_seq: i64,
+    pub prev_hash: String,
+    pub record_hash: String,
+    pub seal_id: String,
+    pub tx_id: String,
+    pub agent_id: String,
+    pub action_summary: String,
+    pub status: String,
+    pub payload_hash: String,
+    pub user_id: Option<String>,
+    pub risk_level: Option<String>,
+    pub timestamp: DateTime<Utc>,
+    pub entry_type: String,
+    pub supersedes_seal_id: Option<String>,
+}
+
+const ENTRY_COLUMNS: &str = "chain_seq, prev_hash, record_hash, seal_id, tx_id, agent_id, action_summary, \
+     status, payload_hash, user_id, risk_level, timestamp, entry_type, supersedes_seal_id";
+
+/// Hash of a record as computed by compliance_record_hash() in the database
+pub fn record_hash(entry: &ChainEntry) -> String {
+    let chain_seq = entry.chain_seq.to_string();
+    let timestamp = entry.timestamp.format("%Y-%m-%dT%H:%M:%S%.6fZ").to_string();
+    let fields: [&str; 13] = [
+        &chain_seq,
+        &entry.prev_hash,
+        &entry.seal_id,
+        &entry.tx_id,
+        &entry.agent_id,
+        &entry.action_summary,
+        &entry.status,
+        &entry.payload_hash,
+        entry.user_id.as_deref().unwrap_or(""),
+        entry.risk_level.as_deref().unwrap_or(""),
+        &timestamp,
+        &entry.entry_type,
+        entry.supersedes_seal_id.as_deref().unwrap_or(""),
+    ];
+    let mut hasher = Sha256::new();
+    hasher.update(fields.join("\u{1f}").as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// A problem found while verifying the chain
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ChainIssue {
+    /// Where in the chain the problem is
+    pub chain_seq: i64,
+    pub seal_id: Option<String>,
+    /// GAP, BROKEN_LINK, MODIFIED or UNCHAINED
+    pub kind: String,
+    pub detail: String,
+}
+
+/// Result of verifying a range of the chain
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ChainVerification {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub first_seq: Option<i64>,
+    pub last_seq: Option<i64>,
+    pub records_checked: u64,
+    /// record_hash of the last record checked
+    pub head_hash: Option<String>,
+    pub valid: bool,
+    pub issues: Vec<ChainIssue>,
+}
+
+/// Checks records in chain order, one at a time
+pub struct ChainVerifier {
+    expected_seq: i64,
+    last_hash: Option<String>,
+    pub records_checked: u64,
+    pub issues: Vec<ChainIssue>,
+}
+
+impl ChainVerifier {
+    /// Start after `previous` (seq and record_hash); None when starting at the genesis record
+    pub fn new(previous: Option<(i64, String)>) -> Self {
+        match previous {
+            Some((seq, hash)) => Self { expected_seq: seq + 1, last_hash: Some(hash), records_checked: 0, issues: Vec::new() },
+            None => Self { expected_seq: 1, last_hash: Some(GENESIS_HASH.to_string()), records_checked: 0, issues: Vec::new() },
+        }
+    }
+
+    /// Start at `first_seq` without a known predecessor (its link is not checked)
+    pub fn detached(first_seq: i64) -> Self {
+        Self { expected_seq: first_seq, last_hash: None, records_checked: 0, issues: Vec::new() }
+    }
+
+    pub fn push(&mut self, entry: &ChainEntry) {
+        if entry.chain_seq != self.expected_seq {
+            self.issues.push(ChainIssue {
+                chain_seq: self.expected_seq,
+                seal_id: None,
+                kind: "GAP".to_string(),
+                detail: if entry.chain_seq > self.expected_seq {
+                    format!("records {}..{} are missing", self.expected_seq, entry.chain_seq - 1)
+                } else {
+                    format!("record {} appears out of order", entry.chain_seq)
+                },
+            });
+        }
+        if let Some(last_hash) = &self.last_hash {
+            if &entry.prev_hash != last_hash {
+                self.issues.push(ChainIssue {
+                    chain_seq: entry.chain_seq,
+                    seal_id: Some(entry.seal_id.clone()),
+                    kind: "BROKEN_LINK".to_string(),
+                    detail: "prev_hash does not match the hash of the preceding record".to_string(),
+                });
+            }
+        }
+        if record_hash(entry) != entry.record_hash {
+            self.issues.push(ChainIssue {
+                chain_seq: entry.chain_seq,
+                seal_id: Some(entry.seal_id.clone()),
+                kind: "MODIFIED".to_string(),
+                detail: "record content does not match its record_hash".to_string(),
+            });
+        }
+        self.expected_seq = entry.chain_seq + 1;
+        self.last_hash = Some(entry.record_hash.clone());
+        self.records_checked += 1;
+    }
+
+    pub fn last_hash(&self) -> Option<&str> {
+        self.last_hash.as_deref()
+    }
+}
+
+/// The hash-chained compliance_records ledger
+pub struct AuditChain;
+
+impl AuditChain {
+    /// Append an entry superseding an action record, copying its identity
+    ///
+    /// Returns the new entry's seal_id, or None if there is no such action record
+    /// (for the given user, if any) or it has already been erased.
+    pub async fn append_superseding(
+        db_pool: &PgPool,
+        seal_id: &str,
+        entry_type: EntryType,
+        status: &str,
+        action_summary: &str,
+        user_id: Option<&str>,
+    ) -> Result<Option<String>, sqlx::Error> {
+        let entry_seal_id = format!("{}-{}", entry_type.as_str(), uuid::Uuid::new_v4());
+        sqlx::query_scalar(
+            "INSERT INTO compliance_records (
+                id, timestamp, agent_id, action_summary, seal_id, status, human_oversight_status,
+                risk_level, user_id, tx_id, payload_hash, entry_type, supersedes_seal_id
+            )
+            SELECT gen_random_uuid(), CURRENT_TIMESTAMP, agent_id, $3, $2, $4, human_oversight_status,
+                   risk_level, user_id, tx_id, payload_hash, $5, seal_id
+            FROM compliance_records
+            WHERE seal_id = $1
+              AND entry_type = 'ACTION'
+              AND ($6::TEXT IS NULL OR user_id = $6)
+              AND NOT EXISTS (
+                  SELECT 1 FROM compliance_records t
+                  WHERE t.supersedes_seal_id = $1 AND t.entry_type = 'TOMBSTONE'
+              )
+            RETURNING seal_id"
+        )
+        .bind(seal_id)
+        .bind(&entry_seal_id)
+        .bind(action_summary)
+        .bind(status)
+        .bind(entry_type.as_str())
+        .bind(user_id)
+        .fetch_optional(db_pool)
+        .await
+    }
+
+    /// Whether an action record already has an erasure tombstone
+    pub async fn is_erased(db_pool: &PgPool, seal_id: &str) -> Result<bool, sqlx::Error> {
+        sqlx::query_scalar(
+            "SELECT EXISTS (
+                SELECT 1 FROM compliance_records
+                WHERE supersedes_seal_id = $1 AND entry_type = 'TOMBSTONE'
+            )"
+        )
+        .bind(seal_id)
+        .fetch_one(db_pool)
+        .await
+    }
+
+    /// Append an erasure tombstone for an action record
+    pub async fn append_tombstone(
+        db_pool: &PgPool,
+        seal_id: &str,
+        status: &str,
+        action_summary: &str,
+    ) -> Result<Option<String>, sqlx::Error> {
+        Self::append_superseding(db_pool, seal_id, EntryType::Tombstone, status, action_summary, None).await
+    }
+
+    /// Current state of action records, newest first, with their total count
+    ///
+    /// Filtered by seal_id, or else by agent_id, when given.
+    pub async fn current_records(
+        db_pool: &PgPool,
+        seal_id: Option<&str>,
+        agent_id: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(i64, Vec<ComplianceRecordDb>), sqlx::Error> {
+        let agent_id = if seal_id.is_some() { None } else { agent_id };
+        let total: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM compliance_records_current
+             WHERE ($1::TEXT IS NULL OR seal_id = $1) AND ($2::TEXT IS NULL OR agent_id = $2)"
+        )
+        .bind(seal_id)
+        .bind(agent_id)
+        .fetch_one(db_pool)
+        .await?;
+        let records = sqlx::query_as(
+            "SELECT * FROM compliance_records_current
+             WHERE ($1::TEXT IS NULL OR seal_id = $1) AND ($2::TEXT IS NULL OR agent_id = $2)
+             ORDER BY timestamp DESC
+             LIMIT $3 OFFSET $4"
+        )
+        .bind(seal_id)
+        .bind(agent_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(db_pool)
+        .await?;
+        Ok((total, records))
+    }
+
+    /// Current state of the records indexed for a data subject (GDPR Art. 15 and 20)
+    pub async fn subject_records(db_pool: &PgPool, user_id: &str) -> Result<Vec<ComplianceRecordDb>, sqlx::Error> {
+        sqlx::query_as(
+            "SELECT cr.* FROM compliance_records_current cr
+             INNER JOIN user_data_index udi ON cr.seal_id = udi.seal_id
+             WHERE udi.user_id = $1
+             ORDER BY cr.timestamp DESC"
+        )
+        .bind(user_id)
+        .fetch_all(db_pool)
+        .await
+    }
+
+    /// Verify the records chained between `from` and `to` (the whole chain when both are None)
+    pub async fn verify(
+        db_pool: &PgPool,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<ChainVerification, sqlx::Error> {
+        let (first_seq, last_seq): (Option<i64>, Option<i64>) = sqlx::query_as(
+            "SELECT MIN(chain_seq), MAX(chain_seq) FROM compliance_records
+             WHERE chain_seq IS NOT NULL
+               AND ($1::TIMESTAMPTZ IS NULL OR chained_at >= $1)
+               AND ($2::TIMESTAMPTZ IS NULL OR chained_at <= $2)"
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_one(db_pool)
+        .await?;
+
+        let mut issues = Vec::new();
+        let unchained: Vec<String> = sqlx::query_scalar(
+            "SELECT seal_id FROM compliance_records
+             WHERE chain_seq IS NULL
+               AND ($1::TIMESTAMPTZ IS NULL OR created_at >= $1)
+               AND ($2::TIMESTAMPTZ IS NULL OR created_at <= $2)
+             ORDER BY created_at
+             LIMIT 100"
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_all(db_pool)
+        .await?;
+        for seal_id in unchained {
+            issues.push(ChainIssue {
+                chain_seq: 0,
+                seal_id: Some(seal_id),
+                kind: "UNCHAINED".to_string(),
+                detail: "record was inserted without a chain position".to_string(),
+            });
+        }
+
+        let (Some(first_seq), Some(last_seq)) = (first_seq, last_seq) else {
+            return Ok(ChainVerification {
+                from,
+                to,
+                first_seq: None,
+                last_seq: None,
+                records_checked: 0,
+                head_hash: None,
+                valid: issues.is_empty(),
+                issues,
+            });
+        };
+
+        // Link the range to the record before it
+        let mut verifier = if first_seq == 1 {
+            ChainVerifier::new(None)
+        } else {
+            let previous: Option<String> = sqlx::query_scalar(
+                "SELECT record_hash FROM compliance_records WHERE chain_seq = $1"
+            )
+            .bind(first_seq - 1)
+            .fetch_optional(db_pool)
+            .await?;
+            match previous {
+                Some(hash) => ChainVerifier::new(Some((first_seq - 1, hash))),
+                None => {
+                    issues.push(ChainIssue {
+                        chain_seq: first_seq - 1,
+                        seal_id: None,
+                        kind: "GAP".to_string(),
+                        detail: format!("record {} before the range is missing", first_seq - 1),
+                    });
+                    ChainVerifier::detached(first_seq)
+                }
+            }
+        };
+
+        let mut after = first_seq - 1;
+        loop {
+            let batch: Vec<ChainEntry> = sqlx::query_as(&format!(
+                "SELECT {} FROM compliance_records
+                 WHERE chain_seq > $1 AND chain_seq <= $2
+                 ORDER BY chain_seq
+                 LIMIT $3",
+                ENTRY_COLUMNS
+            ))
+            .bind(after)
+            .bind(last_seq)
+            .bind(VERIFY_BATCH)
+            .fetch_all(db_pool)
+            .await?;
+
+            for entry in &batch {
+                verifier.push(entry);
+            }
+            match batch.last() {
+                Some(entry) if batch.len() as i64 == VERIFY_BATCH => after = entry.chain_seq,
+                _ => break,
+            }
+        }
+
+        let head_hash = verifier.last_hash().map(str::to_string);
+        let records_checked = verifier.records_checked;
+        issues.extend(verifier.issues);
+        Ok(ChainVerification {
+            from,
+            to,
+            first_seq: Some(first_seq),
+            last_seq: Some(last_seq),
+            records_checked,
+            head_hash,
+            valid: issues.is_empty(),
+            issues,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chain(len: i64) -> Vec<ChainEntry> {
+        let mut prev_hash = GENESIS_HASH.to_string();
+        (1..=len)
+            .map(|seq| {
+                let mut entry = ChainEntry {
+                    chain_seq: seq,
+                    prev_hash: prev_hash.clone(),
+                    record_hash: String::new(),
+                    seal_id: format!("seal-{}", seq),
+                    tx_id: format!("tx-{}", seq),
+                    agent_id: "agent-1".to_string(),
+                    action_summary: format!("agent-1: action {}", seq),
+                    status: "COMPLIANT".to_string(),
+                    payload_hash: "ab".repeat(32),
+                    user_id: None,
+                    risk_level: Some("LOW".to_string()),
+                    timestamp: DateTime::parse_from_rfc3339("2026-01-01T00:00:00.000001Z").unwrap().with_timezone(&Utc),
+                    entry_type: EntryType::Action.as_str().to_string(),
+                    supersedes_seal_id: None,
+                };
+                entry.record_hash = record_hash(&entry);
+                prev_hash = entry.record_hash.clone();
+                entry
+            })
+            .collect()
+    }
+
+    fn verify(entries: &[ChainEntry]) -> Vec<String> {
+        let mut verifier = ChainVerifier::new(None);
+        entries.iter().for_each(|e| verifier.push(e));
+        verifier.issues.into_iter().map(|i| format!("{}@{}", i.kind, i.chain_seq)).collect()
+    }
+
+    #[test]
+    fn test_intact_chain_verifies() {
+        let entries = chain(5);
+        assert!(verify(&entries).is_empty());
+        assert_eq!(entries[0].prev_hash, GENESIS_HASH);
+        assert_eq!(entries[0].record_hash.len(), 64);
+    }
+
+    #[test]
+    fn test_modification_and_gap_are_found() {
+        let mut entries = chain(5);
+        entries[1].status = "BLOCKED (SOVEREIGNTY)".to_string();
+        assert_eq!(verify(&entries), vec!["MODIFIED@2"]);
+
+        // Re-hashing a modified record breaks the link to the next one
+        entries[1].record_hash = record_hash(&entries[1]);
+        assert_eq!(verify(&entries), vec!["BROKEN_LINK@3"]);
+
+        let mut entries = chain(5);
+        entries.remove(2);
+        assert_eq!(verify(&entries), vec!["GAP@3", "BROKEN_LINK@4"]);
+    }
+
+    /// Needs PostgreSQL (TEST_DATABASE_URL or DATABASE_URL)
+    #[tokio::test]
+    #[ignore] // Ignore by default, run with: cargo test -- --ignored
+    async fn test_erased_record_hides_original_content() {
+        let db_pool = &crate::test_helpers::create_test_db_pool().await;
+
+        let tag = uuid::Uuid::new_v4().to_string();
+        let (agent_id, user_id) = (format!("agent-{}", tag), format!("user-{}", tag));
+        let (seal_id, kept_seal_id) = (format!("SEAL-{}", tag), format!("SEAL-kept-{}", tag));
+        for (seal, summary) in [(&seal_id, "Email jane@example.com"), (&kept_seal_id, "Score credit")] {
+            sqlx::query(
+                "INSERT INTO compliance_records (agent_id, action_summary, seal_id, status, user_id, tx_id, payload_hash)
+                 VALUES ($1, $2, $3, 'COMPLIANT', $4, $3, 'hash')"
+            )
+            .bind(&agent_id).bind(summary).bind(seal).bind(&user_id)
+            .execute(db_pool).await.unwrap();
+            sqlx::query("INSERT INTO user_data_index (user_id, seal_id) VALUES ($1, $2)")
+                .bind(&user_id).bind(seal)
+                .execute(db_pool).await.unwrap();
+        }
+
+        // As shred_data does
+        assert!(!AuditChain::is_erased(db_pool, &seal_id).await.unwrap());
+        let tombstone = AuditChain::append_tombstone(
+            db_pool,
+            &seal_id,
+            "ERASED (Art. 17)",
+            "[GDPR PURGED] Data Cryptographically Erased",
+        ).await.unwrap();
+        assert!(tombstone.is_some());
+        assert!(AuditChain::is_erased(db_pool, &seal_id).await.unwrap());
+        let again = AuditChain::append_tombstone(db_pool, &seal_id, "ERASED (Art. 17)", "erased").await.unwrap();
+        assert!(again.is_none());
+
+        // GET /logs: one row per action record, the erased one purged
+        let (total, records) = AuditChain::current_records(db_pool, None, Some(&agent_id), 10, 0).await.unwrap();
+        assert_eq!(total, 2);
+        let erased = records.iter().find(|r| r.seal_id == seal_id).unwrap();
+        assert_eq!(erased.action_summary, "[GDPR PURGED] Data Cryptographically Erased");
+        assert_eq!(erased.status, "ERASED (Art. 17)");
+        assert!(records.iter().all(|r| !r.action_summary.contains("jane@example.com")));
+        let (_, by_seal) = AuditChain::current_records(db_pool, Some(&seal_id), None, 10, 0).await.unwrap();
+        assert_eq!(by_seal[0].status, "ERASED (Art. 17)");
+
+        // Data subject access and export
+        let records = AuditChain::subject_records(db_pool, &user_id).await.unwrap();
+        assert_eq!(records.len(), 2);
+        assert!(records.iter().all(|r| !r.action_summary.contains("jane@example.com")));
+        let kept = records.iter().find(|r| r.seal_id == kept_seal_id).unwrap();
+        assert_eq!((kept.action_summary.as_str(), kept.status.as_str()), ("Score credit", "COMPLIANT"));
+    }
+}
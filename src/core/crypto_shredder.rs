@@ -4,67 +4,173 @@ use aes_gcm::{
 };
 use rand::RngCore;
 use rand::thread_rng;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
 use std::collections::HashMap;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
+
+use crate::core::key_provider::{self, KeyProvider, LEGACY_MASTER_KEY_ID};
+
+/// `master_key_id` recorded for DEKs wrapped by a data subject key (KEK)
+/// instead of a master key. The KEK itself is wrapped by the master key.
+pub const SUBJECT_KEY_ID: &str = "subject";
+
+/// SHA-256 fingerprint of key material (hex). Identifies a key in erasure
+/// certificates without revealing it.
+pub fn key_fingerprint(key: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(key))
+}
 
 /// Immutable encrypted log entry
 /// Contains only ciphertext and nonce, no keys
 #[derive(Debug, Clone)]
 pub struct EncryptedLog {
     pub log_id: String,
-    #[allow(dead_code)]
     pub ciphertext: Vec<u8>,
-    #[allow(dead_code)]
     pub nonce: Vec<u8>,
 }
 
+/// A DEK wrapped by one master key version, or by a data subject key
+#[derive(Debug, Clone)]
+struct WrappedDek {
+    /// Master key version, or `SUBJECT_KEY_ID` when wrapped by a subject KEK
+    master_key_id: String,
+    /// Data subject whose KEK wraps this DEK
+    subject_id: Option<String>,
+    /// Wrapped DEK as returned by the key provider
+    bytes: Vec<u8>,
+    /// Fingerprint of the plaintext key (only known when created on this node)
+    fingerprint: Option<String>,
+}
+
+impl WrappedDek {
+    fn new(master_key_id: String, subject_id: Option<String>, bytes: Vec<u8>) -> Self {
+        Self { master_key_id, subject_id, bytes, fingerprint: None }
+    }
+}
+
+/// Keys destroyed by a data subject erasure
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SubjectErasure {
+    /// Fingerprint of the destroyed data subject key, if the subject had one
+    pub subject_key_fingerprint: Option<String>,
+    /// Shredded log entries (tx_id) with the fingerprint of each destroyed DEK
+    pub shredded_keys: Vec<(String, String)>,
+}
+
+/// Outcome of a DEK re-wrap run
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RewrapReport {
+    /// DEKs moved to the current master key
+    pub rewrapped: usize,
+    /// DEKs that could not be unwrapped and were left untouched
+    pub failed: usize,
+}
+
+/// Position of a re-wrap run in the key tables
+///
+/// Each batch starts after the last row of the previous one, so rows that
+/// cannot be unwrapped are not picked again and do not hide the rows behind them.
+#[derive(Debug, Clone, Default)]
+pub struct RewrapCursor {
+    dek: Option<(chrono::NaiveDateTime, String)>,
+    subject_key: Option<(chrono::NaiveDateTime, uuid::Uuid)>,
+}
+
 /// Mutable key store for managing wrapped Data Encryption Keys (DEKs)
 pub struct VeridionKeyStore {
-    /// Maps log_id -> Wrapped DEK (encrypted with master key)
-    keys: Mutex<HashMap<String, Vec<u8>>>,
-    /// Master key for wrapping/unwrapping DEKs
-    master_key: Vec<u8>,
+    /// Maps log_id -> Wrapped DEK (encrypted with a master key version)
+    keys: Mutex<HashMap<String, WrappedDek>>,
+    /// Maps subject_id -> Wrapped data subject key (KEK, encrypted with a master key version)
+    subject_keys: Mutex<HashMap<String, WrappedDek>>,
+    /// Backend holding the master keys; wraps and unwraps DEKs
+    provider: Arc<dyn KeyProvider>,
+    /// Wrap DEKs of records with a user_id under a per-subject KEK
+    subject_keys_enabled: bool,
 }
 
 impl VeridionKeyStore {
-    /// Create a new VeridionKeyStore by loading the master key from environment
+    /// Create a new VeridionKeyStore using the key provider configured in the
+    /// environment (`VERIDION_KEY_PROVIDER`, see `key_provider::provider_from_env`)
+    /// 
+    /// Per-subject keys are enabled with `VERIDION_SUBJECT_KEYS=true`.
     /// 
     /// # Panics
     /// 
-    /// Panics if VERIDION_MASTER_KEY is not set in the environment
+    /// Panics if the selected key provider is misconfigured
     pub fn new() -> Self {
-        let master_key_str = std::env::var("VERIDION_MASTER_KEY")
-            .expect("VERIDION_MASTER_KEY must be set in environment");
-        
-        // Convert the master key string to bytes
-        // For simplicity, we'll use the first 32 bytes (pad or truncate as needed)
-        let mut master_key = master_key_str.as_bytes().to_vec();
-        
-        // Ensure master key is exactly 32 bytes (256 bits) for AES-256
-        if master_key.len() < 32 {
-            // Pad with zeros if too short
-            master_key.resize(32, 0);
-        } else if master_key.len() > 32 {
-            // Truncate if too long
-            master_key.truncate(32);
-        }
-        
+        let subject_keys_enabled = std::env::var("VERIDION_SUBJECT_KEYS")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse::<bool>()
+            .unwrap_or(false);
+        let mut store = Self::with_provider(key_provider::provider_from_env());
+        store.subject_keys_enabled = subject_keys_enabled;
+        store
+    }
+
+    /// Create a VeridionKeyStore backed by an explicit key provider
+    pub fn with_provider(provider: Arc<dyn KeyProvider>) -> Self {
+        log::info!(
+            "Crypto-shredder using '{}' key provider (current master key {})",
+            provider.name(),
+            provider.current_key_id()
+        );
         Self {
             keys: Mutex::new(HashMap::new()),
-            master_key,
+            subject_keys: Mutex::new(HashMap::new()),
+            provider,
+            subject_keys_enabled: false,
         }
     }
 
-    /// Log an event by encrypting the payload and storing the wrapped DEK
-    /// 
-    /// # Arguments
-    /// 
-    /// * `payload` - The plaintext data to encrypt
-    /// 
-    /// # Returns
-    /// 
-    /// * `EncryptedLog` containing the encrypted data and metadata (no keys)
-    pub fn log_event(&self, payload: &str) -> EncryptedLog {
+    /// Id of the master key version used to wrap new DEKs
+    pub fn current_master_key_id(&self) -> &str {
+        self.provider.current_key_id()
+    }
+
+    /// Ids of all master key versions currently available for unwrapping
+    pub fn master_key_ids(&self) -> Vec<String> {
+        let mut ids = self.provider.key_ids();
+        ids.sort();
+        ids
+    }
+
+    /// Wrap (encrypt) a DEK with the current master key
+    async fn wrap_dek(&self, dek: &[u8]) -> Result<WrappedDek, String> {
+        let master_key_id = self.provider.current_key_id().to_string();
+        let bytes = self.provider.wrap(&master_key_id, dek).await?;
+        Ok(WrappedDek::new(master_key_id, None, bytes))
+    }
+
+    /// Unwrap (decrypt) a DEK with the key that wrapped it: its master key
+    /// version, or the KEK of its data subject
+    async fn unwrap_dek(&self, wrapped: &WrappedDek) -> Result<Vec<u8>, String> {
+        match &wrapped.subject_id {
+            Some(subject_id) => {
+                let wrapped_kek = self
+                    .subject_keys
+                    .lock()
+                    .expect("Failed to acquire lock")
+                    .get(subject_id)
+                    .cloned()
+                    .ok_or_else(|| "GDPR_PURGED: Data subject key destroyed".to_string())?;
+                let kek = self
+                    .provider
+                    .unwrap(&wrapped_kek.master_key_id, &wrapped_kek.bytes)
+                    .await?;
+                key_provider::aes_unwrap(&kek, &wrapped.bytes)
+            }
+            None => self.provider.unwrap(&wrapped.master_key_id, &wrapped.bytes).await,
+        }
+    }
+
+    /// Encrypt a payload under a fresh DEK and cache the DEK, wrapped by `wrap`
+    async fn encrypt_payload<F, Fut>(&self, payload: &str, wrap: F) -> Result<EncryptedLog, String>
+    where
+        F: FnOnce(Vec<u8>) -> Fut,
+        Fut: std::future::Future<Output = Result<WrappedDek, String>>,
+    {
         // Generate a random 256-bit (32-byte) DEK
         let mut dek = vec![0u8; 32];
         thread_rng().fill_bytes(&mut dek);
@@ -83,24 +189,10 @@ impl VeridionKeyStore {
             .encrypt(nonce, payload.as_bytes())
             .expect("Failed to encrypt payload");
         
-        // Wrap (encrypt) the DEK using the master key
-        let master_cipher = Aes256Gcm::new_from_slice(&self.master_key)
-            .expect("Failed to create master cipher");
-        
-        // Generate a nonce for wrapping the DEK
-        let mut wrap_nonce_bytes = vec![0u8; 12];
-        thread_rng().fill_bytes(&mut wrap_nonce_bytes);
-        let wrap_nonce = Nonce::from_slice(&wrap_nonce_bytes);
-        
-        // Encrypt the DEK (wrap it)
-        // We'll prepend the wrap_nonce to the wrapped DEK for later decryption
-        let wrapped_dek = master_cipher
-            .encrypt(wrap_nonce, dek.as_slice())
-            .expect("Failed to wrap DEK");
-        
-        // Combine wrap_nonce and wrapped_dek for storage
-        let mut wrapped_dek_with_nonce = wrap_nonce_bytes;
-        wrapped_dek_with_nonce.extend_from_slice(&wrapped_dek);
+        // Wrap (encrypt) the DEK
+        let fingerprint = key_fingerprint(&dek);
+        let mut wrapped_dek = wrap(dek).await?;
+        wrapped_dek.fingerprint = Some(fingerprint);
         
         // Generate a unique log_id using random bytes
         let mut log_id_bytes = vec![0u8; 16];
@@ -111,13 +203,28 @@ impl VeridionKeyStore {
         self.keys
             .lock()
             .expect("Failed to acquire lock")
-            .insert(log_id.clone(), wrapped_dek_with_nonce);
+            .insert(log_id.clone(), wrapped_dek);
         
-        EncryptedLog {
+        Ok(EncryptedLog {
             log_id,
             ciphertext,
             nonce: nonce_bytes,
-        }
+        })
+    }
+
+    /// Log an event by encrypting the payload and storing the wrapped DEK
+    /// 
+    /// # Arguments
+    /// 
+    /// * `payload` - The plaintext data to encrypt
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(EncryptedLog)` containing the encrypted data and metadata (no keys)
+    /// * `Err(String)` if the key provider could not wrap the DEK
+    pub async fn log_event(&self, payload: &str) -> Result<EncryptedLog, String> {
+        // The DEK is wrapped by the current master key through the key provider
+        self.encrypt_payload(payload, |dek| async move { self.wrap_dek(&dek).await }).await
     }
 
     /// Read an event by decrypting using the stored wrapped DEK
@@ -130,10 +237,9 @@ impl VeridionKeyStore {
     /// 
     /// * `Ok(String)` with the decrypted payload
     /// * `Err(String)` with "GDPR_PURGED: Data key destroyed" if the key was shredded
-    #[allow(dead_code)]
-    pub fn read_event(&self, log: &EncryptedLog) -> Result<String, String> {
+    pub async fn read_event(&self, log: &EncryptedLog) -> Result<String, String> {
         // Look up log.log_id in the HashMap
-        let wrapped_dek_with_nonce = self
+        let wrapped_dek = self
             .keys
             .lock()
             .expect("Failed to acquire lock")
@@ -141,22 +247,12 @@ impl VeridionKeyStore {
             .ok_or_else(|| "GDPR_PURGED: Data key destroyed".to_string())?
             .clone();
         
-        // Extract the wrap nonce (first 12 bytes) and wrapped DEK (rest)
-        let wrap_nonce_bytes = &wrapped_dek_with_nonce[..12];
-        let wrapped_dek = &wrapped_dek_with_nonce[12..];
-        
-        // Decrypt the wrapped DEK using master key
-        let master_cipher = Aes256Gcm::new_from_slice(&self.master_key)
-            .expect("Failed to create master cipher");
-        
-        let wrap_nonce = Nonce::from_slice(wrap_nonce_bytes);
-        let dek = master_cipher
-            .decrypt(wrap_nonce, wrapped_dek)
-            .map_err(|_| "Failed to unwrap DEK".to_string())?;
+        // Unwrap the DEK through the key provider (or the data subject key)
+        let dek = self.unwrap_dek(&wrapped_dek).await?;
         
         // Decrypt the log.ciphertext using the restored DEK
         let cipher = Aes256Gcm::new_from_slice(&dek)
-            .expect("Failed to create cipher from DEK");
+            .map_err(|_| "Invalid DEK length".to_string())?;
         
         let nonce = Nonce::from_slice(&log.nonce);
         let plaintext = cipher
@@ -183,7 +279,7 @@ impl VeridionKeyStore {
             .lock()
             .expect("Failed to acquire lock")
             .get(log_id)
-            .cloned()
+            .map(|wrapped| wrapped.bytes.clone())
     }
 
     /// Shred (delete) the key for a specific log entry
@@ -197,14 +293,555 @@ impl VeridionKeyStore {
             .expect("Failed to acquire lock")
             .remove(log_id);
     }
+
+    // ========== POSTGRES-BACKED PERSISTENCE ==========
+    // `encrypted_log_keys` is the source of truth. The in-memory map is only a
+    // cache, so every node sharing the database sees the same keys and shreds.
+
+    /// Eagerly load all non-shredded wrapped DEKs from the database
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(usize)` with the number of keys loaded into the cache
+    pub async fn load_from_db(&self, db_pool: &PgPool) -> Result<usize, sqlx::Error> {
+        let rows: Vec<(String, String, Option<String>, Vec<u8>)> = sqlx::query_as(
+            "SELECT log_id, master_key_id, subject_id, wrapped_dek FROM encrypted_log_keys WHERE shredded_at IS NULL"
+        )
+        .fetch_all(db_pool)
+        .await?;
+        let subject_rows: Vec<(String, String, Vec<u8>)> = sqlx::query_as(
+            "SELECT user_id, master_key_id, wrapped_kek FROM data_subject_keys WHERE destroyed_at IS NULL"
+        )
+        .fetch_all(db_pool)
+        .await?;
+
+        let count = rows.len();
+        let mut keys = self.keys.lock().expect("Failed to acquire lock");
+        for (log_id, master_key_id, subject_id, bytes) in rows {
+            keys.insert(log_id, WrappedDek::new(master_key_id, subject_id, bytes));
+        }
+        let mut subject_keys = self.subject_keys.lock().expect("Failed to acquire lock");
+        for (subject_id, master_key_id, bytes) in subject_rows {
+            subject_keys.insert(subject_id, WrappedDek::new(master_key_id, None, bytes));
+        }
+
+        Ok(count)
+    }
+
+    /// Persist the wrapped DEK of a log entry to `encrypted_log_keys`
+    pub async fn persist_key(&self, db_pool: &PgPool, log_id: &str) -> Result<(), sqlx::Error> {
+        let wrapped_dek = self
+            .keys
+            .lock()
+            .expect("Failed to acquire lock")
+            .get(log_id)
+            .cloned();
+        if let Some(wrapped_dek) = wrapped_dek {
+            sqlx::query(
+                "INSERT INTO encrypted_log_keys (log_id, master_key_id, subject_id, wrapped_dek, key_fingerprint)
+                 VALUES ($1, $2, $3, $4, $5)
+                 ON CONFLICT (log_id) DO NOTHING"
+            )
+            .bind(log_id)
+            .bind(&wrapped_dek.master_key_id)
+            .bind(&wrapped_dek.subject_id)
+            .bind(&wrapped_dek.bytes)
+            .bind(&wrapped_dek.fingerprint)
+            .execute(db_pool)
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Refresh the cached key for `log_id` from the database
+    /// 
+    /// Loads keys written by other nodes (lazy loading) and evicts keys that
+    /// were shredded elsewhere.
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(true)` if a live key is available after the refresh
+    /// * `Ok(false)` if the key is shredded or unknown
+    pub async fn sync_key(&self, db_pool: &PgPool, log_id: &str) -> Result<bool, sqlx::Error> {
+        let row: Option<(String, Option<String>, Vec<u8>, Option<chrono::NaiveDateTime>)> = sqlx::query_as(
+            "SELECT master_key_id, subject_id, wrapped_dek, shredded_at FROM encrypted_log_keys WHERE log_id = $1"
+        )
+        .bind(log_id)
+        .fetch_optional(db_pool)
+        .await?;
+
+        match row {
+            Some((master_key_id, subject_id, bytes, None)) if !bytes.is_empty() => {
+                if let Some(subject_id) = &subject_id {
+                    if !self.sync_subject_key(db_pool, subject_id).await? {
+                        self.keys.lock().expect("Failed to acquire lock").remove(log_id);
+                        return Ok(false);
+                    }
+                }
+                self.keys
+                    .lock()
+                    .expect("Failed to acquire lock")
+                    .insert(log_id.to_string(), WrappedDek::new(master_key_id, subject_id, bytes));
+                Ok(true)
+            }
+            _ => {
+                self.keys.lock().expect("Failed to acquire lock").remove(log_id);
+                Ok(false)
+            }
+        }
+    }
+
+    /// Read an event after checking the database for the current key state
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(String)` with the decrypted payload
+    /// * `Err(String)` with "GDPR_PURGED: Data key destroyed" if the key was shredded on any node
+    pub async fn read_event_persisted(&self, db_pool: &PgPool, log: &EncryptedLog) -> Result<String, String> {
+        self.sync_key(db_pool, &log.log_id)
+            .await
+            .map_err(|e| format!("Failed to load key: {}", e))?;
+        self.read_event(log).await
+    }
+
+    /// Shred a key locally and in the database
+    pub async fn shred_key_persisted(&self, db_pool: &PgPool, log_id: &str) -> Result<Option<String>, sqlx::Error> {
+        self.shred_key(log_id);
+        Self::mark_shredded(db_pool, log_id).await
+    }
+
+    /// Destroy the persisted wrapped DEK and record the shred time
+    /// 
+    /// Returns the fingerprint of the destroyed key for erasure certificates. The
+    /// fingerprint is kept on the row, so shredding again returns the same value.
+    /// Does not need a key store instance, so background jobs can shred too.
+    /// Nodes holding the key in cache drop it on their next `sync_key`.
+    pub async fn mark_shredded(db_pool: &PgPool, log_id: &str) -> Result<Option<String>, sqlx::Error> {
+        let fingerprint: Option<Option<String>> = sqlx::query_scalar(
+            "WITH target AS (
+                 SELECT log_id,
+                        CASE WHEN shredded_at IS NULL
+                             THEN COALESCE(key_fingerprint, encode(sha256(wrapped_dek), 'hex'))
+                             ELSE key_fingerprint END AS fingerprint
+                 FROM encrypted_log_keys
+                 WHERE log_id = $1
+                 FOR UPDATE
+             )
+             UPDATE encrypted_log_keys k
+             SET wrapped_dek = ''::bytea,
+                 key_fingerprint = t.fingerprint,
+                 shredded_at = COALESCE(k.shredded_at, CURRENT_TIMESTAMP)
+             FROM target t
+             WHERE k.log_id = t.log_id
+             RETURNING t.fingerprint"
+        )
+        .bind(log_id)
+        .fetch_optional(db_pool)
+        .await?;
+        Ok(fingerprint.flatten())
+    }
+
+    // ========== DATA SUBJECT KEYS ==========
+    // Optional per-subject key hierarchy: master key -> subject KEK -> DEK.
+    // Destroying one KEK makes every record of that data subject unreadable
+    // (GDPR Article 17) without locating each record first.
+
+    /// Whether records with a user_id are logged under a per-subject KEK
+    pub fn subject_keys_enabled(&self) -> bool {
+        self.subject_keys_enabled
+    }
+
+    /// Log an event whose DEK is wrapped by the data subject's KEK
+    /// 
+    /// The KEK is created on first use, and again for data logged after an
+    /// erasure. Fails if the key provider cannot wrap or unwrap the KEK.
+    pub async fn log_event_for_subject(
+        &self,
+        db_pool: &PgPool,
+        subject_id: &str,
+        payload: &str,
+    ) -> Result<EncryptedLog, String> {
+        let kek = self.subject_kek(db_pool, subject_id).await?;
+        self.encrypt_payload(payload, |dek| async move {
+            let bytes = key_provider::aes_wrap(&kek, &dek)?;
+            Ok(WrappedDek::new(SUBJECT_KEY_ID.to_string(), Some(subject_id.to_string()), bytes))
+        })
+        .await
+    }
+
+    /// Get (or create) the live KEK of a data subject, unwrapped
+    async fn subject_kek(&self, db_pool: &PgPool, subject_id: &str) -> Result<Vec<u8>, String> {
+        let cached = self
+            .subject_keys
+            .lock()
+            .expect("Failed to acquire lock")
+            .get(subject_id)
+            .cloned();
+        let wrapped_kek = match cached {
+            Some(wrapped_kek) => wrapped_kek,
+            None => {
+                let found = self
+                    .sync_subject_key(db_pool, subject_id)
+                    .await
+                    .map_err(|e| format!("Failed to load data subject key: {}", e))?;
+                if !found {
+                    self.create_subject_key(db_pool, subject_id).await?;
+                }
+                self.subject_keys
+                    .lock()
+                    .expect("Failed to acquire lock")
+                    .get(subject_id)
+                    .cloned()
+                    .ok_or_else(|| "Failed to create data subject key".to_string())?
+            }
+        };
+        self.provider.unwrap(&wrapped_kek.master_key_id, &wrapped_kek.bytes).await
+    }
+
+    /// Generate and persist a new KEK for a data subject
+    /// 
+    /// If another node created one concurrently, that KEK wins and is cached instead.
+    async fn create_subject_key(&self, db_pool: &PgPool, subject_id: &str) -> Result<(), String> {
+        let mut kek = vec![0u8; 32];
+        thread_rng().fill_bytes(&mut kek);
+        let wrapped_kek = self.wrap_dek(&kek).await?;
+
+        sqlx::query(
+            "INSERT INTO data_subject_keys (user_id, master_key_id, wrapped_kek, key_fingerprint)
+             VALUES ($1, $2, $3, $4)
+             ON CONFLICT (user_id) WHERE destroyed_at IS NULL DO NOTHING"
+        )
+        .bind(subject_id)
+        .bind(&wrapped_kek.master_key_id)
+        .bind(&wrapped_kek.bytes)
+        .bind(key_fingerprint(&kek))
+        .execute(db_pool)
+        .await
+        .map_err(|e| format!("Failed to store data subject key: {}", e))?;
+
+        self.sync_subject_key(db_pool, subject_id)
+            .await
+            .map_err(|e| format!("Failed to load data subject key: {}", e))?;
+        Ok(())
+    }
+
+    /// Refresh the cached KEK of a data subject from the database
+    /// 
+    /// # Returns
+    /// 
+    /// * `Ok(true)` if the subject has a live KEK
+    /// * `Ok(false)` if it has none (never created, or destroyed by an erasure)
+    async fn sync_subject_key(&self, db_pool: &PgPool, subject_id: &str) -> Result<bool, sqlx::Error> {
+        let row: Option<(String, Vec<u8>)> = sqlx::query_as(
+            "SELECT master_key_id, wrapped_kek FROM data_subject_keys
+             WHERE user_id = $1 AND destroyed_at IS NULL"
+        )
+        .bind(subject_id)
+        .fetch_optional(db_pool)
+        .await?;
+
+        let mut subject_keys = self.subject_keys.lock().expect("Failed to acquire lock");
+        match row {
+            Some((master_key_id, bytes)) => {
+                subject_keys.insert(subject_id.to_string(), WrappedDek::new(master_key_id, None, bytes));
+                Ok(true)
+            }
+            None => {
+                subject_keys.remove(subject_id);
+                Ok(false)
+            }
+        }
+    }
+
+    /// Erase a data subject: destroy their KEK and every DEK of their records
+    /// 
+    /// Covers DEKs wrapped by the subject KEK, per-record DEKs of records
+    /// indexed for the subject in `user_data_index` (logged before subject keys
+    /// were enabled) and the subject's PII token vaults. Fingerprints are taken
+    /// before the key material is wiped.
+    pub async fn erase_subject(&self, db_pool: &PgPool, subject_id: &str) -> Result<SubjectErasure, sqlx::Error> {
+        let mut tx = db_pool.begin().await?;
+
+        let subject_key_fingerprint: Option<String> = sqlx::query_scalar(
+            "UPDATE data_subject_keys
+             SET wrapped_kek = ''::bytea, destroyed_at = CURRENT_TIMESTAMP
+             WHERE user_id = $1 AND destroyed_at IS NULL
+             RETURNING key_fingerprint"
+        )
+        .bind(subject_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let shredded_keys: Vec<(String, String)> = sqlx::query_as(
+            "WITH target AS (
+                 SELECT log_id, COALESCE(key_fingerprint, encode(sha256(wrapped_dek), 'hex')) AS fingerprint
+                 FROM encrypted_log_keys
+                 WHERE shredded_at IS NULL
+                   AND (subject_id = $1 OR log_id IN (
+                       SELECT cr.tx_id FROM compliance_records cr
+                       INNER JOIN user_data_index udi ON cr.seal_id = udi.seal_id
+                       WHERE udi.user_id = $1)
+                     OR log_id IN (SELECT vault_id FROM pii_token_vaults WHERE user_id = $1))
+                 FOR UPDATE
+             )
+             UPDATE encrypted_log_keys k
+             SET wrapped_dek = ''::bytea, shredded_at = CURRENT_TIMESTAMP
+             FROM target t
+             WHERE k.log_id = t.log_id
+             RETURNING t.log_id, t.fingerprint"
+        )
+        .bind(subject_id)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        self.subject_keys.lock().expect("Failed to acquire lock").remove(subject_id);
+        let mut keys = self.keys.lock().expect("Failed to acquire lock");
+        keys.retain(|_, wrapped| wrapped.subject_id.as_deref() != Some(subject_id));
+        for (log_id, _) in &shredded_keys {
+            keys.remove(log_id);
+        }
+
+        Ok(SubjectErasure {
+            subject_key_fingerprint,
+            shredded_keys,
+        })
+    }
+
+    // ========== MASTER KEY ROTATION ==========
+    // Rotating only re-wraps DEKs: ciphertext in encrypted_log_payloads is never
+    // touched, and shredded keys stay shredded.
+
+    /// Record the configured master key versions in `master_key_versions`
+    /// 
+    /// Versions retired through the API are dropped from the in-memory keyring,
+    /// even if they are still present in the environment.
+    pub async fn register_master_keys(&self, db_pool: &PgPool) -> Result<(), sqlx::Error> {
+        for key_id in self.master_key_ids() {
+            let kdf = self.provider.kdf(&key_id);
+            let status = if key_id == self.current_master_key_id() { "ACTIVE" } else { "DECRYPT_ONLY" };
+            sqlx::query(
+                "INSERT INTO master_key_versions (key_id, kdf, status, last_seen_at)
+                 VALUES ($1, $2, $3, CURRENT_TIMESTAMP)
+                 ON CONFLICT (key_id) DO UPDATE SET
+                     status = CASE WHEN master_key_versions.status = 'RETIRED'
+                                   THEN master_key_versions.status ELSE EXCLUDED.status END,
+                     last_seen_at = CURRENT_TIMESTAMP"
+            )
+            .bind(&key_id)
+            .bind(kdf)
+            .bind(status)
+            .execute(db_pool)
+            .await?;
+        }
+
+        // Only one version is ACTIVE: the one new DEKs are wrapped with
+        sqlx::query(
+            "UPDATE master_key_versions SET status = 'DECRYPT_ONLY'
+             WHERE status = 'ACTIVE' AND key_id <> $1"
+        )
+        .bind(self.current_master_key_id())
+        .execute(db_pool)
+        .await?;
+
+        let retired: Vec<String> = sqlx::query_scalar(
+            "SELECT key_id FROM master_key_versions WHERE status = 'RETIRED'"
+        )
+        .fetch_all(db_pool)
+        .await?;
+        for key_id in retired {
+            if key_id == self.current_master_key_id() {
+                log::warn!("Current master key version '{}' is marked RETIRED", key_id);
+            } else if self.retire_master_key(&key_id) && key_id != LEGACY_MASTER_KEY_ID {
+                log::warn!(
+                    "Master key version '{}' is retired; remove it from the key provider configuration",
+                    key_id
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Drop a master key version from the in-memory keyring
+    /// 
+    /// Cached DEKs and data subject keys wrapped with it are evicted as well.
+    /// 
+    /// # Returns
+    /// 
+    /// * `true` if the version was configured
+    pub fn retire_master_key(&self, key_id: &str) -> bool {
+        if key_id == self.current_master_key_id() {
+            return false;
+        }
+        self.keys
+            .lock()
+            .expect("Failed to acquire lock")
+            .retain(|_, wrapped| wrapped.master_key_id != key_id);
+        self.subject_keys
+            .lock()
+            .expect("Failed to acquire lock")
+            .retain(|_, wrapped| wrapped.master_key_id != key_id);
+        self.provider.retire(key_id)
+    }
+
+    /// Unwrap a key with its old master key version and wrap it with the current one
+    async fn rewrap_key(&self, label: &str, old: &WrappedDek) -> Option<WrappedDek> {
+        let key = match self.provider.unwrap(&old.master_key_id, &old.bytes).await {
+            Ok(key) => key,
+            Err(e) => {
+                log::warn!("Cannot re-wrap {} (master key {}): {}", label, old.master_key_id, e);
+                return None;
+            }
+        };
+        match self.wrap_dek(&key).await {
+            Ok(new) => Some(new),
+            Err(e) => {
+                log::warn!("Cannot re-wrap {} under master key {}: {}", label, self.current_master_key_id(), e);
+                None
+            }
+        }
+    }
+
+    /// Re-wrap one batch of live DEKs and data subject KEKs that are still
+    /// under an older master key, after the position of `cursor`
+    /// 
+    /// Rows are locked with `FOR UPDATE SKIP LOCKED`, so several nodes can run
+    /// the job at the same time and a concurrent shred is never overwritten.
+    /// DEKs wrapped by a subject KEK are untouched; re-wrapping the KEK covers them.
+    pub async fn rewrap_batch(&self, db_pool: &PgPool, batch_size: i64, cursor: &mut RewrapCursor) -> Result<RewrapReport, sqlx::Error> {
+        let old_key_ids: Vec<String> = self
+            .master_key_ids()
+            .into_iter()
+            .filter(|id| id != self.current_master_key_id())
+            .collect();
+        let mut report = RewrapReport::default();
+        if old_key_ids.is_empty() {
+            return Ok(report);
+        }
+
+        let mut tx = db_pool.begin().await?;
+        let (after, after_id) = cursor.dek.clone().unzip();
+        let rows: Vec<(String, String, Vec<u8>, chrono::NaiveDateTime)> = sqlx::query_as(
+            "SELECT log_id, master_key_id, wrapped_dek, created_at FROM encrypted_log_keys
+             WHERE shredded_at IS NULL AND master_key_id = ANY($1)
+               AND ($3::TIMESTAMP IS NULL OR (created_at, log_id) > ($3, $4))
+             ORDER BY created_at ASC, log_id ASC
+             LIMIT $2
+             FOR UPDATE SKIP LOCKED"
+        )
+        .bind(&old_key_ids)
+        .bind(batch_size)
+        .bind(after)
+        .bind(after_id)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut rewrapped = Vec::with_capacity(rows.len());
+        for (log_id, master_key_id, bytes, created_at) in rows {
+            cursor.dek = Some((created_at, log_id.clone()));
+            let old = WrappedDek::new(master_key_id, None, bytes);
+            let Some(new) = self.rewrap_key(&format!("DEK for {}", log_id), &old).await else {
+                report.failed += 1;
+                continue;
+            };
+            sqlx::query(
+                "UPDATE encrypted_log_keys
+                 SET wrapped_dek = $2, master_key_id = $3, rewrapped_at = CURRENT_TIMESTAMP
+                 WHERE log_id = $1"
+            )
+            .bind(&log_id)
+            .bind(&new.bytes)
+            .bind(&new.master_key_id)
+            .execute(&mut *tx)
+            .await?;
+            rewrapped.push((log_id, new));
+        }
+
+        let (after, after_id) = cursor.subject_key.unzip();
+        let subject_rows: Vec<(uuid::Uuid, String, String, Vec<u8>, chrono::NaiveDateTime)> = sqlx::query_as(
+            "SELECT id, user_id, master_key_id, wrapped_kek, created_at FROM data_subject_keys
+             WHERE destroyed_at IS NULL AND master_key_id = ANY($1)
+               AND ($3::TIMESTAMP IS NULL OR (created_at, id) > ($3, $4))
+             ORDER BY created_at ASC, id ASC
+             LIMIT $2
+             FOR UPDATE SKIP LOCKED"
+        )
+        .bind(&old_key_ids)
+        .bind(batch_size)
+        .bind(after)
+        .bind(after_id)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut rewrapped_subjects = Vec::with_capacity(subject_rows.len());
+        for (id, subject_id, master_key_id, bytes, created_at) in subject_rows {
+            cursor.subject_key = Some((created_at, id));
+            let old = WrappedDek::new(master_key_id, None, bytes);
+            let Some(new) = self.rewrap_key(&format!("data subject key for {}", subject_id), &old).await else {
+                report.failed += 1;
+                continue;
+            };
+            sqlx::query(
+                "UPDATE data_subject_keys
+                 SET wrapped_kek = $2, master_key_id = $3, rewrapped_at = CURRENT_TIMESTAMP
+                 WHERE id = $1"
+            )
+            .bind(id)
+            .bind(&new.bytes)
+            .bind(&new.master_key_id)
+            .execute(&mut *tx)
+            .await?;
+            rewrapped_subjects.push((subject_id, new));
+        }
+        tx.commit().await?;
+
+        // Refresh cached copies; keys this node never cached are loaded lazily
+        let mut keys = self.keys.lock().expect("Failed to acquire lock");
+        for (log_id, new) in rewrapped {
+            if let Some(cached) = keys.get_mut(&log_id) {
+                cached.master_key_id = new.master_key_id;
+                cached.bytes = new.bytes;
+            }
+            report.rewrapped += 1;
+        }
+        let mut subject_keys = self.subject_keys.lock().expect("Failed to acquire lock");
+        for (subject_id, new) in rewrapped_subjects {
+            if let Some(cached) = subject_keys.get_mut(&subject_id) {
+                *cached = new;
+            }
+            report.rewrapped += 1;
+        }
+
+        Ok(report)
+    }
+
+    /// Re-wrap all live DEKs under older master keys, batch by batch
+    /// 
+    /// Each row is visited once; rows that cannot be unwrapped are counted in
+    /// `failed` and left for the next run.
+    pub async fn rewrap_all(&self, db_pool: &PgPool, batch_size: i64) -> Result<RewrapReport, sqlx::Error> {
+        let mut total = RewrapReport::default();
+        let mut cursor = RewrapCursor::default();
+        loop {
+            let batch = self.rewrap_batch(db_pool, batch_size, &mut cursor).await?;
+            total.rewrapped += batch.rewrapped;
+            total.failed += batch.failed;
+            if batch.rewrapped + batch.failed == 0 {
+                return Ok(total);
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::key_provider::EnvKeyProvider;
 
-    #[test]
-    fn test_lifecycle() {
+    #[tokio::test]
+    async fn test_lifecycle() {
         // Set up test environment
         unsafe {
             std::env::set_var("VERIDION_MASTER_KEY", "test_master_key_32_bytes_long_123456");
@@ -214,18 +851,19 @@ mod tests {
         let payload = "Secret Data";
         
         // Log the event
-        let encrypted_log = store.log_event(payload);
+        let encrypted_log = store.log_event(payload).await
+            .expect("Failed to log event");
         
         // Read it back
-        let decrypted = store.read_event(&encrypted_log)
+        let decrypted = store.read_event(&encrypted_log).await
             .expect("Failed to read event");
         
         // Assert it matches
         assert_eq!(decrypted, payload);
     }
 
-    #[test]
-    fn test_shredding() {
+    #[tokio::test]
+    async fn test_shredding() {
         // Set up test environment
         unsafe {
             std::env::set_var("VERIDION_MASTER_KEY", "test_master_key_32_bytes_long_123456");
@@ -235,16 +873,123 @@ mod tests {
         let payload = "User Email";
         
         // Log the event
-        let encrypted_log = store.log_event(payload);
+        let encrypted_log = store.log_event(payload).await
+            .expect("Failed to log event");
         
         // Shred the key
         store.shred_key(&encrypted_log.log_id);
         
         // Attempt to read - should fail with GDPR_PURGED error
-        let result = store.read_event(&encrypted_log);
+        let result = store.read_event(&encrypted_log).await;
         
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), "GDPR_PURGED: Data key destroyed");
     }
-}
 
+    #[tokio::test]
+    async fn test_master_key_rotation() {
+        let old_store = VeridionKeyStore::with_provider(Arc::new(
+            EnvKeyProvider::with_master_keys("v1", "old_master_secret", &[]),
+        ));
+        let encrypted_log = old_store.log_event("Rotated Data").await.unwrap();
+        let wrapped = old_store.keys.lock().unwrap().get(&encrypted_log.log_id).cloned().unwrap();
+        assert_eq!(wrapped.master_key_id, "v1");
+
+        // New current key; the old version stays available for unwrapping
+        let new_store = VeridionKeyStore::with_provider(Arc::new(EnvKeyProvider::with_master_keys(
+            "v2",
+            "new_master_secret",
+            &[("v1".to_string(), "old_master_secret".to_string())],
+        )));
+        assert_eq!(new_store.current_master_key_id(), "v2");
+        new_store.keys.lock().unwrap().insert(encrypted_log.log_id.clone(), wrapped.clone());
+        assert_eq!(new_store.read_event(&encrypted_log).await.unwrap(), "Rotated Data");
+
+        // Re-wrapping moves the DEK to v2 without touching the ciphertext
+        let dek = new_store.unwrap_dek(&wrapped).await.unwrap();
+        let rewrapped = new_store.wrap_dek(&dek).await.unwrap();
+        assert_eq!(rewrapped.master_key_id, "v2");
+        new_store.keys.lock().unwrap().insert(encrypted_log.log_id.clone(), rewrapped);
+
+        assert!(new_store.retire_master_key("v1"));
+        assert!(!new_store.retire_master_key("v2"));
+        assert_eq!(new_store.read_event(&encrypted_log).await.unwrap(), "Rotated Data");
+    }
+
+    /// Needs PostgreSQL (TEST_DATABASE_URL or DATABASE_URL)
+    #[tokio::test]
+    #[ignore] // Ignore by default, run with: cargo test -- --ignored
+    async fn test_shredding_persisted() {
+        let db_pool = crate::test_helpers::create_test_db_pool().await;
+        let provider = || Arc::new(EnvKeyProvider::with_master_keys("v1", "persisted_master_secret", &[]));
+        let store = VeridionKeyStore::with_provider(provider());
+
+        let encrypted_log = store.log_event("User Email").await.unwrap();
+        store.persist_key(&db_pool, &encrypted_log.log_id).await.unwrap();
+        assert_eq!(store.read_event_persisted(&db_pool, &encrypted_log).await.unwrap(), "User Email");
+
+        // Another node loads the key lazily
+        let other_node = VeridionKeyStore::with_provider(provider());
+        assert_eq!(other_node.read_event_persisted(&db_pool, &encrypted_log).await.unwrap(), "User Email");
+
+        // Shredding on one node is seen by every node
+        store.shred_key_persisted(&db_pool, &encrypted_log.log_id).await.unwrap();
+        for node in [&store, &other_node] {
+            assert_eq!(
+                node.read_event_persisted(&db_pool, &encrypted_log).await.unwrap_err(),
+                "GDPR_PURGED: Data key destroyed"
+            );
+        }
+    }
+
+    /// Needs PostgreSQL (TEST_DATABASE_URL or DATABASE_URL)
+    #[tokio::test]
+    #[ignore] // Ignore by default, run with: cargo test -- --ignored
+    async fn test_rewrap_passes_keys_that_cannot_be_unwrapped() {
+        let db_pool = crate::test_helpers::create_test_db_pool().await;
+        let tag = uuid::Uuid::new_v4().simple().to_string();
+        let (old_key_id, new_key_id) = (format!("old-{}", tag), format!("new-{}", tag));
+
+        // Unreadable keys sort first: under the old key id, but not wrapped by it
+        for i in 0..3 {
+            sqlx::query(
+                "INSERT INTO encrypted_log_keys (log_id, master_key_id, wrapped_dek, created_at)
+                 VALUES ($1, $2, $3, TIMESTAMP '2000-01-01')"
+            )
+            .bind(format!("broken-{}-{}", tag, i))
+            .bind(&old_key_id)
+            .bind(vec![i as u8; 60])
+            .execute(&db_pool)
+            .await
+            .unwrap();
+        }
+        let old_store = VeridionKeyStore::with_provider(Arc::new(
+            EnvKeyProvider::with_master_keys(&old_key_id, "old_master_secret", &[]),
+        ));
+        let mut logs = Vec::new();
+        for payload in ["first", "second"] {
+            let log = old_store.log_event(payload).await.unwrap();
+            old_store.persist_key(&db_pool, &log.log_id).await.unwrap();
+            logs.push((log, payload));
+        }
+
+        let new_store = VeridionKeyStore::with_provider(Arc::new(EnvKeyProvider::with_master_keys(
+            &new_key_id,
+            "new_master_secret",
+            &[(old_key_id.clone(), "old_master_secret".to_string())],
+        )));
+        let report = new_store.rewrap_all(&db_pool, 2).await.unwrap();
+        assert_eq!(report.rewrapped, 2);
+        assert_eq!(report.failed, 3);
+
+        for (log, payload) in &logs {
+            let master_key_id: String = sqlx::query_scalar("SELECT master_key_id FROM encrypted_log_keys WHERE log_id = $1")
+                .bind(&log.log_id)
+                .fetch_one(&db_pool)
+                .await
+                .unwrap();
+            assert_eq!(master_key_id, new_key_id);
+            assert_eq!(new_store.read_event_persisted(&db_pool, log).await.unwrap(), *payload);
+        }
+    }
+}
@@ -0,0 +1,275 @@
+// Erasure Certificates (GDPR Article 17)
+// Signed proof that the keys protecting a data subject's records were destroyed.
+// The certificate hash is sealed with the default seal provider, so it can be
+// shown to the data subject or a regulator without revealing any key.
+
+use crate::core::privacy_bridge::hash_payload;
+use crate::core::seal_provider::SealService;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Audit events recorded when a certificate is issued
+pub const ERASURE_AUDIT_EVENTS: [&str; 2] = ["record_erased", "data_subject_erased"];
+
+/// Signed erasure certificate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErasureCertificate {
+    pub certificate_id: String,
+    /// RECORD (single shred) or DATA_SUBJECT (all records of one subject)
+    pub scope: String,
+    pub subject_id: Option<String>,
+    pub seal_ids: Vec<String>,
+    pub tx_ids: Vec<String>,
+    /// SHA-256 fingerprints of the destroyed keys
+    pub key_fingerprints: Vec<String>,
+    /// RFC 3339, second precision
+    pub erased_at: String,
+    /// Username of the operator who requested the erasure
+    pub operator: String,
+    pub operator_id: Option<String>,
+    /// SHA-256 over all fields above
+    pub certificate_hash: String,
+    /// Seal returned by the seal provider for `certificate_hash`
+    pub signature: String,
+}
+
+impl ErasureCertificate {
+    /// Create an unsigned certificate for keys destroyed just now
+    pub fn new(
+        scope: &str,
+        subject_id: Option<String>,
+        seal_ids: Vec<String>,
+        tx_ids: Vec<String>,
+        key_fingerprints: Vec<String>,
+        operator: &str,
+        operator_id: Option<String>,
+    ) -> Self {
+        let mut certificate = Self {
+            certificate_id: format!("ERASE-{}", Uuid::new_v4()),
+            scope: scope.to_string(),
+            subject_id,
+            seal_ids,
+            tx_ids,
+            key_fingerprints,
+            erased_at: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+            operator: operator.to_string(),
+            operator_id,
+            certificate_hash: String::new(),
+            signature: String::new(),
+        };
+        certificate.certificate_hash = certificate.compute_hash();
+        certificate
+    }
+
+    /// Hash of the certified content
+    ///
+    /// serde_json objects serialize with sorted keys, so the encoding is stable.
+    pub fn compute_hash(&self) -> String {
+        let content = serde_json::json!({
+            "certificate_id": self.certificate_id,
+            "scope": self.scope,
+            "subject_id": self.subject_id,
+            "seal_ids": self.seal_ids,
+            "tx_ids": self.tx_ids,
+            "key_fingerprints": self.key_fingerprints,
+            "erased_at": self.erased_at,
+            "operator": self.operator,
+            "operator_id": self.operator_id,
+        });
+        hash_payload(&content.to_string())
+    }
+
+    /// Seal the certificate hash with the default seal provider
+    ///
+    /// Like log_action, a failed seal is queued in the seal outbox rather than
+    /// failing the erasure (the keys are already gone at this point); the
+    /// certificate keeps the placeholder, which `verify` resolves once sealed.
+    pub async fn seal(&mut self, seals: &SealService, db_pool: &PgPool) {
+        self.signature = seals.seal_or_defer(db_pool, None, &self.certificate_hash).await;
+    }
+
+    /// Persist the certificate in `erasure_certificates`
+    pub async fn store(&self, db_pool: &PgPool) -> Result<(), sqlx::Error> {
+        let erased_at = DateTime::parse_from_rfc3339(&self.erased_at)
+            .map(|t| t.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now());
+        sqlx::query(
+            "INSERT INTO erasure_certificates
+             (certificate_id, scope, subject_id, seal_ids, tx_ids, key_fingerprints,
+              erased_at, operator, operator_id, certificate_hash, signature)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)"
+        )
+        .bind(&self.certificate_id)
+        .bind(&self.scope)
+        .bind(&self.subject_id)
+        .bind(&self.seal_ids)
+        .bind(&self.tx_ids)
+        .bind(&self.key_fingerprints)
+        .bind(erased_at)
+        .bind(&self.operator)
+        .bind(&self.operator_id)
+        .bind(&self.certificate_hash)
+        .bind(&self.signature)
+        .execute(db_pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Check the certificate against what this deployment recorded
+    ///
+    /// The certificate must hash to its own `certificate_hash`, be sealed under
+    /// `signature`, match the stored copy and the audit event written when it
+    /// was issued, and every key it names must still be destroyed.
+    pub async fn verify(&self, db_pool: &PgPool, seals: &SealService) -> Result<CertificateVerification, sqlx::Error> {
+        let hash_valid = self.compute_hash() == self.certificate_hash;
+        let sealed = match seals.verify(db_pool, &self.signature).await? {
+            Some(seal) => seal.seals_hash(&self.certificate_hash),
+            None => false,
+        };
+
+        let stored: Option<(String, String)> = sqlx::query_as(
+            "SELECT certificate_hash, signature FROM erasure_certificates WHERE certificate_id = $1"
+        )
+        .bind(&self.certificate_id)
+        .fetch_optional(db_pool)
+        .await?;
+        let matches_stored = stored
+            .map(|(hash, signature)| hash == self.certificate_hash && signature == self.signature)
+            .unwrap_or(false);
+
+        let audit_logged: bool = sqlx::query_scalar(
+            "SELECT EXISTS (
+                 SELECT 1 FROM security_audit_logs
+                 WHERE event_type = ANY($1)
+                   AND success = true
+                   AND metadata->>'certificate_id' = $2
+                   AND metadata->>'certificate_hash' = $3
+             )"
+        )
+        .bind(&ERASURE_AUDIT_EVENTS[..])
+        .bind(&self.certificate_id)
+        .bind(&self.certificate_hash)
+        .fetch_one(db_pool)
+        .await?;
+
+        // Every certified DEK must exist and be shredded, and no live subject key
+        // may carry one of the certified fingerprints
+        let (shredded_dek_count, live_subject_keys): (i64, i64) = sqlx::query_as(
+            "SELECT
+                 (SELECT COUNT(*) FROM encrypted_log_keys
+                  WHERE log_id = ANY($1) AND shredded_at IS NOT NULL AND length(wrapped_dek) = 0),
+                 (SELECT COUNT(*) FROM data_subject_keys
+                  WHERE key_fingerprint = ANY($2) AND destroyed_at IS NULL)"
+        )
+        .bind(&self.tx_ids)
+        .bind(&self.key_fingerprints)
+        .fetch_one(db_pool)
+        .await?;
+        let keys_destroyed = shredded_dek_count == self.tx_ids.len() as i64 && live_subject_keys == 0;
+
+        Ok(CertificateVerification {
+            certificate_id: self.certificate_id.clone(),
+            valid: hash_valid && sealed && matches_stored && audit_logged && keys_destroyed,
+            hash_valid,
+            sealed,
+            matches_stored,
+            audit_logged,
+            keys_destroyed,
+        })
+    }
+}
+
+/// Result of checking a certificate against the audit trail
+#[derive(Debug, Clone, Serialize)]
+pub struct CertificateVerification {
+    pub certificate_id: String,
+    /// All checks below passed
+    pub valid: bool,
+    /// Content hashes to `certificate_hash`
+    pub hash_valid: bool,
+    /// A stored seal token under `signature` covers `certificate_hash`, with a
+    /// valid MAC for LOCAL seals (`GET /seal_tokens` shows the other providers)
+    pub sealed: bool,
+    /// Hash and signature equal the copy stored at issue time
+    pub matches_stored: bool,
+    /// An erasure audit event references this certificate and hash
+    pub audit_logged: bool,
+    /// Every certified key is still destroyed
+    pub keys_destroyed: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_certificate_hash_covers_content() {
+        let certificate = ErasureCertificate::new(
+            "DATA_SUBJECT",
+            Some("user-123".to_string()),
+            vec!["SEAL-1".to_string()],
+            vec!["log_abc".to_string()],
+            vec!["f00d".to_string()],
+            "dpo",
+            None,
+        );
+        assert_eq!(certificate.certificate_hash, certificate.compute_hash());
+
+        let mut tampered = certificate.clone();
+        tampered.tx_ids.push("log_def".to_string());
+        assert_ne!(tampered.compute_hash(), certificate.certificate_hash);
+    }
+
+    /// Needs PostgreSQL (TEST_DATABASE_URL or DATABASE_URL)
+    #[tokio::test]
+    #[ignore] // Ignore by default, run with: cargo test -- --ignored
+    async fn test_certificate_seal_must_cover_its_hash() {
+        use crate::core::privacy_bridge::SignicatClient;
+        use crate::core::seal_provider::{LocalSealProvider, SealProvider};
+        use std::sync::Arc;
+
+        let db_pool = &crate::test_helpers::create_test_db_pool().await;
+
+        /// Seal a hash with the LOCAL provider and store the token as SealService::seal does
+        async fn store_seal(db_pool: &PgPool, local: &LocalSealProvider, hash: &str) -> String {
+            let token = local.seal(hash).await.unwrap();
+            sqlx::query(
+                "INSERT INTO seal_tokens (seal_id, provider, sealed_hash, token, sealed_at)
+                 VALUES ($1, $2, $3, $4, $5)"
+            )
+            .bind(&token.seal_id)
+            .bind(token.provider)
+            .bind(&token.sealed_hash)
+            .bind(&token.token)
+            .bind(token.sealed_at)
+            .execute(db_pool)
+            .await
+            .unwrap();
+            token.seal_id
+        }
+
+        let key = b"erasure certificate test key";
+        let local = LocalSealProvider::new("test", key);
+        let seals = SealService::from_env(Arc::new(SignicatClient::new()))
+            .with_local_provider(LocalSealProvider::new("test", key));
+
+        let mut certificate = ErasureCertificate::new(
+            "RECORD", None, vec![], vec![], vec![], "dpo", None,
+        );
+        certificate.signature = store_seal(db_pool, &local, &certificate.certificate_hash).await;
+        assert!(certificate.verify(db_pool, &seals).await.unwrap().sealed);
+
+        // A genuine seal of another hash, or a made-up seal ID, does not seal the certificate
+        certificate.signature = store_seal(db_pool, &local, &hash_payload("another certificate")).await;
+        assert!(!certificate.verify(db_pool, &seals).await.unwrap().sealed);
+        certificate.signature = "LOCAL_SEAL_made_up".to_string();
+        assert!(!certificate.verify(db_pool, &seals).await.unwrap().sealed);
+
+        // A LOCAL token of the right hash under another key has a forged MAC
+        let forger = LocalSealProvider::new("test", b"not the deployment's key");
+        certificate.signature = store_seal(db_pool, &forger, &certificate.certificate_hash).await;
+        assert!(!certificate.verify(db_pool, &seals).await.unwrap().sealed);
+    }
+}
@@ -0,0 +1,350 @@
+// Jurisdiction Policies - per-tenant Sovereign Lock rules
+// Stored as JURISDICTION rows in policy_versions, so every change is a new
+// version that can be activated or rolled back like any other policy.
+
+use crate::core::sovereign_lock::{SovereigntyResolver, UNKNOWN_COUNTRY};
+use crate::modules::gdpr::article_44_49_international_transfers::GDPRArticle4449Module;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// policy_versions.policy_type of jurisdiction policies
+pub const JURISDICTION_POLICY_TYPE: &str = "JURISDICTION";
+
+/// Jurisdiction rules for one tenant (or globally when company_id is NULL)
+///
+/// An empty policy allows exactly what the `SovereigntyResolver` allows (EU/EEA).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct JurisdictionPolicy {
+    /// Extra countries data may be sent to (ISO 3166-1 alpha-2)
+    #[serde(default)]
+    pub allowed_countries: Vec<String>,
+    /// Countries that are always blocked, even inside the EU/EEA
+    #[serde(default)]
+    pub denied_countries: Vec<String>,
+    /// Allow countries with an EU adequacy decision (GDPR Article 45)
+    #[serde(default)]
+    pub allow_adequacy_countries: bool,
+    /// Cloud region codes mapped to countries, e.g. `eu-central-1` -> `DE`
+    #[serde(default)]
+    pub region_mappings: HashMap<String, String>,
+    /// Approved Standard Contractual Clauses exceptions (GDPR Article 46)
+    #[serde(default)]
+    pub scc_exceptions: Vec<SccException>,
+}
+
+/// Transfer to a non-adequate country approved under SCCs
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SccException {
+    #[schema(example = "US")]
+    pub country: String,
+    /// Restrict the exception to one agent; applies to all agents when omitted
+    #[serde(default)]
+    pub agent_id: Option<String>,
+    /// SCC document or transfer impact assessment reference
+    #[schema(example = "SCC-2024-017")]
+    pub reference: String,
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Outcome of evaluating a target region against a jurisdiction policy
+#[derive(Debug, Clone, Serialize)]
+pub struct JurisdictionDecision {
+    pub allowed: bool,
+    pub country: String,
+    /// EU_EEA, ALLOW_LIST, DENY_LIST, ADEQUACY_DECISION, SCC_EXCEPTION, NOT_PERMITTED or UNRESOLVED
+    pub basis: String,
+    /// SCC reference when `basis` is SCC_EXCEPTION
+    pub scc_reference: Option<String>,
+}
+
+/// Active jurisdiction policy version
+#[derive(Debug, Clone)]
+pub struct ActiveJurisdictionPolicy {
+    pub policy_version_id: Uuid,
+    pub version_number: i32,
+    pub policy: JurisdictionPolicy,
+}
+
+impl JurisdictionPolicy {
+    /// Check that all country codes and mappings are well-formed
+    pub fn validate(&self) -> Result<(), String> {
+        let is_code = |c: &str| c.len() == 2 && c.chars().all(|ch| ch.is_ascii_alphabetic());
+        for country in self.allowed_countries.iter().chain(self.denied_countries.iter()) {
+            if !is_code(country) {
+                return Err(format!("'{}' is not an ISO 3166-1 alpha-2 country code", country));
+            }
+        }
+        for (region, country) in &self.region_mappings {
+            if region.trim().is_empty() || !is_code(country) {
+                return Err(format!("Invalid region mapping '{}' -> '{}'", region, country));
+            }
+        }
+        for exception in &self.scc_exceptions {
+            if !is_code(&exception.country) {
+                return Err(format!("'{}' is not an ISO 3166-1 alpha-2 country code", exception.country));
+            }
+            if exception.reference.trim().is_empty() {
+                return Err("SCC exceptions need a reference".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// Decide whether `agent_id` may send data to `target_region`
+    ///
+    /// Order: deny list, allow list, EU/EEA (resolver verdict), adequacy
+    /// decisions, then SCC exceptions. Everything else is blocked.
+    pub fn evaluate(
+        &self,
+        resolver: &SovereigntyResolver,
+        target_region: &str,
+        agent_id: &str,
+        now: DateTime<Utc>,
+    ) -> JurisdictionDecision {
+        let mapped = self
+            .region_mappings
+            .iter()
+            .find(|(region, _)| region.trim().eq_ignore_ascii_case(target_region.trim()))
+            .map(|(_, country)| country.to_uppercase());
+        let (country, resolver_allowed) = match mapped {
+            Some(country) => {
+                let allowed = resolver.resolve_region(&country).allowed;
+                (country, allowed)
+            }
+            None => {
+                let verdict = resolver.resolve_region(target_region);
+                (verdict.country, verdict.allowed)
+            }
+        };
+
+        let decision = |allowed: bool, basis: &str| JurisdictionDecision {
+            allowed,
+            country: country.clone(),
+            basis: basis.to_string(),
+            scc_reference: None,
+        };
+        let listed = |list: &[String]| list.iter().any(|c| c.eq_ignore_ascii_case(&country));
+
+        if listed(&self.denied_countries) {
+            return decision(false, "DENY_LIST");
+        }
+        if listed(&self.allowed_countries) {
+            return decision(true, "ALLOW_LIST");
+        }
+        if resolver_allowed {
+            return decision(true, "EU_EEA");
+        }
+        if country == UNKNOWN_COUNTRY {
+            return decision(false, "UNRESOLVED");
+        }
+        if self.allow_adequacy_countries && GDPRArticle4449Module::has_adequacy_decision(&country) {
+            return decision(true, "ADEQUACY_DECISION");
+        }
+        let scc = self.scc_exceptions.iter().find(|e| {
+            e.country.eq_ignore_ascii_case(&country)
+                && e.agent_id.as_deref().is_none_or(|a| a == agent_id)
+                && e.expires_at.is_none_or(|exp| exp > now)
+        });
+        if let Some(exception) = scc {
+            return JurisdictionDecision {
+                scc_reference: Some(exception.reference.clone()),
+                ..decision(true, "SCC_EXCEPTION")
+            };
+        }
+        decision(false, "NOT_PERMITTED")
+    }
+
+    /// Load the active policy for a tenant, falling back to the global policy
+    pub async fn load_active(
+        db_pool: &PgPool,
+        company_id: Option<Uuid>,
+    ) -> Result<Option<ActiveJurisdictionPolicy>, sqlx::Error> {
+        let row: Option<(Uuid, i32, serde_json::Value)> = sqlx::query_as(
+            "SELECT id, version_number, policy_config
+             FROM policy_versions
+             WHERE policy_type = $1 AND is_active = true
+               AND (company_id = $2 OR company_id IS NULL)
+             ORDER BY company_id IS NULL, version_number DESC
+             LIMIT 1"
+        )
+        .bind(JURISDICTION_POLICY_TYPE)
+        .bind(company_id)
+        .fetch_optional(db_pool)
+        .await?;
+
+        match row {
+            Some((policy_version_id, version_number, config)) => {
+                let policy = serde_json::from_value(config)
+                    .map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
+                Ok(Some(ActiveJurisdictionPolicy { policy_version_id, version_number, policy }))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Store the policy as the next version for the tenant and activate it
+    ///
+    /// Returns the new policy_versions id and version number.
+    pub async fn publish(
+        &self,
+        db_pool: &PgPool,
+        company_id: Option<Uuid>,
+        policy_name: &str,
+        created_by: &str,
+    ) -> Result<(Uuid, i32), sqlx::Error> {
+        let config = serde_json::to_value(self)
+            .map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
+        let mut tx = db_pool.begin().await?;
+
+        // Serialize publishers of the same tenant
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtext($1))")
+            .bind(format!("{}:{}", JURISDICTION_POLICY_TYPE, company_id.map(|c| c.to_string()).unwrap_or_default()))
+            .execute(&mut *tx)
+            .await?;
+
+        let version_number: i32 = sqlx::query_scalar(
+            "SELECT COALESCE(MAX(version_number), 0) + 1 FROM policy_versions
+             WHERE policy_type = $1 AND company_id IS NOT DISTINCT FROM $2"
+        )
+        .bind(JURISDICTION_POLICY_TYPE)
+        .bind(company_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            "UPDATE policy_versions SET is_active = false, deactivated_at = CURRENT_TIMESTAMP
+             WHERE policy_type = $1 AND company_id IS NOT DISTINCT FROM $2 AND is_active = true"
+        )
+        .bind(JURISDICTION_POLICY_TYPE)
+        .bind(company_id)
+        .execute(&mut *tx)
+        .await?;
+
+        let id: Uuid = sqlx::query_scalar(
+            "INSERT INTO policy_versions (
+                policy_type, policy_name, policy_config, version_number,
+                is_active, company_id, created_by, activated_at
+            ) VALUES ($1, $2, $3, $4, true, $5, $6, CURRENT_TIMESTAMP)
+            RETURNING id"
+        )
+        .bind(JURISDICTION_POLICY_TYPE)
+        .bind(policy_name)
+        .bind(config)
+        .bind(version_number)
+        .bind(company_id)
+        .bind(created_by)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok((id, version_number))
+    }
+
+    /// Re-activate an earlier version of a tenant's policy
+    ///
+    /// Returns false if the id is not a jurisdiction policy.
+    pub async fn activate_version(db_pool: &PgPool, policy_version_id: Uuid) -> Result<bool, sqlx::Error> {
+        let mut tx = db_pool.begin().await?;
+
+        let company_id: Option<Option<Uuid>> = sqlx::query_scalar(
+            "SELECT company_id FROM policy_versions WHERE id = $1 AND policy_type = $2 FOR UPDATE"
+        )
+        .bind(policy_version_id)
+        .bind(JURISDICTION_POLICY_TYPE)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let company_id = match company_id {
+            Some(c) => c,
+            None => return Ok(false),
+        };
+
+        sqlx::query(
+            "UPDATE policy_versions SET is_active = false, deactivated_at = CURRENT_TIMESTAMP
+             WHERE policy_type = $1 AND company_id IS NOT DISTINCT FROM $2 AND is_active = true AND id <> $3"
+        )
+        .bind(JURISDICTION_POLICY_TYPE)
+        .bind(company_id)
+        .bind(policy_version_id)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            "UPDATE policy_versions SET is_active = true, activated_at = CURRENT_TIMESTAMP, deactivated_at = NULL
+             WHERE id = $1"
+        )
+        .bind(policy_version_id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluate(policy: &JurisdictionPolicy, region: &str) -> JurisdictionDecision {
+        policy.evaluate(&SovereigntyResolver::new(), region, "agent-1", Utc::now())
+    }
+
+    #[test]
+    fn test_default_policy_is_eu_eea_only() {
+        let policy = JurisdictionPolicy::default();
+        assert_eq!(evaluate(&policy, "DE").basis, "EU_EEA");
+        assert!(!evaluate(&policy, "JP").allowed);
+        assert_eq!(evaluate(&policy, "somewhere").basis, "UNRESOLVED");
+    }
+
+    #[test]
+    fn test_lists_adequacy_and_region_mappings() {
+        let policy = JurisdictionPolicy {
+            allowed_countries: vec!["CH".to_string()],
+            denied_countries: vec!["HU".to_string()],
+            allow_adequacy_countries: true,
+            region_mappings: HashMap::from([("my-dc-1".to_string(), "JP".to_string())]),
+            scc_exceptions: Vec::new(),
+        };
+        assert!(policy.validate().is_ok());
+
+        assert_eq!(evaluate(&policy, "HU").basis, "DENY_LIST");
+        assert_eq!(evaluate(&policy, "CH").basis, "ALLOW_LIST");
+        let decision = evaluate(&policy, "my-dc-1");
+        assert_eq!((decision.country.as_str(), decision.basis.as_str()), ("JP", "ADEQUACY_DECISION"));
+        assert_eq!(evaluate(&policy, "us-east-1").basis, "NOT_PERMITTED");
+    }
+
+    #[test]
+    fn test_scc_exceptions() {
+        let policy = JurisdictionPolicy {
+            scc_exceptions: vec![
+                SccException {
+                    country: "US".to_string(),
+                    agent_id: Some("agent-1".to_string()),
+                    reference: "SCC-1".to_string(),
+                    expires_at: None,
+                },
+                SccException {
+                    country: "IN".to_string(),
+                    agent_id: None,
+                    reference: "SCC-2".to_string(),
+                    expires_at: Some(Utc::now() - chrono::Duration::days(1)),
+                },
+            ],
+            ..Default::default()
+        };
+
+        let decision = evaluate(&policy, "US");
+        assert!(decision.allowed);
+        assert_eq!(decision.scc_reference.as_deref(), Some("SCC-1"));
+        assert!(!policy.evaluate(&SovereigntyResolver::new(), "US", "agent-2", Utc::now()).allowed);
+        // Expired exception
+        assert!(!evaluate(&policy, "IN").allowed);
+    }
+}
@@ -0,0 +1,775 @@
+// Key Providers for the Crypto-Shredder
+// Master keys never leave the provider: VeridionKeyStore hands DEKs to it for
+// wrapping/unwrapping and only ever stores the wrapped result.
+
+use aes_gcm::{
+    aead::{Aead, KeyInit},
+    Aes256Gcm, Nonce,
+};
+use async_trait::async_trait;
+use base64::Engine;
+use hkdf::Hkdf;
+use rand::RngCore;
+use rand::thread_rng;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Key id recorded for DEKs wrapped before master keys were versioned.
+/// That key is the raw secret, zero-padded or truncated to 32 bytes (no KDF).
+pub const LEGACY_MASTER_KEY_ID: &str = "legacy";
+
+/// HKDF salt for master key derivation
+const MASTER_KEY_KDF_SALT: &[u8] = b"veridion-nexus/crypto-shredder/master-key";
+
+/// Backend holding the master keys that wrap Data Encryption Keys (DEKs)
+#[async_trait]
+pub trait KeyProvider: Send + Sync {
+    /// Short backend name (for logs)
+    fn name(&self) -> &'static str;
+
+    /// Key version used to wrap new DEKs
+    fn current_key_id(&self) -> &str;
+
+    /// All key versions this provider can unwrap with
+    fn key_ids(&self) -> Vec<String>;
+
+    /// How the key version was obtained (stored in `master_key_versions.kdf`)
+    fn kdf(&self, key_id: &str) -> &'static str;
+
+    /// Wrap (encrypt) a DEK with the given key version
+    async fn wrap(&self, key_id: &str, dek: &[u8]) -> Result<Vec<u8>, String>;
+
+    /// Unwrap (decrypt) a DEK with the given key version
+    async fn unwrap(&self, key_id: &str, wrapped: &[u8]) -> Result<Vec<u8>, String>;
+
+    /// Stop using a key version. Returns `true` if the version was known.
+    /// The current key version can never be retired.
+    fn retire(&self, key_id: &str) -> bool;
+}
+
+/// Build the key provider selected by `VERIDION_KEY_PROVIDER`
+///
+/// * `env` (default) - master keys from `VERIDION_MASTER_KEY` (see [`EnvKeyProvider`])
+/// * `file` - local keyring file at `VERIDION_KEYRING_PATH`
+/// * `http` - remote KMS at `VERIDION_KMS_URL`
+///
+/// For `file` and `http`, keys from `VERIDION_MASTER_KEY` (if still set) stay
+/// available for unwrapping only, so the re-wrap job can move existing DEKs
+/// into the new backend. There is no `pkcs11` value: a PKCS#11 HSM needs the
+/// vendor's session binding and is wired in with `VeridionKeyStore::with_provider`
+/// and a [`Pkcs11KeyProvider`].
+///
+/// # Panics
+///
+/// Panics if the selected provider is misconfigured
+pub fn provider_from_env() -> Arc<dyn KeyProvider> {
+    let provider = std::env::var("VERIDION_KEY_PROVIDER").unwrap_or_else(|_| "env".to_string());
+    let primary: Arc<dyn KeyProvider> = match provider.to_lowercase().as_str() {
+        "env" => return Arc::new(EnvKeyProvider::from_env()),
+        "file" => {
+            let path = std::env::var("VERIDION_KEYRING_PATH")
+                .expect("VERIDION_KEYRING_PATH must be set when VERIDION_KEY_PROVIDER=file");
+            Arc::new(FileKeyringProvider::load(&path).unwrap_or_else(|e| panic!("{}", e)))
+        }
+        "http" => Arc::new(HttpKmsProvider::from_env()),
+        other => panic!("Unknown VERIDION_KEY_PROVIDER '{}' (expected env, file or http)", other),
+    };
+
+    if std::env::var("VERIDION_MASTER_KEY").is_ok() {
+        Arc::new(FallbackKeyProvider::new(primary, Arc::new(EnvKeyProvider::from_env())))
+    } else {
+        primary
+    }
+}
+
+// ========== LOCAL AES-256-GCM WRAPPING ==========
+
+/// Wrap a DEK with a local 256-bit key; the 12-byte nonce is prepended
+pub(crate) fn aes_wrap(key: &[u8], dek: &[u8]) -> Result<Vec<u8>, String> {
+    let cipher = Aes256Gcm::new_from_slice(key)
+        .map_err(|_| "Invalid master key length".to_string())?;
+    let mut nonce_bytes = vec![0u8; 12];
+    thread_rng().fill_bytes(&mut nonce_bytes);
+    let wrapped = cipher
+        .encrypt(Nonce::from_slice(&nonce_bytes), dek)
+        .map_err(|_| "Failed to wrap DEK".to_string())?;
+    let mut out = nonce_bytes;
+    out.extend_from_slice(&wrapped);
+    Ok(out)
+}
+
+/// Reverse of [`aes_wrap`]
+pub(crate) fn aes_unwrap(key: &[u8], wrapped: &[u8]) -> Result<Vec<u8>, String> {
+    if wrapped.len() < 12 {
+        return Err("Failed to unwrap DEK".to_string());
+    }
+    let cipher = Aes256Gcm::new_from_slice(key)
+        .map_err(|_| "Invalid master key length".to_string())?;
+    cipher
+        .decrypt(Nonce::from_slice(&wrapped[..12]), &wrapped[12..])
+        .map_err(|_| "Failed to unwrap DEK".to_string())
+}
+
+/// Derive a 256-bit master key with HKDF-SHA256.
+/// The key id is bound into the derivation, so each version gets a distinct key.
+fn derive_master_key(secret: &str, key_id: &str) -> Vec<u8> {
+    let hk = Hkdf::<Sha256>::new(Some(MASTER_KEY_KDF_SALT), secret.as_bytes());
+    let mut master_key = vec![0u8; 32];
+    hk.expand(key_id.as_bytes(), &mut master_key)
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+    master_key
+}
+
+/// Pre-rotation master key: raw secret bytes, zero-padded or truncated to 32 bytes
+fn legacy_master_key(secret: &str) -> Vec<u8> {
+    let mut master_key = secret.as_bytes().to_vec();
+    master_key.resize(32, 0);
+    master_key
+}
+
+// ========== ENVIRONMENT KEYS ==========
+
+/// Master keys derived from secrets in the environment
+pub struct EnvKeyProvider {
+    /// key_id -> 256-bit master key
+    keys: Mutex<HashMap<String, Vec<u8>>>,
+    current_key_id: String,
+}
+
+impl EnvKeyProvider {
+    /// Load master keys from the environment
+    ///
+    /// * `VERIDION_MASTER_KEY` - secret for the current master key version
+    /// * `VERIDION_MASTER_KEY_ID` - id of the current version (default `v1`)
+    /// * `VERIDION_PREVIOUS_MASTER_KEYS` - older versions still needed for unwrapping,
+    ///   as comma-separated `key_id=secret` pairs. A `legacy=secret` entry supplies the
+    ///   pre-rotation key; otherwise it is taken from `VERIDION_MASTER_KEY`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if VERIDION_MASTER_KEY is not set in the environment, or if the
+    /// previous keys are malformed
+    pub fn from_env() -> Self {
+        let master_key_str = std::env::var("VERIDION_MASTER_KEY")
+            .expect("VERIDION_MASTER_KEY must be set in environment");
+        let current_key_id = std::env::var("VERIDION_MASTER_KEY_ID")
+            .unwrap_or_else(|_| "v1".to_string());
+
+        let mut previous = Vec::new();
+        if let Ok(value) = std::env::var("VERIDION_PREVIOUS_MASTER_KEYS") {
+            for entry in value.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+                let (key_id, secret) = entry
+                    .split_once('=')
+                    .expect("VERIDION_PREVIOUS_MASTER_KEYS entries must be key_id=secret");
+                previous.push((key_id.to_string(), secret.to_string()));
+            }
+        }
+
+        Self::with_master_keys(&current_key_id, &master_key_str, &previous)
+    }
+
+    /// Build from explicit master key secrets
+    pub fn with_master_keys(current_key_id: &str, secret: &str, previous: &[(String, String)]) -> Self {
+        if current_key_id == LEGACY_MASTER_KEY_ID {
+            panic!("VERIDION_MASTER_KEY_ID must not be '{}'", LEGACY_MASTER_KEY_ID);
+        }
+
+        let mut keys = HashMap::new();
+        for (key_id, previous_secret) in previous {
+            let master_key = if key_id == LEGACY_MASTER_KEY_ID {
+                legacy_master_key(previous_secret)
+            } else {
+                derive_master_key(previous_secret, key_id)
+            };
+            keys.insert(key_id.clone(), master_key);
+        }
+        keys.entry(LEGACY_MASTER_KEY_ID.to_string())
+            .or_insert_with(|| legacy_master_key(secret));
+        keys.insert(current_key_id.to_string(), derive_master_key(secret, current_key_id));
+
+        Self {
+            keys: Mutex::new(keys),
+            current_key_id: current_key_id.to_string(),
+        }
+    }
+
+    fn key(&self, key_id: &str) -> Result<Vec<u8>, String> {
+        self.keys
+            .lock()
+            .expect("Failed to acquire lock")
+            .get(key_id)
+            .cloned()
+            .ok_or_else(|| format!("Master key version '{}' is not configured", key_id))
+    }
+}
+
+#[async_trait]
+impl KeyProvider for EnvKeyProvider {
+    fn name(&self) -> &'static str {
+        "env"
+    }
+
+    fn current_key_id(&self) -> &str {
+        &self.current_key_id
+    }
+
+    fn key_ids(&self) -> Vec<String> {
+        self.keys.lock().expect("Failed to acquire lock").keys().cloned().collect()
+    }
+
+    fn kdf(&self, key_id: &str) -> &'static str {
+        if key_id == LEGACY_MASTER_KEY_ID { "RAW_PADDED" } else { "HKDF-SHA256" }
+    }
+
+    async fn wrap(&self, key_id: &str, dek: &[u8]) -> Result<Vec<u8>, String> {
+        aes_wrap(&self.key(key_id)?, dek)
+    }
+
+    async fn unwrap(&self, key_id: &str, wrapped: &[u8]) -> Result<Vec<u8>, String> {
+        aes_unwrap(&self.key(key_id)?, wrapped)
+    }
+
+    fn retire(&self, key_id: &str) -> bool {
+        key_id != self.current_key_id
+            && self.keys.lock().expect("Failed to acquire lock").remove(key_id).is_some()
+    }
+}
+
+// ========== LOCAL FILE KEYRING ==========
+
+/// On-disk keyring format
+///
+/// ```json
+/// { "current_key_id": "k2", "keys": { "k1": "<base64 32 bytes>", "k2": "<base64 32 bytes>" } }
+/// ```
+#[derive(Debug, Deserialize)]
+struct KeyringFile {
+    current_key_id: String,
+    keys: HashMap<String, String>,
+}
+
+/// Master keys read from a local keyring file (e.g. mounted from a secrets manager)
+pub struct FileKeyringProvider {
+    keys: Mutex<HashMap<String, Vec<u8>>>,
+    current_key_id: String,
+}
+
+impl FileKeyringProvider {
+    /// Load the keyring file at `path`
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read keyring {}: {}", path, e))?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            if let Ok(metadata) = std::fs::metadata(path) {
+                if metadata.permissions().mode() & 0o077 != 0 {
+                    log::warn!("Keyring {} is readable by group/others; restrict it to 0600", path);
+                }
+            }
+        }
+
+        Self::from_json(&content)
+    }
+
+    /// Parse a keyring from its JSON representation
+    pub fn from_json(content: &str) -> Result<Self, String> {
+        let file: KeyringFile = serde_json::from_str(content)
+            .map_err(|e| format!("Invalid keyring: {}", e))?;
+
+        let mut keys = HashMap::new();
+        for (key_id, encoded) in file.keys {
+            let key = base64::engine::general_purpose::STANDARD
+                .decode(encoded.trim())
+                .map_err(|e| format!("Invalid base64 for key '{}': {}", key_id, e))?;
+            if key.len() != 32 {
+                return Err(format!("Key '{}' must be 32 bytes, got {}", key_id, key.len()));
+            }
+            keys.insert(key_id, key);
+        }
+        if !keys.contains_key(&file.current_key_id) {
+            return Err(format!("Keyring has no key for current_key_id '{}'", file.current_key_id));
+        }
+
+        Ok(Self {
+            keys: Mutex::new(keys),
+            current_key_id: file.current_key_id,
+        })
+    }
+
+    fn key(&self, key_id: &str) -> Result<Vec<u8>, String> {
+        self.keys
+            .lock()
+            .expect("Failed to acquire lock")
+            .get(key_id)
+            .cloned()
+            .ok_or_else(|| format!("Key '{}' is not in the keyring", key_id))
+    }
+}
+
+#[async_trait]
+impl KeyProvider for FileKeyringProvider {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    fn current_key_id(&self) -> &str {
+        &self.current_key_id
+    }
+
+    fn key_ids(&self) -> Vec<String> {
+        self.keys.lock().expect("Failed to acquire lock").keys().cloned().collect()
+    }
+
+    fn kdf(&self, _key_id: &str) -> &'static str {
+        "FILE_KEYRING"
+    }
+
+    async fn wrap(&self, key_id: &str, dek: &[u8]) -> Result<Vec<u8>, String> {
+        aes_wrap(&self.key(key_id)?, dek)
+    }
+
+    async fn unwrap(&self, key_id: &str, wrapped: &[u8]) -> Result<Vec<u8>, String> {
+        aes_unwrap(&self.key(key_id)?, wrapped)
+    }
+
+    fn retire(&self, key_id: &str) -> bool {
+        key_id != self.current_key_id
+            && self.keys.lock().expect("Failed to acquire lock").remove(key_id).is_some()
+    }
+}
+
+// ========== PKCS#11 HSM ==========
+
+/// Minimal PKCS#11-style session, mirroring `C_WrapKey` / `C_UnwrapKey`
+///
+/// Implemented by the vendor binding of the HSM in use. Keys are addressed by
+/// their `CKA_LABEL` and never leave the device. Calls may block on the device;
+/// [`Pkcs11KeyProvider`] makes them on the blocking thread pool.
+pub trait Pkcs11Session: Send + Sync {
+    /// Wrap `key_material` with the wrapping key labelled `wrapping_key_label`
+    fn wrap_key(&self, wrapping_key_label: &str, key_material: &[u8]) -> Result<Vec<u8>, String>;
+
+    /// Unwrap `wrapped_key` with the wrapping key labelled `wrapping_key_label`
+    fn unwrap_key(&self, wrapping_key_label: &str, wrapped_key: &[u8]) -> Result<Vec<u8>, String>;
+}
+
+/// Master keys held in an HSM, used through a [`Pkcs11Session`]
+pub struct Pkcs11KeyProvider {
+    session: Arc<dyn Pkcs11Session>,
+    /// Wrapping key labels usable for unwrapping
+    labels: Mutex<Vec<String>>,
+    current_key_id: String,
+}
+
+impl Pkcs11KeyProvider {
+    /// `current_label` wraps new DEKs; `previous_labels` are kept for unwrapping
+    pub fn new(session: Arc<dyn Pkcs11Session>, current_label: &str, previous_labels: &[String]) -> Self {
+        let mut labels = previous_labels.to_vec();
+        labels.push(current_label.to_string());
+        labels.sort();
+        labels.dedup();
+        Self {
+            session,
+            labels: Mutex::new(labels),
+            current_key_id: current_label.to_string(),
+        }
+    }
+
+    fn check_label(&self, key_id: &str) -> Result<(), String> {
+        if self.labels.lock().expect("Failed to acquire lock").iter().any(|l| l == key_id) {
+            Ok(())
+        } else {
+            Err(format!("HSM key '{}' is not configured", key_id))
+        }
+    }
+}
+
+#[async_trait]
+impl KeyProvider for Pkcs11KeyProvider {
+    fn name(&self) -> &'static str {
+        "pkcs11"
+    }
+
+    fn current_key_id(&self) -> &str {
+        &self.current_key_id
+    }
+
+    fn key_ids(&self) -> Vec<String> {
+        self.labels.lock().expect("Failed to acquire lock").clone()
+    }
+
+    fn kdf(&self, _key_id: &str) -> &'static str {
+        "PKCS11"
+    }
+
+    async fn wrap(&self, key_id: &str, dek: &[u8]) -> Result<Vec<u8>, String> {
+        self.check_label(key_id)?;
+        let (session, label, dek) = (self.session.clone(), key_id.to_string(), dek.to_vec());
+        tokio::task::spawn_blocking(move || session.wrap_key(&label, &dek))
+            .await
+            .map_err(|e| format!("HSM wrap did not complete: {}", e))?
+    }
+
+    async fn unwrap(&self, key_id: &str, wrapped: &[u8]) -> Result<Vec<u8>, String> {
+        self.check_label(key_id)?;
+        let (session, label, wrapped) = (self.session.clone(), key_id.to_string(), wrapped.to_vec());
+        tokio::task::spawn_blocking(move || session.unwrap_key(&label, &wrapped))
+            .await
+            .map_err(|e| format!("HSM unwrap did not complete: {}", e))?
+    }
+
+    fn retire(&self, key_id: &str) -> bool {
+        if key_id == self.current_key_id {
+            return false;
+        }
+        let mut labels = self.labels.lock().expect("Failed to acquire lock");
+        let before = labels.len();
+        labels.retain(|l| l != key_id);
+        labels.len() != before
+    }
+}
+
+// ========== HTTP KMS ==========
+
+/// Request body for `POST {kms_url}/wrap`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct KmsWrapRequest {
+    pub key_id: String,
+    /// Base64-encoded DEK
+    pub plaintext: String,
+}
+
+/// Response body for `POST {kms_url}/wrap`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct KmsWrapResponse {
+    /// Base64-encoded wrapped DEK
+    pub ciphertext: String,
+}
+
+/// Request body for `POST {kms_url}/unwrap`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct KmsUnwrapRequest {
+    pub key_id: String,
+    /// Base64-encoded wrapped DEK
+    pub ciphertext: String,
+}
+
+/// Response body for `POST {kms_url}/unwrap`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct KmsUnwrapResponse {
+    /// Base64-encoded DEK
+    pub plaintext: String,
+}
+
+/// Generic remote KMS exposing `/wrap` and `/unwrap` over HTTPS
+pub struct HttpKmsProvider {
+    base_url: String,
+    client: Client,
+    /// Optional bearer token sent with every request
+    auth_token: Option<String>,
+    key_ids: Mutex<Vec<String>>,
+    current_key_id: String,
+}
+
+impl HttpKmsProvider {
+    /// Load configuration from environment
+    ///
+    /// * `VERIDION_KMS_URL` - base URL of the KMS (required)
+    /// * `VERIDION_KMS_KEY_ID` - key used to wrap new DEKs (required)
+    /// * `VERIDION_KMS_PREVIOUS_KEY_IDS` - comma-separated older keys still used for unwrapping
+    /// * `VERIDION_KMS_TOKEN` - bearer token (optional)
+    pub fn from_env() -> Self {
+        let base_url = std::env::var("VERIDION_KMS_URL")
+            .expect("VERIDION_KMS_URL must be set when VERIDION_KEY_PROVIDER=http");
+        let current_key_id = std::env::var("VERIDION_KMS_KEY_ID")
+            .expect("VERIDION_KMS_KEY_ID must be set when VERIDION_KEY_PROVIDER=http");
+        let previous: Vec<String> = std::env::var("VERIDION_KMS_PREVIOUS_KEY_IDS")
+            .unwrap_or_default()
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect();
+        let auth_token = std::env::var("VERIDION_KMS_TOKEN").ok();
+
+        Self::new(&base_url, &current_key_id, &previous, auth_token)
+    }
+
+    pub fn new(base_url: &str, current_key_id: &str, previous_key_ids: &[String], auth_token: Option<String>) -> Self {
+        let mut key_ids = previous_key_ids.to_vec();
+        key_ids.push(current_key_id.to_string());
+        key_ids.sort();
+        key_ids.dedup();
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            client: Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_else(|_| Client::new()),
+            auth_token,
+            key_ids: Mutex::new(key_ids),
+            current_key_id: current_key_id.to_string(),
+        }
+    }
+
+    fn check_key_id(&self, key_id: &str) -> Result<(), String> {
+        if self.key_ids.lock().expect("Failed to acquire lock").iter().any(|k| k == key_id) {
+            Ok(())
+        } else {
+            Err(format!("KMS key '{}' is not configured", key_id))
+        }
+    }
+
+    async fn post<Req: Serialize, Resp: for<'de> Deserialize<'de>>(&self, op: &str, body: &Req) -> Result<Resp, String> {
+        let mut request = self.client.post(format!("{}/{}", self.base_url, op)).json(body);
+        if let Some(token) = &self.auth_token {
+            request = request.bearer_auth(token);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("KMS {} request failed: {}", op, e))?;
+        if !response.status().is_success() {
+            return Err(format!("KMS {} failed with status {}", op, response.status()));
+        }
+        response
+            .json::<Resp>()
+            .await
+            .map_err(|e| format!("Invalid KMS {} response: {}", op, e))
+    }
+}
+
+#[async_trait]
+impl KeyProvider for HttpKmsProvider {
+    fn name(&self) -> &'static str {
+        "http"
+    }
+
+    fn current_key_id(&self) -> &str {
+        &self.current_key_id
+    }
+
+    fn key_ids(&self) -> Vec<String> {
+        self.key_ids.lock().expect("Failed to acquire lock").clone()
+    }
+
+    fn kdf(&self, _key_id: &str) -> &'static str {
+        "HTTP_KMS"
+    }
+
+    async fn wrap(&self, key_id: &str, dek: &[u8]) -> Result<Vec<u8>, String> {
+        self.check_key_id(key_id)?;
+        let engine = base64::engine::general_purpose::STANDARD;
+        let response: KmsWrapResponse = self
+            .post("wrap", &KmsWrapRequest {
+                key_id: key_id.to_string(),
+                plaintext: engine.encode(dek),
+            })
+            .await?;
+        engine
+            .decode(response.ciphertext)
+            .map_err(|e| format!("Invalid KMS ciphertext: {}", e))
+    }
+
+    async fn unwrap(&self, key_id: &str, wrapped: &[u8]) -> Result<Vec<u8>, String> {
+        self.check_key_id(key_id)?;
+        let engine = base64::engine::general_purpose::STANDARD;
+        let response: KmsUnwrapResponse = self
+            .post("unwrap", &KmsUnwrapRequest {
+                key_id: key_id.to_string(),
+                ciphertext: engine.encode(wrapped),
+            })
+            .await?;
+        engine
+            .decode(response.plaintext)
+            .map_err(|e| format!("Invalid KMS plaintext: {}", e))
+    }
+
+    fn retire(&self, key_id: &str) -> bool {
+        if key_id == self.current_key_id {
+            return false;
+        }
+        let mut key_ids = self.key_ids.lock().expect("Failed to acquire lock");
+        let before = key_ids.len();
+        key_ids.retain(|k| k != key_id);
+        key_ids.len() != before
+    }
+}
+
+// ========== MIGRATION FALLBACK ==========
+
+/// Primary provider plus a decrypt-only fallback
+///
+/// Used while moving to a new backend: new DEKs are wrapped by `primary`,
+/// older DEKs are unwrapped by `fallback` until the re-wrap job has moved them.
+pub struct FallbackKeyProvider {
+    primary: Arc<dyn KeyProvider>,
+    fallback: Arc<dyn KeyProvider>,
+}
+
+impl FallbackKeyProvider {
+    pub fn new(primary: Arc<dyn KeyProvider>, fallback: Arc<dyn KeyProvider>) -> Self {
+        Self { primary, fallback }
+    }
+
+    fn provider_for(&self, key_id: &str) -> &Arc<dyn KeyProvider> {
+        if self.primary.key_ids().iter().any(|k| k == key_id) {
+            &self.primary
+        } else {
+            &self.fallback
+        }
+    }
+}
+
+#[async_trait]
+impl KeyProvider for FallbackKeyProvider {
+    fn name(&self) -> &'static str {
+        self.primary.name()
+    }
+
+    fn current_key_id(&self) -> &str {
+        self.primary.current_key_id()
+    }
+
+    fn key_ids(&self) -> Vec<String> {
+        let mut ids = self.primary.key_ids();
+        for id in self.fallback.key_ids() {
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+        ids
+    }
+
+    fn kdf(&self, key_id: &str) -> &'static str {
+        self.provider_for(key_id).kdf(key_id)
+    }
+
+    async fn wrap(&self, key_id: &str, dek: &[u8]) -> Result<Vec<u8>, String> {
+        // Only the primary wraps; the fallback is decrypt-only
+        self.primary.wrap(key_id, dek).await
+    }
+
+    async fn unwrap(&self, key_id: &str, wrapped: &[u8]) -> Result<Vec<u8>, String> {
+        self.provider_for(key_id).unwrap(key_id, wrapped).await
+    }
+
+    fn retire(&self, key_id: &str) -> bool {
+        if key_id == self.primary.current_key_id() {
+            return false;
+        }
+        let retired_primary = self.primary.retire(key_id);
+        let retired_fallback = self.fallback.retire(key_id);
+        retired_primary || retired_fallback
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legacy_master_key() {
+        // DEKs wrapped before versioning used the raw, zero-padded secret
+        let legacy_key = legacy_master_key("short_secret");
+        assert_eq!(legacy_key.len(), 32);
+        assert_eq!(&legacy_key[..12], b"short_secret");
+
+        let derived = derive_master_key("short_secret", "v1");
+        assert_ne!(derived, legacy_key);
+        assert_ne!(derived, derive_master_key("short_secret", "v2"));
+    }
+
+    #[tokio::test]
+    async fn test_file_keyring_provider() {
+        let engine = base64::engine::general_purpose::STANDARD;
+        let keyring = serde_json::json!({
+            "current_key_id": "k2",
+            "keys": { "k1": engine.encode([1u8; 32]), "k2": engine.encode([2u8; 32]) }
+        });
+        let provider = FileKeyringProvider::from_json(&keyring.to_string()).unwrap();
+        assert_eq!(provider.current_key_id(), "k2");
+
+        let wrapped = provider.wrap("k1", b"data encryption key").await.unwrap();
+        assert_eq!(provider.unwrap("k1", &wrapped).await.unwrap(), b"data encryption key");
+        assert!(provider.unwrap("k2", &wrapped).await.is_err());
+
+        assert!(provider.retire("k1"));
+        assert!(!provider.retire("k2"));
+        assert!(provider.unwrap("k1", &wrapped).await.is_err());
+
+        assert!(FileKeyringProvider::from_json(r#"{"current_key_id":"k9","keys":{}}"#).is_err());
+    }
+
+    #[actix_web::test]
+    async fn test_http_kms_provider_with_mock_server() {
+        let kms_url = crate::test_helpers::spawn_mock_kms(&["kms-v1", "kms-v2"]).await;
+        let provider = HttpKmsProvider::new(&kms_url, "kms-v2", &["kms-v1".to_string()], None);
+
+        let wrapped = provider.wrap("kms-v2", b"data encryption key").await.unwrap();
+        assert_eq!(provider.unwrap("kms-v2", &wrapped).await.unwrap(), b"data encryption key");
+        assert!(provider.unwrap("kms-v1", &wrapped).await.is_err());
+        assert!(provider.wrap("kms-v3", b"x").await.is_err());
+    }
+
+    /// HSM stand-in: one AES key per label, held inside the "device"
+    struct MockPkcs11Session {
+        keys: HashMap<String, [u8; 32]>,
+    }
+
+    impl Pkcs11Session for MockPkcs11Session {
+        fn wrap_key(&self, wrapping_key_label: &str, key_material: &[u8]) -> Result<Vec<u8>, String> {
+            let key = self.keys.get(wrapping_key_label).ok_or("CKR_KEY_HANDLE_INVALID")?;
+            aes_wrap(key, key_material)
+        }
+
+        fn unwrap_key(&self, wrapping_key_label: &str, wrapped_key: &[u8]) -> Result<Vec<u8>, String> {
+            let key = self.keys.get(wrapping_key_label).ok_or("CKR_KEY_HANDLE_INVALID")?;
+            aes_unwrap(key, wrapped_key)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pkcs11_provider_with_mock_session() {
+        let session = Arc::new(MockPkcs11Session {
+            keys: HashMap::from([("hsm-v1".to_string(), [1u8; 32]), ("hsm-v2".to_string(), [2u8; 32])]),
+        });
+        // The current label may also be listed among the previous ones
+        let repeated = Pkcs11KeyProvider::new(session.clone(), "hsm-v2", &["hsm-v2".to_string(), "hsm-v1".to_string()]);
+        assert_eq!(repeated.key_ids(), vec!["hsm-v1".to_string(), "hsm-v2".to_string()]);
+
+        let provider = Pkcs11KeyProvider::new(session, "hsm-v2", &["hsm-v1".to_string()]);
+        assert_eq!(provider.current_key_id(), "hsm-v2");
+
+        let wrapped = provider.wrap("hsm-v2", b"data encryption key").await.unwrap();
+        assert_eq!(provider.unwrap("hsm-v2", &wrapped).await.unwrap(), b"data encryption key");
+        assert!(provider.unwrap("hsm-v1", &wrapped).await.is_err());
+
+        // Labels that are not configured are refused before reaching the device
+        assert!(provider.wrap("hsm-v3", b"x").await.unwrap_err().contains("not configured"));
+        assert!(provider.retire("hsm-v1"));
+        assert!(!provider.retire("hsm-v2"));
+        assert!(provider.unwrap("hsm-v1", &wrapped).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fallback_provider_unwraps_old_keys() {
+        let env = Arc::new(EnvKeyProvider::with_master_keys("v1", "old_master_secret", &[]));
+        let wrapped_by_env = env.wrap("v1", b"old dek").await.unwrap();
+
+        let engine = base64::engine::general_purpose::STANDARD;
+        let keyring = serde_json::json!({ "current_key_id": "k1", "keys": { "k1": engine.encode([7u8; 32]) } });
+        let file = Arc::new(FileKeyringProvider::from_json(&keyring.to_string()).unwrap());
+
+        let provider = FallbackKeyProvider::new(file, env);
+        assert_eq!(provider.current_key_id(), "k1");
+        assert_eq!(provider.unwrap("v1", &wrapped_by_env).await.unwrap(), b"old dek");
+        assert!(provider.wrap("v1", b"new dek").await.is_err());
+        assert!(provider.key_ids().contains(&"v1".to_string()));
+    }
+}
@@ -0,0 +1,323 @@
+// Scoped Lockdown
+// Incident lockdowns narrower than the global panic button: all traffic of one
+// business function, to one vendor domain, to one jurisdiction or of one tenant
+// can be stopped. Rules need a justification, always expire, and each one opens
+// a DORA Lite incident that is resolved when the rule is lifted or expires.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Longest a lockdown may run before it has to be renewed (7 days)
+pub const MAX_DURATION_MINUTES: i64 = 7 * 24 * 60;
+
+/// What a lockdown rule stops
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum LockdownScope {
+    Global,
+    BusinessFunction,
+    VendorDomain,
+    Jurisdiction,
+    Tenant,
+}
+
+impl LockdownScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LockdownScope::Global => "GLOBAL",
+            LockdownScope::BusinessFunction => "BUSINESS_FUNCTION",
+            LockdownScope::VendorDomain => "VENDOR_DOMAIN",
+            LockdownScope::Jurisdiction => "JURISDICTION",
+            LockdownScope::Tenant => "TENANT",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_uppercase().as_str() {
+            "GLOBAL" => Some(LockdownScope::Global),
+            "BUSINESS_FUNCTION" => Some(LockdownScope::BusinessFunction),
+            "VENDOR_DOMAIN" => Some(LockdownScope::VendorDomain),
+            "JURISDICTION" => Some(LockdownScope::Jurisdiction),
+            "TENANT" => Some(LockdownScope::Tenant),
+            _ => None,
+        }
+    }
+
+    /// Canonical form of a scope value (None when the scope needs one and it is empty)
+    pub fn normalize(&self, value: Option<&str>) -> Option<Option<String>> {
+        let value = value.map(str::trim).filter(|v| !v.is_empty());
+        match (self, value) {
+            (LockdownScope::Global, _) => Some(None),
+            (_, None) => None,
+            (LockdownScope::BusinessFunction | LockdownScope::Jurisdiction, Some(v)) => Some(Some(v.to_uppercase())),
+            (LockdownScope::VendorDomain, Some(v)) => {
+                let domain = v.trim_start_matches("*.").trim_end_matches('.').to_lowercase();
+                if domain.is_empty() { None } else { Some(Some(domain)) }
+            }
+            (LockdownScope::Tenant, Some(v)) => Some(Some(v.to_string())),
+        }
+    }
+
+    /// Whether a lockdown of this scope and (normalized) value stops a request
+    pub fn matches(&self, value: Option<&str>, target: &LockdownTarget<'_>) -> bool {
+        let value = value.unwrap_or("");
+        let same = |candidate: Option<&str>| candidate.map(|c| c.trim().eq_ignore_ascii_case(value)).unwrap_or(false);
+        match self {
+            LockdownScope::Global => true,
+            LockdownScope::BusinessFunction => same(target.business_function),
+            LockdownScope::Jurisdiction => same(target.country),
+            LockdownScope::Tenant => same(target.tenant),
+            LockdownScope::VendorDomain => match target.vendor_host {
+                Some(host) => {
+                    let host = host.trim_end_matches('.').to_lowercase();
+                    host == value || host.ends_with(&format!(".{}", value))
+                }
+                None => false,
+            },
+        }
+    }
+}
+
+/// What is known about a request when lockdowns are checked
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LockdownTarget<'a> {
+    pub business_function: Option<&'a str>,
+    /// Host of the vendor API the request goes to
+    pub vendor_host: Option<&'a str>,
+    /// ISO country code the data goes to
+    pub country: Option<&'a str>,
+    pub tenant: Option<&'a str>,
+}
+
+/// A lockdown rule and the DORA Lite incident opened for it
+#[derive(Debug, Clone, Serialize, ToSchema, sqlx::FromRow)]
+pub struct LockdownRule {
+    pub id: Uuid,
+    /// GLOBAL, BUSINESS_FUNCTION, VENDOR_DOMAIN, JURISDICTION or TENANT
+    pub scope_type: String,
+    /// Business function code, vendor domain (subdomains included), ISO country code or tenant id
+    pub scope_value: Option<String>,
+    pub justification: String,
+    pub created_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub lifted_at: Option<DateTime<Utc>>,
+    pub lifted_by: Option<String>,
+    pub lift_reason: Option<String>,
+    pub incident_id: Option<Uuid>,
+}
+
+const RULE_COLUMNS: &str = "id, scope_type, scope_value, justification, created_by, created_at, \
+     expires_at, lifted_at, lifted_by, lift_reason, incident_id";
+
+impl LockdownRule {
+    /// Whether the rule stops a request
+    pub fn matches(&self, target: &LockdownTarget<'_>) -> bool {
+        LockdownScope::parse(&self.scope_type)
+            .is_some_and(|scope| scope.matches(self.scope_value.as_deref(), target))
+    }
+
+    /// Human readable scope, e.g. "VENDOR_DOMAIN api.openai.com"
+    pub fn describe(&self) -> String {
+        match &self.scope_value {
+            Some(value) => format!("{} {}", self.scope_type, value),
+            None => self.scope_type.clone(),
+        }
+    }
+}
+
+/// Lockdown rules backed by lockdown_rules and dora_lite_incidents
+pub struct Lockdowns;
+
+impl Lockdowns {
+    /// Start a lockdown and open its DORA Lite incident
+    pub async fn create(
+        db_pool: &PgPool,
+        scope: LockdownScope,
+        scope_value: Option<&str>,
+        justification: &str,
+        actor: Option<&str>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<LockdownRule, sqlx::Error> {
+        let scope_label = match scope_value {
+            Some(value) => format!("{} {}", scope.as_str(), value),
+            None => scope.as_str().to_string(),
+        };
+
+        let mut tx = db_pool.begin().await?;
+        let incident_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO dora_lite_incidents (
+                incident_type, description, severity, impact_description, mitigation_steps, status
+            ) VALUES ('LOCKDOWN', $1, 'HIGH', $2, $3, 'OPEN')
+            RETURNING id"
+        )
+        .bind(format!("Lockdown of {}: {}", scope_label, justification))
+        .bind(format!("AI traffic for {} is blocked until {}", scope_label, expires_at.format("%Y-%m-%d %H:%M:%S UTC")))
+        .bind(format!("Lockdown started by {}", actor.unwrap_or("system")))
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let rule: LockdownRule = sqlx::query_as(&format!(
+            "INSERT INTO lockdown_rules (scope_type, scope_value, justification, created_by, expires_at, incident_id)
+             VALUES ($1, $2, $3, $4, $5, $6)
+             RETURNING {}",
+            RULE_COLUMNS
+        ))
+        .bind(scope.as_str())
+        .bind(scope_value)
+        .bind(justification)
+        .bind(actor)
+        .bind(expires_at)
+        .bind(incident_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        log::warn!("Lockdown started: {} until {} ({})", scope_label, expires_at, justification);
+        Ok(rule)
+    }
+
+    /// Lift an active lockdown and resolve its incident; None if it is not active
+    pub async fn lift(
+        db_pool: &PgPool,
+        id: Uuid,
+        actor: Option<&str>,
+        reason: Option<&str>,
+    ) -> Result<Option<LockdownRule>, sqlx::Error> {
+        let mut tx = db_pool.begin().await?;
+        let rule: Option<LockdownRule> = sqlx::query_as(&format!(
+            "UPDATE lockdown_rules
+             SET lifted_at = CURRENT_TIMESTAMP, lifted_by = $2, lift_reason = $3
+             WHERE id = $1 AND lifted_at IS NULL AND expires_at > CURRENT_TIMESTAMP
+             RETURNING {}",
+            RULE_COLUMNS
+        ))
+        .bind(id)
+        .bind(actor)
+        .bind(reason)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let Some(rule) = rule else {
+            tx.rollback().await?;
+            return Ok(None);
+        };
+
+        if let Some(incident_id) = rule.incident_id {
+            sqlx::query(
+                "UPDATE dora_lite_incidents
+                 SET status = 'RESOLVED', resolved_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP,
+                     mitigation_steps = COALESCE(mitigation_steps || E'\\n', '') || $2
+                 WHERE id = $1"
+            )
+            .bind(incident_id)
+            .bind(format!(
+                "Lockdown lifted by {}{}",
+                actor.unwrap_or("system"),
+                reason.map(|r| format!(": {}", r)).unwrap_or_default()
+            ))
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        log::info!("Lockdown lifted: {}", rule.describe());
+        Ok(Some(rule))
+    }
+
+    /// Resolve the incidents of lockdowns that ran out; returns how many
+    pub async fn resolve_expired(db_pool: &PgPool) -> Result<u64, sqlx::Error> {
+        let resolved = sqlx::query(
+            "UPDATE dora_lite_incidents i
+             SET status = 'RESOLVED', resolved_at = r.expires_at, updated_at = CURRENT_TIMESTAMP,
+                 mitigation_steps = COALESCE(i.mitigation_steps || E'\\n', '') || 'Lockdown expired'
+             FROM lockdown_rules r
+             WHERE r.incident_id = i.id
+               AND r.lifted_at IS NULL
+               AND r.expires_at <= CURRENT_TIMESTAMP
+               AND i.status <> 'RESOLVED'"
+        )
+        .execute(db_pool)
+        .await?
+        .rows_affected();
+        Ok(resolved)
+    }
+
+    /// Lockdowns in force, newest first
+    pub async fn active(db_pool: &PgPool) -> Result<Vec<LockdownRule>, sqlx::Error> {
+        sqlx::query_as(&format!(
+            "SELECT {} FROM lockdown_rules
+             WHERE lifted_at IS NULL AND expires_at > CURRENT_TIMESTAMP
+             ORDER BY created_at DESC",
+            RULE_COLUMNS
+        ))
+        .fetch_all(db_pool)
+        .await
+    }
+
+    /// Lockdowns of the last `days` days, including lifted and expired ones
+    pub async fn history(db_pool: &PgPool, days: i64) -> Result<Vec<LockdownRule>, sqlx::Error> {
+        sqlx::query_as(&format!(
+            "SELECT {} FROM lockdown_rules
+             WHERE created_at >= CURRENT_TIMESTAMP - ($1 || ' days')::INTERVAL
+             ORDER BY created_at DESC",
+            RULE_COLUMNS
+        ))
+        .bind(days.to_string())
+        .fetch_all(db_pool)
+        .await
+    }
+
+    /// First active lockdown that stops the request
+    pub async fn check(db_pool: &PgPool, target: &LockdownTarget<'_>) -> Result<Option<LockdownRule>, sqlx::Error> {
+        Ok(Self::active(db_pool).await?.into_iter().find(|rule| rule.matches(target)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(scope: LockdownScope, value: Option<&str>) -> LockdownRule {
+        LockdownRule {
+            id: Uuid::new_v4(),
+            scope_type: scope.as_str().to_string(),
+            scope_value: scope.normalize(value).flatten(),
+            justification: "incident".to_string(),
+            created_by: None,
+            created_at: Utc::now(),
+            expires_at: Utc::now() + chrono::Duration::minutes(30),
+            lifted_at: None,
+            lifted_by: None,
+            lift_reason: None,
+            incident_id: None,
+        }
+    }
+
+    #[test]
+    fn test_rules_match_only_their_scope() {
+        let target = LockdownTarget {
+            business_function: Some("CREDIT_SCORING"),
+            vendor_host: Some("eu.api.openai.com"),
+            country: Some("DE"),
+            tenant: Some("acme"),
+        };
+
+        assert!(rule(LockdownScope::Global, None).matches(&target));
+        assert!(rule(LockdownScope::Global, None).matches(&LockdownTarget::default()));
+        assert!(rule(LockdownScope::BusinessFunction, Some("credit_scoring")).matches(&target));
+        assert!(!rule(LockdownScope::BusinessFunction, Some("MARKETING")).matches(&target));
+        assert!(rule(LockdownScope::VendorDomain, Some("*.OpenAI.com")).matches(&target));
+        assert!(!rule(LockdownScope::VendorDomain, Some("ai.com")).matches(&target));
+        assert!(rule(LockdownScope::Jurisdiction, Some("de")).matches(&target));
+        assert!(!rule(LockdownScope::Jurisdiction, Some("US")).matches(&target));
+        assert!(rule(LockdownScope::Tenant, Some(" acme ")).matches(&target));
+        assert!(!rule(LockdownScope::Tenant, Some("acme")).matches(&LockdownTarget::default()));
+
+        assert_eq!(LockdownScope::VendorDomain.normalize(Some("  ")), None);
+        assert_eq!(LockdownScope::Global.normalize(Some("ignored")), Some(None));
+    }
+}
@@ -0,0 +1,317 @@
+// Merkle Anchoring
+// Instead of sealing every compliance record, a background job periodically
+// builds a Merkle tree over the record_hash of each record chained since the
+// previous anchor and seals only the root with the default seal provider. Each
+// record keeps its inclusion proof, so an auditor can check offline that the
+// record is covered by a sealed root.
+//
+// Hashing (hex strings, as stored):
+//   leaf = SHA-256(0x00 || record_hash)
+//   node = SHA-256(0x01 || left || right)
+// A node without a sibling is carried up to the next level unchanged.
+
+use crate::core::seal_provider::SealService;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Records covered by one anchor at most; a larger backlog gets several anchors
+pub const MAX_LEAVES_PER_ANCHOR: i64 = 10_000;
+
+/// Minutes between anchoring runs (AUDIT_ANCHOR_INTERVAL_MINUTES, default 60)
+pub fn anchor_interval_minutes() -> u64 {
+    std::env::var("AUDIT_ANCHOR_INTERVAL_MINUTES")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(60)
+}
+
+/// Whether log_action seals each record itself (AUDIT_SEAL_MODE=RECORD, the default)
+///
+/// With AUDIT_SEAL_MODE=ANCHOR records only get a local seal ID and are sealed
+/// through their anchor's root.
+pub fn per_record_sealing() -> bool {
+    !std::env::var("AUDIT_SEAL_MODE")
+        .map(|v| v.eq_ignore_ascii_case("ANCHOR"))
+        .unwrap_or(false)
+}
+
+pub fn leaf_hash(record_hash: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update([0x00]);
+    hasher.update(record_hash.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+pub fn node_hash(left: &str, right: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update([0x01]);
+    hasher.update(left.as_bytes());
+    hasher.update(right.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// One sibling on the path from a leaf to the root
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ProofStep {
+    pub hash: String,
+    /// LEFT or RIGHT: which side of the current node the sibling is on
+    pub position: String,
+}
+
+/// Merkle tree over leaf hashes, kept level by level
+pub struct MerkleTree {
+    levels: Vec<Vec<String>>,
+}
+
+impl MerkleTree {
+    /// Build a tree over `leaves` (leaf hashes); None if there are none
+    pub fn new(leaves: Vec<String>) -> Option<Self> {
+        if leaves.is_empty() {
+            return None;
+        }
+        let mut levels = vec![leaves];
+        while levels[levels.len() - 1].len() > 1 {
+            let next = levels[levels.len() - 1]
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => node_hash(left, right),
+                    [single] => single.clone(),
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+        Some(Self { levels })
+    }
+
+    pub fn root(&self) -> &str {
+        &self.levels[self.levels.len() - 1][0]
+    }
+
+    /// Inclusion proof of the leaf at `index`
+    pub fn proof(&self, mut index: usize) -> Vec<ProofStep> {
+        let mut steps = Vec::new();
+        for level in &self.levels[..self.levels.len() - 1] {
+            let sibling = index ^ 1;
+            if let Some(hash) = level.get(sibling) {
+                steps.push(ProofStep {
+                    hash: hash.clone(),
+                    position: if sibling < index { "LEFT" } else { "RIGHT" }.to_string(),
+                });
+            }
+            index /= 2;
+        }
+        steps
+    }
+}
+
+/// Root reached by applying `proof` to `leaf`
+pub fn root_from_proof(leaf: &str, proof: &[ProofStep]) -> String {
+    proof.iter().fold(leaf.to_string(), |current, step| {
+        if step.position == "LEFT" {
+            node_hash(&step.hash, &current)
+        } else {
+            node_hash(&current, &step.hash)
+        }
+    })
+}
+
+/// A sealed Merkle root over a range of the chain
+#[derive(Debug, Clone, Serialize, sqlx::FromRow, ToSchema)]
+pub struct AuditAnchor {
+    pub id: Uuid,
+    pub first_chain_seq: i64,
+    pub last_chain_seq: i64,
+    pub leaf_count: i32,
+    pub merkle_root: String,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    /// Seal returned by the seal provider for merkle_root
+    pub seal_id: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Everything needed to check one record against its anchor offline
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct InclusionProof {
+    pub seal_id: String,
+    pub chain_seq: i64,
+    /// The record's hash in the audit chain (see /verify_chain)
+    pub record_hash: String,
+    pub leaf_hash: String,
+    pub leaf_index: i32,
+    pub proof: Vec<ProofStep>,
+    pub anchor: AuditAnchor,
+    /// The proof leads from leaf_hash to the anchor's merkle_root
+    pub valid: bool,
+}
+
+#[derive(sqlx::FromRow)]
+struct PendingLeaf {
+    chain_seq: i64,
+    seal_id: String,
+    record_hash: String,
+    chained_at: DateTime<Utc>,
+}
+
+/// Anchors of the compliance record chain
+pub struct AuditAnchors;
+
+impl AuditAnchors {
+    /// Anchor the records chained since the last anchor (up to `max_leaves`)
+    ///
+    /// Returns None when there is nothing to anchor or another replica is
+    /// anchoring right now. A failed seal stores nothing; the records are picked
+    /// up again on the next run.
+    pub async fn anchor_pending(
+        db_pool: &PgPool,
+        seals: &SealService,
+        max_leaves: i64,
+    ) -> Result<Option<AuditAnchor>, String> {
+        let mut tx = db_pool.begin().await.map_err(|e| e.to_string())?;
+        let locked: bool = sqlx::query_scalar("SELECT pg_try_advisory_xact_lock(hashtext('audit_anchors'))")
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+        if !locked {
+            return Ok(None);
+        }
+
+        let leaves: Vec<PendingLeaf> = sqlx::query_as(
+            "SELECT chain_seq, seal_id, record_hash, chained_at FROM compliance_records
+             WHERE chain_seq > (SELECT COALESCE(MAX(last_chain_seq), 0) FROM audit_anchors)
+             ORDER BY chain_seq
+             LIMIT $1"
+        )
+        .bind(max_leaves)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        let tree = match MerkleTree::new(leaves.iter().map(|l| leaf_hash(&l.record_hash)).collect()) {
+            Some(tree) => tree,
+            None => return Ok(None),
+        };
+        let seal_id = seals.seal(db_pool, None, tree.root()).await?;
+
+        let first = &leaves[0];
+        let last = &leaves[leaves.len() - 1];
+        let anchor: AuditAnchor = sqlx::query_as(
+            "INSERT INTO audit_anchors
+             (first_chain_seq, last_chain_seq, leaf_count, merkle_root, period_start, period_end, seal_id)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)
+             RETURNING *"
+        )
+        .bind(first.chain_seq)
+        .bind(last.chain_seq)
+        .bind(leaves.len() as i32)
+        .bind(tree.root())
+        .bind(first.chained_at)
+        .bind(last.chained_at)
+        .bind(&seal_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        let leaf_level = &tree.levels[0];
+        let proofs: Vec<serde_json::Value> = (0..leaves.len())
+            .map(|i| serde_json::to_value(tree.proof(i)).unwrap_or_default())
+            .collect();
+        sqlx::query(
+            "INSERT INTO audit_anchor_proofs (chain_seq, seal_id, anchor_id, leaf_index, leaf_hash, proof)
+             SELECT seq, seal, $1, idx, leaf, proof
+             FROM UNNEST($2::BIGINT[], $3::TEXT[], $4::INTEGER[], $5::TEXT[], $6::JSONB[])
+                  AS p(seq, seal, idx, leaf, proof)"
+        )
+        .bind(anchor.id)
+        .bind(leaves.iter().map(|l| l.chain_seq).collect::<Vec<_>>())
+        .bind(leaves.iter().map(|l| l.seal_id.clone()).collect::<Vec<_>>())
+        .bind((0..leaves.len() as i32).collect::<Vec<_>>())
+        .bind(leaf_level)
+        .bind(proofs)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        tx.commit().await.map_err(|e| e.to_string())?;
+        Ok(Some(anchor))
+    }
+
+    /// Most recent anchors first
+    pub async fn list(db_pool: &PgPool, limit: i64) -> Result<Vec<AuditAnchor>, sqlx::Error> {
+        sqlx::query_as("SELECT * FROM audit_anchors ORDER BY last_chain_seq DESC LIMIT $1")
+            .bind(limit)
+            .fetch_all(db_pool)
+            .await
+    }
+
+    /// Inclusion proof of a record; None until the record has been anchored
+    pub async fn proof(db_pool: &PgPool, seal_id: &str) -> Result<Option<InclusionProof>, sqlx::Error> {
+        let row: Option<(i64, String, i32, String, serde_json::Value, Uuid)> = sqlx::query_as(
+            "SELECT p.chain_seq, cr.record_hash, p.leaf_index, p.leaf_hash, p.proof, p.anchor_id
+             FROM audit_anchor_proofs p
+             JOIN compliance_records cr ON cr.chain_seq = p.chain_seq
+             WHERE p.seal_id = $1"
+        )
+        .bind(seal_id)
+        .fetch_optional(db_pool)
+        .await?;
+        let (chain_seq, record_hash, leaf_index, stored_leaf, proof, anchor_id) = match row {
+            Some(row) => row,
+            None => return Ok(None),
+        };
+        let anchor: AuditAnchor = sqlx::query_as("SELECT * FROM audit_anchors WHERE id = $1")
+            .bind(anchor_id)
+            .fetch_one(db_pool)
+            .await?;
+        let proof: Vec<ProofStep> = serde_json::from_value(proof).unwrap_or_default();
+
+        // Recompute from the record as it is now, not from the stored leaf
+        let leaf = leaf_hash(&record_hash);
+        let valid = leaf == stored_leaf && root_from_proof(&leaf, &proof) == anchor.merkle_root;
+        Ok(Some(InclusionProof {
+            seal_id: seal_id.to_string(),
+            chain_seq,
+            record_hash,
+            leaf_hash: leaf,
+            leaf_index,
+            proof,
+            anchor,
+            valid,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaves(n: usize) -> Vec<String> {
+        (0..n).map(|i| leaf_hash(&format!("{:064x}", i))).collect()
+    }
+
+    #[test]
+    fn test_every_leaf_proves_against_root() {
+        for n in 1..=9 {
+            let leaves = leaves(n);
+            let tree = MerkleTree::new(leaves.clone()).unwrap();
+            for (i, leaf) in leaves.iter().enumerate() {
+                assert_eq!(root_from_proof(leaf, &tree.proof(i)), tree.root(), "leaf {} of {}", i, n);
+            }
+        }
+        assert!(MerkleTree::new(Vec::new()).is_none());
+    }
+
+    #[test]
+    fn test_proof_rejects_other_leaf() {
+        let leaves = leaves(5);
+        let tree = MerkleTree::new(leaves.clone()).unwrap();
+        assert_ne!(root_from_proof(&leaves[1], &tree.proof(2)), tree.root());
+        assert_ne!(root_from_proof(&leaf_hash("tampered"), &tree.proof(4)), tree.root());
+    }
+}
@@ -2,12 +2,27 @@
 // These modules are mandatory and always enabled
 
 pub mod sovereign_lock;
+pub mod region_catalog;
+pub mod jurisdiction_policy;
 pub mod crypto_shredder;
+pub mod audit_chain;
+pub mod merkle_anchor;
+pub mod agent_revocation;
+pub mod lockdown;
+pub mod key_provider;
+pub mod erasure_certificate;
 pub mod privacy_bridge;
+pub mod seal_provider;
+pub mod seal_outbox;
+pub mod pii_redaction;
 pub mod annex_iv;
 pub mod risk_assessment;
 pub mod policy_simulator;
+pub mod rollout;
 pub mod asset_policy_engine;
+pub mod policy_rules;
+pub mod policy_bundle;
+pub mod usage_budget;
 pub mod executive_assurance;
 pub mod ai_explainability;
 pub mod configuration_drift;
@@ -0,0 +1,674 @@
+// PII Redaction
+// Detects IBANs, payment card numbers, national identifiers, email addresses and
+// phone numbers in free text and replaces them with reversible tokens such as
+// "[EMAIL_3f9a1c2b]". The token -> value mapping (TokenVault) is encrypted by the
+// crypto-shredder, so shredding its key erases the original values.
+
+use crate::core::crypto_shredder::{EncryptedLog, VeridionKeyStore};
+use rand::{thread_rng, RngCore};
+use sqlx::PgPool;
+use std::collections::{BTreeMap, HashMap};
+
+/// Longest token produced by `TokenVault`, e.g. "[NATIONAL_ID_3f9a1c2b]"
+pub const MAX_TOKEN_LEN: usize = 22;
+
+/// Keywords announcing a national identifier, in the spirit of the sensitive
+/// data patterns of `RiskAssessmentService::assess_data_sensitivity`
+const NATIONAL_ID_KEYWORDS: &[&str] = &[
+    "ssn",
+    "social security",
+    "passport",
+    "national id",
+    "id number",
+    "identity card",
+    "personal id",
+    "tax id",
+    "birth number",
+    "rodne cislo",
+    "pesel",
+    "personnummer",
+    "bsn",
+    "codice fiscale",
+    "nif",
+    "dni",
+    "steuer-id",
+];
+
+/// Keywords announcing a phone number written without an international prefix
+const PHONE_KEYWORDS: &[&str] = &["phone", "telephone", "tel", "mobile", "cell", "fax", "whatsapp"];
+
+/// How far after a keyword its value may start (bytes)
+const KEYWORD_WINDOW: usize = 32;
+
+/// Category of personal data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PiiKind {
+    Iban,
+    CardNumber,
+    NationalId,
+    Email,
+    Phone,
+}
+
+impl PiiKind {
+    /// Label used in tokens and counts
+    pub fn label(&self) -> &'static str {
+        match self {
+            PiiKind::Iban => "IBAN",
+            PiiKind::CardNumber => "CARD",
+            PiiKind::NationalId => "NATIONAL_ID",
+            PiiKind::Email => "EMAIL",
+            PiiKind::Phone => "PHONE",
+        }
+    }
+
+    /// Sensitivity score on the scale of the risk assessment's data sensitivity factor
+    pub fn sensitivity(&self) -> f64 {
+        match self {
+            PiiKind::Iban => 0.9,
+            PiiKind::CardNumber => 0.95,
+            PiiKind::NationalId => 0.9,
+            PiiKind::Email => 0.6,
+            PiiKind::Phone => 0.6,
+        }
+    }
+
+    /// Overlapping matches are resolved in favour of the lower rank
+    fn rank(&self) -> u8 {
+        *self as u8
+    }
+}
+
+/// A detected value, as a byte range of the scanned text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PiiMatch {
+    pub kind: PiiKind,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Whether redaction is enabled (`PII_REDACTION_ENABLED`, default true)
+pub fn enabled_from_env() -> bool {
+    std::env::var("PII_REDACTION_ENABLED")
+        .unwrap_or_else(|_| "true".to_string())
+        .parse::<bool>()
+        .unwrap_or(true)
+}
+
+/// Find personal data in `text`, sorted by position and without overlaps
+///
+/// IBANs and card numbers are validated (mod-97, Luhn). National identifiers are
+/// recognised in US SSN format or after a keyword such as "passport" or "PESEL";
+/// phone numbers with an international prefix or after a keyword such as "tel".
+pub fn detect(text: &str) -> Vec<PiiMatch> {
+    let bytes = text.as_bytes();
+    let mut candidates = Vec::new();
+    candidates.extend(find_ibans(bytes));
+    candidates.extend(find_cards(bytes));
+    candidates.extend(find_national_ids(text));
+    candidates.extend(find_emails(bytes));
+    candidates.extend(find_phones(text));
+
+    candidates.sort_by_key(|m| (m.kind.rank(), m.start));
+    let mut kept: Vec<PiiMatch> = Vec::new();
+    for candidate in candidates {
+        if kept.iter().all(|k| candidate.end <= k.start || candidate.start >= k.end) {
+            kept.push(candidate);
+        }
+    }
+    kept.sort_by_key(|m| m.start);
+    kept
+}
+
+/// Token -> original value mapping of one redacted payload or request
+#[derive(Debug, Clone, Default)]
+pub struct TokenVault {
+    /// token -> value
+    values: HashMap<String, String>,
+    /// value -> token, so a repeated value gets the same token
+    tokens: HashMap<String, String>,
+}
+
+impl TokenVault {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of distinct values replaced
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Distinct values per kind label, e.g. {"EMAIL": 2}
+    pub fn counts(&self) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for token in self.values.keys() {
+            // "[EMAIL_3f9a1c2b]" -> "EMAIL"
+            let label = &token[1..token.len() - 10];
+            *counts.entry(label.to_string()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Replace every detected value in `text` with its token
+    pub fn redact(&mut self, text: &str) -> String {
+        let mut redacted = String::with_capacity(text.len());
+        let mut last = 0;
+        for found in detect(text) {
+            redacted.push_str(&text[last..found.start]);
+            redacted.push_str(&self.token_for(found.kind, &text[found.start..found.end]));
+            last = found.end;
+        }
+        redacted.push_str(&text[last..]);
+        redacted
+    }
+
+    /// Redact every string value of a JSON document in place (object keys are kept)
+    pub fn redact_json(&mut self, value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::String(s) => *s = self.redact(s),
+            serde_json::Value::Array(items) => items.iter_mut().for_each(|item| self.redact_json(item)),
+            serde_json::Value::Object(map) => map.values_mut().for_each(|item| self.redact_json(item)),
+            _ => {}
+        }
+    }
+
+    /// Replace the tokens of this vault in `text` with the original values
+    pub fn rehydrate(&self, text: &str) -> String {
+        // Tokens are ASCII, so replacing them keeps the text valid UTF-8
+        String::from_utf8(self.rehydrate_bytes(text.as_bytes())).unwrap_or_else(|_| text.to_string())
+    }
+
+    /// Byte-level `rehydrate`, for response bodies that may not be split on
+    /// character boundaries
+    pub fn rehydrate_bytes(&self, bytes: &[u8]) -> Vec<u8> {
+        let mut out = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] == b'[' {
+                let close = bytes[i..].iter().take(MAX_TOKEN_LEN).position(|&b| b == b']');
+                let value = close.and_then(|close| {
+                    std::str::from_utf8(&bytes[i..=i + close]).ok().and_then(|token| self.values.get(token))
+                });
+                if let (Some(close), Some(value)) = (close, value) {
+                    out.extend_from_slice(value.as_bytes());
+                    i += close + 1;
+                    continue;
+                }
+            }
+            out.push(bytes[i]);
+            i += 1;
+        }
+        out
+    }
+
+    /// Start of a token cut off at the end of `bytes`. Streams hold the tail
+    /// back until the next chunk completes it.
+    pub fn partial_token_start(bytes: &[u8]) -> Option<usize> {
+        let from = bytes.len().saturating_sub(MAX_TOKEN_LEN - 1);
+        let start = from + bytes[from..].iter().rposition(|&b| b == b'[')?;
+        bytes[start + 1..]
+            .iter()
+            .all(|&b| b.is_ascii_uppercase() || b.is_ascii_digit() || b == b'_' || (b'a'..=b'f').contains(&b))
+            .then_some(start)
+    }
+
+    fn token_for(&mut self, kind: PiiKind, value: &str) -> String {
+        if let Some(token) = self.tokens.get(value) {
+            return token.clone();
+        }
+        loop {
+            let mut id = [0u8; 4];
+            thread_rng().fill_bytes(&mut id);
+            let token = format!(
+                "[{}_{}]",
+                kind.label(),
+                id.iter().map(|b| format!("{:02x}", b)).collect::<String>()
+            );
+            if !self.values.contains_key(&token) {
+                self.values.insert(token.clone(), value.to_string());
+                self.tokens.insert(value.to_string(), token.clone());
+                return token;
+            }
+        }
+    }
+
+    // ========== PERSISTENCE ==========
+    // The mapping is stored like a logged payload: ciphertext in
+    // encrypted_log_payloads, wrapped DEK in encrypted_log_keys, keyed by the
+    // vault id. Shredding that key (or erasing the data subject) destroys it.
+
+    /// Encrypt the mapping through the crypto-shredder and record it in `pii_token_vaults`
+    ///
+    /// With per-subject keys enabled and a `user_id`, the DEK is wrapped by the
+    /// subject's KEK. Returns the vault id.
+    pub async fn store(
+        &self,
+        key_store: &VeridionKeyStore,
+        db_pool: &PgPool,
+        source: &str,
+        agent_id: &str,
+        user_id: Option<&str>,
+        tx_id: Option<&str>,
+    ) -> Result<String, String> {
+        let mapping = serde_json::to_string(&self.values)
+            .map_err(|e| format!("Failed to serialize token vault: {}", e))?;
+        let encrypted = match user_id {
+            Some(user_id) if key_store.subject_keys_enabled() => {
+                key_store.log_event_for_subject(db_pool, user_id, &mapping).await?
+            }
+            _ => key_store.log_event(&mapping).await?,
+        };
+        key_store
+            .persist_key(db_pool, &encrypted.log_id)
+            .await
+            .map_err(|e| format!("Failed to persist token vault key: {}", e))?;
+
+        sqlx::query(
+            "INSERT INTO encrypted_log_payloads (log_id, ciphertext, nonce) VALUES ($1, $2, $3)"
+        )
+        .bind(&encrypted.log_id)
+        .bind(&encrypted.ciphertext)
+        .bind(&encrypted.nonce)
+        .execute(db_pool)
+        .await
+        .map_err(|e| format!("Failed to store token vault: {}", e))?;
+
+        sqlx::query(
+            "INSERT INTO pii_token_vaults (vault_id, source, agent_id, user_id, tx_id, token_counts)
+             VALUES ($1, $2, $3, $4, $5, $6)"
+        )
+        .bind(&encrypted.log_id)
+        .bind(source)
+        .bind(agent_id)
+        .bind(user_id)
+        .bind(tx_id)
+        .bind(serde_json::json!(self.counts()))
+        .execute(db_pool)
+        .await
+        .map_err(|e| format!("Failed to store token vault: {}", e))?;
+
+        Ok(encrypted.log_id)
+    }
+
+    /// Decrypt a stored vault
+    ///
+    /// Fails with "GDPR_PURGED: Data key destroyed" once its key was shredded.
+    pub async fn load(key_store: &VeridionKeyStore, db_pool: &PgPool, vault_id: &str) -> Result<Self, String> {
+        let stored: Option<(Vec<u8>, Vec<u8>)> = sqlx::query_as(
+            "SELECT ciphertext, nonce FROM encrypted_log_payloads WHERE log_id = $1"
+        )
+        .bind(vault_id)
+        .fetch_optional(db_pool)
+        .await
+        .map_err(|e| format!("Failed to load token vault: {}", e))?;
+        let (ciphertext, nonce) = stored.ok_or_else(|| "Token vault not found".to_string())?;
+
+        let encrypted = EncryptedLog {
+            log_id: vault_id.to_string(),
+            ciphertext,
+            nonce,
+        };
+        let mapping = key_store.read_event_persisted(db_pool, &encrypted).await?;
+        let values: HashMap<String, String> = serde_json::from_str(&mapping)
+            .map_err(|e| format!("Invalid token vault: {}", e))?;
+        let tokens = values.iter().map(|(token, value)| (value.clone(), token.clone())).collect();
+        Ok(Self { values, tokens })
+    }
+
+    /// Ids of the vaults holding the redacted values of a log_action record
+    pub async fn ids_for_tx(db_pool: &PgPool, tx_id: &str) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar("SELECT vault_id FROM pii_token_vaults WHERE tx_id = $1")
+            .bind(tx_id)
+            .fetch_all(db_pool)
+            .await
+    }
+
+    /// Put back the values redacted from a log_action record, as far as its
+    /// vaults have not been shredded
+    pub async fn rehydrate_record(key_store: &VeridionKeyStore, db_pool: &PgPool, tx_id: &str, text: &str) -> String {
+        let mut text = text.to_string();
+        for vault_id in Self::ids_for_tx(db_pool, tx_id).await.unwrap_or_default() {
+            if let Ok(vault) = Self::load(key_store, db_pool, &vault_id).await {
+                text = vault.rehydrate(&text);
+            }
+        }
+        text
+    }
+}
+
+// ========== DETECTORS ==========
+// Byte-level scanners. Every pattern is ASCII, so match boundaries always fall
+// on character boundaries of the UTF-8 text.
+
+fn is_word_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric()
+}
+
+fn boundary_before(bytes: &[u8], i: usize) -> bool {
+    i == 0 || !is_word_byte(bytes[i - 1])
+}
+
+fn boundary_after(bytes: &[u8], end: usize) -> bool {
+    end == bytes.len() || !is_word_byte(bytes[end])
+}
+
+/// Runs of `allowed` bytes starting at `start`, separated by exactly one separator
+fn groups(bytes: &[u8], start: usize, allowed: impl Fn(u8) -> bool, separators: &[u8]) -> Vec<(usize, usize)> {
+    let mut groups = Vec::new();
+    let mut pos = start;
+    loop {
+        let group_start = pos;
+        while pos < bytes.len() && allowed(bytes[pos]) {
+            pos += 1;
+        }
+        if pos == group_start {
+            break;
+        }
+        groups.push((group_start, pos));
+        if pos + 1 < bytes.len() && separators.contains(&bytes[pos]) && allowed(bytes[pos + 1]) {
+            pos += 1;
+        } else {
+            break;
+        }
+    }
+    groups
+}
+
+fn find_ibans(bytes: &[u8]) -> Vec<PiiMatch> {
+    let mut found = Vec::new();
+    let mut i = 0;
+    while i + 4 <= bytes.len() {
+        let looks_like_iban = boundary_before(bytes, i)
+            && bytes[i].is_ascii_uppercase()
+            && bytes[i + 1].is_ascii_uppercase()
+            && bytes[i + 2].is_ascii_digit()
+            && bytes[i + 3].is_ascii_digit();
+        if looks_like_iban {
+            let groups = groups(bytes, i, |b| b.is_ascii_uppercase() || b.is_ascii_digit(), b" ");
+            // Printed IBANs come in groups of four: take the longest valid prefix
+            let end = groups.iter().rev().map(|&(_, end)| end).find(|&end| {
+                let compact: Vec<u8> = bytes[i..end].iter().copied().filter(|b| *b != b' ').collect();
+                (15..=34).contains(&compact.len()) && iban_checksum_ok(&compact) && boundary_after(bytes, end)
+            });
+            if let Some(end) = end {
+                found.push(PiiMatch { kind: PiiKind::Iban, start: i, end });
+                i = end;
+                continue;
+            }
+        }
+        i += 1;
+    }
+    found
+}
+
+/// ISO 13616 check: country code and check digits moved to the end, mod 97 == 1
+fn iban_checksum_ok(iban: &[u8]) -> bool {
+    let mut remainder: u32 = 0;
+    for &b in iban[4..].iter().chain(&iban[..4]) {
+        remainder = if b.is_ascii_digit() {
+            (remainder * 10 + (b - b'0') as u32) % 97
+        } else {
+            (remainder * 100 + (b - b'A') as u32 + 10) % 97
+        };
+    }
+    remainder == 1
+}
+
+fn find_cards(bytes: &[u8]) -> Vec<PiiMatch> {
+    let mut found = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i].is_ascii_digit() && boundary_before(bytes, i) && (i == 0 || bytes[i - 1] != b'+') {
+            let groups = groups(bytes, i, |b| b.is_ascii_digit(), b" -");
+            let end = groups.iter().rev().map(|&(_, end)| end).find(|&end| {
+                let digits: Vec<u8> = bytes[i..end].iter().copied().filter(u8::is_ascii_digit).collect();
+                (13..=19).contains(&digits.len()) && luhn_ok(&digits) && boundary_after(bytes, end)
+            });
+            if let Some(end) = end {
+                found.push(PiiMatch { kind: PiiKind::CardNumber, start: i, end });
+                i = end;
+            } else {
+                i = groups[0].1;
+            }
+            continue;
+        }
+        i += 1;
+    }
+    found
+}
+
+fn luhn_ok(digits: &[u8]) -> bool {
+    // Runs of one digit ("0000 0000 0000 0") pass Luhn but are placeholders
+    if digits.iter().all(|&d| d == digits[0]) {
+        return false;
+    }
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| {
+            let d = (d - b'0') as u32;
+            if i % 2 == 1 {
+                if d * 2 > 9 { d * 2 - 9 } else { d * 2 }
+            } else {
+                d
+            }
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+fn find_national_ids(text: &str) -> Vec<PiiMatch> {
+    let bytes = text.as_bytes();
+    let mut found = Vec::new();
+
+    // US social security numbers: 123-45-6789
+    for i in 0..bytes.len().saturating_sub(10) {
+        let candidate = &bytes[i..i + 11];
+        let ssn_shape = candidate.iter().enumerate().all(|(j, &b)| {
+            if j == 3 || j == 6 { b == b'-' } else { b.is_ascii_digit() }
+        });
+        if ssn_shape && &candidate[..3] != b"000" && boundary_before(bytes, i) && boundary_after(bytes, i + 11) {
+            found.push(PiiMatch { kind: PiiKind::NationalId, start: i, end: i + 11 });
+        }
+    }
+
+    // Identifiers announced by a keyword: "passport no. X1234567", "PESEL: 44051401359"
+    for start in values_after_keywords(text, NATIONAL_ID_KEYWORDS) {
+        let run_end = (start..bytes.len())
+            .find(|&j| !(bytes[j].is_ascii_alphanumeric() || matches!(bytes[j], b'/' | b'-' | b'.')))
+            .unwrap_or(bytes.len());
+        let mut end = run_end;
+        while end > start && matches!(bytes[end - 1], b'/' | b'-' | b'.') {
+            end -= 1;
+        }
+        let digits = bytes[start..end].iter().filter(|b| b.is_ascii_digit()).count();
+        if (5..=20).contains(&(end - start)) && digits >= 4 {
+            found.push(PiiMatch { kind: PiiKind::NationalId, start, end });
+        }
+    }
+    found
+}
+
+fn find_emails(bytes: &[u8]) -> Vec<PiiMatch> {
+    let is_local = |b: u8| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'_' | b'%' | b'+' | b'-');
+    let is_domain = |b: u8| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'-');
+
+    let mut found = Vec::new();
+    for at in (0..bytes.len()).filter(|&i| bytes[i] == b'@') {
+        let mut start = at;
+        while start > 0 && is_local(bytes[start - 1]) {
+            start -= 1;
+        }
+        while start < at && bytes[start] == b'.' {
+            start += 1;
+        }
+        let mut end = at + 1;
+        while end < bytes.len() && is_domain(bytes[end]) {
+            end += 1;
+        }
+        while end > at + 1 && matches!(bytes[end - 1], b'.' | b'-') {
+            end -= 1;
+        }
+
+        let domain = &bytes[at + 1..end];
+        let tld = domain.rsplit(|&b| b == b'.').next().unwrap_or_default();
+        let valid = start < at
+            && domain.contains(&b'.')
+            && !domain.windows(2).any(|w| w == b"..")
+            && tld.len() >= 2
+            && tld.iter().all(u8::is_ascii_alphabetic);
+        if valid {
+            found.push(PiiMatch { kind: PiiKind::Email, start, end });
+        }
+    }
+    found
+}
+
+fn find_phones(text: &str) -> Vec<PiiMatch> {
+    let bytes = text.as_bytes();
+    let mut found = Vec::new();
+
+    // International format: +421 905 123 456, +49 (30) 1234567
+    for i in (0..bytes.len()).filter(|&i| bytes[i] == b'+') {
+        if boundary_before(bytes, i) {
+            if let Some((end, digits)) = phone_number_at(bytes, i) {
+                if (8..=15).contains(&digits) {
+                    found.push(PiiMatch { kind: PiiKind::Phone, start: i, end });
+                }
+            }
+        }
+    }
+
+    // National format after a keyword: "tel. 0905 123 456"
+    for start in values_after_keywords(text, PHONE_KEYWORDS) {
+        if let Some((end, digits)) = phone_number_at(bytes, start) {
+            if (7..=15).contains(&digits) {
+                found.push(PiiMatch { kind: PiiKind::Phone, start, end });
+            }
+        }
+    }
+    found
+}
+
+/// End and digit count of a phone number starting at `start` ('+', '(' or a digit)
+fn phone_number_at(bytes: &[u8], start: usize) -> Option<(usize, usize)> {
+    if !(bytes.get(start)?.is_ascii_digit() || matches!(bytes[start], b'+' | b'(')) {
+        return None;
+    }
+    let mut pos = start + 1;
+    let mut end = if bytes[start].is_ascii_digit() { pos } else { start };
+    let mut digits = usize::from(bytes[start].is_ascii_digit());
+    while pos < bytes.len() {
+        let b = bytes[pos];
+        if b.is_ascii_digit() {
+            digits += 1;
+            end = pos + 1;
+        } else if !matches!(b, b' ' | b'-' | b'.' | b'(' | b')') || (b == b' ' && bytes[pos - 1] == b' ') {
+            break;
+        }
+        pos += 1;
+    }
+    (end > start && boundary_after(bytes, end)).then_some((end, digits))
+}
+
+/// Positions where a value may follow one of `keywords` (whole words, any case),
+/// after punctuation and up to three filler words such as "number" or "is"
+fn values_after_keywords(text: &str, keywords: &[&str]) -> Vec<usize> {
+    let lower = text.to_ascii_lowercase();
+    let bytes = lower.as_bytes();
+    let mut positions = Vec::new();
+    for keyword in keywords {
+        for (at, _) in lower.match_indices(keyword) {
+            let keyword_end = at + keyword.len();
+            if !boundary_before(bytes, at) || !boundary_after(bytes, keyword_end) {
+                continue;
+            }
+            let mut pos = keyword_end;
+            let mut fillers = 0;
+            loop {
+                while pos < bytes.len()
+                    && pos - keyword_end < KEYWORD_WINDOW
+                    && !bytes[pos].is_ascii_alphanumeric()
+                    && !matches!(bytes[pos], b'+' | b'(' | b'\n')
+                {
+                    pos += 1;
+                }
+                let word_end = (pos..bytes.len()).find(|&j| !bytes[j].is_ascii_alphanumeric()).unwrap_or(bytes.len());
+                if fillers < 3 && word_end > pos && bytes[pos..word_end].iter().all(u8::is_ascii_alphabetic) {
+                    pos = word_end;
+                    fillers += 1;
+                } else {
+                    break;
+                }
+            }
+            if pos < bytes.len() && pos - keyword_end <= KEYWORD_WINDOW {
+                positions.push(pos);
+            }
+        }
+    }
+    positions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(text: &str) -> Vec<(PiiKind, &str)> {
+        detect(text).into_iter().map(|m| (m.kind, &text[m.start..m.end])).collect()
+    }
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(
+            kinds("Pay to DE89 3704 0044 0532 0130 00 EUR, card 4111 1111 1111 1111."),
+            vec![(PiiKind::Iban, "DE89 3704 0044 0532 0130 00"), (PiiKind::CardNumber, "4111 1111 1111 1111")]
+        );
+        assert_eq!(
+            kinds("Mail jana.novak@example.sk or call +421 905 123 456; tel. 0905 123 456"),
+            vec![
+                (PiiKind::Email, "jana.novak@example.sk"),
+                (PiiKind::Phone, "+421 905 123 456"),
+                (PiiKind::Phone, "0905 123 456"),
+            ]
+        );
+        assert_eq!(
+            kinds("SSN 078-05-1120, passport number is X1234567, PESEL: 44051401359"),
+            vec![
+                (PiiKind::NationalId, "078-05-1120"),
+                (PiiKind::NationalId, "X1234567"),
+                (PiiKind::NationalId, "44051401359"),
+            ]
+        );
+
+        // Failed checksums, ordinary numbers and look-alike words stay untouched
+        assert!(detect("IBAN DE00 3704 0044 0532 0130 00, order 4111 1111 1111 1112").is_empty());
+        assert!(detect("Significant results in 2024: 1234 hotels, v1.2.3@latest").is_empty());
+    }
+
+    #[test]
+    fn test_redact_and_rehydrate() {
+        let mut vault = TokenVault::new();
+        let mut body = serde_json::json!({
+            "messages": [{"role": "user", "content": "Email jana@example.eu twice: jana@example.eu"}]
+        });
+        vault.redact_json(&mut body);
+
+        let content = body["messages"][0]["content"].as_str().unwrap().to_string();
+        assert!(!content.contains("jana@example.eu"));
+        assert_eq!(vault.len(), 1);
+        assert_eq!(vault.counts().get("EMAIL"), Some(&1));
+        assert_eq!(vault.rehydrate(&content), "Email jana@example.eu twice: jana@example.eu");
+
+        // A token cut off by a chunk boundary is held back
+        let token = content.split_whitespace().nth(1).unwrap();
+        let chunk = format!("Reply to {}", &token[..7]);
+        assert_eq!(TokenVault::partial_token_start(chunk.as_bytes()), Some(9));
+        assert_eq!(TokenVault::partial_token_start(b"[1, 2]"), None);
+    }
+}
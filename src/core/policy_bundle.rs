@@ -0,0 +1,935 @@
+// Policy Bundles - policy-as-code export and import
+// The live policy state (active policy versions, asset policies, jurisdiction
+// rules and the system enforcement mode) is written as a directory of JSON
+// files that can be reviewed in Git:
+//
+//   bundle.json                          format marker
+//   enforcement.json                     system enforcement mode
+//   policies/<type>/<tenant>.json        active policy_versions per type and tenant
+//   jurisdiction/<tenant>.json           active JURISDICTION policies
+//   asset_policies/<name>.json           active asset policies, matched by name
+//
+// <tenant> is the company_id or "global". Files contain no IDs or timestamps
+// and keys are sorted, so exporting the same state twice gives identical files.
+//
+// An imported bundle is diffed against the live state and stored as a
+// POLICY_BUNDLE row of policy_versions that needs approval through
+// POST /policies/{id}/approve. Applying it publishes new policy versions and
+// records them in policy_activation_history; the replaced state is kept so the
+// import can be rolled back.
+
+use crate::core::jurisdiction_policy::{JurisdictionPolicy, JURISDICTION_POLICY_TYPE};
+use crate::core::policy_rules::RuleSet;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use sqlx::{PgConnection, PgPool};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// policy_versions.policy_type of imported bundles awaiting approval
+pub const BUNDLE_POLICY_TYPE: &str = "POLICY_BUNDLE";
+pub const ENFORCEMENT_MODES: &[&str] = &["SHADOW", "DRY_RUN", "ENFORCING"];
+
+const MANIFEST_FILE: &str = "bundle.json";
+const ENFORCEMENT_FILE: &str = "enforcement.json";
+const BUNDLE_FORMAT: &str = "veridion-policy-bundle";
+const BUNDLE_VERSION: i64 = 1;
+
+/// Bundle files by relative path
+pub type BundleFiles = BTreeMap<String, String>;
+
+/// Approvals an import needs, not counting the requester (POLICY_BUNDLE_REQUIRED_APPROVALS, default 1)
+pub fn required_approvals() -> i32 {
+    std::env::var("POLICY_BUNDLE_REQUIRED_APPROVALS")
+        .ok()
+        .and_then(|v| v.parse::<i32>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(1)
+}
+
+/// An active policy version (policies/ and jurisdiction/)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+#[serde(deny_unknown_fields)]
+pub struct BundlePolicy {
+    pub policy_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub company_id: Option<Uuid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy_name: Option<String>,
+    /// SHADOW, DRY_RUN or ENFORCING instead of the system mode
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enforcement_mode_override: Option<String>,
+    pub policy_config: Value,
+}
+
+/// An active asset policy (asset_policies/)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema, sqlx::FromRow)]
+#[serde(deny_unknown_fields)]
+pub struct BundleAssetPolicy {
+    pub policy_name: String,
+    pub policy_type: String,
+    pub priority: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub business_function_filter: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub department_filter: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location_filter: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub risk_profile_filter: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset_tags_filter: Option<Vec<String>>,
+    pub policy_config: Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BundleEnforcement {
+    enforcement_mode: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BundleManifest {
+    format: String,
+    version: i64,
+}
+
+/// Policy state described by a bundle
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolicyState {
+    pub policies: Vec<BundlePolicy>,
+    pub asset_policies: Vec<BundleAssetPolicy>,
+    pub enforcement_mode: String,
+}
+
+#[derive(sqlx::FromRow)]
+struct ActivePolicyRow {
+    id: Uuid,
+    #[sqlx(flatten)]
+    policy: BundlePolicy,
+}
+
+#[derive(sqlx::FromRow)]
+struct ActiveAssetPolicyRow {
+    id: Uuid,
+    #[sqlx(flatten)]
+    policy: BundleAssetPolicy,
+}
+
+fn policy_key(policy: &BundlePolicy) -> (String, Option<Uuid>) {
+    (policy.policy_type.clone(), policy.company_id)
+}
+
+/// Pretty JSON with sorted keys and a trailing newline
+fn canonical_json(value: &Value) -> String {
+    fn sorted(value: &Value) -> Value {
+        match value {
+            Value::Object(map) => {
+                let mut keys: Vec<&String> = map.keys().collect();
+                keys.sort();
+                Value::Object(keys.into_iter().map(|k| (k.clone(), sorted(&map[k]))).collect())
+            }
+            Value::Array(items) => Value::Array(items.iter().map(sorted).collect()),
+            other => other.clone(),
+        }
+    }
+    let mut text = serde_json::to_string_pretty(&sorted(value)).unwrap_or_default();
+    text.push('\n');
+    text
+}
+
+fn to_file<T: Serialize>(item: &T) -> String {
+    canonical_json(&serde_json::to_value(item).unwrap_or(Value::Null))
+}
+
+fn tenant_label(company_id: Option<Uuid>) -> String {
+    company_id.map(|c| c.to_string()).unwrap_or_else(|| "global".to_string())
+}
+
+/// File name stem for a policy name
+fn slug(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.chars().flat_map(|c| c.to_lowercase()) {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_matches('-');
+    if slug.is_empty() { "policy".to_string() } else { slug.to_string() }
+}
+
+/// SHA-256 over the paths and contents of the files, in path order
+pub fn digest(files: &BundleFiles) -> String {
+    let mut hasher = Sha256::new();
+    for (path, content) in files {
+        hasher.update(path.as_bytes());
+        hasher.update([0]);
+        hasher.update(content.as_bytes());
+        hasher.update([0]);
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+impl PolicyState {
+    async fn active_policies(conn: &mut PgConnection) -> Result<Vec<ActivePolicyRow>, sqlx::Error> {
+        sqlx::query_as(
+            "SELECT DISTINCT ON (policy_type, company_id)
+                    id, policy_type, company_id, policy_name, enforcement_mode_override, policy_config
+             FROM policy_versions
+             WHERE is_active = true AND policy_type <> $1
+             ORDER BY policy_type, company_id, version_number DESC"
+        )
+        .bind(BUNDLE_POLICY_TYPE)
+        .fetch_all(conn)
+        .await
+    }
+
+    async fn active_asset_policies(conn: &mut PgConnection) -> Result<Vec<ActiveAssetPolicyRow>, sqlx::Error> {
+        sqlx::query_as(
+            "SELECT id, policy_name, policy_type, COALESCE(priority, 100) AS priority,
+                    business_function_filter, department_filter, location_filter,
+                    risk_profile_filter, asset_tags_filter, policy_config
+             FROM asset_policies
+             WHERE is_active = true
+             ORDER BY policy_name, priority, created_at, id"
+        )
+        .fetch_all(conn)
+        .await
+    }
+
+    async fn enforcement_mode(conn: &mut PgConnection) -> Result<String, sqlx::Error> {
+        let mode: Option<String> = sqlx::query_scalar(
+            "SELECT enforcement_mode FROM system_enforcement_mode ORDER BY enabled_at DESC LIMIT 1"
+        )
+        .fetch_optional(conn)
+        .await?;
+        Ok(mode.unwrap_or_else(|| "ENFORCING".to_string()))
+    }
+
+    /// The live policy state
+    pub async fn load(conn: &mut PgConnection) -> Result<Self, sqlx::Error> {
+        let mut policies: Vec<BundlePolicy> = Self::active_policies(conn).await?.into_iter().map(|r| r.policy).collect();
+        policies.sort_by_key(policy_key);
+        Ok(Self {
+            policies,
+            asset_policies: Self::active_asset_policies(conn).await?.into_iter().map(|r| r.policy).collect(),
+            enforcement_mode: Self::enforcement_mode(conn).await?,
+        })
+    }
+
+    pub fn to_files(&self) -> BundleFiles {
+        let mut files = BundleFiles::new();
+        files.insert(
+            MANIFEST_FILE.to_string(),
+            to_file(&BundleManifest { format: BUNDLE_FORMAT.to_string(), version: BUNDLE_VERSION }),
+        );
+        files.insert(
+            ENFORCEMENT_FILE.to_string(),
+            to_file(&BundleEnforcement { enforcement_mode: self.enforcement_mode.clone() }),
+        );
+        for policy in &self.policies {
+            let path = if policy.policy_type == JURISDICTION_POLICY_TYPE {
+                format!("jurisdiction/{}.json", tenant_label(policy.company_id))
+            } else {
+                format!("policies/{}/{}.json", slug(&policy.policy_type), tenant_label(policy.company_id))
+            };
+            files.insert(path, to_file(policy));
+        }
+        for policy in &self.asset_policies {
+            let stem = slug(&policy.policy_name);
+            let mut path = format!("asset_policies/{}.json", stem);
+            let mut n = 2;
+            while files.contains_key(&path) {
+                path = format!("asset_policies/{}-{}.json", stem, n);
+                n += 1;
+            }
+            files.insert(path, to_file(policy));
+        }
+        files
+    }
+
+    /// Parse and validate bundle files
+    pub fn from_files(files: &BundleFiles) -> Result<Self, String> {
+        fn parse<T: serde::de::DeserializeOwned>(path: &str, content: &str) -> Result<T, String> {
+            serde_json::from_str(content).map_err(|e| format!("{}: {}", path, e))
+        }
+
+        let manifest: BundleManifest = parse(
+            MANIFEST_FILE,
+            files.get(MANIFEST_FILE).ok_or("bundle.json is missing; not a policy bundle")?,
+        )?;
+        if manifest.format != BUNDLE_FORMAT || manifest.version != BUNDLE_VERSION {
+            return Err(format!("bundle.json: unsupported format {} version {}", manifest.format, manifest.version));
+        }
+        let enforcement: BundleEnforcement = parse(
+            ENFORCEMENT_FILE,
+            files.get(ENFORCEMENT_FILE).ok_or("enforcement.json is missing")?,
+        )?;
+        if !ENFORCEMENT_MODES.contains(&enforcement.enforcement_mode.as_str()) {
+            return Err(format!("enforcement.json: enforcement_mode must be one of {}", ENFORCEMENT_MODES.join(", ")));
+        }
+
+        let mut policies: Vec<BundlePolicy> = Vec::new();
+        let mut asset_policies: Vec<BundleAssetPolicy> = Vec::new();
+        for (path, content) in files {
+            if path == MANIFEST_FILE || path == ENFORCEMENT_FILE {
+                continue;
+            }
+            if !path.ends_with(".json") {
+                return Err(format!("{}: unexpected file", path));
+            }
+            if path.starts_with("policies/") || path.starts_with("jurisdiction/") {
+                let policy: BundlePolicy = parse(path, content)?;
+                let is_jurisdiction = policy.policy_type == JURISDICTION_POLICY_TYPE;
+                if is_jurisdiction != path.starts_with("jurisdiction/") {
+                    return Err(format!("{}: JURISDICTION policies belong in jurisdiction/, others in policies/", path));
+                }
+                if policy.policy_type.trim().is_empty() || policy.policy_type == BUNDLE_POLICY_TYPE {
+                    return Err(format!("{}: invalid policy_type '{}'", path, policy.policy_type));
+                }
+                if let Some(mode) = &policy.enforcement_mode_override {
+                    if !ENFORCEMENT_MODES.contains(&mode.as_str()) {
+                        return Err(format!("{}: enforcement_mode_override must be one of {}", path, ENFORCEMENT_MODES.join(", ")));
+                    }
+                }
+                if is_jurisdiction {
+                    let rules: JurisdictionPolicy = serde_json::from_value(policy.policy_config.clone())
+                        .map_err(|e| format!("{}: {}", path, e))?;
+                    rules.validate().map_err(|e| format!("{}: {}", path, e))?;
+                }
+                if policies.iter().any(|p| policy_key(p) == policy_key(&policy)) {
+                    return Err(format!("{}: second {} policy for tenant {}", path, policy.policy_type, tenant_label(policy.company_id)));
+                }
+                policies.push(policy);
+            } else if path.starts_with("asset_policies/") {
+                let policy: BundleAssetPolicy = parse(path, content)?;
+                if policy.policy_name.trim().is_empty() {
+                    return Err(format!("{}: policy_name is required", path));
+                }
+                if policy.policy_type == crate::core::asset_policy_engine::RULES_POLICY_TYPE {
+                    RuleSet::parse(&policy.policy_config).map_err(|e| format!("{}: invalid rules: {}", path, e))?;
+                }
+                if asset_policies.iter().any(|p| p.policy_name == policy.policy_name) {
+                    return Err(format!("{}: duplicate asset policy name '{}' (asset policies are matched by name)", path, policy.policy_name));
+                }
+                asset_policies.push(policy);
+            } else {
+                return Err(format!("{}: unexpected file", path));
+            }
+        }
+        policies.sort_by_key(policy_key);
+        asset_policies.sort_by(|a, b| a.policy_name.cmp(&b.policy_name));
+
+        Ok(Self { policies, asset_policies, enforcement_mode: enforcement.enforcement_mode })
+    }
+
+    /// Make the live state match this one, within `conn`'s transaction
+    ///
+    /// Changed policies are published as a new active version and recorded in
+    /// policy_activation_history with the version they replace.
+    async fn apply(&self, conn: &mut PgConnection, performed_by: &str, notes: &str) -> Result<ApplySummary, sqlx::Error> {
+        let mut summary = ApplySummary::default();
+
+        let current: HashMap<(String, Option<Uuid>), ActivePolicyRow> = Self::active_policies(conn)
+            .await?
+            .into_iter()
+            .map(|row| (policy_key(&row.policy), row))
+            .collect();
+        for policy in &self.policies {
+            let previous = current.get(&policy_key(policy));
+            if previous.is_some_and(|row| row.policy == *policy) {
+                continue;
+            }
+            sqlx::query(
+                "UPDATE policy_versions SET is_active = false, deactivated_at = CURRENT_TIMESTAMP
+                 WHERE policy_type = $1 AND company_id IS NOT DISTINCT FROM $2 AND is_active = true"
+            )
+            .bind(&policy.policy_type)
+            .bind(policy.company_id)
+            .execute(&mut *conn)
+            .await?;
+            let id: Uuid = sqlx::query_scalar(
+                "INSERT INTO policy_versions (
+                    policy_type, policy_name, policy_config, version_number, is_active,
+                    company_id, created_by, activated_at, enforcement_mode_override
+                ) SELECT $1, $2, $3, COALESCE(MAX(version_number), 0) + 1, true, $4, $5, CURRENT_TIMESTAMP, $6
+                  FROM policy_versions WHERE policy_type = $1 AND company_id IS NOT DISTINCT FROM $4
+                RETURNING id"
+            )
+            .bind(&policy.policy_type)
+            .bind(&policy.policy_name)
+            .bind(&policy.policy_config)
+            .bind(policy.company_id)
+            .bind(performed_by)
+            .bind(&policy.enforcement_mode_override)
+            .fetch_one(&mut *conn)
+            .await?;
+            record_history(conn, id, "ACTIVATED", performed_by, previous.map(|row| row.id), notes).await?;
+            summary.policies_activated += 1;
+        }
+        for (key, row) in &current {
+            if self.policies.iter().any(|p| policy_key(p) == *key) {
+                continue;
+            }
+            sqlx::query("UPDATE policy_versions SET is_active = false, deactivated_at = CURRENT_TIMESTAMP WHERE id = $1")
+                .bind(row.id)
+                .execute(&mut *conn)
+                .await?;
+            record_history(conn, row.id, "DEACTIVATED", performed_by, None, notes).await?;
+            summary.policies_deactivated += 1;
+        }
+
+        let mut current_assets: HashMap<String, Vec<ActiveAssetPolicyRow>> = HashMap::new();
+        for row in Self::active_asset_policies(conn).await? {
+            current_assets.entry(row.policy.policy_name.clone()).or_default().push(row);
+        }
+        for policy in &self.asset_policies {
+            let rows = current_assets.remove(&policy.policy_name).unwrap_or_default();
+            if let [row] = rows.as_slice() {
+                if row.policy == *policy {
+                    continue;
+                }
+            }
+            match rows.first() {
+                Some(row) => {
+                    sqlx::query(
+                        "UPDATE asset_policies SET policy_type = $2, priority = $3, business_function_filter = $4,
+                             department_filter = $5, location_filter = $6, risk_profile_filter = $7,
+                             asset_tags_filter = $8, policy_config = $9, updated_at = CURRENT_TIMESTAMP
+                         WHERE id = $1"
+                    )
+                    .bind(row.id)
+                    .bind(&policy.policy_type)
+                    .bind(policy.priority)
+                    .bind(&policy.business_function_filter)
+                    .bind(&policy.department_filter)
+                    .bind(&policy.location_filter)
+                    .bind(&policy.risk_profile_filter)
+                    .bind(&policy.asset_tags_filter)
+                    .bind(&policy.policy_config)
+                    .execute(&mut *conn)
+                    .await?;
+                }
+                None => {
+                    sqlx::query(
+                        "INSERT INTO asset_policies (
+                            id, policy_name, policy_type, priority, business_function_filter,
+                            department_filter, location_filter, risk_profile_filter,
+                            asset_tags_filter, policy_config, created_by
+                        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)"
+                    )
+                    .bind(Uuid::new_v4())
+                    .bind(&policy.policy_name)
+                    .bind(&policy.policy_type)
+                    .bind(policy.priority)
+                    .bind(&policy.business_function_filter)
+                    .bind(&policy.department_filter)
+                    .bind(&policy.location_filter)
+                    .bind(&policy.risk_profile_filter)
+                    .bind(&policy.asset_tags_filter)
+                    .bind(&policy.policy_config)
+                    .bind(performed_by)
+                    .execute(&mut *conn)
+                    .await?;
+                }
+            }
+            // Rows sharing the name beyond the first are retired
+            let extra: Vec<Uuid> = rows.iter().skip(1).map(|row| row.id).collect();
+            sqlx::query("UPDATE asset_policies SET is_active = false, updated_at = CURRENT_TIMESTAMP WHERE id = ANY($1)")
+                .bind(&extra)
+                .execute(&mut *conn)
+                .await?;
+            summary.asset_policies_changed += 1;
+        }
+        let removed: Vec<Uuid> = current_assets.values().flatten().map(|row| row.id).collect();
+        if !removed.is_empty() {
+            sqlx::query("UPDATE asset_policies SET is_active = false, updated_at = CURRENT_TIMESTAMP WHERE id = ANY($1)")
+                .bind(&removed)
+                .execute(&mut *conn)
+                .await?;
+            summary.asset_policies_changed += current_assets.len();
+        }
+
+        if Self::enforcement_mode(conn).await? != self.enforcement_mode {
+            sqlx::query(
+                "INSERT INTO system_enforcement_mode (enforcement_mode, description, enabled_by, notes)
+                 VALUES ($1, 'Set by policy bundle import', $2, $3)"
+            )
+            .bind(&self.enforcement_mode)
+            .bind(performed_by)
+            .bind(notes)
+            .execute(&mut *conn)
+            .await?;
+            summary.enforcement_mode_changed = true;
+        }
+
+        Ok(summary)
+    }
+}
+
+async fn record_history(
+    conn: &mut PgConnection,
+    policy_version_id: Uuid,
+    action: &str,
+    performed_by: &str,
+    previous_version_id: Option<Uuid>,
+    notes: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO policy_activation_history (policy_version_id, action, performed_by, previous_version_id, notes)
+         VALUES ($1, $2, $3, $4, $5)"
+    )
+    .bind(policy_version_id)
+    .bind(action)
+    .bind(performed_by)
+    .bind(previous_version_id)
+    .bind(notes)
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+/// One file that an import adds, modifies or removes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BundleChange {
+    pub path: String,
+    /// ADDED, MODIFIED or REMOVED
+    pub change: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Value>,
+}
+
+/// File-level differences from `current` to `incoming`, in path order
+pub fn diff(current: &BundleFiles, incoming: &BundleFiles) -> Vec<BundleChange> {
+    let json = |content: &String| serde_json::from_str(content).ok();
+    let mut paths: Vec<&String> = current.keys().chain(incoming.keys()).collect();
+    paths.sort();
+    paths.dedup();
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let (before, after) = (current.get(path), incoming.get(path));
+            let change = match (before, after) {
+                (None, Some(_)) => "ADDED",
+                (Some(_), None) => "REMOVED",
+                (Some(b), Some(a)) if b != a => "MODIFIED",
+                _ => return None,
+            };
+            Some(BundleChange {
+                path: path.clone(),
+                change: change.to_string(),
+                before: before.and_then(json),
+                after: after.and_then(json),
+            })
+        })
+        .collect()
+}
+
+/// A bundle compared with the live state
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct BundlePlan {
+    pub digest: String,
+    /// Digest of the live state
+    pub base_digest: String,
+    pub changes: Vec<BundleChange>,
+    #[serde(skip)]
+    pub files: BundleFiles,
+}
+
+/// What applying a bundle changed
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct ApplySummary {
+    pub policies_activated: usize,
+    pub policies_deactivated: usize,
+    pub asset_policies_changed: usize,
+    pub enforcement_mode_changed: bool,
+}
+
+/// An imported bundle
+#[derive(Debug, Clone, Serialize, sqlx::FromRow, ToSchema)]
+pub struct PolicyBundleImport {
+    /// ID of the POLICY_BUNDLE policy version to approve
+    pub id: Uuid,
+    pub digest: String,
+    pub base_digest: String,
+    pub diff: Value,
+    /// PENDING_APPROVAL, APPLIED, REJECTED or ROLLED_BACK
+    pub status: String,
+    pub notes: Option<String>,
+    pub requested_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub applied_by: Option<String>,
+    pub applied_at: Option<DateTime<Utc>>,
+    pub rolled_back_by: Option<String>,
+    pub rolled_back_at: Option<DateTime<Utc>>,
+}
+
+const IMPORT_COLUMNS: &str = "id, digest, base_digest, diff, status, notes, requested_by, created_at,
+    applied_by, applied_at, rolled_back_by, rolled_back_at";
+
+/// Why an import could not be applied or rolled back
+#[derive(Debug)]
+pub enum BundleError {
+    NotFound,
+    /// Not enough approvals from users other than the requester
+    NotApproved { approvals: i64, required: i32 },
+    Rejected,
+    /// The import is not in the status the operation needs
+    WrongStatus(String),
+    /// The live state changed since the import was diffed or applied
+    StateChanged,
+    Invalid(String),
+    Database(sqlx::Error),
+}
+
+impl fmt::Display for BundleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BundleError::NotFound => write!(f, "Policy bundle import not found"),
+            BundleError::NotApproved { approvals, required } => write!(
+                f,
+                "Import has {} of {} required approvals (POST /policies/{{id}}/approve; the requester's own approval does not count)",
+                approvals, required
+            ),
+            BundleError::Rejected => write!(f, "Import was rejected"),
+            BundleError::WrongStatus(status) => write!(f, "Import is {}", status),
+            BundleError::StateChanged => write!(f, "Policies changed since the import was diffed; import the bundle again"),
+            BundleError::Invalid(e) => write!(f, "Invalid bundle: {}", e),
+            BundleError::Database(e) => write!(f, "Database error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for BundleError {}
+
+impl From<sqlx::Error> for BundleError {
+    fn from(e: sqlx::Error) -> Self {
+        BundleError::Database(e)
+    }
+}
+
+/// Export, import and rollback of policy bundles
+pub struct PolicyBundles;
+
+impl PolicyBundles {
+    /// Files and digest of the live state
+    pub async fn export(db_pool: &PgPool) -> Result<(String, BundleFiles), sqlx::Error> {
+        let mut conn = db_pool.acquire().await?;
+        let files = PolicyState::load(&mut conn).await?.to_files();
+        Ok((digest(&files), files))
+    }
+
+    /// Validate `files` and diff them against the live state
+    ///
+    /// The plan holds the files as they would be exported, so formatting
+    /// differences do not show up as changes.
+    pub async fn plan(db_pool: &PgPool, files: &BundleFiles) -> Result<BundlePlan, BundleError> {
+        let incoming = PolicyState::from_files(files).map_err(BundleError::Invalid)?.to_files();
+        let (base_digest, current) = Self::export(db_pool).await?;
+        Ok(BundlePlan {
+            digest: digest(&incoming),
+            base_digest,
+            changes: diff(&current, &incoming),
+            files: incoming,
+        })
+    }
+
+    /// Store a plan for approval as a POLICY_BUNDLE policy version
+    pub async fn submit(
+        db_pool: &PgPool,
+        plan: &BundlePlan,
+        requested_by: &str,
+        notes: Option<&str>,
+    ) -> Result<PolicyBundleImport, sqlx::Error> {
+        let mut tx = db_pool.begin().await?;
+        let id: Uuid = sqlx::query_scalar(
+            "INSERT INTO policy_versions (
+                policy_type, policy_name, policy_config, version_number, is_active, created_by,
+                requires_approval, approval_required_count, approval_status
+            ) SELECT $1, $2, $3, COALESCE(MAX(version_number), 0) + 1, false, $4, true, $5, 'PENDING'
+              FROM policy_versions WHERE policy_type = $1
+            RETURNING id"
+        )
+        .bind(BUNDLE_POLICY_TYPE)
+        .bind(format!("Policy bundle {}", &plan.digest[..12]))
+        .bind(serde_json::json!({ "digest": plan.digest, "base_digest": plan.base_digest, "changes": plan.changes.len() }))
+        .bind(requested_by)
+        .bind(required_approvals())
+        .fetch_one(&mut *tx)
+        .await?;
+        let import = sqlx::query_as(&format!(
+            "INSERT INTO policy_bundle_imports (id, digest, files, base_digest, diff, notes, requested_by)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)
+             RETURNING {}",
+            IMPORT_COLUMNS
+        ))
+        .bind(id)
+        .bind(&plan.digest)
+        .bind(serde_json::to_value(&plan.files).unwrap_or_default())
+        .bind(&plan.base_digest)
+        .bind(serde_json::to_value(&plan.changes).unwrap_or_default())
+        .bind(notes)
+        .bind(requested_by)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(import)
+    }
+
+    /// Most recent imports first
+    pub async fn list(db_pool: &PgPool, limit: i64) -> Result<Vec<PolicyBundleImport>, sqlx::Error> {
+        sqlx::query_as(&format!(
+            "SELECT {} FROM policy_bundle_imports ORDER BY created_at DESC LIMIT $1",
+            IMPORT_COLUMNS
+        ))
+        .bind(limit)
+        .fetch_all(db_pool)
+        .await
+    }
+
+    /// Apply an approved import
+    ///
+    /// Fails with StateChanged if the live state is no longer the one the
+    /// import was diffed against.
+    pub async fn apply(db_pool: &PgPool, id: Uuid, performed_by: &str) -> Result<(PolicyBundleImport, ApplySummary), BundleError> {
+        let mut tx = db_pool.begin().await?;
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtext('policy_bundle'))")
+            .execute(&mut *tx)
+            .await?;
+
+        let row: Option<(String, Value, String, String, Option<String>, Option<String>, i32)> = sqlx::query_as(
+            "SELECT i.status, i.files, i.base_digest, i.digest, i.requested_by, pv.approval_status, pv.approval_required_count
+             FROM policy_bundle_imports i JOIN policy_versions pv ON pv.id = i.id
+             WHERE i.id = $1
+             FOR UPDATE OF i"
+        )
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let (status, files, base_digest, bundle_digest, requested_by, approval_status, required) =
+            row.ok_or(BundleError::NotFound)?;
+        if status != "PENDING_APPROVAL" {
+            return Err(BundleError::WrongStatus(status));
+        }
+        if approval_status.as_deref() == Some("REJECTED") {
+            sqlx::query("UPDATE policy_bundle_imports SET status = 'REJECTED' WHERE id = $1")
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+            tx.commit().await?;
+            return Err(BundleError::Rejected);
+        }
+        let approvals: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM policy_approvals
+             WHERE policy_version_id = $1 AND action = 'APPROVED' AND approver_id IS DISTINCT FROM $2"
+        )
+        .bind(id)
+        .bind(&requested_by)
+        .fetch_one(&mut *tx)
+        .await?;
+        if approvals < required as i64 {
+            return Err(BundleError::NotApproved { approvals, required });
+        }
+
+        let current = PolicyState::load(&mut tx).await?.to_files();
+        if digest(&current) != base_digest {
+            return Err(BundleError::StateChanged);
+        }
+        let files: BundleFiles = serde_json::from_value(files).map_err(|e| BundleError::Invalid(e.to_string()))?;
+        let state = PolicyState::from_files(&files).map_err(BundleError::Invalid)?;
+        let notes = format!("Policy bundle {} (import {})", bundle_digest, id);
+        let summary = state.apply(&mut tx, performed_by, &notes).await?;
+
+        let previous_import: Option<Uuid> = sqlx::query_scalar(
+            "SELECT id FROM policy_bundle_imports WHERE status = 'APPLIED' ORDER BY applied_at DESC LIMIT 1"
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+        record_history(&mut tx, id, "ACTIVATED", performed_by, previous_import, &notes).await?;
+        let import = sqlx::query_as(&format!(
+            "UPDATE policy_bundle_imports
+             SET status = 'APPLIED', previous_files = $2, applied_by = $3, applied_at = CURRENT_TIMESTAMP
+             WHERE id = $1
+             RETURNING {}",
+            IMPORT_COLUMNS
+        ))
+        .bind(id)
+        .bind(serde_json::to_value(&current).unwrap_or_default())
+        .bind(performed_by)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok((import, summary))
+    }
+
+    /// Restore the state an applied import replaced
+    ///
+    /// Only possible while the live state is still the one the import produced.
+    pub async fn rollback(
+        db_pool: &PgPool,
+        id: Uuid,
+        performed_by: &str,
+        reason: Option<&str>,
+    ) -> Result<(PolicyBundleImport, ApplySummary), BundleError> {
+        let mut tx = db_pool.begin().await?;
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtext('policy_bundle'))")
+            .execute(&mut *tx)
+            .await?;
+
+        let row: Option<(String, String, Option<Value>)> = sqlx::query_as(
+            "SELECT status, digest, previous_files FROM policy_bundle_imports WHERE id = $1 FOR UPDATE"
+        )
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let (status, bundle_digest, previous_files) = row.ok_or(BundleError::NotFound)?;
+        if status != "APPLIED" {
+            return Err(BundleError::WrongStatus(status));
+        }
+        if digest(&PolicyState::load(&mut tx).await?.to_files()) != bundle_digest {
+            return Err(BundleError::StateChanged);
+        }
+        let files: BundleFiles = previous_files
+            .and_then(|f| serde_json::from_value(f).ok())
+            .ok_or_else(|| BundleError::Invalid("previous state was not recorded".to_string()))?;
+        let state = PolicyState::from_files(&files).map_err(BundleError::Invalid)?;
+        let notes = match reason {
+            Some(reason) => format!("Rollback of policy bundle import {}: {}", id, reason),
+            None => format!("Rollback of policy bundle import {}", id),
+        };
+        let summary = state.apply(&mut tx, performed_by, &notes).await?;
+
+        record_history(&mut tx, id, "ROLLED_BACK", performed_by, None, &notes).await?;
+        let import = sqlx::query_as(&format!(
+            "UPDATE policy_bundle_imports
+             SET status = 'ROLLED_BACK', rolled_back_by = $2, rolled_back_at = CURRENT_TIMESTAMP
+             WHERE id = $1
+             RETURNING {}",
+            IMPORT_COLUMNS
+        ))
+        .bind(id)
+        .bind(performed_by)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok((import, summary))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn state() -> PolicyState {
+        PolicyState {
+            policies: vec![
+                BundlePolicy {
+                    policy_type: JURISDICTION_POLICY_TYPE.to_string(),
+                    company_id: None,
+                    policy_name: Some("EU only".into()),
+                    enforcement_mode_override: None,
+                    policy_config: json!({"allowed_countries": ["CH"], "allow_adequacy_countries": true}),
+                },
+                BundlePolicy {
+                    policy_type: "SOVEREIGN_LOCK".into(),
+                    company_id: None,
+                    policy_name: None,
+                    enforcement_mode_override: Some("SHADOW".into()),
+                    policy_config: json!({"z": 1, "a": {"y": 2, "b": 3}}),
+                },
+            ],
+            asset_policies: vec![BundleAssetPolicy {
+                policy_name: "Credit Scoring / EU".into(),
+                policy_type: "RULES".into(),
+                priority: 10,
+                business_function_filter: Some("CREDIT_SCORING".into()),
+                department_filter: None,
+                location_filter: None,
+                risk_profile_filter: None,
+                asset_tags_filter: None,
+                policy_config: json!({"rules": [{"effect": "DENY", "when": {"field": "region", "equals": "US"}}]}),
+            }],
+            enforcement_mode: "ENFORCING".into(),
+        }
+    }
+
+    #[test]
+    fn test_files_round_trip_deterministically() {
+        let files = state().to_files();
+        assert_eq!(
+            files.keys().collect::<Vec<_>>(),
+            vec![
+                "asset_policies/credit-scoring-eu.json",
+                "bundle.json",
+                "enforcement.json",
+                "jurisdiction/global.json",
+                "policies/sovereign-lock/global.json",
+            ]
+        );
+        assert!(files["policies/sovereign-lock/global.json"].contains("\"a\": {\n      \"b\": 3,\n      \"y\": 2\n    },\n    \"z\": 1"));
+        assert!(files.values().all(|content| content.ends_with("}\n")));
+
+        let parsed = PolicyState::from_files(&files).unwrap();
+        assert_eq!(parsed, state());
+        assert_eq!(digest(&parsed.to_files()), digest(&files));
+    }
+
+    #[test]
+    fn test_diff_reports_file_changes() {
+        let current = state().to_files();
+        let mut changed = state();
+        changed.enforcement_mode = "SHADOW".into();
+        changed.policies.remove(1);
+        changed.asset_policies[0].policy_name = "Credit".into();
+        let changes = diff(&current, &changed.to_files());
+        let summary: Vec<(&str, &str)> = changes.iter().map(|c| (c.path.as_str(), c.change.as_str())).collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("asset_policies/credit-scoring-eu.json", "REMOVED"),
+                ("asset_policies/credit.json", "ADDED"),
+                ("enforcement.json", "MODIFIED"),
+                ("policies/sovereign-lock/global.json", "REMOVED"),
+            ]
+        );
+        assert_eq!(changes[2].after, Some(json!({"enforcement_mode": "SHADOW"})));
+        assert!(diff(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn test_invalid_bundles_are_rejected() {
+        let mutations: Vec<Box<dyn Fn(&mut BundleFiles)>> = vec![
+            Box::new(|f| { f.remove("bundle.json"); }),
+            Box::new(|f| { f.insert("enforcement.json".into(), "{\"enforcement_mode\": \"OFF\"}".into()); }),
+            Box::new(|f| { f.insert("notes.txt".into(), "hi".into()); }),
+            Box::new(|f| { f.insert("policies/x/global.json".into(), f["jurisdiction/global.json"].clone()); }),
+            Box::new(|f| { f.insert("asset_policies/copy.json".into(), f["asset_policies/credit-scoring-eu.json"].clone()); }),
+            Box::new(|f| { f.insert("asset_policies/bad.json".into(),
+                json!({"policy_name": "bad", "policy_type": "RULES", "priority": 1, "policy_config": {"rules": [{"effect": "NOPE"}]}}).to_string()); }),
+            Box::new(|f| { f.insert("jurisdiction/global.json".into(),
+                json!({"policy_type": "JURISDICTION", "policy_config": {}, "unknown": 1}).to_string()); }),
+        ];
+        for (i, mutate) in mutations.iter().enumerate() {
+            let mut files = state().to_files();
+            mutate(&mut files);
+            assert!(PolicyState::from_files(&files).is_err(), "mutation {}", i);
+        }
+    }
+
+    #[test]
+    fn test_slug() {
+        assert_eq!(slug("Credit Scoring - EU Only"), "credit-scoring-eu-only");
+        assert_eq!(slug("SOVEREIGN_LOCK"), "sovereign-lock");
+        assert_eq!(slug("%%"), "policy");
+    }
+}
@@ -0,0 +1,467 @@
+// Policy Rules
+// Declarative rules for the asset policy engine. An asset policy of type RULES
+// carries a rule set in its policy_config:
+//
+//   {"rules": [
+//     {"id": "no-iban-to-us", "effect": "DENY",
+//      "when": {"all": [{"field": "data_class", "contains": "IBAN"},
+//                       {"field": "region", "in": ["US", "CN"]}]},
+//      "reason": "Bank data stays in the EU"},
+//     {"id": "hr-oversight", "effect": "REQUIRE_OVERSIGHT",
+//      "when": {"field": "business_function", "equals": "HR_RECRUITMENT"}}
+//   ]}
+//
+// Rules are evaluated in order (policies by priority, then rule order). The
+// first matching ALLOW or DENY decides; REQUIRE_OVERSIGHT and REDACT matched
+// before it are obligations that add up. Without a deciding rule the request
+// is allowed. log_action, the proxy and the policy simulator all evaluate
+// through `RuleSet::evaluate`.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Outcome of a rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Effect {
+    Allow,
+    Deny,
+    RequireOversight,
+    Redact,
+}
+
+impl Effect {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Effect::Allow => "ALLOW",
+            Effect::Deny => "DENY",
+            Effect::RequireOversight => "REQUIRE_OVERSIGHT",
+            Effect::Redact => "REDACT",
+        }
+    }
+
+    fn parse(value: &str) -> Result<Self, String> {
+        match value.to_uppercase().replace('-', "_").as_str() {
+            "ALLOW" => Ok(Effect::Allow),
+            "DENY" => Ok(Effect::Deny),
+            "REQUIRE_OVERSIGHT" => Ok(Effect::RequireOversight),
+            "REDACT" => Ok(Effect::Redact),
+            other => Err(format!("unknown effect '{}' (ALLOW, DENY, REQUIRE_OVERSIGHT or REDACT)", other)),
+        }
+    }
+}
+
+/// Consent state of the data subject
+pub const CONSENT_GRANTED: &str = "GRANTED";
+pub const CONSENT_MISSING: &str = "MISSING";
+/// No data subject involved (no user_id, proxy traffic)
+pub const CONSENT_NOT_APPLICABLE: &str = "NOT_APPLICABLE";
+
+/// Facts a rule can test
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct RuleContext {
+    pub agent: Option<String>,
+    pub action: Option<String>,
+    pub business_function: Option<String>,
+    /// Personal data categories found in the payload (IBAN, CARD, NATIONAL_ID, EMAIL, PHONE)
+    #[serde(default)]
+    pub data_class: Vec<String>,
+    /// ISO country code of the destination
+    pub region: Option<String>,
+    /// Host of the AI vendor
+    pub vendor: Option<String>,
+    /// GRANTED, MISSING or NOT_APPLICABLE
+    pub consent: Option<String>,
+    /// LOW, MEDIUM, HIGH or CRITICAL
+    pub risk_level: Option<String>,
+    pub tenant: Option<String>,
+}
+
+/// Personal data categories found in `text`, sorted and without duplicates
+pub fn data_classes(text: &str) -> Vec<String> {
+    let mut classes: Vec<String> = crate::core::pii_redaction::detect(text)
+        .iter()
+        .map(|m| m.kind.label().to_string())
+        .collect();
+    classes.sort();
+    classes.dedup();
+    classes
+}
+
+const FIELDS: &[&str] = &[
+    "agent", "action", "business_function", "data_class", "region", "vendor", "consent", "risk_level", "tenant",
+];
+const RISK_LEVELS: &[&str] = &["LOW", "MEDIUM", "HIGH", "CRITICAL"];
+
+/// Comparison of one field
+#[derive(Debug, Clone, PartialEq)]
+pub enum Test {
+    Equals(String),
+    In(Vec<String>),
+    /// Glob with `*`, e.g. `*.openai.com`
+    Matches(String),
+    /// Substring, or for data_class an element
+    Contains(String),
+    /// Risk level at or above
+    AtLeast(usize),
+    Exists(bool),
+}
+
+/// Condition tree of a rule
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    All(Vec<Condition>),
+    Any(Vec<Condition>),
+    Not(Box<Condition>),
+    Field { field: String, test: Test },
+}
+
+fn string_of(value: &Value, what: &str) -> Result<String, String> {
+    match value {
+        Value::String(s) => Ok(s.clone()),
+        Value::Bool(b) => Ok(b.to_string()),
+        Value::Number(n) => Ok(n.to_string()),
+        _ => Err(format!("{} must be a string", what)),
+    }
+}
+
+impl Condition {
+    pub fn parse(value: &Value) -> Result<Self, String> {
+        let object = value.as_object().ok_or("condition must be an object")?;
+        let list = |key: &str, items: &Value| -> Result<Vec<Condition>, String> {
+            let items = items.as_array().ok_or_else(|| format!("'{}' takes a list of conditions", key))?;
+            if items.is_empty() {
+                return Err(format!("'{}' needs at least one condition", key));
+            }
+            items.iter().map(Condition::parse).collect()
+        };
+        if object.len() == 1 {
+            if let Some(items) = object.get("all") {
+                return Ok(Condition::All(list("all", items)?));
+            }
+            if let Some(items) = object.get("any") {
+                return Ok(Condition::Any(list("any", items)?));
+            }
+            if let Some(inner) = object.get("not") {
+                return Ok(Condition::Not(Box::new(Condition::parse(inner)?)));
+            }
+        }
+
+        let field = object
+            .get("field")
+            .and_then(|f| f.as_str())
+            .ok_or("condition needs 'all', 'any', 'not' or a 'field' test")?;
+        if !FIELDS.contains(&field) {
+            return Err(format!("unknown field '{}' (one of {})", field, FIELDS.join(", ")));
+        }
+        let operators: Vec<(&String, &Value)> = object.iter().filter(|(k, _)| k.as_str() != "field").collect();
+        let (operator, operand) = match operators.as_slice() {
+            [single] => *single,
+            _ => return Err(format!("test on '{}' needs exactly one operator", field)),
+        };
+        let test = match operator.as_str() {
+            "equals" => Test::Equals(string_of(operand, "equals")?),
+            "in" => Test::In(
+                operand
+                    .as_array()
+                    .ok_or("'in' takes a list")?
+                    .iter()
+                    .map(|v| string_of(v, "'in' value"))
+                    .collect::<Result<_, _>>()?,
+            ),
+            "matches" => Test::Matches(string_of(operand, "matches")?),
+            "contains" => Test::Contains(string_of(operand, "contains")?),
+            "at_least" => {
+                if field != "risk_level" {
+                    return Err("'at_least' applies to risk_level only".to_string());
+                }
+                let level = string_of(operand, "at_least")?.to_uppercase();
+                Test::AtLeast(
+                    RISK_LEVELS
+                        .iter()
+                        .position(|l| *l == level)
+                        .ok_or_else(|| format!("unknown risk level '{}'", level))?,
+                )
+            }
+            "exists" => Test::Exists(operand.as_bool().ok_or("'exists' takes true or false")?),
+            other => return Err(format!("unknown operator '{}' (equals, in, matches, contains, at_least, exists)", other)),
+        };
+        Ok(Condition::Field { field: field.to_string(), test })
+    }
+
+    pub fn matches(&self, context: &RuleContext) -> bool {
+        match self {
+            Condition::All(conditions) => conditions.iter().all(|c| c.matches(context)),
+            Condition::Any(conditions) => conditions.iter().any(|c| c.matches(context)),
+            Condition::Not(condition) => !condition.matches(context),
+            Condition::Field { field, test } => {
+                if field == "data_class" {
+                    return match test {
+                        Test::Exists(expected) => context.data_class.is_empty() != *expected,
+                        _ => context.data_class.iter().any(|class| test.matches(class)),
+                    };
+                }
+                let value = match field.as_str() {
+                    "agent" => &context.agent,
+                    "action" => &context.action,
+                    "business_function" => &context.business_function,
+                    "region" => &context.region,
+                    "vendor" => &context.vendor,
+                    "consent" => &context.consent,
+                    "risk_level" => &context.risk_level,
+                    "tenant" => &context.tenant,
+                    _ => &None,
+                };
+                match (value.as_deref().filter(|v| !v.is_empty()), test) {
+                    (value, Test::Exists(expected)) => value.is_some() == *expected,
+                    (Some(value), test) => test.matches(value),
+                    (None, _) => false,
+                }
+            }
+        }
+    }
+}
+
+impl Test {
+    /// Comparisons ignore case
+    fn matches(&self, value: &str) -> bool {
+        let value = value.to_uppercase();
+        match self {
+            Test::Equals(expected) => value == expected.to_uppercase(),
+            Test::In(options) => options.iter().any(|o| value == o.to_uppercase()),
+            Test::Matches(pattern) => glob_matches(&pattern.to_uppercase(), &value),
+            Test::Contains(part) => value.contains(&part.to_uppercase()),
+            Test::AtLeast(min) => RISK_LEVELS.iter().position(|l| *l == value).is_some_and(|level| level >= *min),
+            Test::Exists(_) => true,
+        }
+    }
+}
+
+/// `*` matches any run of characters
+fn glob_matches(pattern: &str, value: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('*').collect();
+    if parts.len() == 1 {
+        return pattern == value;
+    }
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if !value.starts_with(first) || value.len() < first.len() + last.len() || !value.ends_with(last) {
+        return false;
+    }
+    let mut rest = &value[first.len()..value.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+/// One rule of a rule set
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+    pub id: String,
+    pub effect: Effect,
+    pub when: Condition,
+    pub reason: Option<String>,
+}
+
+/// Ordered rules of one policy
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RuleSet {
+    pub policy_id: Option<Uuid>,
+    pub policy_name: Option<String>,
+    pub rules: Vec<Rule>,
+}
+
+/// A rule that matched
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MatchedRule {
+    pub policy_id: Option<Uuid>,
+    pub policy_name: Option<String>,
+    pub rule_id: String,
+    pub effect: Effect,
+    pub reason: Option<String>,
+}
+
+/// Result of evaluating rule sets against a request
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RuleVerdict {
+    /// DENY if denied, else REQUIRE_OVERSIGHT or REDACT if required, else ALLOW
+    pub effect: Effect,
+    pub require_oversight: bool,
+    pub redact: bool,
+    /// Matched rules in evaluation order
+    pub matched: Vec<MatchedRule>,
+}
+
+impl RuleVerdict {
+    pub fn allow() -> Self {
+        Self { effect: Effect::Allow, require_oversight: false, redact: false, matched: Vec::new() }
+    }
+
+    pub fn denied(&self) -> bool {
+        self.effect == Effect::Deny
+    }
+
+    /// Reason of the deciding (last matched) rule, or its ID
+    pub fn reason(&self) -> Option<String> {
+        self.matched.last().map(|m| m.reason.clone().unwrap_or_else(|| format!("Rule {}", m.rule_id)))
+    }
+}
+
+impl RuleSet {
+    /// Parse `{"rules": [...]}` (or a bare list of rules)
+    pub fn parse(config: &Value) -> Result<Self, String> {
+        let rules = config
+            .get("rules")
+            .unwrap_or(config)
+            .as_array()
+            .ok_or("policy_config needs a 'rules' list")?;
+        let rules = rules
+            .iter()
+            .enumerate()
+            .map(|(i, rule)| {
+                let at = |e: String| format!("rule {}: {}", i + 1, e);
+                let id = rule
+                    .get("id")
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string())
+                    .unwrap_or_else(|| format!("rule-{}", i + 1));
+                let effect = rule
+                    .get("effect")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| at("'effect' is required".to_string()))
+                    .and_then(|e| Effect::parse(e).map_err(at))?;
+                let when = match rule.get("when") {
+                    Some(when) => Condition::parse(when).map_err(|e| format!("rule {}: {}", id, e))?,
+                    None => Condition::All(Vec::new()),
+                };
+                Ok(Rule {
+                    id,
+                    effect,
+                    when,
+                    reason: rule.get("reason").and_then(|v| v.as_str()).map(|s| s.to_string()),
+                })
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(Self { policy_id: None, policy_name: None, rules })
+    }
+
+    /// Evaluate rule sets in order against one request
+    pub fn evaluate(rule_sets: &[RuleSet], context: &RuleContext) -> RuleVerdict {
+        let mut verdict = RuleVerdict::allow();
+        'sets: for set in rule_sets {
+            for rule in &set.rules {
+                if !rule.when.matches(context) {
+                    continue;
+                }
+                verdict.matched.push(MatchedRule {
+                    policy_id: set.policy_id,
+                    policy_name: set.policy_name.clone(),
+                    rule_id: rule.id.clone(),
+                    effect: rule.effect,
+                    reason: rule.reason.clone(),
+                });
+                match rule.effect {
+                    Effect::RequireOversight => verdict.require_oversight = true,
+                    Effect::Redact => verdict.redact = true,
+                    Effect::Allow | Effect::Deny => {
+                        verdict.effect = rule.effect;
+                        break 'sets;
+                    }
+                }
+            }
+        }
+        if verdict.effect != Effect::Deny {
+            verdict.effect = if verdict.require_oversight {
+                Effect::RequireOversight
+            } else if verdict.redact {
+                Effect::Redact
+            } else {
+                Effect::Allow
+            };
+        }
+        verdict
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rules(config: Value) -> Vec<RuleSet> {
+        vec![RuleSet::parse(&config).unwrap()]
+    }
+
+    fn context() -> RuleContext {
+        RuleContext {
+            agent: Some("credit-bot".into()),
+            action: Some("credit_scoring".into()),
+            data_class: vec!["IBAN".into()],
+            region: Some("US".into()),
+            vendor: Some("api.openai.com".into()),
+            risk_level: Some("HIGH".into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_first_final_rule_decides_and_obligations_add_up() {
+        let sets = rules(json!({"rules": [
+            {"id": "redact-iban", "effect": "REDACT", "when": {"field": "data_class", "contains": "IBAN"}},
+            {"id": "allow-eu", "effect": "ALLOW", "when": {"field": "region", "in": ["DE", "FR"]}},
+            {"id": "deny-openai-high", "effect": "DENY", "reason": "No high-risk data to OpenAI",
+             "when": {"all": [{"field": "vendor", "matches": "*.openai.com"}, {"field": "risk_level", "at_least": "high"}]}},
+            {"id": "never-reached", "effect": "REQUIRE_OVERSIGHT"}
+        ]}));
+        let verdict = RuleSet::evaluate(&sets, &context());
+        assert!(verdict.denied());
+        assert!(verdict.redact);
+        assert!(!verdict.require_oversight);
+        assert_eq!(verdict.reason().as_deref(), Some("No high-risk data to OpenAI"));
+
+        let eu = RuleContext { region: Some("de".into()), ..context() };
+        let verdict = RuleSet::evaluate(&sets, &eu);
+        assert_eq!(verdict.effect, Effect::Redact);
+        assert_eq!(verdict.matched.len(), 2);
+    }
+
+    #[test]
+    fn test_missing_fields_and_negation() {
+        let sets = rules(json!({"rules": [
+            {"effect": "REQUIRE_OVERSIGHT", "when": {"not": {"field": "consent", "equals": "GRANTED"}}},
+            {"effect": "DENY", "when": {"field": "tenant", "exists": true}}
+        ]}));
+        let verdict = RuleSet::evaluate(&sets, &context());
+        assert_eq!(verdict.effect, Effect::RequireOversight);
+        assert_eq!(verdict.matched[0].rule_id, "rule-1");
+        assert!(RuleSet::evaluate(&[], &context()).matched.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_rules_are_rejected() {
+        for config in [
+            json!({"rules": [{"effect": "BLOCK"}]}),
+            json!({"rules": [{"effect": "DENY", "when": {"field": "colour", "equals": "red"}}]}),
+            json!({"rules": [{"effect": "DENY", "when": {"field": "region", "equals": "US", "in": ["CN"]}}]}),
+            json!({"rules": [{"effect": "DENY", "when": {"field": "region", "at_least": "HIGH"}}]}),
+            json!({"rules": [{"effect": "DENY", "when": {"any": []}}]}),
+            json!({"policy": "x"}),
+        ] {
+            assert!(RuleSet::parse(&config).is_err(), "{}", config);
+        }
+    }
+
+    #[test]
+    fn test_glob() {
+        assert!(glob_matches("*.OPENAI.COM", "API.OPENAI.COM"));
+        assert!(glob_matches("CREDIT_*", "CREDIT_SCORING"));
+        assert!(glob_matches("A*B*C", "AXXBYYC"));
+        assert!(!glob_matches("A*B*C", "AC"));
+        assert!(!glob_matches("*.OPENAI.COM", "OPENAI.COM"));
+    }
+}
@@ -1,11 +1,41 @@
 // Policy Simulator - Impact Analysis for Operational Safety
 // Simulates policy changes before enforcement to prevent production breakage
+//
+// Historical compliance_records are replayed against a candidate policy set:
+// revocations, processing restrictions, consent, sovereignty, lockdown scopes,
+// usage budgets, asset policy rules and PII redaction, checked in the order
+// log_action and the proxy apply them. Each simulation type is converted into
+// such a set, and the simulated verdict of every record is compared with the
+// verdict recorded at the time.
+//
+// Records are filtered in SQL and streamed rather than loaded all at once;
+// simulations too long for a request run as background jobs.
 
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, QueryBuilder};
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc, Duration};
-use std::collections::HashMap;
+use futures::TryStreamExt;
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
 use utoipa::ToSchema;
+use uuid::Uuid;
+use crate::core::asset_policy_engine::{AssetContext, AssetPolicyEngine, RULES_POLICY_TYPE};
+use crate::core::jurisdiction_policy::JurisdictionPolicy;
+use crate::core::lockdown::{LockdownScope, LockdownTarget};
+use crate::core::policy_bundle::BundleAssetPolicy;
+use crate::core::policy_rules::{self, Effect, RuleContext, RuleSet, RuleVerdict};
+use crate::core::region_catalog::RegionCatalog;
+use crate::core::sovereign_lock::{SovereigntyResolver, UNKNOWN_COUNTRY};
+use crate::core::usage_budget::{self, BudgetStatus, UsageBudget};
+
+/// Most verdict changes listed in a simulation result
+const MAX_VERDICT_CHANGES: usize = 500;
+/// Seconds between progress reports of a simulation job
+const PROGRESS_INTERVAL_SECS: u64 = 5;
+/// A running job that stopped reporting progress is claimed again after this long
+const JOB_LEASE_SECS: i64 = 300;
+/// Claims of a job before it is given up as FAILED
+const MAX_JOB_ATTEMPTS: i32 = 3;
 
 /// Policy type for simulation
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -15,6 +45,10 @@ pub enum PolicyType {
     AgentRevocation,
     ConsentRequirement,
     ProcessingRestriction,
+    /// Declarative rule set (policy_config as in a RULES asset policy)
+    Rules,
+    /// Any combination of policies (policy_config is a `CandidatePolicySet`)
+    PolicySet,
 }
 
 /// Simulation request
@@ -27,6 +61,10 @@ pub struct SimulationRequest {
     pub business_function_filter: Option<Vec<String>>, // Optional: filter by business function
     pub location_filter: Option<Vec<String>>, // Optional: filter by location/country
     pub time_offset_days: Option<i64>, // Optional: simulate "what if" scenario from N days ago
+    /// Share of records to replay (0 < rate <= 1); the same records are picked for every policy
+    #[serde(default)]
+    #[schema(example = 0.1)]
+    pub sample_rate: Option<f64>,
 }
 
 /// Simulation result showing impact
@@ -41,9 +79,21 @@ pub struct SimulationResult {
     pub requests_by_country: HashMap<String, i64>, // country -> count
     pub requests_by_business_function: Option<HashMap<String, i32>>,
     pub requests_by_location: Option<HashMap<String, i32>>,
+    /// Records per rule effect (simulations with rules only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requests_by_effect: Option<HashMap<String, i64>>,
     pub estimated_impact: ImpactLevel,
     pub critical_agents: Vec<String>, // Agents that would be 100% blocked
     pub partial_impact_agents: Vec<String>, // Agents with mixed traffic
+    /// Records per verdict change, e.g. "ALLOWED -> BLOCKED (POLICY)": 12
+    #[serde(default)]
+    pub verdict_transitions: HashMap<String, i64>,
+    /// Records whose simulated verdict differs from the recorded one (newest first, at most 500)
+    #[serde(default)]
+    pub verdict_changes: Vec<VerdictChange>,
+    /// Share of records replayed when sampled; the counts above cover the sample only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sample_rate: Option<f64>,
     pub simulation_timestamp: DateTime<Utc>,
 }
 
@@ -70,6 +120,234 @@ pub struct AgentImpact {
     pub business_function: Option<String>, // Business function this agent belongs to
 }
 
+/// A record the candidate policy set would have decided differently
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct VerdictChange {
+    pub record_id: Uuid,
+    pub seal_id: String,
+    pub timestamp: DateTime<Utc>,
+    pub agent_id: String,
+    pub action: String,
+    /// Verdict at the time
+    #[schema(example = "ALLOWED")]
+    pub recorded: String,
+    /// Verdict under the candidate policy set
+    #[schema(example = "BLOCKED (POLICY)")]
+    pub simulated: String,
+    /// Rules that matched the record, in evaluation order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub matched_rules: Vec<String>,
+}
+
+/// Usage budget of a candidate policy set
+///
+/// Replayed as a HARD budget (SOFT budgets never change a verdict) against
+/// ai_usage_records, so only proxied requests can run out of budget.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CandidateBudget {
+    /// AGENT or BUSINESS_FUNCTION
+    #[schema(example = "BUSINESS_FUNCTION")]
+    pub scope_type: String,
+    #[schema(example = "CUSTOMER_SERVICE")]
+    pub scope_value: String,
+    /// DAILY or MONTHLY
+    #[serde(default = "default_budget_period")]
+    #[schema(example = "MONTHLY")]
+    pub period: String,
+    #[serde(default)]
+    pub max_tokens: Option<i64>,
+    #[serde(default)]
+    pub max_requests: Option<i64>,
+    #[serde(default)]
+    pub max_cost_eur: Option<f64>,
+}
+
+fn default_budget_period() -> String {
+    "MONTHLY".to_string()
+}
+
+/// Lockdown scope of a candidate policy set
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CandidateLockdown {
+    /// GLOBAL, BUSINESS_FUNCTION, VENDOR_DOMAIN, JURISDICTION or TENANT
+    #[schema(example = "VENDOR_DOMAIN")]
+    pub scope_type: String,
+    #[serde(default)]
+    #[schema(example = "api.openai.com")]
+    pub scope_value: Option<String>,
+}
+
+/// Policies records are replayed against (policy_config of a POLICY_SET simulation)
+///
+/// The set stands on its own: whatever it leaves out is not checked, so a set
+/// holding only asset policies ignores revocations and sovereignty.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CandidatePolicySet {
+    #[serde(default)]
+    pub revoked_agents: Vec<String>,
+    /// Enforce the GDPR Article 18 restrictions in force at the time of each record
+    #[serde(default)]
+    pub processing_restrictions: bool,
+    /// Actions restricted for every data subject
+    #[serde(default)]
+    pub restricted_actions: Vec<String>,
+    /// Consent type required for records of a data subject, e.g. PROCESSING
+    #[serde(default)]
+    pub consent_type: Option<String>,
+    /// Countries to block; unresolved regions are blocked too
+    #[serde(default)]
+    pub blocked_countries: Option<Vec<String>>,
+    /// Jurisdiction policy judging regions (ignored when blocked_countries is set)
+    #[serde(default)]
+    pub jurisdiction: Option<JurisdictionPolicy>,
+    #[serde(default)]
+    pub lockdowns: Vec<CandidateLockdown>,
+    #[serde(default)]
+    pub usage_budgets: Vec<CandidateBudget>,
+    /// Asset policies; RULES policies are matched and evaluated like active ones
+    #[serde(default)]
+    pub asset_policies: Vec<BundleAssetPolicy>,
+    /// Redact personal data as with PII_REDACTION_ENABLED (REDACT rules apply either
+    /// way); the recorded redaction is kept when omitted
+    #[serde(default)]
+    pub pii_redaction: Option<bool>,
+}
+
+impl CandidatePolicySet {
+    /// Policy set equivalent to the policy type and config of a simulation request
+    pub fn from_request(policy_type: &PolicyType, config: &serde_json::Value) -> Result<Self, String> {
+        let strings = |key: &str| -> Option<Vec<String>> {
+            config.get(key).and_then(|v| v.as_array()).map(|arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                    .collect()
+            })
+        };
+
+        let set = match policy_type {
+            PolicyType::SovereignLock => match strings("blocked_countries") {
+                Some(countries) => CandidatePolicySet {
+                    blocked_countries: Some(countries),
+                    ..Default::default()
+                },
+                // An empty jurisdiction policy allows what the live resolver allows
+                None => CandidatePolicySet {
+                    jurisdiction: Some(JurisdictionPolicy::default()),
+                    ..Default::default()
+                },
+            },
+            PolicyType::AgentRevocation => CandidatePolicySet {
+                revoked_agents: strings("revoked_agents")
+                    .ok_or_else(|| "Missing 'revoked_agents' in config".to_string())?,
+                ..Default::default()
+            },
+            PolicyType::ConsentRequirement => CandidatePolicySet {
+                consent_type: Some(
+                    config.get("consent_type").and_then(|v| v.as_str()).unwrap_or("PROCESSING").to_string(),
+                ),
+                ..Default::default()
+            },
+            PolicyType::ProcessingRestriction => CandidatePolicySet {
+                processing_restrictions: true,
+                restricted_actions: strings("restricted_actions").unwrap_or_default(),
+                ..Default::default()
+            },
+            PolicyType::Rules => CandidatePolicySet {
+                asset_policies: vec![BundleAssetPolicy {
+                    policy_name: "Simulated rules".to_string(),
+                    policy_type: RULES_POLICY_TYPE.to_string(),
+                    priority: 0,
+                    business_function_filter: None,
+                    department_filter: None,
+                    location_filter: None,
+                    risk_profile_filter: None,
+                    asset_tags_filter: None,
+                    policy_config: config.clone(),
+                }],
+                ..Default::default()
+            },
+            PolicyType::PolicySet => serde_json::from_value(config.clone())
+                .map_err(|e| format!("Invalid policy set: {}", e))?,
+        };
+        Ok(set)
+    }
+
+    /// Check the set and prepare it for replay
+    fn compile(self) -> Result<ReplayPolicies, String> {
+        if let Some(policy) = &self.jurisdiction {
+            policy.validate()?;
+        }
+
+        let mut lockdowns = Vec::with_capacity(self.lockdowns.len());
+        for lockdown in &self.lockdowns {
+            let scope = LockdownScope::parse(&lockdown.scope_type)
+                .ok_or_else(|| format!("Unknown lockdown scope '{}'", lockdown.scope_type))?;
+            let value = scope
+                .normalize(lockdown.scope_value.as_deref())
+                .ok_or_else(|| format!("Lockdown scope {} needs a scope_value", scope.as_str()))?;
+            lockdowns.push((scope, value));
+        }
+
+        let mut budgets = Vec::with_capacity(self.usage_budgets.len());
+        for budget in self.usage_budgets {
+            if budget.scope_type != usage_budget::SCOPE_AGENT && budget.scope_type != usage_budget::SCOPE_BUSINESS_FUNCTION {
+                return Err(format!("Budget scope_type must be AGENT or BUSINESS_FUNCTION, not '{}'", budget.scope_type));
+            }
+            if budget.period != "DAILY" && budget.period != "MONTHLY" {
+                return Err(format!("Budget period must be DAILY or MONTHLY, not '{}'", budget.period));
+            }
+            if budget.max_tokens.is_none() && budget.max_requests.is_none() && budget.max_cost_eur.is_none() {
+                return Err(format!("Budget for {} {} sets no limit", budget.scope_type, budget.scope_value));
+            }
+            budgets.push(UsageBudget {
+                id: Uuid::nil(),
+                scope_type: budget.scope_type,
+                scope_value: budget.scope_value,
+                period: budget.period,
+                max_tokens: budget.max_tokens,
+                max_requests: budget.max_requests,
+                max_cost_eur: budget.max_cost_eur,
+                soft_limit_percent: 100,
+                enforcement: usage_budget::ENFORCEMENT_HARD.to_string(),
+                is_active: true,
+                created_by: None,
+                updated_at: Utc::now(),
+            });
+        }
+
+        // Same order as get_applicable_policies: lowest priority value first
+        let mut rules = Vec::new();
+        for policy in self.asset_policies {
+            if policy.policy_type != RULES_POLICY_TYPE {
+                continue;
+            }
+            let mut set = RuleSet::parse(&policy.policy_config)
+                .map_err(|e| format!("Invalid rules in '{}': {}", policy.policy_name, e))?;
+            set.policy_name = Some(policy.policy_name.clone());
+            rules.push((policy, set));
+        }
+        rules.sort_by_key(|(policy, _)| policy.priority);
+
+        Ok(ReplayPolicies {
+            revoked_agents: self.revoked_agents.into_iter().collect(),
+            processing_restrictions: self.processing_restrictions,
+            restricted_actions: self.restricted_actions,
+            consent_type: self.consent_type,
+            blocked_countries: self
+                .blocked_countries
+                .map(|countries| countries.iter().map(|c| c.trim().to_uppercase()).collect()),
+            jurisdiction: self.jurisdiction,
+            lockdowns,
+            budgets,
+            rules,
+            pii_redaction: self.pii_redaction,
+        })
+    }
+}
+
 /// Policy Simulator Service
 pub struct PolicySimulator;
 
@@ -77,168 +355,1029 @@ impl PolicySimulator {
     /// Simulate a policy change and return impact analysis
     pub async fn simulate(
         db_pool: &PgPool,
+        resolver: &SovereigntyResolver,
+        request: SimulationRequest,
+    ) -> Result<SimulationResult, String> {
+        Self::run(db_pool, resolver, request, None).await
+    }
+
+    /// Check a request before it is queued as a job
+    pub fn validate(request: &SimulationRequest) -> Result<(), String> {
+        sample_ppm(request.sample_rate)?;
+        CandidatePolicySet::from_request(&request.policy_type, &request.policy_config)?.compile()?;
+        Ok(())
+    }
+
+    /// Replay the records of the window, streamed from the database
+    ///
+    /// Progress of a job is reported every few seconds while records come in.
+    async fn run(
+        db_pool: &PgPool,
+        resolver: &SovereigntyResolver,
         request: SimulationRequest,
+        job_id: Option<Uuid>,
     ) -> Result<SimulationResult, String> {
+        let policies = CandidatePolicySet::from_request(&request.policy_type, &request.policy_config)?.compile()?;
+        let sample_ppm = sample_ppm(request.sample_rate)?;
+
         let time_range = request.time_range_days.unwrap_or(7);
         let time_offset = request.time_offset_days.unwrap_or(0);
         let end_time = Utc::now() - Duration::days(time_offset);
         let start_time = end_time - Duration::days(time_range);
 
-        // Get all compliance records in the time range (simplified - filters applied post-query for now)
-        let records: Vec<ComplianceRecordForSimulation> = sqlx::query_as::<_, ComplianceRecordForSimulation>(
-            "SELECT agent_id, action_summary, status, payload_hash, timestamp 
-             FROM compliance_records 
-             WHERE timestamp >= $1 AND timestamp <= $2
-             ORDER BY timestamp DESC"
-        )
-        .bind(start_time)
-        .bind(end_time)
+        let mut replay = Replay::new(policies, resolver);
+        let filter = RecordFilter::resolve(db_pool, &request, &mut replay, start_time, end_time, sample_ppm).await?;
+
+        if let Some(job_id) = job_id {
+            let mut count = sqlx::QueryBuilder::new("SELECT COUNT(*) FROM compliance_records cr WHERE ");
+            filter.push_conditions(&mut count);
+            let total: i64 = count
+                .build_query_scalar()
+                .fetch_one(db_pool)
+                .await
+                .map_err(|e| format!("Database query failed: {}", e))?;
+            SimulationJobs::report_progress(db_pool, job_id, Some(total), 0).await?;
+        }
+
+        replay.load_budget_usage(db_pool, start_time, end_time).await?;
+        let mut tally = Tally::default();
+
+        // The PII token vault of a redacted log_action payload tells which data classes it held.
+        // The recorded verdict is the status of the latest superseding entry (oversight
+        // rejection, erasure), as in compliance_records_current
+        let mut query = sqlx::QueryBuilder::new(
+            "SELECT cr.id, cr.seal_id, cr.agent_id, cr.action_summary,
+                    COALESCE((SELECT s.status FROM compliance_records s
+                              WHERE s.supersedes_seal_id = cr.seal_id
+                              ORDER BY s.chain_seq DESC LIMIT 1), cr.status) AS status,
+                    cr.payload_hash, cr.risk_level, cr.timestamp, cr.user_id, cr.human_oversight_status,
+                    (SELECT v.token_counts FROM pii_token_vaults v WHERE v.tx_id = cr.tx_id LIMIT 1) AS token_counts
+             FROM compliance_records cr
+             WHERE "
+        );
+        filter.push_conditions(&mut query);
+        query.push(" ORDER BY cr.timestamp DESC");
+
+        let mut records = query.build_query_as::<ComplianceRecordForSimulation>().fetch(db_pool);
+        let mut processed = 0i64;
+        let mut last_report = Instant::now();
+        while let Some(record) = records
+            .try_next()
+            .await
+            .map_err(|e| format!("Database query failed: {}", e))?
+        {
+            processed += 1;
+            if let Some(job_id) = job_id {
+                if last_report.elapsed().as_secs() >= PROGRESS_INTERVAL_SECS {
+                    SimulationJobs::report_progress(db_pool, job_id, None, processed).await?;
+                    last_report = Instant::now();
+                }
+            }
+
+            let record = ReplayRecord::new(record, resolver.region_catalog());
+            replay.load(db_pool, &record).await?;
+            // SQL only narrows down log_action records of agents without an asset,
+            // whose business function is inferred from the action
+            let business_function = replay.business_function(&record);
+            if let Some(functions) = &request.business_function_filter {
+                let matches = business_function.as_deref()
+                    .is_some_and(|bf| functions.iter().any(|f| f.eq_ignore_ascii_case(bf)));
+                if !matches {
+                    continue;
+                }
+            }
+
+            let simulated = replay.replay(&record, business_function.as_deref());
+            tally.add(&record, business_function, &simulated);
+        }
+
+        let mut result = tally.finish(request.policy_type, !replay.policies.rules.is_empty());
+        result.sample_rate = sample_ppm.and(request.sample_rate);
+        Ok(result)
+    }
+
+    /// Calculate impact level based on block percentage
+    fn calculate_impact_level(blocked: i64, total: i64) -> ImpactLevel {
+        if total == 0 {
+            return ImpactLevel::Low;
+        }
+
+        let percentage = (blocked as f64 / total as f64) * 100.0;
+
+        if percentage >= 50.0 {
+            ImpactLevel::Critical
+        } else if percentage >= 20.0 {
+            ImpactLevel::High
+        } else if percentage >= 5.0 {
+            ImpactLevel::Medium
+        } else {
+            ImpactLevel::Low
+        }
+    }
+
+    /// Extract endpoint from action summary
+    fn extract_endpoint(action_summary: &str) -> String {
+        // Try to extract URL/endpoint from action summary
+        // Format might be: "PROXY_BLOCKED: Attempted connection to https://api.openai.com/v1/chat/completions"
+        if let Some(url_start) = action_summary.find("http") {
+            let url_part = &action_summary[url_start..];
+            // Find end of URL (space, newline, or end of string)
+            let url_end = url_part
+                .find(' ')
+                .or_else(|| url_part.find('\n'))
+                .unwrap_or(url_part.len());
+            let url = &url_part[..url_end];
+
+            // Extract hostname manually (simpler than parsing full URL)
+            // Look for "://" and then find next "/" or end
+            if let Some(proto_end) = url.find("://") {
+                let after_proto = &url[proto_end + 3..];
+                if let Some(host_end) = after_proto.find('/') {
+                    return after_proto[..host_end].to_string();
+                } else {
+                    return after_proto.to_string();
+                }
+            }
+        }
+        "unknown".to_string()
+    }
+
+    /// Rule `action` of a record: "proxy" for proxied requests, otherwise the
+    /// action logged by log_action ("<agent>: <action>")
+    fn extract_action(agent_id: &str, action_summary: &str, is_proxy: bool) -> String {
+        if is_proxy {
+            return crate::routes::PROXY_RULE_ACTION.to_string();
+        }
+        action_summary
+            .strip_prefix(agent_id)
+            .and_then(|rest| rest.strip_prefix(": "))
+            .unwrap_or(action_summary)
+            .to_string()
+    }
+}
+
+/// Sample rate as records per million, None when every record is replayed
+fn sample_ppm(sample_rate: Option<f64>) -> Result<Option<i64>, String> {
+    match sample_rate {
+        None => Ok(None),
+        Some(rate) if rate > 0.0 && rate <= 1.0 => {
+            Ok(Some(((rate * 1_000_000.0).round() as i64).max(1)).filter(|ppm| *ppm < 1_000_000))
+        }
+        Some(rate) => Err(format!("sample_rate must be greater than 0 and at most 1, not {}", rate)),
+    }
+}
+
+/// Whether a record's country or region code falls under a location filter
+fn location_matches(locations: &[String], country: &str, region_code: Option<&str>) -> bool {
+    // Cloud regions are grouped under their country; the original region code matches too
+    locations.iter().any(|loc| {
+        let loc = loc.to_uppercase();
+        country.contains(&loc) || region_code.is_some_and(|code| code.contains(&loc))
+    })
+}
+
+/// Conditions selecting the records of a simulation, applied in SQL
+struct RecordFilter {
+    start_time: DateTime<Utc>,
+    end_time: DateTime<Utc>,
+    agents: Option<Vec<String>>,
+    /// Agents whose asset is in a requested business function, and agents
+    /// without an asset whose log_action records may be
+    business_functions: Option<(Vec<String>, Vec<String>)>,
+    /// payload_hash values of the regions in a requested location, and whether
+    /// records without a region (UNKNOWN) match
+    locations: Option<(Vec<String>, bool)>,
+    sample_ppm: Option<i64>,
+}
+
+impl RecordFilter {
+    /// Resolve the location and business function filters of a request into
+    /// the region values and agents they select within the window
+    async fn resolve(
+        db_pool: &PgPool,
+        request: &SimulationRequest,
+        replay: &mut Replay<'_>,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+        sample_ppm: Option<i64>,
+    ) -> Result<Self, String> {
+        let mut filter = RecordFilter {
+            start_time,
+            end_time,
+            agents: request.agent_filter.clone(),
+            business_functions: None,
+            locations: None,
+            sample_ppm,
+        };
+
+        if let Some(locations) = &request.location_filter {
+            let catalog = replay.resolver.region_catalog();
+            let regions = filter
+                .distinct(db_pool, "cr.payload_hash", Some("cr.payload_hash LIKE 'region:%'"))
+                .await?
+                .into_iter()
+                .filter(|payload_hash| {
+                    let (country, region_code) = resolve_region(catalog, &payload_hash["region:".len()..]);
+                    location_matches(locations, &country, region_code.as_deref())
+                })
+                .collect();
+            filter.locations = Some((regions, location_matches(locations, UNKNOWN_COUNTRY, None)));
+        }
+
+        if let Some(functions) = &request.business_function_filter {
+            let mut mapped = Vec::new();
+            let mut unmapped = Vec::new();
+            for agent_id in filter.distinct(db_pool, "cr.agent_id", None).await? {
+                replay.load_asset(db_pool, &agent_id).await;
+                match replay.assets.get(&agent_id) {
+                    Some(Some(asset)) => {
+                        let matches = asset.business_function.as_deref()
+                            .is_some_and(|bf| functions.iter().any(|f| f.eq_ignore_ascii_case(bf)));
+                        if matches {
+                            mapped.push(agent_id);
+                        }
+                    }
+                    _ => unmapped.push(agent_id),
+                }
+            }
+            filter.business_functions = Some((mapped, unmapped));
+        }
+
+        Ok(filter)
+    }
+
+    async fn distinct(&self, db_pool: &PgPool, column: &str, condition: Option<&str>) -> Result<Vec<String>, String> {
+        let mut query = QueryBuilder::new(format!("SELECT DISTINCT {} FROM compliance_records cr WHERE ", column));
+        self.push_conditions(&mut query);
+        if let Some(condition) = condition {
+            query.push(" AND ").push(condition);
+        }
+        query
+            .build_query_scalar()
+            .fetch_all(db_pool)
+            .await
+            .map_err(|e| format!("Database query failed: {}", e))
+    }
+
+    fn push_conditions(&self, query: &mut QueryBuilder<'_, Postgres>) {
+        // Erasure, rectification and oversight entries are not actions of their own
+        query
+            .push("cr.entry_type = 'ACTION' AND cr.timestamp >= ")
+            .push_bind(self.start_time)
+            .push(" AND cr.timestamp <= ")
+            .push_bind(self.end_time);
+        if let Some(agents) = &self.agents {
+            query.push(" AND cr.agent_id = ANY(").push_bind(agents.clone()).push(")");
+        }
+        if let Some((mapped, unmapped)) = &self.business_functions {
+            query
+                .push(" AND (cr.agent_id = ANY(")
+                .push_bind(mapped.clone())
+                .push(") OR (cr.agent_id = ANY(")
+                .push_bind(unmapped.clone())
+                .push(") AND cr.action_summary NOT LIKE 'PROXY\\_%' AND cr.action_summary NOT LIKE 'TEST\\_MODE:%'))");
+        }
+        if let Some((regions, unknown)) = &self.locations {
+            query
+                .push(" AND CASE WHEN cr.payload_hash LIKE 'region:%' THEN cr.payload_hash = ANY(")
+                .push_bind(regions.clone())
+                .push(") ELSE ")
+                .push_bind(*unknown)
+                .push(" END");
+        }
+        if let Some(ppm) = self.sample_ppm {
+            // Hashing the id picks the same records on every run
+            query
+                .push(" AND (hashtext(cr.id::text) & 2147483647) % 1000000 < ")
+                .push_bind(ppm);
+        }
+    }
+}
+
+/// A simulation run in the background
+#[derive(Debug, Clone, Serialize, ToSchema, sqlx::FromRow)]
+pub struct SimulationJob {
+    pub id: Uuid,
+    /// SimulationRequest as submitted
+    pub request: serde_json::Value,
+    /// PENDING, RUNNING, COMPLETED or FAILED
+    #[schema(example = "RUNNING")]
+    pub status: String,
+    pub attempts: i32,
+    /// Records selected by the filters and sample, known once the job started
+    pub records_total: Option<i64>,
+    pub records_processed: i64,
+    #[schema(example = 42.5)]
+    pub progress_percent: Option<f64>,
+    /// SimulationResult of a completed job (left out of job lists)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<serde_json::Value>,
+    pub error: Option<String>,
+    pub requested_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub heartbeat_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+const JOB_COLUMNS: &str = "id, request, status, attempts, records_total, records_processed,
+    CASE WHEN records_total > 0 THEN LEAST(records_processed * 100.0 / records_total, 100)::float8
+         WHEN status = 'COMPLETED' THEN 100.0::float8 END AS progress_percent";
+
+/// Background simulation jobs (policy_simulation_jobs)
+pub struct SimulationJobs;
+
+impl SimulationJobs {
+    /// Queue a simulation (checked with `PolicySimulator::validate` beforehand)
+    pub async fn submit(
+        db_pool: &PgPool,
+        request: &SimulationRequest,
+        requested_by: &str,
+    ) -> Result<SimulationJob, String> {
+        let request = serde_json::to_value(request).map_err(|e| format!("Invalid request: {}", e))?;
+        sqlx::query_as(&format!(
+            "INSERT INTO policy_simulation_jobs (request, requested_by) VALUES ($1, $2)
+             RETURNING {}, result, error, requested_by, created_at, started_at, heartbeat_at, finished_at",
+            JOB_COLUMNS
+        ))
+        .bind(request)
+        .bind(requested_by)
+        .fetch_one(db_pool)
+        .await
+        .map_err(|e| format!("Failed to queue simulation: {}", e))
+    }
+
+    pub async fn get(db_pool: &PgPool, job_id: Uuid) -> Result<Option<SimulationJob>, String> {
+        sqlx::query_as(&format!(
+            "SELECT {}, result, error, requested_by, created_at, started_at, heartbeat_at, finished_at
+             FROM policy_simulation_jobs WHERE id = $1",
+            JOB_COLUMNS
+        ))
+        .bind(job_id)
+        .fetch_optional(db_pool)
+        .await
+        .map_err(|e| format!("Database query failed: {}", e))
+    }
+
+    /// Most recent jobs, without their results
+    pub async fn list(db_pool: &PgPool, limit: i64) -> Result<Vec<SimulationJob>, String> {
+        sqlx::query_as(&format!(
+            "SELECT {}, NULL::jsonb AS result, error, requested_by, created_at, started_at, heartbeat_at, finished_at
+             FROM policy_simulation_jobs ORDER BY created_at DESC LIMIT $1",
+            JOB_COLUMNS
+        ))
+        .bind(limit)
         .fetch_all(db_pool)
         .await
+        .map_err(|e| format!("Database query failed: {}", e))
+    }
+
+    /// Claim the oldest pending job, or a running one whose worker stopped reporting
+    async fn claim(db_pool: &PgPool) -> Result<Option<(Uuid, serde_json::Value)>, String> {
+        sqlx::query(
+            "UPDATE policy_simulation_jobs
+             SET status = 'FAILED', error = 'Worker stopped reporting progress', finished_at = CURRENT_TIMESTAMP
+             WHERE status = 'RUNNING' AND attempts >= $1
+               AND heartbeat_at < CURRENT_TIMESTAMP - make_interval(secs => $2)"
+        )
+        .bind(MAX_JOB_ATTEMPTS)
+        .bind(JOB_LEASE_SECS as f64)
+        .execute(db_pool)
+        .await
         .map_err(|e| format!("Database query failed: {}", e))?;
 
-        // Apply filters in memory (for simplicity - can be optimized with SQL filters later)
-        let records: Vec<ComplianceRecordForSimulation> = records.into_iter()
-            .filter(|r| {
-                // Agent filter
-                if let Some(agent_ids) = &request.agent_filter {
-                    if !agent_ids.contains(&r.agent_id) {
-                        return false;
-                    }
-                }
-                // Location filter
-                if let Some(loc_filters) = &request.location_filter {
-                    let region = r.payload_hash.strip_prefix("region:")
-                        .map(|s| s.to_uppercase())
-                        .unwrap_or_else(|| "UNKNOWN".to_string());
-                    if !loc_filters.iter().any(|loc| region.contains(&loc.to_uppercase())) {
-                        return false;
+        sqlx::query_as(
+            "UPDATE policy_simulation_jobs
+             SET status = 'RUNNING', attempts = attempts + 1, records_processed = 0,
+                 started_at = CURRENT_TIMESTAMP, heartbeat_at = CURRENT_TIMESTAMP
+             WHERE id = (
+                 SELECT id FROM policy_simulation_jobs
+                 WHERE status = 'PENDING'
+                    OR (status = 'RUNNING' AND heartbeat_at < CURRENT_TIMESTAMP - make_interval(secs => $1))
+                 ORDER BY created_at
+                 LIMIT 1
+                 FOR UPDATE SKIP LOCKED
+             )
+             RETURNING id, request"
+        )
+        .bind(JOB_LEASE_SECS as f64)
+        .fetch_optional(db_pool)
+        .await
+        .map_err(|e| format!("Database query failed: {}", e))
+    }
+
+    /// Claim and run one job; false when none was waiting
+    pub async fn run_next(db_pool: &PgPool, resolver: &SovereigntyResolver) -> Result<bool, String> {
+        let (job_id, request) = match Self::claim(db_pool).await? {
+            Some(job) => job,
+            None => return Ok(false),
+        };
+
+        let outcome = match serde_json::from_value::<SimulationRequest>(request) {
+            Ok(request) => PolicySimulator::run(db_pool, resolver, request, Some(job_id)).await,
+            Err(e) => Err(format!("Invalid request: {}", e)),
+        };
+        Self::finish(db_pool, job_id, outcome).await?;
+        Ok(true)
+    }
+
+    async fn report_progress(
+        db_pool: &PgPool,
+        job_id: Uuid,
+        records_total: Option<i64>,
+        records_processed: i64,
+    ) -> Result<(), String> {
+        sqlx::query(
+            "UPDATE policy_simulation_jobs
+             SET records_total = COALESCE($2, records_total), records_processed = $3, heartbeat_at = CURRENT_TIMESTAMP
+             WHERE id = $1"
+        )
+        .bind(job_id)
+        .bind(records_total)
+        .bind(records_processed)
+        .execute(db_pool)
+        .await
+        .map_err(|e| format!("Failed to report simulation progress: {}", e))?;
+        Ok(())
+    }
+
+    async fn finish(db_pool: &PgPool, job_id: Uuid, outcome: Result<SimulationResult, String>) -> Result<(), String> {
+        let (status, result, error) = match outcome {
+            Ok(result) => ("COMPLETED", serde_json::to_value(&result).ok(), None),
+            Err(e) => ("FAILED", None, Some(e)),
+        };
+        sqlx::query(
+            "UPDATE policy_simulation_jobs
+             SET status = $2, result = $3, error = $4, finished_at = CURRENT_TIMESTAMP,
+                 records_processed = CASE WHEN $2 = 'COMPLETED' THEN COALESCE(records_total, records_processed)
+                                          ELSE records_processed END
+             WHERE id = $1"
+        )
+        .bind(job_id)
+        .bind(status)
+        .bind(result)
+        .bind(error)
+        .execute(db_pool)
+        .await
+        .map_err(|e| format!("Failed to store simulation result: {}", e))?;
+        Ok(())
+    }
+}
+
+/// Outcome of a request, recorded or simulated
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Decision {
+    Allowed,
+    /// Logged and held for human review (log_action only)
+    Oversight,
+    /// Refused, with the check that refused it
+    Blocked(Option<String>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Verdict {
+    decision: Decision,
+    /// Personal data in the payload was tokenized
+    redacted: bool,
+}
+
+impl Verdict {
+    fn blocked(reason: &str) -> Decision {
+        Decision::Blocked(Some(reason.to_string()))
+    }
+
+    /// What happened to a record: statuses starting with BLOCKED were refused,
+    /// records with an oversight status were held for review, anything else
+    /// (COMPLIANT, TEST_MODE and SHADOW statuses) went through
+    fn recorded(record: &ReplayRecord) -> Self {
+        let decision = if record.status.starts_with("BLOCKED") {
+            let reason = record.status
+                .split_once('(')
+                .and_then(|(_, rest)| rest.strip_suffix(')'))
+                .map(|reason| reason.trim().to_string());
+            Decision::Blocked(reason)
+        } else if record.human_oversight_status.is_some() {
+            Decision::Oversight
+        } else {
+            Decision::Allowed
+        };
+        Verdict { decision, redacted: record.redacted }
+    }
+
+    /// Stopped or held back (what `would_block` counts)
+    fn blocks(&self) -> bool {
+        self.decision != Decision::Allowed
+    }
+
+    /// e.g. "ALLOWED", "OVERSIGHT [REDACTED]" or "BLOCKED (LOCKDOWN)"
+    fn label(&self) -> String {
+        let decision = match &self.decision {
+            Decision::Allowed => "ALLOWED".to_string(),
+            Decision::Oversight => "OVERSIGHT".to_string(),
+            Decision::Blocked(Some(reason)) => format!("BLOCKED ({})", reason),
+            Decision::Blocked(None) => "BLOCKED".to_string(),
+        };
+        if self.redacted {
+            format!("{} [REDACTED]", decision)
+        } else {
+            decision
+        }
+    }
+}
+
+/// Verdict of a replayed record and the rules behind it
+struct Simulated {
+    recorded: Verdict,
+    verdict: Verdict,
+    effect: Effect,
+    matched_rules: Vec<String>,
+}
+
+/// A candidate policy set ready for replay
+struct ReplayPolicies {
+    revoked_agents: HashSet<String>,
+    processing_restrictions: bool,
+    restricted_actions: Vec<String>,
+    consent_type: Option<String>,
+    blocked_countries: Option<Vec<String>>,
+    jurisdiction: Option<JurisdictionPolicy>,
+    lockdowns: Vec<(LockdownScope, Option<String>)>,
+    budgets: Vec<UsageBudget>,
+    rules: Vec<(BundleAssetPolicy, RuleSet)>,
+    pii_redaction: Option<bool>,
+}
+
+impl ReplayPolicies {
+    /// Rule sets of the RULES policies whose filters match an asset context,
+    /// with the semantics of `AssetPolicyEngine::get_applicable_policies`
+    fn rule_sets(&self, context: &AssetContext) -> Vec<RuleSet> {
+        let same = |filter: &Option<String>, value: &Option<String>| match filter {
+            Some(filter) => value.as_ref() == Some(filter),
+            None => true,
+        };
+        self.rules
+            .iter()
+            .filter(|(policy, _)| {
+                same(&policy.business_function_filter, &context.business_function)
+                    && same(&policy.department_filter, &context.department)
+                    && same(&policy.location_filter, &context.location)
+                    && same(&policy.risk_profile_filter, &context.risk_profile)
+                    && match &policy.asset_tags_filter {
+                        Some(filter) => context.tags.as_ref()
+                            .is_some_and(|tags| tags.iter().any(|tag| filter.contains(tag))),
+                        None => true,
                     }
-                }
-                true
             })
-            .collect();
+            .map(|(_, set)| set.clone())
+            .collect()
+    }
+}
+
+/// A processing restriction and the time it was in force
+struct RestrictionPeriod {
+    restriction_type: String,
+    restricted_actions: Vec<String>,
+    from: DateTime<Utc>,
+    until: Option<DateTime<Utc>>,
+}
+
+/// Usage recorded for the scope of a budget, as running totals
+struct BudgetUsage {
+    budget: UsageBudget,
+    at: Vec<DateTime<Utc>>,
+    /// Tokens and cost before the n-th usage record (one entry more than `at`)
+    tokens: Vec<i64>,
+    cost_eur: Vec<f64>,
+}
+
+impl BudgetUsage {
+    fn new(budget: UsageBudget, usage: Vec<(DateTime<Utc>, i64, f64)>) -> Self {
+        let mut at = Vec::with_capacity(usage.len());
+        let mut tokens = vec![0];
+        let mut cost_eur = vec![0.0];
+        for (created_at, total_tokens, cost) in usage {
+            at.push(created_at);
+            tokens.push(tokens[tokens.len() - 1] + total_tokens);
+            cost_eur.push(cost_eur[cost_eur.len() - 1] + cost);
+        }
+        Self { budget, at, tokens, cost_eur }
+    }
+
+    fn covers(&self, agent_id: &str, business_function: Option<&str>) -> bool {
+        if self.budget.scope_type == usage_budget::SCOPE_BUSINESS_FUNCTION {
+            business_function == Some(self.budget.scope_value.as_str())
+        } else {
+            agent_id == self.budget.scope_value
+        }
+    }
+
+    /// Budget status just before a request at `now`
+    fn status_at(&self, now: DateTime<Utc>) -> BudgetStatus {
+        let start = usage_budget::period_start(&self.budget.period, now);
+        let first = self.at.partition_point(|t| *t < start);
+        let last = self.at.partition_point(|t| *t < now).max(first);
+        BudgetStatus::new(
+            self.budget.clone(),
+            start,
+            self.tokens[last] - self.tokens[first],
+            (last - first) as i64,
+            self.cost_eur[last] - self.cost_eur[first],
+        )
+    }
+}
 
-        let total_requests = records.len() as i64;
+/// Replays records against a candidate policy set
+///
+/// Assets, consents and restrictions are loaded per agent and data subject as
+/// records come in; usage for budgets is loaded once for the whole window.
+struct Replay<'a> {
+    policies: ReplayPolicies,
+    resolver: &'a SovereigntyResolver,
+    /// Asset of each agent (None for agents without one)
+    assets: HashMap<String, Option<AssetContext>>,
+    /// Applicable rule sets per asset policy context
+    rule_sets: HashMap<String, Vec<RuleSet>>,
+    /// Periods each data subject had granted the required consent
+    consents: HashMap<String, Vec<(DateTime<Utc>, Option<DateTime<Utc>>)>>,
+    restrictions: HashMap<String, Vec<RestrictionPeriod>>,
+    budgets: Vec<BudgetUsage>,
+}
+
+impl<'a> Replay<'a> {
+    fn new(policies: ReplayPolicies, resolver: &'a SovereigntyResolver) -> Self {
+        Self {
+            policies,
+            resolver,
+            assets: HashMap::new(),
+            rule_sets: HashMap::new(),
+            consents: HashMap::new(),
+            restrictions: HashMap::new(),
+            budgets: Vec::new(),
+        }
+    }
 
-        // Apply policy simulation based on type
-        match request.policy_type {
-            PolicyType::SovereignLock => {
-                Self::simulate_sovereign_lock(records, &request.policy_config)
+    /// Usage of every budget scope from the start of the first period to the end of the window
+    async fn load_budget_usage(
+        &mut self,
+        db_pool: &PgPool,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+    ) -> Result<(), String> {
+        for budget in &self.policies.budgets {
+            let scope_column = if budget.scope_type == usage_budget::SCOPE_BUSINESS_FUNCTION {
+                "business_function"
+            } else {
+                "agent_id"
+            };
+            let usage: Vec<(DateTime<Utc>, i64, f64)> = sqlx::query_as(&format!(
+                "SELECT created_at, total_tokens, cost_eur::float8
+                 FROM ai_usage_records
+                 WHERE {} = $1 AND created_at >= $2 AND created_at <= $3
+                 ORDER BY created_at",
+                scope_column
+            ))
+            .bind(&budget.scope_value)
+            .bind(usage_budget::period_start(&budget.period, start_time))
+            .bind(end_time)
+            .fetch_all(db_pool)
+            .await
+            .map_err(|e| format!("Usage query failed: {}", e))?;
+            self.budgets.push(BudgetUsage::new(budget.clone(), usage));
+        }
+        Ok(())
+    }
+
+    /// Look up the asset of an agent unless it is cached
+    async fn load_asset(&mut self, db_pool: &PgPool, agent_id: &str) {
+        if self.assets.contains_key(agent_id) {
+            return;
+        }
+        // Like log_action and the proxy, carry on without an asset if the lookup fails
+        let asset = match AssetPolicyEngine::get_asset_context_from_agent(db_pool, agent_id).await {
+            Ok(asset) => asset,
+            Err(e) => {
+                log::warn!("Asset lookup for agent {} failed, simulating without asset: {}", agent_id, e);
+                None
             }
-            PolicyType::AgentRevocation => {
-                Self::simulate_agent_revocation(records, &request.policy_config)
+        };
+        self.assets.insert(agent_id.to_string(), asset);
+    }
+
+    /// Load what replaying a record needs and has not been loaded yet
+    async fn load(&mut self, db_pool: &PgPool, record: &ReplayRecord) -> Result<(), String> {
+        self.load_asset(db_pool, &record.agent_id).await;
+        self.prepare(record);
+
+        if let Some(user_id) = &record.user_id {
+            // Rules see the consent log_action checks when no consent type is set
+            let has_rules = self.rule_sets.get(&self.policy_context(record).0).is_some_and(|sets| !sets.is_empty());
+            let consent_type = match &self.policies.consent_type {
+                Some(consent_type) => Some(consent_type.clone()),
+                None if has_rules => Some("PROCESSING".to_string()),
+                None => None,
+            };
+            if let Some(consent_type) = consent_type {
+                if !self.consents.contains_key(user_id) {
+                    // Consent timestamps are stored without time zone, in UTC
+                    let periods: Vec<(DateTime<Utc>, Option<DateTime<Utc>>)> = sqlx::query_as(
+                        "SELECT COALESCE(granted_at, created_at) AT TIME ZONE 'UTC',
+                                LEAST(withdrawn_at, expires_at) AT TIME ZONE 'UTC'
+                         FROM consent_records
+                         WHERE user_id = $1 AND consent_type = $2 AND granted_at IS NOT NULL"
+                    )
+                    .bind(user_id)
+                    .bind(&consent_type)
+                    .fetch_all(db_pool)
+                    .await
+                    .map_err(|e| format!("Consent query failed: {}", e))?;
+                    self.consents.insert(user_id.clone(), periods);
+                }
             }
-            PolicyType::ConsentRequirement => {
-                Self::simulate_consent_requirement(records, &request.policy_config)
+
+            if self.policies.processing_restrictions && !self.restrictions.contains_key(user_id) {
+                let rows: Vec<(String, serde_json::Value, DateTime<Utc>, Option<DateTime<Utc>>)> = sqlx::query_as(
+                    "SELECT restriction_type, restricted_actions, requested_at, LEAST(lifted_at, expires_at)
+                     FROM processing_restrictions
+                     WHERE user_id = $1"
+                )
+                .bind(user_id)
+                .fetch_all(db_pool)
+                .await
+                .map_err(|e| format!("Restriction query failed: {}", e))?;
+                let periods = rows
+                    .into_iter()
+                    .map(|(restriction_type, actions, from, until)| RestrictionPeriod {
+                        restriction_type,
+                        restricted_actions: actions
+                            .as_array()
+                            .map(|arr| arr.iter().filter_map(|a| a.as_str().map(|s| s.to_string())).collect())
+                            .unwrap_or_default(),
+                        from,
+                        until,
+                    })
+                    .collect();
+                self.restrictions.insert(user_id.clone(), periods);
             }
-            PolicyType::ProcessingRestriction => {
-                Self::simulate_processing_restriction(records, &request.policy_config)
+        }
+
+        Ok(())
+    }
+
+    /// Resolve the applicable rule sets for a record's policy context
+    fn prepare(&mut self, record: &ReplayRecord) {
+        let (key, context) = self.policy_context(record);
+        if !self.rule_sets.contains_key(&key) {
+            let sets = self.policies.rule_sets(&context);
+            self.rule_sets.insert(key, sets);
+        }
+    }
+
+    /// Business function of the agent's asset; log_action infers one from the
+    /// action for agents without an asset, the proxy does not
+    fn business_function(&self, record: &ReplayRecord) -> Option<String> {
+        match self.assets.get(&record.agent_id) {
+            Some(Some(asset)) => asset.business_function.clone(),
+            _ if record.is_proxy => None,
+            _ => AssetPolicyEngine::infer_business_function_from_action(&record.action),
+        }
+    }
+
+    /// Asset context asset policies are matched against, keyed for caching
+    fn policy_context(&self, record: &ReplayRecord) -> (String, AssetContext) {
+        let empty = AssetContext {
+            asset_id: None,
+            business_function: None,
+            department: None,
+            location: None,
+            risk_profile: None,
+            tags: None,
+        };
+        match self.assets.get(&record.agent_id) {
+            Some(Some(asset)) => (format!("agent:{}", record.agent_id), asset.clone()),
+            _ => match self.business_function(record) {
+                Some(bf) => (format!("bf:{}", bf), AssetContext { business_function: Some(bf), ..empty }),
+                None => (String::new(), empty),
+            },
+        }
+    }
+
+    /// Verdict of a record under the candidate policy set
+    fn replay(&self, record: &ReplayRecord, business_function: Option<&str>) -> Simulated {
+        let recorded = Verdict::recorded(record);
+
+        let rule_verdict = match self.rule_sets.get(&self.policy_context(record).0) {
+            Some(sets) if !sets.is_empty() => RuleSet::evaluate(sets, &self.rule_context(record, business_function)),
+            _ => RuleVerdict::allow(),
+        };
+        let decision = self.decide(record, business_function, &rule_verdict, &recorded);
+
+        // Only payloads with a token vault are known to hold personal data
+        let redacted = record.redacted
+            && (rule_verdict.redact || self.policies.pii_redaction.unwrap_or(true));
+
+        Simulated {
+            recorded,
+            verdict: Verdict { decision, redacted },
+            effect: rule_verdict.effect,
+            matched_rules: rule_verdict.matched.iter().map(|m| m.rule_id.clone()).collect(),
+        }
+    }
+
+    fn decide(
+        &self,
+        record: &ReplayRecord,
+        business_function: Option<&str>,
+        rule_verdict: &RuleVerdict,
+        recorded: &Verdict,
+    ) -> Decision {
+        let policies = &self.policies;
+        let at = record.timestamp;
+
+        if policies.revoked_agents.contains(&record.agent_id) {
+            return Verdict::blocked("REVOKED");
+        }
+
+        if let Some(user_id) = &record.user_id {
+            let restricted = policies.restricted_actions.contains(&record.action)
+                || self.restrictions.get(user_id).is_some_and(|periods| {
+                    periods.iter().any(|p| {
+                        p.from <= at
+                            && p.until.map_or(true, |until| until > at)
+                            && match p.restriction_type.as_str() {
+                                "FULL" => true,
+                                "PARTIAL" | "SPECIFIC_ACTION" => p.restricted_actions.contains(&record.action),
+                                _ => false,
+                            }
+                    })
+                });
+            if restricted {
+                return Verdict::blocked("RESTRICTION");
+            }
+
+            if policies.consent_type.is_some() && !self.consent_granted(user_id, at) {
+                return Verdict::blocked("CONSENT");
             }
         }
-        .map(|mut result| {
-            result.total_requests = total_requests;
-            result.policy_type = request.policy_type.clone();
-            result.simulation_timestamp = Utc::now();
-            result.estimated_impact = Self::calculate_impact_level(
-                result.would_block,
-                total_requests,
-            );
-            
-            // Enrich agent impacts with business function data (async lookup)
-            // Note: This is done synchronously here - in production, consider batching
-            for agent_impact in &mut result.affected_agents {
-                if agent_impact.business_function.is_none() {
-                    // Will be populated by async lookup if available
-                    agent_impact.business_function = None;
-                }
+
+        if policies.blocked_countries.is_some() || policies.jurisdiction.is_some() {
+            let violated = match &record.country {
+                Some(country) => match (&policies.blocked_countries, &policies.jurisdiction) {
+                    (Some(blocked), _) => country == UNKNOWN_COUNTRY || blocked.contains(country),
+                    (None, Some(jurisdiction)) => {
+                        let region = record.region_code.as_deref().unwrap_or(country);
+                        !jurisdiction.evaluate(self.resolver, region, &record.agent_id, at).allowed
+                    }
+                    (None, None) => false,
+                },
+                // log_action does not store the target region: keep the recorded outcome
+                None => recorded.decision == Verdict::blocked("SOVEREIGNTY"),
+            };
+            if violated {
+                return Verdict::blocked("SOVEREIGNTY");
             }
-            
-            result
+        }
+
+        let target = LockdownTarget {
+            business_function,
+            vendor_host: record.vendor.as_deref(),
+            country: record.country.as_deref().filter(|c| *c != UNKNOWN_COUNTRY),
+            tenant: None,
+        };
+        if policies.lockdowns.iter().any(|(scope, value)| scope.matches(value.as_deref(), &target)) {
+            return Verdict::blocked("LOCKDOWN");
+        }
+
+        // Budgets are enforced by the proxy only
+        if record.is_proxy
+            && self.budgets.iter().any(|b| b.covers(&record.agent_id, business_function) && b.status_at(at).blocks())
+        {
+            return Verdict::blocked("BUDGET");
+        }
+
+        if rule_verdict.denied() {
+            return Verdict::blocked("POLICY");
+        }
+        if rule_verdict.require_oversight {
+            // A proxied call cannot wait for a reviewer
+            return if record.is_proxy { Verdict::blocked("POLICY") } else { Decision::Oversight };
+        }
+        Decision::Allowed
+    }
+
+    /// Whether the user had consent in force at the given time
+    fn consent_granted(&self, user_id: &str, at: DateTime<Utc>) -> bool {
+        self.consents.get(user_id).is_some_and(|periods| {
+            periods.iter().any(|(from, until)| *from <= at && until.is_none_or(|until| until > at))
         })
     }
 
-    /// Simulate Sovereign Lock policy
-    fn simulate_sovereign_lock(
-        records: Vec<ComplianceRecordForSimulation>,
-        config: &serde_json::Value,
-    ) -> Result<SimulationResult, String> {
-        // Extract blocked countries from config
-        let blocked_countries: Vec<String> = config
-            .get("blocked_countries")
-            .and_then(|v| v.as_array())
-            .map(|arr| {
-                arr.iter()
-                    .filter_map(|v| v.as_str().map(|s| s.to_uppercase()))
-                    .collect()
-            })
-            .unwrap_or_else(|| vec!["US".to_string(), "CN".to_string(), "RU".to_string()]);
-
-        let mut would_block = 0;
-        let mut would_allow = 0;
-        let mut agent_stats: HashMap<String, (i64, i64)> = HashMap::new(); // (blocked, total)
-        let mut endpoint_stats: HashMap<String, i64> = HashMap::new();
-        let mut country_stats: HashMap<String, i64> = HashMap::new();
-
-        for record in &records {
-            // Extract region from payload_hash (format: "region:US" or "region:DE")
-            let region = record
-                .payload_hash
-                .strip_prefix("region:")
-                .map(|s| s.to_uppercase())
-                .unwrap_or_else(|| "UNKNOWN".to_string());
-
-            // Extract endpoint from action_summary
-            let endpoint = Self::extract_endpoint(&record.action_summary);
-
-            // Check if region would be blocked
-            let is_blocked = blocked_countries.contains(&region) || region == "UNKNOWN";
-
-            if is_blocked {
-                would_block += 1;
-                let stats = agent_stats.entry(record.agent_id.clone()).or_insert((0, 0));
-                stats.0 += 1;
-            } else {
-                would_allow += 1;
+        fn rule_context(&self, record: &ReplayRecord, business_function: Option<&str>) -> RuleContext {
+        RuleContext {
+            agent: Some(record.agent_id.clone()),
+            action: Some(record.action.clone()),
+            business_function: business_function.map(|bf| bf.to_string()),
+            data_class: record.data_class.clone(),
+            region: record.country.clone().filter(|c| c != UNKNOWN_COUNTRY),
+            vendor: record.vendor.clone(),
+            consent: Some(match &record.user_id {
+                Some(user_id) if self.consent_granted(user_id, record.timestamp) => policy_rules::CONSENT_GRANTED,
+                Some(_) => policy_rules::CONSENT_MISSING,
+                None => policy_rules::CONSENT_NOT_APPLICABLE,
+            }.to_string()),
+            risk_level: record.risk_level.clone(),
+            tenant: None,
+        }
+    }
+}
+
+/// Running totals of a simulation
+#[derive(Default)]
+struct Tally {
+    total: i64,
+    would_block: i64,
+    agents: HashMap<String, AgentTally>,
+    endpoints: HashMap<String, i64>,
+    countries: HashMap<String, i64>,
+    business_functions: HashMap<String, i32>,
+    effects: HashMap<String, i64>,
+    transitions: HashMap<String, i64>,
+    changes: Vec<VerdictChange>,
+}
+
+#[derive(Default)]
+struct AgentTally {
+    total: i64,
+    blocked: i64,
+    endpoints: HashSet<String>,
+    business_function: Option<String>,
+}
+
+impl Tally {
+    fn add(&mut self, record: &ReplayRecord, business_function: Option<String>, simulated: &Simulated) {
+        let endpoint = PolicySimulator::extract_endpoint(&record.action_summary);
+        let blocked = simulated.verdict.blocks();
+
+        self.total += 1;
+        if blocked {
+            self.would_block += 1;
+        }
+        let agent = self.agents.entry(record.agent_id.clone()).or_default();
+        agent.total += 1;
+        if blocked {
+            agent.blocked += 1;
+        }
+        agent.endpoints.insert(endpoint.clone());
+        if agent.business_function.is_none() {
+            agent.business_function = business_function.clone();
+        }
+
+        *self.endpoints.entry(endpoint).or_insert(0) += 1;
+        *self.countries
+            .entry(record.country.clone().unwrap_or_else(|| UNKNOWN_COUNTRY.to_string()))
+            .or_insert(0) += 1;
+        if let Some(bf) = business_function {
+            *self.business_functions.entry(bf).or_insert(0) += 1;
+        }
+        *self.effects.entry(simulated.effect.as_str().to_string()).or_insert(0) += 1;
+
+        if simulated.verdict != simulated.recorded {
+            let recorded = simulated.recorded.label();
+            let verdict = simulated.verdict.label();
+            *self.transitions.entry(format!("{} -> {}", recorded, verdict)).or_insert(0) += 1;
+            if self.changes.len() < MAX_VERDICT_CHANGES {
+                self.changes.push(VerdictChange {
+                    record_id: record.id,
+                    seal_id: record.seal_id.clone(),
+                    timestamp: record.timestamp,
+                    agent_id: record.agent_id.clone(),
+                    action: record.action.clone(),
+                    recorded,
+                    simulated: verdict,
+                    matched_rules: simulated.matched_rules.clone(),
+                });
             }
-            let stats = agent_stats.entry(record.agent_id.clone()).or_insert((0, 0));
-            stats.1 += 1;
-            
-            *endpoint_stats.entry(endpoint).or_insert(0) += 1;
-            *country_stats.entry(region).or_insert(0) += 1;
         }
+    }
 
-        // Build agent impact list
-        let mut agent_impacts: Vec<AgentImpact> = agent_stats
-            .iter()
-            .map(|(agent_id, (blocked, total))| {
-                let block_percentage = if *total > 0 {
-                    (*blocked as f64 / *total as f64) * 100.0
+    fn finish(self, policy_type: PolicyType, has_rules: bool) -> SimulationResult {
+        let mut agent_impacts: Vec<AgentImpact> = self.agents
+            .into_iter()
+            .map(|(agent_id, agent)| {
+                let block_percentage = if agent.total > 0 {
+                    (agent.blocked as f64 / agent.total as f64) * 100.0
                 } else {
                     0.0
                 };
-
-                // Get affected endpoints for this agent
-                let affected_endpoints: Vec<String> = records
-                    .iter()
-                    .filter(|r| r.agent_id == *agent_id)
-                    .map(|r| Self::extract_endpoint(&r.action_summary))
-                    .collect::<std::collections::HashSet<_>>()
-                    .into_iter()
-                    .collect();
-
-                // Get business function for this agent (async, but we'll do it synchronously here)
-                // Note: This is a simplified approach - in production, you might want to batch these lookups
-                let business_function = None; // Will be populated in the calling function if needed
-                
+                let mut affected_endpoints: Vec<String> = agent.endpoints.into_iter().collect();
+                affected_endpoints.sort();
                 AgentImpact {
-                    agent_id: agent_id.clone(),
-                    total_requests: *total,
-                    would_block: *blocked,
-                    would_allow: *total - *blocked,
+                    agent_id,
+                    total_requests: agent.total,
+                    would_block: agent.blocked,
+                    would_allow: agent.total - agent.blocked,
                     block_percentage,
                     affected_endpoints,
-                    business_function,
+                    business_function: agent.business_function,
                 }
             })
             .collect();
@@ -254,226 +1393,318 @@ impl PolicySimulator {
             .filter(|a| a.block_percentage == 100.0 && a.total_requests > 0)
             .map(|a| a.agent_id.clone())
             .collect();
-
         let partial_impact_agents: Vec<String> = agent_impacts
             .iter()
             .filter(|a| a.block_percentage > 0.0 && a.block_percentage < 100.0)
             .map(|a| a.agent_id.clone())
             .collect();
 
-        Ok(SimulationResult {
-            policy_type: PolicyType::SovereignLock,
-            total_requests: records.len() as i64,
-            would_block,
-            would_allow,
+        SimulationResult {
+            policy_type,
+            total_requests: self.total,
+            would_block: self.would_block,
+            would_allow: self.total - self.would_block,
             affected_agents: agent_impacts,
-            affected_endpoints: endpoint_stats,
-            requests_by_country: country_stats,
-            requests_by_business_function: None,
+            affected_endpoints: self.endpoints,
+            requests_by_country: self.countries,
+            requests_by_business_function: Some(self.business_functions).filter(|m| !m.is_empty()),
             requests_by_location: None,
-            estimated_impact: ImpactLevel::Low, // Will be calculated later
+            requests_by_effect: has_rules.then_some(self.effects),
+            estimated_impact: PolicySimulator::calculate_impact_level(self.would_block, self.total),
             critical_agents,
             partial_impact_agents,
+            verdict_transitions: self.transitions,
+            verdict_changes: self.changes,
+            sample_rate: None,
             simulation_timestamp: Utc::now(),
-        })
+        }
     }
+}
 
-    /// Simulate Agent Revocation policy
-    fn simulate_agent_revocation(
-        records: Vec<ComplianceRecordForSimulation>,
-        config: &serde_json::Value,
-    ) -> Result<SimulationResult, String> {
-        let revoked_agents: Vec<String> = config
-            .get("revoked_agents")
-            .and_then(|v| v.as_array())
-            .map(|arr| {
-                arr.iter()
-                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                    .collect()
-            })
-            .ok_or_else(|| "Missing 'revoked_agents' in config".to_string())?;
-
-        let mut would_block = 0;
-        let mut would_allow = 0;
-        let mut agent_stats: HashMap<String, (i64, i64)> = HashMap::new();
-        let mut endpoint_stats: HashMap<String, i64> = HashMap::new();
-        let mut country_stats: HashMap<String, i64> = HashMap::new();
-
-        for record in &records {
-            let is_blocked = revoked_agents.contains(&record.agent_id);
-            let endpoint = Self::extract_endpoint(&record.action_summary);
-            let region = record
-                .payload_hash
-                .strip_prefix("region:")
-                .map(|s| s.to_uppercase())
-                .unwrap_or_else(|| "UNKNOWN".to_string());
-
-            if is_blocked {
-                would_block += 1;
-                let stats = agent_stats.entry(record.agent_id.clone()).or_insert((0, 0));
-                stats.0 += 1;
-            } else {
-                would_allow += 1;
+/// Internal struct for simulation queries
+#[derive(sqlx::FromRow)]
+struct ComplianceRecordForSimulation {
+    id: Uuid,
+    seal_id: String,
+    agent_id: String,
+    action_summary: String,
+    status: String,
+    payload_hash: String,
+    risk_level: Option<String>,
+    timestamp: DateTime<Utc>,
+    user_id: Option<String>,
+    human_oversight_status: Option<String>,
+    token_counts: Option<serde_json::Value>,
+}
+
+/// A compliance record with what the policy checks look at
+struct ReplayRecord {
+    id: Uuid,
+    seal_id: String,
+    timestamp: DateTime<Utc>,
+    agent_id: String,
+    action_summary: String,
+    status: String,
+    risk_level: Option<String>,
+    user_id: Option<String>,
+    human_oversight_status: Option<String>,
+    /// Rule action ("proxy" for proxied requests)
+    action: String,
+    is_proxy: bool,
+    /// ISO country a proxied request went to (UNKNOWN if unresolved); log_action
+    /// records do not keep their target region
+    country: Option<String>,
+    /// Cloud region code the country was derived from
+    region_code: Option<String>,
+    /// Vendor host of a proxied request
+    vendor: Option<String>,
+    /// Data classes tokenized in the payload
+    data_class: Vec<String>,
+    /// The payload was redacted into a PII token vault
+    redacted: bool,
+}
+
+impl ReplayRecord {
+    fn new(row: ComplianceRecordForSimulation, catalog: &RegionCatalog) -> Self {
+        let is_proxy = row.action_summary.starts_with("PROXY_") || row.action_summary.starts_with("TEST_MODE:");
+
+        let (country, region_code) = match row.payload_hash.strip_prefix("region:") {
+            Some(region) => {
+                let (country, region_code) = resolve_region(catalog, region);
+                (Some(country), region_code)
             }
-            let stats = agent_stats.entry(record.agent_id.clone()).or_insert((0, 0));
-            stats.1 += 1;
-            *endpoint_stats.entry(endpoint).or_insert(0) += 1;
-            *country_stats.entry(region).or_insert(0) += 1;
+            None if is_proxy => (Some(UNKNOWN_COUNTRY.to_string()), None),
+            None => (None, None),
+        };
+
+        let vendor = Some(PolicySimulator::extract_endpoint(&row.action_summary))
+            .filter(|endpoint| is_proxy && endpoint != "unknown");
+        let mut data_class: Vec<String> = row.token_counts
+            .as_ref()
+            .and_then(|counts| counts.as_object())
+            .map(|counts| counts.keys().cloned().collect())
+            .unwrap_or_default();
+        data_class.sort();
+
+        Self {
+            action: PolicySimulator::extract_action(&row.agent_id, &row.action_summary, is_proxy),
+            redacted: row.token_counts.is_some(),
+            id: row.id,
+            seal_id: row.seal_id,
+            timestamp: row.timestamp,
+            agent_id: row.agent_id,
+            action_summary: row.action_summary,
+            status: row.status,
+            risk_level: row.risk_level,
+            user_id: row.user_id,
+            human_oversight_status: row.human_oversight_status,
+            is_proxy,
+            country,
+            region_code,
+            vendor,
+            data_class,
         }
+    }
+}
 
-        let agent_impacts: Vec<AgentImpact> = agent_stats
-            .iter()
-            .map(|(agent_id, (blocked, total))| {
-                let block_percentage = if *total > 0 {
-                    (*blocked as f64 / *total as f64) * 100.0
-                } else {
-                    0.0
-                };
+/// Country and cloud region code of a recorded region; cloud region codes
+/// are grouped under their country (eu-central-1 -> DE)
+fn resolve_region(catalog: &RegionCatalog, region: &str) -> (String, Option<String>) {
+    match catalog.lookup(region) {
+        Some(cloud_region) => (cloud_region.country.to_uppercase(), Some(cloud_region.region_code.to_uppercase())),
+        None => (region.to_uppercase(), None),
+    }
+}
 
-                let affected_endpoints: Vec<String> = records
-                    .iter()
-                    .filter(|r| r.agent_id == *agent_id)
-                    .map(|r| Self::extract_endpoint(&r.action_summary))
-                    .collect::<std::collections::HashSet<_>>()
-                    .into_iter()
-                    .collect();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use serde_json::json;
 
-                // Get business function for this agent (async, but we'll do it synchronously here)
-                // Note: This is a simplified approach - in production, you might want to batch these lookups
-                let business_function = None; // Will be populated in the calling function if needed
-                
-                AgentImpact {
-                    agent_id: agent_id.clone(),
-                    total_requests: *total,
-                    would_block: *blocked,
-                    would_allow: *total - *blocked,
-                    block_percentage,
-                    affected_endpoints,
-                    business_function,
-                }
-            })
-            .collect();
+    fn record(agent_id: &str, action_summary: &str, status: &str, payload_hash: &str) -> ReplayRecord {
+        let resolver = SovereigntyResolver::new();
+        ReplayRecord::new(
+            ComplianceRecordForSimulation {
+                id: Uuid::new_v4(),
+                seal_id: format!("SEAL_{}", Uuid::new_v4()),
+                agent_id: agent_id.to_string(),
+                action_summary: action_summary.to_string(),
+                status: status.to_string(),
+                payload_hash: payload_hash.to_string(),
+                risk_level: Some("LOW".to_string()),
+                timestamp: Utc.with_ymd_and_hms(2025, 3, 10, 12, 0, 0).unwrap(),
+                user_id: None,
+                human_oversight_status: None,
+                token_counts: None,
+            },
+            resolver.region_catalog(),
+        )
+    }
 
-        let critical_agents = revoked_agents;
+    fn replay<'a>(resolver: &'a SovereigntyResolver, set: serde_json::Value) -> Replay<'a> {
+        let policies = CandidatePolicySet::from_request(&PolicyType::PolicySet, &set)
+            .unwrap()
+            .compile()
+            .unwrap();
+        Replay::new(policies, resolver)
+    }
 
-        Ok(SimulationResult {
-            policy_type: PolicyType::AgentRevocation,
-            total_requests: records.len() as i64,
-            would_block,
-            would_allow,
-            affected_agents: agent_impacts,
-            affected_endpoints: endpoint_stats,
-            requests_by_country: country_stats,
-            requests_by_business_function: None,
-            requests_by_location: None,
-            estimated_impact: ImpactLevel::Low,
-            critical_agents,
-            partial_impact_agents: vec![],
-            simulation_timestamp: Utc::now(),
-        })
+    fn simulate(replay: &mut Replay<'_>, record: &ReplayRecord) -> String {
+        replay.prepare(record);
+        let business_function = replay.business_function(record);
+        replay.replay(record, business_function.as_deref()).verdict.label()
     }
 
-    /// Simulate Consent Requirement policy
-    fn simulate_consent_requirement(
-        records: Vec<ComplianceRecordForSimulation>,
-        _config: &serde_json::Value,
-    ) -> Result<SimulationResult, String> {
-        // For now, return a basic simulation
-        // In a full implementation, this would check user consent status
-        Ok(SimulationResult {
-            policy_type: PolicyType::ConsentRequirement,
-            total_requests: records.len() as i64,
-            would_block: 0,
-            would_allow: records.len() as i64,
-            affected_agents: vec![],
-            affected_endpoints: HashMap::new(),
-            requests_by_country: HashMap::new(),
-            requests_by_business_function: None,
-            requests_by_location: None,
-            estimated_impact: ImpactLevel::Low,
-            critical_agents: vec![],
-            partial_impact_agents: vec![],
-            simulation_timestamp: Utc::now(),
-        })
+    #[test]
+    fn test_recorded_verdicts() {
+        let label = |status: &str| Verdict::recorded(&record("a", "a: x", status, "abc")).label();
+        assert_eq!(label("COMPLIANT"), "ALLOWED");
+        assert_eq!(label("BLOCKED (SOVEREIGNTY)"), "BLOCKED (SOVEREIGNTY)");
+        assert_eq!(label("BLOCKED"), "BLOCKED");
+        assert_eq!(label("TEST_MODE (WOULD_BLOCK)"), "ALLOWED");
+
+        let mut held = record("a", "a: x", "COMPLIANT", "abc");
+        held.human_oversight_status = Some("PENDING".to_string());
+        held.redacted = true;
+        assert_eq!(Verdict::recorded(&held).label(), "OVERSIGHT [REDACTED]");
     }
 
-    /// Simulate Processing Restriction policy
-    fn simulate_processing_restriction(
-        records: Vec<ComplianceRecordForSimulation>,
-        _config: &serde_json::Value,
-    ) -> Result<SimulationResult, String> {
-        // For now, return a basic simulation
-        Ok(SimulationResult {
-            policy_type: PolicyType::ProcessingRestriction,
-            total_requests: records.len() as i64,
-            would_block: 0,
-            would_allow: records.len() as i64,
-            affected_agents: vec![],
-            affected_endpoints: HashMap::new(),
-            requests_by_country: HashMap::new(),
-            requests_by_business_function: None,
-            requests_by_location: None,
-            estimated_impact: ImpactLevel::Low,
-            critical_agents: vec![],
-            partial_impact_agents: vec![],
-            simulation_timestamp: Utc::now(),
-        })
+    #[test]
+    fn test_policy_set_from_request() {
+        let set = CandidatePolicySet::from_request(&PolicyType::SovereignLock, &json!({})).unwrap();
+        assert!(set.jurisdiction.is_some() && set.blocked_countries.is_none());
+        let set = CandidatePolicySet::from_request(&PolicyType::ConsentRequirement, &json!({})).unwrap();
+        assert_eq!(set.consent_type.as_deref(), Some("PROCESSING"));
+        assert!(CandidatePolicySet::from_request(&PolicyType::AgentRevocation, &json!({})).is_err());
+
+        let invalid = |set: serde_json::Value| {
+            CandidatePolicySet::from_request(&PolicyType::PolicySet, &set).and_then(|s| s.compile()).is_err()
+        };
+        assert!(invalid(json!({"lockdowns": [{"scope_type": "CONTINENT", "scope_value": "EU"}]})));
+        assert!(invalid(json!({"lockdowns": [{"scope_type": "VENDOR_DOMAIN"}]})));
+        assert!(invalid(json!({"usage_budgets": [{"scope_type": "AGENT", "scope_value": "a"}]})));
+        assert!(invalid(json!({"asset_policies": [{
+            "policy_name": "bad", "policy_type": "RULES", "priority": 1,
+            "policy_config": {"rules": [{"id": "r", "effect": "MAYBE"}]}
+        }]})));
+        assert!(invalid(json!({"unknown_policy": true})));
+        assert!(!invalid(json!({"lockdowns": [{"scope_type": "GLOBAL"}]})));
     }
 
-    /// Calculate impact level based on block percentage
-    fn calculate_impact_level(blocked: i64, total: i64) -> ImpactLevel {
-        if total == 0 {
-            return ImpactLevel::Low;
-        }
+    #[test]
+    fn test_replay_order_and_rules() {
+        let resolver = SovereigntyResolver::new();
+        let mut replay = replay(&resolver, json!({
+            "revoked_agents": ["revoked-bot"],
+            "blocked_countries": ["US"],
+            "lockdowns": [{"scope_type": "VENDOR_DOMAIN", "scope_value": "openai.com"}],
+            "asset_policies": [{
+                "policy_name": "Payments",
+                "policy_type": "RULES",
+                "priority": 10,
+                "policy_config": {"rules": [
+                    {"id": "deny-export", "effect": "DENY", "when": {"field": "action", "equals": "export"}},
+                    {"id": "review-score", "effect": "REQUIRE_OVERSIGHT", "when": {"field": "agent", "equals": "scorer"}}
+                ]}
+            }]
+        }));
 
-        let percentage = (blocked as f64 / total as f64) * 100.0;
+        let revoked = record("revoked-bot", "revoked-bot: export", "COMPLIANT", "abc");
+        assert_eq!(simulate(&mut replay, &revoked), "BLOCKED (REVOKED)");
+        let revoked_proxy = record("revoked-bot", "PROXY_ALLOWED: https://api.example.com/v1", "COMPLIANT", "region:DE");
+        assert_eq!(simulate(&mut replay, &revoked_proxy), "BLOCKED (REVOKED)");
+        let export = record("bot", "bot: export", "COMPLIANT", "abc");
+        assert_eq!(simulate(&mut replay, &export), "BLOCKED (POLICY)");
+        let read = record("bot", "bot: read", "COMPLIANT", "abc");
+        assert_eq!(simulate(&mut replay, &read), "ALLOWED");
 
-        if percentage >= 50.0 {
-            ImpactLevel::Critical
-        } else if percentage >= 20.0 {
-            ImpactLevel::High
-        } else if percentage >= 5.0 {
-            ImpactLevel::Medium
-        } else {
-            ImpactLevel::Low
-        }
+        // log_action records keep their region out of the record
+        let sovereign = record("bot", "bot: read", "BLOCKED (SOVEREIGNTY)", "abc");
+        assert_eq!(simulate(&mut replay, &sovereign), "BLOCKED (SOVEREIGNTY)");
+        let us = record("bot", "PROXY_ALLOWED: https://api.example.com/v1", "COMPLIANT", "region:US");
+        assert_eq!(simulate(&mut replay, &us), "BLOCKED (SOVEREIGNTY)");
+        let openai = record("bot", "PROXY_ALLOWED: https://api.openai.com/v1/chat", "COMPLIANT", "region:DE");
+        assert_eq!(simulate(&mut replay, &openai), "BLOCKED (LOCKDOWN)");
+
+        // Oversight holds a logged action but blocks a proxied call
+        let logged = record("scorer", "scorer: score", "COMPLIANT", "abc");
+        assert_eq!(simulate(&mut replay, &logged), "OVERSIGHT");
+        let proxied = record("scorer", "PROXY_ALLOWED: https://api.example.com/v1", "COMPLIANT", "region:DE");
+        let business_function = replay.business_function(&proxied);
+        replay.prepare(&proxied);
+        let simulated = replay.replay(&proxied, business_function.as_deref());
+        assert_eq!(simulated.verdict.label(), "BLOCKED (POLICY)");
+        assert_eq!(simulated.matched_rules, vec!["review-score".to_string()]);
     }
 
-    /// Extract endpoint from action summary
-    fn extract_endpoint(action_summary: &str) -> String {
-        // Try to extract URL/endpoint from action summary
-        // Format might be: "PROXY_BLOCKED: Attempted connection to https://api.openai.com/v1/chat/completions"
-        if let Some(url_start) = action_summary.find("http") {
-            let url_part = &action_summary[url_start..];
-            // Find end of URL (space, newline, or end of string)
-            let url_end = url_part
-                .find(' ')
-                .or_else(|| url_part.find('\n'))
-                .unwrap_or(url_part.len());
-            let url = &url_part[..url_end];
-            
-            // Extract hostname manually (simpler than parsing full URL)
-            // Look for "://" and then find next "/" or end
-            if let Some(proto_end) = url.find("://") {
-                let after_proto = &url[proto_end + 3..];
-                if let Some(host_end) = after_proto.find('/') {
-                    return after_proto[..host_end].to_string();
-                } else {
-                    return after_proto.to_string();
-                }
-            }
-        }
-        "unknown".to_string()
+    #[test]
+    fn test_rules_see_consent_state() {
+        let resolver = SovereigntyResolver::new();
+        let mut replay = replay(&resolver, json!({
+            "asset_policies": [{
+                "policy_name": "Consent",
+                "policy_type": "RULES",
+                "priority": 10,
+                "policy_config": {"rules": [
+                    {"id": "no-consent", "effect": "DENY", "when": {"field": "consent", "equals": "MISSING"}}
+                ]}
+            }]
+        }));
+
+        let anonymous = record("bot", "bot: read", "COMPLIANT", "abc");
+        assert_eq!(simulate(&mut replay, &anonymous), "ALLOWED");
+
+        let mut subject = record("bot", "bot: read", "COMPLIANT", "abc");
+        subject.user_id = Some("user-1".to_string());
+        assert_eq!(simulate(&mut replay, &subject), "BLOCKED (POLICY)");
+
+        let granted_at = subject.timestamp - chrono::Duration::days(1);
+        replay.consents.insert("user-1".to_string(), vec![(granted_at, None)]);
+        assert_eq!(simulate(&mut replay, &subject), "ALLOWED");
+        replay.consents.insert("user-1".to_string(), vec![(granted_at, Some(subject.timestamp))]);
+        assert_eq!(simulate(&mut replay, &subject), "BLOCKED (POLICY)");
     }
-}
 
-/// Internal struct for simulation queries
-#[derive(sqlx::FromRow)]
-struct ComplianceRecordForSimulation {
-    agent_id: String,
-    action_summary: String,
-    status: String,
-    payload_hash: String,
-    timestamp: DateTime<Utc>,
-}
+    #[test]
+    fn test_sampling_and_location_filters() {
+        assert_eq!(sample_ppm(None), Ok(None));
+        assert_eq!(sample_ppm(Some(1.0)), Ok(None));
+        assert_eq!(sample_ppm(Some(0.25)), Ok(Some(250_000)));
+        assert_eq!(sample_ppm(Some(1e-9)), Ok(Some(1)));
+        assert!(sample_ppm(Some(0.0)).is_err());
+        assert!(sample_ppm(Some(1.5)).is_err());
+        assert!(sample_ppm(Some(f64::NAN)).is_err());
 
+        // Regions are matched by country or by their code
+        let resolver = SovereigntyResolver::new();
+        let (country, code) = resolve_region(resolver.region_catalog(), "eu-central-1a");
+        assert_eq!((country.as_str(), code.as_deref()), ("DE", Some("EU-CENTRAL-1")));
+        let locations = vec!["de".to_string()];
+        assert!(location_matches(&locations, &country, code.as_deref()));
+        assert!(location_matches(&["central".to_string()], &country, code.as_deref()));
+        assert!(!location_matches(&locations, UNKNOWN_COUNTRY, None));
+        assert!(location_matches(&["unknown".to_string()], UNKNOWN_COUNTRY, None));
+    }
+
+    #[test]
+    fn test_budget_replay() {
+        let resolver = SovereigntyResolver::new();
+        let mut replay = replay(&resolver, json!({
+            "usage_budgets": [{"scope_type": "AGENT", "scope_value": "bot", "period": "DAILY", "max_tokens": 1000}]
+        }));
+        let at = |day: u32, hour: u32| Utc.with_ymd_and_hms(2025, 3, day, hour, 0, 0).unwrap();
+        let budget = replay.policies.budgets[0].clone();
+        replay.budgets.push(BudgetUsage::new(budget, vec![(at(9, 23), 5000, 1.0), (at(10, 8), 600, 0.1), (at(10, 11), 600, 0.1)]));
+
+        // 1200 tokens used on March 10th before noon; the 9th belongs to the previous period
+        let mut proxied = record("bot", "PROXY_ALLOWED: https://api.example.com/v1", "COMPLIANT", "region:DE");
+        assert_eq!(simulate(&mut replay, &proxied), "BLOCKED (BUDGET)");
+        proxied.timestamp = at(10, 9);
+        assert_eq!(simulate(&mut replay, &proxied), "ALLOWED");
+
+        // log_action does not enforce budgets
+        let logged = record("bot", "bot: read", "COMPLIANT", "abc");
+        assert_eq!(simulate(&mut replay, &logged), "ALLOWED");
+    }
+}
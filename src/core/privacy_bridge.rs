@@ -4,7 +4,6 @@ use std::time::SystemTime;
 use rand::RngCore;
 use rand::thread_rng;
 use std::sync::Mutex;
-use std::collections::VecDeque;
 use base64::Engine;
 
 /// Hash a payload using SHA256 and return the hex string
@@ -27,8 +26,6 @@ pub fn hash_payload(payload: &str) -> String {
 pub struct SignicatClient {
     api_url: String,
     client: Client,
-    /// Buffer for hashes when API is unreachable
-    offline_buffer: Mutex<VecDeque<String>>,
     /// Flag to simulate API outage (for testing)
     simulate_outage: Mutex<bool>,
     /// Signicat OAuth2 client ID
@@ -66,7 +63,6 @@ impl SignicatClient {
         Self {
             api_url,
             client: Client::new(),
-            offline_buffer: Mutex::new(VecDeque::new()),
             simulate_outage: Mutex::new(false),
             client_id,
             client_secret,
@@ -133,23 +129,16 @@ impl SignicatClient {
     /// 
     /// # Returns
     /// 
-    /// * `Ok(String)` with the seal ID and timestamp (or PENDING_SYNC_LOCAL if buffered)
-    /// * `Err(String)` if the request fails
+    /// * `Ok(String)` with the seal ID and timestamp
+    /// * `Err(String)` if the request fails or the API is unreachable; callers
+    ///   queue the hash in the seal outbox (see `SealService::seal_or_defer`)
     pub async fn request_seal(&self, log_hash: &str) -> Result<String, String> {
         // Check if outage is simulated
         let is_outage = *self.simulate_outage.lock().expect("Failed to acquire outage lock");
         
         if is_outage {
-            // Simulated failure - buffer the hash locally
-            self.offline_buffer
-                .lock()
-                .expect("Failed to acquire buffer lock")
-                .push_back(log_hash.to_string());
-            
-            println!("⚠️ CIRCUIT OPEN: API Unreachable. Buffering hash locally...");
-            
-            // Return pending status (do NOT return Err, as we want the agent to continue)
-            return Ok(format!("PENDING_SYNC_LOCAL:[{}]", log_hash));
+            println!("⚠️ CIRCUIT OPEN: API Unreachable.");
+            return Err("CIRCUIT OPEN: Signicat API unreachable".to_string());
         }
         
         // Check if we should use real API or mock mode
@@ -255,34 +244,6 @@ impl SignicatClient {
             Err(format!("API returned error status: {} - Response: {}", status, error_body))
         }
     }
-
-    /// Sync all pending buffered hashes to the API
-    /// 
-    /// This should be called when the API becomes available again
-    #[allow(dead_code)]
-    pub fn sync_pending(&self) {
-        let mut buffer = self.offline_buffer.lock().expect("Failed to acquire buffer lock");
-        
-        // Loop through offline_buffer
-        while let Some(hash) = buffer.pop_front() {
-            // Show first 8 characters of hash for display
-            let hash_start = if hash.len() >= 8 {
-                &hash[..8]
-            } else {
-                &hash
-            };
-            
-            println!("🔄 SYNCING: Uploading buffered hash [{}...]...", hash_start);
-            
-            // In a real implementation, we would actually upload here
-            // For now, we just simulate the upload by doing nothing
-        }
-        
-        // Clear the buffer (already done by pop_front, but ensure it's empty)
-        buffer.clear();
-        
-        println!("✅ SYNC COMPLETE: All logs sealed.");
-    }
 }
 
 #[cfg(test)]
@@ -328,26 +289,16 @@ mod tests {
         // Enable outage
         client.set_outage(true);
         
-        // Request seal (should return PENDING)
+        // Request seal (should fail so the caller can queue the hash)
         let result = client.request_seal(test_hash).await;
-        assert!(result.is_ok());
-        let response = result.unwrap();
-        assert!(response.starts_with("PENDING_SYNC_LOCAL:"));
-        
-        // Check buffer length is 1
-        let buffer = client.offline_buffer.lock().expect("Failed to acquire buffer lock");
-        assert_eq!(buffer.len(), 1);
-        drop(buffer);
+        assert!(result.is_err());
         
         // Disable outage
         client.set_outage(false);
         
-        // Call sync_pending
-        client.sync_pending();
-        
-        // Check buffer is empty
-        let buffer = client.offline_buffer.lock().expect("Failed to acquire buffer lock");
-        assert_eq!(buffer.len(), 0);
+        // Sealing works again
+        let result = client.request_seal(test_hash).await;
+        assert!(result.unwrap().starts_with("QES_SEAL_"));
     }
 
     #[tokio::test]
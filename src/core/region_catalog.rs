@@ -0,0 +1,380 @@
+// Cloud Region Catalog
+// Maps AWS, Azure and GCP region identifiers (e.g. "eu-central-1", "westeurope",
+// "europe-west4") to the ISO country they run in. The built-in table can be
+// extended or corrected at runtime through the cloud_region_catalog table.
+
+use crate::core::sovereign_lock::EU_EEA_WHITELIST;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::RwLock;
+use utoipa::ToSchema;
+
+/// Jurisdiction label of regions inside the EU/EEA
+pub const JURISDICTION_EU_EEA: &str = "EU/EEA";
+/// Jurisdiction label of all other regions (GDPR "third countries")
+pub const JURISDICTION_NON_EU: &str = "NON_EU";
+
+/// (provider, region code, ISO country, location)
+const BUILTIN_REGIONS: &[(&str, &str, &str, &str)] = &[
+    // AWS
+    ("AWS", "us-east-1", "US", "N. Virginia"),
+    ("AWS", "us-east-2", "US", "Ohio"),
+    ("AWS", "us-west-1", "US", "N. California"),
+    ("AWS", "us-west-2", "US", "Oregon"),
+    ("AWS", "us-gov-east-1", "US", "GovCloud (US-East)"),
+    ("AWS", "us-gov-west-1", "US", "GovCloud (US-West)"),
+    ("AWS", "ca-central-1", "CA", "Montreal"),
+    ("AWS", "ca-west-1", "CA", "Calgary"),
+    ("AWS", "mx-central-1", "MX", "Queretaro"),
+    ("AWS", "sa-east-1", "BR", "Sao Paulo"),
+    ("AWS", "eu-central-1", "DE", "Frankfurt"),
+    ("AWS", "eu-central-2", "CH", "Zurich"),
+    ("AWS", "eu-west-1", "IE", "Ireland"),
+    ("AWS", "eu-west-2", "GB", "London"),
+    ("AWS", "eu-west-3", "FR", "Paris"),
+    ("AWS", "eu-north-1", "SE", "Stockholm"),
+    ("AWS", "eu-south-1", "IT", "Milan"),
+    ("AWS", "eu-south-2", "ES", "Aragon"),
+    ("AWS", "af-south-1", "ZA", "Cape Town"),
+    ("AWS", "me-south-1", "BH", "Bahrain"),
+    ("AWS", "me-central-1", "AE", "UAE"),
+    ("AWS", "il-central-1", "IL", "Tel Aviv"),
+    ("AWS", "ap-east-1", "HK", "Hong Kong"),
+    ("AWS", "ap-south-1", "IN", "Mumbai"),
+    ("AWS", "ap-south-2", "IN", "Hyderabad"),
+    ("AWS", "ap-southeast-1", "SG", "Singapore"),
+    ("AWS", "ap-southeast-2", "AU", "Sydney"),
+    ("AWS", "ap-southeast-3", "ID", "Jakarta"),
+    ("AWS", "ap-southeast-4", "AU", "Melbourne"),
+    ("AWS", "ap-southeast-5", "MY", "Malaysia"),
+    ("AWS", "ap-northeast-1", "JP", "Tokyo"),
+    ("AWS", "ap-northeast-2", "KR", "Seoul"),
+    ("AWS", "ap-northeast-3", "JP", "Osaka"),
+    ("AWS", "cn-north-1", "CN", "Beijing"),
+    ("AWS", "cn-northwest-1", "CN", "Ningxia"),
+    // Azure
+    ("AZURE", "eastus", "US", "Virginia"),
+    ("AZURE", "eastus2", "US", "Virginia"),
+    ("AZURE", "centralus", "US", "Iowa"),
+    ("AZURE", "northcentralus", "US", "Illinois"),
+    ("AZURE", "southcentralus", "US", "Texas"),
+    ("AZURE", "westcentralus", "US", "Wyoming"),
+    ("AZURE", "westus", "US", "California"),
+    ("AZURE", "westus2", "US", "Washington"),
+    ("AZURE", "westus3", "US", "Arizona"),
+    ("AZURE", "canadacentral", "CA", "Toronto"),
+    ("AZURE", "canadaeast", "CA", "Quebec City"),
+    ("AZURE", "brazilsouth", "BR", "Sao Paulo"),
+    ("AZURE", "northeurope", "IE", "Ireland"),
+    ("AZURE", "westeurope", "NL", "Netherlands"),
+    ("AZURE", "francecentral", "FR", "Paris"),
+    ("AZURE", "francesouth", "FR", "Marseille"),
+    ("AZURE", "germanywestcentral", "DE", "Frankfurt"),
+    ("AZURE", "germanynorth", "DE", "Berlin"),
+    ("AZURE", "swedencentral", "SE", "Gavle"),
+    ("AZURE", "norwayeast", "NO", "Oslo"),
+    ("AZURE", "norwaywest", "NO", "Stavanger"),
+    ("AZURE", "italynorth", "IT", "Milan"),
+    ("AZURE", "polandcentral", "PL", "Warsaw"),
+    ("AZURE", "spaincentral", "ES", "Madrid"),
+    ("AZURE", "switzerlandnorth", "CH", "Zurich"),
+    ("AZURE", "switzerlandwest", "CH", "Geneva"),
+    ("AZURE", "uksouth", "GB", "London"),
+    ("AZURE", "ukwest", "GB", "Cardiff"),
+    ("AZURE", "uaenorth", "AE", "Dubai"),
+    ("AZURE", "qatarcentral", "QA", "Doha"),
+    ("AZURE", "israelcentral", "IL", "Israel"),
+    ("AZURE", "southafricanorth", "ZA", "Johannesburg"),
+    ("AZURE", "centralindia", "IN", "Pune"),
+    ("AZURE", "southindia", "IN", "Chennai"),
+    ("AZURE", "westindia", "IN", "Mumbai"),
+    ("AZURE", "eastasia", "HK", "Hong Kong"),
+    ("AZURE", "southeastasia", "SG", "Singapore"),
+    ("AZURE", "japaneast", "JP", "Tokyo"),
+    ("AZURE", "japanwest", "JP", "Osaka"),
+    ("AZURE", "koreacentral", "KR", "Seoul"),
+    ("AZURE", "koreasouth", "KR", "Busan"),
+    ("AZURE", "australiaeast", "AU", "New South Wales"),
+    ("AZURE", "australiasoutheast", "AU", "Victoria"),
+    ("AZURE", "australiacentral", "AU", "Canberra"),
+    ("AZURE", "chinanorth", "CN", "Beijing"),
+    ("AZURE", "chinanorth2", "CN", "Beijing"),
+    ("AZURE", "chinaeast", "CN", "Shanghai"),
+    ("AZURE", "chinaeast2", "CN", "Shanghai"),
+    // GCP
+    ("GCP", "us-central1", "US", "Iowa"),
+    ("GCP", "us-east1", "US", "South Carolina"),
+    ("GCP", "us-east4", "US", "N. Virginia"),
+    ("GCP", "us-east5", "US", "Columbus"),
+    ("GCP", "us-south1", "US", "Dallas"),
+    ("GCP", "us-west1", "US", "Oregon"),
+    ("GCP", "us-west2", "US", "Los Angeles"),
+    ("GCP", "us-west3", "US", "Salt Lake City"),
+    ("GCP", "us-west4", "US", "Las Vegas"),
+    ("GCP", "northamerica-northeast1", "CA", "Montreal"),
+    ("GCP", "northamerica-northeast2", "CA", "Toronto"),
+    ("GCP", "southamerica-east1", "BR", "Sao Paulo"),
+    ("GCP", "southamerica-west1", "CL", "Santiago"),
+    ("GCP", "europe-west1", "BE", "St. Ghislain"),
+    ("GCP", "europe-west2", "GB", "London"),
+    ("GCP", "europe-west3", "DE", "Frankfurt"),
+    ("GCP", "europe-west4", "NL", "Eemshaven"),
+    ("GCP", "europe-west6", "CH", "Zurich"),
+    ("GCP", "europe-west8", "IT", "Milan"),
+    ("GCP", "europe-west9", "FR", "Paris"),
+    ("GCP", "europe-west10", "DE", "Berlin"),
+    ("GCP", "europe-west12", "IT", "Turin"),
+    ("GCP", "europe-north1", "FI", "Hamina"),
+    ("GCP", "europe-central2", "PL", "Warsaw"),
+    ("GCP", "europe-southwest1", "ES", "Madrid"),
+    ("GCP", "me-west1", "IL", "Tel Aviv"),
+    ("GCP", "me-central1", "QA", "Doha"),
+    ("GCP", "me-central2", "SA", "Dammam"),
+    ("GCP", "africa-south1", "ZA", "Johannesburg"),
+    ("GCP", "asia-east1", "TW", "Changhua County"),
+    ("GCP", "asia-east2", "HK", "Hong Kong"),
+    ("GCP", "asia-northeast1", "JP", "Tokyo"),
+    ("GCP", "asia-northeast2", "JP", "Osaka"),
+    ("GCP", "asia-northeast3", "KR", "Seoul"),
+    ("GCP", "asia-south1", "IN", "Mumbai"),
+    ("GCP", "asia-south2", "IN", "Delhi"),
+    ("GCP", "asia-southeast1", "SG", "Singapore"),
+    ("GCP", "asia-southeast2", "ID", "Jakarta"),
+    ("GCP", "australia-southeast1", "AU", "Sydney"),
+    ("GCP", "australia-southeast2", "AU", "Melbourne"),
+];
+
+/// One provider region
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct CloudRegion {
+    /// AWS, AZURE or GCP (or any other provider added through the API)
+    #[schema(example = "AWS")]
+    pub provider: String,
+    #[schema(example = "eu-central-1")]
+    pub region_code: String,
+    /// ISO 3166-1 alpha-2 country the region runs in
+    #[schema(example = "DE")]
+    pub country: String,
+    /// EU/EEA or NON_EU
+    #[schema(example = "EU/EEA")]
+    pub jurisdiction: String,
+    #[schema(example = "Frankfurt")]
+    pub location: Option<String>,
+}
+
+impl CloudRegion {
+    /// Region with the jurisdiction derived from its country
+    pub fn new(provider: &str, region_code: &str, country: &str, location: Option<&str>) -> Self {
+        let country = country.to_uppercase();
+        Self {
+            provider: provider.to_uppercase(),
+            region_code: region_code.to_lowercase(),
+            jurisdiction: jurisdiction_for(&country).to_string(),
+            country,
+            location: location.map(|l| l.to_string()),
+        }
+    }
+}
+
+/// Jurisdiction label for a country code
+pub fn jurisdiction_for(country: &str) -> &'static str {
+    if EU_EEA_WHITELIST.contains(&country.to_uppercase().as_str()) {
+        JURISDICTION_EU_EEA
+    } else {
+        JURISDICTION_NON_EU
+    }
+}
+
+/// Where a provider puts the region in its hostnames
+enum RegionLabel {
+    /// The label before the domain: `bedrock-runtime.eu-central-1.amazonaws.com`
+    Last,
+    /// The only label, as a prefix of the service: `europe-west4-aiplatform.googleapis.com`
+    ServicePrefix,
+    /// The only label: `westeurope.api.cognitive.microsoft.com`
+    Whole,
+}
+
+/// Provider-owned domains whose hostnames carry a region, as (provider, domain, position)
+const REGIONAL_DOMAINS: &[(&str, &str, RegionLabel)] = &[
+    ("AWS", "amazonaws.com", RegionLabel::Last),
+    ("GCP", "googleapis.com", RegionLabel::ServicePrefix),
+    ("AZURE", "api.cognitive.microsoft.com", RegionLabel::Whole),
+];
+
+/// Region code catalog, keyed by lowercase region code
+pub struct RegionCatalog {
+    regions: RwLock<HashMap<String, CloudRegion>>,
+}
+
+impl RegionCatalog {
+    /// Catalog with the built-in AWS, Azure and GCP regions
+    pub fn builtin() -> Self {
+        let regions = BUILTIN_REGIONS
+            .iter()
+            .map(|(provider, code, country, location)| {
+                (code.to_string(), CloudRegion::new(provider, code, country, Some(location)))
+            })
+            .collect();
+        Self {
+            regions: RwLock::new(regions),
+        }
+    }
+
+    /// Look up a region code (case-insensitive)
+    ///
+    /// AWS availability zones such as `eu-central-1a` resolve to their region.
+    pub fn lookup(&self, region_code: &str) -> Option<CloudRegion> {
+        let code = region_code.trim().to_lowercase();
+        let regions = self.regions.read().expect("Failed to acquire lock");
+        if let Some(region) = regions.get(&code) {
+            return Some(region.clone());
+        }
+        let mut chars = code.chars().rev();
+        match (chars.next(), chars.next()) {
+            (Some(zone), Some(digit)) if zone.is_ascii_lowercase() && digit.is_ascii_digit() => {
+                regions.get(&code[..code.len() - 1]).cloned()
+            }
+            _ => None,
+        }
+    }
+
+    /// Find the region of a provider hostname
+    ///
+    /// Only hostnames under a provider-owned domain count, with the region in
+    /// the position that provider uses (`REGIONAL_DOMAINS`); anyone can put a
+    /// region code into a hostname of their own.
+    pub fn find_in_hostname(&self, hostname: &str) -> Option<CloudRegion> {
+        let hostname = hostname.to_lowercase();
+        let hostname = hostname.trim_end_matches('.');
+        let regions = self.regions.read().expect("Failed to acquire lock");
+        REGIONAL_DOMAINS.iter().find_map(|(provider, domain, position)| {
+            let subdomain = hostname.strip_suffix(domain)?.strip_suffix('.')?;
+            let of_provider = |r: &&CloudRegion| r.provider.eq_ignore_ascii_case(provider);
+            let region = match position {
+                RegionLabel::Last => regions.get(subdomain.rsplit('.').next()?).filter(of_provider),
+                RegionLabel::Whole => regions.get(subdomain).filter(of_provider),
+                RegionLabel::ServicePrefix => regions
+                    .values()
+                    .filter(of_provider)
+                    .filter(|r| subdomain.starts_with(&format!("{}-", r.region_code)) && !subdomain.contains('.'))
+                    .max_by_key(|r| r.region_code.len()),
+            };
+            region.cloned()
+        })
+    }
+
+    /// All regions, sorted by provider and code
+    pub fn list(&self) -> Vec<CloudRegion> {
+        let mut regions: Vec<CloudRegion> = self
+            .regions
+            .read()
+            .expect("Failed to acquire lock")
+            .values()
+            .cloned()
+            .collect();
+        regions.sort_by(|a, b| (&a.provider, &a.region_code).cmp(&(&b.provider, &b.region_code)));
+        regions
+    }
+
+    /// Add or replace a region in memory
+    pub fn insert(&self, region: CloudRegion) {
+        self.regions
+            .write()
+            .expect("Failed to acquire lock")
+            .insert(region.region_code.clone(), region);
+    }
+
+    /// Apply the overrides stored in `cloud_region_catalog`
+    pub async fn load_overrides(&self, db_pool: &PgPool) -> Result<usize, sqlx::Error> {
+        let rows: Vec<(String, String, String, String, Option<String>)> = sqlx::query_as(
+            "SELECT provider, region_code, country, jurisdiction, location FROM cloud_region_catalog"
+        )
+        .fetch_all(db_pool)
+        .await?;
+
+        let count = rows.len();
+        for (provider, region_code, country, jurisdiction, location) in rows {
+            self.insert(CloudRegion {
+                provider,
+                region_code: region_code.to_lowercase(),
+                country,
+                jurisdiction,
+                location,
+            });
+        }
+        Ok(count)
+    }
+
+    /// Persist a region in `cloud_region_catalog` and apply it
+    pub async fn upsert(&self, db_pool: &PgPool, region: CloudRegion, updated_by: &str) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO cloud_region_catalog (provider, region_code, country, jurisdiction, location, updated_by)
+             VALUES ($1, $2, $3, $4, $5, $6)
+             ON CONFLICT (region_code) DO UPDATE SET
+                 provider = EXCLUDED.provider,
+                 country = EXCLUDED.country,
+                 jurisdiction = EXCLUDED.jurisdiction,
+                 location = EXCLUDED.location,
+                 updated_by = EXCLUDED.updated_by,
+                 updated_at = CURRENT_TIMESTAMP"
+        )
+        .bind(&region.provider)
+        .bind(&region.region_code)
+        .bind(&region.country)
+        .bind(&region.jurisdiction)
+        .bind(&region.location)
+        .bind(updated_by)
+        .execute(db_pool)
+        .await?;
+        self.insert(region);
+        Ok(())
+    }
+}
+
+impl Default for RegionCatalog {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_lookup() {
+        let catalog = RegionCatalog::builtin();
+        let region = catalog.lookup("EU-CENTRAL-1").unwrap();
+        assert_eq!((region.country.as_str(), region.jurisdiction.as_str()), ("DE", JURISDICTION_EU_EEA));
+        assert_eq!(catalog.lookup("westeurope").unwrap().country, "NL");
+        assert_eq!(catalog.lookup("europe-west4").unwrap().country, "NL");
+        assert_eq!(catalog.lookup("eu-central-1b").unwrap().region_code, "eu-central-1");
+
+        // "eu-" / "europe-" prefixes are not always in the EU
+        assert_eq!(catalog.lookup("eu-west-2").unwrap().jurisdiction, JURISDICTION_NON_EU);
+        assert_eq!(catalog.lookup("europe-west6").unwrap().country, "CH");
+        assert!(catalog.lookup("mars-north-1").is_none());
+    }
+
+    #[test]
+    fn test_hostnames_and_overrides() {
+        let catalog = RegionCatalog::builtin();
+        assert_eq!(catalog.find_in_hostname("bedrock-runtime.us-east-1.amazonaws.com").unwrap().country, "US");
+        assert_eq!(catalog.find_in_hostname("europe-west4-aiplatform.googleapis.com").unwrap().region_code, "europe-west4");
+        assert_eq!(catalog.find_in_hostname("westeurope.api.cognitive.microsoft.com").unwrap().country, "NL");
+        assert!(catalog.find_in_hostname("api.example.com").is_none());
+
+        // Region codes in hostnames the provider does not own say nothing
+        assert!(catalog.find_in_hostname("eu-central-1.attacker.example").is_none());
+        assert!(catalog.find_in_hostname("europe-west4-aiplatform.attacker.example").is_none());
+        assert!(catalog.find_in_hostname("eu-central-1.amazonaws.com.attacker.example").is_none());
+        assert!(catalog.find_in_hostname("eu-central-1.fakeamazonaws.com").is_none());
+        assert!(catalog.find_in_hostname("storage.europe-west4.googleapis.com").is_none());
+        assert!(catalog.find_in_hostname("westeurope.openai.azure.com").is_none());
+
+        catalog.insert(CloudRegion::new("ovh", "gra", "FR", Some("Gravelines")));
+        assert_eq!(catalog.lookup("GRA").unwrap().jurisdiction, JURISDICTION_EU_EEA);
+    }
+}
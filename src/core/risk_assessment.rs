@@ -264,6 +264,14 @@ impl RiskAssessmentService {
             }
         }
 
+        // Personal data values found by the PII redaction detectors
+        for found in crate::core::pii_redaction::detect(payload) {
+            max_score = max_score.max(found.kind.sensitivity());
+            if !matched.contains(&found.kind.label()) {
+                matched.push(found.kind.label());
+            }
+        }
+
         // Check payload size (larger payloads may contain more sensitive data)
         let size_score = if payload.len() > 10000 {
             0.3
@@ -336,6 +344,7 @@ impl RiskAssessmentService {
         let similar_count: Option<i64> = sqlx::query_scalar(
             "SELECT COUNT(*) FROM compliance_records
              WHERE agent_id = $1
+               AND entry_type = 'ACTION'
                AND action LIKE $2
                AND timestamp > NOW() - INTERVAL '7 days'"
         )
@@ -419,6 +428,7 @@ impl RiskAssessmentService {
         let similar_count: Option<i64> = sqlx::query_scalar(
             "SELECT COUNT(*) FROM compliance_records
              WHERE user_id = $1
+               AND entry_type = 'ACTION'
                AND action LIKE $2
                AND timestamp > NOW() - INTERVAL '90 days'"
         )
@@ -473,6 +483,7 @@ impl RiskAssessmentService {
         let recent_count: Option<i64> = sqlx::query_scalar(
             "SELECT COUNT(*) FROM compliance_records
              WHERE user_id = $1
+               AND entry_type = 'ACTION'
                AND action LIKE $2
                AND timestamp > NOW() - INTERVAL '30 days'"
         )
@@ -485,6 +496,7 @@ impl RiskAssessmentService {
         let previous_count: Option<i64> = sqlx::query_scalar(
             "SELECT COUNT(*) FROM compliance_records
              WHERE user_id = $1
+               AND entry_type = 'ACTION'
                AND action LIKE $2
                AND timestamp BETWEEN NOW() - INTERVAL '60 days' AND NOW() - INTERVAL '30 days'"
         )
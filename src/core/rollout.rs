@@ -0,0 +1,200 @@
+// Gradual Rollout
+// Deterministic cohort assignment for policies rolled out to a percentage of
+// traffic. The subject (agent, tenant, business function or user) is hashed with
+// SHA-256 and a per-policy salt into 10,000 buckets. The bucket is recorded in
+// rollout_assignments the first time a subject is seen and reused afterwards, so
+// membership only changes when the rollout percentage does.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use utoipa::ToSchema;
+
+/// Number of rollout buckets (one bucket = 0.01% of subjects)
+pub const BUCKETS: u32 = 10_000;
+
+/// What a rollout assigns to cohorts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BucketBy {
+    Agent,
+    Tenant,
+    BusinessFunction,
+    User,
+}
+
+impl BucketBy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BucketBy::Agent => "AGENT",
+            BucketBy::Tenant => "TENANT",
+            BucketBy::BusinessFunction => "BUSINESS_FUNCTION",
+            BucketBy::User => "USER",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_uppercase().as_str() {
+            "AGENT" => Some(BucketBy::Agent),
+            "TENANT" => Some(BucketBy::Tenant),
+            "BUSINESS_FUNCTION" => Some(BucketBy::BusinessFunction),
+            "USER" => Some(BucketBy::User),
+            _ => None,
+        }
+    }
+}
+
+/// Identifiers of a request that a rollout can bucket on
+#[derive(Debug, Clone, Copy)]
+pub struct RolloutSubject<'a> {
+    pub agent_id: &'a str,
+    pub company_id: Option<&'a str>,
+    pub business_function: Option<&'a str>,
+    pub user_id: Option<&'a str>,
+}
+
+impl<'a> RolloutSubject<'a> {
+    /// Bucketing key for `by`; requests without that identifier fall back to the agent
+    pub fn key(&self, by: BucketBy) -> (BucketBy, &'a str) {
+        let key = match by {
+            BucketBy::Agent => None,
+            BucketBy::Tenant => self.company_id,
+            BucketBy::BusinessFunction => self.business_function,
+            BucketBy::User => self.user_id,
+        };
+        match key.filter(|k| !k.is_empty()) {
+            Some(key) => (by, key),
+            None => (BucketBy::Agent, self.agent_id),
+        }
+    }
+}
+
+/// Bucket (0..BUCKETS) of a subject key under a policy salt
+pub fn bucket(salt: &str, key: &str) -> u32 {
+    let mut hasher = Sha256::new();
+    hasher.update(salt.as_bytes());
+    hasher.update([0u8]);
+    hasher.update(key.as_bytes());
+    let digest = hasher.finalize();
+    let mut prefix = [0u8; 8];
+    prefix.copy_from_slice(&digest[..8]);
+    (u64::from_be_bytes(prefix) % BUCKETS as u64) as u32
+}
+
+/// Whether a bucket falls inside a rollout percentage; cohorts only grow as the percentage rises
+pub fn in_rollout(bucket: u32, rollout_percentage: i32) -> bool {
+    bucket < rollout_percentage.clamp(0, 100) as u32 * (BUCKETS / 100)
+}
+
+/// Recorded cohort assignment of one subject
+#[derive(Debug, Clone, Serialize, ToSchema, sqlx::FromRow)]
+pub struct RolloutAssignment {
+    pub policy_version_id: uuid::Uuid,
+    /// AGENT, TENANT, BUSINESS_FUNCTION or USER
+    pub bucket_by: String,
+    pub subject: String,
+    /// Bucket (0-9999) fixed at first assignment
+    pub bucket: i32,
+    pub in_cohort: bool,
+    /// Rollout percentage at the last request
+    pub rollout_percentage: i32,
+    pub request_count: i64,
+    pub first_seen_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+}
+
+/// Sticky cohort assignment backed by rollout_assignments
+pub struct RolloutEngine;
+
+impl RolloutEngine {
+    /// Assign a subject to the policy's cohort, reusing its recorded bucket
+    ///
+    /// `salt` defaults to the policy version id, so each policy splits traffic independently.
+    pub async fn assign(
+        db_pool: &PgPool,
+        policy_version_id: uuid::Uuid,
+        salt: Option<&str>,
+        bucket_by: BucketBy,
+        subject: &RolloutSubject<'_>,
+        rollout_percentage: i32,
+    ) -> Result<RolloutAssignment, sqlx::Error> {
+        let salt = salt.map(|s| s.to_string()).unwrap_or_else(|| policy_version_id.to_string());
+        let (bucket_by, key) = subject.key(bucket_by);
+        let bucket = bucket(&salt, key);
+
+        sqlx::query_as(
+            "INSERT INTO rollout_assignments (
+                policy_version_id, bucket_by, subject, bucket, in_cohort, rollout_percentage
+            ) VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (policy_version_id, bucket_by, subject) DO UPDATE SET
+                in_cohort = rollout_assignments.bucket < $6 * 100,
+                rollout_percentage = $6,
+                request_count = rollout_assignments.request_count + 1,
+                last_seen_at = CURRENT_TIMESTAMP
+            RETURNING policy_version_id, bucket_by, subject, bucket, in_cohort, rollout_percentage,
+                      request_count, first_seen_at, last_seen_at"
+        )
+        .bind(policy_version_id)
+        .bind(bucket_by.as_str())
+        .bind(key)
+        .bind(bucket as i32)
+        .bind(in_rollout(bucket, rollout_percentage))
+        .bind(rollout_percentage.clamp(0, 100))
+        .fetch_one(db_pool)
+        .await
+    }
+
+    /// Every subject seen by a policy's rollout, cohort members first
+    pub async fn assignments(
+        db_pool: &PgPool,
+        policy_version_id: uuid::Uuid,
+    ) -> Result<Vec<RolloutAssignment>, sqlx::Error> {
+        sqlx::query_as(
+            "SELECT policy_version_id, bucket_by, subject, bucket, in_cohort, rollout_percentage,
+                    request_count, first_seen_at, last_seen_at
+             FROM rollout_assignments
+             WHERE policy_version_id = $1
+             ORDER BY in_cohort DESC, bucket_by, subject"
+        )
+        .bind(policy_version_id)
+        .fetch_all(db_pool)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_spread_and_monotonic_cohorts() {
+        // The old char-sum put anagrams in the same bucket
+        assert_ne!(bucket("salt", "agent-ab"), bucket("salt", "agent-ba"));
+        assert_eq!(bucket("salt", "agent-ab"), bucket("salt", "agent-ab"));
+        assert_ne!(bucket("policy-1", "agent-ab"), bucket("policy-2", "agent-ab"));
+
+        let buckets: Vec<u32> = (0..10_000).map(|i| bucket("policy-1", &format!("agent-{}", i))).collect();
+        let at_10 = buckets.iter().filter(|b| in_rollout(**b, 10)).count();
+        assert!((900..=1100).contains(&at_10), "10% rollout got {} of 10000", at_10);
+        assert!(buckets.iter().all(|b| *b < BUCKETS));
+
+        // Raising the percentage keeps every existing member
+        assert!(buckets.iter().filter(|b| in_rollout(**b, 10)).all(|b| in_rollout(*b, 25)));
+        assert!(buckets.iter().all(|b| in_rollout(*b, 100) && !in_rollout(*b, 0)));
+    }
+
+    #[test]
+    fn test_subject_key_falls_back_to_agent() {
+        let subject = RolloutSubject {
+            agent_id: "agent-1",
+            company_id: Some("acme"),
+            business_function: None,
+            user_id: Some(""),
+        };
+        assert_eq!(subject.key(BucketBy::Tenant), (BucketBy::Tenant, "acme"));
+        assert_eq!(subject.key(BucketBy::BusinessFunction), (BucketBy::Agent, "agent-1"));
+        assert_eq!(subject.key(BucketBy::User), (BucketBy::Agent, "agent-1"));
+        assert_eq!(BucketBy::parse("business_function"), Some(BucketBy::BusinessFunction));
+    }
+}
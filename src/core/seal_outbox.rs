@@ -0,0 +1,233 @@
+// Seal Outbox
+// Hashes whose seal provider was unreachable are queued in seal_outbox instead
+// of an in-memory buffer, so they survive restarts. The record is stored with a
+// PENDING_SYNC_LOCAL placeholder seal ID; a background job retries with
+// exponential backoff and, once sealed, writes the real seal ID to
+// compliance_records.resolved_seal_id (seal_id itself is chained and immutable).
+// Entries still pending after the SLA (SEAL_OUTBOX_SLA_MINUTES) are alerted once.
+
+use crate::core::seal_provider::SealService;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Prefix of the seal ID given to records whose seal is still queued
+pub const PENDING_PREFIX: &str = "PENDING_SYNC_LOCAL";
+
+/// Delay before the first retry; doubled after every failed attempt
+const BASE_RETRY_SECS: i64 = 30;
+/// Longest delay between two attempts
+const MAX_RETRY_SECS: i64 = 3600;
+/// How long a claimed entry is held back from other replicas while it is retried
+const CLAIM_LEASE_SECS: i64 = 300;
+
+/// Minutes a record may stay unsealed before an alert (SEAL_OUTBOX_SLA_MINUTES, default 60)
+pub fn sla_minutes() -> i64 {
+    std::env::var("SEAL_OUTBOX_SLA_MINUTES")
+        .ok()
+        .and_then(|v| v.parse::<i64>().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(60)
+}
+
+/// Seconds to wait after `attempts` failed attempts
+pub fn retry_delay_secs(attempts: i32) -> i64 {
+    let doublings = attempts.saturating_sub(1).clamp(0, 16) as u32;
+    (BASE_RETRY_SECS << doublings).min(MAX_RETRY_SECS)
+}
+
+/// A hash waiting for (or given) a seal
+#[derive(Debug, Clone, Serialize, sqlx::FromRow, ToSchema)]
+pub struct SealOutboxEntry {
+    pub id: Uuid,
+    /// Seal ID the record was stored with
+    pub placeholder_seal_id: String,
+    pub company_id: Option<Uuid>,
+    pub sealed_hash: String,
+    /// PENDING or SEALED
+    pub status: String,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: DateTime<Utc>,
+    /// Seal obtained by a retry
+    pub seal_id: Option<String>,
+    pub sealed_at: Option<DateTime<Utc>>,
+    pub sla_alerted_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Backlog of the seal outbox
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SealOutboxStatus {
+    pub pending: i64,
+    pub sealed: i64,
+    /// Pending entries older than the SLA
+    pub overdue: i64,
+    pub sla_minutes: i64,
+    pub oldest_pending_at: Option<DateTime<Utc>>,
+    pub oldest_pending_age_seconds: Option<i64>,
+    /// Oldest overdue entries (at most 50)
+    pub overdue_entries: Vec<SealOutboxEntry>,
+}
+
+/// Persistent queue of hashes to seal
+pub struct SealOutbox;
+
+impl SealOutbox {
+    /// Queue a hash whose first seal attempt failed; returns the placeholder seal ID
+    pub async fn enqueue(
+        db_pool: &PgPool,
+        company_id: Option<Uuid>,
+        hash: &str,
+        error: &str,
+    ) -> Result<String, String> {
+        let id = Uuid::new_v4();
+        let placeholder = format!("{}:[{}]:{}", PENDING_PREFIX, hash, id);
+        sqlx::query(
+            "INSERT INTO seal_outbox (id, placeholder_seal_id, company_id, sealed_hash, attempts, last_error, next_attempt_at)
+             VALUES ($1, $2, $3, $4, 1, $5, CURRENT_TIMESTAMP + make_interval(secs => $6))"
+        )
+        .bind(id)
+        .bind(&placeholder)
+        .bind(company_id)
+        .bind(hash)
+        .bind(error)
+        .bind(retry_delay_secs(1) as f64)
+        .execute(db_pool)
+        .await
+        .map_err(|e| format!("Could not queue seal: {}", e))?;
+        Ok(placeholder)
+    }
+
+    /// Claim up to `limit` entries that are due, oldest first
+    ///
+    /// Claimed entries are pushed back by a lease so another replica does not
+    /// retry them at the same time.
+    async fn claim_due(db_pool: &PgPool, limit: i64) -> Result<Vec<SealOutboxEntry>, sqlx::Error> {
+        sqlx::query_as(
+            "UPDATE seal_outbox SET next_attempt_at = CURRENT_TIMESTAMP + make_interval(secs => $2)
+             WHERE id IN (
+                 SELECT id FROM seal_outbox
+                 WHERE status = 'PENDING' AND next_attempt_at <= CURRENT_TIMESTAMP
+                 ORDER BY created_at
+                 LIMIT $1
+                 FOR UPDATE SKIP LOCKED
+             )
+             RETURNING *"
+        )
+        .bind(limit)
+        .bind(CLAIM_LEASE_SECS as f64)
+        .fetch_all(db_pool)
+        .await
+    }
+
+    /// Retry the due entries; returns how many were sealed and how many failed again
+    pub async fn retry_due(db_pool: &PgPool, seals: &SealService, limit: i64) -> Result<(usize, usize), sqlx::Error> {
+        let (mut sealed, mut failed) = (0, 0);
+        for entry in Self::claim_due(db_pool, limit).await? {
+            match seals.seal(db_pool, entry.company_id, &entry.sealed_hash).await {
+                Ok(seal_id) => {
+                    Self::mark_sealed(db_pool, &entry, &seal_id).await?;
+                    sealed += 1;
+                }
+                Err(e) => {
+                    let attempts = entry.attempts + 1;
+                    sqlx::query(
+                        "UPDATE seal_outbox
+                         SET attempts = $2, last_error = $3,
+                             next_attempt_at = CURRENT_TIMESTAMP + make_interval(secs => $4)
+                         WHERE id = $1"
+                    )
+                    .bind(entry.id)
+                    .bind(attempts)
+                    .bind(&e)
+                    .bind(retry_delay_secs(attempts) as f64)
+                    .execute(db_pool)
+                    .await?;
+                    failed += 1;
+                }
+            }
+        }
+        Ok((sealed, failed))
+    }
+
+    async fn mark_sealed(db_pool: &PgPool, entry: &SealOutboxEntry, seal_id: &str) -> Result<(), sqlx::Error> {
+        let mut tx = db_pool.begin().await?;
+        sqlx::query(
+            "UPDATE seal_outbox SET status = 'SEALED', seal_id = $2, sealed_at = CURRENT_TIMESTAMP, last_error = NULL
+             WHERE id = $1"
+        )
+        .bind(entry.id)
+        .bind(seal_id)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query("UPDATE compliance_records SET resolved_seal_id = $2 WHERE seal_id = $1")
+            .bind(&entry.placeholder_seal_id)
+            .bind(seal_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await
+    }
+
+    /// Pending entries past the SLA that have not been alerted yet, marked as alerted
+    pub async fn claim_overdue_alerts(db_pool: &PgPool, sla_minutes: i64) -> Result<Vec<SealOutboxEntry>, sqlx::Error> {
+        sqlx::query_as(
+            "UPDATE seal_outbox SET sla_alerted_at = CURRENT_TIMESTAMP
+             WHERE status = 'PENDING' AND sla_alerted_at IS NULL
+               AND created_at < CURRENT_TIMESTAMP - make_interval(mins => $1)
+             RETURNING *"
+        )
+        .bind(sla_minutes as i32)
+        .fetch_all(db_pool)
+        .await
+    }
+
+    /// Counts and oldest overdue entries
+    pub async fn status(db_pool: &PgPool, sla_minutes: i64) -> Result<SealOutboxStatus, sqlx::Error> {
+        let (pending, sealed, overdue, oldest_pending_at): (i64, i64, i64, Option<DateTime<Utc>>) = sqlx::query_as(
+            "SELECT COUNT(*) FILTER (WHERE status = 'PENDING'),
+                    COUNT(*) FILTER (WHERE status = 'SEALED'),
+                    COUNT(*) FILTER (WHERE status = 'PENDING' AND created_at < CURRENT_TIMESTAMP - make_interval(mins => $1)),
+                    MIN(created_at) FILTER (WHERE status = 'PENDING')
+             FROM seal_outbox"
+        )
+        .bind(sla_minutes as i32)
+        .fetch_one(db_pool)
+        .await?;
+        let overdue_entries = sqlx::query_as(
+            "SELECT * FROM seal_outbox
+             WHERE status = 'PENDING' AND created_at < CURRENT_TIMESTAMP - make_interval(mins => $1)
+             ORDER BY created_at
+             LIMIT 50"
+        )
+        .bind(sla_minutes as i32)
+        .fetch_all(db_pool)
+        .await?;
+        Ok(SealOutboxStatus {
+            pending,
+            sealed,
+            overdue,
+            sla_minutes,
+            oldest_pending_at,
+            oldest_pending_age_seconds: oldest_pending_at.map(|t| (Utc::now() - t).num_seconds()),
+            overdue_entries,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay_backs_off_to_cap() {
+        assert_eq!(retry_delay_secs(1), 30);
+        assert_eq!(retry_delay_secs(2), 60);
+        assert_eq!(retry_delay_secs(4), 240);
+        assert_eq!(retry_delay_secs(8), MAX_RETRY_SECS);
+        assert_eq!(retry_delay_secs(i32::MAX), MAX_RETRY_SECS);
+        assert_eq!(retry_delay_secs(0), 30);
+    }
+}
@@ -0,0 +1,789 @@
+// Seal Providers
+// Hashes (log records, Merkle roots, erasure certificates) are sealed by a
+// pluggable backend chosen per tenant: Signicat, an RFC 3161 Time-Stamp
+// Authority or, for development, a local HMAC key. Every seal's token is kept
+// in seal_tokens so it can be exported and verified offline.
+
+use crate::core::privacy_bridge::SignicatClient;
+use crate::core::seal_outbox::SealOutbox;
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::Duration;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Backend names, as stored in seal_provider_settings.provider
+pub const SIGNICAT: &str = "SIGNICAT";
+pub const RFC3161: &str = "RFC3161";
+pub const LOCAL: &str = "LOCAL";
+
+/// A seal over one hash
+#[derive(Debug, Clone)]
+pub struct SealToken {
+    pub provider: &'static str,
+    /// ID handed back to callers (compliance_records.seal_id etc.)
+    pub seal_id: String,
+    /// Hex SHA-256 that was sealed
+    pub sealed_hash: String,
+    /// Provider evidence: DER TimeStampToken, local seal JSON or Signicat response
+    pub token: Vec<u8>,
+    pub sealed_at: DateTime<Utc>,
+}
+
+/// Backend that seals hex SHA-256 hashes
+#[async_trait]
+pub trait SealProvider: Send + Sync {
+    /// Backend name (SIGNICAT, RFC3161 or LOCAL)
+    fn name(&self) -> &'static str;
+
+    /// Seal a hex-encoded SHA-256 hash
+    async fn seal(&self, hash: &str) -> Result<SealToken, String>;
+}
+
+fn decode_sha256_hex(hash: &str) -> Result<Vec<u8>, String> {
+    if hash.len() != 64 {
+        return Err(format!("Expected a hex SHA-256 hash, got {} characters", hash.len()));
+    }
+    (0..64)
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hash[i..i + 2], 16).map_err(|_| "Hash is not hex".to_string()))
+        .collect()
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// ========== SIGNICAT ==========
+
+#[async_trait]
+impl SealProvider for SignicatClient {
+    fn name(&self) -> &'static str {
+        SIGNICAT
+    }
+
+    async fn seal(&self, hash: &str) -> Result<SealToken, String> {
+        let seal_id = self.request_seal(hash).await?;
+        Ok(SealToken {
+            provider: SIGNICAT,
+            token: seal_id.clone().into_bytes(),
+            seal_id,
+            sealed_hash: hash.to_string(),
+            sealed_at: Utc::now(),
+        })
+    }
+}
+
+// ========== LOCAL HMAC KEY (development) ==========
+
+/// Seals with HMAC-SHA256 under a local key (VERIDION_LOCAL_SEAL_KEY)
+///
+/// Not a qualified seal: anyone holding the key can produce one. Meant for
+/// development and tests without a Signicat account or TSA.
+pub struct LocalSealProvider {
+    key_id: String,
+    key: Vec<u8>,
+}
+
+/// Token of a local seal, stored as JSON
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalSeal {
+    pub key_id: String,
+    pub hash: String,
+    /// RFC 3339
+    pub sealed_at: String,
+    /// Hex HMAC-SHA256 over `hash` and `sealed_at` joined by '|'
+    pub mac: String,
+}
+
+impl LocalSealProvider {
+    pub fn new(key_id: &str, key: &[u8]) -> Self {
+        Self { key_id: key_id.to_string(), key: key.to_vec() }
+    }
+
+    /// VERIDION_LOCAL_SEAL_KEY (and VERIDION_LOCAL_SEAL_KEY_ID, default `local-v1`); None if unset
+    pub fn from_env() -> Option<Self> {
+        let key = std::env::var("VERIDION_LOCAL_SEAL_KEY").ok().filter(|k| !k.is_empty())?;
+        let key_id = std::env::var("VERIDION_LOCAL_SEAL_KEY_ID").unwrap_or_else(|_| "local-v1".to_string());
+        Some(Self::new(&key_id, key.as_bytes()))
+    }
+
+    fn mac(&self, hash: &str, sealed_at: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.key).expect("HMAC accepts keys of any length");
+        mac.update(format!("{}|{}", hash, sealed_at).as_bytes());
+        encode_hex(&mac.finalize().into_bytes())
+    }
+
+    /// Check a local seal token against this key
+    pub fn verify(&self, seal: &LocalSeal) -> bool {
+        seal.key_id == self.key_id && self.mac(&seal.hash, &seal.sealed_at) == seal.mac
+    }
+}
+
+#[async_trait]
+impl SealProvider for LocalSealProvider {
+    fn name(&self) -> &'static str {
+        LOCAL
+    }
+
+    async fn seal(&self, hash: &str) -> Result<SealToken, String> {
+        let sealed_at = Utc::now();
+        let seal = LocalSeal {
+            key_id: self.key_id.clone(),
+            hash: hash.to_string(),
+            sealed_at: sealed_at.to_rfc3339(),
+            mac: self.mac(hash, &sealed_at.to_rfc3339()),
+        };
+        Ok(SealToken {
+            provider: LOCAL,
+            seal_id: format!("LOCAL_SEAL_{} | TIMESTAMP: {}", &seal.mac[..16], sealed_at.timestamp()),
+            sealed_hash: hash.to_string(),
+            token: serde_json::to_vec(&seal).map_err(|e| e.to_string())?,
+            sealed_at,
+        })
+    }
+}
+
+// ========== RFC 3161 TIME-STAMP AUTHORITY ==========
+
+/// DER: OID 2.16.840.1.101.3.4.2.1 (SHA-256)
+const SHA256_OID: &[u8] = &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01];
+
+pub(crate) mod der {
+    //! Just enough DER for TimeStampReq / TimeStampResp
+
+    pub const INTEGER: u8 = 0x02;
+    pub const BOOLEAN: u8 = 0x01;
+    pub const OCTET_STRING: u8 = 0x04;
+    pub const NULL: u8 = 0x05;
+    pub const OID: u8 = 0x06;
+    pub const UTF8_STRING: u8 = 0x0c;
+    pub const GENERALIZED_TIME: u8 = 0x18;
+    pub const SEQUENCE: u8 = 0x30;
+    /// [0] constructed, context-specific
+    pub const CONTEXT_0: u8 = 0xa0;
+
+    pub fn tlv(tag: u8, content: &[u8]) -> Vec<u8> {
+        let mut out = vec![tag];
+        let len = content.len();
+        if len < 0x80 {
+            out.push(len as u8);
+        } else {
+            let bytes: Vec<u8> = len.to_be_bytes().iter().copied().skip_while(|b| *b == 0).collect();
+            out.push(0x80 | bytes.len() as u8);
+            out.extend(bytes);
+        }
+        out.extend_from_slice(content);
+        out
+    }
+
+    pub fn sequence(items: &[Vec<u8>]) -> Vec<u8> {
+        tlv(SEQUENCE, &items.concat())
+    }
+
+    /// Non-negative INTEGER from big-endian bytes
+    pub fn unsigned_integer(bytes: &[u8]) -> Vec<u8> {
+        let mut content: Vec<u8> = bytes.iter().copied().skip_while(|b| *b == 0).collect();
+        if content.is_empty() || content[0] & 0x80 != 0 {
+            content.insert(0, 0);
+        }
+        tlv(INTEGER, &content)
+    }
+
+    /// OBJECT IDENTIFIER from dotted notation
+    pub fn oid(dotted: &str) -> Result<Vec<u8>, String> {
+        let arcs: Vec<u64> = dotted
+            .split('.')
+            .map(|a| a.parse::<u64>().map_err(|_| format!("Invalid OID '{}'", dotted)))
+            .collect::<Result<_, _>>()?;
+        if arcs.len() < 2 || arcs[0] > 2 {
+            return Err(format!("Invalid OID '{}'", dotted));
+        }
+        let mut content = Vec::new();
+        for arc in std::iter::once(arcs[0] * 40 + arcs[1]).chain(arcs[2..].iter().copied()) {
+            let mut chunk = vec![(arc & 0x7f) as u8];
+            let mut rest = arc >> 7;
+            while rest > 0 {
+                chunk.insert(0, 0x80 | (rest & 0x7f) as u8);
+                rest >>= 7;
+            }
+            content.extend(chunk);
+        }
+        Ok(tlv(OID, &content))
+    }
+
+    /// Dotted notation of OID content bytes
+    pub fn oid_to_string(content: &[u8]) -> String {
+        let mut arcs = Vec::new();
+        let mut value: u64 = 0;
+        for byte in content {
+            value = (value << 7) | (byte & 0x7f) as u64;
+            if byte & 0x80 == 0 {
+                if arcs.is_empty() {
+                    let first = (value / 40).min(2);
+                    arcs.push(first);
+                    arcs.push(value - first * 40);
+                } else {
+                    arcs.push(value);
+                }
+                value = 0;
+            }
+        }
+        arcs.iter().map(|a| a.to_string()).collect::<Vec<_>>().join(".")
+    }
+
+    /// One decoded element
+    pub struct Element<'a> {
+        pub tag: u8,
+        pub content: &'a [u8],
+        /// Tag, length and content
+        pub raw: &'a [u8],
+    }
+
+    /// Read one element, returning it and the bytes after it
+    pub fn read(input: &[u8]) -> Result<(Element<'_>, &[u8]), String> {
+        let truncated = || "Truncated DER".to_string();
+        let tag = *input.first().ok_or_else(truncated)?;
+        let first_len = *input.get(1).ok_or_else(truncated)?;
+        let (len, header) = if first_len < 0x80 {
+            (first_len as usize, 2)
+        } else {
+            let count = (first_len & 0x7f) as usize;
+            if count == 0 || count > 4 {
+                return Err("Unsupported DER length".to_string());
+            }
+            let bytes = input.get(2..2 + count).ok_or_else(truncated)?;
+            (bytes.iter().fold(0usize, |acc, b| (acc << 8) | *b as usize), 2 + count)
+        };
+        let end = header.checked_add(len).ok_or_else(truncated)?;
+        let content = input.get(header..end).ok_or_else(truncated)?;
+        Ok((Element { tag, content, raw: &input[..end] }, &input[end..]))
+    }
+
+    /// All elements inside a constructed element's content
+    pub fn children(mut content: &[u8]) -> Result<Vec<Element<'_>>, String> {
+        let mut items = Vec::new();
+        while !content.is_empty() {
+            let (element, rest) = read(content)?;
+            items.push(element);
+            content = rest;
+        }
+        Ok(items)
+    }
+
+    /// Read one element and check its tag
+    pub fn expect<'a>(input: &'a [u8], tag: u8, what: &str) -> Result<Element<'a>, String> {
+        let (element, _) = read(input)?;
+        if element.tag != tag {
+            return Err(format!("Expected {} (tag {:#04x}), found tag {:#04x}", what, tag, element.tag));
+        }
+        Ok(element)
+    }
+}
+
+/// Build a DER TimeStampReq for a SHA-256 hash (certReq set, so the token carries the TSA certificate)
+pub fn timestamp_request(hash: &[u8], policy_oid: Option<&str>, nonce: &[u8]) -> Result<Vec<u8>, String> {
+    let algorithm = der::sequence(&[der::tlv(der::OID, SHA256_OID), der::tlv(der::NULL, &[])]);
+    let imprint = der::sequence(&[algorithm, der::tlv(der::OCTET_STRING, hash)]);
+    let mut fields = vec![der::unsigned_integer(&[1]), imprint];
+    if let Some(policy) = policy_oid {
+        fields.push(der::oid(policy)?);
+    }
+    fields.push(der::unsigned_integer(nonce));
+    fields.push(der::tlv(der::BOOLEAN, &[0xff]));
+    Ok(der::sequence(&fields))
+}
+
+/// The signed content of a time-stamp token
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TstInfo {
+    /// TSA policy OID
+    pub policy: String,
+    /// Hex hash the TSA time-stamped
+    pub hashed_message: String,
+    /// Hex serial number assigned by the TSA
+    pub serial_number: String,
+    pub gen_time: DateTime<Utc>,
+    /// Hex nonce echoed from the request, without leading zero bytes
+    pub nonce: Option<String>,
+}
+
+/// Extract the TimeStampToken from a DER TimeStampResp; fails unless the TSA granted it
+pub fn parse_timestamp_response(response: &[u8]) -> Result<Vec<u8>, String> {
+    let outer = der::expect(response, der::SEQUENCE, "TimeStampResp")?;
+    let parts = der::children(outer.content)?;
+    let status_info = parts.first().ok_or("Empty TimeStampResp")?;
+    let status_fields = der::children(status_info.content)?;
+    let status = status_fields
+        .first()
+        .filter(|e| e.tag == der::INTEGER)
+        .map(|e| e.content.iter().fold(0u32, |acc, b| (acc << 8) | *b as u32))
+        .ok_or("TimeStampResp has no status")?;
+    // 0 = granted, 1 = grantedWithMods
+    if status > 1 {
+        let text = status_fields
+            .get(1)
+            .and_then(|free_text| der::children(free_text.content).ok())
+            .and_then(|strings| strings.first().filter(|s| s.tag == der::UTF8_STRING).map(|s| String::from_utf8_lossy(s.content).to_string()))
+            .unwrap_or_default();
+        return Err(format!("TSA rejected the request (status {}) {}", status, text).trim().to_string());
+    }
+    let token = parts.get(1).ok_or("TimeStampResp granted without a token")?;
+    Ok(token.raw.to_vec())
+}
+
+/// Decode the TSTInfo inside a DER TimeStampToken (CMS SignedData)
+///
+/// Only the content is decoded; the TSA's CMS signature is checked with
+/// standard tooling (`openssl ts -verify`) against the TSA certificate.
+pub fn parse_timestamp_token(token: &[u8]) -> Result<TstInfo, String> {
+    let content_info = der::expect(token, der::SEQUENCE, "ContentInfo")?;
+    let content_info = der::children(content_info.content)?;
+    let signed_data = content_info.get(1).filter(|e| e.tag == der::CONTEXT_0).ok_or("ContentInfo has no content")?;
+    let signed_data = der::expect(signed_data.content, der::SEQUENCE, "SignedData")?;
+    let signed_data = der::children(signed_data.content)?;
+    let encap = signed_data.get(2).filter(|e| e.tag == der::SEQUENCE).ok_or("SignedData has no encapContentInfo")?;
+    let encap = der::children(encap.content)?;
+    let econtent = encap.get(1).filter(|e| e.tag == der::CONTEXT_0).ok_or("Token has no TSTInfo")?;
+    let octets = der::expect(econtent.content, der::OCTET_STRING, "eContent")?;
+    let tst_info = der::expect(octets.content, der::SEQUENCE, "TSTInfo")?;
+    let fields = der::children(tst_info.content)?;
+
+    let policy = fields.get(1).filter(|e| e.tag == der::OID).ok_or("TSTInfo has no policy")?;
+    let imprint = fields.get(2).filter(|e| e.tag == der::SEQUENCE).ok_or("TSTInfo has no messageImprint")?;
+    let hashed = der::children(imprint.content)?
+        .into_iter()
+        .nth(1)
+        .filter(|e| e.tag == der::OCTET_STRING)
+        .ok_or("messageImprint has no hash")?
+        .content;
+    let serial = fields.get(3).filter(|e| e.tag == der::INTEGER).ok_or("TSTInfo has no serialNumber")?;
+    let gen_time = fields.get(4).filter(|e| e.tag == der::GENERALIZED_TIME).ok_or("TSTInfo has no genTime")?;
+    let gen_time = String::from_utf8_lossy(gen_time.content);
+    let gen_time = gen_time
+        .get(..14)
+        .and_then(|t| NaiveDateTime::parse_from_str(t, "%Y%m%d%H%M%S").ok())
+        .ok_or_else(|| format!("Invalid genTime '{}'", gen_time))?
+        .and_utc();
+    // accuracy (SEQUENCE) and ordering (BOOLEAN) may come first; the only INTEGER is the nonce
+    let nonce = fields.iter().skip(5).find(|e| e.tag == der::INTEGER);
+
+    Ok(TstInfo {
+        policy: der::oid_to_string(policy.content),
+        hashed_message: encode_hex(hashed),
+        serial_number: encode_hex(serial.content),
+        gen_time,
+        nonce: nonce.map(|n| nonce_hex(n.content)),
+    })
+}
+
+/// Hex of a nonce as an unsigned big-endian number, so `00 80 01` and `80 01` compare equal
+fn nonce_hex(bytes: &[u8]) -> String {
+    let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+    encode_hex(&bytes[start..])
+}
+
+/// Time-stamps hashes at an RFC 3161 TSA over HTTP
+pub struct Rfc3161Provider {
+    client: Client,
+    tsa_url: String,
+    policy_oid: Option<String>,
+    /// Authorization header value (RFC3161_TSA_AUTHORIZATION), if the TSA requires one
+    authorization: Option<String>,
+}
+
+impl Rfc3161Provider {
+    pub fn new(client: Client, tsa_url: &str, policy_oid: Option<&str>, authorization: Option<String>) -> Self {
+        Self {
+            client,
+            tsa_url: tsa_url.to_string(),
+            policy_oid: policy_oid.map(|p| p.to_string()),
+            authorization,
+        }
+    }
+}
+
+#[async_trait]
+impl SealProvider for Rfc3161Provider {
+    fn name(&self) -> &'static str {
+        RFC3161
+    }
+
+    async fn seal(&self, hash: &str) -> Result<SealToken, String> {
+        let digest = decode_sha256_hex(hash)?;
+        let mut nonce = vec![0u8; 8];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let request = timestamp_request(&digest, self.policy_oid.as_deref(), &nonce)?;
+
+        let mut http = self
+            .client
+            .post(&self.tsa_url)
+            .header("Content-Type", "application/timestamp-query")
+            .header("Accept", "application/timestamp-reply")
+            .timeout(Duration::from_secs(10))
+            .body(request);
+        if let Some(authorization) = &self.authorization {
+            http = http.header("Authorization", authorization);
+        }
+        let response = http.send().await.map_err(|e| format!("TSA request failed: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!("TSA returned status {}", response.status()));
+        }
+        let body = response.bytes().await.map_err(|e| format!("Failed to read TSA response: {}", e))?;
+
+        let token = parse_timestamp_response(&body)?;
+        let tst_info = parse_timestamp_token(&token)?;
+        if tst_info.hashed_message != hash.to_lowercase() {
+            return Err("TSA time-stamped a different hash".to_string());
+        }
+        // A token without our nonce may be a replayed answer to another request
+        if tst_info.nonce.as_deref() != Some(nonce_hex(&nonce).as_str()) {
+            return Err("TSA response does not carry the request nonce".to_string());
+        }
+        Ok(SealToken {
+            provider: RFC3161,
+            // Serial numbers are only unique per TSA, so add a digest of the token
+            seal_id: format!(
+                "TSA_SEAL_{}-{} | TIMESTAMP: {}",
+                tst_info.serial_number,
+                &encode_hex(&Sha256::digest(&token))[..8],
+                tst_info.gen_time.timestamp()
+            ),
+            sealed_hash: hash.to_string(),
+            token,
+            sealed_at: tst_info.gen_time,
+        })
+    }
+}
+
+// ========== PER-TENANT SELECTION ==========
+
+/// Sealing backend of one tenant (or the default when company_id is None)
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, ToSchema)]
+pub struct SealProviderSetting {
+    pub company_id: Option<Uuid>,
+    /// SIGNICAT, RFC3161 or LOCAL
+    pub provider: String,
+    /// TSA endpoint (RFC3161 only)
+    pub tsa_url: Option<String>,
+    /// Requested TSA policy OID (RFC3161 only)
+    pub tsa_policy_oid: Option<String>,
+    pub updated_by: Option<String>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl SealProviderSetting {
+    /// Check the setting before it is stored
+    pub fn validate(&self) -> Result<(), String> {
+        match self.provider.as_str() {
+            SIGNICAT | LOCAL => Ok(()),
+            RFC3161 => {
+                let url = self.tsa_url.as_deref().ok_or("tsa_url is required for RFC3161")?;
+                let parsed = reqwest::Url::parse(url).map_err(|_| format!("Invalid tsa_url '{}'", url))?;
+                if parsed.scheme() != "http" && parsed.scheme() != "https" {
+                    return Err("tsa_url must be http or https".to_string());
+                }
+                if let Some(oid) = &self.tsa_policy_oid {
+                    der::oid(oid)?;
+                }
+                Ok(())
+            }
+            other => Err(format!("Unknown provider '{}'; expected SIGNICAT, RFC3161 or LOCAL", other)),
+        }
+    }
+}
+
+/// A stored seal and what an offline check of its token found
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SealVerification {
+    pub seal_id: String,
+    pub provider: String,
+    pub company_id: Option<Uuid>,
+    pub sealed_hash: String,
+    pub sealed_at: DateTime<Utc>,
+    /// Base64 of the stored token (DER TimeStampToken for RFC3161)
+    pub token: String,
+    /// Decoded token content (RFC3161 only)
+    pub tst_info: Option<TstInfo>,
+    /// The token covers sealed_hash (for SIGNICAT, the stored reference is taken as is)
+    pub imprint_matches: bool,
+    /// The token's signature was checked here; only LOCAL MACs can be. RFC3161
+    /// tokens are verified with `openssl ts -verify`, SIGNICAT seals with Signicat
+    pub signature_verified: bool,
+    pub detail: String,
+}
+
+impl SealVerification {
+    /// The token seals `hash`, and its signature checked out if it is one this
+    /// deployment can check (LOCAL MACs)
+    pub fn seals_hash(&self, hash: &str) -> bool {
+        self.imprint_matches && self.sealed_hash == hash && (self.provider != LOCAL || self.signature_verified)
+    }
+}
+
+/// (provider, company_id, sealed_hash, token, sealed_at) of a stored seal
+type SealTokenRow = (String, Option<Uuid>, String, Vec<u8>, DateTime<Utc>);
+
+/// Seals hashes with each tenant's configured provider and keeps the tokens
+pub struct SealService {
+    signicat: Arc<SignicatClient>,
+    local: Option<Arc<LocalSealProvider>>,
+    client: Client,
+    /// VERIDION_SEAL_PROVIDER, used when no setting applies
+    default_provider: String,
+    default_tsa_url: Option<String>,
+    default_tsa_policy_oid: Option<String>,
+    tsa_authorization: Option<String>,
+}
+
+impl SealService {
+    /// Default backend from VERIDION_SEAL_PROVIDER (SIGNICAT unless set), with
+    /// RFC3161_TSA_URL / RFC3161_TSA_POLICY_OID for an RFC3161 default
+    pub fn from_env(signicat: Arc<SignicatClient>) -> Self {
+        Self {
+            signicat,
+            local: LocalSealProvider::from_env().map(Arc::new),
+            client: Client::new(),
+            default_provider: std::env::var("VERIDION_SEAL_PROVIDER")
+                .map(|p| p.to_uppercase())
+                .unwrap_or_else(|_| SIGNICAT.to_string()),
+            default_tsa_url: std::env::var("RFC3161_TSA_URL").ok(),
+            default_tsa_policy_oid: std::env::var("RFC3161_TSA_POLICY_OID").ok(),
+            tsa_authorization: std::env::var("RFC3161_TSA_AUTHORIZATION").ok(),
+        }
+    }
+
+    /// Replace the local signing key (tests)
+    pub fn with_local_provider(mut self, local: LocalSealProvider) -> Self {
+        self.local = Some(Arc::new(local));
+        self
+    }
+
+    /// Setting that applies to a tenant: its own, else the stored default, else the environment
+    pub async fn setting(&self, db_pool: &PgPool, company_id: Option<Uuid>) -> Result<SealProviderSetting, sqlx::Error> {
+        let stored: Option<SealProviderSetting> = sqlx::query_as(
+            "SELECT company_id, provider, tsa_url, tsa_policy_oid, updated_by, updated_at
+             FROM seal_provider_settings
+             WHERE company_id = $1 OR company_id IS NULL
+             ORDER BY company_id IS NULL
+             LIMIT 1"
+        )
+        .bind(company_id)
+        .fetch_optional(db_pool)
+        .await?;
+        Ok(stored.unwrap_or_else(|| SealProviderSetting {
+            company_id: None,
+            provider: self.default_provider.clone(),
+            tsa_url: self.default_tsa_url.clone(),
+            tsa_policy_oid: self.default_tsa_policy_oid.clone(),
+            updated_by: None,
+            updated_at: None,
+        }))
+    }
+
+    /// Store a tenant's setting (company_id None sets the default)
+    pub async fn set_setting(&self, db_pool: &PgPool, setting: &SealProviderSetting) -> Result<SealProviderSetting, sqlx::Error> {
+        sqlx::query_as(
+            "INSERT INTO seal_provider_settings (company_id, provider, tsa_url, tsa_policy_oid, updated_by)
+             VALUES ($1, $2, $3, $4, $5)
+             ON CONFLICT ((COALESCE(company_id, '00000000-0000-0000-0000-000000000000'::uuid)))
+             DO UPDATE SET provider = EXCLUDED.provider, tsa_url = EXCLUDED.tsa_url,
+                           tsa_policy_oid = EXCLUDED.tsa_policy_oid, updated_by = EXCLUDED.updated_by,
+                           updated_at = CURRENT_TIMESTAMP
+             RETURNING company_id, provider, tsa_url, tsa_policy_oid, updated_by, updated_at"
+        )
+        .bind(setting.company_id)
+        .bind(&setting.provider)
+        .bind(&setting.tsa_url)
+        .bind(&setting.tsa_policy_oid)
+        .bind(&setting.updated_by)
+        .fetch_one(db_pool)
+        .await
+    }
+
+    fn provider_for(&self, setting: &SealProviderSetting) -> Result<Arc<dyn SealProvider>, String> {
+        match setting.provider.as_str() {
+            SIGNICAT => Ok(self.signicat.clone()),
+            LOCAL => self
+                .local
+                .clone()
+                .map(|local| local as Arc<dyn SealProvider>)
+                .ok_or_else(|| "LOCAL sealing needs VERIDION_LOCAL_SEAL_KEY".to_string()),
+            RFC3161 => {
+                let url = setting.tsa_url.as_deref().ok_or("RFC3161 sealing needs a TSA URL")?;
+                Ok(Arc::new(Rfc3161Provider::new(
+                    self.client.clone(),
+                    url,
+                    setting.tsa_policy_oid.as_deref(),
+                    self.tsa_authorization.clone(),
+                )))
+            }
+            other => Err(format!("Unknown seal provider '{}'", other)),
+        }
+    }
+
+    /// Seal a hex SHA-256 hash with the tenant's provider and keep the token
+    ///
+    /// Returns the seal ID. A token that cannot be stored is logged, not fatal:
+    /// the seal itself exists at the provider.
+    pub async fn seal(&self, db_pool: &PgPool, company_id: Option<Uuid>, hash: &str) -> Result<String, String> {
+        let setting = self.setting(db_pool, company_id).await.map_err(|e| e.to_string())?;
+        let token = self.provider_for(&setting)?.seal(hash).await?;
+        if let Err(e) = sqlx::query(
+            "INSERT INTO seal_tokens (seal_id, provider, company_id, sealed_hash, token, sealed_at)
+             VALUES ($1, $2, $3, $4, $5, $6)
+             ON CONFLICT (seal_id) DO NOTHING"
+        )
+        .bind(&token.seal_id)
+        .bind(token.provider)
+        .bind(company_id)
+        .bind(&token.sealed_hash)
+        .bind(&token.token)
+        .bind(token.sealed_at)
+        .execute(db_pool)
+        .await
+        {
+            eprintln!("Error storing seal token for {}: {}", token.seal_id, e);
+        }
+        Ok(token.seal_id)
+    }
+
+    /// Seal a hash, or queue it in the seal outbox if the provider fails
+    ///
+    /// Returns the seal ID, or a PENDING_SYNC_LOCAL placeholder that the outbox
+    /// resolves later. Only if the hash cannot even be queued is the seal
+    /// recorded as `ERROR: ...`.
+    pub async fn seal_or_defer(&self, db_pool: &PgPool, company_id: Option<Uuid>, hash: &str) -> String {
+        match self.seal(db_pool, company_id, hash).await {
+            Ok(seal_id) => seal_id,
+            Err(e) => {
+                eprintln!("⚠️ Sealing failed, queueing hash in the seal outbox: {}", e);
+                SealOutbox::enqueue(db_pool, company_id, hash, &e)
+                    .await
+                    .unwrap_or_else(|queue_error| format!("ERROR: {}; {}", e, queue_error))
+            }
+        }
+    }
+
+    /// Load a stored seal and check its token; None if no token was stored
+    ///
+    /// A PENDING_SYNC_LOCAL placeholder resolves to the seal the outbox obtained for it.
+    pub async fn verify(&self, db_pool: &PgPool, seal_id: &str) -> Result<Option<SealVerification>, sqlx::Error> {
+        let resolved: Option<String> = sqlx::query_scalar(
+            "SELECT seal_id FROM seal_outbox WHERE placeholder_seal_id = $1 AND status = 'SEALED'"
+        )
+        .bind(seal_id)
+        .fetch_optional(db_pool)
+        .await?;
+        let seal_id = resolved.as_deref().unwrap_or(seal_id);
+        let row: Option<SealTokenRow> = sqlx::query_as(
+            "SELECT provider, company_id, sealed_hash, token, sealed_at FROM seal_tokens WHERE seal_id = $1"
+        )
+        .bind(seal_id)
+        .fetch_optional(db_pool)
+        .await?;
+        let (provider, company_id, sealed_hash, token, sealed_at) = match row {
+            Some(row) => row,
+            None => return Ok(None),
+        };
+
+        let mut tst_info = None;
+        let (imprint_matches, signature_verified, detail) = match provider.as_str() {
+            RFC3161 => match parse_timestamp_token(&token) {
+                Ok(info) => {
+                    let covers = info.hashed_message == sealed_hash.to_lowercase();
+                    tst_info = Some(info);
+                    if covers {
+                        (true, false, "Token time-stamps the sealed hash; verify the TSA signature with `openssl ts -verify`".to_string())
+                    } else {
+                        (false, false, "Token time-stamps a different hash".to_string())
+                    }
+                }
+                Err(e) => (false, false, format!("Token could not be decoded: {}", e)),
+            },
+            LOCAL => match (serde_json::from_slice::<LocalSeal>(&token), &self.local) {
+                (Ok(seal), Some(local)) if seal.hash == sealed_hash && local.verify(&seal) => {
+                    (true, true, "MAC matches the local seal key".to_string())
+                }
+                (Ok(seal), None) => (seal.hash == sealed_hash, false, "No local seal key configured to check the MAC".to_string()),
+                (Ok(seal), Some(_)) => (seal.hash == sealed_hash, false, "MAC does not match".to_string()),
+                (Err(e), _) => (false, false, format!("Token could not be decoded: {}", e)),
+            },
+            _ => (true, false, "Seal reference from the provider; verify it with the provider".to_string()),
+        };
+
+        use base64::Engine;
+        Ok(Some(SealVerification {
+            seal_id: seal_id.to_string(),
+            provider,
+            company_id,
+            sealed_hash,
+            sealed_at,
+            token: base64::engine::general_purpose::STANDARD.encode(&token),
+            tst_info,
+            imprint_matches,
+            signature_verified,
+            detail,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HASH: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+    #[test]
+    fn test_timestamp_request_encoding() {
+        let digest = decode_sha256_hex(HASH).unwrap();
+        let request = timestamp_request(&digest, Some("1.2.3.4"), &[0x80, 1]).unwrap();
+        let outer = der::expect(&request, der::SEQUENCE, "TimeStampReq").unwrap();
+        let fields = der::children(outer.content).unwrap();
+        assert_eq!(fields.len(), 5);
+        assert_eq!(fields[0].content, &[1]);
+        let imprint = der::children(fields[1].content).unwrap();
+        assert_eq!(imprint[1].content, digest.as_slice());
+        assert_eq!(der::oid_to_string(fields[2].content), "1.2.3.4");
+        // A nonce with the high bit set gets a leading zero to stay positive
+        assert_eq!(fields[3].content, &[0, 0x80, 1]);
+        assert_eq!(nonce_hex(fields[3].content), "8001");
+        assert_eq!(der::oid_to_string(SHA256_OID), "2.16.840.1.101.3.4.2.1");
+    }
+
+    #[actix_web::test]
+    async fn test_rfc3161_provider_with_mock_tsa() {
+        let tsa_url = crate::test_helpers::spawn_mock_tsa().await;
+        let provider = Rfc3161Provider::new(Client::new(), &tsa_url, None, None);
+        let token = provider.seal(HASH).await.unwrap();
+        assert!(token.seal_id.starts_with("TSA_SEAL_"));
+
+        let info = parse_timestamp_token(&token.token).unwrap();
+        assert_eq!(info.hashed_message, HASH);
+        assert!(info.nonce.is_some());
+        assert!(provider.seal("not a hash").await.is_err());
+
+        // A TSA answering with another request's nonce is not trusted
+        let replaying = Rfc3161Provider::new(Client::new(), &format!("{}/replay", tsa_url), None, None);
+        assert!(replaying.seal(HASH).await.unwrap_err().contains("nonce"));
+    }
+
+    #[tokio::test]
+    async fn test_local_seal_verifies_only_with_its_key() {
+        let provider = LocalSealProvider::new("dev", b"local development key");
+        let token = provider.seal(HASH).await.unwrap();
+        let seal: LocalSeal = serde_json::from_slice(&token.token).unwrap();
+        assert!(provider.verify(&seal));
+        assert!(!LocalSealProvider::new("dev", b"another key").verify(&seal));
+
+        let tampered = LocalSeal { hash: "00".repeat(32), ..seal };
+        assert!(!provider.verify(&tampered));
+    }
+}
@@ -1,5 +1,11 @@
+use crate::core::region_catalog::RegionCatalog;
+use ipnet::IpNet;
+use maxminddb::Reader;
+use serde::Serialize;
+use std::net::IpAddr;
+use std::sync::Arc;
+
 /// EU/EEA country ISO codes whitelist
-#[allow(dead_code)]
 pub const EU_EEA_WHITELIST: &[&str] = &[
     "EU", // European Union (generic code for EU institutions and .eu domains)
     "AT", // Austria
@@ -35,52 +41,358 @@ pub const EU_EEA_WHITELIST: &[&str] = &[
     "NO", // Norway
 ];
 
-/// Mock geo-lookup function for testing purposes
-/// 
-/// Returns ISO country codes based on IP address patterns:
-/// - Private IPs (192.x.x.x, 10.x.x.x) -> "SK" (Slovakia)
-/// - 8.8.8.8 (Google DNS) -> "US"
-/// - 1.1.1.1 (Cloudflare DNS) -> "US"
-/// - 5.1.2.3 -> "DE" (Germany)
-/// - Default -> "UNKNOWN"
-#[allow(dead_code)]
-pub fn mock_geo_lookup(ip: &str) -> String {
-    // Check for private/local IP ranges
-    if ip.starts_with("192.") || ip.starts_with("10.") {
-        return "SK".to_string();
-    }
-    
-    // Check for specific known IPs
+/// Country code used when the jurisdiction cannot be determined
+pub const UNKNOWN_COUNTRY: &str = "UNKNOWN";
+
+/// How a sovereignty verdict was reached
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum VerdictSource {
+    /// Address matched SOVEREIGNTY_DENY_CIDRS
+    DenyList,
+    /// Address matched SOVEREIGNTY_ALLOW_CIDRS
+    AllowList,
+    /// Private or loopback address (stays inside our own network)
+    PrivateNetwork,
+    /// MaxMind database lookup
+    GeoIp,
+    /// Hostname of a non-EU AI provider or cloud region
+    Hostname,
+    /// Declared region (ISO code, country name or cloud region)
+    Region,
+    /// Nothing matched, treated as non-sovereign
+    Unresolved,
+}
+
+/// Result of a sovereignty check
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SovereigntyVerdict {
+    /// True if data may flow to this destination (EU/EEA or allow-listed)
+    pub allowed: bool,
+    /// ISO country code, "EU" or "UNKNOWN"
+    pub country: String,
+    pub source: VerdictSource,
+    /// Address the verdict was made for. Outbound connections must go to this
+    /// address, not to whatever the hostname resolves to later.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip: Option<IpAddr>,
+}
+
+impl SovereigntyVerdict {
+    fn for_country(country: &str, source: VerdictSource) -> Self {
+        Self {
+            allowed: EU_EEA_WHITELIST.contains(&country),
+            country: country.to_string(),
+            source,
+            ip: None,
+        }
+    }
+
+    fn unresolved() -> Self {
+        Self {
+            allowed: false,
+            country: UNKNOWN_COUNTRY.to_string(),
+            source: VerdictSource::Unresolved,
+            ip: None,
+        }
+    }
+
+    fn at(mut self, ip: IpAddr) -> Self {
+        self.ip = Some(ip);
+        self
+    }
+}
+
+/// Decides whether a destination is in a sovereign (EU/EEA) jurisdiction
+///
+/// Addresses are checked in order: deny list, allow list, private ranges, then
+/// the MaxMind databases. Cloud region codes are resolved through the region
+/// catalog. Anything that cannot be resolved is not allowed.
+pub struct SovereigntyResolver {
+    allow_cidrs: Vec<IpNet>,
+    deny_cidrs: Vec<IpNet>,
+    geoip_readers: Vec<Reader<Vec<u8>>>,
+    region_catalog: Arc<RegionCatalog>,
+}
+
+impl SovereigntyResolver {
+    /// Resolver without lists or GeoIP databases, using the built-in region catalog
+    pub fn new() -> Self {
+        Self {
+            allow_cidrs: Vec::new(),
+            deny_cidrs: Vec::new(),
+            geoip_readers: Vec::new(),
+            region_catalog: Arc::new(RegionCatalog::builtin()),
+        }
+    }
+
+    /// Build the resolver from GEOIP_DB_PATH, SOVEREIGNTY_ALLOW_CIDRS and SOVEREIGNTY_DENY_CIDRS
+    ///
+    /// # Panics
+    ///
+    /// Panics if a CIDR list is malformed, rather than starting with a partial list.
+    pub fn from_env() -> Self {
+        let mut resolver = Self::new();
+
+        if let Ok(list) = std::env::var("SOVEREIGNTY_ALLOW_CIDRS") {
+            resolver.allow_cidrs = parse_cidrs(&list)
+                .unwrap_or_else(|e| panic!("Invalid SOVEREIGNTY_ALLOW_CIDRS: {}", e));
+        }
+        if let Ok(list) = std::env::var("SOVEREIGNTY_DENY_CIDRS") {
+            resolver.deny_cidrs = parse_cidrs(&list)
+                .unwrap_or_else(|e| panic!("Invalid SOVEREIGNTY_DENY_CIDRS: {}", e));
+        }
+
+        match std::env::var("GEOIP_DB_PATH") {
+            Ok(paths) => {
+                for path in paths.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+                    match Reader::open_readfile(path) {
+                        Ok(reader) => {
+                            log::info!("GeoIP database loaded successfully from: {}", path);
+                            resolver.geoip_readers.push(reader);
+                        }
+                        Err(e) => {
+                            log::warn!("Failed to load GeoIP database from {}: {}", path, e);
+                        }
+                    }
+                }
+            }
+            Err(_) => {
+                log::warn!("GEOIP_DB_PATH not set. GeoIP is disabled. Only CIDR lists and private addresses will resolve.");
+            }
+        }
+
+        resolver
+    }
+
+    /// Addresses that are always allowed, whatever their GeoIP country
+    pub fn with_allow_cidrs(mut self, cidrs: Vec<IpNet>) -> Self {
+        self.allow_cidrs = cidrs;
+        self
+    }
+
+    /// Addresses that are always blocked, checked before the allow list
+    pub fn with_deny_cidrs(mut self, cidrs: Vec<IpNet>) -> Self {
+        self.deny_cidrs = cidrs;
+        self
+    }
+
+    /// Use a shared region catalog (e.g. one with database overrides)
+    pub fn with_region_catalog(mut self, catalog: Arc<RegionCatalog>) -> Self {
+        self.region_catalog = catalog;
+        self
+    }
+
+    /// Region catalog used for cloud region codes
+    pub fn region_catalog(&self) -> &Arc<RegionCatalog> {
+        &self.region_catalog
+    }
+
+    /// Verdict for a single address
+    pub fn resolve_addr(&self, ip: IpAddr) -> SovereigntyVerdict {
+        if self.deny_cidrs.iter().any(|net| net.contains(&ip)) {
+            return SovereigntyVerdict {
+                allowed: false,
+                country: self.lookup_geoip(ip).unwrap_or_else(|| UNKNOWN_COUNTRY.to_string()),
+                source: VerdictSource::DenyList,
+                ip: Some(ip),
+            };
+        }
+        if self.allow_cidrs.iter().any(|net| net.contains(&ip)) {
+            return SovereigntyVerdict {
+                allowed: true,
+                country: self.lookup_geoip(ip).unwrap_or_else(|| UNKNOWN_COUNTRY.to_string()),
+                source: VerdictSource::AllowList,
+                ip: Some(ip),
+            };
+        }
+        if is_private_ip(ip) {
+            return SovereigntyVerdict::for_country("EU", VerdictSource::PrivateNetwork).at(ip);
+        }
+        match self.lookup_geoip(ip) {
+            Some(country) => SovereigntyVerdict::for_country(&country, VerdictSource::GeoIp).at(ip),
+            None => SovereigntyVerdict::unresolved().at(ip),
+        }
+    }
+
+    /// Verdict for an IP address given as text
+    pub fn resolve_ip(&self, ip: &str) -> Result<SovereigntyVerdict, String> {
+        let addr: IpAddr = ip.trim().parse()
+            .map_err(|e| format!("Invalid IP address '{}': {}", ip, e))?;
+        Ok(self.resolve_addr(addr))
+    }
+
+    /// Verdict for a declared target region (e.g. `DE`, `United States`, `eu-west-1`)
+    ///
+    /// IP addresses are accepted too and go through `resolve_addr`.
+    pub fn resolve_region(&self, region: &str) -> SovereigntyVerdict {
+        if let Ok(addr) = region.trim().parse::<IpAddr>() {
+            return self.resolve_addr(addr);
+        }
+        if let Some(cloud_region) = self.region_catalog.lookup(region) {
+            return SovereigntyVerdict::for_country(&cloud_region.country, VerdictSource::Region);
+        }
+        match country_for_region(region) {
+            Some(country) => SovereigntyVerdict::for_country(&country, VerdictSource::Region),
+            None => SovereigntyVerdict::unresolved(),
+        }
+    }
+
+    /// Verdict for the host of a URL, made for one concrete address
+    ///
+    /// Every address the hostname resolves to must be allowed. The name can
+    /// only narrow that: a provider hostname in a non-EU cloud region or of a
+    /// known non-EU AI provider is blocked without a DNS lookup, but no name
+    /// can allow an address that is not. Allowed verdicts carry the approved
+    /// address in `ip`: connect to it rather than resolving the name again, or
+    /// DNS rebinding could swap in a different server after the check.
+    pub async fn resolve_url(&self, target_url: &str) -> Result<SovereigntyVerdict, String> {
+        let url = reqwest::Url::parse(target_url)
+            .map_err(|e| format!("Invalid URL: {}", e))?;
+        let hostname = url.host_str()
+            .ok_or_else(|| "No hostname in URL".to_string())?;
+
+        // IPv6 hosts are bracketed in URLs
+        if let Ok(addr) = hostname.trim_matches(|c| c == '[' || c == ']').parse::<IpAddr>() {
+            return Ok(self.resolve_addr(addr));
+        }
+
+        let by_name = self.region_catalog.find_in_hostname(hostname)
+            .map(|cloud_region| cloud_region.country)
+            .or_else(|| country_for_hostname(hostname))
+            .map(|country| SovereigntyVerdict::for_country(&country, VerdictSource::Hostname));
+        if let Some(verdict) = by_name.filter(|v| !v.allowed) {
+            return Ok(verdict);
+        }
+
+        let port = url.port_or_known_default().unwrap_or(443);
+        let addrs: Vec<IpAddr> = tokio::net::lookup_host((hostname, port))
+            .await
+            .map_err(|e| format!("DNS resolution failed: {}", e))?
+            .map(|addr| addr.ip())
+            .collect();
+        let verdicts: Vec<SovereigntyVerdict> = addrs.into_iter().map(|ip| self.resolve_addr(ip)).collect();
+
+        verdicts.iter()
+            .find(|v| !v.allowed)
+            .or_else(|| verdicts.first())
+            .cloned()
+            .ok_or_else(|| "No IP address found".to_string())
+    }
+
+    /// Look up an address in the GeoIP databases, first match wins
+    fn lookup_geoip(&self, ip: IpAddr) -> Option<String> {
+        for reader in &self.geoip_readers {
+            match reader.lookup::<maxminddb::geoip2::Country>(ip) {
+                Ok(country) => {
+                    if let Some(iso_code) = country.country.and_then(|c| c.iso_code) {
+                        log::debug!("GeoIP lookup for {}: {}", ip, iso_code);
+                        return Some(iso_code.to_string());
+                    }
+                }
+                Err(e) => log::debug!("GeoIP lookup failed for {}: {}", ip, e),
+            }
+        }
+        None
+    }
+}
+
+impl Default for SovereigntyResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parse a comma-separated list of CIDR ranges; bare addresses match exactly
+pub fn parse_cidrs(list: &str) -> Result<Vec<IpNet>, String> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            entry.parse::<IpNet>()
+                .or_else(|_| entry.parse::<IpAddr>().map(IpNet::from))
+                .map_err(|_| format!("'{}' is not a CIDR range or IP address", entry))
+        })
+        .collect()
+}
+
+/// Private, loopback and link-local addresses
+pub fn is_private_ip(ip: IpAddr) -> bool {
     match ip {
-        "8.8.8.8" => "US".to_string(),
-        "1.1.1.1" => "US".to_string(),
-        "5.1.2.3" => "DE".to_string(),
-        _ => "UNKNOWN".to_string(),
+        IpAddr::V4(v4) => v4.is_private() || v4.is_loopback() || v4.is_link_local(),
+        // fc00::/7 unique local, fe80::/10 link-local
+        IpAddr::V6(v6) => {
+            v6.is_loopback()
+                || (v6.segments()[0] & 0xfe00) == 0xfc00
+                || (v6.segments()[0] & 0xffc0) == 0xfe80
+        }
     }
 }
 
-/// Check if an IP address is from a sovereign (EU/EEA) jurisdiction
-/// 
-/// # Arguments
-/// 
-/// * `ip_address` - The IP address to check
-/// 
-/// # Returns
-/// 
-/// * `Ok(())` if the IP is from an EU/EEA country
-/// 
-/// # Panics
-/// 
-/// Panics with a specific error message if the IP is from a non-sovereign jurisdiction
-#[allow(dead_code)]
-pub fn check_sovereignty(ip_address: &str) -> Result<(), String> {
-    let country = mock_geo_lookup(ip_address);
-    
-    if EU_EEA_WHITELIST.contains(&country.as_str()) {
-        Ok(())
-    } else {
-        panic!("DATA_SOVEREIGNTY_VIOLATION: Attempted connection to non-sovereign jurisdiction [Country: {}]", country);
+/// Map a declared region to a country code
+fn country_for_region(region: &str) -> Option<String> {
+    let region = region.trim().to_uppercase();
+    if region.is_empty() {
+        return None;
+    }
+
+    // Country names
+    if region == "USA" || region.contains("UNITED STATES") {
+        return Some("US".to_string());
+    }
+    if region == "UNITED KINGDOM" || region == "UK" {
+        return Some("GB".to_string());
+    }
+    match region.as_str() {
+        "EUROPE" | "EUROPEAN UNION" => return Some("EU".to_string()),
+        "CHINA" => return Some("CN".to_string()),
+        "RUSSIA" => return Some("RU".to_string()),
+        _ => {}
+    }
+
+    // ISO 3166-1 alpha-2 codes (and "EU")
+    if region.len() == 2 && region.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Some(region);
+    }
+
+    // Cloud regions missing from the region catalog, by prefix
+    if region.starts_with("EU-") || region.starts_with("EUROPE-") || region.ends_with("EUROPE") {
+        return Some("EU".to_string());
+    }
+    if region.starts_with("US-") {
+        return Some("US".to_string());
+    }
+    if region.starts_with("CN-") {
+        return Some("CN".to_string());
     }
+
+    None
+}
+
+/// Non-EU AI providers, as (domain, country), classified without a DNS lookup
+const PROVIDER_DOMAINS: &[(&str, &str)] = &[
+    ("openai.com", "US"),
+    ("anthropic.com", "US"),
+    ("cohere.com", "US"),
+    ("together.ai", "US"),
+    ("replicate.com", "US"),
+    ("google.com", "US"),
+];
+
+/// Country of a host under a known provider domain
+///
+/// Only whole domains count (`api.openai.com`, not `api.openai.com.example`).
+/// Nothing is allowed by name, so there are no EU entries.
+fn country_for_hostname(hostname: &str) -> Option<String> {
+    let hostname = hostname.to_lowercase();
+    let hostname = hostname.trim_end_matches('.');
+    PROVIDER_DOMAINS
+        .iter()
+        .find(|(domain, _)| {
+            hostname == *domain
+                || hostname.strip_suffix(domain).is_some_and(|rest| rest.ends_with('.'))
+        })
+        .map(|(_, country)| country.to_string())
 }
 
 #[cfg(test)]
@@ -88,18 +400,83 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_allowed_eu_ip() {
-        // "5.1.2.3" should return "DE" which is in the whitelist
-        let result = check_sovereignty("5.1.2.3");
-        assert!(result.is_ok());
+    fn test_allowed_eu_region() {
+        let resolver = SovereigntyResolver::new();
+        let verdict = resolver.resolve_region("DE");
+        assert!(verdict.allowed);
+        assert_eq!(verdict.country, "DE");
+        assert!(resolver.resolve_region("eu-central-1").allowed);
+        assert!(resolver.resolve_region("westeurope").allowed);
     }
 
     #[test]
-    #[should_panic(expected = "DATA_SOVEREIGNTY_VIOLATION: Attempted connection to non-sovereign jurisdiction [Country: US]")]
-    fn test_blocked_us_ip() {
-        // "8.8.8.8" should return "US" which is NOT in the whitelist
-        // This should panic with the specific message
-        let _ = check_sovereignty("8.8.8.8");
+    fn test_blocked_us_region() {
+        // A non-EU destination is a verdict, not a panic
+        let resolver = SovereigntyResolver::new();
+        let verdict = resolver.resolve_region("us-east-1");
+        assert!(!verdict.allowed);
+        assert_eq!(verdict.country, "US");
+        assert!(!resolver.resolve_region("United States").allowed);
+        assert_eq!(resolver.resolve_region("somewhere").source, VerdictSource::Unresolved);
+
+        // Catalogued regions resolve to their real country, not their prefix
+        let verdict = resolver.resolve_region("eu-west-2");
+        assert!(!verdict.allowed);
+        assert_eq!(verdict.country, "GB");
     }
-}
 
+    #[test]
+    fn test_cidr_lists() {
+        let resolver = SovereigntyResolver::new()
+            .with_allow_cidrs(parse_cidrs("5.1.0.0/16, 2001:db8::/32").unwrap())
+            .with_deny_cidrs(parse_cidrs("5.1.2.3").unwrap());
+
+        let verdict = resolver.resolve_ip("5.1.9.9").unwrap();
+        assert!(verdict.allowed);
+        assert_eq!(verdict.source, VerdictSource::AllowList);
+        assert!(resolver.resolve_ip("2001:db8::1").unwrap().allowed);
+
+        // Deny list wins over the allow list
+        let verdict = resolver.resolve_ip("5.1.2.3").unwrap();
+        assert!(!verdict.allowed);
+        assert_eq!(verdict.source, VerdictSource::DenyList);
+
+        // Without GeoIP, public addresses outside the lists stay unresolved
+        assert_eq!(resolver.resolve_ip("8.8.8.8").unwrap(), SovereigntyVerdict::unresolved().at("8.8.8.8".parse().unwrap()));
+        assert!(resolver.resolve_ip("10.0.0.1").unwrap().allowed);
+        assert!(resolver.resolve_ip("not-an-ip").is_err());
+        assert!(parse_cidrs("10.0.0.0/33").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_url() {
+        let resolver = SovereigntyResolver::new();
+        let verdict = resolver.resolve_url("https://api.openai.com/v1/chat/completions").await.unwrap();
+        assert!(!verdict.allowed);
+        assert_eq!(verdict.source, VerdictSource::Hostname);
+        let verdict = resolver.resolve_url("http://192.168.1.10:8000/v1").await.unwrap();
+        assert!(verdict.allowed);
+        assert_eq!(verdict.ip, Some("192.168.1.10".parse().unwrap()));
+
+        // Hostnames are checked and pinned per resolved address
+        let verdict = resolver.resolve_url("http://localhost:8000/v1").await.unwrap();
+        assert!(verdict.allowed);
+        assert!(verdict.ip.unwrap().is_loopback());
+        let verdict = resolver.resolve_url("https://bedrock-runtime.eu-central-2.amazonaws.com/model").await.unwrap();
+        assert_eq!((verdict.allowed, verdict.country.as_str()), (false, "CH"));
+        assert!(resolver.resolve_url("not a url").await.is_err());
+    }
+
+    #[test]
+    fn test_country_for_hostname() {
+        assert_eq!(country_for_hostname("api.openai.com").as_deref(), Some("US"));
+        assert_eq!(country_for_hostname("API.Anthropic.com.").as_deref(), Some("US"));
+        assert_eq!(country_for_hostname("api.openai.com.example.eu"), None);
+        assert_eq!(country_for_hostname("notopenai.com"), None);
+
+        // Names never allow: EU-looking hosts are left to DNS and GeoIP
+        assert_eq!(country_for_hostname("x.de.evil.com"), None);
+        assert_eq!(country_for_hostname("api.mistral.ai"), None);
+        assert_eq!(country_for_hostname("bedrock-runtime.eu-central-1.amazonaws.com"), None);
+    }
+}
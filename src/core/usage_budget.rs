@@ -0,0 +1,599 @@
+// AI Usage Budgets
+// Bounds on AI vendor usage per agent and per business function: tokens,
+// requests and estimated EUR cost per calendar day or month. Usage is parsed
+// from OpenAI-, Anthropic- and Bedrock-style responses passing through the
+// proxy and recorded in ai_usage_records.
+
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::PgPool;
+use utoipa::ToSchema;
+
+pub const SCOPE_AGENT: &str = "AGENT";
+pub const SCOPE_BUSINESS_FUNCTION: &str = "BUSINESS_FUNCTION";
+pub const ENFORCEMENT_HARD: &str = "HARD";
+pub const ENFORCEMENT_SOFT: &str = "SOFT";
+
+/// Estimated list prices in EUR per million tokens (model, input, output).
+/// Matched as a substring of the model id, first match wins, so specific
+/// models come before their families. Bedrock ids such as
+/// "anthropic.claude-3-5-sonnet-20240620-v1:0" match the same entries.
+const MODEL_PRICES_EUR: &[(&str, f64, f64)] = &[
+    // OpenAI
+    ("gpt-4o-mini", 0.14, 0.55),
+    ("gpt-4o", 2.30, 9.20),
+    ("gpt-4.1-nano", 0.09, 0.37),
+    ("gpt-4.1-mini", 0.37, 1.47),
+    ("gpt-4.1", 1.84, 7.36),
+    ("gpt-4-turbo", 9.20, 27.60),
+    ("gpt-4", 27.60, 55.20),
+    ("gpt-3.5-turbo", 0.46, 1.38),
+    ("o1-mini", 1.01, 4.05),
+    ("o3-mini", 1.01, 4.05),
+    ("o1", 13.80, 55.20),
+    ("text-embedding-3-small", 0.02, 0.0),
+    ("text-embedding-3-large", 0.12, 0.0),
+    // Anthropic
+    ("claude-3-5-haiku", 0.74, 3.68),
+    ("claude-3-haiku", 0.23, 1.15),
+    ("claude-3-opus", 13.80, 69.00),
+    ("claude-opus", 13.80, 69.00),
+    ("claude-3-5-sonnet", 2.76, 13.80),
+    ("claude-3-7-sonnet", 2.76, 13.80),
+    ("claude-sonnet", 2.76, 13.80),
+    ("claude-haiku", 0.92, 4.60),
+    // Amazon and Meta on Bedrock
+    ("titan-text-lite", 0.14, 0.18),
+    ("titan-text-express", 0.18, 0.55),
+    ("titan-embed", 0.02, 0.0),
+    ("nova-micro", 0.03, 0.13),
+    ("nova-lite", 0.06, 0.22),
+    ("nova-pro", 0.74, 2.94),
+    ("llama3-70b", 2.43, 3.22),
+    ("llama3-8b", 0.28, 0.55),
+    // Mistral
+    ("mistral-large", 1.84, 5.52),
+    ("mistral-small", 0.18, 0.55),
+];
+
+/// Price of models missing from MODEL_PRICES_EUR, deliberately on the high side
+const DEFAULT_PRICE_EUR: (f64, f64) = (2.76, 13.80);
+
+/// Token usage reported for one proxied response
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct TokenUsage {
+    pub vendor: String,
+    pub model: Option<String>,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+}
+
+impl TokenUsage {
+    pub fn total_tokens(&self) -> i64 {
+        self.input_tokens + self.output_tokens
+    }
+
+    /// Estimated cost in EUR from the model price table
+    pub fn estimated_cost_eur(&self) -> f64 {
+        let model = self.model.as_deref().unwrap_or("").to_lowercase();
+        let (input, output) = MODEL_PRICES_EUR
+            .iter()
+            .find(|(name, _, _)| model.contains(name))
+            .map(|(_, input, output)| (*input, *output))
+            .unwrap_or(DEFAULT_PRICE_EUR);
+        (self.input_tokens as f64 * input + self.output_tokens as f64 * output) / 1_000_000.0
+    }
+
+    /// Parse usage from an upstream response body (JSON or server-sent events) and headers
+    ///
+    /// Understands OpenAI (`usage.prompt_tokens`/`completion_tokens`, also in the
+    /// last chunk of a stream), Anthropic (`usage.input_tokens`/`output_tokens`,
+    /// `message_start`/`message_delta` events) and Bedrock (Converse
+    /// `usage.inputTokens`, `x-amzn-bedrock-*-token-count` headers, stream
+    /// invocation metrics, Titan and Llama body fields). Returns None if the
+    /// response carries no usage.
+    pub fn parse(target_url: &str, headers: &HeaderMap, body: &[u8]) -> Option<Self> {
+        let mut usage = TokenUsage {
+            vendor: vendor_for_url(target_url),
+            model: bedrock_model_from_url(target_url),
+            ..Default::default()
+        };
+        let mut found = false;
+
+        let header_count = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse::<i64>().ok())
+        };
+        if let Some(input) = header_count("x-amzn-bedrock-input-token-count") {
+            usage.input_tokens = input;
+            found = true;
+        }
+        if let Some(output) = header_count("x-amzn-bedrock-output-token-count") {
+            usage.output_tokens = output;
+            found = true;
+        }
+
+        let documents: Vec<Value> = match serde_json::from_slice::<Value>(body) {
+            Ok(document) => vec![document],
+            Err(_) => String::from_utf8_lossy(body)
+                .lines()
+                .filter_map(|line| line.strip_prefix("data:"))
+                .filter_map(|data| serde_json::from_str(data.trim()).ok())
+                .collect(),
+        };
+        for document in &documents {
+            found |= usage.absorb(document);
+        }
+
+        found.then_some(usage)
+    }
+
+    /// Take the counts of one JSON document; streams repeat cumulative counts,
+    /// so the highest value seen wins
+    fn absorb(&mut self, document: &Value) -> bool {
+        if self.model.is_none() {
+            self.model = document["model"]
+                .as_str()
+                .or_else(|| document["message"]["model"].as_str())
+                .or_else(|| document["response"]["model"].as_str())
+                .map(|s| s.to_string());
+        }
+
+        let mut found = false;
+        for usage in [
+            document,
+            &document["usage"],
+            &document["message"]["usage"],
+            &document["response"]["usage"],
+            &document["amazon-bedrock-invocationMetrics"],
+            &document["meta"]["billed_units"],
+        ] {
+            let input = first_count(usage, &[
+                "prompt_tokens",
+                "input_tokens",
+                "inputTokens",
+                "inputTokenCount",
+                "inputTextTokenCount",
+                "prompt_token_count",
+            ]);
+            let output = first_count(usage, &[
+                "completion_tokens",
+                "output_tokens",
+                "outputTokens",
+                "outputTokenCount",
+                "generation_token_count",
+            ]);
+            if let Some(input) = input {
+                self.input_tokens = self.input_tokens.max(input);
+                found = true;
+            }
+            if let Some(output) = output {
+                self.output_tokens = self.output_tokens.max(output);
+                found = true;
+            }
+        }
+
+        // Titan text: {"inputTextTokenCount": .., "results": [{"tokenCount": ..}]}
+        if let Some(results) = document["results"].as_array() {
+            let output: i64 = results.iter().filter_map(|r| r["tokenCount"].as_i64()).sum();
+            if output > 0 {
+                self.output_tokens = self.output_tokens.max(output);
+                found = true;
+            }
+        }
+
+        found
+    }
+}
+
+fn first_count(value: &Value, keys: &[&str]) -> Option<i64> {
+    keys.iter().find_map(|key| value.get(key).and_then(|v| v.as_i64()))
+}
+
+/// Vendor label of a target URL, e.g. "openai", "anthropic", "bedrock"
+pub fn vendor_for_url(target_url: &str) -> String {
+    let host = reqwest::Url::parse(target_url)
+        .ok()
+        .and_then(|url| url.host_str().map(|h| h.to_lowercase()))
+        .unwrap_or_default();
+
+    if host.ends_with(".openai.azure.com") {
+        "azure-openai".to_string()
+    } else if host.ends_with("openai.com") {
+        "openai".to_string()
+    } else if host.ends_with("anthropic.com") {
+        "anthropic".to_string()
+    } else if host.starts_with("bedrock") && host.ends_with(".amazonaws.com") {
+        "bedrock".to_string()
+    } else if host.ends_with("mistral.ai") {
+        "mistral".to_string()
+    } else if host.contains("cohere") {
+        "cohere".to_string()
+    } else if host.ends_with("googleapis.com") {
+        "google".to_string()
+    } else if host.is_empty() {
+        "unknown".to_string()
+    } else {
+        host
+    }
+}
+
+/// Model id from a Bedrock runtime path such as `/model/{modelId}/invoke`
+fn bedrock_model_from_url(target_url: &str) -> Option<String> {
+    let url = reqwest::Url::parse(target_url).ok()?;
+    let mut segments = url.path_segments()?;
+    segments.find(|segment| *segment == "model")?;
+    segments
+        .next()
+        .filter(|id| !id.is_empty())
+        .map(|id| id.replace("%3A", ":").replace("%3a", ":"))
+}
+
+/// A usage budget for an agent or a business function
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, sqlx::FromRow)]
+pub struct UsageBudget {
+    pub id: uuid::Uuid,
+    /// AGENT or BUSINESS_FUNCTION
+    #[schema(example = "BUSINESS_FUNCTION")]
+    pub scope_type: String,
+    /// agent_id or business function
+    #[schema(example = "CUSTOMER_SERVICE")]
+    pub scope_value: String,
+    /// DAILY or MONTHLY (calendar periods, UTC)
+    #[schema(example = "MONTHLY")]
+    pub period: String,
+    pub max_tokens: Option<i64>,
+    pub max_requests: Option<i64>,
+    #[schema(example = 500.0)]
+    pub max_cost_eur: Option<f64>,
+    /// Utilization (percent) at which a soft alert is sent
+    pub soft_limit_percent: i32,
+    /// HARD blocks requests once a limit is reached, SOFT only alerts
+    pub enforcement: String,
+    pub is_active: bool,
+    pub created_by: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+const BUDGET_COLUMNS: &str = "id, scope_type, scope_value, period, max_tokens, max_requests,
+    max_cost_eur::float8 AS max_cost_eur, soft_limit_percent, enforcement, is_active,
+    created_by, updated_at";
+
+/// Consumption of a budget in its current period
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct BudgetStatus {
+    pub budget: UsageBudget,
+    pub period_start: DateTime<Utc>,
+    pub used_tokens: i64,
+    pub used_requests: i64,
+    pub used_cost_eur: f64,
+    /// Highest share of any configured limit, in percent
+    pub utilization_percent: f64,
+    /// A limit has been reached; HARD budgets block further requests
+    pub exceeded: bool,
+    pub soft_limit_reached: bool,
+}
+
+impl BudgetStatus {
+    pub fn new(budget: UsageBudget, period_start: DateTime<Utc>, used_tokens: i64, used_requests: i64, used_cost_eur: f64) -> Self {
+        let shares = [
+            budget.max_tokens.map(|max| (used_tokens as f64, max as f64)),
+            budget.max_requests.map(|max| (used_requests as f64, max as f64)),
+            budget.max_cost_eur.map(|max| (used_cost_eur, max)),
+        ];
+        let mut exceeded = false;
+        let mut utilization_percent: f64 = 0.0;
+        for (used, max) in shares.into_iter().flatten() {
+            exceeded |= used >= max;
+            let share = if max > 0.0 { used / max * 100.0 } else { 100.0 };
+            utilization_percent = utilization_percent.max(share);
+        }
+        let soft_limit_reached = utilization_percent >= budget.soft_limit_percent as f64;
+
+        Self {
+            budget,
+            period_start,
+            used_tokens,
+            used_requests,
+            used_cost_eur,
+            utilization_percent,
+            exceeded,
+            soft_limit_reached,
+        }
+    }
+
+    /// HARD budget that has no room left
+    pub fn blocks(&self) -> bool {
+        self.exceeded && self.budget.enforcement == ENFORCEMENT_HARD
+    }
+}
+
+/// Start of the calendar period (UTC) containing `now`
+pub fn period_start(period: &str, now: DateTime<Utc>) -> DateTime<Utc> {
+    let day = if period == "MONTHLY" { 1 } else { now.day() };
+    Utc.with_ymd_and_hms(now.year(), now.month(), day, 0, 0, 0)
+        .single()
+        .unwrap_or(now)
+}
+
+/// Usage of one model within a usage summary
+#[derive(Debug, Clone, Serialize, ToSchema, sqlx::FromRow)]
+pub struct ModelUsage {
+    pub vendor: String,
+    pub model: Option<String>,
+    pub requests: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cost_eur: f64,
+}
+
+/// Budget store and usage accounting
+pub struct UsageBudgets;
+
+impl UsageBudgets {
+    /// All budgets, active or not
+    pub async fn list(db_pool: &PgPool) -> Result<Vec<UsageBudget>, sqlx::Error> {
+        sqlx::query_as(&format!(
+            "SELECT {} FROM usage_budgets ORDER BY scope_type, scope_value, period",
+            BUDGET_COLUMNS
+        ))
+        .fetch_all(db_pool)
+        .await
+    }
+
+    /// Create or replace the budget for a scope and period
+    #[allow(clippy::too_many_arguments)]
+    pub async fn upsert(
+        db_pool: &PgPool,
+        scope_type: &str,
+        scope_value: &str,
+        period: &str,
+        max_tokens: Option<i64>,
+        max_requests: Option<i64>,
+        max_cost_eur: Option<f64>,
+        soft_limit_percent: i32,
+        enforcement: &str,
+        is_active: bool,
+        created_by: Option<&str>,
+    ) -> Result<UsageBudget, sqlx::Error> {
+        sqlx::query_as(&format!(
+            "INSERT INTO usage_budgets (
+                scope_type, scope_value, period, max_tokens, max_requests, max_cost_eur,
+                soft_limit_percent, enforcement, is_active, created_by
+            ) VALUES ($1, $2, $3, $4, $5, $6::numeric, $7, $8, $9, $10)
+            ON CONFLICT (scope_type, scope_value, period) DO UPDATE SET
+                max_tokens = EXCLUDED.max_tokens,
+                max_requests = EXCLUDED.max_requests,
+                max_cost_eur = EXCLUDED.max_cost_eur,
+                soft_limit_percent = EXCLUDED.soft_limit_percent,
+                enforcement = EXCLUDED.enforcement,
+                is_active = EXCLUDED.is_active,
+                updated_at = CURRENT_TIMESTAMP
+            RETURNING {}",
+            BUDGET_COLUMNS
+        ))
+        .bind(scope_type)
+        .bind(scope_value)
+        .bind(period)
+        .bind(max_tokens)
+        .bind(max_requests)
+        .bind(max_cost_eur)
+        .bind(soft_limit_percent)
+        .bind(enforcement)
+        .bind(is_active)
+        .bind(created_by)
+        .fetch_one(db_pool)
+        .await
+    }
+
+    /// Current status of every active budget covering an agent or its business function
+    pub async fn check(
+        db_pool: &PgPool,
+        agent_id: &str,
+        business_function: Option<&str>,
+    ) -> Result<Vec<BudgetStatus>, sqlx::Error> {
+        let budgets: Vec<UsageBudget> = sqlx::query_as(&format!(
+            "SELECT {} FROM usage_budgets
+             WHERE is_active = true
+               AND ((scope_type = 'AGENT' AND scope_value = $1)
+                 OR (scope_type = 'BUSINESS_FUNCTION' AND scope_value = $2))",
+            BUDGET_COLUMNS
+        ))
+        .bind(agent_id)
+        .bind(business_function)
+        .fetch_all(db_pool)
+        .await?;
+
+        let mut statuses = Vec::with_capacity(budgets.len());
+        for budget in budgets {
+            statuses.push(Self::status(db_pool, budget, Utc::now()).await?);
+        }
+        Ok(statuses)
+    }
+
+    /// Usage of a budget's scope in the period containing `now`
+    pub async fn status(db_pool: &PgPool, budget: UsageBudget, now: DateTime<Utc>) -> Result<BudgetStatus, sqlx::Error> {
+        let start = period_start(&budget.period, now);
+        let scope_column = if budget.scope_type == SCOPE_BUSINESS_FUNCTION {
+            "business_function"
+        } else {
+            "agent_id"
+        };
+        let (used_requests, used_tokens, used_cost_eur): (i64, i64, f64) = sqlx::query_as(&format!(
+            "SELECT COUNT(*), COALESCE(SUM(total_tokens), 0)::bigint, COALESCE(SUM(cost_eur), 0)::float8
+             FROM ai_usage_records
+             WHERE {} = $1 AND created_at >= $2",
+            scope_column
+        ))
+        .bind(&budget.scope_value)
+        .bind(start)
+        .fetch_one(db_pool)
+        .await?;
+
+        Ok(BudgetStatus::new(budget, start, used_tokens, used_requests, used_cost_eur))
+    }
+
+    /// Record one proxied request and the usage its response reported
+    pub async fn record(
+        db_pool: &PgPool,
+        agent_id: &str,
+        business_function: Option<&str>,
+        target_url: &str,
+        upstream_status: u16,
+        usage: Option<&TokenUsage>,
+    ) -> Result<(), sqlx::Error> {
+        let vendor = usage
+            .map(|u| u.vendor.clone())
+            .unwrap_or_else(|| vendor_for_url(target_url));
+        sqlx::query(
+            "INSERT INTO ai_usage_records (
+                agent_id, business_function, vendor, model, target_url, upstream_status,
+                input_tokens, output_tokens, total_tokens, cost_eur, usage_reported
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10::numeric, $11)"
+        )
+        .bind(agent_id)
+        .bind(business_function)
+        .bind(vendor)
+        .bind(usage.and_then(|u| u.model.as_deref()))
+        .bind(target_url)
+        .bind(upstream_status as i32)
+        .bind(usage.map(|u| u.input_tokens).unwrap_or(0))
+        .bind(usage.map(|u| u.output_tokens).unwrap_or(0))
+        .bind(usage.map(|u| u.total_tokens()).unwrap_or(0))
+        .bind(usage.map(|u| u.estimated_cost_eur()).unwrap_or(0.0))
+        .bind(usage.is_some())
+        .execute(db_pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Claim the alert of a level (SOFT, EXCEEDED) for a budget period;
+    /// true only for the first caller, so each alert goes out once per period
+    pub async fn claim_alert(db_pool: &PgPool, status: &BudgetStatus, level: &str) -> Result<bool, sqlx::Error> {
+        let claimed = sqlx::query(
+            "INSERT INTO usage_budget_alerts (budget_id, period_start, level)
+             VALUES ($1, $2, $3)
+             ON CONFLICT DO NOTHING"
+        )
+        .bind(status.budget.id)
+        .bind(status.period_start)
+        .bind(level)
+        .execute(db_pool)
+        .await?;
+        Ok(claimed.rows_affected() == 1)
+    }
+
+    /// Usage of a set of agents since a point in time, per model
+    pub async fn usage_by_model(
+        db_pool: &PgPool,
+        agent_ids: &[String],
+        since: DateTime<Utc>,
+    ) -> Result<Vec<ModelUsage>, sqlx::Error> {
+        sqlx::query_as(
+            "SELECT vendor, model, COUNT(*) AS requests,
+                    COALESCE(SUM(input_tokens), 0)::bigint AS input_tokens,
+                    COALESCE(SUM(output_tokens), 0)::bigint AS output_tokens,
+                    COALESCE(SUM(cost_eur), 0)::float8 AS cost_eur
+             FROM ai_usage_records
+             WHERE agent_id = ANY($1) AND created_at >= $2
+             GROUP BY vendor, model
+             ORDER BY cost_eur DESC"
+        )
+        .bind(agent_ids)
+        .bind(since)
+        .fetch_all(db_pool)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn budget(max_tokens: Option<i64>, max_requests: Option<i64>, max_cost_eur: Option<f64>) -> UsageBudget {
+        UsageBudget {
+            id: uuid::Uuid::new_v4(),
+            scope_type: SCOPE_AGENT.to_string(),
+            scope_value: "agent-1".to_string(),
+            period: "DAILY".to_string(),
+            max_tokens,
+            max_requests,
+            max_cost_eur,
+            soft_limit_percent: 80,
+            enforcement: ENFORCEMENT_HARD.to_string(),
+            is_active: true,
+            created_by: None,
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_parse_vendor_usage() {
+        let none = HeaderMap::new();
+
+        let openai = br#"{"id":"chatcmpl-1","model":"gpt-4o-mini-2024-07-18","usage":{"prompt_tokens":12,"completion_tokens":30,"total_tokens":42}}"#;
+        let usage = TokenUsage::parse("https://api.openai.com/v1/chat/completions", &none, openai).unwrap();
+        assert_eq!((usage.vendor.as_str(), usage.model.as_deref()), ("openai", Some("gpt-4o-mini-2024-07-18")));
+        assert_eq!((usage.input_tokens, usage.output_tokens), (12, 30));
+
+        // Anthropic stream: input in message_start, cumulative output in message_delta
+        let anthropic = b"event: message_start\ndata: {\"type\":\"message_start\",\"message\":{\"model\":\"claude-3-5-sonnet-20241022\",\"usage\":{\"input_tokens\":25,\"output_tokens\":1}}}\n\n\
+            event: content_block_delta\ndata: {\"type\":\"content_block_delta\",\"delta\":{\"text\":\"Hi\"}}\n\n\
+            event: message_delta\ndata: {\"type\":\"message_delta\",\"usage\":{\"output_tokens\":15}}\n\n";
+        let usage = TokenUsage::parse("https://api.anthropic.com/v1/messages", &none, anthropic).unwrap();
+        assert_eq!(usage.model.as_deref(), Some("claude-3-5-sonnet-20241022"));
+        assert_eq!((usage.input_tokens, usage.output_tokens), (25, 15));
+
+        // Bedrock InvokeModel: counts in headers, model in the path
+        let mut headers = HeaderMap::new();
+        headers.insert("x-amzn-bedrock-input-token-count", "100".parse().unwrap());
+        headers.insert("x-amzn-bedrock-output-token-count", "7".parse().unwrap());
+        let usage = TokenUsage::parse(
+            "https://bedrock-runtime.eu-central-1.amazonaws.com/model/anthropic.claude-3-haiku-20240307-v1%3A0/invoke",
+            &headers,
+            b"{\"completion\":\"ok\"}",
+        )
+        .unwrap();
+        assert_eq!(usage.vendor, "bedrock");
+        assert_eq!(usage.model.as_deref(), Some("anthropic.claude-3-haiku-20240307-v1:0"));
+        assert_eq!(usage.total_tokens(), 107);
+
+        let converse = br#"{"output":{},"usage":{"inputTokens":9,"outputTokens":3,"totalTokens":12}}"#;
+        let usage = TokenUsage::parse("https://bedrock-runtime.eu-west-1.amazonaws.com/model/amazon.nova-lite-v1:0/converse", &none, converse).unwrap();
+        assert_eq!((usage.input_tokens, usage.output_tokens), (9, 3));
+
+        assert_eq!(TokenUsage::parse("https://api.openai.com/v1/models", &none, br#"{"data":[]}"#), None);
+    }
+
+    #[test]
+    fn test_cost_and_budget_status() {
+        let usage = TokenUsage {
+            vendor: "openai".to_string(),
+            model: Some("gpt-4o-mini".to_string()),
+            input_tokens: 1_000_000,
+            output_tokens: 1_000_000,
+        };
+        assert!((usage.estimated_cost_eur() - 0.69).abs() < 1e-9);
+        let unknown = TokenUsage { model: None, ..usage };
+        assert!((unknown.estimated_cost_eur() - (DEFAULT_PRICE_EUR.0 + DEFAULT_PRICE_EUR.1)).abs() < 1e-9);
+
+        let start = Utc::now();
+        let status = BudgetStatus::new(budget(Some(1000), Some(10), None), start, 850, 2, 0.0);
+        assert!(!status.exceeded && status.soft_limit_reached);
+        assert!((status.utilization_percent - 85.0).abs() < 1e-9);
+
+        let status = BudgetStatus::new(budget(Some(1000), Some(10), Some(5.0)), start, 10, 10, 1.0);
+        assert!(status.exceeded && status.blocks());
+
+        let status = BudgetStatus::new(budget(None, None, None), start, 1_000_000, 1000, 100.0);
+        assert!(!status.exceeded && !status.soft_limit_reached);
+
+        let now = Utc.with_ymd_and_hms(2025, 3, 17, 15, 4, 5).unwrap();
+        assert_eq!(period_start("DAILY", now), Utc.with_ymd_and_hms(2025, 3, 17, 0, 0, 0).unwrap());
+        assert_eq!(period_start("MONTHLY", now), Utc.with_ymd_and_hms(2025, 3, 1, 0, 0, 0).unwrap());
+    }
+}
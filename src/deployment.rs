@@ -89,6 +89,12 @@ impl From<&str> for DeploymentMode {
 pub struct DeploymentConfig {
     pub mode: DeploymentMode,
     pub proxy_target_url: Option<String>,
+    /// `POST /proxy` rejects requests without a valid Veridion API key
+    pub proxy_require_api_key: bool,
+    /// Forward proxy rejects requests without a valid Veridion API key
+    pub forward_proxy_require_api_key: bool,
+    /// Ports the forward proxy opens CONNECT tunnels to
+    pub forward_proxy_connect_ports: Vec<u16>,
     pub enable_dashboard: bool,
     pub enable_all_modules: bool,
 }
@@ -98,6 +104,19 @@ impl Default for DeploymentConfig {
         Self {
             mode: DeploymentMode::from_env(),
             proxy_target_url: std::env::var("PROXY_TARGET_URL").ok(),
+            proxy_require_api_key: std::env::var("PROXY_REQUIRE_API_KEY")
+                .unwrap_or_else(|_| "true".to_string())
+                .parse()
+                .unwrap_or(true),
+            forward_proxy_require_api_key: std::env::var("FORWARD_PROXY_REQUIRE_API_KEY")
+                .unwrap_or_else(|_| "true".to_string())
+                .parse()
+                .unwrap_or(true),
+            forward_proxy_connect_ports: std::env::var("FORWARD_PROXY_CONNECT_PORTS")
+                .unwrap_or_else(|_| "443".to_string())
+                .split(',')
+                .filter_map(|port| port.trim().parse().ok())
+                .collect(),
             enable_dashboard: std::env::var("ENABLE_DASHBOARD")
                 .unwrap_or_else(|_| "true".to_string())
                 .parse()
@@ -0,0 +1,531 @@
+// Forward Proxy Mode - Transparent HTTP(S) proxy for AI vendor traffic
+// Lets unmodified OpenAI, Anthropic and Bedrock clients go through the Sovereign
+// Lock, either via HTTPS_PROXY (CONNECT tunnels) or a base-URL override.
+
+use crate::api_state::AppState;
+use crate::core::pii_redaction::TokenVault;
+use crate::core::sovereign_lock::is_private_ip;
+use crate::integration::proxy::{
+    downstream_headers, is_hop_by_hop_header, ProxyService, RehydratingStream, StreamOutcome, Transcript,
+    TranscriptStream,
+};
+use crate::routes::{admit_proxy_request, check_usage_budgets, proxy_transcript_logger};
+use crate::security::api_keys::{AgentIdentity, ApiKeyService};
+use actix_web::web::{self, Bytes};
+use base64::engine::Engine as _;
+use hyper::header::{self, HeaderMap, HeaderValue};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode, Uri};
+use std::convert::Infallible;
+use std::net::{IpAddr, SocketAddr};
+
+/// Header carrying a Veridion API key when the client cannot send Proxy-Authorization
+pub const API_KEY_HEADER: &str = "x-veridion-api-key";
+/// Header naming the tenant, for rollouts bucketed by tenant
+pub const COMPANY_HEADER: &str = "x-company-id";
+
+/// Largest request body buffered for redaction, the JSON limit of `POST /proxy`
+const MAX_REDACTED_BODY: usize = 10_485_760;
+
+/// Path prefixes for base-URL overrides, e.g. `OPENAI_BASE_URL=http://veridion:8081/openai/v1`
+const VENDOR_ROUTES: &[(&str, &str)] = &[
+    ("openai", "https://api.openai.com"),
+    ("anthropic", "https://api.anthropic.com"),
+    ("mistral", "https://api.mistral.ai"),
+    ("cohere", "https://api.cohere.com"),
+];
+
+/// Run the forward proxy listener until it fails
+pub async fn serve(addr: SocketAddr, data: web::Data<AppState>) -> std::io::Result<()> {
+    let make_service = make_service_fn(move |_conn| {
+        let data = data.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |req| handle(req, data.clone())))
+        }
+    });
+
+    log::info!("Forward proxy listening on {}", addr);
+    Server::try_bind(&addr)
+        .map_err(std::io::Error::other)?
+        .http1_preserve_header_case(true)
+        .serve(make_service)
+        .await
+        .map_err(std::io::Error::other)
+}
+
+async fn handle(req: Request<Body>, data: web::Data<AppState>) -> Result<Response<Body>, Infallible> {
+    let identity = match identify(req.headers(), &data).await {
+        Ok(identity) => identity,
+        Err(response) => return Ok(response),
+    };
+
+    if req.method() == Method::CONNECT {
+        return Ok(tunnel(req, identity, data).await);
+    }
+
+    let target_url = match upstream_url(req.uri(), data.deployment.proxy_target_url.as_deref()) {
+        Some(url) => url,
+        None => {
+            return Ok(json_response(StatusCode::BAD_REQUEST, serde_json::json!({
+                "error": "UNKNOWN_TARGET",
+                "message": "Use an absolute URL (HTTP_PROXY), a vendor prefix such as /openai/v1, or set PROXY_TARGET_URL"
+            })));
+        }
+    };
+
+    let admission = match admit_proxy_request(&data, &target_url, &identity.agent_id, identity.user_id.clone(), identity.company_id.as_deref(), None).await {
+        Ok(admission) => admission,
+        Err(rejection) => return Ok(json_response(StatusCode::FORBIDDEN, rejection.body)),
+    };
+    // Absolute-form targets are chosen by the client; only PROXY_TARGET_URL may be internal
+    if req.uri().scheme().is_some() && admission.pinned_ip.is_none_or(is_internal_ip) {
+        return Ok(internal_target(&target_url));
+    }
+    if let Err(exhausted) = check_usage_budgets(&data, &admission, &identity.agent_id).await {
+        return Ok(json_response(StatusCode::TOO_MANY_REQUESTS, exhausted));
+    }
+
+    let (parts, body) = req.into_parts();
+    let mut headers = upstream_headers(&parts.headers);
+
+    // Replace personal data in the body with tokens, as POST /proxy does; they are
+    // put back in the response (also when a REDACT policy rule matched)
+    let mut pii_vault = TokenVault::new();
+    let body = if data.pii_redaction_enabled || admission.rule_verdict.redact {
+        let buffered = match read_body(body).await {
+            Ok(buffered) => buffered,
+            Err(response) => return Ok(response),
+        };
+        let redacted = match redact_body(&headers, &buffered, &mut pii_vault) {
+            Some(redacted) => redacted,
+            None => return Ok(redaction_required(&target_url)),
+        };
+        if !pii_vault.is_empty() {
+            headers.insert(header::ACCEPT_ENCODING, HeaderValue::from_static("identity"));
+            if let Err(e) = pii_vault.store(&data.key_store, &data.db_pool, "PROXY", &identity.agent_id, identity.user_id.as_deref(), None).await {
+                log::error!("Forward proxy could not store PII token vault: {}", e);
+                return Ok(json_response(StatusCode::INTERNAL_SERVER_ERROR, serde_json::json!({
+                    "error": "INTERNAL_ERROR",
+                    "message": "Could not store redacted personal data"
+                })));
+            }
+        }
+        reqwest::Body::from(redacted)
+    } else {
+        reqwest::Body::wrap_stream(body)
+    };
+
+    let upstream = ProxyService::pinned(&target_url, admission.pinned_ip)
+        .forward(parts.method, &target_url, headers, body)
+        .await;
+
+    match upstream {
+        Ok(response) => {
+            let status = response.status();
+            let log_transcript = proxy_transcript_logger(
+                &data,
+                &admission,
+                target_url,
+                identity.agent_id,
+                status.as_u16(),
+                response.headers().clone(),
+            );
+
+            let mut downstream = Response::new(Body::empty());
+            *downstream.status_mut() = status;
+            *downstream.headers_mut() = downstream_headers(response.headers());
+            let transcript = TranscriptStream::new(response, log_transcript);
+            if pii_vault.is_empty() {
+                *downstream.body_mut() = Body::wrap_stream(transcript);
+            } else {
+                downstream.headers_mut().insert("x-veridion-pii-redacted", HeaderValue::from(pii_vault.len()));
+                *downstream.body_mut() = Body::wrap_stream(RehydratingStream::new(transcript, pii_vault));
+            }
+            Ok(downstream)
+        }
+        Err(e) => {
+            log::error!("Forward proxy could not reach {}: {}", target_url, e);
+            Ok(json_response(StatusCode::BAD_GATEWAY, serde_json::json!({
+                "error": "PROXY_ERROR",
+                "message": "Failed to forward request to target server"
+            })))
+        }
+    }
+}
+
+/// CONNECT tunnel (HTTPS_PROXY): the sovereignty check runs on the target host,
+/// the encrypted traffic itself is relayed untouched
+///
+/// Tunnels are refused while personal data must be redacted (PII_REDACTION_ENABLED
+/// or a REDACT policy rule).
+async fn tunnel(req: Request<Body>, identity: AgentIdentity, data: web::Data<AppState>) -> Response<Body> {
+    let authority = match req.uri().authority() {
+        Some(authority) => authority.clone(),
+        None => {
+            return json_response(StatusCode::BAD_REQUEST, serde_json::json!({
+                "error": "INVALID_CONNECT",
+                "message": "CONNECT requires host:port"
+            }));
+        }
+    };
+    let port = authority.port_u16().unwrap_or(443);
+    if !data.deployment.forward_proxy_connect_ports.contains(&port) {
+        return json_response(StatusCode::FORBIDDEN, serde_json::json!({
+            "error": "PORT_NOT_ALLOWED",
+            "message": format!("CONNECT to port {} is not allowed (FORWARD_PROXY_CONNECT_PORTS)", port),
+            "status": "BLOCKED"
+        }));
+    }
+    let target_url = if port == 443 {
+        format!("https://{}", authority.host())
+    } else {
+        format!("https://{}:{}", authority.host(), port)
+    };
+
+    let admission = match admit_proxy_request(&data, &target_url, &identity.agent_id, identity.user_id.clone(), identity.company_id.as_deref(), None).await {
+        Ok(admission) => admission,
+        Err(rejection) => return json_response(StatusCode::FORBIDDEN, rejection.body),
+    };
+    // Encrypted traffic cannot be redacted
+    if data.pii_redaction_enabled || admission.rule_verdict.redact {
+        return redaction_required(&target_url);
+    }
+    // Relay to the address that was checked, not a fresh lookup of the host
+    let ip = match admission.pinned_ip {
+        Some(ip) if !is_internal_ip(ip) => ip,
+        _ => return internal_target(&target_url),
+    };
+    if let Err(exhausted) = check_usage_budgets(&data, &admission, &identity.agent_id).await {
+        return json_response(StatusCode::TOO_MANY_REQUESTS, exhausted);
+    }
+
+    // Token usage is not visible inside the tunnel; it counts as one request
+    let log_transcript = proxy_transcript_logger(
+        &data,
+        &admission,
+        target_url,
+        identity.agent_id,
+        StatusCode::OK.as_u16(),
+        HeaderMap::new(),
+    );
+    let address = SocketAddr::new(ip, port);
+    tokio::spawn(async move {
+        let relayed = match hyper::upgrade::on(req).await {
+            Ok(mut client) => match tokio::net::TcpStream::connect(&address).await {
+                Ok(mut server) => tokio::io::copy_bidirectional(&mut client, &mut server)
+                    .await
+                    .map(|(_, from_server)| from_server),
+                Err(e) => Err(e),
+            },
+            Err(e) => Err(std::io::Error::other(e)),
+        };
+        if let Err(e) = &relayed {
+            log::warn!("Forward proxy tunnel to {} failed: {}", address, e);
+        }
+        log_transcript(Transcript {
+            body: Bytes::new(),
+            bytes_streamed: *relayed.as_ref().unwrap_or(&0),
+            truncated: false,
+            outcome: if relayed.is_ok() { StreamOutcome::Completed } else { StreamOutcome::UpstreamError },
+        });
+    });
+
+    Response::new(Body::empty())
+}
+
+/// Resolve the agent and tenant of a request
+///
+/// With a Veridion API key the agent is the key's name and the tenant the key's
+/// tenant; X-Agent-ID, a proxy username or X-Company-ID naming anything else is
+/// rejected. Requests without a key are rejected unless
+/// FORWARD_PROXY_REQUIRE_API_KEY=false, in which case the headers are trusted.
+async fn identify(headers: &HeaderMap, data: &AppState) -> Result<AgentIdentity, Response<Body>> {
+    let header_agent = headers
+        .get("x-agent-id")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+    let header_company = headers
+        .get(COMPANY_HEADER)
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.trim().to_string());
+    let (proxy_user, api_key) = proxy_credentials(headers);
+
+    let key_info = match api_key {
+        Some(key) => match ApiKeyService::new(data.db_pool.clone()).validate_api_key(&key).await {
+            Ok(Some(info)) => info,
+            Ok(None) => return Err(proxy_auth_required("Invalid or expired API key")),
+            Err(e) => {
+                log::error!("Forward proxy API key lookup failed: {}", e);
+                return Err(json_response(StatusCode::INTERNAL_SERVER_ERROR, serde_json::json!({
+                    "error": "INTERNAL_ERROR",
+                    "message": "Could not verify API key"
+                })));
+            }
+        },
+        None if data.deployment.forward_proxy_require_api_key => {
+            return Err(proxy_auth_required("A Veridion API key is required"));
+        }
+        None => {
+            return Ok(AgentIdentity {
+                agent_id: header_agent.or(proxy_user).unwrap_or_else(|| "unknown".to_string()),
+                user_id: None,
+                company_id: header_company,
+            });
+        }
+    };
+
+    let identity = AgentIdentity::from_key(key_info);
+    match identity.conflict(&[header_agent, proxy_user], header_company.as_deref()) {
+        Some(message) => Err(json_response(StatusCode::FORBIDDEN, serde_json::json!({
+            "error": "IDENTITY_MISMATCH",
+            "message": message
+        }))),
+        None => Ok(identity),
+    }
+}
+
+/// (username, API key) from `Proxy-Authorization` (Basic `agent:key` or Bearer) or the API key header
+fn proxy_credentials(headers: &HeaderMap) -> (Option<String>, Option<String>) {
+    let header_key = headers
+        .get(API_KEY_HEADER)
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.trim().to_string());
+
+    let authorization = headers
+        .get(header::PROXY_AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())
+        .unwrap_or("");
+    let (user, key) = if let Some(token) = authorization.strip_prefix("Bearer ") {
+        (None, Some(token.trim().to_string()))
+    } else if let Some(encoded) = authorization.strip_prefix("Basic ") {
+        base64::engine::general_purpose::STANDARD
+            .decode(encoded.trim())
+            .ok()
+            .and_then(|decoded| String::from_utf8(decoded).ok())
+            .map(|credentials| match credentials.split_once(':') {
+                Some((user, key)) => (Some(user.to_string()), Some(key.to_string())),
+                None => (Some(credentials), None),
+            })
+            .unwrap_or((None, None))
+    } else {
+        (None, None)
+    };
+
+    let non_empty = |s: Option<String>| s.filter(|s| !s.is_empty());
+    (non_empty(user), non_empty(header_key.or(key)))
+}
+
+/// Upstream URL for a proxied request
+///
+/// Absolute-form requests (`HTTP_PROXY`) go to their own URL. Origin-form
+/// requests (base-URL override) are routed by vendor prefix, then to
+/// PROXY_TARGET_URL.
+fn upstream_url(uri: &Uri, default_target: Option<&str>) -> Option<String> {
+    if uri.scheme().is_some() && uri.authority().is_some() {
+        return Some(uri.to_string());
+    }
+
+    let path_and_query = uri.path_and_query().map(|pq| pq.as_str()).unwrap_or("/");
+    let trimmed = path_and_query.trim_start_matches('/');
+    let (prefix, rest) = trimmed.split_once('/').unwrap_or((trimmed, ""));
+    let is_label = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+
+    if let Some((_, base)) = VENDOR_ROUTES.iter().find(|(name, _)| *name == prefix) {
+        return Some(format!("{}/{}", base, rest));
+    }
+    match prefix {
+        // /bedrock/{region}/model/... -> bedrock-runtime.{region}.amazonaws.com
+        "bedrock" => {
+            let (region, rest) = rest.split_once('/').unwrap_or((rest, ""));
+            if is_label(region) {
+                return Some(format!("https://bedrock-runtime.{}.amazonaws.com/{}", region, rest));
+            }
+            return None;
+        }
+        // /azure/{resource}/openai/... -> {resource}.openai.azure.com
+        "azure" => {
+            let (resource, rest) = rest.split_once('/').unwrap_or((rest, ""));
+            if is_label(resource) {
+                return Some(format!("https://{}.openai.azure.com/{}", resource, rest));
+            }
+            return None;
+        }
+        _ => {}
+    }
+
+    default_target.map(|base| format!("{}{}", base.trim_end_matches('/'), path_and_query))
+}
+
+/// Client headers to send upstream, without hop-by-hop and Veridion headers
+fn upstream_headers(client: &HeaderMap) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    for (key, value) in client.iter() {
+        let name = key.as_str();
+        if !is_hop_by_hop_header(name) && !matches!(name, "host" | "x-agent-id" | API_KEY_HEADER | COMPANY_HEADER) {
+            headers.append(key.clone(), value.clone());
+        }
+    }
+    headers
+}
+
+fn proxy_auth_required(message: &str) -> Response<Body> {
+    let mut response = json_response(StatusCode::PROXY_AUTHENTICATION_REQUIRED, serde_json::json!({
+        "error": "PROXY_AUTHENTICATION_REQUIRED",
+        "message": message
+    }));
+    response.headers_mut().insert(
+        header::PROXY_AUTHENTICATE,
+        HeaderValue::from_static("Basic realm=\"Veridion Nexus\""),
+    );
+    response
+}
+
+/// Body with personal data replaced by vault tokens; None if it cannot be inspected
+///
+/// JSON bodies are redacted value by value, other text as a whole. Compressed
+/// and binary bodies cannot be checked for personal data.
+fn redact_body(headers: &HeaderMap, body: &[u8], vault: &mut TokenVault) -> Option<Vec<u8>> {
+    let encoded = headers
+        .get(header::CONTENT_ENCODING)
+        .and_then(|h| h.to_str().ok())
+        .is_some_and(|encoding| !encoding.trim().eq_ignore_ascii_case("identity"));
+    if encoded {
+        return None;
+    }
+    if let Ok(mut json) = serde_json::from_slice::<serde_json::Value>(body) {
+        vault.redact_json(&mut json);
+        return serde_json::to_vec(&json).ok();
+    }
+    std::str::from_utf8(body).ok().map(|text| vault.redact(text).into_bytes())
+}
+
+/// Buffer a request body for redaction, up to MAX_REDACTED_BODY
+async fn read_body(mut body: Body) -> Result<Bytes, Response<Body>> {
+    use hyper::body::HttpBody;
+
+    let mut buffered = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk.map_err(|e| {
+            json_response(StatusCode::BAD_REQUEST, serde_json::json!({
+                "error": "INVALID_BODY",
+                "message": format!("Could not read request body: {}", e)
+            }))
+        })?;
+        if buffered.len() + chunk.len() > MAX_REDACTED_BODY {
+            return Err(json_response(StatusCode::PAYLOAD_TOO_LARGE, serde_json::json!({
+                "error": "BODY_TOO_LARGE",
+                "message": format!("Request bodies are limited to {} bytes while personal data is redacted", MAX_REDACTED_BODY)
+            })));
+        }
+        buffered.extend_from_slice(&chunk);
+    }
+    Ok(Bytes::from(buffered))
+}
+
+/// Personal data must be redacted, but the body is tunnelled or cannot be inspected
+fn redaction_required(target_url: &str) -> Response<Body> {
+    json_response(StatusCode::FORBIDDEN, serde_json::json!({
+        "error": "REDACTION_REQUIRED",
+        "message": "Personal data must be redacted and this request cannot be inspected; send it through POST /api/v1/proxy",
+        "target_url": target_url,
+        "status": "BLOCKED"
+    }))
+}
+
+/// Private, loopback, link-local or unspecified address, also when IPv4-mapped
+fn is_internal_ip(ip: IpAddr) -> bool {
+    let ip = ip.to_canonical();
+    ip.is_unspecified() || is_private_ip(ip)
+}
+
+/// The target is on an internal network, or was not resolved to a checked address
+fn internal_target(target_url: &str) -> Response<Body> {
+    json_response(StatusCode::FORBIDDEN, serde_json::json!({
+        "error": "INTERNAL_TARGET",
+        "message": "The forward proxy does not connect to private, loopback or link-local addresses",
+        "target_url": target_url,
+        "status": "BLOCKED"
+    }))
+}
+
+fn json_response(status: StatusCode, body: serde_json::Value) -> Response<Body> {
+    let mut response = Response::new(Body::from(body.to_string()));
+    *response.status_mut() = status;
+    response.headers_mut().insert(header::CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upstream_url() {
+        let url = |s: &str, default: Option<&str>| upstream_url(&s.parse::<Uri>().unwrap(), default);
+
+        assert_eq!(url("http://api.example.eu/v1/chat?x=1", None).unwrap(), "http://api.example.eu/v1/chat?x=1");
+        assert_eq!(url("/openai/v1/chat/completions", None).unwrap(), "https://api.openai.com/v1/chat/completions");
+        assert_eq!(
+            url("/bedrock/eu-central-1/model/m/invoke", None).unwrap(),
+            "https://bedrock-runtime.eu-central-1.amazonaws.com/model/m/invoke"
+        );
+        assert!(url("/bedrock/evil.com%2F/x", None).is_none());
+        assert_eq!(url("/v1/messages", Some("https://llm.internal.eu/")).unwrap(), "https://llm.internal.eu/v1/messages");
+        assert!(url("/v1/messages", None).is_none());
+    }
+
+    #[test]
+    fn test_proxy_credentials() {
+        let mut headers = HeaderMap::new();
+        let basic = base64::engine::general_purpose::STANDARD.encode("credit-agent:vn_secret");
+        headers.insert(header::PROXY_AUTHORIZATION, HeaderValue::from_str(&format!("Basic {}", basic)).unwrap());
+        assert_eq!(
+            proxy_credentials(&headers),
+            (Some("credit-agent".to_string()), Some("vn_secret".to_string()))
+        );
+
+        headers.insert(API_KEY_HEADER, HeaderValue::from_static("vn_other"));
+        assert_eq!(proxy_credentials(&headers).1.as_deref(), Some("vn_other"));
+
+        // Veridion credentials never reach the vendor
+        let forwarded = upstream_headers(&headers);
+        assert!(forwarded.get(header::PROXY_AUTHORIZATION).is_none());
+        assert!(forwarded.get(API_KEY_HEADER).is_none());
+    }
+
+    #[test]
+    fn test_is_internal_ip() {
+        let internal = |s: &str| is_internal_ip(s.parse().unwrap());
+
+        assert!(internal("127.0.0.1"));
+        assert!(internal("10.0.0.5"));
+        assert!(internal("192.168.1.1"));
+        assert!(internal("169.254.169.254"));
+        assert!(internal("0.0.0.0"));
+        assert!(internal("::1"));
+        assert!(internal("::ffff:127.0.0.1"));
+        assert!(internal("fe80::1"));
+        assert!(!internal("104.18.6.192"));
+        assert!(!internal("2a00:1450:4001::1"));
+    }
+
+    #[test]
+    fn test_redact_body() {
+        let mut vault = TokenVault::new();
+        let headers = HeaderMap::new();
+
+        let json = br#"{"messages":[{"role":"user","content":"Mail jan.novak@example.cz"}]}"#;
+        let redacted = redact_body(&headers, json, &mut vault).unwrap();
+        assert!(!String::from_utf8_lossy(&redacted).contains("jan.novak@example.cz"));
+        assert_eq!(vault.len(), 1);
+
+        let text = redact_body(&headers, b"Call jan.novak@example.cz", &mut vault).unwrap();
+        assert!(!String::from_utf8_lossy(&text).contains("jan.novak@example.cz"));
+
+        // Binary and compressed bodies cannot be inspected
+        assert!(redact_body(&headers, &[0xff, 0xfe, 0x00], &mut vault).is_none());
+        let mut gzip = HeaderMap::new();
+        gzip.insert(header::CONTENT_ENCODING, HeaderValue::from_static("gzip"));
+        assert!(redact_body(&gzip, b"{}", &mut vault).is_none());
+    }
+}
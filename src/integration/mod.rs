@@ -3,6 +3,7 @@
 
 pub mod webhooks;
 pub mod proxy;
+pub mod forward_proxy;
 pub mod notifications;
 pub mod veridion_tprm;
 pub mod multi_cloud;
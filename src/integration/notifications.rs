@@ -53,6 +53,9 @@ pub enum NotificationType {
     CanaryPromotion, // Canary deployment auto-promoted
     CanaryRollback, // Canary deployment auto-rolled back
     ComplianceViolation, // Compliance violation detected
+    UsageBudgetAlert, // AI usage budget reached its soft limit
+    UsageBudgetExceeded, // AI usage budget exhausted
+    SealSlaBreached, // Records still unsealed past the seal outbox SLA
 }
 
 impl ToString for NotificationType {
@@ -75,6 +78,9 @@ impl ToString for NotificationType {
             NotificationType::CanaryPromotion => "CANARY_PROMOTION".to_string(),
             NotificationType::CanaryRollback => "CANARY_ROLLBACK".to_string(),
             NotificationType::ComplianceViolation => "COMPLIANCE_VIOLATION".to_string(),
+            NotificationType::UsageBudgetAlert => "USAGE_BUDGET_ALERT".to_string(),
+            NotificationType::UsageBudgetExceeded => "USAGE_BUDGET_EXCEEDED".to_string(),
+            NotificationType::SealSlaBreached => "SEAL_SLA_BREACHED".to_string(),
         }
     }
 }
@@ -1350,6 +1356,196 @@ impl NotificationService {
 
         last_result
     }
+
+    /// Send AI usage budget alert
+    /// Alerts when a usage budget reaches its soft limit or is exhausted (`blocked` once a HARD budget stops requests)
+    pub async fn send_usage_budget_alert(
+        &self,
+        db_pool: &PgPool,
+        status: &crate::core::usage_budget::BudgetStatus,
+        agent_id: &str,
+        blocked: bool,
+        user_id: Option<&str>,
+    ) -> Result<String, String> {
+        let user_id_str = user_id.unwrap_or("system");
+        let budget = &status.budget;
+        let preferred_channels: Vec<String> = sqlx::query_scalar(
+            "SELECT channel FROM user_notification_preferences
+             WHERE user_id::text = $1 AND enabled = true
+             UNION SELECT 'EMAIL' WHERE NOT EXISTS (
+                 SELECT 1 FROM user_notification_preferences WHERE user_id::text = $1
+             )"
+        )
+        .bind(user_id_str)
+        .fetch_all(db_pool)
+        .await
+        .unwrap_or_else(|_| vec!["EMAIL".to_string()]);
+
+        let channel_to_use = if preferred_channels.contains(&"EMAIL".to_string()) {
+            NotificationChannel::Email
+        } else if preferred_channels.contains(&"SMS".to_string()) {
+            NotificationChannel::Sms
+        } else {
+            NotificationChannel::InApp
+        };
+
+        let (notification_type, subject) = if status.exceeded {
+            (
+                NotificationType::UsageBudgetExceeded,
+                format!("AI Usage Budget Exhausted: {} {}", budget.scope_type, budget.scope_value),
+            )
+        } else {
+            (
+                NotificationType::UsageBudgetAlert,
+                format!("AI Usage Budget at {:.0}%: {} {}", status.utilization_percent, budget.scope_type, budget.scope_value),
+            )
+        };
+        let limit = |max: Option<String>| max.unwrap_or_else(|| "none".to_string());
+        let mut body = format!(
+            "AI vendor usage has reached {:.1}% of a {} budget:\n\n\
+            Scope: {} {}\n\
+            Period: {} (since {})\n\
+            Tokens: {} of {}\n\
+            Requests: {} of {}\n\
+            Estimated Cost: EUR {:.2} of {}\n\
+            Enforcement: {}\n\
+            Last Agent: {}\n\
+            Timestamp: {}\n\n",
+            status.utilization_percent,
+            budget.period.to_lowercase(),
+            budget.scope_type,
+            budget.scope_value,
+            budget.period,
+            status.period_start.format("%Y-%m-%d"),
+            status.used_tokens,
+            limit(budget.max_tokens.map(|v| v.to_string())),
+            status.used_requests,
+            limit(budget.max_requests.map(|v| v.to_string())),
+            status.used_cost_eur,
+            limit(budget.max_cost_eur.map(|v| format!("EUR {:.2}", v))),
+            budget.enforcement,
+            agent_id,
+            Utc::now().format("%Y-%m-%d %H:%M:%S")
+        );
+        if blocked {
+            body.push_str("Proxy requests in this scope are blocked (HTTP 429) until the period ends or the budget is raised.");
+        } else if status.exceeded {
+            body.push_str("The budget is SOFT: requests continue. Review usage or raise the budget.");
+        } else {
+            body.push_str("Requests continue. Review usage before the budget is exhausted.");
+        }
+
+        let request = NotificationRequest {
+            user_id: user_id_str.to_string(),
+            notification_type,
+            channel: channel_to_use,
+            subject: Some(subject),
+            body,
+            language: Some("en".to_string()),
+            related_entity_type: Some("USAGE_BUDGET".to_string()),
+            related_entity_id: Some(budget.id.to_string()),
+        };
+
+        let mut last_result = Err("No channels available".to_string());
+        for ch_str in &preferred_channels {
+            let ch = match ch_str.as_str() {
+                "EMAIL" => NotificationChannel::Email,
+                "SMS" => NotificationChannel::Sms,
+                "IN_APP" => NotificationChannel::InApp,
+                _ => continue,
+            };
+
+            let mut req = request.clone();
+            req.channel = ch;
+            last_result = self.send_notification(db_pool, req).await;
+            if last_result.is_ok() {
+                break;
+            }
+        }
+
+        last_result
+    }
+
+    /// Send alert for compliance records left unsealed past the seal outbox SLA
+    pub async fn send_seal_sla_alert(
+        &self,
+        db_pool: &PgPool,
+        overdue: &[crate::core::seal_outbox::SealOutboxEntry],
+        sla_minutes: i64,
+        user_id: Option<&str>,
+    ) -> Result<String, String> {
+        let user_id_str = user_id.unwrap_or("system");
+        let preferred_channels: Vec<String> = sqlx::query_scalar(
+            "SELECT channel FROM user_notification_preferences
+             WHERE user_id::text = $1 AND enabled = true
+             UNION SELECT 'EMAIL' WHERE NOT EXISTS (
+                 SELECT 1 FROM user_notification_preferences WHERE user_id::text = $1
+             )"
+        )
+        .bind(user_id_str)
+        .fetch_all(db_pool)
+        .await
+        .unwrap_or_else(|_| vec!["EMAIL".to_string()]);
+
+        let channel_to_use = if preferred_channels.contains(&"EMAIL".to_string()) {
+            NotificationChannel::Email
+        } else if preferred_channels.contains(&"SMS".to_string()) {
+            NotificationChannel::Sms
+        } else {
+            NotificationChannel::InApp
+        };
+
+        let oldest = overdue.iter().map(|e| e.created_at).min();
+        let last_error = overdue
+            .iter()
+            .filter_map(|e| e.last_error.as_deref())
+            .next()
+            .unwrap_or("unknown");
+        let subject = format!("Seal SLA Breached: {} record(s) unsealed", overdue.len());
+        let body = format!(
+            "{} compliance record(s) have waited more than {} minutes for their seal:\n\n\
+            Oldest Queued: {}\n\
+            Last Error: {}\n\
+            Timestamp: {}\n\n\
+            The records are stored with a PENDING_SYNC_LOCAL seal ID and are retried automatically. \
+            Check the seal provider configuration and availability; GET /api/v1/seal_outbox lists the backlog.",
+            overdue.len(),
+            sla_minutes,
+            oldest.map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string()).unwrap_or_default(),
+            last_error,
+            Utc::now().format("%Y-%m-%d %H:%M:%S")
+        );
+
+        let request = NotificationRequest {
+            user_id: user_id_str.to_string(),
+            notification_type: NotificationType::SealSlaBreached,
+            channel: channel_to_use,
+            subject: Some(subject),
+            body,
+            language: Some("en".to_string()),
+            related_entity_type: Some("SEAL_OUTBOX".to_string()),
+            related_entity_id: overdue.first().map(|e| e.id.to_string()),
+        };
+
+        let mut last_result = Err("No channels available".to_string());
+        for ch_str in &preferred_channels {
+            let ch = match ch_str.as_str() {
+                "EMAIL" => NotificationChannel::Email,
+                "SMS" => NotificationChannel::Sms,
+                "IN_APP" => NotificationChannel::InApp,
+                _ => continue,
+            };
+
+            let mut req = request.clone();
+            req.channel = ch;
+            last_result = self.send_notification(db_pool, req).await;
+            if last_result.is_ok() {
+                break;
+            }
+        }
+
+        last_result
+    }
 }
 
 impl Default for NotificationService {
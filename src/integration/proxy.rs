@@ -2,15 +2,27 @@
 // Intercepts AI API calls and enforces compliance at network level
 
 use actix_web::{web, HttpRequest, HttpResponse};
+use actix_web::web::Bytes;
+use futures::Stream;
+use reqwest::header::{self, HeaderMap, HeaderValue};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use utoipa::ToSchema;
-use std::sync::Arc;
-use std::net::ToSocketAddrs;
+use std::fmt;
+use std::future::Future;
+use std::net::{IpAddr, SocketAddr};
+use std::pin::Pin;
+use std::task::{Context, Poll};
 use std::time::Duration;
-use maxminddb::Reader;
 use crate::api_state::AppState;
-use crate::core::sovereign_lock::EU_EEA_WHITELIST;
+use crate::core::pii_redaction::TokenVault;
+
+/// Longest wait for upstream response headers, or between two body chunks
+pub const UPSTREAM_IDLE_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Upper bound of the response copy kept for compliance logging
+pub const MAX_TRANSCRIPT_BYTES: usize = 8 * 1024 * 1024;
 
 /// Proxy request configuration
 #[derive(Debug, Deserialize, Serialize, utoipa::ToSchema)]
@@ -34,235 +46,91 @@ fn default_method() -> String {
     "POST".to_string()
 }
 
-/// Proxy service for network-level compliance enforcement
-pub struct ProxyService {
-    client: Client,
-    /// GeoIP database reader (optional, uses hostname patterns if not available)
-    geoip_reader: Option<Arc<Reader<Vec<u8>>>>,
-}
-
-impl ProxyService {
-    pub fn new() -> Self {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(30))
-            .build()
-            .expect("Failed to create HTTP client");
-        
-        // Try to load GeoIP database
-        let geoip_reader = match std::env::var("GEOIP_DB_PATH") {
-            Ok(path) => {
-                match Reader::open_readfile(&path) {
-                    Ok(reader) => {
-                        log::info!("GeoIP database loaded successfully from: {}", path);
-                        Some(Arc::new(reader))
-                    }
-                    Err(e) => {
-                        eprintln!("WARNING: Failed to load GeoIP database from {}: {}. GeoIP is disabled. Only hardcoded hostname pattern checks will work.", path, e);
-                        None
-                    }
-                }
-            }
-            Err(_) => {
-                eprintln!("WARNING: GEOIP_DB_PATH not set. GeoIP is disabled. Only hardcoded hostname pattern checks will work.");
-                None
-            }
-        };
-        
-        Self {
-            client,
-            geoip_reader,
+impl ProxyRequest {
+    /// Replace personal data in the body with tokens from `vault`
+    ///
+    /// When anything was replaced, the upstream is asked for an uncompressed
+    /// response so the tokens can be swapped back on the way out.
+    pub fn redact(&mut self, vault: &mut TokenVault) {
+        if let Some(body) = self.body.as_mut() {
+            vault.redact_json(body);
         }
-    }
-
-    /// Check if target URL is in EU/EEA jurisdiction
-    /// Returns (is_eu, country_code)
-    pub async fn check_sovereignty(&self, target_url: &str) -> Result<(bool, String), String> {
-        // Parse URL
-        let url = reqwest::Url::parse(target_url)
-            .map_err(|e| format!("Invalid URL: {}", e))?;
-        
-        let hostname = url.host_str()
-            .ok_or_else(|| "No hostname in URL".to_string())?;
-
-        // First, check known hostname patterns (fast path)
-        let country = self.check_hostname_pattern(hostname);
-        if country != "UNKNOWN" {
-            let is_eu = EU_EEA_WHITELIST.contains(&country.as_str());
-            return Ok((is_eu, country));
+        if vault.is_empty() {
+            return;
         }
-
-        // Second, try DNS resolution + GeoIP lookup
-        if let Ok(ip) = self.resolve_hostname(hostname).await {
-            // Check if IP is in private ranges (assume EU for private IPs)
-            if self.is_private_ip(&ip) {
-                return Ok((true, "EU".to_string()));
-            }
-            
-            // Try GeoIP lookup if database is available
-            if let Some(country_code) = self.lookup_geoip(&ip).await {
-                let is_eu = EU_EEA_WHITELIST.contains(&country_code.as_str());
-                return Ok((is_eu, country_code));
-            }
-            
-            // If GeoIP database is missing, log and be conservative
-            log::warn!("GeoIP database not available. Cannot determine country for IP: {}. Blocking request.", ip);
-            return Ok((false, "UNKNOWN".to_string()));
-        }
-
-        // If we can't determine, be conservative and block
-        Ok((false, "UNKNOWN".to_string()))
+        let mut headers = match self.headers.take() {
+            Some(serde_json::Value::Object(headers)) => headers,
+            _ => serde_json::Map::new(),
+        };
+        headers.retain(|key, _| !key.eq_ignore_ascii_case("accept-encoding"));
+        headers.insert("accept-encoding".to_string(), serde_json::Value::from("identity"));
+        self.headers = Some(serde_json::Value::Object(headers));
     }
+}
 
-    /// Check hostname patterns for known AI services
-    fn check_hostname_pattern(&self, hostname: &str) -> String {
-        let hostname_lower = hostname.to_lowercase();
-        
-        // --- DEMO PATCH START ---
-        // Hardcoded bypass for demo to avoid DNS issues
-        if hostname_lower.contains("google.com") {
-            return "US".to_string();
-        }
-        if hostname_lower.contains("europa.eu") {
-            return "EU".to_string();
-        }
-        // --- DEMO PATCH END ---
-        
-        // US-based AI services
-        if hostname_lower.contains("openai.com") 
-            || hostname_lower.contains("anthropic.com")
-            || hostname_lower.contains("cohere.com")
-            || hostname_lower.contains("together.ai")
-            || hostname_lower.contains("replicate.com") {
-            return "US".to_string();
-        }
-
-        // Azure - check for EU regions in hostname
-        if hostname_lower.contains("azure.com") || hostname_lower.contains("azure-api.net") {
-            if hostname_lower.contains("westeurope") 
-                || hostname_lower.contains("northeurope")
-                || hostname_lower.contains("francecentral")
-                || hostname_lower.contains("germanywestcentral") {
-                return "EU".to_string();
-            }
-            return "US".to_string();
-        }
-
-        // AWS - check for EU regions
-        if hostname_lower.contains("amazonaws.com") || hostname_lower.contains("bedrock") {
-            if hostname_lower.contains("eu-west") 
-                || hostname_lower.contains("eu-central")
-                || hostname_lower.contains("eu-north")
-                || hostname_lower.contains("eu-south") {
-                return "EU".to_string();
-            }
-            return "US".to_string();
-        }
+/// Error forwarding a request upstream
+#[derive(Debug)]
+pub enum ProxyError {
+    Upstream(reqwest::Error),
+    /// No response headers within `UPSTREAM_IDLE_TIMEOUT`
+    Timeout,
+}
 
-        // Google Cloud - check for EU regions
-        if hostname_lower.contains("googleapis.com") || hostname_lower.contains("googlecloud.com") {
-            if hostname_lower.contains("europe") || hostname_lower.contains("eu-") {
-                return "EU".to_string();
-            }
-            return "US".to_string();
+impl fmt::Display for ProxyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProxyError::Upstream(e) => write!(f, "Upstream request failed: {}", e),
+            ProxyError::Timeout => write!(f, "Upstream did not respond within {:?}", UPSTREAM_IDLE_TIMEOUT),
         }
+    }
+}
 
-        // EU-based services
-        if hostname_lower.contains(".eu") 
-            || hostname_lower.contains(".de")
-            || hostname_lower.contains(".fr")
-            || hostname_lower.contains(".nl")
-            || hostname_lower.contains(".ie") {
-            return "EU".to_string();
-        }
+impl std::error::Error for ProxyError {}
 
-        "UNKNOWN".to_string()
-    }
+/// Proxy service for network-level compliance enforcement
+///
+/// Sovereignty is checked by the caller through `AppState::sovereignty`
+/// before anything is forwarded. Redirects are returned to the client rather
+/// than followed, since their targets were never checked.
+pub struct ProxyService {
+    client: Client,
+}
 
-    /// Resolve hostname to IP address
-    async fn resolve_hostname(&self, hostname: &str) -> Result<String, String> {
-        // Use tokio::net::lookup_host for async DNS resolution
-        let addr = format!("{}:80", hostname);
-        match addr.to_socket_addrs() {
-            Ok(mut addrs) => {
-                if let Some(addr) = addrs.next() {
-                    Ok(addr.ip().to_string())
-                } else {
-                    Err("No IP address found".to_string())
-                }
-            }
-            Err(e) => Err(format!("DNS resolution failed: {}", e)),
-        }
+impl ProxyService {
+    pub fn new() -> Self {
+        Self::pinned("", None)
     }
 
-    /// Look up IP address in GeoIP database
-    /// Returns country code (e.g., "DE", "FR", "US") if found, None otherwise
-    async fn lookup_geoip(&self, ip: &str) -> Option<String> {
-        let geoip_reader = self.geoip_reader.as_ref()?;
-        
-        // Parse IP address
-        let ip_addr: std::net::IpAddr = match ip.parse() {
-            Ok(addr) => addr,
-            Err(e) => {
-                log::warn!("Failed to parse IP address '{}': {}", ip, e);
-                return None;
-            }
-        };
+    /// Service whose connections to the host of `target_url` go to `ip`, the
+    /// address the sovereignty verdict was made for, instead of a fresh DNS
+    /// lookup. The URL is unchanged, so TLS SNI and the Host header still
+    /// carry the hostname. HTTP(S)_PROXY from the environment is ignored: an
+    /// egress proxy would resolve the hostname again and bypass the pin.
+    pub fn pinned(target_url: &str, ip: Option<IpAddr>) -> Self {
+        // No overall timeout: streamed completions can run for minutes.
+        // Stalls are caught by UPSTREAM_IDLE_TIMEOUT instead.
+        let mut builder = Client::builder()
+            .connect_timeout(Duration::from_secs(30))
+            .redirect(reqwest::redirect::Policy::none())
+            .no_proxy();
 
-        // Look up in GeoIP database
-        match geoip_reader.lookup::<maxminddb::geoip2::Country>(ip_addr) {
-            Ok(country) => {
-                if let Some(country_data) = country.country {
-                    if let Some(iso_code) = country_data.iso_code {
-                        let code = iso_code.to_string();
-                        log::debug!("GeoIP lookup for {}: {}", ip, code);
-                        return Some(code);
-                    }
-                }
-                log::debug!("GeoIP lookup for {}: No country code found", ip);
-                None
-            }
-            Err(e) => {
-                log::warn!("GeoIP lookup failed for {}: {}", ip, e);
-                None
-            }
+        let host = reqwest::Url::parse(target_url).ok()
+            .and_then(|url| url.host_str().map(str::to_string));
+        if let (Some(host), Some(ip)) = (host, ip) {
+            // reqwest keeps the URL's port; only the address is overridden
+            builder = builder.resolve(&host, SocketAddr::new(ip, 0));
         }
-    }
 
-    /// Check if IP is in private range
-    fn is_private_ip(&self, ip: &str) -> bool {
-        if let Ok(ip_addr) = ip.parse::<std::net::IpAddr>() {
-            match ip_addr {
-                std::net::IpAddr::V4(ipv4) => {
-                    let octets = ipv4.octets();
-                    // 10.0.0.0/8
-                    if octets[0] == 10 {
-                        return true;
-                    }
-                    // 172.16.0.0/12
-                    if octets[0] == 172 && octets[1] >= 16 && octets[1] <= 31 {
-                        return true;
-                    }
-                    // 192.168.0.0/16
-                    if octets[0] == 192 && octets[1] == 168 {
-                        return true;
-                    }
-                }
-                std::net::IpAddr::V6(_) => {
-                    // IPv6 private ranges (simplified)
-                    if ip.starts_with("fc00:") || ip.starts_with("fd00:") {
-                        return true;
-                    }
-                }
-            }
-        }
-        false
+        let client = builder.build().expect("Failed to create HTTP client");
+
+        Self { client }
     }
 
     /// Forward request to target URL
     pub async fn forward_request(
         &self,
         proxy_req: &ProxyRequest,
-    ) -> Result<reqwest::Response, reqwest::Error> {
+    ) -> Result<reqwest::Response, ProxyError> {
         let method = match proxy_req.method.as_str() {
             "GET" => reqwest::Method::GET,
             "POST" => reqwest::Method::POST,
@@ -290,7 +158,26 @@ impl ProxyService {
             request = request.json(body);
         }
 
-        request.send().await
+        Self::send(request).await
+    }
+
+    /// Forward a raw request (method, headers and body as received by the forward proxy)
+    pub async fn forward(
+        &self,
+        method: reqwest::Method,
+        target_url: &str,
+        headers: HeaderMap,
+        body: reqwest::Body,
+    ) -> Result<reqwest::Response, ProxyError> {
+        let request = self.client.request(method, target_url).headers(headers).body(body);
+        Self::send(request).await
+    }
+
+    async fn send(request: reqwest::RequestBuilder) -> Result<reqwest::Response, ProxyError> {
+        match tokio::time::timeout(UPSTREAM_IDLE_TIMEOUT, request.send()).await {
+            Ok(result) => result.map_err(ProxyError::Upstream),
+            Err(_) => Err(ProxyError::Timeout),
+        }
     }
 }
 
@@ -299,3 +186,342 @@ impl Default for ProxyService {
         Self::new()
     }
 }
+
+/// How a streamed upstream response ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamOutcome {
+    Completed,
+    UpstreamError,
+    TimedOut,
+    /// The client went away before the upstream finished
+    ClientDisconnected,
+}
+
+impl StreamOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StreamOutcome::Completed => "COMPLETED",
+            StreamOutcome::UpstreamError => "UPSTREAM_ERROR",
+            StreamOutcome::TimedOut => "TIMED_OUT",
+            StreamOutcome::ClientDisconnected => "CLIENT_DISCONNECTED",
+        }
+    }
+}
+
+/// Copy of a proxied response body, handed over once the stream ends
+#[derive(Debug, Clone)]
+pub struct Transcript {
+    /// Body as received, up to `MAX_TRANSCRIPT_BYTES`
+    pub body: Bytes,
+    /// Bytes forwarded to the client (may exceed `body.len()`)
+    pub bytes_streamed: u64,
+    pub truncated: bool,
+    pub outcome: StreamOutcome,
+}
+
+impl Transcript {
+    /// SHA-256 of the captured body (hex)
+    pub fn sha256(&self) -> String {
+        format!("{:x}", Sha256::digest(&self.body))
+    }
+
+    /// `data:` payloads of a server-sent events body, without the `[DONE]` marker
+    pub fn sse_events(&self) -> Vec<String> {
+        String::from_utf8_lossy(&self.body)
+            .lines()
+            .filter_map(|line| line.strip_prefix("data:"))
+            .map(|data| data.trim_start().to_string())
+            .filter(|data| data != "[DONE]")
+            .collect()
+    }
+}
+
+type CompletionHandler = Box<dyn FnOnce(Transcript) + Send>;
+
+/// Streams an upstream body to the client chunk by chunk (SSE included) while
+/// keeping a copy for compliance logging
+///
+/// `on_complete` runs exactly once: when the upstream finishes, fails, stalls for
+/// longer than `UPSTREAM_IDLE_TIMEOUT`, or the client disconnects.
+pub struct TranscriptStream {
+    upstream: Pin<Box<dyn Stream<Item = reqwest::Result<Bytes>> + Send>>,
+    idle: Pin<Box<tokio::time::Sleep>>,
+    body: Vec<u8>,
+    bytes_streamed: u64,
+    truncated: bool,
+    on_complete: Option<CompletionHandler>,
+}
+
+impl TranscriptStream {
+    pub fn new(response: reqwest::Response, on_complete: impl FnOnce(Transcript) + Send + 'static) -> Self {
+        Self::from_stream(response.bytes_stream(), on_complete)
+    }
+
+    fn from_stream(
+        upstream: impl Stream<Item = reqwest::Result<Bytes>> + Send + 'static,
+        on_complete: impl FnOnce(Transcript) + Send + 'static,
+    ) -> Self {
+        Self {
+            upstream: Box::pin(upstream),
+            idle: Box::pin(tokio::time::sleep(UPSTREAM_IDLE_TIMEOUT)),
+            body: Vec::new(),
+            bytes_streamed: 0,
+            truncated: false,
+            on_complete: Some(Box::new(on_complete)),
+        }
+    }
+
+    fn finish(&mut self, outcome: StreamOutcome) {
+        if let Some(on_complete) = self.on_complete.take() {
+            on_complete(Transcript {
+                body: Bytes::from(std::mem::take(&mut self.body)),
+                bytes_streamed: self.bytes_streamed,
+                truncated: self.truncated,
+                outcome,
+            });
+        }
+    }
+}
+
+impl Stream for TranscriptStream {
+    type Item = Result<Bytes, std::io::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.on_complete.is_none() {
+            return Poll::Ready(None);
+        }
+        match self.upstream.as_mut().poll_next(cx) {
+            Poll::Ready(Some(Ok(chunk))) => {
+                let this = &mut *self;
+                this.bytes_streamed += chunk.len() as u64;
+                let room = MAX_TRANSCRIPT_BYTES.saturating_sub(this.body.len());
+                if chunk.len() > room {
+                    this.truncated = true;
+                }
+                this.body.extend_from_slice(&chunk[..chunk.len().min(room)]);
+                this.idle.as_mut().reset(tokio::time::Instant::now() + UPSTREAM_IDLE_TIMEOUT);
+                Poll::Ready(Some(Ok(chunk)))
+            }
+            Poll::Ready(Some(Err(e))) => {
+                self.finish(StreamOutcome::UpstreamError);
+                Poll::Ready(Some(Err(std::io::Error::other(e))))
+            }
+            Poll::Ready(None) => {
+                self.finish(StreamOutcome::Completed);
+                Poll::Ready(None)
+            }
+            Poll::Pending => match self.idle.as_mut().poll(cx) {
+                Poll::Ready(()) => {
+                    self.finish(StreamOutcome::TimedOut);
+                    Poll::Ready(Some(Err(std::io::Error::new(
+                        std::io::ErrorKind::TimedOut,
+                        "Upstream stream stalled",
+                    ))))
+                }
+                Poll::Pending => Poll::Pending,
+            },
+        }
+    }
+}
+
+impl Drop for TranscriptStream {
+    fn drop(&mut self) {
+        self.finish(StreamOutcome::ClientDisconnected);
+    }
+}
+
+/// Puts the original values back into the response to a redacted request
+///
+/// Tokens split across chunks are held back until complete. A token that the
+/// model spreads over several server-sent events (one delta each) is not
+/// reassembled and reaches the client as-is.
+pub struct RehydratingStream<S> {
+    inner: S,
+    vault: TokenVault,
+    pending: Vec<u8>,
+}
+
+impl<S> RehydratingStream<S> {
+    pub fn new(inner: S, vault: TokenVault) -> Self {
+        Self { inner, vault, pending: Vec::new() }
+    }
+}
+
+impl<S> Stream for RehydratingStream<S>
+where
+    S: Stream<Item = Result<Bytes, std::io::Error>> + Unpin,
+{
+    type Item = Result<Bytes, std::io::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            match Pin::new(&mut this.inner).poll_next(cx) {
+                Poll::Ready(Some(Ok(chunk))) => {
+                    this.pending.extend_from_slice(&chunk);
+                    let hold = TokenVault::partial_token_start(&this.pending).unwrap_or(this.pending.len());
+                    let tail = this.pending.split_off(hold);
+                    let ready = std::mem::replace(&mut this.pending, tail);
+                    if !ready.is_empty() {
+                        return Poll::Ready(Some(Ok(Bytes::from(this.vault.rehydrate_bytes(&ready)))));
+                    }
+                }
+                Poll::Ready(None) if !this.pending.is_empty() => {
+                    let rest = std::mem::take(&mut this.pending);
+                    return Poll::Ready(Some(Ok(Bytes::from(this.vault.rehydrate_bytes(&rest)))));
+                }
+                other => return other,
+            }
+        }
+    }
+}
+
+/// Hop-by-hop headers that must not be copied between client and upstream
+pub fn is_hop_by_hop_header(name: &str) -> bool {
+    matches!(
+        name.to_ascii_lowercase().as_str(),
+        "connection" | "keep-alive" | "proxy-authenticate" | "proxy-authorization"
+            | "proxy-connection" | "te" | "trailer" | "transfer-encoding" | "upgrade" | "content-length"
+    )
+}
+
+/// Headers to send back to the client for an upstream response
+///
+/// End-to-end headers are kept as-is; the body is passed through untouched, so
+/// the upstream content-encoding stays valid. Server-sent events must reach the
+/// client unbuffered, so compression and reverse-proxy buffering are turned off.
+pub fn downstream_headers(upstream: &HeaderMap) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    for (key, value) in upstream.iter() {
+        if !is_hop_by_hop_header(key.as_str()) {
+            headers.append(key.clone(), value.clone());
+        }
+    }
+
+    let is_event_stream = upstream.get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.starts_with("text/event-stream"))
+        .unwrap_or(false);
+    if is_event_stream {
+        if !headers.contains_key(header::CONTENT_ENCODING) {
+            headers.insert(header::CONTENT_ENCODING, HeaderValue::from_static("identity"));
+        }
+        if !headers.contains_key(header::CACHE_CONTROL) {
+            headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+        }
+        headers.insert("x-accel-buffering", HeaderValue::from_static("no"));
+    }
+    headers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+    use std::sync::{Arc, Mutex};
+
+    #[tokio::test]
+    async fn test_transcript_stream_forwards_chunks_and_reports_once() {
+        let chunks: Vec<reqwest::Result<Bytes>> = vec![
+            Ok(Bytes::from_static(b"data: {\"delta\":\"Hel\"}\n\n")),
+            Ok(Bytes::from_static(b"data: {\"delta\":\"lo\"}\n\n")),
+            Ok(Bytes::from_static(b"data: [DONE]\n\n")),
+        ];
+        let seen: Arc<Mutex<Vec<Transcript>>> = Arc::new(Mutex::new(Vec::new()));
+        let seen_clone = seen.clone();
+        let mut stream = TranscriptStream::from_stream(futures::stream::iter(chunks), move |t| {
+            seen_clone.lock().unwrap().push(t);
+        });
+
+        let mut forwarded = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            forwarded.push(chunk.unwrap());
+        }
+        drop(stream);
+
+        assert_eq!(forwarded.len(), 3);
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 1);
+        assert_eq!(seen[0].outcome, StreamOutcome::Completed);
+        assert_eq!(seen[0].bytes_streamed, forwarded.iter().map(|c| c.len() as u64).sum::<u64>());
+        assert_eq!(seen[0].sse_events(), vec!["{\"delta\":\"Hel\"}", "{\"delta\":\"lo\"}"]);
+    }
+
+    #[test]
+    fn test_downstream_headers() {
+        let mut upstream = HeaderMap::new();
+        upstream.insert(header::CONTENT_TYPE, HeaderValue::from_static("text/event-stream"));
+        upstream.insert(header::TRANSFER_ENCODING, HeaderValue::from_static("chunked"));
+        upstream.insert("x-request-id", HeaderValue::from_static("req_123"));
+
+        let headers = downstream_headers(&upstream);
+        assert!(!headers.contains_key(header::TRANSFER_ENCODING));
+        assert_eq!(headers.get("x-request-id").unwrap(), "req_123");
+        assert_eq!(headers.get(header::CONTENT_ENCODING).unwrap(), "identity");
+        assert_eq!(headers.get("x-accel-buffering").unwrap(), "no");
+    }
+
+    #[tokio::test]
+    async fn test_transcript_stream_reports_client_disconnect() {
+        let seen: Arc<Mutex<Option<StreamOutcome>>> = Arc::new(Mutex::new(None));
+        let seen_clone = seen.clone();
+        let stream = TranscriptStream::from_stream(futures::stream::pending(), move |t| {
+            *seen_clone.lock().unwrap() = Some(t.outcome);
+        });
+        drop(stream);
+        assert_eq!(*seen.lock().unwrap(), Some(StreamOutcome::ClientDisconnected));
+    }
+
+    #[tokio::test]
+    async fn test_rehydrating_stream_restores_split_tokens() {
+        let mut vault = TokenVault::new();
+        let mut proxy_req = ProxyRequest {
+            target_url: "https://api.mistral.ai/v1/chat/completions".to_string(),
+            method: "POST".to_string(),
+            headers: Some(serde_json::json!({"Accept-Encoding": "gzip"})),
+            body: Some(serde_json::json!({"prompt": "Refund IBAN DE89 3704 0044 0532 0130 00"})),
+        };
+        proxy_req.redact(&mut vault);
+        let prompt = proxy_req.body.as_ref().unwrap()["prompt"].as_str().unwrap().to_string();
+        assert!(!prompt.contains("DE89"));
+        assert_eq!(proxy_req.headers, Some(serde_json::json!({"accept-encoding": "identity"})));
+
+        // The upstream echoes the token, cut in two by a chunk boundary
+        let token = prompt.trim_start_matches("Refund IBAN ").to_string();
+        let chunks: Vec<Result<Bytes, std::io::Error>> = vec![
+            Ok(Bytes::from(format!("Refunded to {}", &token[..5]))),
+            Ok(Bytes::from(format!("{}.", &token[5..]))),
+        ];
+        let rehydrated: Vec<Bytes> = RehydratingStream::new(futures::stream::iter(chunks), vault)
+            .map(|chunk| chunk.unwrap())
+            .collect()
+            .await;
+        assert_eq!(rehydrated.concat(), b"Refunded to DE89 3704 0044 0532 0130 00.");
+    }
+
+    #[tokio::test]
+    async fn test_pinned_service_connects_to_checked_address() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = vec![0u8; 4096];
+            let n = socket.read(&mut request).await.unwrap();
+            socket.write_all(b"HTTP/1.1 204 No Content\r\ncontent-length: 0\r\n\r\n").await.unwrap();
+            String::from_utf8_lossy(&request[..n]).to_lowercase()
+        });
+
+        // The hostname does not resolve; only the pinned address makes this work
+        let target_url = format!("http://sovereign.invalid:{}/v1/models", port);
+        let response = ProxyService::pinned(&target_url, Some("127.0.0.1".parse().unwrap()))
+            .forward(reqwest::Method::GET, &target_url, HeaderMap::new(), reqwest::Body::from(""))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), reqwest::StatusCode::NO_CONTENT);
+        let request = server.await.unwrap();
+        assert!(request.contains(&format!("host: sovereign.invalid:{}", port)));
+    }
+}
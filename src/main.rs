@@ -31,8 +31,27 @@ use routes::*;
         routes::log_action,
         routes::get_logs,
         routes::shred_data,
+        routes::read_payload,
+        routes::list_master_keys,
+        routes::rewrap_master_keys,
+        routes::retire_master_key,
+        routes::data_subject_erase,
+        routes::verify_erasure_certificate,
+        routes::verify_chain,
+        routes::list_audit_anchors,
+        routes::get_anchor_proof,
+        routes::get_seal_provider,
+        routes::set_seal_provider,
+        routes::get_seal_token,
+        routes::get_seal_outbox,
         routes::download_report,
         routes::revoke_access,
+        routes::restore_access,
+        routes::list_agent_revocations,
+        routes::get_agent_revocation_history,
+        routes::create_lockdown,
+        routes::list_lockdowns,
+        routes::lift_lockdown,
         routes::data_subject_access,
         routes::data_subject_export,
         routes::data_subject_rectify,
@@ -95,8 +114,23 @@ use routes::*;
         routes::gdpr_article_12::get_privacy_notice_templates,
         routes::proxy_request,
         routes::simulate_policy,
+        routes::submit_simulation_job,
+        routes::list_simulation_jobs,
+        routes::get_simulation_job,
         routes::export_simulation_report,
         routes::rollback_policy,
+        routes::list_jurisdiction_policies,
+        routes::publish_jurisdiction_policy,
+        routes::activate_jurisdiction_policy,
+        routes::export_policy_bundle,
+        routes::import_policy_bundle,
+        routes::list_policy_bundle_imports,
+        routes::apply_policy_bundle_import,
+        routes::rollback_policy_bundle_import,
+        routes::list_region_catalog,
+        routes::upsert_region_catalog_entry,
+        routes::list_usage_budgets,
+        routes::upsert_usage_budget,
         routes::get_policy_impact_analytics,
         routes::get_shadow_mode_analytics,
         routes::export_shadow_mode_logs,
@@ -120,6 +154,7 @@ use routes::*;
         routes::get_business_function_dashboard,
         routes::create_or_update_asset,
         routes::list_assets,
+        routes::get_asset_usage,
         routes::get_asset_by_agent,
         routes::list_business_functions,
         routes::create_asset_policy,
@@ -218,6 +253,40 @@ use routes::*;
         routes::modules::EnableModuleRequest,
         routes::modules::ModuleResponse,
         routes::RollbackRequest,
+        routes::PublishJurisdictionPolicyRequest,
+        crate::core::jurisdiction_policy::JurisdictionPolicy,
+        crate::core::jurisdiction_policy::SccException,
+        routes::ImportPolicyBundleRequest,
+        routes::RollbackPolicyBundleRequest,
+        crate::core::policy_bundle::PolicyBundleImport,
+        crate::core::policy_bundle::BundlePlan,
+        crate::core::policy_bundle::BundleChange,
+        crate::core::policy_bundle::ApplySummary,
+        crate::core::region_catalog::CloudRegion,
+        routes::UpsertCloudRegionRequest,
+        routes::RevokeAccessRequest,
+        routes::RestoreAccessRequest,
+        crate::core::agent_revocation::AgentRevocation,
+        crate::core::agent_revocation::RevocationEvent,
+        routes::CreateLockdownRequest,
+        routes::LiftLockdownRequest,
+        crate::core::lockdown::LockdownRule,
+        crate::core::audit_chain::ChainVerification,
+        crate::core::audit_chain::ChainIssue,
+        crate::core::merkle_anchor::AuditAnchor,
+        crate::core::merkle_anchor::InclusionProof,
+        crate::core::merkle_anchor::ProofStep,
+        routes::SetSealProviderRequest,
+        crate::core::seal_provider::SealProviderSetting,
+        crate::core::seal_provider::SealVerification,
+        crate::core::seal_provider::TstInfo,
+        crate::core::seal_outbox::SealOutboxEntry,
+        crate::core::seal_outbox::SealOutboxStatus,
+        crate::core::usage_budget::UsageBudget,
+        crate::core::usage_budget::BudgetStatus,
+        crate::core::usage_budget::ModelUsage,
+        routes::UpsertUsageBudgetRequest,
+        routes::AssetUsageResponse,
         routes::PolicyImpactAnalytics,
         routes::AgentStats,
         routes::RiskAssessmentSummary,
@@ -231,6 +300,7 @@ use routes::*;
         routes::PolicyCircuitBreakerStatus,
         routes::CanaryAnalytics,
         routes::PolicyCanaryStatus,
+        crate::core::rollout::RolloutAssignment,
         routes::CanaryTransition,
         routes::TPRMComplianceReport,
         routes::VendorComplianceInfo,
@@ -264,6 +334,12 @@ use routes::*;
         routes::DelegationListResponse,
         crate::core::policy_simulator::SimulationRequest,
         crate::core::policy_simulator::SimulationResult,
+        crate::core::policy_simulator::VerdictChange,
+        crate::core::policy_simulator::SimulationJob,
+        crate::core::policy_simulator::CandidatePolicySet,
+        crate::core::policy_simulator::CandidateBudget,
+        crate::core::policy_simulator::CandidateLockdown,
+        crate::core::policy_bundle::BundleAssetPolicy,
         routes::AssetRequest,
         routes::AssetResponse,
         routes::AssetsListResponse,
@@ -393,6 +469,74 @@ async fn main() -> std::io::Result<()> {
         worker5.process_canary_deployment().await;
     });
 
+    let db_pool_for_rewrap = app_state.db_pool.clone();
+    let key_store_for_rewrap = app_state.key_store.clone();
+    let worker6 = background_worker::BackgroundWorker::new(db_pool_for_rewrap);
+    tokio::spawn(async move {
+        worker6.process_master_key_rewrap(key_store_for_rewrap).await;
+    });
+
+    let db_pool_for_revocations = app_state.db_pool.clone();
+    let revocations = app_state.revocations.clone();
+    let worker7 = background_worker::BackgroundWorker::new(db_pool_for_revocations);
+    tokio::spawn(async move {
+        worker7.process_revocation_notifications(revocations).await;
+    });
+
+    let db_pool_for_lockdowns = app_state.db_pool.clone();
+    let worker8 = background_worker::BackgroundWorker::new(db_pool_for_lockdowns);
+    tokio::spawn(async move {
+        worker8.process_lockdown_expiry().await;
+    });
+
+    let db_pool_for_anchors = app_state.db_pool.clone();
+    let seals_for_anchors = app_state.seals.clone();
+    let worker9 = background_worker::BackgroundWorker::new(db_pool_for_anchors);
+    tokio::spawn(async move {
+        worker9.process_audit_anchoring(seals_for_anchors).await;
+    });
+
+    let db_pool_for_seal_outbox = app_state.db_pool.clone();
+    let seals_for_outbox = app_state.seals.clone();
+    let worker10 = background_worker::BackgroundWorker::new(db_pool_for_seal_outbox);
+    tokio::spawn(async move {
+        worker10.process_seal_outbox(seals_for_outbox).await;
+    });
+
+    let db_pool_for_simulations = app_state.db_pool.clone();
+    let sovereignty_for_simulations = app_state.sovereignty.clone();
+    let worker11 = background_worker::BackgroundWorker::new(db_pool_for_simulations);
+    tokio::spawn(async move {
+        worker11.process_simulation_jobs(sovereignty_for_simulations).await;
+    });
+
+    // Forward proxy listener (HTTPS_PROXY / base-URL override for AI SDKs).
+    // Proxy deployments run only this listener; other modes start it when
+    // FORWARD_PROXY_PORT is set.
+    let deployment_mode = deployment::DeploymentMode::from_env();
+    let forward_proxy_port = std::env::var("FORWARD_PROXY_PORT").ok();
+    if deployment_mode == deployment::DeploymentMode::Proxy || forward_proxy_port.is_some() {
+        let port = forward_proxy_port
+            .unwrap_or_else(|| "8081".to_string())
+            .parse::<u16>()
+            .expect("FORWARD_PROXY_PORT must be a valid port number");
+        let host = std::env::var("SERVER_HOST").unwrap_or_else(|_| "0.0.0.0".to_string());
+        let addr: std::net::SocketAddr = format!("{}:{}", host, port)
+            .parse()
+            .expect("SERVER_HOST must be a valid IP address");
+        println!("🛡️  Forward proxy listening on {}", addr);
+
+        if deployment_mode == deployment::DeploymentMode::Proxy {
+            return integration::forward_proxy::serve(addr, app_state).await;
+        }
+        let proxy_state = app_state.clone();
+        tokio::spawn(async move {
+            if let Err(e) = integration::forward_proxy::serve(addr, proxy_state).await {
+                log::error!("Forward proxy stopped: {}", e);
+            }
+        });
+    }
+
     // Initialize security services
     let rate_limiter = RateLimit::new(RateLimitConfig {
         requests_per_minute: 100,
@@ -461,12 +605,29 @@ async fn main() -> std::io::Result<()> {
                     .service(web::resource("/log_action").route(web::post().to(log_action)))
                     .service(web::resource("/logs").route(web::get().to(get_logs)))
                     .service(web::resource("/shred_data").route(web::post().to(shred_data)))
+                    .service(web::resource("/payloads/{tx_id}").route(web::get().to(read_payload)))
+                    .service(web::resource("/keys/master").route(web::get().to(list_master_keys)))
+                    .service(web::resource("/keys/master/rewrap").route(web::post().to(rewrap_master_keys)))
+                    .service(web::resource("/keys/master/{key_id}/retire").route(web::post().to(retire_master_key)))
                     .service(web::resource("/download_report").route(web::get().to(download_report)))
                     .service(web::resource("/revoke_access").route(web::post().to(revoke_access)))
+                    .service(web::resource("/restore_access").route(web::post().to(restore_access)))
+                    .service(web::resource("/agent_revocations").route(web::get().to(list_agent_revocations)))
+                    .service(web::resource("/agent_revocations/{agent_id}/history").route(web::get().to(get_agent_revocation_history)))
+                    .service(web::resource("/lockdowns").route(web::post().to(create_lockdown)).route(web::get().to(list_lockdowns)))
+                    .service(web::resource("/lockdowns/{lockdown_id}/lift").route(web::post().to(lift_lockdown)))
                     // Priority 1: Data Subject Rights
                     .service(web::resource("/data_subject/{user_id}/access").route(web::get().to(data_subject_access)))
                     .service(web::resource("/data_subject/{user_id}/export").route(web::get().to(data_subject_export)))
                     .service(web::resource("/data_subject/{user_id}/rectify").route(web::put().to(data_subject_rectify)))
+                    .service(web::resource("/data_subject/{user_id}/erase").route(web::post().to(data_subject_erase)))
+                    .service(web::resource("/erasure_certificates/verify").route(web::post().to(verify_erasure_certificate)))
+                    .service(web::resource("/verify_chain").route(web::get().to(verify_chain)))
+                    .service(web::resource("/audit_anchors").route(web::get().to(list_audit_anchors)))
+                    .service(web::resource("/audit_anchors/proof/{seal_id}").route(web::get().to(get_anchor_proof)))
+                    .service(web::resource("/seal_provider").route(web::get().to(get_seal_provider)).route(web::put().to(set_seal_provider)))
+                    .service(web::resource("/seal_tokens").route(web::get().to(get_seal_token)))
+                    .service(web::resource("/seal_outbox").route(web::get().to(get_seal_outbox)))
                     // GDPR Article 18: Processing Restrictions
                     .service(web::resource("/data_subject/{user_id}/restrict").route(web::post().to(request_processing_restriction)))
                     .service(web::resource("/data_subject/{user_id}/lift_restriction").route(web::post().to(lift_processing_restriction)))
@@ -566,9 +727,20 @@ async fn main() -> std::io::Result<()> {
                     // Policy Management - Operational Safety
                     .service(web::resource("/policies/simulate").route(web::post().to(routes::simulate_policy)))
                     .service(web::resource("/policies/simulate/export").route(web::post().to(routes::export_simulation_report)))
+                    .service(web::resource("/policies/simulate/jobs").route(web::post().to(routes::submit_simulation_job)).route(web::get().to(routes::list_simulation_jobs)))
+                    .service(web::resource("/policies/simulate/jobs/{job_id}").route(web::get().to(routes::get_simulation_job)))
                     .service(web::resource("/policies/preview-impact").route(web::get().to(routes::preview_policy_impact)))
                     .service(web::resource("/policies/compare").route(web::post().to(routes::compare_policies)))
                     .service(web::resource("/policies/{policy_id}/rollback").route(web::post().to(routes::rollback_policy)))
+                    .service(web::resource("/jurisdiction_policies").route(web::get().to(routes::list_jurisdiction_policies)).route(web::post().to(routes::publish_jurisdiction_policy)))
+                    .service(web::resource("/jurisdiction_policies/{policy_id}/activate").route(web::post().to(routes::activate_jurisdiction_policy)))
+                    .service(web::resource("/policy_bundle").route(web::get().to(routes::export_policy_bundle)))
+                    .service(web::resource("/policy_bundle/import").route(web::post().to(routes::import_policy_bundle)))
+                    .service(web::resource("/policy_bundle/imports").route(web::get().to(routes::list_policy_bundle_imports)))
+                    .service(web::resource("/policy_bundle/imports/{import_id}/apply").route(web::post().to(routes::apply_policy_bundle_import)))
+                    .service(web::resource("/policy_bundle/imports/{import_id}/rollback").route(web::post().to(routes::rollback_policy_bundle_import)))
+                    .service(web::resource("/region_catalog").route(web::get().to(routes::list_region_catalog)).route(web::put().to(routes::upsert_region_catalog_entry)))
+                    .service(web::resource("/usage_budgets").route(web::get().to(routes::list_usage_budgets)).route(web::post().to(routes::upsert_usage_budget)))
                     .service(web::resource("/policies/{policy_id}/health").route(web::get().to(routes::get_policy_health)))
                     .service(web::resource("/policies/{policy_id}/approve").route(web::post().to(routes::approve_policy)))
                     .service(web::resource("/policies/{policy_id}/reject").route(web::post().to(routes::reject_policy)))
@@ -593,6 +765,7 @@ async fn main() -> std::io::Result<()> {
                     // Veridion TPRM Integration
                     .service(web::resource("/vendors/{vendor_domain}/risk-score").route(web::get().to(routes::get_vendor_risk_score)))
                     .service(web::resource("/assets/{asset_id}/enrich-tprm").route(web::post().to(routes::enrich_asset_tprm)))
+                    .service(web::resource("/assets/{asset_id}/usage").route(web::get().to(routes::get_asset_usage)))
                     .service(web::resource("/policies/auto-generate-from-tprm").route(web::post().to(routes::auto_generate_tprm_policies)))
                     // Executive Assurance Reporting
                     .service(web::resource("/reports/executive-assurance").route(web::get().to(routes::get_executive_assurance)))
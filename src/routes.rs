@@ -44,6 +44,47 @@ async fn authenticate_and_authorize(
 
     Ok(claims)
 }
+
+/// Agent and tenant of the Veridion API key sent in X-Veridion-API-Key
+///
+/// Ok(None) without a key. An invalid key is refused with 401; an agent or
+/// tenant claim the key does not vouch for with 403 IDENTITY_MISMATCH.
+async fn api_key_identity(
+    http_req: &HttpRequest,
+    db_pool: &sqlx::PgPool,
+    claimed_agents: &[Option<String>],
+    claimed_company: Option<&str>,
+) -> Result<Option<crate::security::api_keys::AgentIdentity>, HttpResponse> {
+    let key = http_req.headers()
+        .get(crate::integration::forward_proxy::API_KEY_HEADER)
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.trim().to_string());
+    let identity = match key {
+        Some(key) => match crate::security::api_keys::ApiKeyService::new(db_pool.clone()).validate_api_key(&key).await {
+            Ok(Some(info)) => crate::security::api_keys::AgentIdentity::from_key(info),
+            Ok(None) => {
+                return Err(HttpResponse::Unauthorized().json(serde_json::json!({
+                    "error": "Unauthorized",
+                    "message": "Invalid or expired API key"
+                })));
+            }
+            Err(e) => {
+                let request_id = generate_request_id();
+                log_error_safely("validating API key", &e, &request_id);
+                return Err(create_error_response(&request_id));
+            }
+        },
+        None => return Ok(None),
+    };
+
+    match identity.conflict(claimed_agents, claimed_company) {
+        Some(message) => Err(HttpResponse::Forbidden().json(serde_json::json!({
+            "error": "IDENTITY_MISMATCH",
+            "message": message
+        }))),
+        None => Ok(Some(identity)),
+    }
+}
 use crate::core::annex_iv::ComplianceRecord;
 use crate::compliance_models::*;
 use crate::models::db_models::*;
@@ -59,14 +100,24 @@ use uuid::Uuid;
 
 #[derive(Serialize, Deserialize, ToSchema)]
 pub struct LogRequest {
+    /// Agent the action is logged for; the agent of the API key when one is sent
     pub agent_id: String,
     pub action: String,
     pub payload: String,
-    /// Target region for data processing. Use ISO country codes (e.g., "EU", "DE", "SK") for allowed regions.
-    /// Blocked regions: "US", "CN", "RU" (case-insensitive, also blocks "US-*" patterns like "US-EAST-1", "US-WEST-2")
-    /// Examples: "EU" (allowed), "DE" (allowed), "US" (blocked), "us-east-1" (blocked), "CN" (blocked)
+    /// Target region for data processing: ISO country code, country name, cloud region or IP address.
+    /// Checked against the tenant's jurisdiction policy; by default only EU/EEA destinations are allowed.
+    /// Examples: "EU" (allowed), "DE" (allowed), "eu-central-1" (allowed), "US" (blocked), "us-east-1" (blocked)
     #[schema(example = "EU")]
     pub target_region: Option<String>,
+    /// Tenant whose jurisdiction policy applies. Taken from the Veridion API key
+    /// (X-Veridion-API-Key); when given it must be the key's tenant. Requests
+    /// without a key fall under the global policy
+    #[serde(default)]
+    pub company_id: Option<Uuid>,
+    /// Host of the vendor API the action sends data to, checked against VENDOR_DOMAIN lockdowns
+    #[serde(default)]
+    #[schema(example = "api.openai.com")]
+    pub vendor_domain: Option<String>,
     /// Transparency flag (EU AI Act Article 13)
     #[schema(example = true)]
     pub user_notified: Option<bool>,
@@ -120,6 +171,10 @@ pub struct LogResponse {
     /// Human oversight status
     #[schema(example = "PENDING")]
     pub human_oversight_status: Option<String>,
+    /// Number of personal data values replaced with tokens before logging
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = 2)]
+    pub pii_redacted: Option<usize>,
 }
 
 #[derive(Serialize, Deserialize, ToSchema)]
@@ -131,22 +186,25 @@ pub struct ShredRequest {
 /// Log AI action for compliance tracking
 /// 
 /// Logs an AI agent action with full compliance tracking. Automatically enforces:
-/// - Sovereign Lock (blocks US/CN/RU regions and US-* patterns like us-east-1)
+/// - Sovereign Lock (tenant jurisdiction policy, EU/EEA only by default)
 /// - Risk Assessment
 /// - eIDAS Sealing
 /// - Audit Logging
 /// 
 /// **Authentication:** Requires JWT token in `Authorization: Bearer <token>` header.
+/// The tenant comes from the Veridion API key in `X-Veridion-API-Key`, if one is sent.
 /// 
-/// **Example allowed regions:** EU, DE, SK, FR, IT
-/// **Example blocked regions:** US, CN, RU, us-east-1, US-WEST-2
+/// **Example allowed regions:** EU, DE, SK, FR, IT, eu-central-1, westeurope
+/// **Example blocked regions (default policy):** US, CN, RU, JP, us-east-1, US-WEST-2
 #[utoipa::path(
     post,
     path = "/log_action",
     request_body = LogRequest,
     responses(
         (status = 200, description = "Action logged successfully (COMPLIANT)", body = LogResponse),
-        (status = 403, description = "Action blocked (SOVEREIGNTY violation or other compliance issue)", body = LogResponse)
+        (status = 401, description = "Invalid or expired API key"),
+        (status = 503, description = "Active lockdowns could not be checked (LOCKDOWN_CHECK_FAILED)"),
+        (status = 403, description = "Action blocked (SOVEREIGNTY violation or other compliance issue), or agent_id or company_id not matching the API key (IDENTITY_MISMATCH)", body = LogResponse)
     ),
     tag = "Compliance"
 )]
@@ -206,6 +264,37 @@ pub async fn log_action(
         Err(resp) => return resp,
     };
 
+    // PII REDACTION
+    // Personal data in the payload is replaced with tokens before it is hashed,
+    // sealed, encrypted or stored anywhere. The original values go to a token
+    // vault under the crypto-shredder; only the risk assessment sees them.
+    let mut req = req.into_inner();
+
+    // AGENT & TENANT
+    // With an API key, agent_id must be the key's agent, so a revoked agent cannot
+    // log under another name; the tenant whose policies apply comes from the key
+    let claimed_company = req.company_id.map(|id| id.to_string());
+    let claimed_agents = [Some(req.agent_id.clone())];
+    req.company_id = match api_key_identity(&http_req, &data.db_pool, &claimed_agents, claimed_company.as_deref()).await {
+        Ok(Some(identity)) => identity.company_id.and_then(|id| Uuid::parse_str(&id).ok()),
+        Ok(None) if claimed_company.is_some() => {
+            return HttpResponse::Forbidden().json(serde_json::json!({
+                "error": "IDENTITY_MISMATCH",
+                "message": "The tenant is taken from the Veridion API key (X-Veridion-API-Key)"
+            }));
+        }
+        Ok(None) => None,
+        Err(resp) => return resp,
+    };
+
+    let mut pii_vault = crate::core::pii_redaction::TokenVault::new();
+    let raw_payload = if data.pii_redaction_enabled {
+        let redacted = pii_vault.redact(&req.payload);
+        Some(std::mem::replace(&mut req.payload, redacted))
+    } else {
+        None
+    };
+
     // A. AGENT REVOCATION CHECK (granular per agent)
     if data.is_agent_revoked(&req.agent_id) {
         return HttpResponse::Forbidden().json(serde_json::json!({
@@ -329,18 +418,23 @@ pub async fn log_action(
 
     // A.3. CONSENT CHECK (GDPR Article 6, 7) - if user_id is provided
     // Check consent AFTER objections and restrictions (consent is checked last)
-    if let Some(ref user_id) = req.user_id {
-        let has_consent = check_consent(&data.db_pool, user_id, "PROCESSING").await
-            .unwrap_or(false);
-        
-        if !has_consent {
-            return HttpResponse::Forbidden().json(serde_json::json!({
-                "status": "CONSENT_REQUIRED",
-                "reason": "User has not granted consent for data processing",
-                "user_id": user_id
-            }));
+    // The outcome is also the consent state policy rules see
+    let consent_state = match req.user_id {
+        Some(ref user_id) => {
+            let has_consent = check_consent(&data.db_pool, user_id, "PROCESSING").await
+                .unwrap_or(false);
+
+            if !has_consent {
+                return HttpResponse::Forbidden().json(serde_json::json!({
+                    "status": "CONSENT_REQUIRED",
+                    "reason": "User has not granted consent for data processing",
+                    "user_id": user_id
+                }));
+            }
+            crate::core::policy_rules::CONSENT_GRANTED
         }
-    }
+        None => crate::core::policy_rules::CONSENT_NOT_APPLICABLE,
+    };
 
     // 0. CHECK ENFORCEMENT MODE (Shadow Mode Infrastructure)
     // Get system-wide enforcement mode
@@ -367,16 +461,26 @@ pub async fn log_action(
     let is_shadow_mode = enforcement_mode == "SHADOW" || enforcement_mode == "DRY_RUN";
 
     // B. SOVEREIGN LOCK
-    // Check for blocked regions (case-insensitive, also catches AWS region patterns like "us-east-1")
-    let target = req.target_region.as_deref().unwrap_or("").to_uppercase();
-    let is_violation = target == "US" 
-        || target == "CN"
-        || target == "RU"
-        || target == "USA"
-        || target.starts_with("US-")  // Catches "US-EAST-1", "US-WEST-2", etc.
-        || target.contains("UNITED STATES")
-        || target == "CHINA"
-        || target == "RUSSIA";
+    // Evaluate the declared region against the tenant's jurisdiction policy.
+    // Actions without a target_region do not transfer data and are not checked.
+    let target = req.target_region.as_deref().unwrap_or("").trim().to_uppercase();
+    let jurisdiction_decision = if target.is_empty() {
+        None
+    } else {
+        let policy = match crate::core::jurisdiction_policy::JurisdictionPolicy::load_active(&data.db_pool, req.company_id).await {
+            Ok(active) => active.map(|a| a.policy).unwrap_or_default(),
+            Err(e) => {
+                // Fall back to the strict default (EU/EEA only)
+                log::warn!("Failed to load jurisdiction policy: {}", e);
+                crate::core::jurisdiction_policy::JurisdictionPolicy::default()
+            }
+        };
+        Some(policy.evaluate(&data.sovereignty, &target, &req.agent_id, Utc::now()))
+    };
+    let is_violation = jurisdiction_decision.as_ref().map(|d| !d.allowed).unwrap_or(false);
+    let detected_country = jurisdiction_decision.as_ref()
+        .map(|d| d.country.clone())
+        .unwrap_or_else(|| target.clone());
     
     let status = if is_violation { "BLOCKED (SOVEREIGNTY)" } else { "COMPLIANT" };
     
@@ -402,12 +506,12 @@ pub async fn log_action(
         .bind(false) // would_allow
         .bind("SOVEREIGN_LOCK")
         .bind("HIGH")
-        .bind(&target)
+        .bind(&detected_country)
         .bind(chrono::Utc::now())
         .execute(&data.db_pool)
         .await;
         
-        log::warn!("SHADOW MODE: Would block {} -> {} (Country: {})", req.agent_id, req.action, target);
+        log::warn!("SHADOW MODE: Would block {} -> {} (Country: {})", req.agent_id, req.action, detected_country);
         
         // Send shadow mode alert (async, don't block)
         let notification_service = crate::integration::notifications::NotificationService::new();
@@ -448,7 +552,7 @@ pub async fn log_action(
         .bind(true)  // would_allow
         .bind("SOVEREIGN_LOCK")
         .bind("LOW")
-        .bind(&target)
+        .bind(&detected_country)
         .bind(chrono::Utc::now())
         .execute(&data.db_pool)
         .await;
@@ -474,6 +578,36 @@ pub async fn log_action(
         crate::core::asset_policy_engine::AssetPolicyEngine::infer_business_function_from_action(&req.action)
     };
 
+    // C.1. SCOPED LOCKDOWN CHECK (business function, vendor domain, jurisdiction, tenant)
+    let tenant = req.company_id.map(|id| id.to_string());
+    let lockdown_target = crate::core::lockdown::LockdownTarget {
+        business_function: business_function.as_deref(),
+        vendor_host: req.vendor_domain.as_deref(),
+        country: Some(detected_country.as_str()).filter(|c| !c.is_empty()),
+        tenant: tenant.as_deref(),
+    };
+    match crate::core::lockdown::Lockdowns::check(&data.db_pool, &lockdown_target).await {
+        Ok(Some(lockdown)) => {
+            return HttpResponse::Forbidden().json(serde_json::json!({
+                "status": "SCOPED_LOCKDOWN",
+                "reason": format!("Lockdown active for {}", lockdown.describe()),
+                "lockdown_id": lockdown.id,
+                "expires_at": lockdown.expires_at
+            }));
+        }
+        Ok(None) => {}
+        Err(e) => {
+            // An emergency lockdown must not fail open
+            let request_id = generate_request_id();
+            log_error_safely("checking lockdowns", &e, &request_id);
+            return HttpResponse::ServiceUnavailable().json(serde_json::json!({
+                "status": "LOCKDOWN_CHECK_FAILED",
+                "reason": "Active lockdowns could not be checked; the action was not logged",
+                "request_id": request_id
+            }));
+        }
+    }
+
     // Get applicable asset-based policies
     let asset_policies = if let Some(ctx) = &asset_context {
         crate::core::asset_policy_engine::AssetPolicyEngine::get_applicable_policies(
@@ -495,7 +629,19 @@ pub async fn log_action(
             &temp_context,
         ).await.ok().unwrap_or_default()
     } else {
-        Vec::new()
+        // Policies without asset filters still apply
+        let empty_context = crate::core::asset_policy_engine::AssetContext {
+            asset_id: None,
+            business_function: None,
+            department: None,
+            location: None,
+            risk_profile: None,
+            tags: None,
+        };
+        crate::core::asset_policy_engine::AssetPolicyEngine::get_applicable_policies(
+            &data.db_pool,
+            &empty_context,
+        ).await.ok().unwrap_or_default()
     };
 
     // C. RISK ASSESSMENT (EU AI Act Article 9)
@@ -503,7 +649,7 @@ pub async fn log_action(
     let risk_assessment_result = crate::core::risk_assessment::RiskAssessmentService::assess_risk(
         &data.db_pool,
         &req.action,
-        &req.payload,
+        raw_payload.as_deref().unwrap_or(&req.payload),
         req.user_id.as_deref(),
         Some(&req.agent_id),
         is_violation,
@@ -524,28 +670,98 @@ pub async fn log_action(
         }
     };
 
-    // D. PRIVACY BRIDGE (Signicat)
-    let log_hash = crate::core::privacy_bridge::hash_payload(&req.payload);
-    let seal_result = data.signicat.request_seal(&log_hash).await;
-    let seal_id = seal_result.unwrap_or_else(|e| format!("ERROR: {}", e));
+    // C.2. POLICY RULES (declarative rules of the applicable asset policies)
+    let rule_context = crate::core::policy_rules::RuleContext {
+        agent: Some(req.agent_id.clone()),
+        action: Some(req.action.clone()),
+        business_function: business_function.clone(),
+        data_class: crate::core::policy_rules::data_classes(raw_payload.as_deref().unwrap_or(&req.payload)),
+        region: Some(detected_country.clone()).filter(|c| !c.is_empty()),
+        vendor: req.vendor_domain.clone(),
+        consent: Some(consent_state.to_string()),
+        risk_level: Some(risk_level.clone()),
+        tenant: tenant.clone(),
+    };
+    let rule_verdict = crate::core::policy_rules::RuleSet::evaluate(
+        &crate::core::asset_policy_engine::AssetPolicyEngine::rule_sets(&asset_policies),
+        &rule_context,
+    );
+    let policy_denied = rule_verdict.denied();
+    if policy_denied && !is_violation {
+        log::warn!("POLICY RULES: Deny {} -> {} ({})", req.agent_id, req.action, rule_verdict.reason().unwrap_or_default());
+    }
+    // A REDACT rule tokenizes personal data even with PII_REDACTION_ENABLED=false
+    if rule_verdict.redact && raw_payload.is_none() {
+        req.payload = pii_vault.redact(&req.payload);
+    }
+    let status = if policy_denied && !is_violation { "BLOCKED (POLICY)" } else { status };
+    let is_blocked = is_violation || policy_denied;
 
-    // E. CRYPTO-SHREDDER
-    let encrypted_log = data.key_store.log_event(&req.payload);
+    // D. CRYPTO-SHREDDER (per-subject KEK when enabled, so one erasure covers all records)
+    let encrypted_log_result = match &req.user_id {
+        Some(user_id) if data.key_store.subject_keys_enabled() => {
+            data.key_store.log_event_for_subject(&data.db_pool, user_id, &req.payload).await
+        }
+        _ => data.key_store.log_event(&req.payload).await,
+    };
+    let encrypted_log = match encrypted_log_result {
+        Ok(log) => log,
+        Err(e) => {
+            let request_id = generate_request_id();
+            log::error!("Error in wrapping DEK: {} (Request ID: {})", e, request_id);
+            return create_error_response(&request_id);
+        }
+    };
     
-    // Store wrapped DEK in database for persistence
-    if let Some(wrapped_dek) = data.key_store.get_wrapped_dek(&encrypted_log.log_id) {
-        let _ = sqlx::query(
-            "INSERT INTO encrypted_log_keys (log_id, wrapped_dek) VALUES ($1, $2)
-             ON CONFLICT (log_id) DO NOTHING"
-        )
-        .bind(&encrypted_log.log_id)
-        .bind(&wrapped_dek)
-        .execute(&data.db_pool)
-        .await;
+    // Token vault of the redacted payload, linked to the record so that
+    // shredding the record erases the original values too. Stored before
+    // anything of the record, so a failure here leaves no record behind
+    if !pii_vault.is_empty() {
+        if let Err(e) = pii_vault.store(
+            &data.key_store,
+            &data.db_pool,
+            "LOG_ACTION",
+            &req.agent_id,
+            req.user_id.as_deref(),
+            Some(&encrypted_log.log_id),
+        ).await {
+            let request_id = generate_request_id();
+            log::error!("Error in storing PII token vault: {} (Request ID: {})", e, request_id);
+            return create_error_response(&request_id);
+        }
+    }
+
+    // Store wrapped DEK in database for persistence (survives restarts, shared across nodes)
+    if let Err(e) = data.key_store.persist_key(&data.db_pool, &encrypted_log.log_id).await {
+        log_error_safely("persisting wrapped DEK", &e, &generate_request_id());
+    }
+
+    // Store ciphertext + nonce so the payload can be read back until the key is shredded
+    if let Err(e) = sqlx::query(
+        "INSERT INTO encrypted_log_payloads (log_id, ciphertext, nonce) VALUES ($1, $2, $3)
+         ON CONFLICT (log_id) DO NOTHING"
+    )
+    .bind(&encrypted_log.log_id)
+    .bind(&encrypted_log.ciphertext)
+    .bind(&encrypted_log.nonce)
+    .execute(&data.db_pool)
+    .await
+    {
+        log_error_safely("storing encrypted payload", &e, &generate_request_id());
     }
 
+    // E. PRIVACY BRIDGE (Signicat)
+    // In ANCHOR seal mode the record is sealed later through a Merkle root.
+    // If the provider is unreachable the hash goes to the seal outbox.
+    let log_hash = crate::core::privacy_bridge::hash_payload(&req.payload);
+    let seal_id = if crate::core::merkle_anchor::per_record_sealing() {
+        data.seals.seal_or_defer(&data.db_pool, req.company_id, &log_hash).await
+    } else {
+        format!("ANCHORED_{}", Uuid::new_v4())
+    };
+
     // F. HUMAN OVERSIGHT (EU AI Act Article 14)
-    let human_oversight_status = if req.requires_human_oversight.unwrap_or(false) {
+    let human_oversight_status = if req.requires_human_oversight.unwrap_or(false) || rule_verdict.require_oversight {
         // Insert into database
         let _ = sqlx::query(
             "INSERT INTO human_oversight (seal_id, status) VALUES ($1, 'PENDING')
@@ -752,7 +968,7 @@ pub async fn log_action(
                 .map(|ctx| ctx.trend == "INCREASING")
                 .unwrap_or(false));
     
-    if should_notify && req.user_id.is_some() && !is_violation {
+    if should_notify && req.user_id.is_some() && !is_blocked {
         let notification_service = data.notification_service.clone();
         let db_pool = data.db_pool.clone();
         let user_id = req.user_id.clone().unwrap();
@@ -800,23 +1016,26 @@ pub async fn log_action(
         "status": status,
         "risk_level": risk_level,
         "human_oversight_status": human_oversight_status,
-        "is_violation": is_violation,
+        "is_violation": is_blocked,
+        "jurisdiction": jurisdiction_decision,
+        "policy_rules": rule_verdict,
         "timestamp": now.to_rfc3339(),
     });
     trigger_webhook_event(&data.db_pool, "compliance.action", webhook_data).await;
 
     // In shadow mode, never block - always return OK but mark status appropriately
-    if is_violation && !is_shadow_mode {
+    if is_blocked && !is_shadow_mode {
         HttpResponse::Forbidden().json(LogResponse {
             status: status.to_string(),
             seal_id: "N/A (Connection Refused)".to_string(),
             tx_id: "0000".to_string(),
             risk_level: Some(risk_level),
             human_oversight_status,
+            pii_redacted: (!pii_vault.is_empty()).then_some(pii_vault.len()),
         })
     } else {
         // Shadow mode: return OK even if violation (already logged to shadow_mode_logs)
-        let final_status = if is_shadow_mode && is_violation {
+        let final_status = if is_shadow_mode && is_blocked {
             format!("SHADOW_MODE: {}", status)
         } else {
             status.to_string()
@@ -824,7 +1043,7 @@ pub async fn log_action(
         
         HttpResponse::Ok().json(LogResponse {
             status: final_status,
-            seal_id: if is_shadow_mode && is_violation { 
+            seal_id: if is_shadow_mode && is_blocked { 
                 format!("SHADOW-{}", seal_id) 
             } else { 
                 seal_id 
@@ -832,6 +1051,7 @@ pub async fn log_action(
             tx_id: encrypted_log.log_id,
             risk_level: Some(risk_level),
             human_oversight_status,
+            pii_redacted: (!pii_vault.is_empty()).then_some(pii_vault.len()),
         })
     }
 }
@@ -952,63 +1172,18 @@ pub async fn get_logs(
     let seal_id = query.get("seal_id");
     let agent_id = query.get("agent_id");
 
-    // Build dynamic queries based on filters
-    let (total_count, records_result) = if let Some(sid) = seal_id {
-        // Filter by seal_id
-        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM compliance_records WHERE seal_id = $1")
-            .bind(sid)
-            .fetch_one(&data.db_pool)
-            .await
-            .unwrap_or(0);
-        
-        let records = sqlx::query_as::<_, ComplianceRecordDb>(
-            "SELECT * FROM compliance_records WHERE seal_id = $1 ORDER BY timestamp DESC LIMIT $2 OFFSET $3"
-        )
-        .bind(sid)
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(&data.db_pool)
-        .await;
-        
-        (count, records)
-    } else if let Some(aid) = agent_id {
-        // Filter by agent_id
-        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM compliance_records WHERE agent_id = $1")
-            .bind(aid)
-            .fetch_one(&data.db_pool)
-            .await
-            .unwrap_or(0);
-        
-        let records = sqlx::query_as::<_, ComplianceRecordDb>(
-            "SELECT * FROM compliance_records WHERE agent_id = $1 ORDER BY timestamp DESC LIMIT $2 OFFSET $3"
-        )
-        .bind(aid)
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(&data.db_pool)
-        .await;
-        
-        (count, records)
-    } else {
-        // No filter - get all records
-        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM compliance_records")
-            .fetch_one(&data.db_pool)
-            .await
-            .unwrap_or(0);
-        
-        let records = sqlx::query_as::<_, ComplianceRecordDb>(
-            "SELECT * FROM compliance_records ORDER BY timestamp DESC LIMIT $1 OFFSET $2"
-        )
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(&data.db_pool)
-        .await;
-        
-        (count, records)
-    };
+    // Current state of each action record: erasures and rectifications are applied
+    let records_result = crate::core::audit_chain::AuditChain::current_records(
+        &data.db_pool,
+        seal_id.map(|s| s.as_str()),
+        agent_id.map(|s| s.as_str()),
+        limit,
+        offset,
+    )
+    .await;
 
     match records_result {
-        Ok(records) => {
+        Ok((total_count, records)) => {
             let compliance_records: Vec<ComplianceRecord> = records.into_iter().map(|r| r.into()).collect();
             HttpResponse::Ok().json(serde_json::json!({
                 "data": compliance_records,
@@ -1032,7 +1207,13 @@ pub async fn get_logs(
 #[utoipa::path(
     post, 
     path = "/shred_data", 
-    request_body = ShredRequest
+    request_body = ShredRequest,
+    responses(
+        (status = 200, description = "Keys destroyed, tombstone appended and erasure certificate issued"),
+        (status = 404, description = "No such record"),
+        (status = 409, description = "Record already erased (ALREADY_ERASED)"),
+        (status = 500, description = "Erasure failed")
+    )
 )]
 pub async fn shred_data(
     req: web::Json<ShredRequest>,
@@ -1040,7 +1221,7 @@ pub async fn shred_data(
     http_req: HttpRequest,
 ) -> impl Responder {
     // AUTHENTICATION & AUTHORIZATION
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "compliance", "delete").await {
+    let claims = match authenticate_and_authorize(&http_req, &data.db_pool, "compliance", "delete").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
@@ -1063,35 +1244,57 @@ pub async fn shred_data(
         }
     };
 
-    // Shred the key in key_store
-    data.key_store.shred_key(&tx_id);
+    // Shredding cannot be undone: refuse a second erasure before destroying anything
+    match crate::core::audit_chain::AuditChain::is_erased(&data.db_pool, &req.seal_id).await {
+        Ok(false) => {}
+        Ok(true) => return already_erased(&req.seal_id),
+        Err(e) => {
+            let request_id = generate_request_id();
+            log_error_safely("checking for an erasure tombstone", &e, &request_id);
+            return create_error_response(&request_id);
+        }
+    }
 
-    // Mark key as shredded in database
-    let _ = sqlx::query(
-        "UPDATE encrypted_log_keys SET shredded_at = CURRENT_TIMESTAMP WHERE log_id = $1"
-    )
-    .bind(&tx_id)
-    .execute(&data.db_pool)
-    .await;
+    // Shred the key in key_store and destroy the persisted wrapped DEK
+    let key_fingerprint = match data.key_store.shred_key_persisted(&data.db_pool, &tx_id).await {
+        Ok(fp) => fp,
+        Err(e) => {
+            let request_id = generate_request_id();
+            log_error_safely("shredding key", &e, &request_id);
+            return create_error_response(&request_id);
+        }
+    };
+
+    // The token vault of a redacted payload holds the original values: shred it too
+    let vault_ids = match crate::core::pii_redaction::TokenVault::ids_for_tx(&data.db_pool, &tx_id).await {
+        Ok(ids) => ids,
+        Err(e) => {
+            let request_id = generate_request_id();
+            log_error_safely("fetching PII token vaults", &e, &request_id);
+            return create_error_response(&request_id);
+        }
+    };
+    for vault_id in vault_ids {
+        if let Err(e) = data.key_store.shred_key_persisted(&data.db_pool, &vault_id).await {
+            let request_id = generate_request_id();
+            log_error_safely("shredding PII token vault", &e, &request_id);
+            return create_error_response(&request_id);
+        }
+    }
 
-    // Update compliance record status
+    // Append an erasure tombstone to the audit chain (the record itself is immutable)
     let erased_summary = "[GDPR PURGED] Data Cryptographically Erased";
     let erased_status = "ERASED (Art. 17)";
-    let result = sqlx::query(
-        "UPDATE compliance_records 
-         SET action_summary = $2,
-             status = $3
-         WHERE seal_id = $1"
-    )
-    .bind(&req.seal_id)
-    .bind(erased_summary)
-    .bind(erased_status)
-    .execute(&data.db_pool)
-    .await;
+    let result = crate::core::audit_chain::AuditChain::append_tombstone(
+        &data.db_pool,
+        &req.seal_id,
+        erased_status,
+        erased_summary,
+    ).await;
 
     match result {
-        Ok(rows) if rows.rows_affected() > 0 => {
-            println!("🗑️ Shredded record: {}", req.seal_id);
+        Ok(Some(tombstone_seal_id)) => {
+            println!("🗑️ Shredded record: {} (tombstone {})", req.seal_id, tombstone_seal_id);
             
             // GDPR Article 19: Notify recipients of erasure
             let notification_service = data.notification_service.clone();
@@ -1168,3216 +1371,2819 @@ pub async fn shred_data(
                 "timestamp": Utc::now().to_rfc3339(),
             });
             trigger_webhook_event(&data.db_pool, "gdpr.erased", webhook_data).await;
-            
-            HttpResponse::Ok().json(serde_json::json!({"status": "SUCCESS"}))
+
+            // Erasure certificate: proof for the data subject or a regulator
+            let mut certificate = crate::core::erasure_certificate::ErasureCertificate::new(
+                "RECORD",
+                None,
+                vec![req.seal_id.clone()],
+                vec![tx_id.clone()],
+                key_fingerprint.into_iter().collect(),
+                &claims.username,
+                Some(claims.sub.clone()),
+            );
+            certificate.seal(&data.seals, &data.db_pool).await;
+            if let Err(e) = certificate.store(&data.db_pool).await {
+                let request_id = generate_request_id();
+                log_error_safely("storing erasure certificate", &e, &request_id);
+                return create_error_response(&request_id);
+            }
+
+            let audit_service = AuditService::new(data.db_pool.clone());
+            let _ = audit_service.log_event(
+                Uuid::parse_str(&claims.sub).ok(),
+                None,
+                "record_erased",
+                Some("compliance"),
+                Some("delete"),
+                http_req.connection_info().peer_addr(),
+                None,
+                true,
+                None,
+                Some(serde_json::json!({
+                    "seal_id": req.seal_id,
+                    "tx_id": tx_id,
+                    "certificate_id": certificate.certificate_id,
+                    "certificate_hash": certificate.certificate_hash
+                })),
+            ).await;
+
+            HttpResponse::Ok().json(serde_json::json!({
+                "status": "SUCCESS",
+                "tombstone_seal_id": tombstone_seal_id,
+                "certificate": certificate
+            }))
+        }
+        // A concurrent request erased the record first
+        Ok(None) => already_erased(&req.seal_id),
+        Err(e) => {
+            // The keys are gone but the erasure is not on the chain yet
+            let request_id = generate_request_id();
+            log_error_safely("appending erasure tombstone", &e, &request_id);
+            create_error_response(&request_id)
         }
-        _ => HttpResponse::NotFound().json(serde_json::json!({"status": "NOT_FOUND"}))
     }
 }
 
-// 4. DOWNLOAD REPORT (Enhanced with format support and extended Annex IV fields)
+fn already_erased(seal_id: &str) -> HttpResponse {
+    HttpResponse::Conflict().json(serde_json::json!({
+        "status": "ALREADY_ERASED",
+        "message": "The record has already been erased",
+        "seal_id": seal_id
+    }))
+}
+
+// 3.1. READ ENCRYPTED PAYLOAD (Crypto-Shredder)
+/// Decrypt a logged payload by its tx_id
+///
+/// Loads the stored ciphertext and decrypts it through the key store. Once the
+/// record has been shredded the DEK is gone and the endpoint returns `410 Gone`
+/// with status `GDPR_PURGED`.
 #[utoipa::path(
     get,
-    path = "/download_report",
+    path = "/payloads/{tx_id}",
     params(
-        ("format" = Option<String>, Query, description = "Export format: pdf, json, xml (default: pdf)"),
-        ("seal_id" = Option<String>, Query, description = "Filter by specific seal_id"),
-        ("report_type" = Option<String>, Query, description = "Report type: annex_iv, dora_register (default: annex_iv)")
+        ("tx_id" = String, Path, description = "Transaction ID returned by /log_action")
     ),
     responses(
-        (status = 200, description = "Report generated successfully"),
-        (status = 500, description = "Internal server error")
+        (status = 200, description = "Payload decrypted"),
+        (status = 404, description = "No encrypted payload stored for this tx_id"),
+        (status = 410, description = "Key destroyed (GDPR_PURGED)")
     ),
-    tag = "Reports"
+    tag = "Compliance"
 )]
-pub async fn download_report(
-    query: web::Query<std::collections::HashMap<String, String>>,
+pub async fn read_payload(
+    path: web::Path<String>,
     data: web::Data<AppState>,
+    http_req: HttpRequest,
 ) -> impl Responder {
-    let format_str = query.get("format").map(|s| s.as_str()).unwrap_or("pdf");
-    let format = crate::core::annex_iv::ExportFormat::from_str(format_str)
-        .unwrap_or(crate::core::annex_iv::ExportFormat::Pdf);
-    
-    let report_type = query.get("report_type").map(|s| s.as_str()).unwrap_or("annex_iv");
-    let seal_id_filter = query.get("seal_id");
-    
-    // Build query with optional seal_id filter
-    let query_str = if seal_id_filter.is_some() {
-        "SELECT cr.* FROM compliance_records cr WHERE cr.seal_id = $1 ORDER BY cr.timestamp DESC"
-    } else {
-        "SELECT cr.* FROM compliance_records cr ORDER BY cr.timestamp DESC LIMIT 1000"
+    // AUTHENTICATION & AUTHORIZATION - decrypting payloads needs its own permission
+    let claims = match authenticate_and_authorize(&http_req, &data.db_pool, "compliance", "decrypt").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
     };
-    
-    let result = if let Some(seal_id) = seal_id_filter {
-        sqlx::query_as::<_, ComplianceRecordDb>(query_str)
-            .bind(seal_id)
-    .fetch_all(&data.db_pool)
+    let tx_id = path.into_inner();
+
+    let stored: Option<(Vec<u8>, Vec<u8>)> = match sqlx::query_as(
+        "SELECT ciphertext, nonce FROM encrypted_log_payloads WHERE log_id = $1"
+    )
+    .bind(&tx_id)
+    .fetch_optional(&data.db_pool)
     .await
-    } else {
-        sqlx::query_as::<_, ComplianceRecordDb>(query_str)
-            .fetch_all(&data.db_pool)
-            .await
+    {
+        Ok(row) => row,
+        Err(e) => {
+            let request_id = generate_request_id();
+            log_error_safely("fetching encrypted payload", &e, &request_id);
+            return create_error_response(&request_id);
+        }
     };
-    
-    match result {
-        Ok(records) => {
-            // Convert to extended ComplianceRecord with Annex IV fields
-            let mut compliance_records: Vec<crate::core::annex_iv::ComplianceRecord> = Vec::new();
-            
-            for r in records {
-                let mut record: crate::core::annex_iv::ComplianceRecord = r.clone().into();
-                
-                // Add extended Annex IV fields
-                // Try to extract lifecycle stage from action_summary or use default
-                record.lifecycle_stage = Some("DEPLOYMENT".to_string()); // Default, can be enhanced
-                
-                // Get risk assessment data for mitigation actions
-                if let Ok(Some(ra)) = sqlx::query_as::<_, RiskAssessmentDb>(
-                    "SELECT * FROM risk_assessments WHERE seal_id = $1 LIMIT 1"
-                )
-                .bind(&r.seal_id)
-                .fetch_optional(&data.db_pool)
-                .await
-                {
-                    // Extract risk management measures from mitigation_actions
-                    if let Ok(measures) = serde_json::from_value::<Vec<String>>(ra.mitigation_actions.clone()) {
-                        record.risk_management_measures = Some(measures);
-                    }
-                }
-                
-                // Get post-market monitoring if available
-                if let Ok(Some(pm)) = sqlx::query_scalar::<_, Option<String>>(
-                    "SELECT monitoring_results FROM post_market_monitoring WHERE seal_id = $1 LIMIT 1"
-                )
-                .bind(&r.seal_id)
-                .fetch_optional(&data.db_pool)
-                .await
-                {
-                    record.post_market_monitoring = pm;
-                }
-                
-                compliance_records.push(record);
-            }
-            
-            // Determine filename, temp_file, and generation function based on report_type
-            let (filename, temp_file, export_result) = match report_type {
-                "dora_register" => {
-                    // DORA Register: Simplified vendor supply chain report (PDF only for now)
-                    let filename = format!("Veridion_DORA_Register_{}.pdf", 
-                        chrono::Utc::now().format("%Y%m%d_%H%M%S")
-                    );
-                    let temp_file = format!("temp_report_{}.pdf", 
-                        uuid::Uuid::new_v4().to_string().replace("-", "")
-                    );
-                    
-                    let result = crate::core::annex_iv::generate_dora_register(&compliance_records, &temp_file);
-                    (filename, temp_file, result)
-                }
-                _ => {
-                    // Default: Annex IV report
-            let filename = format!("Veridion_Annex_IV_{}.{}", 
-                chrono::Utc::now().format("%Y%m%d_%H%M%S"),
-                format.file_extension()
-            );
-            let temp_file = format!("temp_report_{}.{}", 
-                uuid::Uuid::new_v4().to_string().replace("-", ""),
-                format.file_extension()
-            );
-            
-                    let result = match format {
-                crate::core::annex_iv::ExportFormat::Pdf => {
-                    crate::core::annex_iv::generate_report(&compliance_records, &temp_file)
-                }
-                crate::core::annex_iv::ExportFormat::Json => {
-                    crate::core::annex_iv::export_to_json(&compliance_records, &temp_file)
-                }
-                crate::core::annex_iv::ExportFormat::Xml => {
-                    crate::core::annex_iv::export_to_xml(&compliance_records, &temp_file)
-                        }
-                    };
-                    (filename, temp_file, result)
-                }
-            };
-            
-            match export_result {
-                Ok(_) => {
-                    if let Ok(bytes) = fs::read(&temp_file) {
-                        // Clean up temp file
-                        let _ = fs::remove_file(&temp_file);
-                        
-                        // Determine content type based on report type
-                        let content_type = if report_type == "dora_register" {
-                            "application/pdf"
-                        } else {
-                            format.content_type()
-                        };
-                        
-                        HttpResponse::Ok()
-                            .content_type(content_type)
-                            .append_header(ContentDisposition::attachment(&filename))
-                            .body(bytes)
-            } else { 
-                        HttpResponse::InternalServerError().json(serde_json::json!({
-                            "error": "Failed to read generated report"
-                        }))
-                    }
-                }
-                Err(e) => {
-                    let request_id = generate_request_id();
-                    // e is a String, convert to &dyn Error
-                    let error_msg = format!("{}", e);
-                    log::error!("Error generating report: {} (Request ID: {})", error_msg, request_id);
-                    HttpResponse::InternalServerError().json(serde_json::json!({
-                        "error": "Failed to generate report"
-                    }))
-                }
-            }
+
+    let (ciphertext, nonce) = match stored {
+        Some(row) => row,
+        None => return HttpResponse::NotFound().json(serde_json::json!({"status": "NOT_FOUND"})),
+    };
+
+    let encrypted_log = crate::core::crypto_shredder::EncryptedLog {
+        log_id: tx_id.clone(),
+        ciphertext,
+        nonce,
+    };
+
+    let audit_service = AuditService::new(data.db_pool.clone());
+    let user_id = Uuid::parse_str(&claims.sub).ok();
+
+    match data.key_store.read_event_persisted(&data.db_pool, &encrypted_log).await {
+        Ok(payload) => {
+            // Put back values tokenized at log time, unless their vault was shredded
+            let payload = crate::core::pii_redaction::TokenVault::rehydrate_record(
+                &data.key_store, &data.db_pool, &tx_id, &payload,
+            ).await;
+            let _ = audit_service.log_event(
+                user_id,
+                None,
+                "payload_decrypted",
+                Some("encrypted_log_payloads"),
+                Some("decrypt"),
+                http_req.connection_info().peer_addr(),
+                None,
+                true,
+                None,
+                Some(serde_json::json!({ "tx_id": tx_id })),
+            ).await;
+            HttpResponse::Ok().json(serde_json::json!({
+                "status": "DECRYPTED",
+                "tx_id": tx_id,
+                "payload": payload
+            }))
+        }
+        Err(e) if e.starts_with("GDPR_PURGED") => {
+            let _ = audit_service.log_event(
+                user_id,
+                None,
+                "payload_decrypted",
+                Some("encrypted_log_payloads"),
+                Some("decrypt"),
+                http_req.connection_info().peer_addr(),
+                None,
+                false,
+                Some(&e),
+                Some(serde_json::json!({ "tx_id": tx_id })),
+            ).await;
+            HttpResponse::Gone().json(serde_json::json!({
+                "status": "GDPR_PURGED",
+                "tx_id": tx_id,
+                "message": e
+            }))
         }
         Err(e) => {
             let request_id = generate_request_id();
-            log_error_safely("fetching records for report", &e, &request_id);
+            log::error!("Error in decrypting payload: {} (Request ID: {})", e, request_id);
             create_error_response(&request_id)
         }
     }
 }
 
-// 5. REVOKE ACCESS
-#[derive(Deserialize, ToSchema)]
-pub struct RevokeAccessRequest {
-    /// Optional agent_id to revoke. If not provided, triggers global system lockdown.
-    #[schema(example = "agent-001")]
-    pub agent_id: Option<String>,
-}
-
+// 3.2. MASTER KEY ROTATION (Crypto-Shredder)
+/// List master key versions
+///
+/// Shows every known master key version with the number of live DEKs and
+/// data subject keys still wrapped under it. A version can be retired once
+/// both counts reach zero.
 #[utoipa::path(
-    post,
-    path = "/revoke_access",
-    request_body = RevokeAccessRequest,
+    get,
+    path = "/keys/master",
     responses(
-        (status = 200, description = "Access revoked successfully"),
+        (status = 200, description = "Master key versions"),
         (status = 500, description = "Internal server error")
     ),
-    tag = "System Management"
+    tag = "Compliance"
 )]
-pub async fn revoke_access(
-    req: Option<web::Json<RevokeAccessRequest>>,
+pub async fn list_master_keys(
     data: web::Data<AppState>,
+    http_req: HttpRequest,
 ) -> impl Responder {
-    // Check if agent_id is provided in request body
-    if let Some(body) = req {
-        if let Some(agent_id) = &body.agent_id {
-            // Granular revocation: revoke specific agent
-            data.revoke_agent(agent_id);
-            return HttpResponse::Ok().json(serde_json::json!({
-                "status": "SUCCESS",
-                "message": format!("Agent '{}' access revoked", agent_id),
-                "agent_id": agent_id
-            }));
-        }
-    }
+    // AUTHENTICATION & AUTHORIZATION
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "keys", "read").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
 
-    // No agent_id provided: trigger global system lockdown (panic button)
-    match data.set_locked_down(true).await {
-        Ok(_) => HttpResponse::Ok().json(serde_json::json!({
-            "status": "SUCCESS",
-            "message": "Global system lockdown activated"
-        })),
+    let rows = sqlx::query(
+        "SELECT v.key_id, v.kdf, v.status, v.created_at, v.last_seen_at, v.retired_at,
+                (SELECT COUNT(*) FROM encrypted_log_keys k
+                 WHERE k.master_key_id = v.key_id AND k.shredded_at IS NULL) AS live_deks,
+                (SELECT COUNT(*) FROM data_subject_keys s
+                 WHERE s.master_key_id = v.key_id AND s.destroyed_at IS NULL) AS live_subject_keys
+         FROM master_key_versions v
+         ORDER BY v.created_at ASC"
+    )
+    .fetch_all(&data.db_pool)
+    .await;
+
+    match rows {
+        Ok(rows) => {
+            let configured = data.key_store.master_key_ids();
+            let versions: Vec<serde_json::Value> = rows
+                .iter()
+                .map(|row| {
+                    let key_id: String = row.get("key_id");
+                    serde_json::json!({
+                        "key_id": key_id,
+                        "kdf": row.get::<String, _>("kdf"),
+                        "status": row.get::<String, _>("status"),
+                        "live_deks": row.get::<i64, _>("live_deks"),
+                        "live_subject_keys": row.get::<i64, _>("live_subject_keys"),
+                        "configured": configured.contains(&key_id),
+                        "current": key_id == data.key_store.current_master_key_id(),
+                        "created_at": row.get::<chrono::NaiveDateTime, _>("created_at").and_utc().to_rfc3339(),
+                        "last_seen_at": row.get::<Option<chrono::NaiveDateTime>, _>("last_seen_at").map(|t| t.and_utc().to_rfc3339()),
+                        "retired_at": row.get::<Option<chrono::NaiveDateTime>, _>("retired_at").map(|t| t.and_utc().to_rfc3339()),
+                    })
+                })
+                .collect();
+
+            HttpResponse::Ok().json(serde_json::json!({
+                "current_key_id": data.key_store.current_master_key_id(),
+                "versions": versions
+            }))
+        }
         Err(e) => {
             let request_id = generate_request_id();
-            log_error_safely("setting lockdown", &e, &request_id);
-            HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to set lockdown"
-            }))
+            log_error_safely("fetching master key versions", &e, &request_id);
+            create_error_response(&request_id)
         }
     }
 }
 
-// ========== PRIORITY 1: DATA SUBJECT RIGHTS (GDPR Articles 15-22) ==========
-
-// 6. DATA SUBJECT ACCESS (GDPR Article 15)
+/// Re-wrap DEKs under the current master key
+///
+/// Runs the re-wrap job immediately instead of waiting for the background
+/// worker. Ciphertext is not touched and shredded keys stay shredded.
 #[utoipa::path(
-    get,
-    path = "/data_subject/{user_id}/access",
-    responses((status = 200, body = DataSubjectAccessResponse))
+    post,
+    path = "/keys/master/rewrap",
+    responses(
+        (status = 200, description = "Re-wrap finished"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Compliance"
 )]
-pub async fn data_subject_access(
-    path: web::Path<String>,
+pub async fn rewrap_master_keys(
     data: web::Data<AppState>,
     http_req: HttpRequest,
 ) -> impl Responder {
-    // AUTHENTICATION & AUTHORIZATION - Critical: data_subject.read
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "data_subject", "read").await {
+    // AUTHENTICATION & AUTHORIZATION
+    let claims = match authenticate_and_authorize(&http_req, &data.db_pool, "keys", "write").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
 
-    let user_id = path.into_inner();
-    
-    // Get all records for this user from database
-    match sqlx::query_as::<_, ComplianceRecordDb>(
-        "SELECT cr.* FROM compliance_records cr
-         INNER JOIN user_data_index udi ON cr.seal_id = udi.seal_id
-         WHERE udi.user_id = $1
-         ORDER BY cr.timestamp DESC"
-    )
-    .bind(&user_id)
-    .fetch_all(&data.db_pool)
-    .await
-    {
-        Ok(records) => {
-            let user_records: Vec<DataSubjectRecord> = records.into_iter().map(|r| {
-                DataSubjectRecord {
-                    timestamp: r.timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
-                    action_summary: r.action_summary,
-                    seal_id: r.seal_id,
-                    status: r.status,
-                    risk_level: r.risk_level,
-                }
-            }).collect();
-            
-            let response = DataSubjectAccessResponse {
-                records: user_records,
-                format: "json".to_string(),
-                exported_at: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
-            };
-            
-            HttpResponse::Ok().json(response)
+    match data.key_store.rewrap_all(&data.db_pool, 500).await {
+        Ok(report) => {
+            let audit_service = AuditService::new(data.db_pool.clone());
+            let _ = audit_service.log_event(
+                Uuid::parse_str(&claims.sub).ok(),
+                None,
+                "master_key_rewrap",
+                Some("encrypted_log_keys"),
+                Some("rewrap"),
+                http_req.connection_info().peer_addr(),
+                None,
+                report.failed == 0,
+                None,
+                Some(serde_json::json!({
+                    "current_key_id": data.key_store.current_master_key_id(),
+                    "rewrapped": report.rewrapped,
+                    "failed": report.failed
+                })),
+            ).await;
+
+            HttpResponse::Ok().json(serde_json::json!({
+                "status": "REWRAPPED",
+                "current_key_id": data.key_store.current_master_key_id(),
+                "rewrapped": report.rewrapped,
+                "failed": report.failed
+            }))
         }
         Err(e) => {
             let request_id = generate_request_id();
-            let request_id = generate_request_id();
-            log_error_safely("fetching user data", &e, &request_id);
-            HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to fetch user data"
-            }))
+            log_error_safely("re-wrapping DEKs", &e, &request_id);
+            create_error_response(&request_id)
         }
     }
 }
 
-// 7. DATA SUBJECT EXPORT (GDPR Article 20 - Data Portability)
+/// Retire a master key version
+///
+/// Only allowed for versions other than the current one and once no live DEK
+/// is wrapped under them. After retirement the version is never used again,
+/// even if it is still configured on a node.
 #[utoipa::path(
-    get,
-    path = "/data_subject/{user_id}/export",
-    responses((status = 200, body = DataSubjectAccessResponse))
+    post,
+    path = "/keys/master/{key_id}/retire",
+    params(
+        ("key_id" = String, Path, description = "Master key version to retire")
+    ),
+    responses(
+        (status = 200, description = "Master key version retired"),
+        (status = 404, description = "Unknown master key version"),
+        (status = 409, description = "Version is current or still wraps live DEKs")
+    ),
+    tag = "Compliance"
 )]
-pub async fn data_subject_export(
+pub async fn retire_master_key(
     path: web::Path<String>,
     data: web::Data<AppState>,
     http_req: HttpRequest,
 ) -> impl Responder {
-    // AUTHENTICATION & AUTHORIZATION - Critical: data_subject.export
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "data_subject", "export").await {
+    // AUTHENTICATION & AUTHORIZATION
+    let claims = match authenticate_and_authorize(&http_req, &data.db_pool, "keys", "write").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
+    let key_id = path.into_inner();
 
-    // Same as access, but could be extended with different format options
-    // Reuse the access logic - we already have auth, so just call it
-    // Note: We need to clone path since it's moved in the call
-    let user_id = path.into_inner();
-    
-    // Get all records for this user from database
-    match sqlx::query_as::<_, ComplianceRecordDb>(
-        "SELECT cr.* FROM compliance_records cr
-         INNER JOIN user_data_index udi ON cr.seal_id = udi.seal_id
-         WHERE udi.user_id = $1
-         ORDER BY cr.timestamp DESC"
+    if key_id == data.key_store.current_master_key_id() {
+        return HttpResponse::Conflict().json(serde_json::json!({
+            "error": "Cannot retire the current master key version",
+            "key_id": key_id
+        }));
+    }
+
+    let status: Option<String> = match sqlx::query_scalar(
+        "SELECT status FROM master_key_versions WHERE key_id = $1"
     )
-    .bind(&user_id)
-    .fetch_all(&data.db_pool)
+    .bind(&key_id)
+    .fetch_optional(&data.db_pool)
     .await
     {
-        Ok(records) => {
-            let user_records: Vec<DataSubjectRecord> = records.into_iter().map(|r| {
-                DataSubjectRecord {
-                    timestamp: r.timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
-                    action_summary: r.action_summary,
-                    seal_id: r.seal_id,
-                    status: r.status,
-                    risk_level: r.risk_level,
-                }
-            }).collect();
-            
-            let response = DataSubjectAccessResponse {
-                records: user_records,
-                format: "json".to_string(),
-                exported_at: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
-            };
-            
-            HttpResponse::Ok().json(response)
-        }
+        Ok(s) => s,
         Err(e) => {
             let request_id = generate_request_id();
-            let request_id = generate_request_id();
-            log_error_safely("fetching user data", &e, &request_id);
-            HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to fetch user data"
+            log_error_safely("fetching master key version", &e, &request_id);
+            return create_error_response(&request_id);
+        }
+    };
+    match status.as_deref() {
+        None => return HttpResponse::NotFound().json(serde_json::json!({"status": "NOT_FOUND"})),
+        Some("RETIRED") => {
+            return HttpResponse::Ok().json(serde_json::json!({
+                "status": "RETIRED",
+                "key_id": key_id
             }))
         }
+        Some(_) => {}
     }
-}
 
-// 8. DATA SUBJECT RECTIFICATION (GDPR Article 16)
-#[utoipa::path(
-    put,
-    path = "/data_subject/{user_id}/rectify",
-    request_body = DataSubjectRectificationRequest,
-    responses((status = 200, body = serde_json::Value))
-)]
-pub async fn data_subject_rectify(
-    path: web::Path<String>,
-    req: web::Json<DataSubjectRectificationRequest>,
-    data: web::Data<AppState>,
-    http_req: HttpRequest,
-) -> impl Responder {
-    // AUTHENTICATION & AUTHORIZATION - Critical: data_subject.rectify
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "data_subject", "rectify").await {
+    let live_deks: i64 = match sqlx::query_scalar(
+        "SELECT (SELECT COUNT(*) FROM encrypted_log_keys WHERE master_key_id = $1 AND shredded_at IS NULL)
+              + (SELECT COUNT(*) FROM data_subject_keys WHERE master_key_id = $1 AND destroyed_at IS NULL)"
+    )
+    .bind(&key_id)
+    .fetch_one(&data.db_pool)
+    .await
+    {
         Ok(c) => c,
-        Err(resp) => return resp,
+        Err(e) => {
+            let request_id = generate_request_id();
+            log_error_safely("counting live DEKs", &e, &request_id);
+            return create_error_response(&request_id);
+        }
     };
-    let user_id = path.into_inner();
-    
-    if user_id != req.user_id {
-        return HttpResponse::BadRequest().json(serde_json::json!({
-            "error": "User ID does not match"
+    if live_deks > 0 {
+        return HttpResponse::Conflict().json(serde_json::json!({
+            "error": "Master key version still wraps live keys; run the re-wrap job first",
+            "key_id": key_id,
+            "live_deks": live_deks
         }));
     }
-    
-    // Update record in database
-    let status_text = "RECTIFIED (Art. 16)";
-    let result = sqlx::query(
-        "UPDATE compliance_records 
-         SET action_summary = $1, status = $4
-         WHERE seal_id = $2 AND user_id = $3"
+
+    let user_id = Uuid::parse_str(&claims.sub).ok();
+    if let Err(e) = sqlx::query(
+        "UPDATE master_key_versions
+         SET status = 'RETIRED', retired_at = CURRENT_TIMESTAMP, retired_by = $2
+         WHERE key_id = $1"
     )
-    .bind(format!("[RECTIFIED] {}", req.corrected_data))
-    .bind(&req.seal_id)
-    .bind(&user_id)
-    .bind(status_text)
+    .bind(&key_id)
+    .bind(user_id)
     .execute(&data.db_pool)
-    .await;
-
-    match result {
-        Ok(rows) if rows.rows_affected() > 0 => {
-            println!("📝 Rectified record: {} for user: {}", req.seal_id, user_id);
-            
-            // GDPR Article 19: Notify recipients of rectification
-            let notification_service = data.notification_service.clone();
-            let db_pool = data.db_pool.clone();
-            let user_id_clone = user_id.clone();
-            let seal_id_clone = req.seal_id.clone();
-            let corrected_data_clone = req.corrected_data.clone();
-            let now = Utc::now();
-            
-            // Spawn async task to send notifications to recipients (non-blocking)
-            tokio::spawn(async move {
-                // Get all recipients who received this user's data
-                let recipients: Vec<(String, String)> = sqlx::query_as(
-                    "SELECT recipient_name, recipient_contact 
-                     FROM data_recipients 
-                     WHERE user_id = $1 AND seal_id = $2"
-                )
-                .bind(&user_id_clone)
-                .bind(&seal_id_clone)
-                .fetch_all(&db_pool)
-                .await
-                .unwrap_or_default();
-                
-                // Send notification to user
-                let _ = notification_service.send_notification(
-                    &db_pool,
-                    crate::integration::notifications::NotificationRequest {
-                        user_id: user_id_clone.clone(),
-                        notification_type: crate::integration::notifications::NotificationType::RectificationDone,
-                        channel: crate::integration::notifications::NotificationChannel::Email,
-                        subject: Some("Data Rectification Notification".to_string()),
-                        body: format!(
-                            "Dear User,\n\nYour personal data has been rectified as requested.\n\nSeal ID: {}\nCorrected Data: {}\n\nAs required by GDPR Article 19, we have notified all recipients of your personal data about this rectification.\n\nBest regards,\nVeridion Nexus Compliance Team",
-                            seal_id_clone, corrected_data_clone
-                        ),
-                        language: Some("en".to_string()),
-                        related_entity_type: Some("COMPLIANCE_RECORD".to_string()),
-                        related_entity_id: Some(seal_id_clone.clone()),
-                    }
-                ).await;
-                
-                // Log notification to recipients (GDPR Article 19 requirement)
-                for (name, contact) in recipients {
-                    let _ = sqlx::query(
-                        "INSERT INTO user_notifications (
-                            id, notification_id, user_id, notification_type, channel,
-                            subject, body, status, language, related_entity_type, related_entity_id
-                        ) VALUES (gen_random_uuid(), $1, $2, 'RECTIFICATION_NOTIFICATION', 'EMAIL', 
-                                  $3, $4, 'PENDING', 'en', 'RECIPIENT', $5)"
-                    )
-                    .bind(format!("NOTIF-RECIP-{}", uuid::Uuid::new_v4().to_string().replace("-", "").chars().take(12).collect::<String>()))
-                    .bind(&user_id_clone)
-                    .bind(Some(format!("Data Rectification - {}", name)))
-                    .bind(format!("Data subject {} has requested rectification of their personal data. Please update your records accordingly.", user_id_clone))
-                    .bind(Some(contact))
-                    .execute(&db_pool)
-                    .await;
-                }
-            });
-            
-            HttpResponse::Ok().json(serde_json::json!({
-                "status": "SUCCESS",
-                "message": "Data rectified successfully. Recipients will be notified as per GDPR Article 19."
-            }))
-        }
-        _ => HttpResponse::NotFound().json(serde_json::json!({
-            "error": "Record not found or access denied"
-        }))
+    .await
+    {
+        let request_id = generate_request_id();
+        log_error_safely("retiring master key version", &e, &request_id);
+        return create_error_response(&request_id);
     }
-}
+    data.key_store.retire_master_key(&key_id);
+
+    let audit_service = AuditService::new(data.db_pool.clone());
+    let _ = audit_service.log_event(
+        user_id,
+        None,
+        "master_key_retired",
+        Some("master_key_versions"),
+        Some("retire"),
+        http_req.connection_info().peer_addr(),
+        None,
+        true,
+        None,
+        Some(serde_json::json!({ "key_id": key_id })),
+    ).await;
 
-// ========== GDPR Article 18: Right to Restriction of Processing ==========
+    HttpResponse::Ok().json(serde_json::json!({
+        "status": "RETIRED",
+        "key_id": key_id
+    }))
+}
 
-// 8.1. REQUEST PROCESSING RESTRICTION (GDPR Article 18)
+// 3.3. DATA SUBJECT ERASURE (Crypto-Shredder, GDPR Article 17)
+/// Erase all records of a data subject
+///
+/// Destroys the subject's key (KEK) and every DEK of records indexed for the
+/// subject, so all of their logged payloads become unreadable in one call.
+/// Returns an erasure certificate sealed through the Privacy Bridge.
 #[utoipa::path(
     post,
-    path = "/data_subject/{user_id}/restrict",
-    request_body = ProcessingRestrictionRequest,
-    responses((status = 200, body = ProcessingRestrictionResponse))
+    path = "/data_subject/{user_id}/erase",
+    params(
+        ("user_id" = String, Path, description = "Data subject identifier")
+    ),
+    responses(
+        (status = 200, description = "Data subject erased, certificate returned"),
+        (status = 404, description = "No keys or records found for this data subject"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Compliance"
 )]
-pub async fn request_processing_restriction(
+pub async fn data_subject_erase(
     path: web::Path<String>,
-    req: web::Json<ProcessingRestrictionRequest>,
     data: web::Data<AppState>,
     http_req: HttpRequest,
 ) -> impl Responder {
-    // AUTHENTICATION & AUTHORIZATION - Critical: data_subject.write
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "data_subject", "write").await {
+    // AUTHENTICATION & AUTHORIZATION - Critical: data_subject.erase
+    let claims = match authenticate_and_authorize(&http_req, &data.db_pool, "data_subject", "erase").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
-
     let user_id = path.into_inner();
-    if user_id != req.user_id {
-        return HttpResponse::BadRequest().json(serde_json::json!({
-            "error": "User ID does not match"
-        }));
-    }
 
-    // Check if there's already an active restriction
-    let existing: Option<(String,)> = sqlx::query_as(
-        "SELECT restriction_id FROM processing_restrictions 
-         WHERE user_id = $1 AND status = 'ACTIVE'"
+    let erasure = match data.key_store.erase_subject(&data.db_pool, &user_id).await {
+        Ok(e) => e,
+        Err(e) => {
+            let request_id = generate_request_id();
+            log_error_safely("erasing data subject keys", &e, &request_id);
+            return create_error_response(&request_id);
+        }
+    };
+
+    let seal_ids: Vec<String> = match sqlx::query_scalar(
+        "SELECT seal_id FROM user_data_index WHERE user_id = $1 ORDER BY seal_id"
     )
     .bind(&user_id)
-    .fetch_optional(&data.db_pool)
+    .fetch_all(&data.db_pool)
     .await
-    .unwrap_or(None);
+    {
+        Ok(ids) => ids,
+        Err(e) => {
+            let request_id = generate_request_id();
+            log_error_safely("fetching data subject records", &e, &request_id);
+            return create_error_response(&request_id);
+        }
+    };
 
-    if existing.is_some() {
-        return HttpResponse::Conflict().json(serde_json::json!({
-            "error": "User already has an active processing restriction",
-            "restriction_id": existing.unwrap().0
-        }));
+    if erasure.subject_key_fingerprint.is_none() && erasure.shredded_keys.is_empty() && seal_ids.is_empty() {
+        return HttpResponse::NotFound().json(serde_json::json!({"status": "NOT_FOUND"}));
     }
 
-    let restriction_id = format!("RESTRICT-{}", Local::now().format("%Y%m%d-%H%M%S"));
-    let now = Utc::now();
-    
-    // Parse expires_at if provided
-    let expires_at = req.expires_at.as_ref()
-        .and_then(|s| chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").ok())
-        .map(|dt| DateTime::<Utc>::from_naive_utc_and_offset(dt, Utc));
+    // Append erasure tombstones to the audit chain (same marking as shred_data)
+    for seal_id in &seal_ids {
+        if let Err(e) = crate::core::audit_chain::AuditChain::append_tombstone(
+            &data.db_pool,
+            seal_id,
+            "ERASED (Art. 17)",
+            "[GDPR PURGED] Data Cryptographically Erased",
+        ).await {
+            let request_id = generate_request_id();
+            log_error_safely("appending erasure tombstones", &e, &request_id);
+            return create_error_response(&request_id);
+        }
+    }
 
-    let restricted_actions_json = serde_json::to_value(req.restricted_actions.as_ref().unwrap_or(&vec![]))
-        .unwrap_or(serde_json::json!([]));
+    let mut key_fingerprints: Vec<String> = erasure.subject_key_fingerprint.iter().cloned().collect();
+    key_fingerprints.extend(erasure.shredded_keys.iter().map(|(_, fp)| fp.clone()));
+    let tx_ids: Vec<String> = erasure.shredded_keys.iter().map(|(tx_id, _)| tx_id.clone()).collect();
+
+    let mut certificate = crate::core::erasure_certificate::ErasureCertificate::new(
+        "DATA_SUBJECT",
+        Some(user_id.clone()),
+        seal_ids,
+        tx_ids,
+        key_fingerprints,
+        &claims.username,
+        Some(claims.sub.clone()),
+    );
+    certificate.seal(&data.seals, &data.db_pool).await;
+    if let Err(e) = certificate.store(&data.db_pool).await {
+        let request_id = generate_request_id();
+        log_error_safely("storing erasure certificate", &e, &request_id);
+        return create_error_response(&request_id);
+    }
 
-    let result = sqlx::query(
-        "INSERT INTO processing_restrictions (
-            restriction_id, user_id, restriction_type, restricted_actions,
-            reason, status, requested_at, expires_at
-        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)"
-    )
-    .bind(&restriction_id)
-    .bind(&user_id)
-    .bind(&req.restriction_type)
-    .bind(restricted_actions_json)
-    .bind(&req.reason)
-    .bind("ACTIVE")
-    .bind(now)
-    .bind(expires_at)
-    .execute(&data.db_pool)
-    .await;
+    let audit_service = AuditService::new(data.db_pool.clone());
+    let _ = audit_service.log_event(
+        Uuid::parse_str(&claims.sub).ok(),
+        None,
+        "data_subject_erased",
+        Some("data_subject"),
+        Some("erase"),
+        http_req.connection_info().peer_addr(),
+        None,
+        true,
+        None,
+        Some(serde_json::json!({
+            "user_id": user_id,
+            "certificate_id": certificate.certificate_id,
+            "certificate_hash": certificate.certificate_hash,
+            "records_erased": certificate.seal_ids.len(),
+            "keys_destroyed": certificate.key_fingerprints.len()
+        })),
+    ).await;
 
-    match result {
-        Ok(_) => {
-            let response = ProcessingRestrictionResponse {
-                restriction_id: restriction_id.clone(),
-                user_id: user_id.clone(),
-                restriction_type: req.restriction_type.clone(),
-                status: "ACTIVE".to_string(),
-                requested_at: now.format("%Y-%m-%d %H:%M:%S").to_string(),
-                expires_at: expires_at.map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
-            };
+    println!("🗑️ Erased data subject: {} ({} records)", user_id, certificate.seal_ids.len());
 
-            println!("🔒 Processing restriction requested: {} | User: {} | Type: {}", 
-                restriction_id, user_id, req.restriction_type);
+    HttpResponse::Ok().json(serde_json::json!({
+        "status": "ERASED",
+        "user_id": user_id,
+        "records_erased": certificate.seal_ids.len(),
+        "certificate": certificate
+    }))
+}
 
-            HttpResponse::Ok().json(response)
-        }
+// 3.4. ERASURE CERTIFICATE VERIFICATION (Crypto-Shredder)
+/// Verify an erasure certificate
+///
+/// Accepts a certificate as returned by `/shred_data` or
+/// `/data_subject/{user_id}/erase` and checks it against the stored copy, the
+/// security audit trail and the current state of the destroyed keys.
+#[utoipa::path(
+    post,
+    path = "/erasure_certificates/verify",
+    responses(
+        (status = 200, description = "Verification result; `valid` is true when every check passed"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Compliance"
+)]
+pub async fn verify_erasure_certificate(
+    req: web::Json<crate::core::erasure_certificate::ErasureCertificate>,
+    data: web::Data<AppState>,
+    http_req: HttpRequest,
+) -> impl Responder {
+    // AUTHENTICATION & AUTHORIZATION
+    if let Err(resp) = authenticate_and_authorize(&http_req, &data.db_pool, "compliance", "read").await {
+        return resp;
+    }
+
+    match req.verify(&data.db_pool, &data.seals).await {
+        Ok(verification) => HttpResponse::Ok().json(verification),
         Err(e) => {
             let request_id = generate_request_id();
-            let request_id = generate_request_id();
-            log_error_safely("creating restriction", &e, &request_id);
-            HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to create processing restriction"
-            }))
+            log_error_safely("verifying erasure certificate", &e, &request_id);
+            create_error_response(&request_id)
         }
     }
 }
 
-// 8.2. LIFT PROCESSING RESTRICTION (GDPR Article 18)
+// 3.5. AUDIT CHAIN VERIFICATION
+/// Verify the compliance record hash chain
+///
+/// Recomputes every record hash chained between `from` and `to` and checks
+/// each record against its predecessor. Reports gaps in the sequence, broken
+/// links, modified records and records that were never chained.
 #[utoipa::path(
-    post,
-    path = "/data_subject/{user_id}/lift_restriction",
-    request_body = LiftRestrictionRequest,
-    responses((status = 200, body = serde_json::Value))
+    get,
+    path = "/verify_chain",
+    params(
+        ("from" = Option<String>, Query, description = "Start of the window (RFC 3339); defaults to the start of the chain"),
+        ("to" = Option<String>, Query, description = "End of the window (RFC 3339); defaults to the head of the chain")
+    ),
+    responses(
+        (status = 200, description = "Verification result; `valid` is true when no issues were found", body = ChainVerification),
+        (status = 400, description = "Invalid timestamp"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Compliance"
 )]
-pub async fn lift_processing_restriction(
-    path: web::Path<String>,
-    req: web::Json<LiftRestrictionRequest>,
+pub async fn verify_chain(
+    query: web::Query<std::collections::HashMap<String, String>>,
     data: web::Data<AppState>,
     http_req: HttpRequest,
 ) -> impl Responder {
-    // AUTHENTICATION & AUTHORIZATION - Critical: data_subject.write
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "data_subject", "write").await {
-        Ok(c) => c,
-        Err(resp) => return resp,
-    };
-
-    let user_id = path.into_inner();
-    if user_id != req.user_id {
-        return HttpResponse::BadRequest().json(serde_json::json!({
-            "error": "User ID does not match"
-        }));
+    // AUTHENTICATION & AUTHORIZATION
+    if let Err(resp) = authenticate_and_authorize(&http_req, &data.db_pool, "compliance", "read").await {
+        return resp;
     }
 
-    let now = Utc::now();
-    let lifted_by = req.lifted_by.as_deref().unwrap_or("SYSTEM");
-
-    let result = sqlx::query(
-        "UPDATE processing_restrictions 
-         SET status = 'LIFTED', lifted_at = $1, lifted_by = $2, lift_reason = $3
-         WHERE user_id = $4 AND status = 'ACTIVE'"
-    )
-    .bind(now)
-    .bind(lifted_by)
-    .bind(&req.reason)
-    .bind(&user_id)
-    .execute(&data.db_pool)
-    .await;
-
-    match result {
-        Ok(rows) => {
-            if rows.rows_affected() == 0 {
-                return HttpResponse::NotFound().json(serde_json::json!({
-                    "error": "No active restriction found for this user"
-                }));
-            }
-
-            println!("🔓 Processing restriction lifted: User: {} | Lifted by: {}", user_id, lifted_by);
-
-            HttpResponse::Ok().json(serde_json::json!({
-                "status": "SUCCESS",
-                "message": "Processing restriction lifted successfully",
-                "user_id": user_id,
-                "lifted_at": now.format("%Y-%m-%d %H:%M:%S").to_string()
-            }))
+    let parse_bound = |name: &str| -> Result<Option<DateTime<Utc>>, HttpResponse> {
+        match query.get(name) {
+            None => Ok(None),
+            Some(value) => DateTime::parse_from_rfc3339(value)
+                .map(|ts| Some(ts.with_timezone(&Utc)))
+                .map_err(|_| HttpResponse::BadRequest().json(serde_json::json!({
+                    "error": "Invalid input",
+                    "message": format!("'{}' must be an RFC 3339 timestamp", name)
+                }))),
         }
+    };
+    let from = match parse_bound("from") {
+        Ok(ts) => ts,
+        Err(resp) => return resp,
+    };
+    let to = match parse_bound("to") {
+        Ok(ts) => ts,
+        Err(resp) => return resp,
+    };
+
+    match crate::core::audit_chain::AuditChain::verify(&data.db_pool, from, to).await {
+        Ok(verification) => HttpResponse::Ok().json(verification),
         Err(e) => {
             let request_id = generate_request_id();
-            let request_id = generate_request_id();
-            log_error_safely("lifting restriction", &e, &request_id);
-            HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to lift processing restriction"
-            }))
+            log_error_safely("verifying audit chain", &e, &request_id);
+            create_error_response(&request_id)
         }
     }
 }
 
-// 8.3. GET PROCESSING RESTRICTIONS (GDPR Article 18)
+// 3.6. MERKLE ANCHORS
+/// List sealed Merkle anchors of the compliance record chain
 #[utoipa::path(
     get,
-    path = "/data_subject/{user_id}/restrictions",
-    responses((status = 200, body = RestrictionsResponse))
+    path = "/audit_anchors",
+    params(
+        ("limit" = Option<i64>, Query, description = "Number of anchors, most recent first (default: 50, max: 1000)")
+    ),
+    responses(
+        (status = 200, description = "Anchors", body = Vec<crate::core::merkle_anchor::AuditAnchor>),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Compliance"
 )]
-pub async fn get_processing_restrictions(
-    path: web::Path<String>,
+pub async fn list_audit_anchors(
+    query: web::Query<std::collections::HashMap<String, String>>,
     data: web::Data<AppState>,
     http_req: HttpRequest,
 ) -> impl Responder {
-    // AUTHENTICATION & AUTHORIZATION - Critical: data_subject.read
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "data_subject", "read").await {
-        Ok(c) => c,
-        Err(resp) => return resp,
-    };
-
-    let user_id = path.into_inner();
-
-    #[derive(sqlx::FromRow)]
-    struct RestrictionRow {
-        restriction_id: String,
-        user_id: String,
-        restriction_type: String,
-        status: String,
-        requested_at: DateTime<Utc>,
-        expires_at: Option<DateTime<Utc>>,
+    // AUTHENTICATION & AUTHORIZATION
+    if let Err(resp) = authenticate_and_authorize(&http_req, &data.db_pool, "compliance", "read").await {
+        return resp;
     }
 
-    let result = sqlx::query_as::<_, RestrictionRow>(
-        "SELECT restriction_id, user_id, restriction_type, status, requested_at, expires_at
-         FROM processing_restrictions
-         WHERE user_id = $1
-         ORDER BY requested_at DESC"
-    )
-    .bind(&user_id)
-    .fetch_all(&data.db_pool)
-    .await;
-
-    match result {
-        Ok(rows) => {
-            let restrictions: Vec<ProcessingRestrictionResponse> = rows.into_iter().map(|r| {
-                ProcessingRestrictionResponse {
-                    restriction_id: r.restriction_id,
-                    user_id: r.user_id,
-                    restriction_type: r.restriction_type,
-                    status: r.status,
-                    requested_at: r.requested_at.format("%Y-%m-%d %H:%M:%S").to_string(),
-                    expires_at: r.expires_at.map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
-                }
-            }).collect();
-
-            let response = RestrictionsResponse {
-                user_id: user_id.clone(),
-                restrictions,
-            };
+    let limit = query
+        .get("limit")
+        .and_then(|s| s.parse::<i64>().ok())
+        .unwrap_or(50)
+        .clamp(1, 1000);
 
-            HttpResponse::Ok().json(response)
-        }
+    match crate::core::merkle_anchor::AuditAnchors::list(&data.db_pool, limit).await {
+        Ok(anchors) => HttpResponse::Ok().json(anchors),
         Err(e) => {
             let request_id = generate_request_id();
-            let request_id = generate_request_id();
-            log_error_safely("fetching restrictions", &e, &request_id);
-            HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to fetch processing restrictions"
-            }))
+            log_error_safely("listing audit anchors", &e, &request_id);
+            create_error_response(&request_id)
         }
     }
 }
 
-// ========== GDPR Article 21: Right to Object ==========
-
-// 8.4. REQUEST PROCESSING OBJECTION (GDPR Article 21)
+/// Get a record's Merkle inclusion proof
+///
+/// Returns the sibling hashes leading from the record's leaf to the sealed
+/// root of its anchor, so the record can be verified offline:
+/// leaf = SHA-256(0x00 || record_hash), node = SHA-256(0x01 || left || right).
 #[utoipa::path(
-    post,
-    path = "/data_subject/{user_id}/object",
-    request_body = ProcessingObjectionRequest,
-    responses((status = 200, body = ProcessingObjectionResponse))
+    get,
+    path = "/audit_anchors/proof/{seal_id}",
+    params(
+        ("seal_id" = String, Path, description = "Seal ID of the compliance record")
+    ),
+    responses(
+        (status = 200, description = "Inclusion proof", body = crate::core::merkle_anchor::InclusionProof),
+        (status = 404, description = "Record not found or not anchored yet"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Compliance"
 )]
-pub async fn request_processing_objection(
+pub async fn get_anchor_proof(
     path: web::Path<String>,
-    req: web::Json<ProcessingObjectionRequest>,
     data: web::Data<AppState>,
     http_req: HttpRequest,
 ) -> impl Responder {
-    // AUTHENTICATION & AUTHORIZATION - Critical: data_subject.write
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "data_subject", "write").await {
-        Ok(c) => c,
-        Err(resp) => return resp,
-    };
-
-    let user_id = path.into_inner();
-    if user_id != req.user_id {
-        return HttpResponse::BadRequest().json(serde_json::json!({
-            "error": "User ID does not match"
-        }));
+    // AUTHENTICATION & AUTHORIZATION
+    if let Err(resp) = authenticate_and_authorize(&http_req, &data.db_pool, "compliance", "read").await {
+        return resp;
     }
 
-    // Check if there's already an active objection of this type
-    let existing: Option<(String,)> = sqlx::query_as(
-        "SELECT objection_id FROM processing_objections 
-         WHERE user_id = $1 AND objection_type = $2 AND status = 'ACTIVE'"
-    )
-    .bind(&user_id)
-    .bind(&req.objection_type)
-    .fetch_optional(&data.db_pool)
-    .await
-    .unwrap_or(None);
-
-    if existing.is_some() {
-        return HttpResponse::Conflict().json(serde_json::json!({
-            "error": format!("User already has an active {} objection", req.objection_type),
-            "objection_id": existing.unwrap().0
-        }));
+    let seal_id = path.into_inner();
+    match crate::core::merkle_anchor::AuditAnchors::proof(&data.db_pool, &seal_id).await {
+        Ok(Some(proof)) => HttpResponse::Ok().json(proof),
+        Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Not found",
+            "message": format!("No anchored record with seal_id {}", seal_id)
+        })),
+        Err(e) => {
+            let request_id = generate_request_id();
+            log_error_safely("fetching anchor proof", &e, &request_id);
+            create_error_response(&request_id)
+        }
     }
+}
 
-    let objection_id = format!("OBJECT-{}-{}", Local::now().format("%Y%m%d-%H%M%S"), Uuid::new_v4().to_string().chars().take(8).collect::<String>());
-    let now = Utc::now();
-
-    let objected_actions_json = serde_json::to_value(req.objected_actions.as_ref().unwrap_or(&vec![]))
-        .unwrap_or(serde_json::json!([]));
-
-    let result = sqlx::query(
-        "INSERT INTO processing_objections (
-            objection_id, user_id, objection_type, objected_actions,
-            legal_basis, reason, status, requested_at
-        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)"
-    )
-    .bind(&objection_id)
-    .bind(&user_id)
-    .bind(&req.objection_type)
-    .bind(objected_actions_json)
-    .bind(&req.legal_basis)
-    .bind(&req.reason)
-    .bind("ACTIVE")
-    .bind(now)
-    .execute(&data.db_pool)
-    .await;
+// 3.7. SEAL PROVIDERS
+#[derive(Deserialize, ToSchema)]
+pub struct SetSealProviderRequest {
+    /// Tenant ID; sets the default for all tenants when omitted
+    pub company_id: Option<Uuid>,
+    /// SIGNICAT, RFC3161 or LOCAL
+    #[schema(example = "RFC3161")]
+    pub provider: String,
+    /// TSA endpoint (required for RFC3161)
+    #[schema(example = "https://tsa.example.eu/tsr")]
+    pub tsa_url: Option<String>,
+    /// TSA policy OID to request (RFC3161 only)
+    pub tsa_policy_oid: Option<String>,
+}
 
-    match result {
-        Ok(_) => {
-            let response = ProcessingObjectionResponse {
-                objection_id: objection_id.clone(),
-                user_id: user_id.clone(),
-                objection_type: req.objection_type.clone(),
-                status: "ACTIVE".to_string(),
-                requested_at: now.format("%Y-%m-%d %H:%M:%S").to_string(),
-                rejection_reason: None,
-            };
+/// Get the seal provider that applies to a tenant
+#[utoipa::path(
+    get,
+    path = "/seal_provider",
+    params(
+        ("company_id" = Option<String>, Query, description = "Tenant ID; the default when omitted")
+    ),
+    responses(
+        (status = 200, description = "Setting in effect", body = crate::core::seal_provider::SealProviderSetting),
+        (status = 400, description = "Invalid company_id"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Compliance"
+)]
+pub async fn get_seal_provider(
+    query: web::Query<std::collections::HashMap<String, String>>,
+    data: web::Data<AppState>,
+    http_req: HttpRequest,
+) -> impl Responder {
+    // AUTHENTICATION & AUTHORIZATION
+    if let Err(resp) = authenticate_and_authorize(&http_req, &data.db_pool, "policy", "read").await {
+        return resp;
+    }
 
-            println!("🚫 Processing objection requested: {} | User: {} | Type: {}", 
-                objection_id, user_id, req.objection_type);
+    let company_id = match query.get("company_id").map(|s| Uuid::parse_str(s)) {
+        None => None,
+        Some(Ok(id)) => Some(id),
+        Some(Err(_)) => return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Invalid input",
+            "message": "company_id must be a UUID"
+        })),
+    };
 
-            HttpResponse::Ok().json(response)
-        }
+    match data.seals.setting(&data.db_pool, company_id).await {
+        Ok(setting) => HttpResponse::Ok().json(setting),
         Err(e) => {
             let request_id = generate_request_id();
-            let request_id = generate_request_id();
-            log_error_safely("creating objection", &e, &request_id);
-            HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to create processing objection"
-            }))
+            log_error_safely("loading seal provider setting", &e, &request_id);
+            create_error_response(&request_id)
         }
     }
 }
 
-// 8.5. WITHDRAW PROCESSING OBJECTION (GDPR Article 21)
+/// Choose the seal provider of a tenant (or the default)
+///
+/// New seals for the tenant's records use this provider; existing seals and
+/// their tokens are unchanged.
 #[utoipa::path(
-    post,
-    path = "/data_subject/{user_id}/withdraw_objection",
-    request_body = WithdrawObjectionRequest,
-    responses((status = 200, body = serde_json::Value))
+    put,
+    path = "/seal_provider",
+    request_body = SetSealProviderRequest,
+    responses(
+        (status = 200, description = "Setting stored", body = crate::core::seal_provider::SealProviderSetting),
+        (status = 400, description = "Invalid setting"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+    ),
+    tag = "Compliance",
+    security(
+        ("bearer" = [])
+    )
 )]
-pub async fn withdraw_processing_objection(
-    path: web::Path<String>,
-    req: web::Json<WithdrawObjectionRequest>,
+pub async fn set_seal_provider(
+    req: web::Json<SetSealProviderRequest>,
     data: web::Data<AppState>,
     http_req: HttpRequest,
 ) -> impl Responder {
-    // AUTHENTICATION & AUTHORIZATION - Critical: data_subject.write
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "data_subject", "write").await {
+    // AUTHENTICATION & AUTHORIZATION
+    let claims = match authenticate_and_authorize(&http_req, &data.db_pool, "policy", "write").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
 
-    let user_id = path.into_inner();
-    if user_id != req.user_id {
+    let req = req.into_inner();
+    let setting = crate::core::seal_provider::SealProviderSetting {
+        company_id: req.company_id,
+        provider: req.provider.to_uppercase(),
+        tsa_url: req.tsa_url,
+        tsa_policy_oid: req.tsa_policy_oid,
+        updated_by: Some(claims.sub.clone()),
+        updated_at: None,
+    };
+    if let Err(message) = setting.validate() {
         return HttpResponse::BadRequest().json(serde_json::json!({
-            "error": "User ID does not match"
+            "error": "Invalid input",
+            "message": message
         }));
     }
 
-    let now = Utc::now();
-    let withdrawn_by = "USER"; // User withdrawing their own objection
-
-    let result = sqlx::query(
-        "UPDATE processing_objections 
-         SET status = 'WITHDRAWN', withdrawn_at = $1, withdrawn_by = $2, withdraw_reason = $3
-         WHERE user_id = $4 AND status = 'ACTIVE'"
-    )
-    .bind(now)
-    .bind(withdrawn_by)
-    .bind(&req.reason)
-    .bind(&user_id)
-    .execute(&data.db_pool)
-    .await;
-
-    match result {
-        Ok(rows) => {
-            if rows.rows_affected() == 0 {
-                return HttpResponse::NotFound().json(serde_json::json!({
-                    "error": "No active objection found for this user"
-                }));
-            }
-
-            println!("✅ Processing objection withdrawn: User: {}", user_id);
-
-            HttpResponse::Ok().json(serde_json::json!({
-                "status": "SUCCESS",
-                "message": "Processing objection withdrawn successfully",
-                "user_id": user_id,
-                "withdrawn_at": now.format("%Y-%m-%d %H:%M:%S").to_string()
-            }))
-        }
+    match data.seals.set_setting(&data.db_pool, &setting).await {
+        Ok(stored) => HttpResponse::Ok().json(stored),
         Err(e) => {
             let request_id = generate_request_id();
-            let request_id = generate_request_id();
-            log_error_safely("withdrawing objection", &e, &request_id);
-            HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to withdraw processing objection"
-            }))
+            log_error_safely("storing seal provider setting", &e, &request_id);
+            create_error_response(&request_id)
         }
     }
 }
 
-// 8.6. REJECT PROCESSING OBJECTION (GDPR Article 21(1) - must provide reason)
+/// Export and check a stored seal token
+///
+/// Returns the token (base64; a DER TimeStampToken for RFC 3161 seals) with
+/// the hash it covers, for verification outside Veridion Nexus.
 #[utoipa::path(
-    post,
-    path = "/data_subject/{user_id}/reject_objection",
-    request_body = RejectObjectionRequest,
-    responses((status = 200, body = serde_json::Value))
+    get,
+    path = "/seal_tokens",
+    params(
+        ("seal_id" = String, Query, description = "Seal ID as returned by log_action, an anchor or an erasure certificate; a PENDING_SYNC_LOCAL placeholder resolves once the seal outbox has sealed it")
+    ),
+    responses(
+        (status = 200, description = "Token and check result", body = crate::core::seal_provider::SealVerification),
+        (status = 400, description = "seal_id missing"),
+        (status = 404, description = "No token stored for this seal"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Compliance"
 )]
-pub async fn reject_processing_objection(
-    path: web::Path<String>,
-    req: web::Json<RejectObjectionRequest>,
+pub async fn get_seal_token(
+    query: web::Query<std::collections::HashMap<String, String>>,
     data: web::Data<AppState>,
     http_req: HttpRequest,
 ) -> impl Responder {
-    // AUTHENTICATION & AUTHORIZATION - Critical: data_subject.write (admin only for rejection)
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "data_subject", "write").await {
-        Ok(c) => c,
-        Err(resp) => return resp,
-    };
-
-    let user_id = path.into_inner();
-    if user_id != req.user_id {
-        return HttpResponse::BadRequest().json(serde_json::json!({
-            "error": "User ID does not match"
-        }));
-    }
-
-    // GDPR Article 21(1) requires a reason for rejection
-    if req.rejection_reason.is_empty() {
-        return HttpResponse::BadRequest().json(serde_json::json!({
-            "error": "Rejection reason is required per GDPR Article 21(1)"
-        }));
+    // AUTHENTICATION & AUTHORIZATION
+    if let Err(resp) = authenticate_and_authorize(&http_req, &data.db_pool, "compliance", "read").await {
+        return resp;
     }
 
-    let now = Utc::now();
-    let rejected_by = req.rejected_by.as_deref().unwrap_or("SYSTEM");
-
-    let result = sqlx::query(
-        "UPDATE processing_objections 
-         SET status = 'REJECTED', rejected_at = $1, rejected_by = $2, rejection_reason = $3
-         WHERE user_id = $4 AND status = 'ACTIVE'"
-    )
-    .bind(now)
-    .bind(rejected_by)
-    .bind(&req.rejection_reason)
-    .bind(&user_id)
-    .execute(&data.db_pool)
-    .await;
-
-    match result {
-        Ok(rows) => {
-            if rows.rows_affected() == 0 {
-                return HttpResponse::NotFound().json(serde_json::json!({
-                    "error": "No active objection found for this user"
-                }));
-            }
-
-            println!("❌ Processing objection rejected: User: {} | By: {} | Reason: {}", 
-                user_id, rejected_by, req.rejection_reason);
+    let seal_id = match query.get("seal_id") {
+        Some(seal_id) => seal_id,
+        None => return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Invalid input",
+            "message": "seal_id is required"
+        })),
+    };
 
-            HttpResponse::Ok().json(serde_json::json!({
-                "status": "SUCCESS",
-                "message": "Processing objection rejected",
-                "user_id": user_id,
-                "rejection_reason": req.rejection_reason,
-                "rejected_at": now.format("%Y-%m-%d %H:%M:%S").to_string()
-            }))
-        }
+    match data.seals.verify(&data.db_pool, seal_id).await {
+        Ok(Some(verification)) => HttpResponse::Ok().json(verification),
+        Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Not found",
+            "message": format!("No token stored for seal {}", seal_id)
+        })),
         Err(e) => {
             let request_id = generate_request_id();
-            let request_id = generate_request_id();
-            log_error_safely("rejecting objection", &e, &request_id);
-            HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to reject processing objection"
-            }))
+            log_error_safely("loading seal token", &e, &request_id);
+            create_error_response(&request_id)
         }
     }
 }
 
-// 8.7. GET PROCESSING OBJECTIONS (GDPR Article 21)
+// 3.8. SEAL OUTBOX
+/// Backlog of records waiting for their seal
+///
+/// Counts of queued and resolved seals, the age of the oldest pending one and
+/// the entries pending longer than the sealing SLA (SEAL_OUTBOX_SLA_MINUTES).
 #[utoipa::path(
     get,
-    path = "/data_subject/{user_id}/objections",
-    responses((status = 200, body = ObjectionsResponse))
+    path = "/seal_outbox",
+    responses(
+        (status = 200, description = "Seal outbox status", body = crate::core::seal_outbox::SealOutboxStatus),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Compliance"
 )]
-pub async fn get_processing_objections(
-    path: web::Path<String>,
+pub async fn get_seal_outbox(
     data: web::Data<AppState>,
     http_req: HttpRequest,
 ) -> impl Responder {
-    // AUTHENTICATION & AUTHORIZATION - Critical: data_subject.read
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "data_subject", "read").await {
-        Ok(c) => c,
-        Err(resp) => return resp,
-    };
-
-    let user_id = path.into_inner();
-
-    #[derive(sqlx::FromRow)]
-    struct ObjectionRow {
-        objection_id: String,
-        user_id: String,
-        objection_type: String,
-        status: String,
-        requested_at: DateTime<Utc>,
-        rejection_reason: Option<String>,
+    // AUTHENTICATION & AUTHORIZATION
+    if let Err(resp) = authenticate_and_authorize(&http_req, &data.db_pool, "compliance", "read").await {
+        return resp;
     }
 
-    let result = sqlx::query_as::<_, ObjectionRow>(
-        "SELECT objection_id, user_id, objection_type, status, requested_at, rejection_reason
-         FROM processing_objections
-         WHERE user_id = $1
-         ORDER BY requested_at DESC"
-    )
-    .bind(&user_id)
-    .fetch_all(&data.db_pool)
-    .await;
-
-    match result {
-        Ok(rows) => {
-            let objections: Vec<ProcessingObjectionResponse> = rows.into_iter().map(|r| {
-                ProcessingObjectionResponse {
-                    objection_id: r.objection_id,
-                    user_id: r.user_id,
-                    objection_type: r.objection_type,
-                    status: r.status,
-                    requested_at: r.requested_at.format("%Y-%m-%d %H:%M:%S").to_string(),
-                    rejection_reason: r.rejection_reason,
-                }
-            }).collect();
-
-            let response = ObjectionsResponse {
-                user_id: user_id.clone(),
-                objections,
-            };
-
-            HttpResponse::Ok().json(response)
-        }
+    let sla_minutes = crate::core::seal_outbox::sla_minutes();
+    match crate::core::seal_outbox::SealOutbox::status(&data.db_pool, sla_minutes).await {
+        Ok(status) => HttpResponse::Ok().json(status),
         Err(e) => {
             let request_id = generate_request_id();
-            let request_id = generate_request_id();
-            log_error_safely("fetching objections", &e, &request_id);
-            HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to fetch processing objections"
-            }))
+            log_error_safely("loading seal outbox status", &e, &request_id);
+            create_error_response(&request_id)
         }
     }
 }
 
-// ========== GDPR Article 22: Automated Decision-Making ==========
-
-// 8.8. REQUEST HUMAN REVIEW (GDPR Article 22)
+// 4. DOWNLOAD REPORT (Enhanced with format support and extended Annex IV fields)
 #[utoipa::path(
-    post,
-    path = "/data_subject/{user_id}/request_review",
-    request_body = RequestReviewRequest,
-    responses((status = 200, body = RequestReviewResponse))
+    get,
+    path = "/download_report",
+    params(
+        ("format" = Option<String>, Query, description = "Export format: pdf, json, xml (default: pdf)"),
+        ("seal_id" = Option<String>, Query, description = "Filter by specific seal_id"),
+        ("report_type" = Option<String>, Query, description = "Report type: annex_iv, dora_register (default: annex_iv)")
+    ),
+    responses(
+        (status = 200, description = "Report generated successfully"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Reports"
 )]
-pub async fn request_human_review(
-    path: web::Path<String>,
-    req: web::Json<RequestReviewRequest>,
+pub async fn download_report(
+    query: web::Query<std::collections::HashMap<String, String>>,
     data: web::Data<AppState>,
-    http_req: HttpRequest,
 ) -> impl Responder {
-    // AUTHENTICATION & AUTHORIZATION - Critical: data_subject.write
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "data_subject", "write").await {
-        Ok(c) => c,
-        Err(resp) => return resp,
-    };
-
-    let user_id = path.into_inner();
-    if user_id != req.user_id {
-        return HttpResponse::BadRequest().json(serde_json::json!({
-            "error": "User ID does not match"
-        }));
-    }
-
-    // Find decision by decision_id or seal_id
-    let decision_result: Option<(String, String)> = if let Some(ref decision_id) = req.decision_id {
-        sqlx::query_as(
-            "SELECT decision_id, seal_id FROM automated_decisions 
-             WHERE decision_id = $1 AND user_id = $2"
-        )
-        .bind(decision_id)
-        .bind(&user_id)
-        .fetch_optional(&data.db_pool)
-        .await
-        .unwrap_or(None)
-    } else if let Some(ref seal_id) = req.seal_id {
-        sqlx::query_as(
-            "SELECT decision_id, seal_id FROM automated_decisions 
-             WHERE seal_id = $1 AND user_id = $2"
-        )
-        .bind(seal_id)
-        .bind(&user_id)
-        .fetch_optional(&data.db_pool)
-        .await
-        .unwrap_or(None)
+    let format_str = query.get("format").map(|s| s.as_str()).unwrap_or("pdf");
+    let format = crate::core::annex_iv::ExportFormat::from_str(format_str)
+        .unwrap_or(crate::core::annex_iv::ExportFormat::Pdf);
+    
+    let report_type = query.get("report_type").map(|s| s.as_str()).unwrap_or("annex_iv");
+    let seal_id_filter = query.get("seal_id");
+    
+    // Build query with optional seal_id filter
+    let query_str = if seal_id_filter.is_some() {
+        "SELECT cr.* FROM compliance_records_current cr WHERE cr.seal_id = $1 ORDER BY cr.timestamp DESC"
     } else {
-        return HttpResponse::BadRequest().json(serde_json::json!({
-            "error": "Either decision_id or seal_id must be provided"
-        }));
+        "SELECT cr.* FROM compliance_records_current cr ORDER BY cr.timestamp DESC LIMIT 1000"
     };
-
-    let (decision_id, seal_id) = match decision_result {
-        Some((did, sid)) => (did, sid),
-        None => {
-            return HttpResponse::NotFound().json(serde_json::json!({
-                "error": "Automated decision not found"
-            }));
-        }
+    
+    let result = if let Some(seal_id) = seal_id_filter {
+        sqlx::query_as::<_, ComplianceRecordDb>(query_str)
+            .bind(seal_id)
+    .fetch_all(&data.db_pool)
+    .await
+    } else {
+        sqlx::query_as::<_, ComplianceRecordDb>(query_str)
+            .fetch_all(&data.db_pool)
+            .await
     };
-
-    // Update decision status to UNDER_REVIEW and create human oversight record
-    let now = Utc::now();
     
-    // Update automated decision
-    let update_result = sqlx::query(
-        "UPDATE automated_decisions 
-         SET status = 'UNDER_REVIEW', human_review_required = true, updated_at = $1
-         WHERE decision_id = $2"
-    )
-    .bind(now)
-    .bind(&decision_id)
-    .execute(&data.db_pool)
-    .await;
-
-    match update_result {
-        Ok(_) => {
-            // Create human oversight record (if not exists)
-            let _ = sqlx::query(
-                "INSERT INTO human_oversight (seal_id, status) VALUES ($1, 'PENDING')
-                 ON CONFLICT (seal_id) DO UPDATE SET status = 'PENDING', updated_at = CURRENT_TIMESTAMP"
-            )
-            .bind(&seal_id)
-            .execute(&data.db_pool)
-            .await;
+    match result {
+        Ok(records) => {
+            // Convert to extended ComplianceRecord with Annex IV fields
+            let mut compliance_records: Vec<crate::core::annex_iv::ComplianceRecord> = Vec::new();
+            
+            for r in records {
+                let mut record: crate::core::annex_iv::ComplianceRecord = r.clone().into();
+                
+                // Add extended Annex IV fields
+                // Try to extract lifecycle stage from action_summary or use default
+                record.lifecycle_stage = Some("DEPLOYMENT".to_string()); // Default, can be enhanced
+                
+                // Get risk assessment data for mitigation actions
+                if let Ok(Some(ra)) = sqlx::query_as::<_, RiskAssessmentDb>(
+                    "SELECT * FROM risk_assessments WHERE seal_id = $1 LIMIT 1"
+                )
+                .bind(&r.seal_id)
+                .fetch_optional(&data.db_pool)
+                .await
+                {
+                    // Extract risk management measures from mitigation_actions
+                    if let Ok(measures) = serde_json::from_value::<Vec<String>>(ra.mitigation_actions.clone()) {
+                        record.risk_management_measures = Some(measures);
+                    }
+                }
+                
+                // Get post-market monitoring if available
+                if let Ok(Some(pm)) = sqlx::query_scalar::<_, Option<String>>(
+                    "SELECT monitoring_results FROM post_market_monitoring WHERE seal_id = $1 LIMIT 1"
+                )
+                .bind(&r.seal_id)
+                .fetch_optional(&data.db_pool)
+                .await
+                {
+                    record.post_market_monitoring = pm;
+                }
+                
+                compliance_records.push(record);
+            }
+            
+            // Determine filename, temp_file, and generation function based on report_type
+            let (filename, temp_file, export_result) = match report_type {
+                "dora_register" => {
+                    // DORA Register: Simplified vendor supply chain report (PDF only for now)
+                    let filename = format!("Veridion_DORA_Register_{}.pdf", 
+                        chrono::Utc::now().format("%Y%m%d_%H%M%S")
+                    );
+                    let temp_file = format!("temp_report_{}.pdf", 
+                        uuid::Uuid::new_v4().to_string().replace("-", "")
+                    );
+                    
+                    let result = crate::core::annex_iv::generate_dora_register(&compliance_records, data.sovereignty.region_catalog(), &temp_file);
+                    (filename, temp_file, result)
+                }
+                _ => {
+                    // Default: Annex IV report
+            let filename = format!("Veridion_Annex_IV_{}.{}", 
+                chrono::Utc::now().format("%Y%m%d_%H%M%S"),
+                format.file_extension()
+            );
+            let temp_file = format!("temp_report_{}.{}", 
+                uuid::Uuid::new_v4().to_string().replace("-", ""),
+                format.file_extension()
+            );
+            
+                    let result = match format {
+                crate::core::annex_iv::ExportFormat::Pdf => {
+                    crate::core::annex_iv::generate_report(&compliance_records, &temp_file)
+                }
+                crate::core::annex_iv::ExportFormat::Json => {
+                    crate::core::annex_iv::export_to_json(&compliance_records, &temp_file)
+                }
+                crate::core::annex_iv::ExportFormat::Xml => {
+                    crate::core::annex_iv::export_to_xml(&compliance_records, &temp_file)
+                        }
+                    };
+                    (filename, temp_file, result)
+                }
+            };
+            
+            match export_result {
+                Ok(_) => {
+                    if let Ok(bytes) = fs::read(&temp_file) {
+                        // Clean up temp file
+                        let _ = fs::remove_file(&temp_file);
+                        
+                        // Determine content type based on report type
+                        let content_type = if report_type == "dora_register" {
+                            "application/pdf"
+                        } else {
+                            format.content_type()
+                        };
+                        
+                        HttpResponse::Ok()
+                            .content_type(content_type)
+                            .append_header(ContentDisposition::attachment(&filename))
+                            .body(bytes)
+            } else { 
+                        HttpResponse::InternalServerError().json(serde_json::json!({
+                            "error": "Failed to read generated report"
+                        }))
+                    }
+                }
+                Err(e) => {
+                    let request_id = generate_request_id();
+                    // e is a String, convert to &dyn Error
+                    let error_msg = format!("{}", e);
+                    log::error!("Error generating report: {} (Request ID: {})", error_msg, request_id);
+                    HttpResponse::InternalServerError().json(serde_json::json!({
+                        "error": "Failed to generate report"
+                    }))
+                }
+            }
+        }
+        Err(e) => {
+            let request_id = generate_request_id();
+            log_error_safely("fetching records for report", &e, &request_id);
+            create_error_response(&request_id)
+        }
+    }
+}
 
-            println!("👤 Human review requested: Decision: {} | User: {}", decision_id, user_id);
+// 5. REVOKE ACCESS
+#[derive(Deserialize, ToSchema)]
+pub struct RevokeAccessRequest {
+    /// Optional agent_id to revoke. If not provided, triggers global system lockdown.
+    #[schema(example = "agent-001")]
+    pub agent_id: Option<String>,
+    /// Why the agent is revoked (kept in the revocation history)
+    #[schema(example = "Suspicious data export volume")]
+    pub reason: Option<String>,
+    /// Restore access automatically after this many minutes; permanent until restored when omitted
+    #[schema(example = 60)]
+    pub expires_in_minutes: Option<i64>,
+}
 
-            let response = RequestReviewResponse {
-                decision_id: decision_id.clone(),
-                status: "UNDER_REVIEW".to_string(),
-                message: "Human review requested successfully. The decision will be reviewed by a human reviewer.".to_string(),
+#[utoipa::path(
+    post,
+    path = "/revoke_access",
+    request_body = RevokeAccessRequest,
+    responses(
+        (status = 200, description = "Access revoked successfully"),
+        (status = 400, description = "Invalid expiry"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "System Management"
+)]
+pub async fn revoke_access(
+    req: Option<web::Json<RevokeAccessRequest>>,
+    data: web::Data<AppState>,
+    http_req: HttpRequest,
+) -> impl Responder {
+    // Check if agent_id is provided in request body
+    if let Some(body) = req {
+        if let Some(agent_id) = &body.agent_id {
+            // Granular revocation: revoke specific agent on every replica
+            let expires_at = match body.expires_in_minutes {
+                Some(minutes) if minutes <= 0 => {
+                    return HttpResponse::BadRequest().json(serde_json::json!({
+                        "error": "Invalid input",
+                        "message": "expires_in_minutes must be positive"
+                    }));
+                }
+                Some(minutes) => Some(chrono::Utc::now() + chrono::Duration::minutes(minutes)),
+                None => None,
             };
+            let actor = crate::security::AuthService::new()
+                .ok()
+                .and_then(|auth_service| extract_claims(&http_req, &auth_service).ok())
+                .map(|c| c.sub);
 
-            HttpResponse::Ok().json(response)
+            return match data.revoke_agent(agent_id, body.reason.as_deref(), actor.as_deref(), expires_at).await {
+                Ok(revocation) => HttpResponse::Ok().json(serde_json::json!({
+                    "status": "SUCCESS",
+                    "message": format!("Agent '{}' access revoked", agent_id),
+                    "agent_id": agent_id,
+                    "revocation": revocation
+                })),
+                Err(e) => {
+                    let request_id = generate_request_id();
+                    log_error_safely("revoking agent", &e, &request_id);
+                    create_error_response(&request_id)
+                }
+            };
         }
+    }
+
+    // No agent_id provided: trigger global system lockdown (panic button)
+    match data.set_locked_down(true).await {
+        Ok(_) => HttpResponse::Ok().json(serde_json::json!({
+            "status": "SUCCESS",
+            "message": "Global system lockdown activated"
+        })),
         Err(e) => {
             let request_id = generate_request_id();
-            let request_id = generate_request_id();
-            log_error_safely("requesting review", &e, &request_id);
+            log_error_safely("setting lockdown", &e, &request_id);
             HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to request human review"
+                "error": "Failed to set lockdown"
             }))
         }
     }
 }
 
-// 8.9. APPEAL AUTOMATED DECISION (GDPR Article 22)
+#[derive(Deserialize, ToSchema)]
+pub struct RestoreAccessRequest {
+    #[schema(example = "agent-001")]
+    pub agent_id: String,
+    /// Why access is restored (kept in the revocation history)
+    pub reason: Option<String>,
+}
+
+/// Restore a revoked agent on all replicas
 #[utoipa::path(
     post,
-    path = "/data_subject/{user_id}/appeal_decision",
-    request_body = AppealDecisionRequest,
-    responses((status = 200, body = serde_json::Value))
+    path = "/restore_access",
+    request_body = RestoreAccessRequest,
+    responses(
+        (status = 200, description = "Access restored"),
+        (status = 404, description = "Agent is not revoked"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+    ),
+    tag = "System Management",
+    security(
+        ("bearer" = [])
+    )
 )]
-pub async fn appeal_automated_decision(
-    path: web::Path<String>,
-    req: web::Json<AppealDecisionRequest>,
+pub async fn restore_access(
+    req: web::Json<RestoreAccessRequest>,
     data: web::Data<AppState>,
     http_req: HttpRequest,
 ) -> impl Responder {
-    // AUTHENTICATION & AUTHORIZATION - Critical: data_subject.write
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "data_subject", "write").await {
+    // AUTHENTICATION & AUTHORIZATION
+    let claims = match authenticate_and_authorize(&http_req, &data.db_pool, "policy", "write").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
 
-    let user_id = path.into_inner();
-    if user_id != req.user_id {
-        return HttpResponse::BadRequest().json(serde_json::json!({
-            "error": "User ID does not match"
-        }));
+    match data.restore_agent(&req.agent_id, req.reason.as_deref(), Some(&claims.sub)).await {
+        Ok(true) => HttpResponse::Ok().json(serde_json::json!({
+            "status": "SUCCESS",
+            "message": format!("Agent '{}' access restored", req.agent_id),
+            "agent_id": req.agent_id
+        })),
+        Ok(false) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "AGENT_NOT_REVOKED",
+            "message": format!("Agent '{}' is not revoked", req.agent_id)
+        })),
+        Err(e) => {
+            let request_id = generate_request_id();
+            log_error_safely("restoring agent", &e, &request_id);
+            create_error_response(&request_id)
+        }
     }
+}
 
-    if req.appeal_reason.is_empty() {
-        return HttpResponse::BadRequest().json(serde_json::json!({
-            "error": "Appeal reason is required"
-        }));
-    }
-
-    let now = Utc::now();
-
-    let result = sqlx::query(
-        "UPDATE automated_decisions 
-         SET appeal_requested = true, appeal_requested_at = $1, appeal_reason = $2, status = 'APPEALED'
-         WHERE decision_id = $3 AND user_id = $4"
+/// List revoked agents
+#[utoipa::path(
+    get,
+    path = "/agent_revocations",
+    responses(
+        (status = 200, description = "Revocations in force", body = Vec<crate::core::agent_revocation::AgentRevocation>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+    ),
+    tag = "System Management",
+    security(
+        ("bearer" = [])
     )
-    .bind(now)
-    .bind(&req.appeal_reason)
-    .bind(&req.decision_id)
-    .bind(&user_id)
-    .execute(&data.db_pool)
-    .await;
-
-    match result {
-        Ok(rows) => {
-            if rows.rows_affected() == 0 {
-                return HttpResponse::NotFound().json(serde_json::json!({
-                    "error": "Automated decision not found or access denied"
-                }));
-            }
-
-            println!("📝 Appeal requested: Decision: {} | User: {}", req.decision_id, user_id);
+)]
+pub async fn list_agent_revocations(
+    data: web::Data<AppState>,
+    http_req: HttpRequest,
+) -> impl Responder {
+    // AUTHENTICATION & AUTHORIZATION
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "policy", "read").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
 
-            HttpResponse::Ok().json(serde_json::json!({
-                "status": "SUCCESS",
-                "message": "Appeal requested successfully",
-                "decision_id": req.decision_id,
-                "appeal_requested_at": now.format("%Y-%m-%d %H:%M:%S").to_string()
-            }))
-        }
+    match crate::core::agent_revocation::AgentRevocations::active(&data.db_pool).await {
+        Ok(revocations) => HttpResponse::Ok().json(revocations),
         Err(e) => {
             let request_id = generate_request_id();
-            let request_id = generate_request_id();
-            log_error_safely("appealing decision", &e, &request_id);
-            HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to appeal automated decision"
-            }))
+            log_error_safely("listing agent revocations", &e, &request_id);
+            create_error_response(&request_id)
         }
     }
 }
 
-// 8.10. GET AUTOMATED DECISIONS (GDPR Article 22)
+/// Revoke and restore history of an agent
 #[utoipa::path(
     get,
-    path = "/data_subject/{user_id}/automated_decisions",
-    responses((status = 200, body = AutomatedDecisionsResponse))
+    path = "/agent_revocations/{agent_id}/history",
+    params(
+        ("agent_id" = String, Path, description = "Agent ID")
+    ),
+    responses(
+        (status = 200, description = "Revoke and restore events, newest first", body = Vec<crate::core::agent_revocation::RevocationEvent>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+    ),
+    tag = "System Management",
+    security(
+        ("bearer" = [])
+    )
 )]
-pub async fn get_automated_decisions(
+pub async fn get_agent_revocation_history(
     path: web::Path<String>,
     data: web::Data<AppState>,
     http_req: HttpRequest,
 ) -> impl Responder {
-    // AUTHENTICATION & AUTHORIZATION - Critical: data_subject.read
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "data_subject", "read").await {
+    // AUTHENTICATION & AUTHORIZATION
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "policy", "read").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
 
-    let user_id = path.into_inner();
-
-    #[derive(sqlx::FromRow)]
-    struct DecisionRow {
-        decision_id: String,
-        user_id: String,
-        seal_id: String,
-        action_type: String,
-        decision_outcome: String,
-        decision_reasoning: Option<String>,
-        legal_effect: Option<String>,
-        significant_impact: bool,
-        status: String,
-        decision_timestamp: DateTime<Utc>,
-        human_review_required: bool,
-    }
-
-    let result = sqlx::query_as::<_, DecisionRow>(
-        "SELECT decision_id, user_id, seal_id, action_type, decision_outcome,
-                decision_reasoning, legal_effect, significant_impact, status,
-                decision_timestamp, human_review_required
-         FROM automated_decisions
-         WHERE user_id = $1
-         ORDER BY decision_timestamp DESC"
-    )
-    .bind(&user_id)
-    .fetch_all(&data.db_pool)
-    .await;
-
-    match result {
-        Ok(rows) => {
-            let decisions: Vec<AutomatedDecisionResponse> = rows.into_iter().map(|r| {
-                AutomatedDecisionResponse {
-                    decision_id: r.decision_id,
-                    user_id: r.user_id,
-                    seal_id: r.seal_id,
-                    action_type: r.action_type,
-                    decision_outcome: r.decision_outcome,
-                    decision_reasoning: r.decision_reasoning,
-                    legal_effect: r.legal_effect,
-                    significant_impact: r.significant_impact,
-                    status: r.status,
-                    decision_timestamp: r.decision_timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
-                    human_review_required: r.human_review_required,
-                }
-            }).collect();
-
-            let response = AutomatedDecisionsResponse {
-                user_id: user_id.clone(),
-                decisions,
-            };
-
-            HttpResponse::Ok().json(response)
-        }
+    match crate::core::agent_revocation::AgentRevocations::history(&data.db_pool, &path.into_inner()).await {
+        Ok(events) => HttpResponse::Ok().json(events),
         Err(e) => {
             let request_id = generate_request_id();
-            let request_id = generate_request_id();
-            log_error_safely("fetching automated decisions", &e, &request_id);
-            HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to fetch automated decisions"
-            }))
+            log_error_safely("loading agent revocation history", &e, &request_id);
+            create_error_response(&request_id)
         }
     }
 }
 
-// ========== PRIORITY 1: HUMAN OVERSIGHT (EU AI Act Article 14) ==========
+// SCOPED LOCKDOWNS
+#[derive(Deserialize, ToSchema)]
+pub struct CreateLockdownRequest {
+    /// GLOBAL, BUSINESS_FUNCTION, VENDOR_DOMAIN, JURISDICTION or TENANT
+    #[schema(example = "VENDOR_DOMAIN")]
+    pub scope_type: String,
+    /// Business function code, vendor domain (subdomains included), ISO country code or tenant id; ignored for GLOBAL
+    #[schema(example = "api.openai.com")]
+    pub scope_value: Option<String>,
+    /// Why the lockdown is needed (recorded on the DORA Lite incident)
+    #[schema(example = "Vendor reported a data leak affecting prompt logs")]
+    pub justification: String,
+    /// The lockdown ends on its own after this many minutes (at most 7 days)
+    #[schema(example = 120)]
+    pub expires_in_minutes: i64,
+}
 
-// 9. REQUIRE HUMAN OVERSIGHT
+#[derive(Deserialize, ToSchema)]
+pub struct LiftLockdownRequest {
+    /// Why the lockdown is lifted early (recorded on the DORA Lite incident)
+    pub reason: Option<String>,
+}
+
+/// Lock down one business function, vendor domain, jurisdiction or tenant
+///
+/// `log_action` and proxied requests matching the scope are refused (HTTP 403)
+/// until the lockdown is lifted or expires. A DORA Lite incident is opened for
+/// every lockdown and resolved when it ends.
 #[utoipa::path(
     post,
-    path = "/action/{seal_id}/require_approval",
-    request_body = HumanOversightRequest,
-    responses((status = 200, body = serde_json::Value))
+    path = "/lockdowns",
+    request_body = CreateLockdownRequest,
+    responses(
+        (status = 200, description = "Lockdown active", body = crate::core::lockdown::LockdownRule),
+        (status = 400, description = "Unknown scope, missing scope value or justification, or invalid expiry"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+    ),
+    tag = "System Management",
+    security(
+        ("bearer" = [])
+    )
 )]
-pub async fn require_human_oversight(
-    path: web::Path<String>,
-    req: web::Json<HumanOversightRequest>,
+pub async fn create_lockdown(
+    req: web::Json<CreateLockdownRequest>,
     data: web::Data<AppState>,
+    http_req: HttpRequest,
 ) -> impl Responder {
-    let seal_id = path.into_inner();
-    
-    if seal_id != req.seal_id {
+    use crate::core::lockdown::{LockdownScope, Lockdowns, MAX_DURATION_MINUTES};
+
+    // AUTHENTICATION & AUTHORIZATION
+    let claims = match authenticate_and_authorize(&http_req, &data.db_pool, "policy", "write").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
+
+    let Some(scope) = LockdownScope::parse(&req.scope_type) else {
         return HttpResponse::BadRequest().json(serde_json::json!({
-            "error": "Seal ID does not match"
+            "error": "Invalid input",
+            "message": "scope_type must be GLOBAL, BUSINESS_FUNCTION, VENDOR_DOMAIN, JURISDICTION or TENANT"
+        }));
+    };
+    let Some(scope_value) = scope.normalize(req.scope_value.as_deref()) else {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Invalid input",
+            "message": format!("scope_value is required for {} lockdowns", scope.as_str())
+        }));
+    };
+    let justification = req.justification.trim();
+    if justification.is_empty() || justification.len() > 2000 {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Invalid input",
+            "message": "justification must be between 1 and 2000 characters"
         }));
     }
-    
-    // Insert or update human oversight in database
-    let result = sqlx::query(
-        "INSERT INTO human_oversight (seal_id, status) VALUES ($1, 'PENDING')
-         ON CONFLICT (seal_id) DO UPDATE SET status = 'PENDING', updated_at = CURRENT_TIMESTAMP"
-    )
-    .bind(&seal_id)
-    .execute(&data.db_pool)
-    .await;
-
-    // Update compliance record
-    let _ = sqlx::query(
-        "UPDATE compliance_records SET human_oversight_status = 'PENDING' WHERE seal_id = $1"
-    )
-    .bind(&seal_id)
-    .execute(&data.db_pool)
-    .await;
+    if req.expires_in_minutes <= 0 || req.expires_in_minutes > MAX_DURATION_MINUTES {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Invalid input",
+            "message": format!("expires_in_minutes must be between 1 and {}", MAX_DURATION_MINUTES)
+        }));
+    }
+    let expires_at = chrono::Utc::now() + chrono::Duration::minutes(req.expires_in_minutes);
 
-    match result {
-        Ok(_) => {
-            // Trigger webhook event for human oversight required
-            let webhook_data = serde_json::json!({
-                "seal_id": seal_id,
-                "status": "PENDING",
-                "timestamp": Utc::now().to_rfc3339(),
-            });
-            trigger_webhook_event(&data.db_pool, "human_oversight.required", webhook_data).await;
-            
-            HttpResponse::Ok().json(serde_json::json!({
-                "status": "SUCCESS",
-                "message": "Human oversight required",
-                "seal_id": seal_id
-            }))
-        },
+    match Lockdowns::create(&data.db_pool, scope, scope_value.as_deref(), justification, Some(&claims.sub), expires_at).await {
+        Ok(lockdown) => HttpResponse::Ok().json(lockdown),
         Err(e) => {
             let request_id = generate_request_id();
-            let request_id = generate_request_id();
-            log_error_safely("requiring human oversight", &e, &request_id);
-            HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to require human oversight"
-            }))
+            log_error_safely("creating lockdown", &e, &request_id);
+            create_error_response(&request_id)
         }
     }
 }
 
-// 10. APPROVE ACTION (Human Oversight)
+/// List lockdowns
 #[utoipa::path(
-    post,
-    path = "/action/{seal_id}/approve",
-    request_body = HumanOversightResponse,
-    responses((status = 200, body = serde_json::Value))
+    get,
+    path = "/lockdowns",
+    params(
+        ("include_inactive" = Option<bool>, Query, description = "Also return lifted and expired lockdowns"),
+        ("days" = Option<i64>, Query, description = "History window for include_inactive (default 30)")
+    ),
+    responses(
+        (status = 200, description = "Lockdowns, newest first", body = Vec<crate::core::lockdown::LockdownRule>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+    ),
+    tag = "System Management",
+    security(
+        ("bearer" = [])
+    )
 )]
-pub async fn approve_action(
-    path: web::Path<String>,
-    req: web::Json<HumanOversightResponse>,
+pub async fn list_lockdowns(
+    query: web::Query<HashMap<String, String>>,
     data: web::Data<AppState>,
     http_req: HttpRequest,
 ) -> impl Responder {
-    // AUTHENTICATION & AUTHORIZATION - Critical: oversight.approve
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "oversight", "approve").await {
+    use crate::core::lockdown::Lockdowns;
+
+    // AUTHENTICATION & AUTHORIZATION
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "policy", "read").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
-    let seal_id = path.into_inner();
-    
-    // Update human oversight in database
-    let result = sqlx::query(
-        "UPDATE human_oversight 
-         SET status = 'APPROVED', reviewer_id = $1, decided_at = CURRENT_TIMESTAMP, comments = $2
-         WHERE seal_id = $3"
-    )
-    .bind(&req.reviewer_id)
-    .bind(&req.comments)
-    .bind(&seal_id)
-    .execute(&data.db_pool)
-    .await;
 
-    // Update compliance record
-    let _ = sqlx::query(
-        "UPDATE compliance_records SET human_oversight_status = 'APPROVED' WHERE seal_id = $1"
-    )
-    .bind(&seal_id)
-    .execute(&data.db_pool)
-    .await;
+    let include_inactive = query.get("include_inactive").map(|v| v == "true").unwrap_or(false);
+    let result = if include_inactive {
+        let days = query.get("days").and_then(|d| d.parse::<i64>().ok()).unwrap_or(30).clamp(1, 366);
+        Lockdowns::history(&data.db_pool, days).await
+    } else {
+        Lockdowns::active(&data.db_pool).await
+    };
 
     match result {
-        Ok(rows) if rows.rows_affected() > 0 => {
-            println!("✅ Action approved: {} by reviewer: {:?}", seal_id, req.reviewer_id);
-            
-            // Trigger webhook event for human oversight resolved
-            let webhook_data = serde_json::json!({
-                "seal_id": seal_id,
-                "status": "APPROVED",
-                "reviewer_id": req.reviewer_id,
-                "comments": req.comments,
-                "timestamp": Utc::now().to_rfc3339(),
-            });
-            trigger_webhook_event(&data.db_pool, "human_oversight.resolved", webhook_data).await;
-            
-            HttpResponse::Ok().json(serde_json::json!({
-                "status": "SUCCESS",
-                "message": "Action approved",
-                "seal_id": seal_id
-            }))
+        Ok(lockdowns) => HttpResponse::Ok().json(lockdowns),
+        Err(e) => {
+            let request_id = generate_request_id();
+            log_error_safely("listing lockdowns", &e, &request_id);
+            create_error_response(&request_id)
         }
-        _ => HttpResponse::NotFound().json(serde_json::json!({
-            "error": "Human oversight record not found"
-        }))
     }
 }
 
-// 11. REJECT ACTION (Human Oversight)
+/// Lift a lockdown before it expires and resolve its DORA Lite incident
 #[utoipa::path(
     post,
-    path = "/action/{seal_id}/reject",
-    request_body = HumanOversightResponse,
-    responses((status = 200, body = serde_json::Value))
+    path = "/lockdowns/{lockdown_id}/lift",
+    params(
+        ("lockdown_id" = String, Path, description = "Lockdown ID")
+    ),
+    request_body = LiftLockdownRequest,
+    responses(
+        (status = 200, description = "Lockdown lifted", body = crate::core::lockdown::LockdownRule),
+        (status = 404, description = "No active lockdown with this ID"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+    ),
+    tag = "System Management",
+    security(
+        ("bearer" = [])
+    )
 )]
-pub async fn reject_action(
+pub async fn lift_lockdown(
     path: web::Path<String>,
-    req: web::Json<HumanOversightResponse>,
+    req: Option<web::Json<LiftLockdownRequest>>,
     data: web::Data<AppState>,
     http_req: HttpRequest,
 ) -> impl Responder {
-    // AUTHENTICATION & AUTHORIZATION - Critical: oversight.approve
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "oversight", "approve").await {
+    // AUTHENTICATION & AUTHORIZATION
+    let claims = match authenticate_and_authorize(&http_req, &data.db_pool, "policy", "write").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
-    let seal_id = path.into_inner();
-    
-    // Update human oversight in database
-    let result = sqlx::query(
-        "UPDATE human_oversight 
-         SET status = 'REJECTED', reviewer_id = $1, decided_at = CURRENT_TIMESTAMP, comments = $2
-         WHERE seal_id = $3"
-    )
-    .bind(&req.reviewer_id)
-    .bind(&req.comments)
-    .bind(&seal_id)
-    .execute(&data.db_pool)
-    .await;
 
-    // Update compliance record
-    let _ = sqlx::query(
-        "UPDATE compliance_records 
-         SET human_oversight_status = 'REJECTED', status = 'REJECTED (Human Oversight)'
-         WHERE seal_id = $1"
-    )
-    .bind(&seal_id)
-    .execute(&data.db_pool)
-    .await;
+    let lockdown_id = match Uuid::parse_str(&path.into_inner()) {
+        Ok(id) => id,
+        Err(_) => return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Invalid input",
+            "message": "lockdown_id must be a UUID"
+        })),
+    };
+    let reason = req.as_ref().and_then(|r| r.reason.as_deref());
 
-    match result {
-        Ok(rows) if rows.rows_affected() > 0 => {
-            println!("❌ Action rejected: {} by reviewer: {:?}", seal_id, req.reviewer_id);
-            
-            // Trigger webhook event for human oversight resolved
-            let webhook_data = serde_json::json!({
-                "seal_id": seal_id,
-                "status": "REJECTED",
-                "reviewer_id": req.reviewer_id,
-                "comments": req.comments,
-                "timestamp": Utc::now().to_rfc3339(),
-            });
-            trigger_webhook_event(&data.db_pool, "human_oversight.resolved", webhook_data).await;
-            
-            HttpResponse::Ok().json(serde_json::json!({
-                "status": "SUCCESS",
-                "message": "Action rejected",
-                "seal_id": seal_id
-            }))
+    match crate::core::lockdown::Lockdowns::lift(&data.db_pool, lockdown_id, Some(&claims.sub), reason).await {
+        Ok(Some(lockdown)) => HttpResponse::Ok().json(lockdown),
+        Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "LOCKDOWN_NOT_ACTIVE",
+            "message": format!("Lockdown {} is not active", lockdown_id)
+        })),
+        Err(e) => {
+            let request_id = generate_request_id();
+            log_error_safely("lifting lockdown", &e, &request_id);
+            create_error_response(&request_id)
         }
-        _ => HttpResponse::NotFound().json(serde_json::json!({
-            "error": "Human oversight record not found"
-        }))
     }
 }
 
-// ========== PRIORITY 1: RISK ASSESSMENT (EU AI Act Article 9) ==========
+// ========== PRIORITY 1: DATA SUBJECT RIGHTS (GDPR Articles 15-22) ==========
 
-// 12. GET RISK ASSESSMENT
+// 6. DATA SUBJECT ACCESS (GDPR Article 15)
 #[utoipa::path(
     get,
-    path = "/risk_assessment/{seal_id}",
-    responses((status = 200, body = RiskAssessment))
+    path = "/data_subject/{user_id}/access",
+    responses((status = 200, body = DataSubjectAccessResponse))
 )]
-pub async fn get_risk_assessment(
+pub async fn data_subject_access(
     path: web::Path<String>,
-    http_req: HttpRequest,
     data: web::Data<AppState>,
+    http_req: HttpRequest,
 ) -> impl Responder {
-    // AUTHENTICATION & AUTHORIZATION
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "risk", "read").await {
+    // AUTHENTICATION & AUTHORIZATION - Critical: data_subject.read
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "data_subject", "read").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
 
-    let seal_id = path.into_inner();
+    let user_id = path.into_inner();
     
-    match sqlx::query_as::<_, RiskAssessmentDb>(
-        "SELECT * FROM risk_assessments WHERE seal_id = $1"
-    )
-    .bind(&seal_id)
-    .fetch_optional(&data.db_pool)
-    .await
-    {
-        Ok(Some(risk_db)) => {
-            let risk = RiskAssessment {
-                risk_level: risk_db.risk_level,
-                risk_factors: serde_json::from_value(risk_db.risk_factors).unwrap_or_default(),
-                mitigation_actions: serde_json::from_value(risk_db.mitigation_actions).unwrap_or_default(),
-                assessed_at: risk_db.assessed_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+    // Get all records for this user from database
+    match crate::core::audit_chain::AuditChain::subject_records(&data.db_pool, &user_id).await {
+        Ok(records) => {
+            let user_records: Vec<DataSubjectRecord> = records.into_iter().map(|r| {
+                DataSubjectRecord {
+                    timestamp: r.timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    action_summary: r.action_summary,
+                    seal_id: r.seal_id,
+                    status: r.status,
+                    risk_level: r.risk_level,
+                }
+            }).collect();
+            
+            let response = DataSubjectAccessResponse {
+                records: user_records,
+                format: "json".to_string(),
+                exported_at: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
             };
-            HttpResponse::Ok().json(risk)
+            
+            HttpResponse::Ok().json(response)
         }
-        Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
-            "error": "Risk assessment not found"
-        })),
         Err(e) => {
             let request_id = generate_request_id();
             let request_id = generate_request_id();
-            log_error_safely("fetching risk assessment", &e, &request_id);
+            log_error_safely("fetching user data", &e, &request_id);
             HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to fetch risk assessment"
+                "error": "Failed to fetch user data"
             }))
         }
     }
 }
 
-// 13. GET ALL RISKS (with pagination)
+// 7. DATA SUBJECT EXPORT (GDPR Article 20 - Data Portability)
 #[utoipa::path(
     get,
-    path = "/risks",
-    params(
-        ("page" = Option<i64>, Query, description = "Page number (default: 1)"),
-        ("limit" = Option<i64>, Query, description = "Items per page (default: 100, max: 1000)")
-    ),
-    responses((status = 200, body = serde_json::Value))
+    path = "/data_subject/{user_id}/export",
+    responses((status = 200, body = DataSubjectAccessResponse))
 )]
-pub async fn get_all_risks(
-    query: web::Query<std::collections::HashMap<String, String>>,
-    http_req: HttpRequest,
+pub async fn data_subject_export(
+    path: web::Path<String>,
     data: web::Data<AppState>,
+    http_req: HttpRequest,
 ) -> impl Responder {
-    // AUTHENTICATION & AUTHORIZATION
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "risk", "read").await {
+    // AUTHENTICATION & AUTHORIZATION - Critical: data_subject.export
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "data_subject", "export").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
-    let page = query
-        .get("page")
-        .and_then(|s| s.parse::<i64>().ok())
-        .unwrap_or(1)
-        .max(1);
-    let limit = (query
-        .get("limit")
-        .and_then(|s| s.parse::<i64>().ok())
-        .unwrap_or(100))
-        .min(1000)
-        .max(1);
-    let offset = (page - 1) * limit;
-
-    // Get total count
-    let total_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM risk_assessments")
-        .fetch_one(&data.db_pool)
-        .await
-        .unwrap_or(0);
 
-    match sqlx::query_as::<_, RiskAssessmentDb>(
-        "SELECT * FROM risk_assessments ORDER BY assessed_at DESC LIMIT $1 OFFSET $2"
-    )
-    .bind(limit)
-    .bind(offset)
-    .fetch_all(&data.db_pool)
-    .await
-    {
+    // Same as access, but could be extended with different format options
+    // Reuse the access logic - we already have auth, so just call it
+    // Note: We need to clone path since it's moved in the call
+    let user_id = path.into_inner();
+    
+    // Get all records for this user from database
+    match crate::core::audit_chain::AuditChain::subject_records(&data.db_pool, &user_id).await {
         Ok(records) => {
-            let all_risks: Vec<RiskAssessment> = records.into_iter().map(|r| {
-                RiskAssessment {
+            let user_records: Vec<DataSubjectRecord> = records.into_iter().map(|r| {
+                DataSubjectRecord {
+                    timestamp: r.timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    action_summary: r.action_summary,
+                    seal_id: r.seal_id,
+                    status: r.status,
                     risk_level: r.risk_level,
-                    risk_factors: serde_json::from_value(r.risk_factors).unwrap_or_default(),
-                    mitigation_actions: serde_json::from_value(r.mitigation_actions).unwrap_or_default(),
-                    assessed_at: r.assessed_at.format("%Y-%m-%d %H:%M:%S").to_string(),
                 }
             }).collect();
-            HttpResponse::Ok().json(serde_json::json!({
-                "data": all_risks,
-                "pagination": {
-                    "page": page,
-                    "limit": limit,
-                    "total": total_count,
-                    "total_pages": (total_count + limit - 1) / limit
-                }
-            }))
+            
+            let response = DataSubjectAccessResponse {
+                records: user_records,
+                format: "json".to_string(),
+                exported_at: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            };
+            
+            HttpResponse::Ok().json(response)
         }
         Err(e) => {
             let request_id = generate_request_id();
             let request_id = generate_request_id();
-            log_error_safely("fetching risks", &e, &request_id);
+            log_error_safely("fetching user data", &e, &request_id);
             HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to fetch risks"
+                "error": "Failed to fetch user data"
             }))
         }
     }
 }
 
-// ========== PRIORITY 1: DATA BREACH (GDPR Articles 33-34) ==========
-
-// 14. REPORT DATA BREACH
+// 8. DATA SUBJECT RECTIFICATION (GDPR Article 16)
 #[utoipa::path(
-    post,
-    path = "/breach_report",
-    request_body = DataBreachReport,
-    responses((status = 200, body = DataBreachResponse))
+    put,
+    path = "/data_subject/{user_id}/rectify",
+    request_body = DataSubjectRectificationRequest,
+    responses((status = 200, body = serde_json::Value))
 )]
-pub async fn report_breach(
-    req: web::Json<DataBreachReport>,
-    data: web::Data<AppState>,
+pub async fn data_subject_rectify(
+    path: web::Path<String>,
+    req: web::Json<DataSubjectRectificationRequest>,
+    data: web::Data<AppState>,
     http_req: HttpRequest,
 ) -> impl Responder {
-    // AUTHENTICATION & AUTHORIZATION - Critical: breach.write
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "breach", "write").await {
+    // AUTHENTICATION & AUTHORIZATION - Critical: data_subject.rectify
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "data_subject", "rectify").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
-    let breach_id = format!("BREACH-{}", Local::now().format("%Y%m%d-%H%M%S"));
-    let breach_report = req.into_inner();
-    let now = Utc::now();
-    
-    // Parse detected_at
-    let detected_at = chrono::NaiveDateTime::parse_from_str(&breach_report.detected_at, "%Y-%m-%d %H:%M:%S")
-        .map(|dt| DateTime::<Utc>::from_naive_utc_and_offset(dt, Utc))
-        .unwrap_or(now);
-    
-    // Simulate notification to authority (within 72 hours)
-    let authority_notified_at = Some(now);
-    
-    // Simulate user notification (if high risk)
-    let users_notified_at = if breach_report.breach_type == "DATA_LEAK" || breach_report.breach_type == "SYSTEM_COMPROMISE" {
-        Some(now)
-    } else {
-        None
-    };
+    let user_id = path.into_inner();
     
-    let affected_users_json = serde_json::to_value(&breach_report.affected_users).unwrap_or(serde_json::json!([]));
+    if user_id != req.user_id {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "User ID does not match"
+        }));
+    }
     
-    // Store breach in database
-    let result = sqlx::query(
-        "INSERT INTO data_breaches (
-            breach_id, description, breach_type, affected_users, detected_at,
-            affected_records_count, status, authority_notified_at, users_notified_at
-        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"
-    )
-    .bind(&breach_id)
-    .bind(&breach_report.description)
-    .bind(&breach_report.breach_type)
-    .bind(affected_users_json)
-    .bind(detected_at)
-    .bind(breach_report.affected_records_count.map(|c| c as i32))
-    .bind("REPORTED")
-    .bind(authority_notified_at)
-    .bind(users_notified_at)
-    .execute(&data.db_pool)
-    .await;
+    // Append the corrected content to the audit chain; the original record stays as logged
+    let status_text = "RECTIFIED (Art. 16)";
+    let result = crate::core::audit_chain::AuditChain::append_superseding(
+        &data.db_pool,
+        &req.seal_id,
+        crate::core::audit_chain::EntryType::Rectification,
+        status_text,
+        &format!("[RECTIFIED] {}", req.corrected_data),
+        Some(&user_id),
+    ).await;
 
     match result {
-        Ok(_) => {
-            let response = DataBreachResponse {
-                breach_id: breach_id.clone(),
-                status: "REPORTED".to_string(),
-                authority_notified_at: authority_notified_at.map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
-                users_notified_at: users_notified_at.map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
-            };
+        Ok(Some(rectification_seal_id)) => {
+            println!("📝 Rectified record: {} for user: {} ({})", req.seal_id, user_id, rectification_seal_id);
             
-            println!("🚨 Data breach reported: {} | Type: {} | Affected: {} users", 
-                breach_id, breach_report.breach_type, breach_report.affected_users.len());
+            // GDPR Article 19: Notify recipients of rectification
+            let notification_service = data.notification_service.clone();
+            let db_pool = data.db_pool.clone();
+            let user_id_clone = user_id.clone();
+            let seal_id_clone = req.seal_id.clone();
+            let corrected_data_clone = req.corrected_data.clone();
+            let now = Utc::now();
             
-            // Send notifications to affected users (GDPR Article 33)
-            if users_notified_at.is_some() {
-                let notification_service = data.notification_service.clone();
-                let db_pool = data.db_pool.clone();
-                let breach_id_clone = breach_id.clone();
-                let breach_type_clone = breach_report.breach_type.clone();
-                let description_clone = breach_report.description.clone();
-                let detected_at_clone = detected_at;
-                let affected_users_clone = breach_report.affected_users.clone();
+            // Spawn async task to send notifications to recipients (non-blocking)
+            tokio::spawn(async move {
+                // Get all recipients who received this user's data
+                let recipients: Vec<(String, String)> = sqlx::query_as(
+                    "SELECT recipient_name, recipient_contact 
+                     FROM data_recipients 
+                     WHERE user_id = $1 AND seal_id = $2"
+                )
+                .bind(&user_id_clone)
+                .bind(&seal_id_clone)
+                .fetch_all(&db_pool)
+                .await
+                .unwrap_or_default();
                 
-                // Spawn async task to send notifications (non-blocking)
-                tokio::spawn(async move {
-                    for user_id in &affected_users_clone {
-                        // Try email first, fallback to SMS if email fails
-                        let email_result = notification_service.notify_data_breach(
-                            &db_pool,
-                            user_id,
-                            &breach_id_clone,
-                            &breach_type_clone,
-                            &detected_at_clone,
-                            &description_clone,
-                            NotificationChannel::Email,
-                        ).await;
-                        
-                        if email_result.is_err() {
-                            // Fallback to SMS
-                            let _ = notification_service.notify_data_breach(
-                                &db_pool,
-                                user_id,
-                                &breach_id_clone,
-                                &breach_type_clone,
-                                &detected_at_clone,
-                                &description_clone,
-                                NotificationChannel::Sms,
-                            ).await;
-                        }
+                // Send notification to user
+                let _ = notification_service.send_notification(
+                    &db_pool,
+                    crate::integration::notifications::NotificationRequest {
+                        user_id: user_id_clone.clone(),
+                        notification_type: crate::integration::notifications::NotificationType::RectificationDone,
+                        channel: crate::integration::notifications::NotificationChannel::Email,
+                        subject: Some("Data Rectification Notification".to_string()),
+                        body: format!(
+                            "Dear User,\n\nYour personal data has been rectified as requested.\n\nSeal ID: {}\nCorrected Data: {}\n\nAs required by GDPR Article 19, we have notified all recipients of your personal data about this rectification.\n\nBest regards,\nVeridion Nexus Compliance Team",
+                            seal_id_clone, corrected_data_clone
+                        ),
+                        language: Some("en".to_string()),
+                        related_entity_type: Some("COMPLIANCE_RECORD".to_string()),
+                        related_entity_id: Some(seal_id_clone.clone()),
                     }
-                });
-            }
-            
-            // Trigger webhook event for data breach
-            let webhook_data = serde_json::json!({
-                "breach_id": breach_id,
-                "breach_type": breach_report.breach_type,
-                "description": breach_report.description,
-                "affected_users_count": breach_report.affected_users.len(),
-                "detected_at": detected_at.to_rfc3339(),
-                "authority_notified_at": authority_notified_at.map(|dt| dt.to_rfc3339()),
-                "users_notified_at": users_notified_at.map(|dt| dt.to_rfc3339()),
+                ).await;
+                
+                // Log notification to recipients (GDPR Article 19 requirement)
+                for (name, contact) in recipients {
+                    let _ = sqlx::query(
+                        "INSERT INTO user_notifications (
+                            id, notification_id, user_id, notification_type, channel,
+                            subject, body, status, language, related_entity_type, related_entity_id
+                        ) VALUES (gen_random_uuid(), $1, $2, 'RECTIFICATION_NOTIFICATION', 'EMAIL', 
+                                  $3, $4, 'PENDING', 'en', 'RECIPIENT', $5)"
+                    )
+                    .bind(format!("NOTIF-RECIP-{}", uuid::Uuid::new_v4().to_string().replace("-", "").chars().take(12).collect::<String>()))
+                    .bind(&user_id_clone)
+                    .bind(Some(format!("Data Rectification - {}", name)))
+                    .bind(format!("Data subject {} has requested rectification of their personal data. Please update your records accordingly.", user_id_clone))
+                    .bind(Some(contact))
+                    .execute(&db_pool)
+                    .await;
+                }
             });
-            trigger_webhook_event(&data.db_pool, "data_breach.detected", webhook_data).await;
             
-            HttpResponse::Ok().json(response)
-        }
-        Err(e) => {
-            let request_id = generate_request_id();
-            let request_id = generate_request_id();
-            log_error_safely("reporting breach", &e, &request_id);
-            HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to report breach"
+            HttpResponse::Ok().json(serde_json::json!({
+                "status": "SUCCESS",
+                "message": "Data rectified successfully. Recipients will be notified as per GDPR Article 19.",
+                "rectification_seal_id": rectification_seal_id
             }))
         }
+        _ => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Record not found or access denied"
+        }))
     }
 }
 
-// 15. GET ALL BREACHES (with pagination)
+// ========== GDPR Article 18: Right to Restriction of Processing ==========
+
+// 8.1. REQUEST PROCESSING RESTRICTION (GDPR Article 18)
 #[utoipa::path(
-    get,
-    path = "/breaches",
-    params(
-        ("page" = Option<i64>, Query, description = "Page number (default: 1)"),
-        ("limit" = Option<i64>, Query, description = "Items per page (default: 100, max: 1000)")
-    ),
-    responses((status = 200, body = serde_json::Value))
+    post,
+    path = "/data_subject/{user_id}/restrict",
+    request_body = ProcessingRestrictionRequest,
+    responses((status = 200, body = ProcessingRestrictionResponse))
 )]
-pub async fn get_breaches(
-    query: web::Query<std::collections::HashMap<String, String>>,
+pub async fn request_processing_restriction(
+    path: web::Path<String>,
+    req: web::Json<ProcessingRestrictionRequest>,
     data: web::Data<AppState>,
     http_req: HttpRequest,
 ) -> impl Responder {
-    // AUTHENTICATION & AUTHORIZATION - Critical: breach.read
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "breach", "read").await {
+    // AUTHENTICATION & AUTHORIZATION - Critical: data_subject.write
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "data_subject", "write").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
 
-    let page = query
-        .get("page")
-        .and_then(|s| s.parse::<i64>().ok())
-        .unwrap_or(1)
-        .max(1);
-    let limit = (query
-        .get("limit")
-        .and_then(|s| s.parse::<i64>().ok())
-        .unwrap_or(100))
-        .min(1000)
-        .max(1);
-    let offset = (page - 1) * limit;
-
-    // Get total count
-    let total_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM data_breaches")
-        .fetch_one(&data.db_pool)
-        .await
-        .unwrap_or(0);
+    let user_id = path.into_inner();
+    if user_id != req.user_id {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "User ID does not match"
+        }));
+    }
 
-    match sqlx::query_as::<_, DataBreachDb>(
-        "SELECT * FROM data_breaches ORDER BY detected_at DESC LIMIT $1 OFFSET $2"
+    // Check if there's already an active restriction
+    let existing: Option<(String,)> = sqlx::query_as(
+        "SELECT restriction_id FROM processing_restrictions 
+         WHERE user_id = $1 AND status = 'ACTIVE'"
     )
-    .bind(limit)
-    .bind(offset)
-    .fetch_all(&data.db_pool)
+    .bind(&user_id)
+    .fetch_optional(&data.db_pool)
     .await
-    {
-        Ok(records) => {
-            let breaches: Vec<DataBreachReport> = records.into_iter().map(|r| {
-                DataBreachReport {
-                    description: r.description,
-                    breach_type: r.breach_type,
-                    affected_users: serde_json::from_value(r.affected_users).unwrap_or_default(),
-                    detected_at: r.detected_at.format("%Y-%m-%d %H:%M:%S").to_string(),
-                    affected_records_count: r.affected_records_count.map(|c| c.max(0) as u32),
-                }
-            }).collect();
-            HttpResponse::Ok().json(serde_json::json!({
-                "data": breaches,
-                "pagination": {
-                    "page": page,
-                    "limit": limit,
-                    "total": total_count,
-                    "total_pages": (total_count + limit - 1) / limit
-                }
-            }))
-        }
-        Err(e) => {
-            let request_id = generate_request_id();
-            let request_id = generate_request_id();
-            log_error_safely("fetching breaches", &e, &request_id);
-            HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to fetch breaches"
-            }))
-        }
-    }
-}
+    .unwrap_or(None);
 
-// ========== PRIORITY 2: CONSENT MANAGEMENT (GDPR Articles 6, 7) ==========
+    if existing.is_some() {
+        return HttpResponse::Conflict().json(serde_json::json!({
+            "error": "User already has an active processing restriction",
+            "restriction_id": existing.unwrap().0
+        }));
+    }
 
-// 16. GRANT CONSENT
-#[utoipa::path(
-    post,
-    path = "/consent",
-    request_body = ConsentRequest,
-    responses((status = 200, body = ConsentResponse))
-)]
-pub async fn grant_consent(
-    req: web::Json<ConsentRequest>,
-    http_req: HttpRequest,
-    data: web::Data<AppState>,
-) -> impl Responder {
-    // AUTHENTICATION & AUTHORIZATION
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "consent", "write").await {
-        Ok(c) => c,
-        Err(resp) => return resp,
-    };
-    let consent_req = req.into_inner();
+    let restriction_id = format!("RESTRICT-{}", Local::now().format("%Y%m%d-%H%M%S"));
     let now = Utc::now();
     
     // Parse expires_at if provided
-    let expires_at = consent_req.expires_at.as_ref()
+    let expires_at = req.expires_at.as_ref()
         .and_then(|s| chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").ok())
         .map(|dt| DateTime::<Utc>::from_naive_utc_and_offset(dt, Utc));
-    
-    // Get current version for this consent type
-    let current_version: Option<i32> = sqlx::query_scalar(
-        "SELECT MAX(version) FROM consent_records WHERE user_id = $1 AND consent_type = $2"
-    )
-    .bind(&consent_req.user_id)
-    .bind(&consent_req.consent_type)
-    .fetch_optional(&data.db_pool)
-    .await
-    .unwrap_or(None);
-    
-    let version = current_version.map(|v| v + 1).unwrap_or(1);
-    
-    // Withdraw any existing consent of this type
-    let _ = sqlx::query(
-        "UPDATE consent_records 
-         SET granted = false, withdrawn_at = CURRENT_TIMESTAMP
-         WHERE user_id = $1 AND consent_type = $2 AND granted = true AND withdrawn_at IS NULL"
-    )
-    .bind(&consent_req.user_id)
-    .bind(&consent_req.consent_type)
-    .execute(&data.db_pool)
-    .await;
-    
-    // Insert new consent
-    let consent_id = Uuid::new_v4();
+
+    let restricted_actions_json = serde_json::to_value(req.restricted_actions.as_ref().unwrap_or(&vec![]))
+        .unwrap_or(serde_json::json!([]));
+
     let result = sqlx::query(
-        "INSERT INTO consent_records (
-            id, user_id, consent_type, purpose, legal_basis, granted, granted_at,
-            expires_at, consent_method, ip_address, user_agent, consent_text, version
-        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
-        RETURNING id"
+        "INSERT INTO processing_restrictions (
+            restriction_id, user_id, restriction_type, restricted_actions,
+            reason, status, requested_at, expires_at
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)"
     )
-    .bind(consent_id)
-    .bind(&consent_req.user_id)
-    .bind(&consent_req.consent_type)
-    .bind(&consent_req.purpose)
-    .bind(&consent_req.legal_basis)
-    .bind(true)
+    .bind(&restriction_id)
+    .bind(&user_id)
+    .bind(&req.restriction_type)
+    .bind(restricted_actions_json)
+    .bind(&req.reason)
+    .bind("ACTIVE")
     .bind(now)
     .bind(expires_at)
-    .bind(&consent_req.consent_method)
-    .bind(&consent_req.ip_address)
-    .bind(&consent_req.user_agent)
-    .bind(&consent_req.consent_text)
-    .bind(version)
-    .fetch_one(&data.db_pool)
+    .execute(&data.db_pool)
     .await;
 
     match result {
         Ok(_) => {
-            let user_id = consent_req.user_id.clone();
-            let consent_type = consent_req.consent_type.clone();
-            
-            let response = ConsentResponse {
-                consent_id: consent_id.to_string(),
+            let response = ProcessingRestrictionResponse {
+                restriction_id: restriction_id.clone(),
                 user_id: user_id.clone(),
-                consent_type: consent_type.clone(),
-                granted: true,
-                granted_at: Some(now.format("%Y-%m-%d %H:%M:%S").to_string()),
+                restriction_type: req.restriction_type.clone(),
+                status: "ACTIVE".to_string(),
+                requested_at: now.format("%Y-%m-%d %H:%M:%S").to_string(),
                 expires_at: expires_at.map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
-                version,
             };
-            
-            println!("✅ Consent granted: {} for user: {}", consent_type, user_id);
+
+            println!("🔒 Processing restriction requested: {} | User: {} | Type: {}", 
+                restriction_id, user_id, req.restriction_type);
+
             HttpResponse::Ok().json(response)
         }
         Err(e) => {
             let request_id = generate_request_id();
             let request_id = generate_request_id();
-            log_error_safely("granting consent", &e, &request_id);
+            log_error_safely("creating restriction", &e, &request_id);
             HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to grant consent"
+                "error": "Failed to create processing restriction"
             }))
         }
     }
 }
 
-// 17. WITHDRAW CONSENT
+// 8.2. LIFT PROCESSING RESTRICTION (GDPR Article 18)
 #[utoipa::path(
     post,
-    path = "/consent/withdraw",
-    request_body = WithdrawConsentRequest,
+    path = "/data_subject/{user_id}/lift_restriction",
+    request_body = LiftRestrictionRequest,
     responses((status = 200, body = serde_json::Value))
 )]
-pub async fn withdraw_consent(
-    req: web::Json<WithdrawConsentRequest>,
-    http_req: HttpRequest,
+pub async fn lift_processing_restriction(
+    path: web::Path<String>,
+    req: web::Json<LiftRestrictionRequest>,
     data: web::Data<AppState>,
+    http_req: HttpRequest,
 ) -> impl Responder {
-    // AUTHENTICATION & AUTHORIZATION
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "consent", "write").await {
+    // AUTHENTICATION & AUTHORIZATION - Critical: data_subject.write
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "data_subject", "write").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
 
-    let withdraw_req = req.into_inner();
-    
-    let result = if let Some(consent_type) = withdraw_req.consent_type {
-        // Withdraw specific consent type
-        sqlx::query(
-            "UPDATE consent_records 
-             SET granted = false, withdrawn_at = CURRENT_TIMESTAMP
-             WHERE user_id = $1 AND consent_type = $2 AND granted = true AND withdrawn_at IS NULL"
-        )
-        .bind(&withdraw_req.user_id)
-        .bind(&consent_type)
-        .execute(&data.db_pool)
-        .await
-    } else {
-        // Withdraw all consents for user
-        sqlx::query(
-            "UPDATE consent_records 
-             SET granted = false, withdrawn_at = CURRENT_TIMESTAMP
-             WHERE user_id = $1 AND granted = true AND withdrawn_at IS NULL"
-        )
-        .bind(&withdraw_req.user_id)
-        .execute(&data.db_pool)
-        .await
-    };
+    let user_id = path.into_inner();
+    if user_id != req.user_id {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "User ID does not match"
+        }));
+    }
+
+    let now = Utc::now();
+    let lifted_by = req.lifted_by.as_deref().unwrap_or("SYSTEM");
+
+    let result = sqlx::query(
+        "UPDATE processing_restrictions 
+         SET status = 'LIFTED', lifted_at = $1, lifted_by = $2, lift_reason = $3
+         WHERE user_id = $4 AND status = 'ACTIVE'"
+    )
+    .bind(now)
+    .bind(lifted_by)
+    .bind(&req.reason)
+    .bind(&user_id)
+    .execute(&data.db_pool)
+    .await;
 
     match result {
-        Ok(rows) if rows.rows_affected() > 0 => {
-            println!("❌ Consent withdrawn for user: {}", withdraw_req.user_id);
+        Ok(rows) => {
+            if rows.rows_affected() == 0 {
+                return HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "No active restriction found for this user"
+                }));
+            }
+
+            println!("🔓 Processing restriction lifted: User: {} | Lifted by: {}", user_id, lifted_by);
+
             HttpResponse::Ok().json(serde_json::json!({
                 "status": "SUCCESS",
-                "message": "Consent withdrawn successfully"
+                "message": "Processing restriction lifted successfully",
+                "user_id": user_id,
+                "lifted_at": now.format("%Y-%m-%d %H:%M:%S").to_string()
             }))
         }
-        Ok(_) => HttpResponse::NotFound().json(serde_json::json!({
-            "error": "No active consent found"
-        })),
         Err(e) => {
             let request_id = generate_request_id();
             let request_id = generate_request_id();
-            log_error_safely("withdrawing consent", &e, &request_id);
+            log_error_safely("lifting restriction", &e, &request_id);
             HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to withdraw consent"
+                "error": "Failed to lift processing restriction"
             }))
         }
     }
 }
 
-// 18. GET USER CONSENTS
+// 8.3. GET PROCESSING RESTRICTIONS (GDPR Article 18)
 #[utoipa::path(
     get,
-    path = "/consent/{user_id}",
-    responses((status = 200, body = UserConsentsResponse))
+    path = "/data_subject/{user_id}/restrictions",
+    responses((status = 200, body = RestrictionsResponse))
 )]
-pub async fn get_user_consents(
+pub async fn get_processing_restrictions(
     path: web::Path<String>,
-    http_req: HttpRequest,
     data: web::Data<AppState>,
+    http_req: HttpRequest,
 ) -> impl Responder {
-    // AUTHENTICATION & AUTHORIZATION
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "consent", "read").await {
+    // AUTHENTICATION & AUTHORIZATION - Critical: data_subject.read
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "data_subject", "read").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
 
     let user_id = path.into_inner();
-    
-    match sqlx::query_as::<_, ConsentRecordDb>(
-        "SELECT * FROM consent_records 
-         WHERE user_id = $1 
-         ORDER BY created_at DESC"
+
+    #[derive(sqlx::FromRow)]
+    struct RestrictionRow {
+        restriction_id: String,
+        user_id: String,
+        restriction_type: String,
+        status: String,
+        requested_at: DateTime<Utc>,
+        expires_at: Option<DateTime<Utc>>,
+    }
+
+    let result = sqlx::query_as::<_, RestrictionRow>(
+        "SELECT restriction_id, user_id, restriction_type, status, requested_at, expires_at
+         FROM processing_restrictions
+         WHERE user_id = $1
+         ORDER BY requested_at DESC"
     )
     .bind(&user_id)
     .fetch_all(&data.db_pool)
-    .await
-    {
-        Ok(records) => {
-            let consents: Vec<ConsentResponse> = records.into_iter().map(|r| {
-                ConsentResponse {
-                    consent_id: r.id.to_string(),
+    .await;
+
+    match result {
+        Ok(rows) => {
+            let restrictions: Vec<ProcessingRestrictionResponse> = rows.into_iter().map(|r| {
+                ProcessingRestrictionResponse {
+                    restriction_id: r.restriction_id,
                     user_id: r.user_id,
-                    consent_type: r.consent_type,
-                    granted: r.granted && r.withdrawn_at.is_none(),
-                    granted_at: r.granted_at.map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
+                    restriction_type: r.restriction_type,
+                    status: r.status,
+                    requested_at: r.requested_at.format("%Y-%m-%d %H:%M:%S").to_string(),
                     expires_at: r.expires_at.map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
-                    version: r.version,
                 }
             }).collect();
-            
-            let response = UserConsentsResponse {
-                user_id,
-                consents,
+
+            let response = RestrictionsResponse {
+                user_id: user_id.clone(),
+                restrictions,
             };
-            
+
             HttpResponse::Ok().json(response)
         }
         Err(e) => {
             let request_id = generate_request_id();
             let request_id = generate_request_id();
-            log_error_safely("fetching consents", &e, &request_id);
+            log_error_safely("fetching restrictions", &e, &request_id);
             HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to fetch consents"
+                "error": "Failed to fetch processing restrictions"
             }))
         }
     }
 }
 
-// 19. CHECK CONSENT (Helper function for log_action)
-pub async fn check_consent(
-    db_pool: &sqlx::PgPool,
-    user_id: &str,
-    consent_type: &str,
-) -> Result<bool, sqlx::Error> {
-    let result: Option<bool> = sqlx::query_scalar(
-        "SELECT granted FROM consent_records 
-         WHERE user_id = $1 
-           AND consent_type = $2 
-           AND granted = true 
-           AND withdrawn_at IS NULL
-           AND (expires_at IS NULL OR expires_at > CURRENT_TIMESTAMP)
-         ORDER BY version DESC
-         LIMIT 1"
-    )
-    .bind(user_id)
-    .bind(consent_type)
-    .fetch_optional(db_pool)
-    .await?;
-    
-    Ok(result.unwrap_or(false))
-}
-
-// ========== PRIORITY 2: DPIA TRACKING (GDPR Article 35) ==========
+// ========== GDPR Article 21: Right to Object ==========
 
-// 20. CREATE DPIA
+// 8.4. REQUEST PROCESSING OBJECTION (GDPR Article 21)
 #[utoipa::path(
     post,
-    path = "/dpia",
-    request_body = DpiaRequest,
-    responses((status = 200, body = DpiaResponse))
+    path = "/data_subject/{user_id}/object",
+    request_body = ProcessingObjectionRequest,
+    responses((status = 200, body = ProcessingObjectionResponse))
 )]
-pub async fn create_dpia(
-    req: web::Json<DpiaRequest>,
-    http_req: HttpRequest,
+pub async fn request_processing_objection(
+    path: web::Path<String>,
+    req: web::Json<ProcessingObjectionRequest>,
     data: web::Data<AppState>,
+    http_req: HttpRequest,
 ) -> impl Responder {
-    // AUTHENTICATION & AUTHORIZATION
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "dpia", "write").await {
+    // AUTHENTICATION & AUTHORIZATION - Critical: data_subject.write
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "data_subject", "write").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
 
-    let dpia_req = req.into_inner();
+    let user_id = path.into_inner();
+    if user_id != req.user_id {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "User ID does not match"
+        }));
+    }
+
+    // Check if there's already an active objection of this type
+    let existing: Option<(String,)> = sqlx::query_as(
+        "SELECT objection_id FROM processing_objections 
+         WHERE user_id = $1 AND objection_type = $2 AND status = 'ACTIVE'"
+    )
+    .bind(&user_id)
+    .bind(&req.objection_type)
+    .fetch_optional(&data.db_pool)
+    .await
+    .unwrap_or(None);
+
+    if existing.is_some() {
+        return HttpResponse::Conflict().json(serde_json::json!({
+            "error": format!("User already has an active {} objection", req.objection_type),
+            "objection_id": existing.unwrap().0
+        }));
+    }
+
+    let objection_id = format!("OBJECT-{}-{}", Local::now().format("%Y%m%d-%H%M%S"), Uuid::new_v4().to_string().chars().take(8).collect::<String>());
     let now = Utc::now();
-    
-    // Generate DPIA ID
-    let dpia_id = format!("DPIA-{}-{:03}", now.format("%Y"), 
-        sqlx::query_scalar::<_, i64>(
-            "SELECT COUNT(*) FROM dpia_records WHERE dpia_id LIKE $1"
-        )
-        .bind(format!("DPIA-{}%", now.format("%Y")))
-        .fetch_one(&data.db_pool)
-        .await
-        .unwrap_or(0) + 1);
-    
-    // Determine if consultation is required (Article 36 - High risk)
-    let consultation_required = dpia_req.risk_level == "HIGH" || 
-        dpia_req.identified_risks.iter().any(|r| 
-            r.to_lowercase().contains("discrimination") || 
-            r.to_lowercase().contains("systematic")
-        );
-    
-    let identified_risks_json = serde_json::to_value(&dpia_req.identified_risks).unwrap_or(serde_json::json!([]));
-    let mitigation_measures_json = serde_json::to_value(&dpia_req.mitigation_measures).unwrap_or(serde_json::json!([]));
-    
-    let dpia_uuid = Uuid::new_v4();
+
+    let objected_actions_json = serde_json::to_value(req.objected_actions.as_ref().unwrap_or(&vec![]))
+        .unwrap_or(serde_json::json!([]));
+
     let result = sqlx::query(
-        "INSERT INTO dpia_records (
-            id, dpia_id, activity_name, description, legal_basis,
-            data_categories, data_subject_categories, processing_purposes,
-            risk_level, identified_risks, mitigation_measures, residual_risks,
-            consultation_required, status, created_by
-        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
-        RETURNING dpia_id"
+        "INSERT INTO processing_objections (
+            objection_id, user_id, objection_type, objected_actions,
+            legal_basis, reason, status, requested_at
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)"
     )
-    .bind(dpia_uuid)
-    .bind(&dpia_id)
-    .bind(&dpia_req.activity_name)
-    .bind(&dpia_req.description)
-    .bind(&dpia_req.legal_basis)
-    .bind(&dpia_req.data_categories)
-    .bind(&dpia_req.data_subject_categories)
-    .bind(&dpia_req.processing_purposes)
-    .bind(&dpia_req.risk_level)
-    .bind(identified_risks_json)
-    .bind(mitigation_measures_json)
-    .bind(serde_json::json!([]))
-    .bind(consultation_required)
-    .bind("DRAFT")
-    .bind(&dpia_req.created_by)
-    .fetch_one(&data.db_pool)
+    .bind(&objection_id)
+    .bind(&user_id)
+    .bind(&req.objection_type)
+    .bind(objected_actions_json)
+    .bind(&req.legal_basis)
+    .bind(&req.reason)
+    .bind("ACTIVE")
+    .bind(now)
+    .execute(&data.db_pool)
     .await;
 
     match result {
         Ok(_) => {
-            let response = DpiaResponse {
-                dpia_id: dpia_id.clone(),
-                activity_name: dpia_req.activity_name,
-                status: "DRAFT".to_string(),
-                risk_level: dpia_req.risk_level,
-                consultation_required,
-                created_at: now.format("%Y-%m-%d %H:%M:%S").to_string(),
+            let response = ProcessingObjectionResponse {
+                objection_id: objection_id.clone(),
+                user_id: user_id.clone(),
+                objection_type: req.objection_type.clone(),
+                status: "ACTIVE".to_string(),
+                requested_at: now.format("%Y-%m-%d %H:%M:%S").to_string(),
+                rejection_reason: None,
             };
-            
-            println!("📋 DPIA created: {} for activity: {}", dpia_id, response.activity_name);
+
+            println!("🚫 Processing objection requested: {} | User: {} | Type: {}", 
+                objection_id, user_id, req.objection_type);
+
             HttpResponse::Ok().json(response)
         }
         Err(e) => {
             let request_id = generate_request_id();
             let request_id = generate_request_id();
-            log_error_safely("creating DPIA", &e, &request_id);
+            log_error_safely("creating objection", &e, &request_id);
             HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to create DPIA"
+                "error": "Failed to create processing objection"
             }))
         }
     }
 }
 
-// 21. UPDATE DPIA
+// 8.5. WITHDRAW PROCESSING OBJECTION (GDPR Article 21)
 #[utoipa::path(
-    put,
-    path = "/dpia/{dpia_id}",
-    request_body = UpdateDpiaRequest,
-    responses((status = 200, body = DpiaResponse))
+    post,
+    path = "/data_subject/{user_id}/withdraw_objection",
+    request_body = WithdrawObjectionRequest,
+    responses((status = 200, body = serde_json::Value))
 )]
-pub async fn update_dpia(
+pub async fn withdraw_processing_objection(
     path: web::Path<String>,
-    req: web::Json<UpdateDpiaRequest>,
-    http_req: HttpRequest,
+    req: web::Json<WithdrawObjectionRequest>,
     data: web::Data<AppState>,
+    http_req: HttpRequest,
 ) -> impl Responder {
-    // AUTHENTICATION & AUTHORIZATION
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "dpia", "write").await {
+    // AUTHENTICATION & AUTHORIZATION - Critical: data_subject.write
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "data_subject", "write").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
 
-    let dpia_id = path.into_inner();
-    let update_req = req.into_inner();
-    
-    // Clone values before using them
-    let status_clone = update_req.status.clone();
-    let reviewed_by_clone = update_req.reviewed_by.clone();
-    let next_review_date_parsed = update_req.next_review_date.as_ref()
-        .and_then(|s| chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").ok())
-        .map(|dt| DateTime::<Utc>::from_naive_utc_and_offset(dt, Utc));
-    let residual_risks_json = update_req.residual_risks.as_ref()
-        .map(|r| serde_json::to_value(r).unwrap_or(serde_json::json!([])));
-    
-    // For simplicity, use a fixed query structure
-    let result = if status_clone.is_some() && reviewed_by_clone.is_some() {
-        sqlx::query(
-            "UPDATE dpia_records 
-             SET status = $1, risk_level = COALESCE($2, risk_level),
-                 residual_risks = COALESCE($3, residual_risks),
-                 reviewed_by = $4, reviewed_at = CURRENT_TIMESTAMP,
-                 approval_date = CASE WHEN $1 = 'APPROVED' THEN CURRENT_TIMESTAMP ELSE approval_date END,
-                 next_review_date = COALESCE($5, next_review_date),
-                 updated_at = CURRENT_TIMESTAMP
-             WHERE dpia_id = $6"
-        )
-        .bind(status_clone.unwrap())
-        .bind(update_req.risk_level)
-        .bind(residual_risks_json)
-        .bind(reviewed_by_clone)
-        .bind(next_review_date_parsed)
-        .bind(&dpia_id)
-        .execute(&data.db_pool)
-        .await
-    } else {
-        // Simplified update for other cases
-        sqlx::query(
-            "UPDATE dpia_records 
-             SET status = COALESCE($1, status),
-                 risk_level = COALESCE($2, risk_level),
-                 residual_risks = COALESCE($3, residual_risks),
-                 next_review_date = COALESCE($4, next_review_date),
-                 updated_at = CURRENT_TIMESTAMP
-             WHERE dpia_id = $5"
-        )
-        .bind(status_clone)
-        .bind(update_req.risk_level)
-        .bind(residual_risks_json)
-        .bind(next_review_date_parsed)
-        .bind(&dpia_id)
-        .execute(&data.db_pool)
-        .await
-    };
+    let user_id = path.into_inner();
+    if user_id != req.user_id {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "User ID does not match"
+        }));
+    }
 
-    match result {
-        Ok(rows) if rows.rows_affected() > 0 => {
-            // Fetch updated DPIA
-            match sqlx::query_as::<_, DpiaRecordDb>(
-                "SELECT * FROM dpia_records WHERE dpia_id = $1"
-            )
-            .bind(&dpia_id)
-            .fetch_optional(&data.db_pool)
-            .await
-            {
-                Ok(Some(dpia)) => {
-                    let response = DpiaResponse {
-                        dpia_id: dpia.dpia_id,
-                        activity_name: dpia.activity_name,
-                        status: dpia.status,
-                        risk_level: dpia.risk_level,
-                        consultation_required: dpia.consultation_required,
-                        created_at: dpia.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
-                    };
-                    HttpResponse::Ok().json(response)
-                }
-                _ => HttpResponse::InternalServerError().json(serde_json::json!({
-                    "error": "Failed to fetch updated DPIA"
-                }))
-            }
-        }
-        _ => HttpResponse::NotFound().json(serde_json::json!({
-            "error": "DPIA not found"
-        }))
-    }
-}
+    let now = Utc::now();
+    let withdrawn_by = "USER"; // User withdrawing their own objection
 
-// 22. GET ALL DPIAs (with pagination)
-#[utoipa::path(
-    get,
-    path = "/dpias",
-    params(
-        ("page" = Option<i64>, Query, description = "Page number (default: 1)"),
-        ("limit" = Option<i64>, Query, description = "Items per page (default: 100, max: 1000)")
-    ),
-    responses((status = 200, body = serde_json::Value))
-)]
-pub async fn get_all_dpias(
-    query: web::Query<std::collections::HashMap<String, String>>,
-    http_req: HttpRequest,
-    data: web::Data<AppState>,
-) -> impl Responder {
-    // AUTHENTICATION & AUTHORIZATION
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "dpia", "read").await {
-        Ok(c) => c,
-        Err(resp) => return resp,
-    };
-    let page = query
-        .get("page")
-        .and_then(|s| s.parse::<i64>().ok())
-        .unwrap_or(1)
-        .max(1);
-    let limit = (query
-        .get("limit")
-        .and_then(|s| s.parse::<i64>().ok())
-        .unwrap_or(100))
-        .min(1000)
-        .max(1);
-    let offset = (page - 1) * limit;
+    let result = sqlx::query(
+        "UPDATE processing_objections 
+         SET status = 'WITHDRAWN', withdrawn_at = $1, withdrawn_by = $2, withdraw_reason = $3
+         WHERE user_id = $4 AND status = 'ACTIVE'"
+    )
+    .bind(now)
+    .bind(withdrawn_by)
+    .bind(&req.reason)
+    .bind(&user_id)
+    .execute(&data.db_pool)
+    .await;
 
-    // Get total count
-    let total_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM dpia_records")
-        .fetch_one(&data.db_pool)
-        .await
-        .unwrap_or(0);
+    match result {
+        Ok(rows) => {
+            if rows.rows_affected() == 0 {
+                return HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "No active objection found for this user"
+                }));
+            }
+
+            println!("✅ Processing objection withdrawn: User: {}", user_id);
 
-    match sqlx::query_as::<_, DpiaRecordDb>(
-        "SELECT * FROM dpia_records ORDER BY created_at DESC LIMIT $1 OFFSET $2"
-    )
-    .bind(limit)
-    .bind(offset)
-    .fetch_all(&data.db_pool)
-    .await
-    {
-        Ok(records) => {
-            let dpias: Vec<DpiaResponse> = records.into_iter().map(|r| {
-                DpiaResponse {
-                    dpia_id: r.dpia_id,
-                    activity_name: r.activity_name,
-                    status: r.status,
-                    risk_level: r.risk_level,
-                    consultation_required: r.consultation_required,
-                    created_at: r.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
-                }
-            }).collect();
-            
             HttpResponse::Ok().json(serde_json::json!({
-                "data": dpias,
-                "pagination": {
-                    "page": page,
-                    "limit": limit,
-                    "total": total_count,
-                    "total_pages": (total_count + limit - 1) / limit
-                }
+                "status": "SUCCESS",
+                "message": "Processing objection withdrawn successfully",
+                "user_id": user_id,
+                "withdrawn_at": now.format("%Y-%m-%d %H:%M:%S").to_string()
             }))
         }
         Err(e) => {
             let request_id = generate_request_id();
             let request_id = generate_request_id();
-            log_error_safely("fetching DPIAs", &e, &request_id);
+            log_error_safely("withdrawing objection", &e, &request_id);
             HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to fetch DPIAs"
+                "error": "Failed to withdraw processing objection"
             }))
         }
     }
 }
 
-// ========== PRIORITY 2: RETENTION PERIOD AUTOMATION (GDPR Article 5(1)(e)) ==========
-
-// 23. CREATE RETENTION POLICY
+// 8.6. REJECT PROCESSING OBJECTION (GDPR Article 21(1) - must provide reason)
 #[utoipa::path(
     post,
-    path = "/retention/policy",
-    request_body = RetentionPolicyRequest,
-    responses((status = 200, body = RetentionPolicyResponse))
+    path = "/data_subject/{user_id}/reject_objection",
+    request_body = RejectObjectionRequest,
+    responses((status = 200, body = serde_json::Value))
 )]
-pub async fn create_retention_policy(
-    req: web::Json<RetentionPolicyRequest>,
-    http_req: HttpRequest,
+pub async fn reject_processing_objection(
+    path: web::Path<String>,
+    req: web::Json<RejectObjectionRequest>,
     data: web::Data<AppState>,
+    http_req: HttpRequest,
 ) -> impl Responder {
-    // AUTHENTICATION & AUTHORIZATION
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "retention", "write").await {
+    // AUTHENTICATION & AUTHORIZATION - Critical: data_subject.write (admin only for rejection)
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "data_subject", "write").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
 
-    let policy_req = req.into_inner();
+    let user_id = path.into_inner();
+    if user_id != req.user_id {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "User ID does not match"
+        }));
+    }
+
+    // GDPR Article 21(1) requires a reason for rejection
+    if req.rejection_reason.is_empty() {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Rejection reason is required per GDPR Article 21(1)"
+        }));
+    }
+
     let now = Utc::now();
-    
-    let policy_id = Uuid::new_v4();
+    let rejected_by = req.rejected_by.as_deref().unwrap_or("SYSTEM");
+
     let result = sqlx::query(
-        "INSERT INTO retention_policies (
-            id, policy_name, data_category, retention_period_days,
-            legal_basis, description, auto_delete, notification_days_before
-        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
-        RETURNING id"
+        "UPDATE processing_objections 
+         SET status = 'REJECTED', rejected_at = $1, rejected_by = $2, rejection_reason = $3
+         WHERE user_id = $4 AND status = 'ACTIVE'"
     )
-    .bind(policy_id)
-    .bind(&policy_req.policy_name)
-    .bind(&policy_req.data_category)
-    .bind(policy_req.retention_period_days)
-    .bind(&policy_req.legal_basis)
-    .bind(&policy_req.description)
-    .bind(policy_req.auto_delete.unwrap_or(true))
-    .bind(policy_req.notification_days_before)
-    .fetch_one(&data.db_pool)
+    .bind(now)
+    .bind(rejected_by)
+    .bind(&req.rejection_reason)
+    .bind(&user_id)
+    .execute(&data.db_pool)
     .await;
 
     match result {
-        Ok(_) => {
-            let response = RetentionPolicyResponse {
-                policy_id: policy_id.to_string(),
-                policy_name: policy_req.policy_name,
-                data_category: policy_req.data_category,
-                retention_period_days: policy_req.retention_period_days,
-                auto_delete: policy_req.auto_delete.unwrap_or(true),
-                created_at: now.format("%Y-%m-%d %H:%M:%S").to_string(),
-            };
-            
-            println!("📅 Retention policy created: {} for category: {}", response.policy_name, response.data_category);
-            HttpResponse::Ok().json(response)
+        Ok(rows) => {
+            if rows.rows_affected() == 0 {
+                return HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "No active objection found for this user"
+                }));
+            }
+
+            println!("❌ Processing objection rejected: User: {} | By: {} | Reason: {}", 
+                user_id, rejected_by, req.rejection_reason);
+
+            HttpResponse::Ok().json(serde_json::json!({
+                "status": "SUCCESS",
+                "message": "Processing objection rejected",
+                "user_id": user_id,
+                "rejection_reason": req.rejection_reason,
+                "rejected_at": now.format("%Y-%m-%d %H:%M:%S").to_string()
+            }))
         }
         Err(e) => {
             let request_id = generate_request_id();
             let request_id = generate_request_id();
-            log_error_safely("creating retention policy", &e, &request_id);
+            log_error_safely("rejecting objection", &e, &request_id);
             HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to create retention policy"
+                "error": "Failed to reject processing objection"
             }))
         }
     }
 }
 
-// 25. GET EXPIRING RECORDS
+// 8.7. GET PROCESSING OBJECTIONS (GDPR Article 21)
 #[utoipa::path(
     get,
-    path = "/retention/expiring",
-    responses((status = 200, body = ExpiringRecordsResponse))
+    path = "/data_subject/{user_id}/objections",
+    responses((status = 200, body = ObjectionsResponse))
 )]
-#[allow(dead_code)]
-pub async fn get_expiring_records(
-    query: web::Query<std::collections::HashMap<String, String>>,
+pub async fn get_processing_objections(
+    path: web::Path<String>,
     data: web::Data<AppState>,
+    http_req: HttpRequest,
 ) -> impl Responder {
-    // Get days parameter (default: 30 days)
-    let days: i32 = query.get("days")
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(30);
-    
-    let now = Utc::now();
-    let expiration_threshold = now + chrono::Duration::days(days as i64);
-    
-    match sqlx::query_as::<_, RetentionAssignmentDb>(
-        "SELECT * FROM retention_assignments 
-         WHERE deleted_at IS NULL 
-           AND expires_at <= $1 
-           AND deletion_status = 'PENDING'
-         ORDER BY expires_at ASC
-         LIMIT 100"
-    )
-    .bind(expiration_threshold)
-    .fetch_all(&data.db_pool)
-    .await
-    {
-        Ok(records) => {
-            let expiring_records: Vec<RetentionTrackingResponse> = records.into_iter().map(|r| {
-                let days_until = (r.expires_at - now).num_days();
-                RetentionTrackingResponse {
-                    record_type: r.record_type,
-                    record_id: r.record_id,
-                    created_at: r.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
-                    expires_at: r.expires_at.format("%Y-%m-%d %H:%M:%S").to_string(),
-                    deletion_status: r.deletion_status,
-                    days_until_expiration: Some(days_until),
+    // AUTHENTICATION & AUTHORIZATION - Critical: data_subject.read
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "data_subject", "read").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
+
+    let user_id = path.into_inner();
+
+    #[derive(sqlx::FromRow)]
+    struct ObjectionRow {
+        objection_id: String,
+        user_id: String,
+        objection_type: String,
+        status: String,
+        requested_at: DateTime<Utc>,
+        rejection_reason: Option<String>,
+    }
+
+    let result = sqlx::query_as::<_, ObjectionRow>(
+        "SELECT objection_id, user_id, objection_type, status, requested_at, rejection_reason
+         FROM processing_objections
+         WHERE user_id = $1
+         ORDER BY requested_at DESC"
+    )
+    .bind(&user_id)
+    .fetch_all(&data.db_pool)
+    .await;
+
+    match result {
+        Ok(rows) => {
+            let objections: Vec<ProcessingObjectionResponse> = rows.into_iter().map(|r| {
+                ProcessingObjectionResponse {
+                    objection_id: r.objection_id,
+                    user_id: r.user_id,
+                    objection_type: r.objection_type,
+                    status: r.status,
+                    requested_at: r.requested_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    rejection_reason: r.rejection_reason,
                 }
             }).collect();
-            
-            let total_count = expiring_records.len() as i64;
-            let response = ExpiringRecordsResponse {
-                expiring_records,
-                total_count,
+
+            let response = ObjectionsResponse {
+                user_id: user_id.clone(),
+                objections,
             };
-            
+
             HttpResponse::Ok().json(response)
         }
         Err(e) => {
             let request_id = generate_request_id();
             let request_id = generate_request_id();
-            log_error_safely("fetching expiring records", &e, &request_id);
+            log_error_safely("fetching objections", &e, &request_id);
             HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to fetch expiring records"
+                "error": "Failed to fetch processing objections"
             }))
         }
     }
 }
 
-// 26. EXECUTE RETENTION DELETION (Manual trigger for automated deletion)
+// ========== GDPR Article 22: Automated Decision-Making ==========
+
+// 8.8. REQUEST HUMAN REVIEW (GDPR Article 22)
 #[utoipa::path(
     post,
-    path = "/retention/execute",
-    responses((status = 200, body = serde_json::Value))
+    path = "/data_subject/{user_id}/request_review",
+    request_body = RequestReviewRequest,
+    responses((status = 200, body = RequestReviewResponse))
 )]
-#[allow(dead_code)]
-pub async fn execute_retention_deletion(
+pub async fn request_human_review(
+    path: web::Path<String>,
+    req: web::Json<RequestReviewRequest>,
     data: web::Data<AppState>,
+    http_req: HttpRequest,
 ) -> impl Responder {
+    // AUTHENTICATION & AUTHORIZATION - Critical: data_subject.write
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "data_subject", "write").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
+
+    let user_id = path.into_inner();
+    if user_id != req.user_id {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "User ID does not match"
+        }));
+    }
+
+    // Find decision by decision_id or seal_id
+    let decision_result: Option<(String, String)> = if let Some(ref decision_id) = req.decision_id {
+        sqlx::query_as(
+            "SELECT decision_id, seal_id FROM automated_decisions 
+             WHERE decision_id = $1 AND user_id = $2"
+        )
+        .bind(decision_id)
+        .bind(&user_id)
+        .fetch_optional(&data.db_pool)
+        .await
+        .unwrap_or(None)
+    } else if let Some(ref seal_id) = req.seal_id {
+        sqlx::query_as(
+            "SELECT decision_id, seal_id FROM automated_decisions 
+             WHERE seal_id = $1 AND user_id = $2"
+        )
+        .bind(seal_id)
+        .bind(&user_id)
+        .fetch_optional(&data.db_pool)
+        .await
+        .unwrap_or(None)
+    } else {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Either decision_id or seal_id must be provided"
+        }));
+    };
+
+    let (decision_id, seal_id) = match decision_result {
+        Some((did, sid)) => (did, sid),
+        None => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Automated decision not found"
+            }));
+        }
+    };
+
+    // Update decision status to UNDER_REVIEW and create human oversight record
     let now = Utc::now();
     
-    // Find records that should be deleted
-    let expired_records: Vec<RetentionAssignmentDb> = sqlx::query_as(
-        "SELECT * FROM retention_assignments 
-         WHERE deleted_at IS NULL 
-           AND expires_at <= $1 
-           AND deletion_status = 'PENDING'
-           AND (record_type, record_id) NOT IN (
-               SELECT record_type, record_id FROM retention_exemptions 
-               WHERE (expires_at IS NULL OR expires_at > CURRENT_TIMESTAMP)
-           )"
+    // Update automated decision
+    let update_result = sqlx::query(
+        "UPDATE automated_decisions 
+         SET status = 'UNDER_REVIEW', human_review_required = true, updated_at = $1
+         WHERE decision_id = $2"
     )
     .bind(now)
-    .fetch_all(&data.db_pool)
-    .await
-    .unwrap_or_default();
-    
-    let mut deleted_count = 0;
-    let mut errors = Vec::new();
-    
-    for record in expired_records {
-        // Mark as deleted in assignment
-        let update_result = sqlx::query(
-            "UPDATE retention_assignments 
-             SET deleted_at = CURRENT_TIMESTAMP, deletion_status = 'DELETED'
-             WHERE id = $1"
-        )
-        .bind(record.id)
-        .execute(&data.db_pool)
-        .await;
-        
-        if update_result.is_ok() {
-            // Log deletion
+    .bind(&decision_id)
+    .execute(&data.db_pool)
+    .await;
+
+    match update_result {
+        Ok(_) => {
+            // Create human oversight record (if not exists)
             let _ = sqlx::query(
-                "INSERT INTO retention_deletion_log (
-                    record_type, record_id, policy_id, deletion_method, deleted_by, records_affected
-                ) VALUES ($1, $2, $3, 'AUTO', 'system', 1)"
+                "INSERT INTO human_oversight (seal_id, status) VALUES ($1, 'PENDING')
+                 ON CONFLICT (seal_id) DO UPDATE SET status = 'PENDING', updated_at = CURRENT_TIMESTAMP"
             )
-            .bind(&record.record_type)
-            .bind(&record.record_id)
-            .bind(&record.policy_id)
+            .bind(&seal_id)
             .execute(&data.db_pool)
             .await;
-            
-            // Actually delete the record based on type
-            match record.record_type.as_str() {
-                "COMPLIANCE_RECORD" => {
-                    // Delete compliance record and related data
-                    let _ = sqlx::query("DELETE FROM compliance_records WHERE seal_id = $1")
-                        .bind(&record.record_id)
-                        .execute(&data.db_pool)
-                        .await;
-                }
-                "CONSENT" => {
-                    let _ = sqlx::query("DELETE FROM consent_records WHERE id::text = $1")
-                        .bind(&record.record_id)
-                        .execute(&data.db_pool)
-                        .await;
-                }
-                "DPIA" => {
-                    let _ = sqlx::query("DELETE FROM dpia_records WHERE dpia_id = $1")
-                        .bind(&record.record_id)
-                        .execute(&data.db_pool)
-                        .await;
-                }
-                "BREACH" => {
-                    let _ = sqlx::query("DELETE FROM data_breaches WHERE breach_id = $1")
-                        .bind(&record.record_id)
-                        .execute(&data.db_pool)
-                        .await;
-                }
-                _ => {
-                    errors.push(format!("Unknown record type: {}", record.record_type));
-                }
-            }
-            
-            deleted_count += 1;
-        } else {
-            errors.push(format!("Failed to update tracking for record: {}", record.record_id));
+
+            println!("👤 Human review requested: Decision: {} | User: {}", decision_id, user_id);
+
+            let response = RequestReviewResponse {
+                decision_id: decision_id.clone(),
+                status: "UNDER_REVIEW".to_string(),
+                message: "Human review requested successfully. The decision will be reviewed by a human reviewer.".to_string(),
+            };
+
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => {
+            let request_id = generate_request_id();
+            let request_id = generate_request_id();
+            log_error_safely("requesting review", &e, &request_id);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to request human review"
+            }))
         }
     }
-    
-    HttpResponse::Ok().json(serde_json::json!({
-        "status": "SUCCESS",
-        "deleted_count": deleted_count,
-        "errors": errors
-    }))
 }
 
-// ========== PRIORITY 2: RETENTION PERIOD AUTOMATION (GDPR Article 5(1)(e)) ==========
-
-// 24. ASSIGN RETENTION POLICY TO RECORD
+// 8.9. APPEAL AUTOMATED DECISION (GDPR Article 22)
 #[utoipa::path(
     post,
-    path = "/retention/assign",
-    request_body = AssignRetentionRequest,
-    responses((status = 200, body = RetentionStatusResponse))
+    path = "/data_subject/{user_id}/appeal_decision",
+    request_body = AppealDecisionRequest,
+    responses((status = 200, body = serde_json::Value))
 )]
-pub async fn assign_retention_policy(
-    req: web::Json<AssignRetentionRequest>,
-    http_req: HttpRequest,
+pub async fn appeal_automated_decision(
+    path: web::Path<String>,
+    req: web::Json<AppealDecisionRequest>,
     data: web::Data<AppState>,
+    http_req: HttpRequest,
 ) -> impl Responder {
-    // AUTHENTICATION & AUTHORIZATION
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "retention", "write").await {
+    // AUTHENTICATION & AUTHORIZATION - Critical: data_subject.write
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "data_subject", "write").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
 
-    let assign_req = req.into_inner();
-    
-    // Get policy details
-    let policy_result: Result<Option<RetentionPolicyDb>, _> = sqlx::query_as(
-        "SELECT * FROM retention_policies WHERE policy_name = $1"
-    )
-    .bind(&assign_req.policy_name)
-    .fetch_optional(&data.db_pool)
-    .await;
-    
-    let policy = match policy_result {
-        Ok(Some(p)) => p,
-        Ok(None) => return HttpResponse::NotFound().json(serde_json::json!({
-            "error": "Retention policy not found"
-        })),
-        Err(e) => {
-            let request_id = generate_request_id();
-            let request_id = generate_request_id();
-            log_error_safely("fetching policy", &e, &request_id);
-            return HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to fetch retention policy"
-            }));
-        }
-    };
-    
-    // Calculate expiration date
-    let created_at = Utc::now();
-    let expires_at = created_at + chrono::Duration::days(policy.retention_period_days as i64);
-    
-    let assignment_id = Uuid::new_v4();
+    let user_id = path.into_inner();
+    if user_id != req.user_id {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "User ID does not match"
+        }));
+    }
+
+    if req.appeal_reason.is_empty() {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Appeal reason is required"
+        }));
+    }
+
+    let now = Utc::now();
+
     let result = sqlx::query(
-        "INSERT INTO retention_assignments (
-            id, record_type, record_id, policy_id, created_at, expires_at, deletion_status
-        ) VALUES ($1, $2, $3, $4, $5, $6, 'PENDING')
-        ON CONFLICT (record_type, record_id) 
-        DO UPDATE SET policy_id = EXCLUDED.policy_id, expires_at = EXCLUDED.expires_at, deletion_status = 'PENDING'
-        RETURNING expires_at"
+        "UPDATE automated_decisions 
+         SET appeal_requested = true, appeal_requested_at = $1, appeal_reason = $2, status = 'APPEALED'
+         WHERE decision_id = $3 AND user_id = $4"
     )
-    .bind(assignment_id)
-    .bind(&assign_req.record_type)
-    .bind(&assign_req.record_id)
-    .bind(policy.id)
-    .bind(created_at)
-    .bind(expires_at)
-    .fetch_one(&data.db_pool)
+    .bind(now)
+    .bind(&req.appeal_reason)
+    .bind(&req.decision_id)
+    .bind(&user_id)
+    .execute(&data.db_pool)
     .await;
 
     match result {
-        Ok(row) => {
-            let expires_at_db: chrono::DateTime<chrono::Utc> = sqlx::Row::get(&row, 0);
-            let days_until = (expires_at_db - Utc::now()).num_days();
-            
-            // Update record's retention_expires_at if applicable
-            match assign_req.record_type.as_str() {
-                "COMPLIANCE_RECORD" => {
-                    let _ = sqlx::query(
-                        "UPDATE compliance_records SET retention_expires_at = $1 WHERE seal_id = $2"
-                    )
-                    .bind(expires_at_db)
-                    .bind(&assign_req.record_id)
-                    .execute(&data.db_pool)
-                    .await;
-                }
-                "CONSENT_RECORD" => {
-                    let _ = sqlx::query(
-                        "UPDATE consent_records SET retention_expires_at = $1 WHERE id::text = $2"
-                    )
-                    .bind(expires_at_db)
-                    .bind(&assign_req.record_id)
-                    .execute(&data.db_pool)
-                    .await;
-                }
-                _ => {}
+        Ok(rows) => {
+            if rows.rows_affected() == 0 {
+                return HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "Automated decision not found or access denied"
+                }));
             }
-            
-            let policy_name_clone = policy.policy_name.clone();
-            let record_id_clone = assign_req.record_id.clone();
-            let record_type_clone = assign_req.record_type.clone();
-            
-            let response = RetentionStatusResponse {
-                record_type: record_type_clone.clone(),
-                record_id: record_id_clone.clone(),
-                policy_name: policy_name_clone.clone(),
-                expires_at: expires_at_db.format("%Y-%m-%d %H:%M:%S").to_string(),
-                days_until_expiration: days_until,
-                deletion_status: "PENDING".to_string(),
-            };
-            
-            println!("📅 Retention policy assigned: {} to {} ({})", policy_name_clone, record_id_clone, record_type_clone);
-            HttpResponse::Ok().json(response)
+
+            println!("📝 Appeal requested: Decision: {} | User: {}", req.decision_id, user_id);
+
+            HttpResponse::Ok().json(serde_json::json!({
+                "status": "SUCCESS",
+                "message": "Appeal requested successfully",
+                "decision_id": req.decision_id,
+                "appeal_requested_at": now.format("%Y-%m-%d %H:%M:%S").to_string()
+            }))
         }
         Err(e) => {
             let request_id = generate_request_id();
             let request_id = generate_request_id();
-            log_error_safely("assigning retention policy", &e, &request_id);
+            log_error_safely("appealing decision", &e, &request_id);
             HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to assign retention policy"
+                "error": "Failed to appeal automated decision"
             }))
         }
     }
 }
 
-// 25. GET RETENTION STATUS
+// 8.10. GET AUTOMATED DECISIONS (GDPR Article 22)
 #[utoipa::path(
     get,
-    path = "/retention/status/{record_type}/{record_id}",
-    responses((status = 200, body = RetentionStatusResponse))
+    path = "/data_subject/{user_id}/automated_decisions",
+    responses((status = 200, body = AutomatedDecisionsResponse))
 )]
-pub async fn get_retention_status(
-    path: web::Path<(String, String)>,
-    http_req: HttpRequest,
+pub async fn get_automated_decisions(
+    path: web::Path<String>,
     data: web::Data<AppState>,
+    http_req: HttpRequest,
 ) -> impl Responder {
-    // AUTHENTICATION & AUTHORIZATION
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "retention", "read").await {
+    // AUTHENTICATION & AUTHORIZATION - Critical: data_subject.read
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "data_subject", "read").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
 
-    let (record_type, record_id) = path.into_inner();
-    
-    match sqlx::query_as::<_, RetentionAssignmentDb>(
-        "SELECT ra.* FROM retention_assignments ra
-         INNER JOIN retention_policies rp ON ra.policy_id = rp.id
-         WHERE ra.record_type = $1 AND ra.record_id = $2 AND ra.deleted_at IS NULL
-         ORDER BY ra.created_at DESC
-         LIMIT 1"
+    let user_id = path.into_inner();
+
+    #[derive(sqlx::FromRow)]
+    struct DecisionRow {
+        decision_id: String,
+        user_id: String,
+        seal_id: String,
+        action_type: String,
+        decision_outcome: String,
+        decision_reasoning: Option<String>,
+        legal_effect: Option<String>,
+        significant_impact: bool,
+        status: String,
+        decision_timestamp: DateTime<Utc>,
+        human_review_required: bool,
+    }
+
+    let result = sqlx::query_as::<_, DecisionRow>(
+        "SELECT decision_id, user_id, seal_id, action_type, decision_outcome,
+                decision_reasoning, legal_effect, significant_impact, status,
+                decision_timestamp, human_review_required
+         FROM automated_decisions
+         WHERE user_id = $1
+         ORDER BY decision_timestamp DESC"
     )
-    .bind(&record_type)
-    .bind(&record_id)
-    .fetch_optional(&data.db_pool)
-    .await
-    {
-        Ok(Some(assignment)) => {
-            // Get policy name
-            let policy_name: String = sqlx::query_scalar(
-                "SELECT policy_name FROM retention_policies WHERE id = $1"
-            )
-            .bind(assignment.policy_id)
-            .fetch_one(&data.db_pool)
-            .await
-            .unwrap_or_else(|_| "UNKNOWN".to_string());
-            
-            let days_until = (assignment.expires_at - Utc::now()).num_days();
-            
-            let response = RetentionStatusResponse {
-                record_type,
-                record_id,
-                policy_name,
-                expires_at: assignment.expires_at.format("%Y-%m-%d %H:%M:%S").to_string(),
-                days_until_expiration: days_until,
-                deletion_status: assignment.deletion_status,
+    .bind(&user_id)
+    .fetch_all(&data.db_pool)
+    .await;
+
+    match result {
+        Ok(rows) => {
+            let decisions: Vec<AutomatedDecisionResponse> = rows.into_iter().map(|r| {
+                AutomatedDecisionResponse {
+                    decision_id: r.decision_id,
+                    user_id: r.user_id,
+                    seal_id: r.seal_id,
+                    action_type: r.action_type,
+                    decision_outcome: r.decision_outcome,
+                    decision_reasoning: r.decision_reasoning,
+                    legal_effect: r.legal_effect,
+                    significant_impact: r.significant_impact,
+                    status: r.status,
+                    decision_timestamp: r.decision_timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    human_review_required: r.human_review_required,
+                }
+            }).collect();
+
+            let response = AutomatedDecisionsResponse {
+                user_id: user_id.clone(),
+                decisions,
             };
-            
+
             HttpResponse::Ok().json(response)
         }
-        Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
-            "error": "Retention assignment not found"
-        })),
         Err(e) => {
             let request_id = generate_request_id();
             let request_id = generate_request_id();
-            log_error_safely("fetching retention status", &e, &request_id);
+            log_error_safely("fetching automated decisions", &e, &request_id);
             HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to fetch retention status"
+                "error": "Failed to fetch automated decisions"
             }))
         }
     }
 }
 
-// 26. GET ALL RETENTION POLICIES
+// ========== PRIORITY 1: HUMAN OVERSIGHT (EU AI Act Article 14) ==========
+
+// 9. REQUIRE HUMAN OVERSIGHT
 #[utoipa::path(
-    get,
-    path = "/retention/policies",
-    responses((status = 200, body = RetentionPoliciesResponse))
+    post,
+    path = "/action/{seal_id}/require_approval",
+    request_body = HumanOversightRequest,
+    responses((status = 200, body = serde_json::Value))
 )]
-pub async fn get_all_retention_policies(
-    http_req: HttpRequest,
+pub async fn require_human_oversight(
+    path: web::Path<String>,
+    req: web::Json<HumanOversightRequest>,
     data: web::Data<AppState>,
 ) -> impl Responder {
-    // AUTHENTICATION & AUTHORIZATION
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "retention", "read").await {
-        Ok(c) => c,
-        Err(resp) => return resp,
-    };
-    match sqlx::query_as::<_, RetentionPolicyDb>(
-        "SELECT * FROM retention_policies ORDER BY created_at DESC"
-    )
-    .fetch_all(&data.db_pool)
-    .await
-    {
-        Ok(records) => {
-            let policies: Vec<RetentionPolicyResponse> = records.into_iter().map(|r| {
-                RetentionPolicyResponse {
-                    policy_id: r.id.to_string(),
-                    policy_name: r.policy_name,
-                    data_category: r.data_category,
-                    retention_period_days: r.retention_period_days,
-                    auto_delete: r.auto_delete,
-                    created_at: r.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
-                }
-            }).collect();
+    let seal_id = path.into_inner();
+    
+    if seal_id != req.seal_id {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Seal ID does not match"
+        }));
+    }
+    
+    // Insert or update human oversight in database
+    let result = sqlx::query(
+        "INSERT INTO human_oversight (seal_id, status) VALUES ($1, 'PENDING')
+         ON CONFLICT (seal_id) DO UPDATE SET status = 'PENDING', updated_at = CURRENT_TIMESTAMP"
+    )
+    .bind(&seal_id)
+    .execute(&data.db_pool)
+    .await;
+
+    // Update compliance record
+    let _ = sqlx::query(
+        "UPDATE compliance_records SET human_oversight_status = 'PENDING' WHERE seal_id = $1"
+    )
+    .bind(&seal_id)
+    .execute(&data.db_pool)
+    .await;
+
+    match result {
+        Ok(_) => {
+            // Trigger webhook event for human oversight required
+            let webhook_data = serde_json::json!({
+                "seal_id": seal_id,
+                "status": "PENDING",
+                "timestamp": Utc::now().to_rfc3339(),
+            });
+            trigger_webhook_event(&data.db_pool, "human_oversight.required", webhook_data).await;
             
-            let response = RetentionPoliciesResponse { policies };
-            HttpResponse::Ok().json(response)
-        }
+            HttpResponse::Ok().json(serde_json::json!({
+                "status": "SUCCESS",
+                "message": "Human oversight required",
+                "seal_id": seal_id
+            }))
+        },
         Err(e) => {
             let request_id = generate_request_id();
             let request_id = generate_request_id();
-            log_error_safely("fetching retention policies", &e, &request_id);
+            log_error_safely("requiring human oversight", &e, &request_id);
             HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to fetch retention policies"
+                "error": "Failed to require human oversight"
             }))
         }
     }
 }
 
-// 27. EXECUTE RETENTION DELETION (Background job - can be called manually)
+// 10. APPROVE ACTION (Human Oversight)
 #[utoipa::path(
     post,
-    path = "/retention/execute_deletions",
+    path = "/action/{seal_id}/approve",
+    request_body = HumanOversightResponse,
     responses((status = 200, body = serde_json::Value))
 )]
-pub async fn execute_retention_deletions(
+pub async fn approve_action(
+    path: web::Path<String>,
+    req: web::Json<HumanOversightResponse>,
     data: web::Data<AppState>,
     http_req: HttpRequest,
 ) -> impl Responder {
-    // AUTHENTICATION & AUTHORIZATION - Critical: admin only
-    let auth_service = match AuthService::new() {
-        Ok(service) => service,
-        Err(e) => {
-            return HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": format!("Failed to initialize auth service: {}", e)
-            }));
-        }
-    };
-    let claims = match extract_claims(&http_req, &auth_service) {
+    // AUTHENTICATION & AUTHORIZATION - Critical: oversight.approve
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "oversight", "approve").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
+    let seal_id = path.into_inner();
     
-    // Only admin can execute retention deletions
-    if !claims.has_role("admin") {
-        return HttpResponse::Forbidden().json(serde_json::json!({
-            "error": "Forbidden",
-            "message": "Only administrators can execute retention deletions"
-        }));
-    }
-    // Find all expired assignments that haven't been deleted
-    let expired_assignments: Vec<RetentionAssignmentDb> = sqlx::query_as(
-        "SELECT ra.* FROM retention_assignments ra
-         INNER JOIN retention_policies rp ON ra.policy_id = rp.id
-         WHERE ra.expires_at <= CURRENT_TIMESTAMP 
-           AND ra.deleted_at IS NULL 
-           AND ra.deletion_status = 'PENDING'
-           AND rp.auto_delete = true
-         LIMIT 100"
+    // Update human oversight in database
+    let result = sqlx::query(
+        "UPDATE human_oversight 
+         SET status = 'APPROVED', reviewer_id = $1, decided_at = CURRENT_TIMESTAMP, comments = $2
+         WHERE seal_id = $3"
     )
-    .fetch_all(&data.db_pool)
-    .await
-    .unwrap_or_default();
-    
-    let mut deleted_count = 0;
-    let mut errors = Vec::new();
-    
-    for assignment in expired_assignments {
-        // Mark as scheduled
-        let _ = sqlx::query(
-            "UPDATE retention_assignments SET deletion_status = 'SCHEDULED' WHERE id = $1"
-        )
-        .bind(assignment.id)
-        .execute(&data.db_pool)
-        .await;
-        
-        // Delete based on record type
-        let deletion_result = match assignment.record_type.as_str() {
-            "COMPLIANCE_RECORD" => {
-                // Use crypto-shredder for compliance records
-                let tx_id_result: Result<Option<String>, _> = sqlx::query_scalar(
-                    "SELECT tx_id FROM compliance_records WHERE seal_id = $1"
-                )
-                .bind(&assignment.record_id)
-                .fetch_optional(&data.db_pool)
-                .await;
-                
-                if let Ok(Some(tx_id)) = tx_id_result {
-                    // Shred the key
-                    data.key_store.shred_key(&tx_id);
-                    
-                    // Mark key as shredded
-                    let _ = sqlx::query(
-                        "UPDATE encrypted_log_keys SET shredded_at = CURRENT_TIMESTAMP WHERE log_id = $1"
-                    )
-                    .bind(&tx_id)
-                    .execute(&data.db_pool)
-                    .await;
-                    
-                    // Update compliance record
-                    let retention_summary = "[RETENTION EXPIRED] Data Automatically Deleted";
-                    let retention_status = "DELETED (Retention Period)";
-                    sqlx::query(
-                        "UPDATE compliance_records 
-                         SET action_summary = $2,
-                             status = $3
-                         WHERE seal_id = $1"
-                    )
-                    .bind(&assignment.record_id)
-                    .bind(retention_summary)
-                    .bind(retention_status)
-                    .execute(&data.db_pool)
-                    .await
-                } else {
-                    Ok(sqlx::postgres::PgQueryResult::default())
-                }
-            }
-            "CONSENT_RECORD" => {
-                // Mark consent as withdrawn
-                sqlx::query(
-                    "UPDATE consent_records 
-                     SET granted = false, withdrawn_at = CURRENT_TIMESTAMP
-                     WHERE id::text = $1"
-                )
-                .bind(&assignment.record_id)
-                .execute(&data.db_pool)
-                .await
-            }
-            _ => {
-                // For other types, just mark as deleted
-                Ok(sqlx::postgres::PgQueryResult::default())
-            }
-        };
-        
-        match deletion_result {
-            Ok(_) => {
-                // Mark assignment as deleted
-                let _ = sqlx::query(
-                    "UPDATE retention_assignments 
-                     SET deleted_at = CURRENT_TIMESTAMP, deletion_status = 'DELETED'
-                     WHERE id = $1"
-                )
-                .bind(assignment.id)
-                .execute(&data.db_pool)
-                .await;
-                
-                // Log deletion
-                let policy_name: String = sqlx::query_scalar(
-                    "SELECT policy_name FROM retention_policies WHERE id = $1"
-                )
-                .bind(assignment.policy_id)
-                .fetch_one(&data.db_pool)
-                .await
-                .unwrap_or_else(|_| "UNKNOWN".to_string());
-                
-                let _ = sqlx::query(
-                    "INSERT INTO retention_deletion_log (
-                        assignment_id, record_type, record_id, policy_name, deletion_method
-                    ) VALUES ($1, $2, $3, $4, 'AUTO')"
-                )
-                .bind(assignment.id)
-                .bind(&assignment.record_type)
-                .bind(&assignment.record_id)
-                .bind(&policy_name)
-                .execute(&data.db_pool)
-                .await;
-                
-                deleted_count += 1;
-            }
-            Err(e) => {
-                errors.push(format!("Failed to delete {} {}: {}", assignment.record_type, assignment.record_id, e));
-            }
+    .bind(&req.reviewer_id)
+    .bind(&req.comments)
+    .bind(&seal_id)
+    .execute(&data.db_pool)
+    .await;
+
+    // Update compliance record
+    let _ = sqlx::query(
+        "UPDATE compliance_records SET human_oversight_status = 'APPROVED' WHERE seal_id = $1"
+    )
+    .bind(&seal_id)
+    .execute(&data.db_pool)
+    .await;
+
+    match result {
+        Ok(rows) if rows.rows_affected() > 0 => {
+            println!("✅ Action approved: {} by reviewer: {:?}", seal_id, req.reviewer_id);
+            
+            // Trigger webhook event for human oversight resolved
+            let webhook_data = serde_json::json!({
+                "seal_id": seal_id,
+                "status": "APPROVED",
+                "reviewer_id": req.reviewer_id,
+                "comments": req.comments,
+                "timestamp": Utc::now().to_rfc3339(),
+            });
+            trigger_webhook_event(&data.db_pool, "human_oversight.resolved", webhook_data).await;
+            
+            HttpResponse::Ok().json(serde_json::json!({
+                "status": "SUCCESS",
+                "message": "Action approved",
+                "seal_id": seal_id
+            }))
         }
+        _ => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Human oversight record not found"
+        }))
     }
-    
-    HttpResponse::Ok().json(serde_json::json!({
-        "status": "SUCCESS",
-        "deleted_count": deleted_count,
-        "errors": errors
-    }))
 }
 
-// ========== PRIORITY 2: POST-MARKET MONITORING (EU AI Act Article 72) ==========
-
-// 28. CREATE MONITORING EVENT
+// 11. REJECT ACTION (Human Oversight)
 #[utoipa::path(
     post,
-    path = "/monitoring/event",
-    request_body = MonitoringEventRequest,
-    responses((status = 200, body = MonitoringEventResponse))
+    path = "/action/{seal_id}/reject",
+    request_body = HumanOversightResponse,
+    responses((status = 200, body = serde_json::Value))
 )]
-pub async fn create_monitoring_event(
-    req: web::Json<MonitoringEventRequest>,
-    http_req: HttpRequest,
-    data: web::Data<AppState>,
-) -> impl Responder {
-    // AUTHENTICATION & AUTHORIZATION
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "monitoring", "write").await {
+pub async fn reject_action(
+    path: web::Path<String>,
+    req: web::Json<HumanOversightResponse>,
+    data: web::Data<AppState>,
+    http_req: HttpRequest,
+) -> impl Responder {
+    // AUTHENTICATION & AUTHORIZATION - Critical: oversight.approve
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "oversight", "approve").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
-
-    let event_req = req.into_inner();
-    let now = Utc::now();
-    
-    // Generate event ID
-    let event_id = format!("EVT-{}-{:06}", now.format("%Y%m%d"), 
-        sqlx::query_scalar::<_, i64>(
-            "SELECT COUNT(*) FROM monitoring_events WHERE event_id LIKE $1"
-        )
-        .bind(format!("EVT-{}%", now.format("%Y%m%d")))
-        .fetch_one(&data.db_pool)
-        .await
-        .unwrap_or(0) + 1);
-    
-    let detected_at = event_req.detected_at.as_ref()
-        .and_then(|s| chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").ok())
-        .map(|dt| DateTime::<Utc>::from_naive_utc_and_offset(dt, Utc))
-        .unwrap_or(now);
-    
-    let affected_users_json = serde_json::to_value(event_req.affected_users.unwrap_or_default()).unwrap_or(serde_json::json!([]));
-    let affected_count = affected_users_json.as_array().map(|a| a.len() as i32).unwrap_or(0);
+    let seal_id = path.into_inner();
     
-    let event_uuid = Uuid::new_v4();
+    // Update human oversight in database
     let result = sqlx::query(
-        "INSERT INTO monitoring_events (
-            id, event_id, event_type, severity, system_id, system_version,
-            description, affected_users, affected_records_count, detected_at,
-            resolution_status
-        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, 'OPEN')
-        RETURNING event_id"
+        "UPDATE human_oversight 
+         SET status = 'REJECTED', reviewer_id = $1, decided_at = CURRENT_TIMESTAMP, comments = $2
+         WHERE seal_id = $3"
     )
-    .bind(event_uuid)
-    .bind(&event_id)
-    .bind(&event_req.event_type)
-    .bind(&event_req.severity)
-    .bind(&event_req.system_id)
-    .bind(&event_req.system_version)
-    .bind(&event_req.description)
-    .bind(affected_users_json)
-    .bind(affected_count)
-    .bind(detected_at)
-    .fetch_one(&data.db_pool)
+    .bind(&req.reviewer_id)
+    .bind(&req.comments)
+    .bind(&seal_id)
+    .execute(&data.db_pool)
+    .await;
+
+    // Update compliance record (oversight status only; the chained status is superseded below)
+    let _ = sqlx::query(
+        "UPDATE compliance_records 
+         SET human_oversight_status = 'REJECTED'
+         WHERE seal_id = $1"
+    )
+    .bind(&seal_id)
+    .execute(&data.db_pool)
     .await;
 
     match result {
-        Ok(_) => {
-            let response = MonitoringEventResponse {
-                event_id: event_id.clone(),
-                event_type: event_req.event_type,
-                severity: event_req.severity,
-                system_id: event_req.system_id,
-                resolution_status: "OPEN".to_string(),
-                detected_at: detected_at.format("%Y-%m-%d %H:%M:%S").to_string(),
-            };
+        Ok(rows) if rows.rows_affected() > 0 => {
+            println!("❌ Action rejected: {} by reviewer: {:?}", seal_id, req.reviewer_id);
+
+            if let Err(e) = crate::core::audit_chain::AuditChain::append_superseding(
+                &data.db_pool,
+                &seal_id,
+                crate::core::audit_chain::EntryType::Oversight,
+                "REJECTED (Human Oversight)",
+                "[REJECTED] Action rejected by human oversight",
+                None,
+            ).await {
+                let request_id = generate_request_id();
+                log_error_safely("appending oversight rejection", &e, &request_id);
+                return create_error_response(&request_id);
+            }
             
-            println!("📊 Monitoring event created: {} for system: {}", event_id, response.system_id);
-            HttpResponse::Ok().json(response)
-        }
-        Err(e) => {
-            let request_id = generate_request_id();
-            let request_id = generate_request_id();
-            log_error_safely("creating monitoring event", &e, &request_id);
-            HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to create monitoring event"
+            // Trigger webhook event for human oversight resolved
+            let webhook_data = serde_json::json!({
+                "seal_id": seal_id,
+                "status": "REJECTED",
+                "reviewer_id": req.reviewer_id,
+                "comments": req.comments,
+                "timestamp": Utc::now().to_rfc3339(),
+            });
+            trigger_webhook_event(&data.db_pool, "human_oversight.resolved", webhook_data).await;
+            
+            HttpResponse::Ok().json(serde_json::json!({
+                "status": "SUCCESS",
+                "message": "Action rejected",
+                "seal_id": seal_id
             }))
         }
+        _ => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Human oversight record not found"
+        }))
     }
 }
 
-// 29. UPDATE EVENT RESOLUTION
+// ========== PRIORITY 1: RISK ASSESSMENT (EU AI Act Article 9) ==========
+
+// 12. GET RISK ASSESSMENT
 #[utoipa::path(
-    put,
-    path = "/monitoring/event/{event_id}",
-    request_body = UpdateEventResolutionRequest,
-    responses((status = 200, body = MonitoringEventResponse))
+    get,
+    path = "/risk_assessment/{seal_id}",
+    responses((status = 200, body = RiskAssessment))
 )]
-pub async fn update_event_resolution(
+pub async fn get_risk_assessment(
     path: web::Path<String>,
-    req: web::Json<UpdateEventResolutionRequest>,
     http_req: HttpRequest,
     data: web::Data<AppState>,
 ) -> impl Responder {
     // AUTHENTICATION & AUTHORIZATION
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "monitoring", "write").await {
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "risk", "read").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
 
-    let event_id = path.into_inner();
-    let update_req = req.into_inner();
-    
-    let resolved_at = if update_req.resolution_status == "RESOLVED" {
-        Some(Utc::now())
-    } else {
-        None
-    };
+    let seal_id = path.into_inner();
     
-    // Check if event exists
-    let _event_exists = match sqlx::query_as::<_, MonitoringEventDb>(
-        "SELECT * FROM monitoring_events WHERE event_id = $1"
-    )
-    .bind(&event_id)
-    .fetch_optional(&data.db_pool)
-    .await {
-        Ok(Some(_)) => {} // Event exists, continue
-        Ok(None) => {
-            return HttpResponse::NotFound().json(serde_json::json!({
-                "error": "Monitoring event not found"
-            }));
-        }
-        Err(e) => {
-            let request_id = generate_request_id();
-            let request_id = generate_request_id();
-            log_error_safely("checking event existence", &e, &request_id);
-            return HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Database error"
-            }));
-        }
-    };
-
-    // Update the event
-    let update_result = sqlx::query(
-        "UPDATE monitoring_events 
-         SET resolution_status = $1, resolution_notes = $2,
-             corrective_action_taken = $3, preventive_measures = $4,
-             resolved_at = $5, updated_at = CURRENT_TIMESTAMP
-         WHERE event_id = $6"
-    )
-    .bind(&update_req.resolution_status)
-    .bind(&update_req.resolution_notes)
-    .bind(&update_req.corrective_action_taken)
-    .bind(&update_req.preventive_measures)
-    .bind(resolved_at)
-    .bind(&event_id)
-    .execute(&data.db_pool)
-    .await;
-
-    // Check if update succeeded
-    match update_result {
-        Ok(_) => {}
-        Err(e) => {
-            let request_id = generate_request_id();
-            let request_id = generate_request_id();
-            log_error_safely("updating event", &e, &request_id);
-            return HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to update monitoring event"
-            }));
-        }
-    }
-
-    // Fetch updated event
-    let updated_event = sqlx::query_as::<_, MonitoringEventDb>(
-        "SELECT * FROM monitoring_events WHERE event_id = $1"
+    match sqlx::query_as::<_, RiskAssessmentDb>(
+        "SELECT * FROM risk_assessments WHERE seal_id = $1"
     )
-    .bind(&event_id)
+    .bind(&seal_id)
     .fetch_optional(&data.db_pool)
-    .await;
-
-    match updated_event {
-        Ok(Some(event)) => {
-            let response = MonitoringEventResponse {
-                event_id: event.event_id,
-                event_type: event.event_type,
-                severity: event.severity,
-                system_id: event.system_id,
-                resolution_status: event.resolution_status,
-                detected_at: event.detected_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+    .await
+    {
+        Ok(Some(risk_db)) => {
+            let risk = RiskAssessment {
+                risk_level: risk_db.risk_level,
+                risk_factors: serde_json::from_value(risk_db.risk_factors).unwrap_or_default(),
+                mitigation_actions: serde_json::from_value(risk_db.mitigation_actions).unwrap_or_default(),
+                assessed_at: risk_db.assessed_at.format("%Y-%m-%d %H:%M:%S").to_string(),
             };
-            HttpResponse::Ok().json(response)
+            HttpResponse::Ok().json(risk)
         }
         Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
-            "error": "Monitoring event not found"
+            "error": "Risk assessment not found"
         })),
         Err(e) => {
             let request_id = generate_request_id();
             let request_id = generate_request_id();
-            log_error_safely("updating event resolution", &e, &request_id);
+            log_error_safely("fetching risk assessment", &e, &request_id);
             HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to update event resolution"
+                "error": "Failed to fetch risk assessment"
             }))
         }
     }
 }
 
-// 30. GET ALL MONITORING EVENTS (with pagination)
+// 13. GET ALL RISKS (with pagination)
 #[utoipa::path(
     get,
-    path = "/monitoring/events",
+    path = "/risks",
     params(
-        ("system_id" = Option<String>, Query, description = "Filter by system ID"),
         ("page" = Option<i64>, Query, description = "Page number (default: 1)"),
         ("limit" = Option<i64>, Query, description = "Items per page (default: 100, max: 1000)")
     ),
-    responses((status = 200, body = MonitoringEventsResponse))
+    responses((status = 200, body = serde_json::Value))
 )]
-pub async fn get_all_monitoring_events(
+pub async fn get_all_risks(
     query: web::Query<std::collections::HashMap<String, String>>,
     http_req: HttpRequest,
     data: web::Data<AppState>,
 ) -> impl Responder {
     // AUTHENTICATION & AUTHORIZATION
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "monitoring", "read").await {
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "risk", "read").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
-    let system_id = query.get("system_id");
     let page = query
         .get("page")
         .and_then(|s| s.parse::<i64>().ok())
@@ -4390,2036 +4196,3856 @@ pub async fn get_all_monitoring_events(
         .min(1000)
         .max(1);
     let offset = (page - 1) * limit;
-    
+
     // Get total count
-    let total_count: i64 = if let Some(sid) = system_id {
-        sqlx::query_scalar("SELECT COUNT(*) FROM monitoring_events WHERE system_id = $1")
-            .bind(sid)
-            .fetch_one(&data.db_pool)
-            .await
-            .unwrap_or(0)
-    } else {
-        sqlx::query_scalar("SELECT COUNT(*) FROM monitoring_events")
-            .fetch_one(&data.db_pool)
-            .await
-            .unwrap_or(0)
-    };
-    
-    let query_final = if let Some(sid) = system_id {
-        sqlx::query_as::<_, MonitoringEventDb>(
-            "SELECT * FROM monitoring_events WHERE system_id = $1 ORDER BY detected_at DESC LIMIT $2 OFFSET $3"
-        )
-        .bind(sid)
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(&data.db_pool)
+    let total_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM risk_assessments")
+        .fetch_one(&data.db_pool)
         .await
-    } else {
-        sqlx::query_as::<_, MonitoringEventDb>(
-            "SELECT * FROM monitoring_events ORDER BY detected_at DESC LIMIT $1 OFFSET $2"
-        )
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(&data.db_pool)
-        .await
-    };
+        .unwrap_or(0);
 
-    match query_final {
+    match sqlx::query_as::<_, RiskAssessmentDb>(
+        "SELECT * FROM risk_assessments ORDER BY assessed_at DESC LIMIT $1 OFFSET $2"
+    )
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&data.db_pool)
+    .await
+    {
         Ok(records) => {
-            let events: Vec<MonitoringEventResponse> = records.into_iter().map(|r| {
-                MonitoringEventResponse {
-                    event_id: r.event_id,
-                    event_type: r.event_type,
-                    severity: r.severity,
-                    system_id: r.system_id,
-                    resolution_status: r.resolution_status,
-                    detected_at: r.detected_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            let all_risks: Vec<RiskAssessment> = records.into_iter().map(|r| {
+                RiskAssessment {
+                    risk_level: r.risk_level,
+                    risk_factors: serde_json::from_value(r.risk_factors).unwrap_or_default(),
+                    mitigation_actions: serde_json::from_value(r.mitigation_actions).unwrap_or_default(),
+                    assessed_at: r.assessed_at.format("%Y-%m-%d %H:%M:%S").to_string(),
                 }
             }).collect();
-            
-            let response = MonitoringEventsResponse {
-                events,
-                total_count,
-            };
-            
-            HttpResponse::Ok().json(response)
+            HttpResponse::Ok().json(serde_json::json!({
+                "data": all_risks,
+                "pagination": {
+                    "page": page,
+                    "limit": limit,
+                    "total": total_count,
+                    "total_pages": (total_count + limit - 1) / limit
+                }
+            }))
         }
         Err(e) => {
             let request_id = generate_request_id();
             let request_id = generate_request_id();
-            log_error_safely("fetching monitoring events", &e, &request_id);
+            log_error_safely("fetching risks", &e, &request_id);
             HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to fetch monitoring events"
+                "error": "Failed to fetch risks"
             }))
         }
     }
 }
 
-// 31. GET SYSTEM HEALTH STATUS
+// ========== PRIORITY 1: DATA BREACH (GDPR Articles 33-34) ==========
+
+// 14. REPORT DATA BREACH
 #[utoipa::path(
-    get,
-    path = "/monitoring/health/{system_id}",
-    responses((status = 200, body = SystemHealthStatusResponse))
+    post,
+    path = "/breach_report",
+    request_body = DataBreachReport,
+    responses((status = 200, body = DataBreachResponse))
 )]
-pub async fn get_system_health(
-    path: web::Path<String>,
-    http_req: HttpRequest,
+pub async fn report_breach(
+    req: web::Json<DataBreachReport>,
     data: web::Data<AppState>,
+    http_req: HttpRequest,
 ) -> impl Responder {
-    // AUTHENTICATION & AUTHORIZATION
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "monitoring", "read").await {
+    // AUTHENTICATION & AUTHORIZATION - Critical: breach.write
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "breach", "write").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
-
-    let system_id = path.into_inner();
+    let breach_id = format!("BREACH-{}", Local::now().format("%Y%m%d-%H%M%S"));
+    let breach_report = req.into_inner();
+    let now = Utc::now();
     
-    match sqlx::query_as::<_, SystemHealthStatusDb>(
-        "SELECT * FROM system_health_status WHERE system_id = $1"
+    // Parse detected_at
+    let detected_at = chrono::NaiveDateTime::parse_from_str(&breach_report.detected_at, "%Y-%m-%d %H:%M:%S")
+        .map(|dt| DateTime::<Utc>::from_naive_utc_and_offset(dt, Utc))
+        .unwrap_or(now);
+    
+    // Simulate notification to authority (within 72 hours)
+    let authority_notified_at = Some(now);
+    
+    // Simulate user notification (if high risk)
+    let users_notified_at = if breach_report.breach_type == "DATA_LEAK" || breach_report.breach_type == "SYSTEM_COMPROMISE" {
+        Some(now)
+    } else {
+        None
+    };
+    
+    let affected_users_json = serde_json::to_value(&breach_report.affected_users).unwrap_or(serde_json::json!([]));
+    
+    // Store breach in database
+    let result = sqlx::query(
+        "INSERT INTO data_breaches (
+            breach_id, description, breach_type, affected_users, detected_at,
+            affected_records_count, status, authority_notified_at, users_notified_at
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"
     )
-    .bind(&system_id)
-    .fetch_optional(&data.db_pool)
-    .await
-    {
-        Ok(Some(health)) => {
-            let response = SystemHealthStatusResponse {
-                system_id: health.system_id,
-                overall_status: health.overall_status,
-                compliance_status: health.compliance_status,
-                active_incidents_count: health.active_incidents_count,
-                critical_incidents_count: health.critical_incidents_count,
-                performance_score: health.performance_score,
-                compliance_score: health.compliance_score,
-                last_health_check: health.last_health_check.format("%Y-%m-%d %H:%M:%S").to_string(),
+    .bind(&breach_id)
+    .bind(&breach_report.description)
+    .bind(&breach_report.breach_type)
+    .bind(affected_users_json)
+    .bind(detected_at)
+    .bind(breach_report.affected_records_count.map(|c| c as i32))
+    .bind("REPORTED")
+    .bind(authority_notified_at)
+    .bind(users_notified_at)
+    .execute(&data.db_pool)
+    .await;
+
+    match result {
+        Ok(_) => {
+            let response = DataBreachResponse {
+                breach_id: breach_id.clone(),
+                status: "REPORTED".to_string(),
+                authority_notified_at: authority_notified_at.map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
+                users_notified_at: users_notified_at.map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
             };
+            
+            println!("🚨 Data breach reported: {} | Type: {} | Affected: {} users", 
+                breach_id, breach_report.breach_type, breach_report.affected_users.len());
+            
+            // Send notifications to affected users (GDPR Article 33)
+            if users_notified_at.is_some() {
+                let notification_service = data.notification_service.clone();
+                let db_pool = data.db_pool.clone();
+                let breach_id_clone = breach_id.clone();
+                let breach_type_clone = breach_report.breach_type.clone();
+                let description_clone = breach_report.description.clone();
+                let detected_at_clone = detected_at;
+                let affected_users_clone = breach_report.affected_users.clone();
+                
+                // Spawn async task to send notifications (non-blocking)
+                tokio::spawn(async move {
+                    for user_id in &affected_users_clone {
+                        // Try email first, fallback to SMS if email fails
+                        let email_result = notification_service.notify_data_breach(
+                            &db_pool,
+                            user_id,
+                            &breach_id_clone,
+                            &breach_type_clone,
+                            &detected_at_clone,
+                            &description_clone,
+                            NotificationChannel::Email,
+                        ).await;
+                        
+                        if email_result.is_err() {
+                            // Fallback to SMS
+                            let _ = notification_service.notify_data_breach(
+                                &db_pool,
+                                user_id,
+                                &breach_id_clone,
+                                &breach_type_clone,
+                                &detected_at_clone,
+                                &description_clone,
+                                NotificationChannel::Sms,
+                            ).await;
+                        }
+                    }
+                });
+            }
+            
+            // Trigger webhook event for data breach
+            let webhook_data = serde_json::json!({
+                "breach_id": breach_id,
+                "breach_type": breach_report.breach_type,
+                "description": breach_report.description,
+                "affected_users_count": breach_report.affected_users.len(),
+                "detected_at": detected_at.to_rfc3339(),
+                "authority_notified_at": authority_notified_at.map(|dt| dt.to_rfc3339()),
+                "users_notified_at": users_notified_at.map(|dt| dt.to_rfc3339()),
+            });
+            trigger_webhook_event(&data.db_pool, "data_breach.detected", webhook_data).await;
+            
             HttpResponse::Ok().json(response)
         }
-        Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
-            "error": "System health status not found"
-        })),
         Err(e) => {
             let request_id = generate_request_id();
             let request_id = generate_request_id();
-            log_error_safely("fetching system health", &e, &request_id);
+            log_error_safely("reporting breach", &e, &request_id);
             HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to fetch system health"
+                "error": "Failed to report breach"
             }))
         }
     }
 }
 
-// ========== AI-BOM (CycloneDX) EXPORT ==========
-
-// 32. EXPORT AI-BOM (CycloneDX format)
+// 15. GET ALL BREACHES (with pagination)
 #[utoipa::path(
     get,
-    path = "/ai_bom/{system_id}",
+    path = "/breaches",
     params(
-        ("system_id" = String, Path, description = "System ID to export"),
-        ("format" = Option<String>, Query, description = "Export format (default: cyclonedx)")
+        ("page" = Option<i64>, Query, description = "Page number (default: 1)"),
+        ("limit" = Option<i64>, Query, description = "Items per page (default: 100, max: 1000)")
     ),
-    responses((status = 200, body = CycloneDxBom))
+    responses((status = 200, body = serde_json::Value))
 )]
-pub async fn export_ai_bom(
-    path: web::Path<String>,
+pub async fn get_breaches(
     query: web::Query<std::collections::HashMap<String, String>>,
     data: web::Data<AppState>,
+    http_req: HttpRequest,
 ) -> impl Responder {
-    let system_id = path.into_inner();
-    let format = query.get("format").map(|s| s.as_str()).unwrap_or("cyclonedx");
+    // AUTHENTICATION & AUTHORIZATION - Critical: breach.read
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "breach", "read").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
 
-    if format != "cyclonedx" {
-        return HttpResponse::BadRequest().json(serde_json::json!({
-            "error": "Only 'cyclonedx' format is currently supported"
-        }));
-    }
+    let page = query
+        .get("page")
+        .and_then(|s| s.parse::<i64>().ok())
+        .unwrap_or(1)
+        .max(1);
+    let limit = (query
+        .get("limit")
+        .and_then(|s| s.parse::<i64>().ok())
+        .unwrap_or(100))
+        .min(1000)
+        .max(1);
+    let offset = (page - 1) * limit;
 
-    // Fetch system inventory from database
-    let inventory_result: Result<Option<serde_json::Value>, _> = sqlx::query_scalar(
-        "SELECT row_to_json(ai_system_inventory.*) FROM ai_system_inventory WHERE system_id = $1"
-    )
-    .bind(&system_id)
-    .fetch_optional(&data.db_pool)
-    .await;
+    // Get total count
+    let total_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM data_breaches")
+        .fetch_one(&data.db_pool)
+        .await
+        .unwrap_or(0);
 
-    let inventory = match inventory_result {
-        Ok(Some(inv)) => inv,
-        Ok(None) => {
-            // If no inventory record, create a basic one from monitoring events
-            let monitoring_result: Result<Option<MonitoringEventDb>, _> = sqlx::query_as(
-                "SELECT * FROM monitoring_events WHERE system_id = $1 ORDER BY detected_at DESC LIMIT 1"
-            )
-            .bind(&system_id)
-            .fetch_optional(&data.db_pool)
-            .await;
-
-            if let Ok(Some(event)) = monitoring_result {
-                serde_json::json!({
-                    "system_id": system_id,
-                    "system_name": format!("AI System {}", system_id),
-                    "system_version": event.system_version,
-                    "system_type": "MODEL",
-                    "description": event.description,
-                })
-            } else {
-                return HttpResponse::NotFound().json(serde_json::json!({
-                    "error": "System not found"
-                }));
-            }
+    match sqlx::query_as::<_, DataBreachDb>(
+        "SELECT * FROM data_breaches ORDER BY detected_at DESC LIMIT $1 OFFSET $2"
+    )
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&data.db_pool)
+    .await
+    {
+        Ok(records) => {
+            let breaches: Vec<DataBreachReport> = records.into_iter().map(|r| {
+                DataBreachReport {
+                    description: r.description,
+                    breach_type: r.breach_type,
+                    affected_users: serde_json::from_value(r.affected_users).unwrap_or_default(),
+                    detected_at: r.detected_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    affected_records_count: r.affected_records_count.map(|c| c.max(0) as u32),
+                }
+            }).collect();
+            HttpResponse::Ok().json(serde_json::json!({
+                "data": breaches,
+                "pagination": {
+                    "page": page,
+                    "limit": limit,
+                    "total": total_count,
+                    "total_pages": (total_count + limit - 1) / limit
+                }
+            }))
         }
         Err(e) => {
             let request_id = generate_request_id();
             let request_id = generate_request_id();
-            log_error_safely("fetching system inventory", &e, &request_id);
-            return HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to fetch system inventory"
-            }));
-        }
-    };
-
-    // Fetch related DPIA if available
-    let dpia_id: Option<String> = inventory.get("dpia_id")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
-
-    let mut components = Vec::new();
-
-    // Main component
-    let mut properties = Vec::new();
-    if let Some(risk_level) = inventory.get("risk_level").and_then(|v| v.as_str()) {
-        properties.push(crate::compliance_models::CycloneDxProperty {
-            name: "ai:risk_level".to_string(),
-            value: risk_level.to_string(),
-        });
-    }
-    if let Some(compliance_status) = inventory.get("compliance_status").and_then(|v| v.as_str()) {
-        properties.push(crate::compliance_models::CycloneDxProperty {
-            name: "ai:compliance_status".to_string(),
-            value: compliance_status.to_string(),
-        });
-    }
-    if let Some(dpia) = dpia_id {
-        properties.push(crate::compliance_models::CycloneDxProperty {
-            name: "ai:dpia_id".to_string(),
-            value: dpia,
-        });
-    }
-
-    components.push(crate::compliance_models::CycloneDxComponent {
-        component_type: "application".to_string(),
-        name: inventory.get("system_name")
-            .and_then(|v| v.as_str())
-            .unwrap_or(&system_id)
-            .to_string(),
-        version: inventory.get("system_version")
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string()),
-        description: inventory.get("description")
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string()),
-        purl: inventory.get("source_url")
-            .and_then(|v| v.as_str())
-            .map(|s| format!("pkg:generic/{}@{}", system_id, s)),
-        properties: Some(properties),
-        bom_ref: Some(system_id.clone()),
-    });
-
-    // Add dependencies if available
-    if let Some(deps) = inventory.get("dependencies").and_then(|v| v.as_array()) {
-        for dep in deps {
-            if let Some(dep_id) = dep.as_str() {
-                components.push(crate::compliance_models::CycloneDxComponent {
-                    component_type: "library".to_string(),
-                    name: dep_id.to_string(),
-                    version: None,
-                    description: Some(format!("Dependency of {}", system_id)),
-                    purl: None,
-                    properties: None,
-                    bom_ref: Some(dep_id.to_string()),
-                });
-            }
+            log_error_safely("fetching breaches", &e, &request_id);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch breaches"
+            }))
         }
     }
-
-    let bom = crate::compliance_models::CycloneDxBom {
-        bom_format: "CycloneDX".to_string(),
-        spec_version: "1.5".to_string(),
-        version: 1,
-        metadata: crate::compliance_models::CycloneDxMetadata {
-            timestamp: Utc::now().to_rfc3339(),
-            tools: Some(vec![crate::compliance_models::CycloneDxTool {
-                name: "Veridion Nexus".to_string(),
-                version: "1.0.0".to_string(),
-                vendor: Some("Veridion".to_string()),
-            }]),
-            properties: Some(vec![
-                crate::compliance_models::CycloneDxProperty {
-                    name: "ai:spec_version".to_string(),
-                    value: "1.5".to_string(),
-                },
-            ]),
-        },
-        components,
-    };
-
-    HttpResponse::Ok().json(bom)
 }
 
-// 33. REGISTER AI SYSTEM INVENTORY
+// ========== PRIORITY 2: CONSENT MANAGEMENT (GDPR Articles 6, 7) ==========
+
+// 16. GRANT CONSENT
 #[utoipa::path(
     post,
-    path = "/ai_bom/inventory",
-    request_body = AiSystemInventoryRequest,
-    responses((status = 200, body = serde_json::Value))
+    path = "/consent",
+    request_body = ConsentRequest,
+    responses((status = 200, body = ConsentResponse))
 )]
-pub async fn register_ai_system(
-    req: web::Json<AiSystemInventoryRequest>,
+pub async fn grant_consent(
+    req: web::Json<ConsentRequest>,
+    http_req: HttpRequest,
     data: web::Data<AppState>,
 ) -> impl Responder {
-    let inv_req = req.into_inner();
+    // AUTHENTICATION & AUTHORIZATION
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "consent", "write").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
+    let consent_req = req.into_inner();
+    let now = Utc::now();
+    
+    // Parse expires_at if provided
+    let expires_at = consent_req.expires_at.as_ref()
+        .and_then(|s| chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").ok())
+        .map(|dt| DateTime::<Utc>::from_naive_utc_and_offset(dt, Utc));
+    
+    // Get current version for this consent type
+    let current_version: Option<i32> = sqlx::query_scalar(
+        "SELECT MAX(version) FROM consent_records WHERE user_id = $1 AND consent_type = $2"
+    )
+    .bind(&consent_req.user_id)
+    .bind(&consent_req.consent_type)
+    .fetch_optional(&data.db_pool)
+    .await
+    .unwrap_or(None);
+    
+    let version = current_version.map(|v| v + 1).unwrap_or(1);
+    
+    // Withdraw any existing consent of this type
+    let _ = sqlx::query(
+        "UPDATE consent_records 
+         SET granted = false, withdrawn_at = CURRENT_TIMESTAMP
+         WHERE user_id = $1 AND consent_type = $2 AND granted = true AND withdrawn_at IS NULL"
+    )
+    .bind(&consent_req.user_id)
+    .bind(&consent_req.consent_type)
+    .execute(&data.db_pool)
+    .await;
     
+    // Insert new consent
+    let consent_id = Uuid::new_v4();
     let result = sqlx::query(
-        "INSERT INTO ai_system_inventory (
-            system_id, system_name, system_version, system_type,
-            description, vendor, license, source_url, checksum_sha256,
-            dependencies, training_data_info, risk_level, dpia_id,
-            compliance_status
-        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, 'REVIEW_REQUIRED')
-        ON CONFLICT (system_id) DO UPDATE SET
-            system_name = EXCLUDED.system_name,
-            system_version = EXCLUDED.system_version,
-            description = EXCLUDED.description,
-            vendor = EXCLUDED.vendor,
-            license = EXCLUDED.license,
-            source_url = EXCLUDED.source_url,
-            checksum_sha256 = EXCLUDED.checksum_sha256,
-            dependencies = EXCLUDED.dependencies,
-            training_data_info = EXCLUDED.training_data_info,
-            risk_level = EXCLUDED.risk_level,
-            dpia_id = EXCLUDED.dpia_id,
-            updated_at = CURRENT_TIMESTAMP
-        RETURNING system_id"
+        "INSERT INTO consent_records (
+            id, user_id, consent_type, purpose, legal_basis, granted, granted_at,
+            expires_at, consent_method, ip_address, user_agent, consent_text, version
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+        RETURNING id"
     )
-    .bind(&inv_req.system_id)
-    .bind(&inv_req.system_name)
-    .bind(&inv_req.system_version)
-    .bind(&inv_req.system_type)
-    .bind(&inv_req.description)
-    .bind(&inv_req.vendor)
-    .bind(&inv_req.license)
-    .bind(&inv_req.source_url)
-    .bind(&inv_req.checksum_sha256)
-    .bind(inv_req.dependencies.map(|d| serde_json::to_value(d).unwrap_or(serde_json::json!([]))))
-    .bind(&inv_req.training_data_info)
-    .bind(&inv_req.risk_level)
-    .bind(&inv_req.dpia_id)
+    .bind(consent_id)
+    .bind(&consent_req.user_id)
+    .bind(&consent_req.consent_type)
+    .bind(&consent_req.purpose)
+    .bind(&consent_req.legal_basis)
+    .bind(true)
+    .bind(now)
+    .bind(expires_at)
+    .bind(&consent_req.consent_method)
+    .bind(&consent_req.ip_address)
+    .bind(&consent_req.user_agent)
+    .bind(&consent_req.consent_text)
+    .bind(version)
     .fetch_one(&data.db_pool)
     .await;
 
     match result {
         Ok(_) => {
-            println!("📦 AI system registered: {}", inv_req.system_id);
-            HttpResponse::Ok().json(serde_json::json!({
-                "status": "SUCCESS",
-                "system_id": inv_req.system_id
-            }))
-        }
-        Err(e) => {
-            let request_id = generate_request_id();
-            let request_id = generate_request_id();
-            log_error_safely("registering AI system", &e, &request_id);
-            HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to register AI system"
-            }))
+            let user_id = consent_req.user_id.clone();
+            let consent_type = consent_req.consent_type.clone();
+            
+            let response = ConsentResponse {
+                consent_id: consent_id.to_string(),
+                user_id: user_id.clone(),
+                consent_type: consent_type.clone(),
+                granted: true,
+                granted_at: Some(now.format("%Y-%m-%d %H:%M:%S").to_string()),
+                expires_at: expires_at.map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
+                version,
+            };
+            
+            println!("✅ Consent granted: {} for user: {}", consent_type, user_id);
+            HttpResponse::Ok().json(response)
         }
-    }
-}
-
-// ========== WEBHOOK SUPPORT ==========
-
-/// Helper function to trigger webhook events asynchronously
-async fn trigger_webhook_event(
-    db_pool: &sqlx::PgPool,
-    event_type: &str,
-    event_data: serde_json::Value,
-) {
-    // Find active webhooks subscribed to this event type
-    let webhooks_result: Result<Vec<WebhookEndpointDb>, _> = sqlx::query_as(
-        "SELECT * FROM webhook_endpoints 
-         WHERE active = true 
-         AND $1 = ANY(event_types)"
-    )
-    .bind(event_type)
-    .fetch_all(db_pool)
-    .await;
-
-    let webhooks = match webhooks_result {
-        Ok(wh) => wh,
         Err(e) => {
             let request_id = generate_request_id();
             let request_id = generate_request_id();
-            log_error_safely("fetching webhooks", &e, &request_id);
-            return;
+            log_error_safely("granting consent", &e, &request_id);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to grant consent"
+            }))
         }
-    };
-
-    if webhooks.is_empty() {
-        return;
-    }
-
-    let timestamp = Utc::now().to_rfc3339();
-
-    // Create webhook event
-    let webhook_event = WebhookEvent {
-        event_type: event_type.to_string(),
-        timestamp: timestamp.clone(),
-        data: event_data,
-        signature: None, // Will be added by webhook service
-    };
-
-    // Deliver to each webhook endpoint
-    for webhook in webhooks {
-        let endpoint_url = webhook.endpoint_url.clone();
-        let secret_key = webhook.secret_key.clone();
-        let timeout = webhook.timeout_seconds as u64;
-        let max_retries = webhook.retry_count;
-        let webhook_id = webhook.id;
-        let event_type_clone = event_type.to_string();
-
-        // Clone event for each webhook
-        let event_clone = webhook_event.clone();
-
-        // Spawn async task for webhook delivery
-        let db_pool_clone = db_pool.clone();
-        tokio::spawn(async move {
-            let webhook_service = WebhookService::new();
-            let (success, attempts, response) = webhook_service
-                .deliver_with_retry(
-                    &endpoint_url,
-                    &event_clone,
-                    &secret_key,
-                    timeout,
-                    max_retries,
-                )
-                .await;
-
-            // Log delivery result
-            let status = if success { "delivered" } else { "failed" };
-            let response_code = if success {
-                response.as_ref()
-                    .and_then(|r| r.split(':').next())
-                    .and_then(|s| s.split_whitespace().nth(1))
-                    .and_then(|s| s.parse::<i32>().ok())
-            } else {
-                None
-            };
-
-            let _ = sqlx::query(
-                "INSERT INTO webhook_deliveries (
-                    webhook_endpoint_id, event_type, event_payload,
-                    status, response_code, response_body, attempts, delivered_at
-                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)"
-            )
-            .bind(webhook_id)
-            .bind(&event_type_clone)
-            .bind(serde_json::to_value(&event_clone).unwrap_or(serde_json::json!({})))
-            .bind(status)
-            .bind(response_code)
-            .bind(&response)
-            .bind(attempts)
-            .bind(if success { Some(Utc::now()) } else { None })
-            .execute(&db_pool_clone)
-            .await;
-        });
     }
 }
 
-// 34. REGISTER WEBHOOK ENDPOINT
+// 17. WITHDRAW CONSENT
 #[utoipa::path(
     post,
-    path = "/webhooks",
-    request_body = WebhookEndpointRequest,
-    responses((status = 200, body = WebhookEndpointResponse))
+    path = "/consent/withdraw",
+    request_body = WithdrawConsentRequest,
+    responses((status = 200, body = serde_json::Value))
 )]
-pub async fn register_webhook(
-    req: web::Json<WebhookEndpointRequest>,
+pub async fn withdraw_consent(
+    req: web::Json<WithdrawConsentRequest>,
     http_req: HttpRequest,
     data: web::Data<AppState>,
 ) -> impl Responder {
     // AUTHENTICATION & AUTHORIZATION
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "webhook", "write").await {
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "consent", "write").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
 
-    let webhook_req = req.into_inner();
-
-    // Generate secret key if not provided
-    let secret_key = webhook_req.secret_key.unwrap_or_else(|| {
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
-        let bytes: Vec<u8> = (0..32).map(|_| rng.gen()).collect();
-        use base64::Engine;
-        base64::engine::general_purpose::STANDARD.encode(&bytes)
-    });
-
-    let webhook_id = Uuid::new_v4();
-    let retry_count = webhook_req.retry_count.unwrap_or(3);
-    let timeout_seconds = webhook_req.timeout_seconds.unwrap_or(30);
-
-    let result = sqlx::query_as::<_, WebhookEndpointDb>(
-        "INSERT INTO webhook_endpoints (
-            id, endpoint_url, secret_key, event_types,
-            active, retry_count, timeout_seconds
-        ) VALUES ($1, $2, $3, $4, $5, $6, $7)
-        RETURNING *"
-    )
-    .bind(webhook_id)
-    .bind(&webhook_req.endpoint_url)
-    .bind(&secret_key)
-    .bind(&webhook_req.event_types)
-    .bind(true)
-    .bind(retry_count)
-    .bind(timeout_seconds)
-    .fetch_one(&data.db_pool)
-    .await;
+    let withdraw_req = req.into_inner();
+    
+    let result = if let Some(consent_type) = withdraw_req.consent_type {
+        // Withdraw specific consent type
+        sqlx::query(
+            "UPDATE consent_records 
+             SET granted = false, withdrawn_at = CURRENT_TIMESTAMP
+             WHERE user_id = $1 AND consent_type = $2 AND granted = true AND withdrawn_at IS NULL"
+        )
+        .bind(&withdraw_req.user_id)
+        .bind(&consent_type)
+        .execute(&data.db_pool)
+        .await
+    } else {
+        // Withdraw all consents for user
+        sqlx::query(
+            "UPDATE consent_records 
+             SET granted = false, withdrawn_at = CURRENT_TIMESTAMP
+             WHERE user_id = $1 AND granted = true AND withdrawn_at IS NULL"
+        )
+        .bind(&withdraw_req.user_id)
+        .execute(&data.db_pool)
+        .await
+    };
 
     match result {
-        Ok(webhook) => {
-            println!("🔔 Webhook registered: {}", webhook.endpoint_url);
-            HttpResponse::Ok().json(WebhookEndpointResponse {
-                id: webhook.id.to_string(),
-                endpoint_url: webhook.endpoint_url,
-                event_types: webhook.event_types,
-                active: webhook.active,
-                retry_count: webhook.retry_count,
-                timeout_seconds: webhook.timeout_seconds,
-                created_at: webhook.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
-            })
+        Ok(rows) if rows.rows_affected() > 0 => {
+            println!("❌ Consent withdrawn for user: {}", withdraw_req.user_id);
+            HttpResponse::Ok().json(serde_json::json!({
+                "status": "SUCCESS",
+                "message": "Consent withdrawn successfully"
+            }))
         }
+        Ok(_) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "No active consent found"
+        })),
         Err(e) => {
             let request_id = generate_request_id();
-            log_error_safely("registering webhook", &e, &request_id);
+            let request_id = generate_request_id();
+            log_error_safely("withdrawing consent", &e, &request_id);
             HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to register webhook"
+                "error": "Failed to withdraw consent"
             }))
         }
     }
 }
 
-// 35. LIST WEBHOOK ENDPOINTS (with pagination)
+// 18. GET USER CONSENTS
 #[utoipa::path(
     get,
-    path = "/webhooks",
-    params(
-        ("page" = Option<i64>, Query, description = "Page number (default: 1)"),
-        ("limit" = Option<i64>, Query, description = "Items per page (default: 100, max: 1000)")
-    ),
-    responses((status = 200, body = WebhookEndpointsResponse))
+    path = "/consent/{user_id}",
+    responses((status = 200, body = UserConsentsResponse))
 )]
-pub async fn list_webhooks(
-    query: web::Query<std::collections::HashMap<String, String>>,
+pub async fn get_user_consents(
+    path: web::Path<String>,
     http_req: HttpRequest,
     data: web::Data<AppState>,
 ) -> impl Responder {
     // AUTHENTICATION & AUTHORIZATION
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "webhook", "read").await {
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "consent", "read").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
-    let page = query
-        .get("page")
-        .and_then(|s| s.parse::<i64>().ok())
-        .unwrap_or(1)
-        .max(1);
-    let limit = (query
-        .get("limit")
-        .and_then(|s| s.parse::<i64>().ok())
-        .unwrap_or(100))
-        .min(1000)
-        .max(1);
-    let offset = (page - 1) * limit;
-
-    // Get total count
-    let total_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM webhook_endpoints")
-        .fetch_one(&data.db_pool)
-        .await
-        .unwrap_or(0);
 
-    match sqlx::query_as::<_, WebhookEndpointDb>(
-        "SELECT * FROM webhook_endpoints ORDER BY created_at DESC LIMIT $1 OFFSET $2"
+    let user_id = path.into_inner();
+    
+    match sqlx::query_as::<_, ConsentRecordDb>(
+        "SELECT * FROM consent_records 
+         WHERE user_id = $1 
+         ORDER BY created_at DESC"
     )
-    .bind(limit)
-    .bind(offset)
+    .bind(&user_id)
     .fetch_all(&data.db_pool)
     .await
     {
-        Ok(webhooks) => {
-            let endpoints: Vec<WebhookEndpointResponse> = webhooks.into_iter().map(|w| {
-                WebhookEndpointResponse {
-                    id: w.id.to_string(),
-                    endpoint_url: w.endpoint_url,
-                    event_types: w.event_types,
-                    active: w.active,
-                    retry_count: w.retry_count,
-                    timeout_seconds: w.timeout_seconds,
-                    created_at: w.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+        Ok(records) => {
+            let consents: Vec<ConsentResponse> = records.into_iter().map(|r| {
+                ConsentResponse {
+                    consent_id: r.id.to_string(),
+                    user_id: r.user_id,
+                    consent_type: r.consent_type,
+                    granted: r.granted && r.withdrawn_at.is_none(),
+                    granted_at: r.granted_at.map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
+                    expires_at: r.expires_at.map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
+                    version: r.version,
                 }
             }).collect();
-
-            HttpResponse::Ok().json(WebhookEndpointsResponse {
-                endpoints,
-                total_count,
-            })
+            
+            let response = UserConsentsResponse {
+                user_id,
+                consents,
+            };
+            
+            HttpResponse::Ok().json(response)
         }
         Err(e) => {
             let request_id = generate_request_id();
-            log_error_safely("fetching webhooks", &e, &request_id);
+            let request_id = generate_request_id();
+            log_error_safely("fetching consents", &e, &request_id);
             HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to fetch webhooks"
+                "error": "Failed to fetch consents"
             }))
         }
     }
 }
 
-// 36. UPDATE WEBHOOK ENDPOINT
+// 19. CHECK CONSENT (Helper function for log_action)
+pub async fn check_consent(
+    db_pool: &sqlx::PgPool,
+    user_id: &str,
+    consent_type: &str,
+) -> Result<bool, sqlx::Error> {
+    let result: Option<bool> = sqlx::query_scalar(
+        "SELECT granted FROM consent_records 
+         WHERE user_id = $1 
+           AND consent_type = $2 
+           AND granted = true 
+           AND withdrawn_at IS NULL
+           AND (expires_at IS NULL OR expires_at > CURRENT_TIMESTAMP)
+         ORDER BY version DESC
+         LIMIT 1"
+    )
+    .bind(user_id)
+    .bind(consent_type)
+    .fetch_optional(db_pool)
+    .await?;
+    
+    Ok(result.unwrap_or(false))
+}
+
+// ========== PRIORITY 2: DPIA TRACKING (GDPR Article 35) ==========
+
+// 20. CREATE DPIA
 #[utoipa::path(
-    put,
-    path = "/webhooks/{id}",
-    request_body = UpdateWebhookEndpointRequest,
-    responses((status = 200, body = WebhookEndpointResponse))
+    post,
+    path = "/dpia",
+    request_body = DpiaRequest,
+    responses((status = 200, body = DpiaResponse))
 )]
-pub async fn update_webhook(
-    path: web::Path<String>,
-    req: web::Json<UpdateWebhookEndpointRequest>,
+pub async fn create_dpia(
+    req: web::Json<DpiaRequest>,
     http_req: HttpRequest,
     data: web::Data<AppState>,
 ) -> impl Responder {
     // AUTHENTICATION & AUTHORIZATION
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "webhook", "write").await {
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "dpia", "write").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
 
-    let webhook_id = path.into_inner();
-    let update_req = req.into_inner();
-
-    // Parse UUID
-    let id = match Uuid::parse_str(&webhook_id) {
-        Ok(uuid) => uuid,
-        Err(_) => {
-            return HttpResponse::BadRequest().json(serde_json::json!({
-                "error": "Invalid webhook ID"
-            }));
-        }
-    };
-
-    let result = sqlx::query_as::<_, WebhookEndpointDb>(
-        "UPDATE webhook_endpoints SET
-            endpoint_url = COALESCE($1, endpoint_url),
-            event_types = COALESCE($2, event_types),
-            active = COALESCE($3, active),
-            retry_count = COALESCE($4, retry_count),
-            timeout_seconds = COALESCE($5, timeout_seconds),
-            updated_at = CURRENT_TIMESTAMP
-        WHERE id = $6
-        RETURNING *"
+    let dpia_req = req.into_inner();
+    let now = Utc::now();
+    
+    // Generate DPIA ID
+    let dpia_id = format!("DPIA-{}-{:03}", now.format("%Y"), 
+        sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM dpia_records WHERE dpia_id LIKE $1"
+        )
+        .bind(format!("DPIA-{}%", now.format("%Y")))
+        .fetch_one(&data.db_pool)
+        .await
+        .unwrap_or(0) + 1);
+    
+    // Determine if consultation is required (Article 36 - High risk)
+    let consultation_required = dpia_req.risk_level == "HIGH" || 
+        dpia_req.identified_risks.iter().any(|r| 
+            r.to_lowercase().contains("discrimination") || 
+            r.to_lowercase().contains("systematic")
+        );
+    
+    let identified_risks_json = serde_json::to_value(&dpia_req.identified_risks).unwrap_or(serde_json::json!([]));
+    let mitigation_measures_json = serde_json::to_value(&dpia_req.mitigation_measures).unwrap_or(serde_json::json!([]));
+    
+    let dpia_uuid = Uuid::new_v4();
+    let result = sqlx::query(
+        "INSERT INTO dpia_records (
+            id, dpia_id, activity_name, description, legal_basis,
+            data_categories, data_subject_categories, processing_purposes,
+            risk_level, identified_risks, mitigation_measures, residual_risks,
+            consultation_required, status, created_by
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+        RETURNING dpia_id"
     )
-    .bind(update_req.endpoint_url)
-    .bind(update_req.event_types)
-    .bind(update_req.active)
-    .bind(update_req.retry_count)
-    .bind(update_req.timeout_seconds)
-    .bind(id)
-    .fetch_optional(&data.db_pool)
+    .bind(dpia_uuid)
+    .bind(&dpia_id)
+    .bind(&dpia_req.activity_name)
+    .bind(&dpia_req.description)
+    .bind(&dpia_req.legal_basis)
+    .bind(&dpia_req.data_categories)
+    .bind(&dpia_req.data_subject_categories)
+    .bind(&dpia_req.processing_purposes)
+    .bind(&dpia_req.risk_level)
+    .bind(identified_risks_json)
+    .bind(mitigation_measures_json)
+    .bind(serde_json::json!([]))
+    .bind(consultation_required)
+    .bind("DRAFT")
+    .bind(&dpia_req.created_by)
+    .fetch_one(&data.db_pool)
     .await;
 
     match result {
-        Ok(Some(webhook)) => {
-            HttpResponse::Ok().json(WebhookEndpointResponse {
-                id: webhook.id.to_string(),
-                endpoint_url: webhook.endpoint_url,
-                event_types: webhook.event_types,
-                active: webhook.active,
-                retry_count: webhook.retry_count,
-                timeout_seconds: webhook.timeout_seconds,
-                created_at: webhook.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
-            })
+        Ok(_) => {
+            let response = DpiaResponse {
+                dpia_id: dpia_id.clone(),
+                activity_name: dpia_req.activity_name,
+                status: "DRAFT".to_string(),
+                risk_level: dpia_req.risk_level,
+                consultation_required,
+                created_at: now.format("%Y-%m-%d %H:%M:%S").to_string(),
+            };
+            
+            println!("📋 DPIA created: {} for activity: {}", dpia_id, response.activity_name);
+            HttpResponse::Ok().json(response)
         }
-        Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
-            "error": "Webhook not found"
-        })),
         Err(e) => {
             let request_id = generate_request_id();
             let request_id = generate_request_id();
-            log_error_safely("updating webhook", &e, &request_id);
+            log_error_safely("creating DPIA", &e, &request_id);
             HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to update webhook"
+                "error": "Failed to create DPIA"
             }))
         }
     }
 }
 
-// 37. DELETE WEBHOOK ENDPOINT
+// 21. UPDATE DPIA
 #[utoipa::path(
-    delete,
-    path = "/webhooks/{id}",
-    responses((status = 200, body = serde_json::Value))
+    put,
+    path = "/dpia/{dpia_id}",
+    request_body = UpdateDpiaRequest,
+    responses((status = 200, body = DpiaResponse))
 )]
-pub async fn delete_webhook(
+pub async fn update_dpia(
     path: web::Path<String>,
+    req: web::Json<UpdateDpiaRequest>,
     http_req: HttpRequest,
     data: web::Data<AppState>,
 ) -> impl Responder {
     // AUTHENTICATION & AUTHORIZATION
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "webhook", "delete").await {
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "dpia", "write").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
 
-    let webhook_id = path.into_inner();
-    let id = match Uuid::parse_str(&webhook_id) {
-        Ok(uuid) => uuid,
-        Err(_) => {
-            return HttpResponse::BadRequest().json(serde_json::json!({
-                "error": "Invalid webhook ID"
-            }));
-        }
-    };
-
-    let result = sqlx::query("DELETE FROM webhook_endpoints WHERE id = $1")
-        .bind(id)
+    let dpia_id = path.into_inner();
+    let update_req = req.into_inner();
+    
+    // Clone values before using them
+    let status_clone = update_req.status.clone();
+    let reviewed_by_clone = update_req.reviewed_by.clone();
+    let next_review_date_parsed = update_req.next_review_date.as_ref()
+        .and_then(|s| chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").ok())
+        .map(|dt| DateTime::<Utc>::from_naive_utc_and_offset(dt, Utc));
+    let residual_risks_json = update_req.residual_risks.as_ref()
+        .map(|r| serde_json::to_value(r).unwrap_or(serde_json::json!([])));
+    
+    // For simplicity, use a fixed query structure
+    let result = if status_clone.is_some() && reviewed_by_clone.is_some() {
+        sqlx::query(
+            "UPDATE dpia_records 
+             SET status = $1, risk_level = COALESCE($2, risk_level),
+                 residual_risks = COALESCE($3, residual_risks),
+                 reviewed_by = $4, reviewed_at = CURRENT_TIMESTAMP,
+                 approval_date = CASE WHEN $1 = 'APPROVED' THEN CURRENT_TIMESTAMP ELSE approval_date END,
+                 next_review_date = COALESCE($5, next_review_date),
+                 updated_at = CURRENT_TIMESTAMP
+             WHERE dpia_id = $6"
+        )
+        .bind(status_clone.unwrap())
+        .bind(update_req.risk_level)
+        .bind(residual_risks_json)
+        .bind(reviewed_by_clone)
+        .bind(next_review_date_parsed)
+        .bind(&dpia_id)
         .execute(&data.db_pool)
-        .await;
-
-    match result {
-        Ok(rows) if rows.rows_affected() > 0 => {
-            println!("🔔 Webhook deleted: {}", webhook_id);
-            HttpResponse::Ok().json(serde_json::json!({
-                "status": "SUCCESS",
-                "message": "Webhook deleted"
-            }))
-        }
-        _ => HttpResponse::NotFound().json(serde_json::json!({
-            "error": "Webhook not found"
-        }))
-    }
-}
+        .await
+    } else {
+        // Simplified update for other cases
+        sqlx::query(
+            "UPDATE dpia_records 
+             SET status = COALESCE($1, status),
+                 risk_level = COALESCE($2, risk_level),
+                 residual_risks = COALESCE($3, residual_risks),
+                 next_review_date = COALESCE($4, next_review_date),
+                 updated_at = CURRENT_TIMESTAMP
+             WHERE dpia_id = $5"
+        )
+        .bind(status_clone)
+        .bind(update_req.risk_level)
+        .bind(residual_risks_json)
+        .bind(next_review_date_parsed)
+        .bind(&dpia_id)
+        .execute(&data.db_pool)
+        .await
+    };
 
-// 38. GET WEBHOOK DELIVERIES
+    match result {
+        Ok(rows) if rows.rows_affected() > 0 => {
+            // Fetch updated DPIA
+            match sqlx::query_as::<_, DpiaRecordDb>(
+                "SELECT * FROM dpia_records WHERE dpia_id = $1"
+            )
+            .bind(&dpia_id)
+            .fetch_optional(&data.db_pool)
+            .await
+            {
+                Ok(Some(dpia)) => {
+                    let response = DpiaResponse {
+                        dpia_id: dpia.dpia_id,
+                        activity_name: dpia.activity_name,
+                        status: dpia.status,
+                        risk_level: dpia.risk_level,
+                        consultation_required: dpia.consultation_required,
+                        created_at: dpia.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    };
+                    HttpResponse::Ok().json(response)
+                }
+                _ => HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Failed to fetch updated DPIA"
+                }))
+            }
+        }
+        _ => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "DPIA not found"
+        }))
+    }
+}
+
+// 22. GET ALL DPIAs (with pagination)
 #[utoipa::path(
     get,
-    path = "/webhooks/{id}/deliveries",
-    responses((status = 200, body = WebhookDeliveriesResponse))
+    path = "/dpias",
+    params(
+        ("page" = Option<i64>, Query, description = "Page number (default: 1)"),
+        ("limit" = Option<i64>, Query, description = "Items per page (default: 100, max: 1000)")
+    ),
+    responses((status = 200, body = serde_json::Value))
 )]
-pub async fn get_webhook_deliveries(
-    path: web::Path<String>,
+pub async fn get_all_dpias(
+    query: web::Query<std::collections::HashMap<String, String>>,
     http_req: HttpRequest,
     data: web::Data<AppState>,
 ) -> impl Responder {
     // AUTHENTICATION & AUTHORIZATION
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "webhook", "read").await {
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "dpia", "read").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
+    let page = query
+        .get("page")
+        .and_then(|s| s.parse::<i64>().ok())
+        .unwrap_or(1)
+        .max(1);
+    let limit = (query
+        .get("limit")
+        .and_then(|s| s.parse::<i64>().ok())
+        .unwrap_or(100))
+        .min(1000)
+        .max(1);
+    let offset = (page - 1) * limit;
 
-    let webhook_id = path.into_inner();
-    let id = match Uuid::parse_str(&webhook_id) {
-        Ok(uuid) => uuid,
-        Err(_) => {
-            return HttpResponse::BadRequest().json(serde_json::json!({
-                "error": "Invalid webhook ID"
-            }));
-        }
-    };
+    // Get total count
+    let total_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM dpia_records")
+        .fetch_one(&data.db_pool)
+        .await
+        .unwrap_or(0);
 
-    match sqlx::query_as::<_, WebhookDeliveryDb>(
-        "SELECT * FROM webhook_deliveries 
-         WHERE webhook_endpoint_id = $1 
-         ORDER BY created_at DESC 
-         LIMIT 100"
+    match sqlx::query_as::<_, DpiaRecordDb>(
+        "SELECT * FROM dpia_records ORDER BY created_at DESC LIMIT $1 OFFSET $2"
     )
-    .bind(id)
+    .bind(limit)
+    .bind(offset)
     .fetch_all(&data.db_pool)
     .await
     {
-        Ok(deliveries) => {
-            let delivery_responses: Vec<WebhookDeliveryResponse> = deliveries.into_iter().map(|d| {
-                WebhookDeliveryResponse {
-                    id: d.id.to_string(),
-                    event_type: d.event_type,
-                    status: d.status,
-                    response_code: d.response_code,
-                    attempts: d.attempts,
-                    created_at: d.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
-                    delivered_at: d.delivered_at.map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
+        Ok(records) => {
+            let dpias: Vec<DpiaResponse> = records.into_iter().map(|r| {
+                DpiaResponse {
+                    dpia_id: r.dpia_id,
+                    activity_name: r.activity_name,
+                    status: r.status,
+                    risk_level: r.risk_level,
+                    consultation_required: r.consultation_required,
+                    created_at: r.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
                 }
             }).collect();
-
-            let total_count = delivery_responses.len() as i64;
-            HttpResponse::Ok().json(WebhookDeliveriesResponse {
-                deliveries: delivery_responses,
-                total_count,
-            })
+            
+            HttpResponse::Ok().json(serde_json::json!({
+                "data": dpias,
+                "pagination": {
+                    "page": page,
+                    "limit": limit,
+                    "total": total_count,
+                    "total_pages": (total_count + limit - 1) / limit
+                }
+            }))
         }
         Err(e) => {
             let request_id = generate_request_id();
             let request_id = generate_request_id();
-            log_error_safely("fetching webhook deliveries", &e, &request_id);
+            log_error_safely("fetching DPIAs", &e, &request_id);
             HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to fetch webhook deliveries"
+                "error": "Failed to fetch DPIAs"
             }))
         }
     }
 }
 
-// ============================================================================
-// Priority 2: User Notification Preferences (EU AI Act Article 13)
-// ============================================================================
-
-#[derive(Serialize, Deserialize, ToSchema)]
-pub struct NotificationPreferenceRequest {
-    #[schema(example = "HIGH_RISK_AI_ACTION")]
-    pub notification_type: String,
-    #[schema(example = r#"["EMAIL", "SMS", "IN_APP"]"#)]
-    pub preferred_channels: Vec<String>,
-    #[schema(example = "en")]
-    pub language: String,
-    #[schema(example = true)]
-    pub enabled: bool,
-}
-
-#[derive(Serialize, Deserialize, ToSchema)]
-pub struct NotificationPreferenceResponse {
-    pub notification_type: String,
-    pub preferred_channels: Vec<String>,
-    pub language: String,
-    pub enabled: bool,
-    pub created_at: String,
-    pub updated_at: String,
-}
+// ========== PRIORITY 2: RETENTION PERIOD AUTOMATION (GDPR Article 5(1)(e)) ==========
 
-/// Set user notification preferences (EU AI Act Article 13)
+// 23. CREATE RETENTION POLICY
 #[utoipa::path(
     post,
-    path = "/user/{user_id}/notification_preferences",
-    request_body = NotificationPreferenceRequest,
-    responses(
-        (status = 200, description = "Preferences updated successfully", body = NotificationPreferenceResponse),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden")
-    ),
-    tag = "Notifications"
+    path = "/retention/policy",
+    request_body = RetentionPolicyRequest,
+    responses((status = 200, body = RetentionPolicyResponse))
 )]
-pub async fn set_notification_preferences(
-    user_id: web::Path<String>,
-    req: web::Json<NotificationPreferenceRequest>,
-    data: web::Data<AppState>,
+pub async fn create_retention_policy(
+    req: web::Json<RetentionPolicyRequest>,
     http_req: HttpRequest,
+    data: web::Data<AppState>,
 ) -> impl Responder {
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "notifications", "write").await {
+    // AUTHENTICATION & AUTHORIZATION
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "retention", "write").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
 
-    let user_id_str = user_id.into_inner();
-    let pref = req.into_inner();
-    let channels_json = serde_json::to_value(&pref.preferred_channels).unwrap_or(serde_json::json!([]));
-
+    let policy_req = req.into_inner();
+    let now = Utc::now();
+    
+    let policy_id = Uuid::new_v4();
     let result = sqlx::query(
-        "INSERT INTO user_notification_preferences (
-            user_id, notification_type, preferred_channels, language, enabled
-        ) VALUES ($1, $2, $3, $4, $5)
-        ON CONFLICT (user_id, notification_type) DO UPDATE SET
-            preferred_channels = EXCLUDED.preferred_channels,
-            language = EXCLUDED.language,
-            enabled = EXCLUDED.enabled,
-            updated_at = CURRENT_TIMESTAMP
-        RETURNING created_at, updated_at"
+        "INSERT INTO retention_policies (
+            id, policy_name, data_category, retention_period_days,
+            legal_basis, description, auto_delete, notification_days_before
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        RETURNING id"
     )
-    .bind(&user_id_str)
-    .bind(&pref.notification_type)
-    .bind(channels_json)
-    .bind(&pref.language)
-    .bind(pref.enabled)
+    .bind(policy_id)
+    .bind(&policy_req.policy_name)
+    .bind(&policy_req.data_category)
+    .bind(policy_req.retention_period_days)
+    .bind(&policy_req.legal_basis)
+    .bind(&policy_req.description)
+    .bind(policy_req.auto_delete.unwrap_or(true))
+    .bind(policy_req.notification_days_before)
     .fetch_one(&data.db_pool)
     .await;
 
     match result {
-        Ok(row) => {
-            let created_at: chrono::DateTime<chrono::Utc> = row.try_get("created_at").unwrap_or_else(|_| chrono::Utc::now());
-            let updated_at: chrono::DateTime<chrono::Utc> = row.try_get("updated_at").unwrap_or_else(|_| chrono::Utc::now());
-            HttpResponse::Ok().json(NotificationPreferenceResponse {
-                notification_type: pref.notification_type,
-                preferred_channels: pref.preferred_channels,
-                language: pref.language,
-                enabled: pref.enabled,
-                created_at: created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
-                updated_at: updated_at.format("%Y-%m-%d %H:%M:%S").to_string(),
-            })
+        Ok(_) => {
+            let response = RetentionPolicyResponse {
+                policy_id: policy_id.to_string(),
+                policy_name: policy_req.policy_name,
+                data_category: policy_req.data_category,
+                retention_period_days: policy_req.retention_period_days,
+                auto_delete: policy_req.auto_delete.unwrap_or(true),
+                created_at: now.format("%Y-%m-%d %H:%M:%S").to_string(),
+            };
+            
+            println!("📅 Retention policy created: {} for category: {}", response.policy_name, response.data_category);
+            HttpResponse::Ok().json(response)
         }
         Err(e) => {
             let request_id = generate_request_id();
-            log_error_safely("setting notification preferences", &e, &request_id);
+            let request_id = generate_request_id();
+            log_error_safely("creating retention policy", &e, &request_id);
             HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to set notification preferences"
+                "error": "Failed to create retention policy"
             }))
         }
     }
 }
 
-/// Get user notification preferences
+// 25. GET EXPIRING RECORDS
 #[utoipa::path(
     get,
-    path = "/user/{user_id}/notification_preferences",
-    responses(
-        (status = 200, description = "Preferences retrieved successfully", body = Vec<NotificationPreferenceResponse>),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden")
-    ),
-    tag = "Notifications"
+    path = "/retention/expiring",
+    responses((status = 200, body = ExpiringRecordsResponse))
 )]
-pub async fn get_notification_preferences(
-    user_id: web::Path<String>,
+#[allow(dead_code)]
+pub async fn get_expiring_records(
+    query: web::Query<std::collections::HashMap<String, String>>,
     data: web::Data<AppState>,
-    http_req: HttpRequest,
 ) -> impl Responder {
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "notifications", "read").await {
-        Ok(c) => c,
-        Err(resp) => return resp,
-    };
-
-    let user_id_str = user_id.into_inner();
-
-    #[derive(sqlx::FromRow)]
-    struct PreferenceRow {
-        notification_type: String,
-        preferred_channels: serde_json::Value,
-        language: String,
-        enabled: bool,
-        created_at: chrono::DateTime<chrono::Utc>,
-        updated_at: chrono::DateTime<chrono::Utc>,
-    }
-
-    match sqlx::query_as::<_, PreferenceRow>(
-        "SELECT notification_type, preferred_channels, language, enabled, created_at, updated_at
-         FROM user_notification_preferences
-         WHERE user_id = $1
-         ORDER BY notification_type"
+    // Get days parameter (default: 30 days)
+    let days: i32 = query.get("days")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(30);
+    
+    let now = Utc::now();
+    let expiration_threshold = now + chrono::Duration::days(days as i64);
+    
+    match sqlx::query_as::<_, RetentionAssignmentDb>(
+        "SELECT * FROM retention_assignments 
+         WHERE deleted_at IS NULL 
+           AND expires_at <= $1 
+           AND deletion_status = 'PENDING'
+         ORDER BY expires_at ASC
+         LIMIT 100"
     )
-    .bind(&user_id_str)
+    .bind(expiration_threshold)
     .fetch_all(&data.db_pool)
     .await
     {
-        Ok(prefs) => {
-            let responses: Vec<NotificationPreferenceResponse> = prefs.into_iter().map(|p| {
-                let channels: Vec<String> = serde_json::from_value(p.preferred_channels)
-                    .unwrap_or_else(|_| vec![]);
-                NotificationPreferenceResponse {
-                    notification_type: p.notification_type,
-                    preferred_channels: channels,
-                    language: p.language,
-                    enabled: p.enabled,
-                    created_at: p.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
-                    updated_at: p.updated_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+        Ok(records) => {
+            let expiring_records: Vec<RetentionTrackingResponse> = records.into_iter().map(|r| {
+                let days_until = (r.expires_at - now).num_days();
+                RetentionTrackingResponse {
+                    record_type: r.record_type,
+                    record_id: r.record_id,
+                    created_at: r.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    expires_at: r.expires_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    deletion_status: r.deletion_status,
+                    days_until_expiration: Some(days_until),
                 }
             }).collect();
-            HttpResponse::Ok().json(responses)
+            
+            let total_count = expiring_records.len() as i64;
+            let response = ExpiringRecordsResponse {
+                expiring_records,
+                total_count,
+            };
+            
+            HttpResponse::Ok().json(response)
         }
         Err(e) => {
             let request_id = generate_request_id();
             let request_id = generate_request_id();
-            log_error_safely("fetching notification preferences", &e, &request_id);
+            log_error_safely("fetching expiring records", &e, &request_id);
             HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to fetch notification preferences"
+                "error": "Failed to fetch expiring records"
             }))
         }
     }
 }
 
-/// Get notification history for a user
+// 26. EXECUTE RETENTION DELETION (Manual trigger for automated deletion)
 #[utoipa::path(
-    get,
-    path = "/user/{user_id}/notifications",
-    params(
-        ("user_id" = String, Path, description = "User ID"),
-        ("limit" = Option<i32>, Query, description = "Maximum number of notifications to return (default: 50)")
-    ),
-    responses(
-        (status = 200, description = "Notifications retrieved successfully"),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden")
-    ),
-    tag = "Notifications"
+    post,
+    path = "/retention/execute",
+    responses((status = 200, body = serde_json::Value))
 )]
-pub async fn get_user_notifications(
-    user_id: web::Path<String>,
-    query: web::Query<std::collections::HashMap<String, String>>,
+#[allow(dead_code)]
+pub async fn execute_retention_deletion(
     data: web::Data<AppState>,
-    http_req: HttpRequest,
 ) -> impl Responder {
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "notifications", "read").await {
-        Ok(c) => c,
-        Err(resp) => return resp,
-    };
-
-    let user_id_str = user_id.into_inner();
-    let limit = query.get("limit")
-        .and_then(|l| l.parse::<i32>().ok())
-        .unwrap_or(50)
-        .min(100); // Cap at 100
-
-    #[derive(sqlx::FromRow)]
-    struct NotificationRow {
-        notification_id: String,
-        notification_type: String,
-        channel: String,
-        subject: Option<String>,
-        body: String,
-        status: String,
-        sent_at: Option<chrono::DateTime<chrono::Utc>>,
-        created_at: chrono::DateTime<chrono::Utc>,
-    }
-
-    match sqlx::query_as::<_, NotificationRow>(
-        "SELECT notification_id, notification_type, channel, subject, body, status, sent_at, created_at
-         FROM user_notifications
-         WHERE user_id = $1
-         ORDER BY created_at DESC
-         LIMIT $2"
+    let now = Utc::now();
+    
+    // Find records that should be deleted
+    let expired_records: Vec<RetentionAssignmentDb> = sqlx::query_as(
+        "SELECT * FROM retention_assignments 
+         WHERE deleted_at IS NULL 
+           AND expires_at <= $1 
+           AND deletion_status = 'PENDING'
+           AND (record_type, record_id) NOT IN (
+               SELECT record_type, record_id FROM retention_exemptions 
+               WHERE (expires_at IS NULL OR expires_at > CURRENT_TIMESTAMP)
+           )"
     )
-    .bind(&user_id_str)
-    .bind(limit)
+    .bind(now)
     .fetch_all(&data.db_pool)
     .await
-    {
-        Ok(notifications) => {
-            let responses: Vec<serde_json::Value> = notifications.into_iter().map(|n| {
-                serde_json::json!({
-                    "notification_id": n.notification_id,
-                    "notification_type": n.notification_type,
-                    "channel": n.channel,
-                    "subject": n.subject,
-                    "body": n.body,
-                    "status": n.status,
-                    "sent_at": n.sent_at.map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
-                    "created_at": n.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
-                })
-            }).collect();
-            HttpResponse::Ok().json(serde_json::json!({
-                "notifications": responses,
-                "total": responses.len()
-            }))
-        }
-        Err(e) => {
-            let request_id = generate_request_id();
-            log_error_safely("fetching user notifications", &e, &request_id);
-            HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to fetch notifications"
-            }))
+    .unwrap_or_default();
+    
+    let mut deleted_count = 0;
+    let mut errors = Vec::new();
+    
+    for record in expired_records {
+        // Mark as deleted in assignment
+        let update_result = sqlx::query(
+            "UPDATE retention_assignments 
+             SET deleted_at = CURRENT_TIMESTAMP, deletion_status = 'DELETED'
+             WHERE id = $1"
+        )
+        .bind(record.id)
+        .execute(&data.db_pool)
+        .await;
+        
+        if update_result.is_ok() {
+            // Log deletion
+            let _ = sqlx::query(
+                "INSERT INTO retention_deletion_log (
+                    record_type, record_id, policy_id, deletion_method, deleted_by, records_affected
+                ) VALUES ($1, $2, $3, 'AUTO', 'system', 1)"
+            )
+            .bind(&record.record_type)
+            .bind(&record.record_id)
+            .bind(&record.policy_id)
+            .execute(&data.db_pool)
+            .await;
+            
+            // Actually delete the record based on type
+            match record.record_type.as_str() {
+                "COMPLIANCE_RECORD" => {
+                    // Compliance records are append-only: supersede with a tombstone
+                    let _ = crate::core::audit_chain::AuditChain::append_tombstone(
+                        &data.db_pool,
+                        &record.record_id,
+                        "DELETED (Retention Period)",
+                        "[RETENTION EXPIRED] Data Automatically Deleted",
+                    )
+                    .await;
+                }
+                "CONSENT" => {
+                    let _ = sqlx::query("DELETE FROM consent_records WHERE id::text = $1")
+                        .bind(&record.record_id)
+                        .execute(&data.db_pool)
+                        .await;
+                }
+                "DPIA" => {
+                    let _ = sqlx::query("DELETE FROM dpia_records WHERE dpia_id = $1")
+                        .bind(&record.record_id)
+                        .execute(&data.db_pool)
+                        .await;
+                }
+                "BREACH" => {
+                    let _ = sqlx::query("DELETE FROM data_breaches WHERE breach_id = $1")
+                        .bind(&record.record_id)
+                        .execute(&data.db_pool)
+                        .await;
+                }
+                _ => {
+                    errors.push(format!("Unknown record type: {}", record.record_type));
+                }
+            }
+            
+            deleted_count += 1;
+        } else {
+            errors.push(format!("Failed to update tracking for record: {}", record.record_id));
         }
     }
+    
+    HttpResponse::Ok().json(serde_json::json!({
+        "status": "SUCCESS",
+        "deleted_count": deleted_count,
+        "errors": errors
+    }))
 }
 
-// ============================================================================
-// Priority 3: GDPR Article 30 - Records of Processing Activities
-// ============================================================================
+// ========== PRIORITY 2: RETENTION PERIOD AUTOMATION (GDPR Article 5(1)(e)) ==========
 
-#[derive(Serialize, Deserialize, ToSchema)]
-pub struct ProcessingRecordResponse {
-    pub activity_name: String,
-    pub purpose: String,
-    pub legal_basis: String,
-    pub data_categories: Vec<String>,
-    pub data_subject_categories: Vec<String>,
-    pub recipients: Vec<String>,
-    pub third_country_transfers: bool,
-    pub third_countries: Vec<String>,
-    pub retention_period_days: Option<i32>,
-    pub security_measures: Vec<String>,
-    pub seal_id: Option<String>,
-    pub timestamp: String,
-}
-
-/// Get processing records in GDPR Article 30 format
+// 24. ASSIGN RETENTION POLICY TO RECORD
 #[utoipa::path(
-    get,
-    path = "/processing_records",
-    params(
-        ("format" = Option<String>, Query, description = "Export format: json, csv (default: json)")
-    ),
-    responses(
-        (status = 200, description = "Processing records retrieved successfully"),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden")
-    ),
-    tag = "Compliance"
+    post,
+    path = "/retention/assign",
+    request_body = AssignRetentionRequest,
+    responses((status = 200, body = RetentionStatusResponse))
 )]
-pub async fn get_processing_records(
-    query: web::Query<std::collections::HashMap<String, String>>,
-    data: web::Data<AppState>,
+pub async fn assign_retention_policy(
+    req: web::Json<AssignRetentionRequest>,
     http_req: HttpRequest,
+    data: web::Data<AppState>,
 ) -> impl Responder {
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "compliance", "read").await {
+    // AUTHENTICATION & AUTHORIZATION
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "retention", "write").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
 
-    let format_str = query.get("format").map(|s| s.as_str()).unwrap_or("json");
-
-    // Get processing activities from database
-    match sqlx::query_as::<_, ProcessingActivityDb>(
-        "SELECT * FROM processing_activities ORDER BY created_at DESC"
+    let assign_req = req.into_inner();
+    
+    // Get policy details
+    let policy_result: Result<Option<RetentionPolicyDb>, _> = sqlx::query_as(
+        "SELECT * FROM retention_policies WHERE policy_name = $1"
     )
-    .fetch_all(&data.db_pool)
-    .await
-    {
-        Ok(activities) => {
-            // Also get compliance records to enrich with seal_id and timestamp
-            let mut records: Vec<ProcessingRecordResponse> = Vec::new();
+    .bind(&assign_req.policy_name)
+    .fetch_optional(&data.db_pool)
+    .await;
+    
+    let policy = match policy_result {
+        Ok(Some(p)) => p,
+        Ok(None) => return HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Retention policy not found"
+        })),
+        Err(e) => {
+            let request_id = generate_request_id();
+            let request_id = generate_request_id();
+            log_error_safely("fetching policy", &e, &request_id);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch retention policy"
+            }));
+        }
+    };
+    
+    // Calculate expiration date
+    let created_at = Utc::now();
+    let expires_at = created_at + chrono::Duration::days(policy.retention_period_days as i64);
+    
+    let assignment_id = Uuid::new_v4();
+    let result = sqlx::query(
+        "INSERT INTO retention_assignments (
+            id, record_type, record_id, policy_id, created_at, expires_at, deletion_status
+        ) VALUES ($1, $2, $3, $4, $5, $6, 'PENDING')
+        ON CONFLICT (record_type, record_id) 
+        DO UPDATE SET policy_id = EXCLUDED.policy_id, expires_at = EXCLUDED.expires_at, deletion_status = 'PENDING'
+        RETURNING expires_at"
+    )
+    .bind(assignment_id)
+    .bind(&assign_req.record_type)
+    .bind(&assign_req.record_id)
+    .bind(policy.id)
+    .bind(created_at)
+    .bind(expires_at)
+    .fetch_one(&data.db_pool)
+    .await;
+
+    match result {
+        Ok(row) => {
+            let expires_at_db: chrono::DateTime<chrono::Utc> = sqlx::Row::get(&row, 0);
+            let days_until = (expires_at_db - Utc::now()).num_days();
             
-            for activity in activities {
-                // Try to find related compliance record
-                let seal_id: Option<String> = sqlx::query_scalar(
-                    "SELECT seal_id FROM compliance_records 
-                     WHERE action_summary LIKE $1 
-                     ORDER BY timestamp DESC LIMIT 1"
-                )
-                .bind(format!("%{}%", activity.activity_name))
-                .fetch_optional(&data.db_pool)
-                .await
-                .unwrap_or(None);
-                
-                let timestamp: Option<chrono::DateTime<chrono::Utc>> = if let Some(ref sid) = seal_id {
-                    sqlx::query_scalar(
-                        "SELECT timestamp FROM compliance_records WHERE seal_id = $1"
+            // Update record's retention_expires_at if applicable
+            match assign_req.record_type.as_str() {
+                "COMPLIANCE_RECORD" => {
+                    let _ = sqlx::query(
+                        "UPDATE compliance_records SET retention_expires_at = $1 WHERE seal_id = $2"
                     )
-                    .bind(sid)
-                    .fetch_optional(&data.db_pool)
-                    .await
-                    .unwrap_or(None)
-                } else {
-                    Some(activity.created_at)
-                };
-                
-                records.push(ProcessingRecordResponse {
-                    activity_name: activity.activity_name,
-                    purpose: activity.purpose,
-                    legal_basis: activity.legal_basis,
-                    data_categories: activity.data_categories,
-                    data_subject_categories: activity.data_subject_categories,
-                    recipients: activity.recipients,
-                    third_country_transfers: activity.third_country_transfers,
-                    third_countries: activity.third_countries,
-                    retention_period_days: activity.retention_period_days,
-                    security_measures: activity.security_measures,
-                    seal_id,
-                    timestamp: timestamp.map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
-                        .unwrap_or_else(|| activity.created_at.format("%Y-%m-%d %H:%M:%S").to_string()),
-                });
-            }
-            
-            // If no processing_activities exist, generate from compliance_records
-            if records.is_empty() {
-                let compliance_records: Vec<ComplianceRecordDb> = sqlx::query_as(
-                    "SELECT * FROM compliance_records 
-                     WHERE user_id IS NOT NULL 
-                     ORDER BY timestamp DESC LIMIT 100"
-                )
-                .fetch_all(&data.db_pool)
-                .await
-                .unwrap_or_default();
-                
-                for cr in compliance_records {
-                    records.push(ProcessingRecordResponse {
-                        activity_name: cr.action_summary.clone(),
-                        purpose: "AI system processing".to_string(),
-                        legal_basis: "CONSENT".to_string(), // Default, should be from consent_records
-                        data_categories: vec!["Personal data".to_string()],
-                        data_subject_categories: vec!["Data subjects".to_string()],
-                        recipients: vec![],
-                        third_country_transfers: false,
-                        third_countries: vec![],
-                        retention_period_days: None,
-                        security_measures: vec!["Encryption".to_string(), "Access controls".to_string()],
-                        seal_id: Some(cr.seal_id),
-                        timestamp: cr.timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
-                    });
+                    .bind(expires_at_db)
+                    .bind(&assign_req.record_id)
+                    .execute(&data.db_pool)
+                    .await;
                 }
-            }
-            
-            if format_str == "csv" {
-                // Generate CSV
-                let mut csv = String::from("Activity Name,Purpose,Legal Basis,Data Categories,Data Subject Categories,Recipients,Third Country Transfers,Third Countries,Retention Period (Days),Security Measures,Seal ID,Timestamp\n");
-                
-                for record in &records {
-                    csv.push_str(&format!(
-                        "\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",{},\"{}\",{},\"{}\",\"{}\",\"{}\"\n",
-                        record.activity_name,
-                        record.purpose,
-                        record.legal_basis,
-                        record.data_categories.join("; "),
-                        record.data_subject_categories.join("; "),
-                        record.recipients.join("; "),
-                        record.third_country_transfers,
-                        record.third_countries.join("; "),
-                        record.retention_period_days.map(|d| d.to_string()).unwrap_or_else(|| "N/A".to_string()),
-                        record.security_measures.join("; "),
-                        record.seal_id.as_deref().unwrap_or("N/A"),
-                        record.timestamp
-                    ));
+                "CONSENT_RECORD" => {
+                    let _ = sqlx::query(
+                        "UPDATE consent_records SET retention_expires_at = $1 WHERE id::text = $2"
+                    )
+                    .bind(expires_at_db)
+                    .bind(&assign_req.record_id)
+                    .execute(&data.db_pool)
+                    .await;
                 }
-                
-                HttpResponse::Ok()
-                    .content_type("text/csv")
-                    .append_header(actix_web::http::header::ContentDisposition::attachment("processing_records_article30.csv"))
-                    .body(csv)
-            } else {
-                HttpResponse::Ok().json(serde_json::json!({
-                    "records": records,
-                    "total": records.len(),
-                    "format": "GDPR Article 30"
-                }))
+                _ => {}
             }
+            
+            let policy_name_clone = policy.policy_name.clone();
+            let record_id_clone = assign_req.record_id.clone();
+            let record_type_clone = assign_req.record_type.clone();
+            
+            let response = RetentionStatusResponse {
+                record_type: record_type_clone.clone(),
+                record_id: record_id_clone.clone(),
+                policy_name: policy_name_clone.clone(),
+                expires_at: expires_at_db.format("%Y-%m-%d %H:%M:%S").to_string(),
+                days_until_expiration: days_until,
+                deletion_status: "PENDING".to_string(),
+            };
+            
+            println!("📅 Retention policy assigned: {} to {} ({})", policy_name_clone, record_id_clone, record_type_clone);
+            HttpResponse::Ok().json(response)
         }
         Err(e) => {
             let request_id = generate_request_id();
-            log_error_safely("fetching processing records", &e, &request_id);
+            let request_id = generate_request_id();
+            log_error_safely("assigning retention policy", &e, &request_id);
             HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to fetch processing records"
+                "error": "Failed to assign retention policy"
             }))
         }
     }
 }
 
-// ============================================================================
-// Priority 3: EU AI Act Article 8 - Conformity Assessment
-// ============================================================================
+// 25. GET RETENTION STATUS
+#[utoipa::path(
+    get,
+    path = "/retention/status/{record_type}/{record_id}",
+    responses((status = 200, body = RetentionStatusResponse))
+)]
+pub async fn get_retention_status(
+    path: web::Path<(String, String)>,
+    http_req: HttpRequest,
+    data: web::Data<AppState>,
+) -> impl Responder {
+    // AUTHENTICATION & AUTHORIZATION
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "retention", "read").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
 
-#[derive(Serialize, Deserialize, ToSchema)]
-pub struct ConformityAssessmentRequest {
-    pub system_id: String,
-    pub system_name: String,
-    pub assessment_type: String, // 'SELF_ASSESSMENT', 'THIRD_PARTY', 'NOTIFIED_BODY'
-    pub assessment_date: String,
-    pub expiration_date: Option<String>,
-    pub assessment_result: serde_json::Value,
-    pub assessor_name: Option<String>,
-    pub assessor_contact: Option<String>,
-    pub certificate_number: Option<String>,
-    pub notes: Option<String>,
-}
-
-#[derive(Serialize, Deserialize, ToSchema)]
-pub struct ConformityAssessmentResponse {
-    pub assessment_id: String,
-    pub system_id: String,
-    pub system_name: String,
-    pub assessment_type: String,
-    pub assessment_date: String,
-    pub expiration_date: Option<String>,
-    pub status: String,
-    pub days_until_expiration: Option<i64>,
-    pub assessor_name: Option<String>,
-    pub certificate_number: Option<String>,
+    let (record_type, record_id) = path.into_inner();
+    
+    match sqlx::query_as::<_, RetentionAssignmentDb>(
+        "SELECT ra.* FROM retention_assignments ra
+         INNER JOIN retention_policies rp ON ra.policy_id = rp.id
+         WHERE ra.record_type = $1 AND ra.record_id = $2 AND ra.deleted_at IS NULL
+         ORDER BY ra.created_at DESC
+         LIMIT 1"
+    )
+    .bind(&record_type)
+    .bind(&record_id)
+    .fetch_optional(&data.db_pool)
+    .await
+    {
+        Ok(Some(assignment)) => {
+            // Get policy name
+            let policy_name: String = sqlx::query_scalar(
+                "SELECT policy_name FROM retention_policies WHERE id = $1"
+            )
+            .bind(assignment.policy_id)
+            .fetch_one(&data.db_pool)
+            .await
+            .unwrap_or_else(|_| "UNKNOWN".to_string());
+            
+            let days_until = (assignment.expires_at - Utc::now()).num_days();
+            
+            let response = RetentionStatusResponse {
+                record_type,
+                record_id,
+                policy_name,
+                expires_at: assignment.expires_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                days_until_expiration: days_until,
+                deletion_status: assignment.deletion_status,
+            };
+            
+            HttpResponse::Ok().json(response)
+        }
+        Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Retention assignment not found"
+        })),
+        Err(e) => {
+            let request_id = generate_request_id();
+            let request_id = generate_request_id();
+            log_error_safely("fetching retention status", &e, &request_id);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch retention status"
+            }))
+        }
+    }
 }
 
-/// Create or update conformity assessment
+// 26. GET ALL RETENTION POLICIES
 #[utoipa::path(
-    post,
-    path = "/conformity_assessments",
-    request_body = ConformityAssessmentRequest,
-    responses(
-        (status = 200, description = "Assessment created/updated successfully", body = ConformityAssessmentResponse),
-        (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden")
-    ),
-    tag = "Compliance"
+    get,
+    path = "/retention/policies",
+    responses((status = 200, body = RetentionPoliciesResponse))
 )]
-pub async fn create_conformity_assessment(
-    req: web::Json<ConformityAssessmentRequest>,
-    data: web::Data<AppState>,
+pub async fn get_all_retention_policies(
     http_req: HttpRequest,
+    data: web::Data<AppState>,
 ) -> impl Responder {
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "compliance", "write").await {
+    // AUTHENTICATION & AUTHORIZATION
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "retention", "read").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
-
-    let assessment_id = format!("ASSESS-{}", uuid::Uuid::new_v4().to_string().replace("-", "").chars().take(12).collect::<String>());
-    let assessment_req = req.into_inner();
-    
-    let assessment_date = chrono::NaiveDateTime::parse_from_str(&assessment_req.assessment_date, "%Y-%m-%d %H:%M:%S")
-        .map(|dt| DateTime::<Utc>::from_naive_utc_and_offset(dt, Utc))
-        .unwrap_or(Utc::now());
-    
-    let expiration_date = assessment_req.expiration_date.and_then(|d| {
-        chrono::NaiveDateTime::parse_from_str(&d, "%Y-%m-%d %H:%M:%S")
-            .ok()
-            .map(|dt| DateTime::<Utc>::from_naive_utc_and_offset(dt, Utc))
-    });
-    
-    // Determine status based on expiration
-    let status = if let Some(exp) = expiration_date {
-        if exp < Utc::now() {
-            "EXPIRED"
-        } else {
-            "PASSED"
-        }
-    } else {
-        "PASSED"
-    }.to_string();
-    
-    let result = sqlx::query(
-        "INSERT INTO conformity_assessments (
-            assessment_id, system_id, system_name, assessment_type,
-            assessment_date, expiration_date, status, assessment_result,
-            assessor_name, assessor_contact, certificate_number, notes
-        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
-        ON CONFLICT (assessment_id) DO UPDATE SET
-            system_name = EXCLUDED.system_name,
-            assessment_type = EXCLUDED.assessment_type,
-            assessment_date = EXCLUDED.assessment_date,
-            expiration_date = EXCLUDED.expiration_date,
-            status = EXCLUDED.status,
-            assessment_result = EXCLUDED.assessment_result,
-            assessor_name = EXCLUDED.assessor_name,
-            assessor_contact = EXCLUDED.assessor_contact,
-            certificate_number = EXCLUDED.certificate_number,
-            notes = EXCLUDED.notes,
-            updated_at = CURRENT_TIMESTAMP"
+    match sqlx::query_as::<_, RetentionPolicyDb>(
+        "SELECT * FROM retention_policies ORDER BY created_at DESC"
     )
-    .bind(&assessment_id)
-    .bind(&assessment_req.system_id)
-    .bind(&assessment_req.system_name)
-    .bind(&assessment_req.assessment_type)
-    .bind(assessment_date)
-    .bind(expiration_date)
-    .bind(&status)
-    .bind(&assessment_req.assessment_result)
-    .bind(&assessment_req.assessor_name)
-    .bind(&assessment_req.assessor_contact)
-    .bind(&assessment_req.certificate_number)
-    .bind(&assessment_req.notes)
-    .execute(&data.db_pool)
-    .await;
-
-    match result {
-        Ok(_) => {
-            let days_until_expiration = expiration_date.map(|exp| {
-                (exp - Utc::now()).num_days()
-            });
-            
-            // Check if expiration is within 30 days and send notification
-            if let Some(days) = days_until_expiration {
-                if days <= 30 && days > 0 {
-                    // Send notification about expiring assessment
-                    let notification_service = data.notification_service.clone();
-                    let db_pool = data.db_pool.clone();
-                    let system_name_clone = assessment_req.system_name.clone();
-                    let assessment_id_clone = assessment_id.clone();
-                    
-                    tokio::spawn(async move {
-                        // Notify compliance team (using system admin user or default)
-                        let _ = notification_service.send_notification(
-                            &db_pool,
-                            crate::integration::notifications::NotificationRequest {
-                                user_id: "SYSTEM_ADMIN".to_string(),
-                                notification_type: crate::integration::notifications::NotificationType::HighRiskAiAction,
-                                channel: crate::integration::notifications::NotificationChannel::Email,
-                                subject: Some(format!("Conformity Assessment Expiring Soon - {}", system_name_clone)),
-                                body: format!(
-                                    "The conformity assessment for AI system '{}' will expire in {} days.\n\nAssessment ID: {}\n\nPlease schedule a new assessment before expiration.\n\nBest regards,\nVeridion Nexus Compliance Team",
-                                    system_name_clone, days, assessment_id_clone
-                                ),
-                                language: Some("en".to_string()),
-                                related_entity_type: Some("CONFORMITY_ASSESSMENT".to_string()),
-                                related_entity_id: Some(assessment_id_clone),
-                            }
-                        ).await;
-                    });
+    .fetch_all(&data.db_pool)
+    .await
+    {
+        Ok(records) => {
+            let policies: Vec<RetentionPolicyResponse> = records.into_iter().map(|r| {
+                RetentionPolicyResponse {
+                    policy_id: r.id.to_string(),
+                    policy_name: r.policy_name,
+                    data_category: r.data_category,
+                    retention_period_days: r.retention_period_days,
+                    auto_delete: r.auto_delete,
+                    created_at: r.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
                 }
-            }
+            }).collect();
             
-            HttpResponse::Ok().json(ConformityAssessmentResponse {
-                assessment_id,
-                system_id: assessment_req.system_id,
-                system_name: assessment_req.system_name,
-                assessment_type: assessment_req.assessment_type,
-                assessment_date: assessment_date.format("%Y-%m-%d %H:%M:%S").to_string(),
-                expiration_date: expiration_date.map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string()),
-                status,
-                days_until_expiration,
-                assessor_name: assessment_req.assessor_name,
-                certificate_number: assessment_req.certificate_number,
-            })
+            let response = RetentionPoliciesResponse { policies };
+            HttpResponse::Ok().json(response)
         }
         Err(e) => {
             let request_id = generate_request_id();
             let request_id = generate_request_id();
-            log_error_safely("creating conformity assessment", &e, &request_id);
+            log_error_safely("fetching retention policies", &e, &request_id);
             HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to create conformity assessment"
+                "error": "Failed to fetch retention policies"
             }))
         }
     }
 }
 
-/// Get all conformity assessments
+// 27. EXECUTE RETENTION DELETION (Background job - can be called manually)
 #[utoipa::path(
-    get,
-    path = "/conformity_assessments",
-    params(
-        ("system_id" = Option<String>, Query, description = "Filter by system_id"),
-        ("status" = Option<String>, Query, description = "Filter by status")
-    ),
-    responses(
-        (status = 200, description = "Assessments retrieved successfully", body = Vec<ConformityAssessmentResponse>),
-        (status = 401, description = "Unauthorized")
-    ),
-    tag = "Compliance"
+    post,
+    path = "/retention/execute_deletions",
+    responses((status = 200, body = serde_json::Value))
 )]
-pub async fn get_conformity_assessments(
-    query: web::Query<std::collections::HashMap<String, String>>,
+pub async fn execute_retention_deletions(
     data: web::Data<AppState>,
     http_req: HttpRequest,
 ) -> impl Responder {
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "compliance", "read").await {
+    // AUTHENTICATION & AUTHORIZATION - Critical: admin only
+    let auth_service = match AuthService::new() {
+        Ok(service) => service,
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Failed to initialize auth service: {}", e)
+            }));
+        }
+    };
+    let claims = match extract_claims(&http_req, &auth_service) {
         Ok(c) => c,
         Err(resp) => return resp,
     };
-
-    let system_id_filter = query.get("system_id");
-    let status_filter = query.get("status");
-
-    let mut query_str = "SELECT assessment_id, system_id, system_name, assessment_type, assessment_date, expiration_date, status, assessor_name, certificate_number FROM conformity_assessments WHERE 1=1".to_string();
-    let mut bindings = Vec::new();
     
-    if let Some(sid) = system_id_filter {
-        query_str.push_str(" AND system_id = $1");
-        bindings.push(sid.clone());
+    // Only admin can execute retention deletions
+    if !claims.has_role("admin") {
+        return HttpResponse::Forbidden().json(serde_json::json!({
+            "error": "Forbidden",
+            "message": "Only administrators can execute retention deletions"
+        }));
     }
+    // Find all expired assignments that haven't been deleted
+    let expired_assignments: Vec<RetentionAssignmentDb> = sqlx::query_as(
+        "SELECT ra.* FROM retention_assignments ra
+         INNER JOIN retention_policies rp ON ra.policy_id = rp.id
+         WHERE ra.expires_at <= CURRENT_TIMESTAMP 
+           AND ra.deleted_at IS NULL 
+           AND ra.deletion_status = 'PENDING'
+           AND rp.auto_delete = true
+         LIMIT 100"
+    )
+    .fetch_all(&data.db_pool)
+    .await
+    .unwrap_or_default();
     
-    if let Some(st) = status_filter {
-        let param_num = if system_id_filter.is_some() { 2 } else { 1 };
-        query_str.push_str(&format!(" AND status = ${}", param_num));
-        bindings.push(st.clone());
-    }
+    let mut deleted_count = 0;
+    let mut errors = Vec::new();
     
-    query_str.push_str(" ORDER BY assessment_date DESC");
-
-    #[derive(sqlx::FromRow)]
-    struct AssessmentRow {
-        assessment_id: String,
-        system_id: String,
-        system_name: String,
-        assessment_type: String,
-        assessment_date: chrono::DateTime<chrono::Utc>,
-        expiration_date: Option<chrono::DateTime<chrono::Utc>>,
-        status: String,
-        assessor_name: Option<String>,
-        certificate_number: Option<String>,
-    }
-
-    let result = if system_id_filter.is_some() && status_filter.is_some() {
-        sqlx::query_as::<_, AssessmentRow>(&query_str)
-            .bind(&bindings[0])
-            .bind(&bindings[1])
-            .fetch_all(&data.db_pool)
-            .await
-    } else if system_id_filter.is_some() {
-        sqlx::query_as::<_, AssessmentRow>(&query_str)
-            .bind(&bindings[0])
-            .fetch_all(&data.db_pool)
-            .await
-    } else if status_filter.is_some() {
-        sqlx::query_as::<_, AssessmentRow>(&query_str)
-            .bind(&bindings[0])
-            .fetch_all(&data.db_pool)
-            .await
-    } else {
-        sqlx::query_as::<_, AssessmentRow>(&query_str)
-            .fetch_all(&data.db_pool)
-            .await
-    };
-
-    match result {
-        Ok(assessments) => {
-            let responses: Vec<ConformityAssessmentResponse> = assessments.into_iter().map(|a| {
-                let days_until_expiration = a.expiration_date.map(|exp| {
-                    (exp - Utc::now()).num_days()
-                });
+    for assignment in expired_assignments {
+        // Mark as scheduled
+        let _ = sqlx::query(
+            "UPDATE retention_assignments SET deletion_status = 'SCHEDULED' WHERE id = $1"
+        )
+        .bind(assignment.id)
+        .execute(&data.db_pool)
+        .await;
+        
+        // Delete based on record type
+        let deletion_result = match assignment.record_type.as_str() {
+            "COMPLIANCE_RECORD" => {
+                // Use crypto-shredder for compliance records
+                let tx_id_result: Result<Option<String>, _> = sqlx::query_scalar(
+                    "SELECT tx_id FROM compliance_records WHERE seal_id = $1"
+                )
+                .bind(&assignment.record_id)
+                .fetch_optional(&data.db_pool)
+                .await;
                 
-                ConformityAssessmentResponse {
-                    assessment_id: a.assessment_id,
-                    system_id: a.system_id,
-                    system_name: a.system_name,
-                    assessment_type: a.assessment_type,
-                    assessment_date: a.assessment_date.format("%Y-%m-%d %H:%M:%S").to_string(),
-                    expiration_date: a.expiration_date.map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string()),
-                    status: a.status,
-                    days_until_expiration,
-                    assessor_name: a.assessor_name,
-                    certificate_number: a.certificate_number,
+                if let Ok(Some(tx_id)) = tx_id_result {
+                    // Shred the key (in memory and in the database)
+                    let _ = data.key_store.shred_key_persisted(&data.db_pool, &tx_id).await;
+                    
+                    // Append a retention tombstone to the audit chain
+                    let retention_summary = "[RETENTION EXPIRED] Data Automatically Deleted";
+                    let retention_status = "DELETED (Retention Period)";
+                    crate::core::audit_chain::AuditChain::append_tombstone(
+                        &data.db_pool,
+                        &assignment.record_id,
+                        retention_status,
+                        retention_summary,
+                    )
+                    .await
+                    .map(|_| sqlx::postgres::PgQueryResult::default())
+                } else {
+                    Ok(sqlx::postgres::PgQueryResult::default())
                 }
-            }).collect();
-            HttpResponse::Ok().json(responses)
-        }
-        Err(e) => {
-            let request_id = generate_request_id();
-            let request_id = generate_request_id();
-            log_error_safely("fetching conformity assessments", &e, &request_id);
-            HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to fetch conformity assessments"
-            }))
+            }
+            "CONSENT_RECORD" => {
+                // Mark consent as withdrawn
+                sqlx::query(
+                    "UPDATE consent_records 
+                     SET granted = false, withdrawn_at = CURRENT_TIMESTAMP
+                     WHERE id::text = $1"
+                )
+                .bind(&assignment.record_id)
+                .execute(&data.db_pool)
+                .await
+            }
+            _ => {
+                // For other types, just mark as deleted
+                Ok(sqlx::postgres::PgQueryResult::default())
+            }
+        };
+        
+        match deletion_result {
+            Ok(_) => {
+                // Mark assignment as deleted
+                let _ = sqlx::query(
+                    "UPDATE retention_assignments 
+                     SET deleted_at = CURRENT_TIMESTAMP, deletion_status = 'DELETED'
+                     WHERE id = $1"
+                )
+                .bind(assignment.id)
+                .execute(&data.db_pool)
+                .await;
+                
+                // Log deletion
+                let policy_name: String = sqlx::query_scalar(
+                    "SELECT policy_name FROM retention_policies WHERE id = $1"
+                )
+                .bind(assignment.policy_id)
+                .fetch_one(&data.db_pool)
+                .await
+                .unwrap_or_else(|_| "UNKNOWN".to_string());
+                
+                let _ = sqlx::query(
+                    "INSERT INTO retention_deletion_log (
+                        assignment_id, record_type, record_id, policy_name, deletion_method
+                    ) VALUES ($1, $2, $3, $4, 'AUTO')"
+                )
+                .bind(assignment.id)
+                .bind(&assignment.record_type)
+                .bind(&assignment.record_id)
+                .bind(&policy_name)
+                .execute(&data.db_pool)
+                .await;
+                
+                deleted_count += 1;
+            }
+            Err(e) => {
+                errors.push(format!("Failed to delete {} {}: {}", assignment.record_type, assignment.record_id, e));
+            }
         }
     }
+    
+    HttpResponse::Ok().json(serde_json::json!({
+        "status": "SUCCESS",
+        "deleted_count": deleted_count,
+        "errors": errors
+    }))
 }
 
-// ============================================================================
-// Priority 3: EU AI Act Article 11 - Data Governance Extension
-// ============================================================================
-
-#[derive(Serialize, Deserialize, ToSchema)]
-pub struct DataQualityMetricRequest {
-    pub seal_id: String,
-    pub data_source: Option<String>,
-    pub metric_type: String, // 'COMPLETENESS', 'ACCURACY', 'CONSISTENCY', 'VALIDITY', 'TIMELINESS'
-    pub metric_value: f64,
-    pub threshold: Option<f64>,
-}
-
-#[derive(Serialize, Deserialize, ToSchema)]
-pub struct DataBiasDetectionRequest {
-    pub seal_id: String,
-    pub bias_type: String, // 'DEMOGRAPHIC', 'GEOGRAPHIC', 'TEMPORAL', 'REPRESENTATION'
-    pub bias_metric: f64,
-    pub affected_groups: serde_json::Value,
-    pub mitigation_applied: Option<bool>,
-}
-
-#[derive(Serialize, Deserialize, ToSchema)]
-pub struct DataLineageRequest {
-    pub seal_id: String,
-    pub source_seal_id: Option<String>,
-    pub transformation_type: Option<String>,
-    pub transformation_details: Option<serde_json::Value>,
-}
+// ========== PRIORITY 2: POST-MARKET MONITORING (EU AI Act Article 72) ==========
 
-/// Record data quality metric
+// 28. CREATE MONITORING EVENT
 #[utoipa::path(
     post,
-    path = "/data_quality/metrics",
-    request_body = DataQualityMetricRequest,
-    responses(
-        (status = 200, description = "Metric recorded successfully"),
-        (status = 401, description = "Unauthorized")
-    ),
-    tag = "Data Governance"
+    path = "/monitoring/event",
+    request_body = MonitoringEventRequest,
+    responses((status = 200, body = MonitoringEventResponse))
 )]
-pub async fn record_data_quality_metric(
-    req: web::Json<DataQualityMetricRequest>,
-    data: web::Data<AppState>,
+pub async fn create_monitoring_event(
+    req: web::Json<MonitoringEventRequest>,
     http_req: HttpRequest,
+    data: web::Data<AppState>,
 ) -> impl Responder {
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "compliance", "write").await {
+    // AUTHENTICATION & AUTHORIZATION
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "monitoring", "write").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
 
+    let event_req = req.into_inner();
+    let now = Utc::now();
+    
+    // Generate event ID
+    let event_id = format!("EVT-{}-{:06}", now.format("%Y%m%d"), 
+        sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM monitoring_events WHERE event_id LIKE $1"
+        )
+        .bind(format!("EVT-{}%", now.format("%Y%m%d")))
+        .fetch_one(&data.db_pool)
+        .await
+        .unwrap_or(0) + 1);
+    
+    let detected_at = event_req.detected_at.as_ref()
+        .and_then(|s| chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").ok())
+        .map(|dt| DateTime::<Utc>::from_naive_utc_and_offset(dt, Utc))
+        .unwrap_or(now);
+    
+    let affected_users_json = serde_json::to_value(event_req.affected_users.unwrap_or_default()).unwrap_or(serde_json::json!([]));
+    let affected_count = affected_users_json.as_array().map(|a| a.len() as i32).unwrap_or(0);
+    
+    let event_uuid = Uuid::new_v4();
     let result = sqlx::query(
-        "INSERT INTO data_quality_metrics (
-            seal_id, data_source, metric_type, metric_value, threshold
-        ) VALUES ($1, $2, $3, $4, $5)"
+        "INSERT INTO monitoring_events (
+            id, event_id, event_type, severity, system_id, system_version,
+            description, affected_users, affected_records_count, detected_at,
+            resolution_status
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, 'OPEN')
+        RETURNING event_id"
     )
-    .bind(&req.seal_id)
-    .bind(&req.data_source)
-    .bind(&req.metric_type)
-    .bind(req.metric_value as f64)
-    .bind(req.threshold.map(|t| t as f64))
-    .execute(&data.db_pool)
+    .bind(event_uuid)
+    .bind(&event_id)
+    .bind(&event_req.event_type)
+    .bind(&event_req.severity)
+    .bind(&event_req.system_id)
+    .bind(&event_req.system_version)
+    .bind(&event_req.description)
+    .bind(affected_users_json)
+    .bind(affected_count)
+    .bind(detected_at)
+    .fetch_one(&data.db_pool)
     .await;
 
     match result {
         Ok(_) => {
-            // Check if metric is below threshold
-            if let Some(threshold) = req.threshold {
-                if req.metric_value < threshold {
-                    println!("⚠️ Data quality alert: {} metric {} is below threshold {}", 
-                        req.metric_type, req.metric_value, threshold);
-                }
-            }
-            HttpResponse::Ok().json(serde_json::json!({
-                "status": "SUCCESS",
-                "message": "Data quality metric recorded"
-            }))
+            let response = MonitoringEventResponse {
+                event_id: event_id.clone(),
+                event_type: event_req.event_type,
+                severity: event_req.severity,
+                system_id: event_req.system_id,
+                resolution_status: "OPEN".to_string(),
+                detected_at: detected_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            };
+            
+            println!("📊 Monitoring event created: {} for system: {}", event_id, response.system_id);
+            HttpResponse::Ok().json(response)
         }
         Err(e) => {
             let request_id = generate_request_id();
             let request_id = generate_request_id();
-            log_error_safely("recording data quality metric", &e, &request_id);
+            log_error_safely("creating monitoring event", &e, &request_id);
             HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to record metric"
+                "error": "Failed to create monitoring event"
             }))
         }
     }
 }
 
-/// Record data bias detection
+// 29. UPDATE EVENT RESOLUTION
 #[utoipa::path(
-    post,
-    path = "/data_quality/bias",
-    request_body = DataBiasDetectionRequest,
-    responses(
-        (status = 200, description = "Bias detection recorded successfully"),
-        (status = 401, description = "Unauthorized")
-    ),
-    tag = "Data Governance"
+    put,
+    path = "/monitoring/event/{event_id}",
+    request_body = UpdateEventResolutionRequest,
+    responses((status = 200, body = MonitoringEventResponse))
 )]
-pub async fn record_data_bias(
-    req: web::Json<DataBiasDetectionRequest>,
-    data: web::Data<AppState>,
+pub async fn update_event_resolution(
+    path: web::Path<String>,
+    req: web::Json<UpdateEventResolutionRequest>,
     http_req: HttpRequest,
+    data: web::Data<AppState>,
 ) -> impl Responder {
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "compliance", "write").await {
+    // AUTHENTICATION & AUTHORIZATION
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "monitoring", "write").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
 
-    let result = sqlx::query(
-        "INSERT INTO data_bias_detections (
-            seal_id, bias_type, bias_metric, affected_groups, mitigation_applied
-        ) VALUES ($1, $2, $3, $4, $5)"
+    let event_id = path.into_inner();
+    let update_req = req.into_inner();
+    
+    let resolved_at = if update_req.resolution_status == "RESOLVED" {
+        Some(Utc::now())
+    } else {
+        None
+    };
+    
+    // Check if event exists
+    let _event_exists = match sqlx::query_as::<_, MonitoringEventDb>(
+        "SELECT * FROM monitoring_events WHERE event_id = $1"
     )
-    .bind(&req.seal_id)
-    .bind(&req.bias_type)
-    .bind(req.bias_metric as f64)
-    .bind(&req.affected_groups)
-    .bind(req.mitigation_applied.unwrap_or(false))
-    .execute(&data.db_pool)
-    .await;
-
-    match result {
-        Ok(_) => {
-            println!("⚠️ Data bias detected: {} bias (metric: {}) for seal: {}", 
-                req.bias_type, req.bias_metric, req.seal_id);
-            HttpResponse::Ok().json(serde_json::json!({
-                "status": "SUCCESS",
-                "message": "Bias detection recorded"
-            }))
+    .bind(&event_id)
+    .fetch_optional(&data.db_pool)
+    .await {
+        Ok(Some(_)) => {} // Event exists, continue
+        Ok(None) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Monitoring event not found"
+            }));
         }
         Err(e) => {
             let request_id = generate_request_id();
             let request_id = generate_request_id();
-            log_error_safely("recording bias detection", &e, &request_id);
-            HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to record bias detection"
-            }))
+            log_error_safely("checking event existence", &e, &request_id);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Database error"
+            }));
         }
-    }
-}
-
-/// Record data lineage
-#[utoipa::path(
-    post,
-    path = "/data_quality/lineage",
-    request_body = DataLineageRequest,
-    responses(
-        (status = 200, description = "Lineage recorded successfully"),
-        (status = 401, description = "Unauthorized")
-    ),
-    tag = "Data Governance"
-)]
-pub async fn record_data_lineage(
-    req: web::Json<DataLineageRequest>,
-    data: web::Data<AppState>,
-    http_req: HttpRequest,
-) -> impl Responder {
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "compliance", "write").await {
-        Ok(c) => c,
-        Err(resp) => return resp,
     };
 
-    // Build lineage path
-    let mut lineage_path = Vec::new();
-    if let Some(ref source) = req.source_seal_id {
-        lineage_path.push(source.clone());
+    // Update the event
+    let update_result = sqlx::query(
+        "UPDATE monitoring_events 
+         SET resolution_status = $1, resolution_notes = $2,
+             corrective_action_taken = $3, preventive_measures = $4,
+             resolved_at = $5, updated_at = CURRENT_TIMESTAMP
+         WHERE event_id = $6"
+    )
+    .bind(&update_req.resolution_status)
+    .bind(&update_req.resolution_notes)
+    .bind(&update_req.corrective_action_taken)
+    .bind(&update_req.preventive_measures)
+    .bind(resolved_at)
+    .bind(&event_id)
+    .execute(&data.db_pool)
+    .await;
+
+    // Check if update succeeded
+    match update_result {
+        Ok(_) => {}
+        Err(e) => {
+            let request_id = generate_request_id();
+            let request_id = generate_request_id();
+            log_error_safely("updating event", &e, &request_id);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to update monitoring event"
+            }));
+        }
     }
-    lineage_path.push(req.seal_id.clone());
 
-    let result = sqlx::query(
-        "INSERT INTO data_lineage (
-            seal_id, source_seal_id, transformation_type, transformation_details, lineage_path
-        ) VALUES ($1, $2, $3, $4, $5)"
+    // Fetch updated event
+    let updated_event = sqlx::query_as::<_, MonitoringEventDb>(
+        "SELECT * FROM monitoring_events WHERE event_id = $1"
     )
-    .bind(&req.seal_id)
-    .bind(&req.source_seal_id)
-    .bind(&req.transformation_type)
-    .bind(&req.transformation_details)
-    .bind(&lineage_path)
-    .execute(&data.db_pool)
+    .bind(&event_id)
+    .fetch_optional(&data.db_pool)
     .await;
 
-    match result {
-        Ok(_) => {
-            HttpResponse::Ok().json(serde_json::json!({
-                "status": "SUCCESS",
-                "message": "Data lineage recorded"
-            }))
+    match updated_event {
+        Ok(Some(event)) => {
+            let response = MonitoringEventResponse {
+                event_id: event.event_id,
+                event_type: event.event_type,
+                severity: event.severity,
+                system_id: event.system_id,
+                resolution_status: event.resolution_status,
+                detected_at: event.detected_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            };
+            HttpResponse::Ok().json(response)
         }
+        Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Monitoring event not found"
+        })),
         Err(e) => {
             let request_id = generate_request_id();
             let request_id = generate_request_id();
-            log_error_safely("recording data lineage", &e, &request_id);
+            log_error_safely("updating event resolution", &e, &request_id);
             HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": "Failed to record lineage"
+                "error": "Failed to update event resolution"
             }))
         }
     }
 }
 
-/// Get data quality report for a seal
+// 30. GET ALL MONITORING EVENTS (with pagination)
 #[utoipa::path(
     get,
-    path = "/data_quality/report/{seal_id}",
-    responses(
-        (status = 200, description = "Quality report retrieved successfully"),
-        (status = 401, description = "Unauthorized")
+    path = "/monitoring/events",
+    params(
+        ("system_id" = Option<String>, Query, description = "Filter by system ID"),
+        ("page" = Option<i64>, Query, description = "Page number (default: 1)"),
+        ("limit" = Option<i64>, Query, description = "Items per page (default: 100, max: 1000)")
     ),
-    tag = "Data Governance"
+    responses((status = 200, body = MonitoringEventsResponse))
 )]
-pub async fn get_data_quality_report(
-    seal_id: web::Path<String>,
-    data: web::Data<AppState>,
+pub async fn get_all_monitoring_events(
+    query: web::Query<std::collections::HashMap<String, String>>,
     http_req: HttpRequest,
+    data: web::Data<AppState>,
 ) -> impl Responder {
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "compliance", "read").await {
+    // AUTHENTICATION & AUTHORIZATION
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "monitoring", "read").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
+    let system_id = query.get("system_id");
+    let page = query
+        .get("page")
+        .and_then(|s| s.parse::<i64>().ok())
+        .unwrap_or(1)
+        .max(1);
+    let limit = (query
+        .get("limit")
+        .and_then(|s| s.parse::<i64>().ok())
+        .unwrap_or(100))
+        .min(1000)
+        .max(1);
+    let offset = (page - 1) * limit;
+    
+    // Get total count
+    let total_count: i64 = if let Some(sid) = system_id {
+        sqlx::query_scalar("SELECT COUNT(*) FROM monitoring_events WHERE system_id = $1")
+            .bind(sid)
+            .fetch_one(&data.db_pool)
+            .await
+            .unwrap_or(0)
+    } else {
+        sqlx::query_scalar("SELECT COUNT(*) FROM monitoring_events")
+            .fetch_one(&data.db_pool)
+            .await
+            .unwrap_or(0)
+    };
+    
+    let query_final = if let Some(sid) = system_id {
+        sqlx::query_as::<_, MonitoringEventDb>(
+            "SELECT * FROM monitoring_events WHERE system_id = $1 ORDER BY detected_at DESC LIMIT $2 OFFSET $3"
+        )
+        .bind(sid)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&data.db_pool)
+        .await
+    } else {
+        sqlx::query_as::<_, MonitoringEventDb>(
+            "SELECT * FROM monitoring_events ORDER BY detected_at DESC LIMIT $1 OFFSET $2"
+        )
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&data.db_pool)
+        .await
+    };
 
-    let seal_id_str = seal_id.into_inner();
-
-    // Get quality metrics
-    #[derive(sqlx::FromRow)]
-    struct QualityRow {
-        metric_type: String,
-        metric_value: f64,
-        measured_at: chrono::DateTime<chrono::Utc>,
-    }
-
-    let metrics: Vec<QualityRow> = sqlx::query_as(
-        "SELECT metric_type, metric_value, measured_at 
-         FROM data_quality_metrics 
-         WHERE seal_id = $1 
-         ORDER BY measured_at DESC"
-    )
-    .bind(&seal_id_str)
-    .fetch_all(&data.db_pool)
-    .await
-    .unwrap_or_default();
-
-    // Get bias detections
-    #[derive(sqlx::FromRow)]
-    struct BiasRow {
-        bias_type: String,
-        bias_metric: f64,
-        affected_groups: serde_json::Value,
-        mitigation_applied: bool,
-        detected_at: chrono::DateTime<chrono::Utc>,
+    match query_final {
+        Ok(records) => {
+            let events: Vec<MonitoringEventResponse> = records.into_iter().map(|r| {
+                MonitoringEventResponse {
+                    event_id: r.event_id,
+                    event_type: r.event_type,
+                    severity: r.severity,
+                    system_id: r.system_id,
+                    resolution_status: r.resolution_status,
+                    detected_at: r.detected_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                }
+            }).collect();
+            
+            let response = MonitoringEventsResponse {
+                events,
+                total_count,
+            };
+            
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => {
+            let request_id = generate_request_id();
+            let request_id = generate_request_id();
+            log_error_safely("fetching monitoring events", &e, &request_id);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch monitoring events"
+            }))
+        }
     }
+}
 
-    let biases: Vec<BiasRow> = sqlx::query_as(
-        "SELECT bias_type, bias_metric, affected_groups, mitigation_applied, detected_at 
-         FROM data_bias_detections 
-         WHERE seal_id = $1 
-         ORDER BY detected_at DESC"
-    )
-    .bind(&seal_id_str)
-    .fetch_all(&data.db_pool)
-    .await
-    .unwrap_or_default();
-
-    // Get lineage
-    #[derive(sqlx::FromRow)]
-    struct LineageRow {
-        source_seal_id: Option<String>,
-        transformation_type: Option<String>,
-        lineage_path: Vec<String>,
-    }
+// 31. GET SYSTEM HEALTH STATUS
+#[utoipa::path(
+    get,
+    path = "/monitoring/health/{system_id}",
+    responses((status = 200, body = SystemHealthStatusResponse))
+)]
+pub async fn get_system_health(
+    path: web::Path<String>,
+    http_req: HttpRequest,
+    data: web::Data<AppState>,
+) -> impl Responder {
+    // AUTHENTICATION & AUTHORIZATION
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "monitoring", "read").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
 
-    let lineage: Vec<LineageRow> = sqlx::query_as(
-        "SELECT source_seal_id, transformation_type, lineage_path 
-         FROM data_lineage 
-         WHERE seal_id = $1"
+    let system_id = path.into_inner();
+    
+    match sqlx::query_as::<_, SystemHealthStatusDb>(
+        "SELECT * FROM system_health_status WHERE system_id = $1"
     )
-    .bind(&seal_id_str)
-    .fetch_all(&data.db_pool)
+    .bind(&system_id)
+    .fetch_optional(&data.db_pool)
     .await
-    .unwrap_or_default();
-
-    HttpResponse::Ok().json(serde_json::json!({
-        "seal_id": seal_id_str,
-        "quality_metrics": metrics.iter().map(|m| serde_json::json!({
-            "type": m.metric_type,
-            "value": m.metric_value,
-            "measured_at": m.measured_at.format("%Y-%m-%d %H:%M:%S").to_string()
-        })).collect::<Vec<_>>(),
-        "bias_detections": biases.iter().map(|b| serde_json::json!({
-            "type": b.bias_type,
-            "metric": b.bias_metric,
-            "affected_groups": b.affected_groups,
-            "mitigation_applied": b.mitigation_applied,
-            "detected_at": b.detected_at.format("%Y-%m-%d %H:%M:%S").to_string()
-        })).collect::<Vec<_>>(),
-        "lineage": lineage.iter().map(|l| serde_json::json!({
-            "source_seal_id": l.source_seal_id,
-            "transformation_type": l.transformation_type,
-            "lineage_path": l.lineage_path
-        })).collect::<Vec<_>>()
-    }))
+    {
+        Ok(Some(health)) => {
+            let response = SystemHealthStatusResponse {
+                system_id: health.system_id,
+                overall_status: health.overall_status,
+                compliance_status: health.compliance_status,
+                active_incidents_count: health.active_incidents_count,
+                critical_incidents_count: health.critical_incidents_count,
+                performance_score: health.performance_score,
+                compliance_score: health.compliance_score,
+                last_health_check: health.last_health_check.format("%Y-%m-%d %H:%M:%S").to_string(),
+            };
+            HttpResponse::Ok().json(response)
+        }
+        Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "System health status not found"
+        })),
+        Err(e) => {
+            let request_id = generate_request_id();
+            let request_id = generate_request_id();
+            log_error_safely("fetching system health", &e, &request_id);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch system health"
+            }))
+        }
+    }
 }
 
-// PROXY MODE - Network-level compliance enforcement
-// Endpoint: POST /api/v1/proxy
+// ========== AI-BOM (CycloneDX) EXPORT ==========
+
+// 32. EXPORT AI-BOM (CycloneDX format)
 #[utoipa::path(
-    post,
-    path = "/proxy",
-    request_body = crate::integration::proxy::ProxyRequest,
-    responses(
-        (status = 200, description = "Request proxied successfully"),
-        (status = 403, description = "Sovereignty violation - blocked"),
-        (status = 400, description = "Invalid request"),
-        (status = 500, description = "Proxy error")
+    get,
+    path = "/ai_bom/{system_id}",
+    params(
+        ("system_id" = String, Path, description = "System ID to export"),
+        ("format" = Option<String>, Query, description = "Export format (default: cyclonedx)")
     ),
-    tag = "Proxy Mode"
+    responses((status = 200, body = CycloneDxBom))
 )]
-pub async fn proxy_request(
-    req: HttpRequest,
-    body: web::Json<crate::integration::proxy::ProxyRequest>,
+pub async fn export_ai_bom(
+    path: web::Path<String>,
+    query: web::Query<std::collections::HashMap<String, String>>,
     data: web::Data<AppState>,
 ) -> impl Responder {
-    use crate::integration::proxy::ProxyService;
-    
-    let proxy_req = body.into_inner();
-    let proxy_service = ProxyService::new();
+    let system_id = path.into_inner();
+    let format = query.get("format").map(|s| s.as_str()).unwrap_or("cyclonedx");
 
-    // 0. Check policy state (test mode, rollout percentage, circuit breaker)
-    #[derive(sqlx::FromRow)]
-    struct PolicyState {
-        id: uuid::Uuid,
-        is_test_mode: bool,
-        rollout_percentage: i32,
-        circuit_breaker_enabled: bool,
-        circuit_breaker_state: Option<String>,
-        circuit_breaker_error_threshold: Option<f64>,
+    if format != "cyclonedx" {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Only 'cyclonedx' format is currently supported"
+        }));
     }
-    
-    let policy_state: Option<PolicyState> = sqlx::query_as(
-        "SELECT id, COALESCE(is_test_mode, false) as is_test_mode, 
-                COALESCE(rollout_percentage, 100) as rollout_percentage,
-                COALESCE(circuit_breaker_enabled, false) as circuit_breaker_enabled,
-                circuit_breaker_state,
-                circuit_breaker_error_threshold
-         FROM policy_versions 
-         WHERE policy_type = 'SOVEREIGN_LOCK' AND is_active = true 
-         LIMIT 1"
+
+    // Fetch system inventory from database
+    let inventory_result: Result<Option<serde_json::Value>, _> = sqlx::query_scalar(
+        "SELECT row_to_json(ai_system_inventory.*) FROM ai_system_inventory WHERE system_id = $1"
     )
+    .bind(&system_id)
     .fetch_optional(&data.db_pool)
-    .await
-    .ok()
-    .flatten();
-    
-    let is_test_mode = policy_state.as_ref().map(|p| p.is_test_mode).unwrap_or(false);
-    let rollout_percentage = policy_state.as_ref().map(|p| p.rollout_percentage).unwrap_or(100);
-    let circuit_breaker_enabled = policy_state.as_ref().map(|p| p.circuit_breaker_enabled).unwrap_or(false);
-    let circuit_breaker_state = policy_state.as_ref()
-        .and_then(|p| p.circuit_breaker_state.as_ref())
-        .map(|s| s.as_str())
-        .unwrap_or("CLOSED");
-    let policy_version_id = policy_state.as_ref().map(|p| p.id);
-    
-    // Check circuit breaker state - if OPEN, skip policy enforcement
-    if circuit_breaker_enabled && circuit_breaker_state == "OPEN" {
-        log::warn!("CIRCUIT BREAKER OPEN: Skipping policy enforcement for {}", proxy_req.target_url);
-        // Track this as a bypass (not an error, but a circuit breaker bypass)
-        if let Some(pv_id) = policy_version_id {
-            let _ = sqlx::query(
-                "INSERT INTO policy_error_tracking (
-                    policy_version_id, policy_type, error_type, 
-                    error_count, total_requests, error_rate, 
-                    window_start, window_end
-                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
-                ON CONFLICT DO NOTHING"
+    .await;
+
+    let inventory = match inventory_result {
+        Ok(Some(inv)) => inv,
+        Ok(None) => {
+            // If no inventory record, create a basic one from monitoring events
+            let monitoring_result: Result<Option<MonitoringEventDb>, _> = sqlx::query_as(
+                "SELECT * FROM monitoring_events WHERE system_id = $1 ORDER BY detected_at DESC LIMIT 1"
             )
-            .bind(pv_id)
-            .bind("SOVEREIGN_LOCK")
-            .bind("CIRCUIT_BYPASS")
-            .bind(0) // Not an error, just bypass
-            .bind(1) // Total request
-            .bind(0.0) // Error rate
-            .bind(chrono::Utc::now())
-            .bind(chrono::Utc::now())
-            .execute(&data.db_pool)
+            .bind(&system_id)
+            .fetch_optional(&data.db_pool)
             .await;
+
+            if let Ok(Some(event)) = monitoring_result {
+                serde_json::json!({
+                    "system_id": system_id,
+                    "system_name": format!("AI System {}", system_id),
+                    "system_version": event.system_version,
+                    "system_type": "MODEL",
+                    "description": event.description,
+                })
+            } else {
+                return HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "System not found"
+                }));
+            }
         }
-        // Continue without policy enforcement
+        Err(e) => {
+            let request_id = generate_request_id();
+            let request_id = generate_request_id();
+            log_error_safely("fetching system inventory", &e, &request_id);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch system inventory"
+            }));
+        }
+    };
+
+    // Fetch related DPIA if available
+    let dpia_id: Option<String> = inventory.get("dpia_id")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string());
+
+    let mut components = Vec::new();
+
+    // Main component
+    let mut properties = Vec::new();
+    if let Some(risk_level) = inventory.get("risk_level").and_then(|v| v.as_str()) {
+        properties.push(crate::compliance_models::CycloneDxProperty {
+            name: "ai:risk_level".to_string(),
+            value: risk_level.to_string(),
+        });
     }
-    
-    // Check if this request should be subject to policy (gradual rollout)
-    let should_apply_policy = if rollout_percentage < 100 {
-        // Use agent_id as deterministic seed for rollout
-        let agent_id = req.headers()
-            .get("X-Agent-ID")
-            .and_then(|h| h.to_str().ok())
-            .unwrap_or("unknown");
-        // Simple hash-based rollout (consistent per agent)
-        let hash = agent_id.chars().map(|c| c as u32).sum::<u32>();
-        (hash % 100) < (rollout_percentage as u32)
-    } else {
-        true // 100% rollout - apply to all
+    if let Some(compliance_status) = inventory.get("compliance_status").and_then(|v| v.as_str()) {
+        properties.push(crate::compliance_models::CycloneDxProperty {
+            name: "ai:compliance_status".to_string(),
+            value: compliance_status.to_string(),
+        });
+    }
+    if let Some(dpia) = dpia_id {
+        properties.push(crate::compliance_models::CycloneDxProperty {
+            name: "ai:dpia_id".to_string(),
+            value: dpia,
+        });
+    }
+
+    components.push(crate::compliance_models::CycloneDxComponent {
+        component_type: "application".to_string(),
+        name: inventory.get("system_name")
+            .and_then(|v| v.as_str())
+            .unwrap_or(&system_id)
+            .to_string(),
+        version: inventory.get("system_version")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string()),
+        description: inventory.get("description")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string()),
+        purl: inventory.get("source_url")
+            .and_then(|v| v.as_str())
+            .map(|s| format!("pkg:generic/{}@{}", system_id, s)),
+        properties: Some(properties),
+        bom_ref: Some(system_id.clone()),
+    });
+
+    // Add dependencies if available
+    if let Some(deps) = inventory.get("dependencies").and_then(|v| v.as_array()) {
+        for dep in deps {
+            if let Some(dep_id) = dep.as_str() {
+                components.push(crate::compliance_models::CycloneDxComponent {
+                    component_type: "library".to_string(),
+                    name: dep_id.to_string(),
+                    version: None,
+                    description: Some(format!("Dependency of {}", system_id)),
+                    purl: None,
+                    properties: None,
+                    bom_ref: Some(dep_id.to_string()),
+                });
+            }
+        }
+    }
+
+    let bom = crate::compliance_models::CycloneDxBom {
+        bom_format: "CycloneDX".to_string(),
+        spec_version: "1.5".to_string(),
+        version: 1,
+        metadata: crate::compliance_models::CycloneDxMetadata {
+            timestamp: Utc::now().to_rfc3339(),
+            tools: Some(vec![crate::compliance_models::CycloneDxTool {
+                name: "Veridion Nexus".to_string(),
+                version: "1.0.0".to_string(),
+                vendor: Some("Veridion".to_string()),
+            }]),
+            properties: Some(vec![
+                crate::compliance_models::CycloneDxProperty {
+                    name: "ai:spec_version".to_string(),
+                    value: "1.5".to_string(),
+                },
+            ]),
+        },
+        components,
     };
 
-    // 1. Check data sovereignty BEFORE forwarding
-    let detected_country = match proxy_service.check_sovereignty(&proxy_req.target_url).await {
-        Ok((is_eu, country)) => {
-            if !is_eu {
-                // Skip policy enforcement if not in rollout percentage
-                if !should_apply_policy {
-                    log::debug!("ROLLOUT: Skipping policy for {} (rollout: {}%)", proxy_req.target_url, rollout_percentage);
-                    // Continue to forward - not in rollout yet, return country and proceed
-                } else if is_test_mode {
-                    log::warn!("TEST MODE: Would block {} -> {} (Country: {})", proxy_req.target_url, country, country);
-                    // Log test mode action but continue
-                    let agent_id = req.headers()
-                        .get("X-Agent-ID")
-                        .and_then(|h| h.to_str().ok())
-                        .unwrap_or("unknown")
-                        .to_string();
-                    
-                    let record_id = uuid::Uuid::new_v4();
-                    let seal_id = uuid::Uuid::new_v4().to_string();
-                    let tx_id = uuid::Uuid::new_v4().to_string();
-                    let action_summary = format!("TEST_MODE: Would block {} ({})", proxy_req.target_url, country);
-                    let _ = sqlx::query(
-                        "INSERT INTO compliance_records (
-                            id, seal_id, tx_id, agent_id, action_summary, status, 
-                            risk_level, timestamp, payload_hash
-                        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"
-                    )
-                    .bind(record_id)
-                    .bind(&seal_id)
-                    .bind(&tx_id)
-                    .bind(&agent_id)
-                    .bind(&action_summary)
-                    .bind("TEST_MODE (WOULD_BLOCK)")
-                    .bind("HIGH")
-                    .bind(chrono::Utc::now())
-                    .bind(&format!("region:{}", country))
-                    .execute(&data.db_pool)
-                    .await;
-                    // Continue to forward in test mode - return country and proceed
-                    // Note: This is inside a match arm, so we just continue to the end
-                } else {
-                // Log the violation attempt
-                let agent_id = req.headers()
-                    .get("X-Agent-ID")
-                    .and_then(|h| h.to_str().ok())
-                    .unwrap_or("unknown")
-                    .to_string();
-                
-                let user_id = crate::security::AuthService::new()
-                    .ok()
-                    .and_then(|auth_service| extract_claims(&req, &auth_service).ok())
-                    .map(|c| c.sub);
+    HttpResponse::Ok().json(bom)
+}
 
-                // Log compliance action
-                let record_id = uuid::Uuid::new_v4();
-                let seal_id = uuid::Uuid::new_v4().to_string();
-                let tx_id = uuid::Uuid::new_v4().to_string();
-                let action_summary = format!("PROXY_BLOCKED: Attempted connection to {} ({})", proxy_req.target_url, country);
-                if let Err(e) = sqlx::query(
-                    "INSERT INTO compliance_records (
-                        id, seal_id, tx_id, agent_id, action_summary, status, 
-                        risk_level, user_id, timestamp, payload_hash
-                    ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)"
-                )
-                .bind(record_id)
-                .bind(&seal_id)
-                .bind(&tx_id)
-                .bind(&agent_id)
-                .bind(&action_summary)
-                .bind("BLOCKED (SOVEREIGNTY)")
-                .bind("HIGH")
-                .bind(user_id.as_deref())
-                .bind(chrono::Utc::now())
-                .bind(&format!("region:{}", country))
-                .execute(&data.db_pool)
-                .await {
-                    log::error!("Failed to log proxy block: {}", e);
-                }
-                
-                // Track error for circuit breaker (if enabled)
-                if let Some(pv_id) = policy_version_id {
-                    let now = chrono::Utc::now();
-                    let window_start = now - chrono::Duration::minutes(5);
-                    
-                    // Update error tracking
-                    let _ = sqlx::query(
-                        "INSERT INTO policy_error_tracking (
-                            policy_version_id, policy_type, error_type,
-                            error_count, total_requests, error_rate,
-                            window_start, window_end
-                        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
-                        ON CONFLICT DO UPDATE SET
-                            error_count = policy_error_tracking.error_count + 1,
-                            total_requests = policy_error_tracking.total_requests + 1,
-                            error_rate = ((policy_error_tracking.error_count + 1)::DECIMAL / 
-                                         (policy_error_tracking.total_requests + 1)::DECIMAL) * 100.0,
-                            updated_at = CURRENT_TIMESTAMP"
-                    )
-                    .bind(pv_id)
-                    .bind("SOVEREIGN_LOCK")
-                    .bind("BLOCKED")
-                    .bind(1)
-                    .bind(1)
-                    .bind(100.0) // 100% error rate for blocked requests
-                    .bind(window_start)
-                    .bind(now)
-                    .execute(&data.db_pool)
-                    .await;
-                    
-                    // Check if circuit breaker should open
-                    let should_open: Option<bool> = sqlx::query_scalar(
-                        "SELECT should_open_circuit_breaker($1)"
-                    )
-                    .bind(pv_id)
-                    .fetch_optional(&data.db_pool)
-                    .await
-                    .ok()
-                    .flatten();
-                    
-                    if should_open.unwrap_or(false) {
-                        // Open circuit breaker
-                        let _ = sqlx::query(
-                            "UPDATE policy_versions 
-                             SET circuit_breaker_state = 'OPEN',
-                                 circuit_breaker_opened_at = CURRENT_TIMESTAMP,
-                                 circuit_breaker_last_error_at = CURRENT_TIMESTAMP
-                             WHERE id = $1"
-                        )
-                        .bind(pv_id)
-                        .execute(&data.db_pool)
-                        .await;
-                        
-                        // Log circuit breaker history
-                        let _ = sqlx::query(
-                            "INSERT INTO circuit_breaker_history (
-                                policy_version_id, state_transition, error_rate,
-                                error_count, total_requests, triggered_by, notes
-                            ) VALUES ($1, $2, $3, $4, $5, $6, $7)"
-                        )
-                        .bind(pv_id)
+// 33. REGISTER AI SYSTEM INVENTORY
+#[utoipa::path(
+    post,
+    path = "/ai_bom/inventory",
+    request_body = AiSystemInventoryRequest,
+    responses((status = 200, body = serde_json::Value))
+)]
+pub async fn register_ai_system(
+    req: web::Json<AiSystemInventoryRequest>,
+    data: web::Data<AppState>,
+) -> impl Responder {
+    let inv_req = req.into_inner();
+    
+    let result = sqlx::query(
+        "INSERT INTO ai_system_inventory (
+            system_id, system_name, system_version, system_type,
+            description, vendor, license, source_url, checksum_sha256,
+            dependencies, training_data_info, risk_level, dpia_id,
+            compliance_status
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, 'REVIEW_REQUIRED')
+        ON CONFLICT (system_id) DO UPDATE SET
+            system_name = EXCLUDED.system_name,
+            system_version = EXCLUDED.system_version,
+            description = EXCLUDED.description,
+            vendor = EXCLUDED.vendor,
+            license = EXCLUDED.license,
+            source_url = EXCLUDED.source_url,
+            checksum_sha256 = EXCLUDED.checksum_sha256,
+            dependencies = EXCLUDED.dependencies,
+            training_data_info = EXCLUDED.training_data_info,
+            risk_level = EXCLUDED.risk_level,
+            dpia_id = EXCLUDED.dpia_id,
+            updated_at = CURRENT_TIMESTAMP
+        RETURNING system_id"
+    )
+    .bind(&inv_req.system_id)
+    .bind(&inv_req.system_name)
+    .bind(&inv_req.system_version)
+    .bind(&inv_req.system_type)
+    .bind(&inv_req.description)
+    .bind(&inv_req.vendor)
+    .bind(&inv_req.license)
+    .bind(&inv_req.source_url)
+    .bind(&inv_req.checksum_sha256)
+    .bind(inv_req.dependencies.map(|d| serde_json::to_value(d).unwrap_or(serde_json::json!([]))))
+    .bind(&inv_req.training_data_info)
+    .bind(&inv_req.risk_level)
+    .bind(&inv_req.dpia_id)
+    .fetch_one(&data.db_pool)
+    .await;
+
+    match result {
+        Ok(_) => {
+            println!("📦 AI system registered: {}", inv_req.system_id);
+            HttpResponse::Ok().json(serde_json::json!({
+                "status": "SUCCESS",
+                "system_id": inv_req.system_id
+            }))
+        }
+        Err(e) => {
+            let request_id = generate_request_id();
+            let request_id = generate_request_id();
+            log_error_safely("registering AI system", &e, &request_id);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to register AI system"
+            }))
+        }
+    }
+}
+
+// ========== WEBHOOK SUPPORT ==========
+
+/// Helper function to trigger webhook events asynchronously
+async fn trigger_webhook_event(
+    db_pool: &sqlx::PgPool,
+    event_type: &str,
+    event_data: serde_json::Value,
+) {
+    // Find active webhooks subscribed to this event type
+    let webhooks_result: Result<Vec<WebhookEndpointDb>, _> = sqlx::query_as(
+        "SELECT * FROM webhook_endpoints 
+         WHERE active = true 
+         AND $1 = ANY(event_types)"
+    )
+    .bind(event_type)
+    .fetch_all(db_pool)
+    .await;
+
+    let webhooks = match webhooks_result {
+        Ok(wh) => wh,
+        Err(e) => {
+            let request_id = generate_request_id();
+            let request_id = generate_request_id();
+            log_error_safely("fetching webhooks", &e, &request_id);
+            return;
+        }
+    };
+
+    if webhooks.is_empty() {
+        return;
+    }
+
+    let timestamp = Utc::now().to_rfc3339();
+
+    // Create webhook event
+    let webhook_event = WebhookEvent {
+        event_type: event_type.to_string(),
+        timestamp: timestamp.clone(),
+        data: event_data,
+        signature: None, // Will be added by webhook service
+    };
+
+    // Deliver to each webhook endpoint
+    for webhook in webhooks {
+        let endpoint_url = webhook.endpoint_url.clone();
+        let secret_key = webhook.secret_key.clone();
+        let timeout = webhook.timeout_seconds as u64;
+        let max_retries = webhook.retry_count;
+        let webhook_id = webhook.id;
+        let event_type_clone = event_type.to_string();
+
+        // Clone event for each webhook
+        let event_clone = webhook_event.clone();
+
+        // Spawn async task for webhook delivery
+        let db_pool_clone = db_pool.clone();
+        tokio::spawn(async move {
+            let webhook_service = WebhookService::new();
+            let (success, attempts, response) = webhook_service
+                .deliver_with_retry(
+                    &endpoint_url,
+                    &event_clone,
+                    &secret_key,
+                    timeout,
+                    max_retries,
+                )
+                .await;
+
+            // Log delivery result
+            let status = if success { "delivered" } else { "failed" };
+            let response_code = if success {
+                response.as_ref()
+                    .and_then(|r| r.split(':').next())
+                    .and_then(|s| s.split_whitespace().nth(1))
+                    .and_then(|s| s.parse::<i32>().ok())
+            } else {
+                None
+            };
+
+            let _ = sqlx::query(
+                "INSERT INTO webhook_deliveries (
+                    webhook_endpoint_id, event_type, event_payload,
+                    status, response_code, response_body, attempts, delivered_at
+                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)"
+            )
+            .bind(webhook_id)
+            .bind(&event_type_clone)
+            .bind(serde_json::to_value(&event_clone).unwrap_or(serde_json::json!({})))
+            .bind(status)
+            .bind(response_code)
+            .bind(&response)
+            .bind(attempts)
+            .bind(if success { Some(Utc::now()) } else { None })
+            .execute(&db_pool_clone)
+            .await;
+        });
+    }
+}
+
+// 34. REGISTER WEBHOOK ENDPOINT
+#[utoipa::path(
+    post,
+    path = "/webhooks",
+    request_body = WebhookEndpointRequest,
+    responses((status = 200, body = WebhookEndpointResponse))
+)]
+pub async fn register_webhook(
+    req: web::Json<WebhookEndpointRequest>,
+    http_req: HttpRequest,
+    data: web::Data<AppState>,
+) -> impl Responder {
+    // AUTHENTICATION & AUTHORIZATION
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "webhook", "write").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
+
+    let webhook_req = req.into_inner();
+
+    // Generate secret key if not provided
+    let secret_key = webhook_req.secret_key.unwrap_or_else(|| {
+        use rand::Rng;
+        let mut rng = rand::thread_rng();
+        let bytes: Vec<u8> = (0..32).map(|_| rng.gen()).collect();
+        use base64::Engine;
+        base64::engine::general_purpose::STANDARD.encode(&bytes)
+    });
+
+    let webhook_id = Uuid::new_v4();
+    let retry_count = webhook_req.retry_count.unwrap_or(3);
+    let timeout_seconds = webhook_req.timeout_seconds.unwrap_or(30);
+
+    let result = sqlx::query_as::<_, WebhookEndpointDb>(
+        "INSERT INTO webhook_endpoints (
+            id, endpoint_url, secret_key, event_types,
+            active, retry_count, timeout_seconds
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING *"
+    )
+    .bind(webhook_id)
+    .bind(&webhook_req.endpoint_url)
+    .bind(&secret_key)
+    .bind(&webhook_req.event_types)
+    .bind(true)
+    .bind(retry_count)
+    .bind(timeout_seconds)
+    .fetch_one(&data.db_pool)
+    .await;
+
+    match result {
+        Ok(webhook) => {
+            println!("🔔 Webhook registered: {}", webhook.endpoint_url);
+            HttpResponse::Ok().json(WebhookEndpointResponse {
+                id: webhook.id.to_string(),
+                endpoint_url: webhook.endpoint_url,
+                event_types: webhook.event_types,
+                active: webhook.active,
+                retry_count: webhook.retry_count,
+                timeout_seconds: webhook.timeout_seconds,
+                created_at: webhook.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            })
+        }
+        Err(e) => {
+            let request_id = generate_request_id();
+            log_error_safely("registering webhook", &e, &request_id);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to register webhook"
+            }))
+        }
+    }
+}
+
+// 35. LIST WEBHOOK ENDPOINTS (with pagination)
+#[utoipa::path(
+    get,
+    path = "/webhooks",
+    params(
+        ("page" = Option<i64>, Query, description = "Page number (default: 1)"),
+        ("limit" = Option<i64>, Query, description = "Items per page (default: 100, max: 1000)")
+    ),
+    responses((status = 200, body = WebhookEndpointsResponse))
+)]
+pub async fn list_webhooks(
+    query: web::Query<std::collections::HashMap<String, String>>,
+    http_req: HttpRequest,
+    data: web::Data<AppState>,
+) -> impl Responder {
+    // AUTHENTICATION & AUTHORIZATION
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "webhook", "read").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
+    let page = query
+        .get("page")
+        .and_then(|s| s.parse::<i64>().ok())
+        .unwrap_or(1)
+        .max(1);
+    let limit = (query
+        .get("limit")
+        .and_then(|s| s.parse::<i64>().ok())
+        .unwrap_or(100))
+        .min(1000)
+        .max(1);
+    let offset = (page - 1) * limit;
+
+    // Get total count
+    let total_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM webhook_endpoints")
+        .fetch_one(&data.db_pool)
+        .await
+        .unwrap_or(0);
+
+    match sqlx::query_as::<_, WebhookEndpointDb>(
+        "SELECT * FROM webhook_endpoints ORDER BY created_at DESC LIMIT $1 OFFSET $2"
+    )
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&data.db_pool)
+    .await
+    {
+        Ok(webhooks) => {
+            let endpoints: Vec<WebhookEndpointResponse> = webhooks.into_iter().map(|w| {
+                WebhookEndpointResponse {
+                    id: w.id.to_string(),
+                    endpoint_url: w.endpoint_url,
+                    event_types: w.event_types,
+                    active: w.active,
+                    retry_count: w.retry_count,
+                    timeout_seconds: w.timeout_seconds,
+                    created_at: w.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                }
+            }).collect();
+
+            HttpResponse::Ok().json(WebhookEndpointsResponse {
+                endpoints,
+                total_count,
+            })
+        }
+        Err(e) => {
+            let request_id = generate_request_id();
+            log_error_safely("fetching webhooks", &e, &request_id);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch webhooks"
+            }))
+        }
+    }
+}
+
+// 36. UPDATE WEBHOOK ENDPOINT
+#[utoipa::path(
+    put,
+    path = "/webhooks/{id}",
+    request_body = UpdateWebhookEndpointRequest,
+    responses((status = 200, body = WebhookEndpointResponse))
+)]
+pub async fn update_webhook(
+    path: web::Path<String>,
+    req: web::Json<UpdateWebhookEndpointRequest>,
+    http_req: HttpRequest,
+    data: web::Data<AppState>,
+) -> impl Responder {
+    // AUTHENTICATION & AUTHORIZATION
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "webhook", "write").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
+
+    let webhook_id = path.into_inner();
+    let update_req = req.into_inner();
+
+    // Parse UUID
+    let id = match Uuid::parse_str(&webhook_id) {
+        Ok(uuid) => uuid,
+        Err(_) => {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "Invalid webhook ID"
+            }));
+        }
+    };
+
+    let result = sqlx::query_as::<_, WebhookEndpointDb>(
+        "UPDATE webhook_endpoints SET
+            endpoint_url = COALESCE($1, endpoint_url),
+            event_types = COALESCE($2, event_types),
+            active = COALESCE($3, active),
+            retry_count = COALESCE($4, retry_count),
+            timeout_seconds = COALESCE($5, timeout_seconds),
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $6
+        RETURNING *"
+    )
+    .bind(update_req.endpoint_url)
+    .bind(update_req.event_types)
+    .bind(update_req.active)
+    .bind(update_req.retry_count)
+    .bind(update_req.timeout_seconds)
+    .bind(id)
+    .fetch_optional(&data.db_pool)
+    .await;
+
+    match result {
+        Ok(Some(webhook)) => {
+            HttpResponse::Ok().json(WebhookEndpointResponse {
+                id: webhook.id.to_string(),
+                endpoint_url: webhook.endpoint_url,
+                event_types: webhook.event_types,
+                active: webhook.active,
+                retry_count: webhook.retry_count,
+                timeout_seconds: webhook.timeout_seconds,
+                created_at: webhook.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            })
+        }
+        Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Webhook not found"
+        })),
+        Err(e) => {
+            let request_id = generate_request_id();
+            let request_id = generate_request_id();
+            log_error_safely("updating webhook", &e, &request_id);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to update webhook"
+            }))
+        }
+    }
+}
+
+// 37. DELETE WEBHOOK ENDPOINT
+#[utoipa::path(
+    delete,
+    path = "/webhooks/{id}",
+    responses((status = 200, body = serde_json::Value))
+)]
+pub async fn delete_webhook(
+    path: web::Path<String>,
+    http_req: HttpRequest,
+    data: web::Data<AppState>,
+) -> impl Responder {
+    // AUTHENTICATION & AUTHORIZATION
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "webhook", "delete").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
+
+    let webhook_id = path.into_inner();
+    let id = match Uuid::parse_str(&webhook_id) {
+        Ok(uuid) => uuid,
+        Err(_) => {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "Invalid webhook ID"
+            }));
+        }
+    };
+
+    let result = sqlx::query("DELETE FROM webhook_endpoints WHERE id = $1")
+        .bind(id)
+        .execute(&data.db_pool)
+        .await;
+
+    match result {
+        Ok(rows) if rows.rows_affected() > 0 => {
+            println!("🔔 Webhook deleted: {}", webhook_id);
+            HttpResponse::Ok().json(serde_json::json!({
+                "status": "SUCCESS",
+                "message": "Webhook deleted"
+            }))
+        }
+        _ => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Webhook not found"
+        }))
+    }
+}
+
+// 38. GET WEBHOOK DELIVERIES
+#[utoipa::path(
+    get,
+    path = "/webhooks/{id}/deliveries",
+    responses((status = 200, body = WebhookDeliveriesResponse))
+)]
+pub async fn get_webhook_deliveries(
+    path: web::Path<String>,
+    http_req: HttpRequest,
+    data: web::Data<AppState>,
+) -> impl Responder {
+    // AUTHENTICATION & AUTHORIZATION
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "webhook", "read").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
+
+    let webhook_id = path.into_inner();
+    let id = match Uuid::parse_str(&webhook_id) {
+        Ok(uuid) => uuid,
+        Err(_) => {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "Invalid webhook ID"
+            }));
+        }
+    };
+
+    match sqlx::query_as::<_, WebhookDeliveryDb>(
+        "SELECT * FROM webhook_deliveries 
+         WHERE webhook_endpoint_id = $1 
+         ORDER BY created_at DESC 
+         LIMIT 100"
+    )
+    .bind(id)
+    .fetch_all(&data.db_pool)
+    .await
+    {
+        Ok(deliveries) => {
+            let delivery_responses: Vec<WebhookDeliveryResponse> = deliveries.into_iter().map(|d| {
+                WebhookDeliveryResponse {
+                    id: d.id.to_string(),
+                    event_type: d.event_type,
+                    status: d.status,
+                    response_code: d.response_code,
+                    attempts: d.attempts,
+                    created_at: d.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    delivered_at: d.delivered_at.map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
+                }
+            }).collect();
+
+            let total_count = delivery_responses.len() as i64;
+            HttpResponse::Ok().json(WebhookDeliveriesResponse {
+                deliveries: delivery_responses,
+                total_count,
+            })
+        }
+        Err(e) => {
+            let request_id = generate_request_id();
+            let request_id = generate_request_id();
+            log_error_safely("fetching webhook deliveries", &e, &request_id);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch webhook deliveries"
+            }))
+        }
+    }
+}
+
+// ============================================================================
+// Priority 2: User Notification Preferences (EU AI Act Article 13)
+// ============================================================================
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct NotificationPreferenceRequest {
+    #[schema(example = "HIGH_RISK_AI_ACTION")]
+    pub notification_type: String,
+    #[schema(example = r#"["EMAIL", "SMS", "IN_APP"]"#)]
+    pub preferred_channels: Vec<String>,
+    #[schema(example = "en")]
+    pub language: String,
+    #[schema(example = true)]
+    pub enabled: bool,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct NotificationPreferenceResponse {
+    pub notification_type: String,
+    pub preferred_channels: Vec<String>,
+    pub language: String,
+    pub enabled: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Set user notification preferences (EU AI Act Article 13)
+#[utoipa::path(
+    post,
+    path = "/user/{user_id}/notification_preferences",
+    request_body = NotificationPreferenceRequest,
+    responses(
+        (status = 200, description = "Preferences updated successfully", body = NotificationPreferenceResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden")
+    ),
+    tag = "Notifications"
+)]
+pub async fn set_notification_preferences(
+    user_id: web::Path<String>,
+    req: web::Json<NotificationPreferenceRequest>,
+    data: web::Data<AppState>,
+    http_req: HttpRequest,
+) -> impl Responder {
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "notifications", "write").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
+
+    let user_id_str = user_id.into_inner();
+    let pref = req.into_inner();
+    let channels_json = serde_json::to_value(&pref.preferred_channels).unwrap_or(serde_json::json!([]));
+
+    let result = sqlx::query(
+        "INSERT INTO user_notification_preferences (
+            user_id, notification_type, preferred_channels, language, enabled
+        ) VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (user_id, notification_type) DO UPDATE SET
+            preferred_channels = EXCLUDED.preferred_channels,
+            language = EXCLUDED.language,
+            enabled = EXCLUDED.enabled,
+            updated_at = CURRENT_TIMESTAMP
+        RETURNING created_at, updated_at"
+    )
+    .bind(&user_id_str)
+    .bind(&pref.notification_type)
+    .bind(channels_json)
+    .bind(&pref.language)
+    .bind(pref.enabled)
+    .fetch_one(&data.db_pool)
+    .await;
+
+    match result {
+        Ok(row) => {
+            let created_at: chrono::DateTime<chrono::Utc> = row.try_get("created_at").unwrap_or_else(|_| chrono::Utc::now());
+            let updated_at: chrono::DateTime<chrono::Utc> = row.try_get("updated_at").unwrap_or_else(|_| chrono::Utc::now());
+            HttpResponse::Ok().json(NotificationPreferenceResponse {
+                notification_type: pref.notification_type,
+                preferred_channels: pref.preferred_channels,
+                language: pref.language,
+                enabled: pref.enabled,
+                created_at: created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                updated_at: updated_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            })
+        }
+        Err(e) => {
+            let request_id = generate_request_id();
+            log_error_safely("setting notification preferences", &e, &request_id);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to set notification preferences"
+            }))
+        }
+    }
+}
+
+/// Get user notification preferences
+#[utoipa::path(
+    get,
+    path = "/user/{user_id}/notification_preferences",
+    responses(
+        (status = 200, description = "Preferences retrieved successfully", body = Vec<NotificationPreferenceResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden")
+    ),
+    tag = "Notifications"
+)]
+pub async fn get_notification_preferences(
+    user_id: web::Path<String>,
+    data: web::Data<AppState>,
+    http_req: HttpRequest,
+) -> impl Responder {
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "notifications", "read").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
+
+    let user_id_str = user_id.into_inner();
+
+    #[derive(sqlx::FromRow)]
+    struct PreferenceRow {
+        notification_type: String,
+        preferred_channels: serde_json::Value,
+        language: String,
+        enabled: bool,
+        created_at: chrono::DateTime<chrono::Utc>,
+        updated_at: chrono::DateTime<chrono::Utc>,
+    }
+
+    match sqlx::query_as::<_, PreferenceRow>(
+        "SELECT notification_type, preferred_channels, language, enabled, created_at, updated_at
+         FROM user_notification_preferences
+         WHERE user_id = $1
+         ORDER BY notification_type"
+    )
+    .bind(&user_id_str)
+    .fetch_all(&data.db_pool)
+    .await
+    {
+        Ok(prefs) => {
+            let responses: Vec<NotificationPreferenceResponse> = prefs.into_iter().map(|p| {
+                let channels: Vec<String> = serde_json::from_value(p.preferred_channels)
+                    .unwrap_or_else(|_| vec![]);
+                NotificationPreferenceResponse {
+                    notification_type: p.notification_type,
+                    preferred_channels: channels,
+                    language: p.language,
+                    enabled: p.enabled,
+                    created_at: p.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    updated_at: p.updated_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                }
+            }).collect();
+            HttpResponse::Ok().json(responses)
+        }
+        Err(e) => {
+            let request_id = generate_request_id();
+            let request_id = generate_request_id();
+            log_error_safely("fetching notification preferences", &e, &request_id);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch notification preferences"
+            }))
+        }
+    }
+}
+
+/// Get notification history for a user
+#[utoipa::path(
+    get,
+    path = "/user/{user_id}/notifications",
+    params(
+        ("user_id" = String, Path, description = "User ID"),
+        ("limit" = Option<i32>, Query, description = "Maximum number of notifications to return (default: 50)")
+    ),
+    responses(
+        (status = 200, description = "Notifications retrieved successfully"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden")
+    ),
+    tag = "Notifications"
+)]
+pub async fn get_user_notifications(
+    user_id: web::Path<String>,
+    query: web::Query<std::collections::HashMap<String, String>>,
+    data: web::Data<AppState>,
+    http_req: HttpRequest,
+) -> impl Responder {
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "notifications", "read").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
+
+    let user_id_str = user_id.into_inner();
+    let limit = query.get("limit")
+        .and_then(|l| l.parse::<i32>().ok())
+        .unwrap_or(50)
+        .min(100); // Cap at 100
+
+    #[derive(sqlx::FromRow)]
+    struct NotificationRow {
+        notification_id: String,
+        notification_type: String,
+        channel: String,
+        subject: Option<String>,
+        body: String,
+        status: String,
+        sent_at: Option<chrono::DateTime<chrono::Utc>>,
+        created_at: chrono::DateTime<chrono::Utc>,
+    }
+
+    match sqlx::query_as::<_, NotificationRow>(
+        "SELECT notification_id, notification_type, channel, subject, body, status, sent_at, created_at
+         FROM user_notifications
+         WHERE user_id = $1
+         ORDER BY created_at DESC
+         LIMIT $2"
+    )
+    .bind(&user_id_str)
+    .bind(limit)
+    .fetch_all(&data.db_pool)
+    .await
+    {
+        Ok(notifications) => {
+            let responses: Vec<serde_json::Value> = notifications.into_iter().map(|n| {
+                serde_json::json!({
+                    "notification_id": n.notification_id,
+                    "notification_type": n.notification_type,
+                    "channel": n.channel,
+                    "subject": n.subject,
+                    "body": n.body,
+                    "status": n.status,
+                    "sent_at": n.sent_at.map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()),
+                    "created_at": n.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                })
+            }).collect();
+            HttpResponse::Ok().json(serde_json::json!({
+                "notifications": responses,
+                "total": responses.len()
+            }))
+        }
+        Err(e) => {
+            let request_id = generate_request_id();
+            log_error_safely("fetching user notifications", &e, &request_id);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch notifications"
+            }))
+        }
+    }
+}
+
+// ============================================================================
+// Priority 3: GDPR Article 30 - Records of Processing Activities
+// ============================================================================
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct ProcessingRecordResponse {
+    pub activity_name: String,
+    pub purpose: String,
+    pub legal_basis: String,
+    pub data_categories: Vec<String>,
+    pub data_subject_categories: Vec<String>,
+    pub recipients: Vec<String>,
+    pub third_country_transfers: bool,
+    pub third_countries: Vec<String>,
+    pub retention_period_days: Option<i32>,
+    pub security_measures: Vec<String>,
+    pub seal_id: Option<String>,
+    pub timestamp: String,
+}
+
+/// Get processing records in GDPR Article 30 format
+#[utoipa::path(
+    get,
+    path = "/processing_records",
+    params(
+        ("format" = Option<String>, Query, description = "Export format: json, csv (default: json)")
+    ),
+    responses(
+        (status = 200, description = "Processing records retrieved successfully"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden")
+    ),
+    tag = "Compliance"
+)]
+pub async fn get_processing_records(
+    query: web::Query<std::collections::HashMap<String, String>>,
+    data: web::Data<AppState>,
+    http_req: HttpRequest,
+) -> impl Responder {
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "compliance", "read").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
+
+    let format_str = query.get("format").map(|s| s.as_str()).unwrap_or("json");
+
+    // Get processing activities from database
+    match sqlx::query_as::<_, ProcessingActivityDb>(
+        "SELECT * FROM processing_activities ORDER BY created_at DESC"
+    )
+    .fetch_all(&data.db_pool)
+    .await
+    {
+        Ok(activities) => {
+            // Also get compliance records to enrich with seal_id and timestamp
+            let mut records: Vec<ProcessingRecordResponse> = Vec::new();
+            
+            for activity in activities {
+                // Try to find related compliance record
+                let seal_id: Option<String> = sqlx::query_scalar(
+                    "SELECT seal_id FROM compliance_records_current 
+                     WHERE action_summary LIKE $1 
+                     ORDER BY timestamp DESC LIMIT 1"
+                )
+                .bind(format!("%{}%", activity.activity_name))
+                .fetch_optional(&data.db_pool)
+                .await
+                .unwrap_or(None);
+                
+                let timestamp: Option<chrono::DateTime<chrono::Utc>> = if let Some(ref sid) = seal_id {
+                    sqlx::query_scalar(
+                        "SELECT timestamp FROM compliance_records WHERE seal_id = $1"
+                    )
+                    .bind(sid)
+                    .fetch_optional(&data.db_pool)
+                    .await
+                    .unwrap_or(None)
+                } else {
+                    Some(activity.created_at)
+                };
+                
+                records.push(ProcessingRecordResponse {
+                    activity_name: activity.activity_name,
+                    purpose: activity.purpose,
+                    legal_basis: activity.legal_basis,
+                    data_categories: activity.data_categories,
+                    data_subject_categories: activity.data_subject_categories,
+                    recipients: activity.recipients,
+                    third_country_transfers: activity.third_country_transfers,
+                    third_countries: activity.third_countries,
+                    retention_period_days: activity.retention_period_days,
+                    security_measures: activity.security_measures,
+                    seal_id,
+                    timestamp: timestamp.map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+                        .unwrap_or_else(|| activity.created_at.format("%Y-%m-%d %H:%M:%S").to_string()),
+                });
+            }
+            
+            // If no processing_activities exist, generate from compliance_records
+            if records.is_empty() {
+                let compliance_records: Vec<ComplianceRecordDb> = sqlx::query_as(
+                    "SELECT * FROM compliance_records_current 
+                     WHERE user_id IS NOT NULL 
+                     ORDER BY timestamp DESC LIMIT 100"
+                )
+                .fetch_all(&data.db_pool)
+                .await
+                .unwrap_or_default();
+                
+                for cr in compliance_records {
+                    records.push(ProcessingRecordResponse {
+                        activity_name: cr.action_summary.clone(),
+                        purpose: "AI system processing".to_string(),
+                        legal_basis: "CONSENT".to_string(), // Default, should be from consent_records
+                        data_categories: vec!["Personal data".to_string()],
+                        data_subject_categories: vec!["Data subjects".to_string()],
+                        recipients: vec![],
+                        third_country_transfers: false,
+                        third_countries: vec![],
+                        retention_period_days: None,
+                        security_measures: vec!["Encryption".to_string(), "Access controls".to_string()],
+                        seal_id: Some(cr.seal_id),
+                        timestamp: cr.timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    });
+                }
+            }
+            
+            if format_str == "csv" {
+                // Generate CSV
+                let mut csv = String::from("Activity Name,Purpose,Legal Basis,Data Categories,Data Subject Categories,Recipients,Third Country Transfers,Third Countries,Retention Period (Days),Security Measures,Seal ID,Timestamp\n");
+                
+                for record in &records {
+                    csv.push_str(&format!(
+                        "\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",{},\"{}\",{},\"{}\",\"{}\",\"{}\"\n",
+                        record.activity_name,
+                        record.purpose,
+                        record.legal_basis,
+                        record.data_categories.join("; "),
+                        record.data_subject_categories.join("; "),
+                        record.recipients.join("; "),
+                        record.third_country_transfers,
+                        record.third_countries.join("; "),
+                        record.retention_period_days.map(|d| d.to_string()).unwrap_or_else(|| "N/A".to_string()),
+                        record.security_measures.join("; "),
+                        record.seal_id.as_deref().unwrap_or("N/A"),
+                        record.timestamp
+                    ));
+                }
+                
+                HttpResponse::Ok()
+                    .content_type("text/csv")
+                    .append_header(actix_web::http::header::ContentDisposition::attachment("processing_records_article30.csv"))
+                    .body(csv)
+            } else {
+                HttpResponse::Ok().json(serde_json::json!({
+                    "records": records,
+                    "total": records.len(),
+                    "format": "GDPR Article 30"
+                }))
+            }
+        }
+        Err(e) => {
+            let request_id = generate_request_id();
+            log_error_safely("fetching processing records", &e, &request_id);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch processing records"
+            }))
+        }
+    }
+}
+
+// ============================================================================
+// Priority 3: EU AI Act Article 8 - Conformity Assessment
+// ============================================================================
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct ConformityAssessmentRequest {
+    pub system_id: String,
+    pub system_name: String,
+    pub assessment_type: String, // 'SELF_ASSESSMENT', 'THIRD_PARTY', 'NOTIFIED_BODY'
+    pub assessment_date: String,
+    pub expiration_date: Option<String>,
+    pub assessment_result: serde_json::Value,
+    pub assessor_name: Option<String>,
+    pub assessor_contact: Option<String>,
+    pub certificate_number: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct ConformityAssessmentResponse {
+    pub assessment_id: String,
+    pub system_id: String,
+    pub system_name: String,
+    pub assessment_type: String,
+    pub assessment_date: String,
+    pub expiration_date: Option<String>,
+    pub status: String,
+    pub days_until_expiration: Option<i64>,
+    pub assessor_name: Option<String>,
+    pub certificate_number: Option<String>,
+}
+
+/// Create or update conformity assessment
+#[utoipa::path(
+    post,
+    path = "/conformity_assessments",
+    request_body = ConformityAssessmentRequest,
+    responses(
+        (status = 200, description = "Assessment created/updated successfully", body = ConformityAssessmentResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden")
+    ),
+    tag = "Compliance"
+)]
+pub async fn create_conformity_assessment(
+    req: web::Json<ConformityAssessmentRequest>,
+    data: web::Data<AppState>,
+    http_req: HttpRequest,
+) -> impl Responder {
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "compliance", "write").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
+
+    let assessment_id = format!("ASSESS-{}", uuid::Uuid::new_v4().to_string().replace("-", "").chars().take(12).collect::<String>());
+    let assessment_req = req.into_inner();
+    
+    let assessment_date = chrono::NaiveDateTime::parse_from_str(&assessment_req.assessment_date, "%Y-%m-%d %H:%M:%S")
+        .map(|dt| DateTime::<Utc>::from_naive_utc_and_offset(dt, Utc))
+        .unwrap_or(Utc::now());
+    
+    let expiration_date = assessment_req.expiration_date.and_then(|d| {
+        chrono::NaiveDateTime::parse_from_str(&d, "%Y-%m-%d %H:%M:%S")
+            .ok()
+            .map(|dt| DateTime::<Utc>::from_naive_utc_and_offset(dt, Utc))
+    });
+    
+    // Determine status based on expiration
+    let status = if let Some(exp) = expiration_date {
+        if exp < Utc::now() {
+            "EXPIRED"
+        } else {
+            "PASSED"
+        }
+    } else {
+        "PASSED"
+    }.to_string();
+    
+    let result = sqlx::query(
+        "INSERT INTO conformity_assessments (
+            assessment_id, system_id, system_name, assessment_type,
+            assessment_date, expiration_date, status, assessment_result,
+            assessor_name, assessor_contact, certificate_number, notes
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+        ON CONFLICT (assessment_id) DO UPDATE SET
+            system_name = EXCLUDED.system_name,
+            assessment_type = EXCLUDED.assessment_type,
+            assessment_date = EXCLUDED.assessment_date,
+            expiration_date = EXCLUDED.expiration_date,
+            status = EXCLUDED.status,
+            assessment_result = EXCLUDED.assessment_result,
+            assessor_name = EXCLUDED.assessor_name,
+            assessor_contact = EXCLUDED.assessor_contact,
+            certificate_number = EXCLUDED.certificate_number,
+            notes = EXCLUDED.notes,
+            updated_at = CURRENT_TIMESTAMP"
+    )
+    .bind(&assessment_id)
+    .bind(&assessment_req.system_id)
+    .bind(&assessment_req.system_name)
+    .bind(&assessment_req.assessment_type)
+    .bind(assessment_date)
+    .bind(expiration_date)
+    .bind(&status)
+    .bind(&assessment_req.assessment_result)
+    .bind(&assessment_req.assessor_name)
+    .bind(&assessment_req.assessor_contact)
+    .bind(&assessment_req.certificate_number)
+    .bind(&assessment_req.notes)
+    .execute(&data.db_pool)
+    .await;
+
+    match result {
+        Ok(_) => {
+            let days_until_expiration = expiration_date.map(|exp| {
+                (exp - Utc::now()).num_days()
+            });
+            
+            // Check if expiration is within 30 days and send notification
+            if let Some(days) = days_until_expiration {
+                if days <= 30 && days > 0 {
+                    // Send notification about expiring assessment
+                    let notification_service = data.notification_service.clone();
+                    let db_pool = data.db_pool.clone();
+                    let system_name_clone = assessment_req.system_name.clone();
+                    let assessment_id_clone = assessment_id.clone();
+                    
+                    tokio::spawn(async move {
+                        // Notify compliance team (using system admin user or default)
+                        let _ = notification_service.send_notification(
+                            &db_pool,
+                            crate::integration::notifications::NotificationRequest {
+                                user_id: "SYSTEM_ADMIN".to_string(),
+                                notification_type: crate::integration::notifications::NotificationType::HighRiskAiAction,
+                                channel: crate::integration::notifications::NotificationChannel::Email,
+                                subject: Some(format!("Conformity Assessment Expiring Soon - {}", system_name_clone)),
+                                body: format!(
+                                    "The conformity assessment for AI system '{}' will expire in {} days.\n\nAssessment ID: {}\n\nPlease schedule a new assessment before expiration.\n\nBest regards,\nVeridion Nexus Compliance Team",
+                                    system_name_clone, days, assessment_id_clone
+                                ),
+                                language: Some("en".to_string()),
+                                related_entity_type: Some("CONFORMITY_ASSESSMENT".to_string()),
+                                related_entity_id: Some(assessment_id_clone),
+                            }
+                        ).await;
+                    });
+                }
+            }
+            
+            HttpResponse::Ok().json(ConformityAssessmentResponse {
+                assessment_id,
+                system_id: assessment_req.system_id,
+                system_name: assessment_req.system_name,
+                assessment_type: assessment_req.assessment_type,
+                assessment_date: assessment_date.format("%Y-%m-%d %H:%M:%S").to_string(),
+                expiration_date: expiration_date.map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string()),
+                status,
+                days_until_expiration,
+                assessor_name: assessment_req.assessor_name,
+                certificate_number: assessment_req.certificate_number,
+            })
+        }
+        Err(e) => {
+            let request_id = generate_request_id();
+            let request_id = generate_request_id();
+            log_error_safely("creating conformity assessment", &e, &request_id);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to create conformity assessment"
+            }))
+        }
+    }
+}
+
+/// Get all conformity assessments
+#[utoipa::path(
+    get,
+    path = "/conformity_assessments",
+    params(
+        ("system_id" = Option<String>, Query, description = "Filter by system_id"),
+        ("status" = Option<String>, Query, description = "Filter by status")
+    ),
+    responses(
+        (status = 200, description = "Assessments retrieved successfully", body = Vec<ConformityAssessmentResponse>),
+        (status = 401, description = "Unauthorized")
+    ),
+    tag = "Compliance"
+)]
+pub async fn get_conformity_assessments(
+    query: web::Query<std::collections::HashMap<String, String>>,
+    data: web::Data<AppState>,
+    http_req: HttpRequest,
+) -> impl Responder {
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "compliance", "read").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
+
+    let system_id_filter = query.get("system_id");
+    let status_filter = query.get("status");
+
+    let mut query_str = "SELECT assessment_id, system_id, system_name, assessment_type, assessment_date, expiration_date, status, assessor_name, certificate_number FROM conformity_assessments WHERE 1=1".to_string();
+    let mut bindings = Vec::new();
+    
+    if let Some(sid) = system_id_filter {
+        query_str.push_str(" AND system_id = $1");
+        bindings.push(sid.clone());
+    }
+    
+    if let Some(st) = status_filter {
+        let param_num = if system_id_filter.is_some() { 2 } else { 1 };
+        query_str.push_str(&format!(" AND status = ${}", param_num));
+        bindings.push(st.clone());
+    }
+    
+    query_str.push_str(" ORDER BY assessment_date DESC");
+
+    #[derive(sqlx::FromRow)]
+    struct AssessmentRow {
+        assessment_id: String,
+        system_id: String,
+        system_name: String,
+        assessment_type: String,
+        assessment_date: chrono::DateTime<chrono::Utc>,
+        expiration_date: Option<chrono::DateTime<chrono::Utc>>,
+        status: String,
+        assessor_name: Option<String>,
+        certificate_number: Option<String>,
+    }
+
+    let result = if system_id_filter.is_some() && status_filter.is_some() {
+        sqlx::query_as::<_, AssessmentRow>(&query_str)
+            .bind(&bindings[0])
+            .bind(&bindings[1])
+            .fetch_all(&data.db_pool)
+            .await
+    } else if system_id_filter.is_some() {
+        sqlx::query_as::<_, AssessmentRow>(&query_str)
+            .bind(&bindings[0])
+            .fetch_all(&data.db_pool)
+            .await
+    } else if status_filter.is_some() {
+        sqlx::query_as::<_, AssessmentRow>(&query_str)
+            .bind(&bindings[0])
+            .fetch_all(&data.db_pool)
+            .await
+    } else {
+        sqlx::query_as::<_, AssessmentRow>(&query_str)
+            .fetch_all(&data.db_pool)
+            .await
+    };
+
+    match result {
+        Ok(assessments) => {
+            let responses: Vec<ConformityAssessmentResponse> = assessments.into_iter().map(|a| {
+                let days_until_expiration = a.expiration_date.map(|exp| {
+                    (exp - Utc::now()).num_days()
+                });
+                
+                ConformityAssessmentResponse {
+                    assessment_id: a.assessment_id,
+                    system_id: a.system_id,
+                    system_name: a.system_name,
+                    assessment_type: a.assessment_type,
+                    assessment_date: a.assessment_date.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    expiration_date: a.expiration_date.map(|d| d.format("%Y-%m-%d %H:%M:%S").to_string()),
+                    status: a.status,
+                    days_until_expiration,
+                    assessor_name: a.assessor_name,
+                    certificate_number: a.certificate_number,
+                }
+            }).collect();
+            HttpResponse::Ok().json(responses)
+        }
+        Err(e) => {
+            let request_id = generate_request_id();
+            let request_id = generate_request_id();
+            log_error_safely("fetching conformity assessments", &e, &request_id);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to fetch conformity assessments"
+            }))
+        }
+    }
+}
+
+// ============================================================================
+// Priority 3: EU AI Act Article 11 - Data Governance Extension
+// ============================================================================
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct DataQualityMetricRequest {
+    pub seal_id: String,
+    pub data_source: Option<String>,
+    pub metric_type: String, // 'COMPLETENESS', 'ACCURACY', 'CONSISTENCY', 'VALIDITY', 'TIMELINESS'
+    pub metric_value: f64,
+    pub threshold: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct DataBiasDetectionRequest {
+    pub seal_id: String,
+    pub bias_type: String, // 'DEMOGRAPHIC', 'GEOGRAPHIC', 'TEMPORAL', 'REPRESENTATION'
+    pub bias_metric: f64,
+    pub affected_groups: serde_json::Value,
+    pub mitigation_applied: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct DataLineageRequest {
+    pub seal_id: String,
+    pub source_seal_id: Option<String>,
+    pub transformation_type: Option<String>,
+    pub transformation_details: Option<serde_json::Value>,
+}
+
+/// Record data quality metric
+#[utoipa::path(
+    post,
+    path = "/data_quality/metrics",
+    request_body = DataQualityMetricRequest,
+    responses(
+        (status = 200, description = "Metric recorded successfully"),
+        (status = 401, description = "Unauthorized")
+    ),
+    tag = "Data Governance"
+)]
+pub async fn record_data_quality_metric(
+    req: web::Json<DataQualityMetricRequest>,
+    data: web::Data<AppState>,
+    http_req: HttpRequest,
+) -> impl Responder {
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "compliance", "write").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
+
+    let result = sqlx::query(
+        "INSERT INTO data_quality_metrics (
+            seal_id, data_source, metric_type, metric_value, threshold
+        ) VALUES ($1, $2, $3, $4, $5)"
+    )
+    .bind(&req.seal_id)
+    .bind(&req.data_source)
+    .bind(&req.metric_type)
+    .bind(req.metric_value as f64)
+    .bind(req.threshold.map(|t| t as f64))
+    .execute(&data.db_pool)
+    .await;
+
+    match result {
+        Ok(_) => {
+            // Check if metric is below threshold
+            if let Some(threshold) = req.threshold {
+                if req.metric_value < threshold {
+                    println!("⚠️ Data quality alert: {} metric {} is below threshold {}", 
+                        req.metric_type, req.metric_value, threshold);
+                }
+            }
+            HttpResponse::Ok().json(serde_json::json!({
+                "status": "SUCCESS",
+                "message": "Data quality metric recorded"
+            }))
+        }
+        Err(e) => {
+            let request_id = generate_request_id();
+            let request_id = generate_request_id();
+            log_error_safely("recording data quality metric", &e, &request_id);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to record metric"
+            }))
+        }
+    }
+}
+
+/// Record data bias detection
+#[utoipa::path(
+    post,
+    path = "/data_quality/bias",
+    request_body = DataBiasDetectionRequest,
+    responses(
+        (status = 200, description = "Bias detection recorded successfully"),
+        (status = 401, description = "Unauthorized")
+    ),
+    tag = "Data Governance"
+)]
+pub async fn record_data_bias(
+    req: web::Json<DataBiasDetectionRequest>,
+    data: web::Data<AppState>,
+    http_req: HttpRequest,
+) -> impl Responder {
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "compliance", "write").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
+
+    let result = sqlx::query(
+        "INSERT INTO data_bias_detections (
+            seal_id, bias_type, bias_metric, affected_groups, mitigation_applied
+        ) VALUES ($1, $2, $3, $4, $5)"
+    )
+    .bind(&req.seal_id)
+    .bind(&req.bias_type)
+    .bind(req.bias_metric as f64)
+    .bind(&req.affected_groups)
+    .bind(req.mitigation_applied.unwrap_or(false))
+    .execute(&data.db_pool)
+    .await;
+
+    match result {
+        Ok(_) => {
+            println!("⚠️ Data bias detected: {} bias (metric: {}) for seal: {}", 
+                req.bias_type, req.bias_metric, req.seal_id);
+            HttpResponse::Ok().json(serde_json::json!({
+                "status": "SUCCESS",
+                "message": "Bias detection recorded"
+            }))
+        }
+        Err(e) => {
+            let request_id = generate_request_id();
+            let request_id = generate_request_id();
+            log_error_safely("recording bias detection", &e, &request_id);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to record bias detection"
+            }))
+        }
+    }
+}
+
+/// Record data lineage
+#[utoipa::path(
+    post,
+    path = "/data_quality/lineage",
+    request_body = DataLineageRequest,
+    responses(
+        (status = 200, description = "Lineage recorded successfully"),
+        (status = 401, description = "Unauthorized")
+    ),
+    tag = "Data Governance"
+)]
+pub async fn record_data_lineage(
+    req: web::Json<DataLineageRequest>,
+    data: web::Data<AppState>,
+    http_req: HttpRequest,
+) -> impl Responder {
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "compliance", "write").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
+
+    // Build lineage path
+    let mut lineage_path = Vec::new();
+    if let Some(ref source) = req.source_seal_id {
+        lineage_path.push(source.clone());
+    }
+    lineage_path.push(req.seal_id.clone());
+
+    let result = sqlx::query(
+        "INSERT INTO data_lineage (
+            seal_id, source_seal_id, transformation_type, transformation_details, lineage_path
+        ) VALUES ($1, $2, $3, $4, $5)"
+    )
+    .bind(&req.seal_id)
+    .bind(&req.source_seal_id)
+    .bind(&req.transformation_type)
+    .bind(&req.transformation_details)
+    .bind(&lineage_path)
+    .execute(&data.db_pool)
+    .await;
+
+    match result {
+        Ok(_) => {
+            HttpResponse::Ok().json(serde_json::json!({
+                "status": "SUCCESS",
+                "message": "Data lineage recorded"
+            }))
+        }
+        Err(e) => {
+            let request_id = generate_request_id();
+            let request_id = generate_request_id();
+            log_error_safely("recording data lineage", &e, &request_id);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to record lineage"
+            }))
+        }
+    }
+}
+
+/// Get data quality report for a seal
+#[utoipa::path(
+    get,
+    path = "/data_quality/report/{seal_id}",
+    responses(
+        (status = 200, description = "Quality report retrieved successfully"),
+        (status = 401, description = "Unauthorized")
+    ),
+    tag = "Data Governance"
+)]
+pub async fn get_data_quality_report(
+    seal_id: web::Path<String>,
+    data: web::Data<AppState>,
+    http_req: HttpRequest,
+) -> impl Responder {
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "compliance", "read").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
+
+    let seal_id_str = seal_id.into_inner();
+
+    // Get quality metrics
+    #[derive(sqlx::FromRow)]
+    struct QualityRow {
+        metric_type: String,
+        metric_value: f64,
+        measured_at: chrono::DateTime<chrono::Utc>,
+    }
+
+    let metrics: Vec<QualityRow> = sqlx::query_as(
+        "SELECT metric_type, metric_value, measured_at 
+         FROM data_quality_metrics 
+         WHERE seal_id = $1 
+         ORDER BY measured_at DESC"
+    )
+    .bind(&seal_id_str)
+    .fetch_all(&data.db_pool)
+    .await
+    .unwrap_or_default();
+
+    // Get bias detections
+    #[derive(sqlx::FromRow)]
+    struct BiasRow {
+        bias_type: String,
+        bias_metric: f64,
+        affected_groups: serde_json::Value,
+        mitigation_applied: bool,
+        detected_at: chrono::DateTime<chrono::Utc>,
+    }
+
+    let biases: Vec<BiasRow> = sqlx::query_as(
+        "SELECT bias_type, bias_metric, affected_groups, mitigation_applied, detected_at 
+         FROM data_bias_detections 
+         WHERE seal_id = $1 
+         ORDER BY detected_at DESC"
+    )
+    .bind(&seal_id_str)
+    .fetch_all(&data.db_pool)
+    .await
+    .unwrap_or_default();
+
+    // Get lineage
+    #[derive(sqlx::FromRow)]
+    struct LineageRow {
+        source_seal_id: Option<String>,
+        transformation_type: Option<String>,
+        lineage_path: Vec<String>,
+    }
+
+    let lineage: Vec<LineageRow> = sqlx::query_as(
+        "SELECT source_seal_id, transformation_type, lineage_path 
+         FROM data_lineage 
+         WHERE seal_id = $1"
+    )
+    .bind(&seal_id_str)
+    .fetch_all(&data.db_pool)
+    .await
+    .unwrap_or_default();
+
+    HttpResponse::Ok().json(serde_json::json!({
+        "seal_id": seal_id_str,
+        "quality_metrics": metrics.iter().map(|m| serde_json::json!({
+            "type": m.metric_type,
+            "value": m.metric_value,
+            "measured_at": m.measured_at.format("%Y-%m-%d %H:%M:%S").to_string()
+        })).collect::<Vec<_>>(),
+        "bias_detections": biases.iter().map(|b| serde_json::json!({
+            "type": b.bias_type,
+            "metric": b.bias_metric,
+            "affected_groups": b.affected_groups,
+            "mitigation_applied": b.mitigation_applied,
+            "detected_at": b.detected_at.format("%Y-%m-%d %H:%M:%S").to_string()
+        })).collect::<Vec<_>>(),
+        "lineage": lineage.iter().map(|l| serde_json::json!({
+            "source_seal_id": l.source_seal_id,
+            "transformation_type": l.transformation_type,
+            "lineage_path": l.lineage_path
+        })).collect::<Vec<_>>()
+    }))
+}
+
+// PROXY MODE - Network-level compliance enforcement
+// Endpoint: POST /api/v1/proxy
+// The upstream status, headers and body are passed through; bodies (including
+// server-sent events) are streamed chunk by chunk and logged once complete.
+// Personal data in the request body is tokenized before it leaves and restored
+// in the response (PII_REDACTION_ENABLED). Token usage is metered against the
+// usage budgets of the agent and its business function. The agent and tenant
+// are those of the Veridion API key in X-Veridion-API-Key.
+#[utoipa::path(
+    post,
+    path = "/proxy",
+    request_body = crate::integration::proxy::ProxyRequest,
+    responses(
+        (status = 200, description = "Request proxied; upstream status, headers and streamed body are passed through"),
+        (status = 401, description = "Missing, invalid or expired Veridion API key"),
+        (status = 403, description = "Revoked agent, sovereignty violation, lockdown or policy rule - blocked; X-Agent-ID or X-Company-ID not matching the API key (IDENTITY_MISMATCH)"),
+        (status = 429, description = "Usage budget of the agent or its business function exhausted"),
+        (status = 400, description = "Invalid request"),
+        (status = 500, description = "Proxy error")
+    ),
+    tag = "Proxy Mode"
+)]
+pub async fn proxy_request(
+    req: HttpRequest,
+    body: web::Json<crate::integration::proxy::ProxyRequest>,
+    data: web::Data<AppState>,
+) -> impl Responder {
+    use crate::integration::proxy::ProxyService;
+    
+    let mut proxy_req = body.into_inner();
+    let header_agent = req.headers()
+        .get("X-Agent-ID")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+    let header_company = req.headers()
+        .get("X-Company-ID")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.trim().to_string());
+
+    // The agent and tenant budgets, revocations and lockdowns apply to come from
+    // the API key; headers are only trusted with PROXY_REQUIRE_API_KEY=false
+    let identity = match api_key_identity(&req, &data.db_pool, &[header_agent.clone()], header_company.as_deref()).await {
+        Ok(Some(identity)) => identity,
+        Ok(None) if data.deployment.proxy_require_api_key => {
+            return HttpResponse::Unauthorized().json(serde_json::json!({
+                "error": "API_KEY_REQUIRED",
+                "message": "A Veridion API key is required (X-Veridion-API-Key)"
+            }));
+        }
+        Ok(None) => crate::security::api_keys::AgentIdentity {
+            agent_id: header_agent.unwrap_or_else(|| "unknown".to_string()),
+            user_id: None,
+            company_id: header_company,
+        },
+        Err(resp) => return resp,
+    };
+    let agent_id = identity.agent_id;
+    let user_id = crate::security::AuthService::new()
+        .ok()
+        .and_then(|auth_service| extract_claims(&req, &auth_service).ok())
+        .map(|c| c.sub)
+        .or(identity.user_id);
+    let company_id = identity.company_id;
+
+    // 0-1. Policy state, rollout, data sovereignty, lockdown and policy rule checks
+    let request_body = proxy_req.body.as_ref().map(|b| b.to_string());
+    let admission = match admit_proxy_request(&data, &proxy_req.target_url, &agent_id, user_id.clone(), company_id.as_deref(), request_body.as_deref()).await {
+        Ok(admission) => admission,
+        Err(rejection) => return HttpResponse::Forbidden().json(rejection.body),
+    };
+
+    // 2. Token, request and cost budgets of the agent and its business function
+    if let Err(exhausted) = check_usage_budgets(&data, &admission, &agent_id).await {
+        return HttpResponse::TooManyRequests().json(exhausted);
+    }
+
+    // 3. Replace personal data in the prompt with tokens; they are put back in the response
+    // (also when a REDACT policy rule matched)
+    let mut pii_vault = crate::core::pii_redaction::TokenVault::new();
+    if data.pii_redaction_enabled || admission.rule_verdict.redact {
+        proxy_req.redact(&mut pii_vault);
+    }
+    if !pii_vault.is_empty() {
+        if let Err(e) = pii_vault.store(&data.key_store, &data.db_pool, "PROXY", &agent_id, user_id.as_deref(), None).await {
+            let request_id = generate_request_id();
+            log::error!("Error in storing PII token vault: {} (Request ID: {})", e, request_id);
+            return create_error_response(&request_id);
+        }
+    }
+
+    // 4. Forward request to target, connecting only to the address that was checked
+    let proxy_service = ProxyService::pinned(&proxy_req.target_url, admission.pinned_ip);
+    match proxy_service.forward_request(&proxy_req).await {
+        Ok(response) => {
+            let status = response.status();
+
+            // 5. Log the proxy action and its token usage once the response stream ends,
+            // on the full (tokenized) transcript
+            let log_transcript = proxy_transcript_logger(
+                &data,
+                &admission,
+                proxy_req.target_url.clone(),
+                agent_id,
+                status.as_u16(),
+                response.headers().clone(),
+            );
+
+            // Build response with the upstream status and headers, streaming the body
+            let mut http_response = HttpResponse::build(status);
+            for (key, value) in crate::integration::proxy::downstream_headers(response.headers()).iter() {
+                http_response.append_header((key.as_str(), value.as_bytes()));
+            }
+
+            let transcript = crate::integration::proxy::TranscriptStream::new(response, log_transcript);
+            if pii_vault.is_empty() {
+                http_response.streaming(transcript)
+            } else {
+                http_response.append_header(("X-Veridion-PII-Redacted", pii_vault.len().to_string()));
+                http_response.streaming(crate::integration::proxy::RehydratingStream::new(transcript, pii_vault))
+            }
+        }
+        Err(e) => {
+            let request_id = generate_request_id();
+            log_error_safely("forwarding proxy request", &e, &request_id);
+            HttpResponse::BadGateway().json(serde_json::json!({
+                "error": "PROXY_ERROR",
+                "message": "Failed to forward request to target server",
+                "request_id": request_id
+            }))
+        }
+    }
+}
+
+/// `action` of proxied requests in policy rules
+pub const PROXY_RULE_ACTION: &str = "proxy";
+
+/// Policy state of a proxy request that may be forwarded
+pub struct ProxyAdmission {
+    /// Country of the target, if sovereignty was resolved
+    pub detected_country: Option<String>,
+    /// Address the sovereignty verdict was made for; the upstream connection
+    /// must use it to rule out DNS rebinding after the check
+    pub pinned_ip: Option<std::net::IpAddr>,
+    pub policy_version_id: Option<uuid::Uuid>,
+    pub rollout_percentage: i32,
+    /// Business function of the agent's registered asset
+    pub business_function: Option<String>,
+    /// Policy rules verdict; REDACT means the body must be redacted before forwarding
+    pub rule_verdict: crate::core::policy_rules::RuleVerdict,
+}
+
+/// A proxy request refused by the Sovereign Lock (HTTP 403)
+pub struct ProxyRejection {
+    pub body: serde_json::Value,
+}
+
+/// Revocation, test mode, rollout, circuit breaker, sovereignty, lockdown and policy rule checks for a proxied request
+///
+/// Shared by `POST /proxy` and the forward proxy listener. Blocked requests are
+/// logged and counted for the circuit breaker and canary before being rejected.
+/// `body` is the request body when it is known, for data_class rules.
+pub async fn admit_proxy_request(
+    data: &AppState,
+    target_url: &str,
+    agent_id: &str,
+    user_id: Option<String>,
+    company_id: Option<&str>,
+    body: Option<&str>,
+) -> Result<ProxyAdmission, ProxyRejection> {
+    // Revoked agents lose vendor access as they lose log_action
+    if data.is_agent_revoked(agent_id) {
+        log::warn!("AGENT REVOKED: Blocked {} for agent {}", target_url, agent_id);
+        return Err(ProxyRejection { body: serde_json::json!({
+            "error": "AGENT_REVOKED",
+            "message": "Agent access has been revoked",
+            "agent_id": agent_id,
+            "target_url": target_url,
+            "status": "BLOCKED"
+        }) });
+    }
+
+    // 0. Check policy state (test mode, rollout percentage, circuit breaker)
+    #[derive(sqlx::FromRow)]
+    struct PolicyState {
+        id: uuid::Uuid,
+        is_test_mode: bool,
+        rollout_percentage: i32,
+        rollout_bucket_by: String,
+        rollout_salt: Option<String>,
+        circuit_breaker_enabled: bool,
+        circuit_breaker_state: Option<String>,
+        circuit_breaker_error_threshold: Option<f64>,
+    }
+    
+    let policy_state: Option<PolicyState> = sqlx::query_as(
+        "SELECT id, COALESCE(is_test_mode, false) as is_test_mode, 
+                COALESCE(rollout_percentage, 100) as rollout_percentage,
+                rollout_bucket_by,
+                rollout_salt,
+                COALESCE(circuit_breaker_enabled, false) as circuit_breaker_enabled,
+                circuit_breaker_state,
+                circuit_breaker_error_threshold
+         FROM policy_versions 
+         WHERE policy_type = 'SOVEREIGN_LOCK' AND is_active = true 
+         LIMIT 1"
+    )
+    .fetch_optional(&data.db_pool)
+    .await
+    .ok()
+    .flatten();
+    
+    let is_test_mode = policy_state.as_ref().map(|p| p.is_test_mode).unwrap_or(false);
+    let rollout_percentage = policy_state.as_ref().map(|p| p.rollout_percentage).unwrap_or(100);
+    let circuit_breaker_enabled = policy_state.as_ref().map(|p| p.circuit_breaker_enabled).unwrap_or(false);
+    let circuit_breaker_state = policy_state.as_ref()
+        .and_then(|p| p.circuit_breaker_state.as_ref())
+        .map(|s| s.as_str())
+        .unwrap_or("CLOSED");
+    let policy_version_id = policy_state.as_ref().map(|p| p.id);
+    
+    // Check circuit breaker state - if OPEN, skip policy enforcement
+    if circuit_breaker_enabled && circuit_breaker_state == "OPEN" {
+        log::warn!("CIRCUIT BREAKER OPEN: Skipping policy enforcement for {}", target_url);
+        // Track this as a bypass (not an error, but a circuit breaker bypass)
+        if let Some(pv_id) = policy_version_id {
+            let _ = sqlx::query(
+                "INSERT INTO policy_error_tracking (
+                    policy_version_id, policy_type, error_type, 
+                    error_count, total_requests, error_rate, 
+                    window_start, window_end
+                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                ON CONFLICT DO NOTHING"
+            )
+            .bind(pv_id)
+            .bind("SOVEREIGN_LOCK")
+            .bind("CIRCUIT_BYPASS")
+            .bind(0) // Not an error, just bypass
+            .bind(1) // Total request
+            .bind(0.0) // Error rate
+            .bind(chrono::Utc::now())
+            .bind(chrono::Utc::now())
+            .execute(&data.db_pool)
+            .await;
+        }
+        // Continue without policy enforcement
+    }
+    
+    // Business function of the agent's asset, for rollout cohorts, usage budgets and accounting
+    let asset_context = match crate::core::asset_policy_engine::AssetPolicyEngine::get_asset_context_from_agent(&data.db_pool, agent_id).await {
+        Ok(context) => context,
+        Err(e) => {
+            log::warn!("Asset lookup for agent {} failed, applying agent budgets only: {}", agent_id, e);
+            None
+        }
+    };
+    let business_function = asset_context.as_ref().and_then(|c| c.business_function.clone());
+
+    // Check if this request should be subject to policy (gradual rollout)
+    let should_apply_policy = match &policy_state {
+        Some(policy) if rollout_percentage < 100 => {
+            use crate::core::rollout::{BucketBy, RolloutEngine, RolloutSubject};
+
+            // Sticky cohort per agent, tenant, business function or user
+            let bucket_by = BucketBy::parse(&policy.rollout_bucket_by).unwrap_or(BucketBy::Agent);
+            let subject = RolloutSubject {
+                agent_id,
+                company_id,
+                business_function: business_function.as_deref(),
+                user_id: user_id.as_deref(),
+            };
+            match RolloutEngine::assign(&data.db_pool, policy.id, policy.rollout_salt.as_deref(), bucket_by, &subject, rollout_percentage).await {
+                Ok(assignment) => assignment.in_cohort,
+                Err(e) => {
+                    log::warn!("Recording rollout assignment for policy {} failed: {}", policy.id, e);
+                    let salt = policy.rollout_salt.clone().unwrap_or_else(|| policy.id.to_string());
+                    let (_, key) = subject.key(bucket_by);
+                    crate::core::rollout::in_rollout(crate::core::rollout::bucket(&salt, key), rollout_percentage)
+                }
+            }
+        }
+        _ => true, // 100% rollout - apply to all
+    };
+
+    // 1. Check data sovereignty BEFORE forwarding
+    let pinned_ip;
+    let detected_country = match data.sovereignty.resolve_url(&target_url).await {
+        Ok(verdict) => {
+            let country = verdict.country;
+            pinned_ip = verdict.ip;
+            let via = pinned_ip.map(|ip| format!(" via {}", ip)).unwrap_or_default();
+            if !verdict.allowed {
+                // Skip policy enforcement if not in rollout percentage
+                if !should_apply_policy {
+                    log::debug!("ROLLOUT: Skipping policy for {} (rollout: {}%)", target_url, rollout_percentage);
+                    // Continue to forward - not in rollout yet, return country and proceed
+                } else if is_test_mode {
+                    log::warn!("TEST MODE: Would block {} -> {} (Country: {})", target_url, country, country);
+                    // Log test mode action but continue
+                    let record_id = uuid::Uuid::new_v4();
+                    let seal_id = uuid::Uuid::new_v4().to_string();
+                    let tx_id = uuid::Uuid::new_v4().to_string();
+                    let action_summary = format!("TEST_MODE: Would block {} ({}){}", target_url, country, via);
+                    let _ = sqlx::query(
+                        "INSERT INTO compliance_records (
+                            id, seal_id, tx_id, agent_id, action_summary, status, 
+                            risk_level, timestamp, payload_hash
+                        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"
+                    )
+                    .bind(record_id)
+                    .bind(&seal_id)
+                    .bind(&tx_id)
+                    .bind(&agent_id)
+                    .bind(&action_summary)
+                    .bind("TEST_MODE (WOULD_BLOCK)")
+                    .bind("HIGH")
+                    .bind(chrono::Utc::now())
+                    .bind(&format!("region:{}", country))
+                    .execute(&data.db_pool)
+                    .await;
+                    // Continue to forward in test mode - return country and proceed
+                    // Note: This is inside a match arm, so we just continue to the end
+                } else {
+                // Log the violation attempt
+                // Log compliance action
+                let record_id = uuid::Uuid::new_v4();
+                let seal_id = uuid::Uuid::new_v4().to_string();
+                let tx_id = uuid::Uuid::new_v4().to_string();
+                let action_summary = format!("PROXY_BLOCKED: Attempted connection to {} ({}){}", target_url, country, via);
+                if let Err(e) = sqlx::query(
+                    "INSERT INTO compliance_records (
+                        id, seal_id, tx_id, agent_id, action_summary, status, 
+                        risk_level, user_id, timestamp, payload_hash
+                    ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)"
+                )
+                .bind(record_id)
+                .bind(&seal_id)
+                .bind(&tx_id)
+                .bind(&agent_id)
+                .bind(&action_summary)
+                .bind("BLOCKED (SOVEREIGNTY)")
+                .bind("HIGH")
+                .bind(user_id.as_deref())
+                .bind(chrono::Utc::now())
+                .bind(&format!("region:{}", country))
+                .execute(&data.db_pool)
+                .await {
+                    log::error!("Failed to log proxy block: {}", e);
+                }
+                
+                // Track error for circuit breaker (if enabled)
+                if let Some(pv_id) = policy_version_id {
+                    let now = chrono::Utc::now();
+                    let window_start = now - chrono::Duration::minutes(5);
+                    
+                    // Update error tracking
+                    let _ = sqlx::query(
+                        "INSERT INTO policy_error_tracking (
+                            policy_version_id, policy_type, error_type,
+                            error_count, total_requests, error_rate,
+                            window_start, window_end
+                        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                        ON CONFLICT DO UPDATE SET
+                            error_count = policy_error_tracking.error_count + 1,
+                            total_requests = policy_error_tracking.total_requests + 1,
+                            error_rate = ((policy_error_tracking.error_count + 1)::DECIMAL / 
+                                         (policy_error_tracking.total_requests + 1)::DECIMAL) * 100.0,
+                            updated_at = CURRENT_TIMESTAMP"
+                    )
+                    .bind(pv_id)
+                    .bind("SOVEREIGN_LOCK")
+                    .bind("BLOCKED")
+                    .bind(1)
+                    .bind(1)
+                    .bind(100.0) // 100% error rate for blocked requests
+                    .bind(window_start)
+                    .bind(now)
+                    .execute(&data.db_pool)
+                    .await;
+                    
+                    // Check if circuit breaker should open
+                    let should_open: Option<bool> = sqlx::query_scalar(
+                        "SELECT should_open_circuit_breaker($1)"
+                    )
+                    .bind(pv_id)
+                    .fetch_optional(&data.db_pool)
+                    .await
+                    .ok()
+                    .flatten();
+                    
+                    if should_open.unwrap_or(false) {
+                        // Open circuit breaker
+                        let _ = sqlx::query(
+                            "UPDATE policy_versions 
+                             SET circuit_breaker_state = 'OPEN',
+                                 circuit_breaker_opened_at = CURRENT_TIMESTAMP,
+                                 circuit_breaker_last_error_at = CURRENT_TIMESTAMP
+                             WHERE id = $1"
+                        )
+                        .bind(pv_id)
+                        .execute(&data.db_pool)
+                        .await;
+                        
+                        // Log circuit breaker history
+                        let _ = sqlx::query(
+                            "INSERT INTO circuit_breaker_history (
+                                policy_version_id, state_transition, error_rate,
+                                error_count, total_requests, triggered_by, notes
+                            ) VALUES ($1, $2, $3, $4, $5, $6, $7)"
+                        )
+                        .bind(pv_id)
                         .bind("OPENED")
                         .bind(100.0)
                         .bind(1)
@@ -6587,275 +8213,1157 @@ pub async fn proxy_request(
                                 let _ = notification_service.send_notification(&db_pool_clone, request).await;
                             });
                         }
-                        
-                        // Trigger webhook event
-                        let success_rate_for_webhook = success_rate;
-                        let db_pool_for_webhook = data.db_pool.clone();
-                        tokio::spawn(async move {
-                            let webhook_data = serde_json::json!({
-                                "policy_id": pv_id,
-                                "policy_name": policy_name_for_webhook,
-                                "action": "AUTO_ROLLBACK",
-                                "from_percentage": rollout_pct,
-                                "to_percentage": prev_percentage,
-                                "success_rate": success_rate_for_webhook,
-                                "reason": "High failure rate detected"
-                            });
-                            trigger_webhook_event(&db_pool_for_webhook, "policy.rollback.auto", webhook_data).await;
-                        });
+                        
+                        // Trigger webhook event
+                        let success_rate_for_webhook = success_rate;
+                        let db_pool_for_webhook = data.db_pool.clone();
+                        tokio::spawn(async move {
+                            let webhook_data = serde_json::json!({
+                                "policy_id": pv_id,
+                                "policy_name": policy_name_for_webhook,
+                                "action": "AUTO_ROLLBACK",
+                                "from_percentage": rollout_pct,
+                                "to_percentage": prev_percentage,
+                                "success_rate": success_rate_for_webhook,
+                                "reason": "High failure rate detected"
+                            });
+                            trigger_webhook_event(&db_pool_for_webhook, "policy.rollback.auto", webhook_data).await;
+                        });
+                    }
+                }
+
+                return Err(ProxyRejection { body: serde_json::json!({
+                    "error": "SOVEREIGN_LOCK_VIOLATION",
+                    "message": format!("Data sovereignty violation: target server in {} is not in EU/EEA", country),
+                    "target_url": target_url,
+                    "detected_country": country,
+                    "resolved_ip": pinned_ip,
+                    "status": "BLOCKED"
+                }) });
+                }
+            }
+            // Store the detected country for logging allowed requests
+            Some(country)
+        }
+        Err(e) => {
+            // If we can't determine sovereignty, be conservative and block
+            log::warn!("Could not determine sovereignty for {}: {}", target_url, e);
+            
+            // Log the failed check attempt
+            // Log compliance action
+            let record_id = uuid::Uuid::new_v4();
+            let seal_id = uuid::Uuid::new_v4().to_string();
+            let tx_id = uuid::Uuid::new_v4().to_string();
+            let action_summary = format!("PROXY_BLOCKED: Could not determine sovereignty for {}", target_url);
+            if let Err(e) = sqlx::query(
+                "INSERT INTO compliance_records (
+                    id, seal_id, tx_id, agent_id, action_summary, status, 
+                    risk_level, user_id, timestamp, payload_hash
+                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)"
+            )
+            .bind(record_id)
+            .bind(&seal_id)
+            .bind(&tx_id)
+            .bind(&agent_id)
+            .bind(&action_summary)
+            .bind("BLOCKED (SOVEREIGNTY)")
+            .bind("HIGH")
+            .bind(user_id.as_deref())
+            .bind(chrono::Utc::now())
+            .bind("region:UNKNOWN")
+            .execute(&data.db_pool)
+            .await {
+                log::error!("Failed to log proxy block: {}", e);
+            }
+            
+            return Err(ProxyRejection { body: serde_json::json!({
+                "error": "SOVEREIGNTY_CHECK_FAILED",
+                "message": "Could not verify data sovereignty. Request blocked for safety.",
+                "target_url": target_url,
+                "detected_country": "UNKNOWN",
+                "status": "BLOCKED"
+            }) });
+        }
+    };
+
+    // 1.1. Global and scoped lockdowns (business function, vendor domain, jurisdiction, tenant)
+    if let Ok(true) = data.is_locked_down().await {
+        return Err(ProxyRejection { body: serde_json::json!({
+            "error": "SYSTEM_LOCKDOWN",
+            "message": "System-wide lockdown active",
+            "target_url": target_url,
+            "status": "BLOCKED"
+        }) });
+    }
+    let vendor_host = reqwest::Url::parse(target_url).ok()
+        .and_then(|url| url.host_str().map(str::to_string));
+    let lockdown_target = crate::core::lockdown::LockdownTarget {
+        business_function: business_function.as_deref(),
+        vendor_host: vendor_host.as_deref(),
+        country: detected_country.as_deref(),
+        tenant: company_id,
+    };
+    match crate::core::lockdown::Lockdowns::check(&data.db_pool, &lockdown_target).await {
+        Ok(Some(lockdown)) => {
+            log::warn!("LOCKDOWN: Blocked {} for agent {} ({})", target_url, agent_id, lockdown.describe());
+            return Err(ProxyRejection { body: serde_json::json!({
+                "error": "SCOPED_LOCKDOWN",
+                "message": format!("Lockdown active for {}", lockdown.describe()),
+                "target_url": target_url,
+                "lockdown_id": lockdown.id,
+                "expires_at": lockdown.expires_at,
+                "status": "BLOCKED"
+            }) });
+        }
+        Ok(None) => {}
+        Err(e) => {
+            log::error!("Lockdown check failed for {}: {}", target_url, e);
+            return Err(ProxyRejection { body: serde_json::json!({
+                "error": "LOCKDOWN_CHECK_FAILED",
+                "message": "Active lockdowns could not be checked",
+                "target_url": target_url,
+                "status": "BLOCKED"
+            }) });
+        }
+    }
+
+    // 1.2. Policy rules of the applicable asset policies (DENY and REQUIRE_OVERSIGHT block:
+    // a proxied call cannot wait for a reviewer)
+    let policy_context = asset_context.unwrap_or(crate::core::asset_policy_engine::AssetContext {
+        asset_id: None,
+        business_function: None,
+        department: None,
+        location: None,
+        risk_profile: None,
+        tags: None,
+    });
+    let rule_sets = match crate::core::asset_policy_engine::AssetPolicyEngine::get_applicable_policies(&data.db_pool, &policy_context).await {
+        Ok(policies) => crate::core::asset_policy_engine::AssetPolicyEngine::rule_sets(&policies),
+        Err(e) => {
+            log::error!("Policy rule lookup failed for {}, no rules applied: {}", target_url, e);
+            Vec::new()
+        }
+    };
+    let rule_context = crate::core::policy_rules::RuleContext {
+        agent: Some(agent_id.to_string()),
+        action: Some(PROXY_RULE_ACTION.to_string()),
+        business_function: business_function.clone(),
+        data_class: body.map(crate::core::policy_rules::data_classes).unwrap_or_default(),
+        region: detected_country.clone(),
+        vendor: vendor_host.clone(),
+        consent: Some(crate::core::policy_rules::CONSENT_NOT_APPLICABLE.to_string()),
+        risk_level: None,
+        tenant: company_id.map(str::to_string),
+    };
+    let rule_verdict = crate::core::policy_rules::RuleSet::evaluate(&rule_sets, &rule_context);
+    if rule_verdict.denied() || rule_verdict.require_oversight {
+        let reason = rule_verdict.reason().unwrap_or_default();
+        log::warn!("POLICY RULES: Blocked {} for agent {} ({})", target_url, agent_id, reason);
+        let action_summary = format!("PROXY_BLOCKED: Policy rules refused {} ({})", target_url, reason);
+        if let Err(e) = sqlx::query(
+            "INSERT INTO compliance_records (
+                id, seal_id, tx_id, agent_id, action_summary, status,
+                risk_level, user_id, timestamp, payload_hash
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)"
+        )
+        .bind(uuid::Uuid::new_v4())
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(&agent_id)
+        .bind(&action_summary)
+        .bind("BLOCKED (POLICY)")
+        .bind("HIGH")
+        .bind(user_id.as_deref())
+        .bind(chrono::Utc::now())
+        .bind(&format!("region:{}", detected_country.as_deref().unwrap_or("UNKNOWN")))
+        .execute(&data.db_pool)
+        .await {
+            log::error!("Failed to log proxy block: {}", e);
+        }
+        return Err(ProxyRejection { body: serde_json::json!({
+            "error": if rule_verdict.denied() { "POLICY_DENIED" } else { "OVERSIGHT_REQUIRED" },
+            "message": reason,
+            "target_url": target_url,
+            "matched_rules": rule_verdict.matched,
+            "status": "BLOCKED"
+        }) });
+    }
+
+    Ok(ProxyAdmission {
+        detected_country,
+        pinned_ip,
+        policy_version_id,
+        rollout_percentage,
+        business_function,
+        rule_verdict,
+    })
+}
+
+/// Usage budgets of the agent and its business function (HTTP 429 once a HARD budget is exhausted)
+///
+/// The first block in a budget period notifies through `NotificationService`.
+/// Budget lookups that fail let the request through.
+pub async fn check_usage_budgets(
+    data: &AppState,
+    admission: &ProxyAdmission,
+    agent_id: &str,
+) -> Result<(), serde_json::Value> {
+    use crate::core::usage_budget::UsageBudgets;
+
+    let statuses = match UsageBudgets::check(&data.db_pool, agent_id, admission.business_function.as_deref()).await {
+        Ok(statuses) => statuses,
+        Err(e) => {
+            log::error!("Usage budget check failed for agent {}, request allowed: {}", agent_id, e);
+            return Ok(());
+        }
+    };
+    let Some(exhausted) = statuses.into_iter().find(|s| s.blocks()) else {
+        return Ok(());
+    };
+
+    if UsageBudgets::claim_alert(&data.db_pool, &exhausted, "EXCEEDED").await.unwrap_or(false) {
+        let db_pool = data.db_pool.clone();
+        let notification_service = data.notification_service.clone();
+        let status = exhausted.clone();
+        let agent_id = agent_id.to_string();
+        tokio::spawn(async move {
+            if let Err(e) = notification_service.send_usage_budget_alert(&db_pool, &status, &agent_id, true, None).await {
+                log::warn!("Failed to send usage budget alert: {}", e);
+            }
+        });
+    }
+
+    let budget = &exhausted.budget;
+    Err(serde_json::json!({
+        "error": "USAGE_BUDGET_EXCEEDED",
+        "message": format!("{} {} budget for {} {} is exhausted", budget.period, budget.enforcement, budget.scope_type, budget.scope_value),
+        "budget_id": budget.id,
+        "period_start": exhausted.period_start,
+        "used_tokens": exhausted.used_tokens,
+        "used_requests": exhausted.used_requests,
+        "used_cost_eur": exhausted.used_cost_eur,
+        "status": "BLOCKED"
+    }))
+}
+
+/// Completion handler for a proxied response: canary metrics, the compliance
+/// record and usage accounting, written once the response stream has ended
+pub fn proxy_transcript_logger(
+    data: &AppState,
+    admission: &ProxyAdmission,
+    target_url: String,
+    agent_id: String,
+    upstream_status: u16,
+    upstream_headers: reqwest::header::HeaderMap,
+) -> impl FnOnce(crate::integration::proxy::Transcript) + Send + 'static {
+    let db_pool = data.db_pool.clone();
+    let notification_service = data.notification_service.clone();
+    let business_function = admission.business_function.clone();
+    let country_for_log = admission.detected_country.clone().unwrap_or_else(|| "UNKNOWN".to_string());
+    let via = admission.pinned_ip.map(|ip| format!(" via {}", ip)).unwrap_or_default();
+    let policy_version_id = admission.policy_version_id;
+    let rollout_percentage = admission.rollout_percentage;
+
+    move |transcript: crate::integration::proxy::Transcript| {
+        tokio::spawn(async move {
+            let record_id = uuid::Uuid::new_v4();
+            let seal_id = uuid::Uuid::new_v4().to_string();
+            let tx_id = uuid::Uuid::new_v4().to_string();
+            let sse_events = transcript.sse_events().len();
+            let action_summary = format!(
+                "PROXY_ALLOWED: {} ({}){} [HTTP {}, {} bytes{}, {} SSE events, {}, sha256:{}]",
+                target_url,
+                country_for_log,
+                via,
+                upstream_status,
+                transcript.bytes_streamed,
+                if transcript.truncated { " (transcript truncated)" } else { "" },
+                sse_events,
+                transcript.outcome.as_str(),
+                transcript.sha256()
+            );
+        
+            // Track canary metrics (if policy has rollout_percentage)
+            if let Some(pv_id) = policy_version_id {
+                let rollout_pct = rollout_percentage;
+                let now = chrono::Utc::now();
+                let window_start = now - chrono::Duration::minutes(10);
+            
+                let _ = sqlx::query(
+                    "INSERT INTO canary_metrics (
+                        policy_version_id, traffic_percentage, total_requests,
+                        successful_requests, failed_requests, blocked_requests,
+                        success_rate, window_start, window_end
+                    ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                    ON CONFLICT (policy_version_id, traffic_percentage, window_start) 
+                    DO UPDATE SET
+                        total_requests = canary_metrics.total_requests + 1,
+                        successful_requests = canary_metrics.successful_requests + 1,
+                        success_rate = ((canary_metrics.successful_requests + 1)::DECIMAL / 
+                                       (canary_metrics.total_requests + 1)::DECIMAL) * 100.0,
+                        updated_at = CURRENT_TIMESTAMP"
+                )
+                .bind(pv_id)
+                .bind(rollout_pct)
+                .bind(1)
+                .bind(1)
+                .bind(0)
+                .bind(0)
+                .bind(100.0)
+                .bind(window_start)
+                .bind(now)
+                .execute(&db_pool)
+                .await;
+            
+                // Check if should auto-promote
+                let should_promote: Option<bool> = sqlx::query_scalar(
+                    "SELECT should_promote_canary($1)"
+                )
+                .bind(pv_id)
+                .fetch_optional(&db_pool)
+                .await
+                .ok()
+                .flatten();
+            
+                if should_promote.unwrap_or(false) {
+                    // Auto-promote to next percentage tier
+                    let next_percentage = match rollout_pct {
+                        1 => 5,
+                        5 => 10,
+                        10 => 25,
+                        25 => 50,
+                        50 => 100,
+                        _ => 100,
+                    };
+                
+                    let success_rate: Option<f64> = sqlx::query_scalar(
+                        "SELECT calculate_canary_success_rate($1, $2, 10)"
+                    )
+                    .bind(pv_id)
+                    .bind(rollout_pct)
+                    .fetch_optional(&db_pool)
+                    .await
+                    .ok()
+                    .flatten();
+                
+                    let _ = sqlx::query(
+                        "UPDATE policy_versions 
+                         SET rollout_percentage = $1
+                         WHERE id = $2"
+                    )
+                    .bind(next_percentage)
+                    .bind(pv_id)
+                    .execute(&db_pool)
+                    .await;
+                
+                    // Log promotion
+                    let _ = sqlx::query(
+                        "INSERT INTO canary_deployment_history (
+                            policy_version_id, action, from_percentage, to_percentage,
+                            success_rate, triggered_by, notes
+                        ) VALUES ($1, $2, $3, $4, $5, $6, $7)"
+                    )
+                    .bind(pv_id)
+                    .bind("PROMOTED")
+                    .bind(rollout_pct)
+                    .bind(next_percentage)
+                    .bind(success_rate.unwrap_or(0.0))
+                    .bind("AUTO")
+                    .bind(format!("Auto-promoted from {}% to {}% based on success rate", rollout_pct, next_percentage))
+                    .execute(&db_pool)
+                    .await;
+                
+                    log::info!("CANARY AUTO-PROMOTED: Policy {} from {}% to {}%", pv_id, rollout_pct, next_percentage);
+                }
+            }
+        
+            if let Err(e) = sqlx::query(
+                "INSERT INTO compliance_records (
+                    id, seal_id, tx_id, agent_id, action_summary, status, 
+                    risk_level, timestamp, payload_hash
+                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"
+            )
+            .bind(record_id)
+            .bind(&seal_id)
+            .bind(&tx_id)
+            .bind(&agent_id)
+            .bind(&action_summary)
+            .bind("COMPLIANT")
+            .bind("LOW")
+            .bind(chrono::Utc::now())
+            .bind(&format!("region:{}", country_for_log))
+            .execute(&db_pool)
+            .await {
+                log::error!("Failed to log proxy success: {}", e);
+            }
+
+            // Usage accounting against the agent's and business function's budgets
+            let usage = crate::core::usage_budget::TokenUsage::parse(&target_url, &upstream_headers, &transcript.body);
+            if let Err(e) = crate::core::usage_budget::UsageBudgets::record(
+                &db_pool,
+                &agent_id,
+                business_function.as_deref(),
+                &target_url,
+                upstream_status,
+                usage.as_ref(),
+            ).await {
+                log::error!("Failed to record AI usage: {}", e);
+                return;
+            }
+            alert_usage_budgets(&db_pool, &notification_service, &agent_id, business_function.as_deref()).await;
+        });
+    }
+}
+
+/// Notify once per budget period when a budget reaches its soft limit or is exhausted
+async fn alert_usage_budgets(
+    db_pool: &sqlx::PgPool,
+    notification_service: &crate::integration::notifications::NotificationService,
+    agent_id: &str,
+    business_function: Option<&str>,
+) {
+    use crate::core::usage_budget::UsageBudgets;
+
+    let statuses = match UsageBudgets::check(db_pool, agent_id, business_function).await {
+        Ok(statuses) => statuses,
+        Err(e) => {
+            log::error!("Usage budget check failed for agent {}: {}", agent_id, e);
+            return;
+        }
+    };
+    for status in statuses {
+        let level = if status.exceeded {
+            "EXCEEDED"
+        } else if status.soft_limit_reached {
+            "SOFT"
+        } else {
+            continue;
+        };
+        if !UsageBudgets::claim_alert(db_pool, &status, level).await.unwrap_or(false) {
+            continue;
+        }
+        log::warn!(
+            "USAGE BUDGET {}: {} {} {} at {:.1}%",
+            level,
+            status.budget.period,
+            status.budget.scope_type,
+            status.budget.scope_value,
+            status.utilization_percent
+        );
+        if let Err(e) = notification_service.send_usage_budget_alert(db_pool, &status, agent_id, status.blocks(), None).await {
+            log::warn!("Failed to send usage budget alert: {}", e);
+        }
+    }
+}
+
+// ========== POLICY SIMULATION & OPERATIONAL SAFETY ==========
+
+/// Simulate policy change impact before enforcement
+#[utoipa::path(
+    post,
+    path = "/policies/simulate",
+    tag = "Policy Management",
+    request_body = SimulationRequest,
+    responses(
+        (status = 200, description = "Simulation result", body = SimulationResult),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+    ),
+    security(
+        ("bearer" = [])
+    )
+)]
+pub async fn simulate_policy(
+    req: web::Json<crate::core::policy_simulator::SimulationRequest>,
+    data: web::Data<AppState>,
+    http_req: HttpRequest,
+) -> impl Responder {
+    // AUTHENTICATION & AUTHORIZATION
+    let claims = match authenticate_and_authorize(&http_req, &data.db_pool, "policy", "read").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
+
+    // Run simulation
+    match crate::core::policy_simulator::PolicySimulator::simulate(
+        &data.db_pool,
+        &data.sovereignty,
+        req.into_inner(),
+    )
+    .await
+    {
+        Ok(result) => {
+            // Cache the result
+            let _ = sqlx::query(
+                "INSERT INTO policy_simulation_results (policy_type, policy_config, simulation_result, time_range_days, simulated_by)
+                 VALUES ($1, $2, $3, $4, $5)"
+            )
+            .bind(format!("{:?}", result.policy_type))
+            .bind(serde_json::json!({}))
+            .bind(serde_json::to_value(&result).unwrap_or(serde_json::Value::Null))
+            .bind(result.total_requests)
+            .bind(&claims.sub)
+            .execute(&data.db_pool)
+            .await;
+
+            HttpResponse::Ok().json(result)
+        }
+        Err(e) => HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "SIMULATION_FAILED",
+            "message": e
+        }))
+    }
+}
+
+/// Export simulation report
+#[utoipa::path(
+    post,
+    path = "/policies/simulate/export",
+    tag = "Policy Simulator",
+    request_body = crate::core::policy_simulator::SimulationRequest,
+    params(
+        ("format" = Option<String>, Query, description = "Export format: json, csv, pdf, or text (default: json)"),
+    ),
+    responses(
+        (status = 200, description = "Simulation report exported"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(
+        ("bearer" = [])
+    )
+)]
+pub async fn export_simulation_report(
+    req: web::Json<crate::core::policy_simulator::SimulationRequest>,
+    query: web::Query<std::collections::HashMap<String, String>>,
+    data: web::Data<AppState>,
+    http_req: HttpRequest,
+) -> impl Responder {
+    // AUTHENTICATION & AUTHORIZATION
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "policy", "read").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
+
+    let format = query.get("format")
+        .map(|s| s.to_lowercase())
+        .unwrap_or_else(|| "json".to_string());
+
+    // Run simulation
+    let result = match crate::core::policy_simulator::PolicySimulator::simulate(
+        &data.db_pool,
+        &data.sovereignty,
+        req.into_inner(),
+    )
+    .await
+    {
+        Ok(r) => r,
+        Err(e) => return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "SIMULATION_FAILED",
+            "message": e
+        }))
+    };
+
+    match format.as_str() {
+        "json" => {
+            HttpResponse::Ok()
+                .content_type("application/json")
+                .append_header(("Content-Disposition", format!("attachment; filename=\"simulation_report_{}.json\"", chrono::Utc::now().format("%Y%m%d_%H%M%S"))))
+                .json(&result)
+        }
+        "csv" => {
+            let mut csv = String::from("policy_type,total_requests,would_block,would_allow,impact_level,simulation_timestamp\n");
+            csv.push_str(&format!(
+                "{:?},{},{},{},{:?},{}\n",
+                result.policy_type,
+                result.total_requests,
+                result.would_block,
+                result.would_allow,
+                result.estimated_impact,
+                result.simulation_timestamp.to_rfc3339(),
+            ));
+            csv.push_str("\n# Affected Agents\nagent_id,total_requests,would_block,would_allow\n");
+            for agent in &result.affected_agents {
+                csv.push_str(&format!(
+                    "{},{},{},{}\n",
+                    escape_csv(&agent.agent_id),
+                    agent.total_requests,
+                    agent.would_block,
+                    agent.would_allow,
+                ));
+            }
+            csv.push_str("\n# Affected Endpoints\nendpoint,count\n");
+            for (endpoint, count) in &result.affected_endpoints {
+                csv.push_str(&format!("{},{}\n", escape_csv(endpoint), count));
+            }
+            csv.push_str("\n# Requests by Country\ncountry,count\n");
+            for (country, count) in &result.requests_by_country {
+                csv.push_str(&format!("{},{}\n", escape_csv(country), count));
+            }
+            HttpResponse::Ok()
+                .content_type("text/csv")
+                .append_header(("Content-Disposition", format!("attachment; filename=\"simulation_report_{}.csv\"", chrono::Utc::now().format("%Y%m%d_%H%M%S"))))
+                .body(csv)
+        }
+        "text" => {
+            let mut text = String::new();
+            text.push_str(&format!("POLICY SIMULATION REPORT\n"));
+            text.push_str(&format!("Generated: {}\n\n", chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC")));
+            text.push_str(&format!("Policy Type: {:?}\n", result.policy_type));
+            text.push_str(&format!("Total Requests: {}\n", result.total_requests));
+            text.push_str(&format!("Would Block: {}\n", result.would_block));
+            text.push_str(&format!("Would Allow: {}\n", result.would_allow));
+            text.push_str(&format!("Impact Level: {:?}\n\n", result.estimated_impact));
+            text.push_str(&format!("Affected Agents: {}\n", result.affected_agents.len()));
+            for agent in &result.affected_agents {
+                text.push_str(&format!("  - {}: {} blocked / {} total\n", agent.agent_id, agent.would_block, agent.total_requests));
+            }
+            text.push_str(&format!("\nAffected Endpoints: {}\n", result.affected_endpoints.len()));
+            for (endpoint, count) in &result.affected_endpoints {
+                text.push_str(&format!("  - {}: {} requests\n", endpoint, count));
+            }
+            text.push_str(&format!("\nRequests by Country: {}\n", result.requests_by_country.len()));
+            for (country, count) in &result.requests_by_country {
+                text.push_str(&format!("  - {}: {} requests\n", country, count));
+            }
+            HttpResponse::Ok()
+                .content_type("text/plain")
+                .append_header(("Content-Disposition", format!("attachment; filename=\"simulation_report_{}.txt\"", chrono::Utc::now().format("%Y%m%d_%H%M%S"))))
+                .body(text)
+        }
+        "pdf" => {
+            let temp_file = format!("temp_simulation_export_{}.pdf", uuid::Uuid::new_v4().to_string().replace("-", ""));
+            match generate_simulation_pdf(&result, &temp_file) {
+                Ok(_) => {
+                    match std::fs::read(&temp_file) {
+                        Ok(pdf_bytes) => {
+                            let _ = std::fs::remove_file(&temp_file);
+                            HttpResponse::Ok()
+                                .content_type("application/pdf")
+                                .append_header(("Content-Disposition", format!("attachment; filename=\"simulation_report_{}.pdf\"", chrono::Utc::now().format("%Y%m%d_%H%M%S"))))
+                                .body(pdf_bytes)
+                        }
+                        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+                            "error": "PDF_READ_FAILED",
+                            "message": format!("Failed to read PDF file: {}", e)
+                        }))
                     }
                 }
+                Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "PDF_GENERATION_FAILED",
+                    "message": format!("Failed to generate PDF: {}", e)
+                }))
+            }
+        }
+        _ => HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "INVALID_FORMAT",
+            "message": "Format must be json, csv, pdf, or text"
+        }))
+    }
+}
+
+/// Queue a policy simulation to run in the background
+///
+/// For replay windows too long for a request: a worker streams the records
+/// through the simulator and reports progress on the job.
+#[utoipa::path(
+    post,
+    path = "/policies/simulate/jobs",
+    tag = "Policy Simulator",
+    request_body = crate::core::policy_simulator::SimulationRequest,
+    responses(
+        (status = 202, description = "Simulation queued", body = crate::core::policy_simulator::SimulationJob),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error"),
+    ),
+    security(
+        ("bearer" = [])
+    )
+)]
+pub async fn submit_simulation_job(
+    req: web::Json<crate::core::policy_simulator::SimulationRequest>,
+    data: web::Data<AppState>,
+    http_req: HttpRequest,
+) -> impl Responder {
+    // AUTHENTICATION & AUTHORIZATION
+    let claims = match authenticate_and_authorize(&http_req, &data.db_pool, "policy", "read").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
+
+    let request = req.into_inner();
+    if let Err(e) = crate::core::policy_simulator::PolicySimulator::validate(&request) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "SIMULATION_FAILED",
+            "message": e
+        }));
+    }
+
+    match crate::core::policy_simulator::SimulationJobs::submit(&data.db_pool, &request, &claims.sub).await {
+        Ok(job) => HttpResponse::Accepted().json(job),
+        Err(e) => {
+            let request_id = generate_request_id();
+            log::error!("Error queueing policy simulation: {} (Request ID: {})", e, request_id);
+            create_error_response(&request_id)
+        }
+    }
+}
+
+/// Recent background policy simulations, without their results
+#[utoipa::path(
+    get,
+    path = "/policies/simulate/jobs",
+    tag = "Policy Simulator",
+    responses(
+        (status = 200, description = "Simulation jobs, newest first", body = Vec<crate::core::policy_simulator::SimulationJob>),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error"),
+    ),
+    security(
+        ("bearer" = [])
+    )
+)]
+pub async fn list_simulation_jobs(
+    data: web::Data<AppState>,
+    http_req: HttpRequest,
+) -> impl Responder {
+    // AUTHENTICATION & AUTHORIZATION
+    if let Err(resp) = authenticate_and_authorize(&http_req, &data.db_pool, "policy", "read").await {
+        return resp;
+    }
+
+    match crate::core::policy_simulator::SimulationJobs::list(&data.db_pool, 50).await {
+        Ok(jobs) => HttpResponse::Ok().json(jobs),
+        Err(e) => {
+            let request_id = generate_request_id();
+            log::error!("Error listing policy simulations: {} (Request ID: {})", e, request_id);
+            create_error_response(&request_id)
+        }
+    }
+}
+
+/// Progress of a background policy simulation and its result once completed
+#[utoipa::path(
+    get,
+    path = "/policies/simulate/jobs/{job_id}",
+    tag = "Policy Simulator",
+    params(
+        ("job_id" = Uuid, Path, description = "Simulation job ID"),
+    ),
+    responses(
+        (status = 200, description = "Simulation job", body = crate::core::policy_simulator::SimulationJob),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Simulation job not found"),
+        (status = 500, description = "Internal server error"),
+    ),
+    security(
+        ("bearer" = [])
+    )
+)]
+pub async fn get_simulation_job(
+    job_id: web::Path<Uuid>,
+    data: web::Data<AppState>,
+    http_req: HttpRequest,
+) -> impl Responder {
+    // AUTHENTICATION & AUTHORIZATION
+    if let Err(resp) = authenticate_and_authorize(&http_req, &data.db_pool, "policy", "read").await {
+        return resp;
+    }
+
+    match crate::core::policy_simulator::SimulationJobs::get(&data.db_pool, job_id.into_inner()).await {
+        Ok(Some(job)) => HttpResponse::Ok().json(job),
+        Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "NOT_FOUND",
+            "message": "Simulation job not found"
+        })),
+        Err(e) => {
+            let request_id = generate_request_id();
+            log::error!("Error loading policy simulation: {} (Request ID: {})", e, request_id);
+            create_error_response(&request_id)
+        }
+    }
+}
+
+/// Generate PDF report for simulation results
+fn generate_simulation_pdf(result: &crate::core::policy_simulator::SimulationResult, output_path: &str) -> Result<(), String> {
+    use printpdf::*;
+    use std::fs::File;
+    use std::io::BufWriter;
+
+    let (doc, page1, layer1) = PdfDocument::new("Policy Simulation Report", Mm(210.0), Mm(297.0), "Layer 1");
+    let current_layer = doc.get_page(page1).get_layer(layer1);
+
+    let font_bold = doc.add_builtin_font(BuiltinFont::HelveticaBold)
+        .map_err(|e| format!("Failed to add font: {:?}", e))?;
+    let font_reg = doc.add_builtin_font(BuiltinFont::Helvetica)
+        .map_err(|e| format!("Failed to add font: {:?}", e))?;
+
+    // Header
+    current_layer.set_fill_color(Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None)));
+    current_layer.use_text("VERIDION NEXUS | POLICY SIMULATION REPORT", 18.0, Mm(10.0), Mm(280.0), &font_bold);
+    current_layer.use_text(&format!("Generated: {}", Utc::now().format("%Y-%m-%d %H:%M:%S UTC")), 10.0, Mm(10.0), Mm(270.0), &font_reg);
+
+    // Summary
+    let mut y_pos = 250.0;
+    current_layer.use_text(&format!("Policy Type: {:?}", result.policy_type), 10.0, Mm(10.0), Mm(y_pos), &font_reg);
+    y_pos -= 10.0;
+    current_layer.use_text(&format!("Total Requests: {}", result.total_requests), 10.0, Mm(10.0), Mm(y_pos), &font_reg);
+    y_pos -= 10.0;
+    current_layer.use_text(&format!("Would Block: {}", result.would_block), 10.0, Mm(10.0), Mm(y_pos), &font_reg);
+    y_pos -= 10.0;
+    current_layer.use_text(&format!("Would Allow: {}", result.would_allow), 10.0, Mm(10.0), Mm(y_pos), &font_reg);
+    y_pos -= 10.0;
+    current_layer.use_text(&format!("Impact Level: {:?}", result.estimated_impact), 10.0, Mm(10.0), Mm(y_pos), &font_reg);
+    y_pos -= 20.0;
+
+    // Affected Agents (top 20)
+    current_layer.use_text("Top Affected Agents:", 10.0, Mm(10.0), Mm(y_pos), &font_bold);
+    y_pos -= 10.0;
+    for agent in result.affected_agents.iter().take(20) {
+        if y_pos < 50.0 { break; }
+        current_layer.use_text(&format!("  {}: {} blocked / {} total", agent.agent_id, agent.would_block, agent.total_requests), 9.0, Mm(10.0), Mm(y_pos), &font_reg);
+        y_pos -= 8.0;
+    }
+
+    // Footer
+    current_layer.set_fill_color(Color::Rgb(Rgb::new(0.5, 0.5, 0.5, None)));
+    current_layer.use_text("Generated automatically by Veridion Nexus - Policy Simulator", 8.0, Mm(10.0), Mm(10.0), &font_reg);
+
+    doc.save(&mut BufWriter::new(File::create(output_path).map_err(|e| e.to_string())?))
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct RollbackRequest {
+    #[schema(example = 3)]
+    pub target_version: Option<i32>,
+    #[schema(example = "Reverting due to production issues")]
+    pub notes: Option<String>,
+    #[schema(example = false)]
+    pub dry_run: Option<bool>, // If true, simulate rollback without executing
+}
+
+/// Rollback a policy to a previous version
+#[utoipa::path(
+    post,
+    path = "/policies/{policy_id}/rollback",
+    tag = "Policy Management",
+    params(
+        ("policy_id" = String, Path, description = "Policy version ID to rollback to")
+    ),
+    request_body = RollbackRequest,
+    responses(
+        (status = 200, description = "Policy rolled back successfully"),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Policy not found"),
+    ),
+    security(
+        ("bearer" = [])
+    )
+)]
+pub async fn rollback_policy(
+    path: web::Path<String>,
+    req: web::Json<RollbackRequest>,
+    data: web::Data<AppState>,
+    http_req: HttpRequest,
+) -> impl Responder {
+    // AUTHENTICATION & AUTHORIZATION
+    let claims = match authenticate_and_authorize(&http_req, &data.db_pool, "policy", "write").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
+
+    let policy_id = path.into_inner();
+    let rollback_req = req.into_inner();
+
+    // Get current active policy
+    let current_policy: Option<(String, i32)> = sqlx::query_as(
+        "SELECT policy_type, version_number FROM policy_versions WHERE id = $1 AND is_active = true"
+    )
+    .bind(&policy_id)
+    .fetch_optional(&data.db_pool)
+    .await
+    .ok()
+    .flatten();
+
+    if current_policy.is_none() {
+        return HttpResponse::NotFound().json(serde_json::json!({
+            "error": "POLICY_NOT_FOUND",
+            "message": "Active policy not found"
+        }));
+    }
+
+    let (policy_type, _current_version) = current_policy.unwrap();
+    let is_dry_run = rollback_req.dry_run.unwrap_or(false);
+
+    // Get target version (or previous version if not specified)
+    let target_version = if let Some(version) = rollback_req.target_version {
+        version
+    } else {
+        // Get previous version
+        sqlx::query_scalar::<_, i32>(
+            "SELECT MAX(version_number) FROM policy_versions 
+             WHERE policy_type = $1 AND version_number < (SELECT version_number FROM policy_versions WHERE id = $2)"
+        )
+        .bind(&policy_type)
+        .bind(&policy_id)
+        .fetch_optional(&data.db_pool)
+        .await
+        .ok()
+        .flatten()
+        .unwrap_or(1)
+    };
+
+    // Check if target version exists
+    let target_policy: Option<(Uuid, String, i32)> = sqlx::query_as(
+        "SELECT id, policy_name, version_number FROM policy_versions 
+         WHERE policy_type = $1 AND version_number = $2"
+    )
+    .bind(&policy_type)
+    .bind(target_version)
+    .fetch_optional(&data.db_pool)
+    .await
+    .ok()
+    .flatten();
+
+    if target_policy.is_none() {
+        return HttpResponse::NotFound().json(serde_json::json!({
+            "error": "TARGET_VERSION_NOT_FOUND",
+            "message": format!("Version {} not found for policy type {}", target_version, policy_type)
+        }));
+    }
+
+    let (target_policy_id, target_policy_name, _) = target_policy.unwrap();
+
+    // If dry run, return simulation without executing
+    if is_dry_run {
+        // Get current policy metrics
+        let current_metrics: Option<(i64, i64, f64)> = sqlx::query_as(
+            "SELECT 
+                COUNT(*)::bigint as total,
+                COUNT(*) FILTER (WHERE status LIKE '%BLOCKED%')::bigint as blocked,
+                AVG(inference_time_ms) as avg_latency
+             FROM compliance_records 
+             WHERE agent_id IN (
+                 SELECT DISTINCT agent_id FROM compliance_records 
+                 WHERE timestamp >= CURRENT_TIMESTAMP - INTERVAL '7 days'
+             )
+             AND entry_type = 'ACTION'
+             AND timestamp >= CURRENT_TIMESTAMP - INTERVAL '7 days'"
+        )
+        .fetch_optional(&data.db_pool)
+        .await
+        .ok()
+        .flatten();
+
+        let (total_requests, blocked_requests, avg_latency) = current_metrics.unwrap_or((0, 0, 100.0));
+        let success_rate = if total_requests > 0 {
+            ((total_requests - blocked_requests) as f64 / total_requests as f64) * 100.0
+        } else {
+            100.0
+        };
 
-                return HttpResponse::Forbidden().json(serde_json::json!({
-                    "error": "SOVEREIGN_LOCK_VIOLATION",
-                    "message": format!("Data sovereignty violation: target server in {} is not in EU/EEA", country),
-                    "target_url": proxy_req.target_url,
-                    "detected_country": country,
-                    "status": "BLOCKED"
-                }));
-                }
-            }
-            // Store the detected country for logging allowed requests
-            Some(country)
-        }
-        Err(e) => {
-            // If we can't determine sovereignty, be conservative and block
-            log::warn!("Could not determine sovereignty for {}: {}", proxy_req.target_url, e);
-            
-            // Log the failed check attempt
-            let agent_id = req.headers()
-                .get("X-Agent-ID")
-                .and_then(|h| h.to_str().ok())
-                .unwrap_or("unknown")
-                .to_string();
-            
-            let user_id = crate::security::AuthService::new()
-                .ok()
-                .and_then(|auth_service| extract_claims(&req, &auth_service).ok())
-                .map(|c| c.sub);
-            
-            // Log compliance action
-            let record_id = uuid::Uuid::new_v4();
-            let seal_id = uuid::Uuid::new_v4().to_string();
-            let tx_id = uuid::Uuid::new_v4().to_string();
-            let action_summary = format!("PROXY_BLOCKED: Could not determine sovereignty for {}", proxy_req.target_url);
-            if let Err(e) = sqlx::query(
-                "INSERT INTO compliance_records (
-                    id, seal_id, tx_id, agent_id, action_summary, status, 
-                    risk_level, user_id, timestamp, payload_hash
-                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)"
+        return HttpResponse::Ok().json(serde_json::json!({
+            "status": "DRY_RUN",
+            "message": format!("Rollback simulation: Would rollback to version {} ({})", target_version, target_policy_name),
+            "target_policy_id": target_policy_id,
+            "target_policy_name": target_policy_name,
+            "target_version": target_version,
+            "current_policy_id": policy_id,
+            "estimated_rollback_time_ms": 50.0, // Estimated based on typical rollback
+            "estimated_rollback_time_sec": 0.05,
+            "estimated_sla_met": true,
+            "current_metrics": {
+                "total_requests": total_requests,
+                "blocked_requests": blocked_requests,
+                "success_rate": success_rate,
+                "avg_latency_ms": avg_latency
+            },
+            "note": "This is a simulation. No changes were made. Use dry_run=false to execute the rollback."
+        }));
+    }
+
+    // Track rollback start time for speed measurement
+    let rollback_start = std::time::Instant::now();
+
+    // Deactivate current policy
+    let _ = sqlx::query(
+        "UPDATE policy_versions SET is_active = false, deactivated_at = CURRENT_TIMESTAMP WHERE id = $1"
+    )
+    .bind(&policy_id)
+    .execute(&data.db_pool)
+    .await;
+
+    // Activate target version
+    let result = sqlx::query(
+        "UPDATE policy_versions 
+         SET is_active = true, activated_at = CURRENT_TIMESTAMP 
+         WHERE policy_type = $1 AND version_number = $2
+         RETURNING id"
+    )
+    .bind(&policy_type)
+    .bind(target_version)
+    .fetch_optional(&data.db_pool)
+    .await;
+
+    // Calculate rollback duration
+    let rollback_duration_ms = rollback_start.elapsed().as_millis() as f64;
+    let rollback_duration_sec = rollback_duration_ms / 1000.0;
+    let sla_met = rollback_duration_sec < 30.0; // < 30 seconds SLA
+
+    match result {
+        Ok(Some(row)) => {
+            let new_policy_id: Uuid = row.get(0);
+
+            // Log rollback in history with speed metrics
+            let _ = sqlx::query(
+                "INSERT INTO policy_activation_history (policy_version_id, action, performed_by, previous_version_id, notes)
+                 VALUES ($1, 'ROLLED_BACK', $2, $3, $4)"
             )
-            .bind(record_id)
-            .bind(&seal_id)
-            .bind(&tx_id)
-            .bind(&agent_id)
-            .bind(&action_summary)
-            .bind("BLOCKED (SOVEREIGNTY)")
-            .bind("HIGH")
-            .bind(user_id.as_deref())
-            .bind(chrono::Utc::now())
-            .bind("region:UNKNOWN")
+            .bind(new_policy_id)
+            .bind(&claims.sub)
+            .bind(Uuid::parse_str(&policy_id).ok())
+            .bind(rollback_req.notes)
             .execute(&data.db_pool)
-            .await {
-                log::error!("Failed to log proxy block: {}", e);
-            }
-            
-            return HttpResponse::Forbidden().json(serde_json::json!({
-                "error": "SOVEREIGNTY_CHECK_FAILED",
-                "message": "Could not verify data sovereignty. Request blocked for safety.",
-                "target_url": proxy_req.target_url,
-                "detected_country": "UNKNOWN",
-                "status": "BLOCKED"
-            }));
-        }
-    };
+            .await;
 
-    // 2. Forward request to target
-    match proxy_service.forward_request(&proxy_req).await {
-        Ok(response) => {
-            let status = response.status();
-            let headers = response.headers().clone();
-            
-            // Get response body
-            let body_bytes = match response.bytes().await {
-                Ok(bytes) => bytes,
-                Err(e) => {
-                    log_error_safely("reading proxy response", &e, &generate_request_id());
-                    return HttpResponse::InternalServerError().json(serde_json::json!({
-                        "error": "Failed to read response from target server"
-                    }));
-                }
-            };
+            // Log rollback speed metrics
+            let _ = sqlx::query(
+                "INSERT INTO policy_rollback_metrics (policy_version_id, rollback_duration_ms, sla_met, performed_by)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (policy_version_id) DO UPDATE SET
+                   rollback_duration_ms = EXCLUDED.rollback_duration_ms,
+                   sla_met = EXCLUDED.sla_met,
+                   performed_by = EXCLUDED.performed_by,
+                   updated_at = CURRENT_TIMESTAMP"
+            )
+            .bind(new_policy_id)
+            .bind(rollback_duration_ms)
+            .bind(sla_met)
+            .bind(&claims.sub)
+            .execute(&data.db_pool)
+            .await;
 
-            // 3. Log successful proxy action (async, non-blocking)
-            let db_pool = data.db_pool.clone();
-            let target_url = proxy_req.target_url.clone();
-            let country_for_log = detected_country.unwrap_or_else(|| "UNKNOWN".to_string());
-            let agent_id = req.headers()
-                .get("X-Agent-ID")
-                .and_then(|h| h.to_str().ok())
-                .unwrap_or("unknown")
-                .to_string();
-            
-            tokio::spawn(async move {
-                let record_id = uuid::Uuid::new_v4();
-                let seal_id = uuid::Uuid::new_v4().to_string();
-                let tx_id = uuid::Uuid::new_v4().to_string();
-                let action_summary = format!("PROXY_ALLOWED: {} ({})", target_url, country_for_log);
-                
-                // Track canary metrics (if policy has rollout_percentage)
-                if let Some(pv_id) = policy_version_id {
-                    let rollout_pct = rollout_percentage;
-                    let now = chrono::Utc::now();
-                    let window_start = now - chrono::Duration::minutes(10);
-                    
-                    let _ = sqlx::query(
-                        "INSERT INTO canary_metrics (
-                            policy_version_id, traffic_percentage, total_requests,
-                            successful_requests, failed_requests, blocked_requests,
-                            success_rate, window_start, window_end
-                        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
-                        ON CONFLICT (policy_version_id, traffic_percentage, window_start) 
-                        DO UPDATE SET
-                            total_requests = canary_metrics.total_requests + 1,
-                            successful_requests = canary_metrics.successful_requests + 1,
-                            success_rate = ((canary_metrics.successful_requests + 1)::DECIMAL / 
-                                           (canary_metrics.total_requests + 1)::DECIMAL) * 100.0,
-                            updated_at = CURRENT_TIMESTAMP"
-                    )
-                    .bind(pv_id)
-                    .bind(rollout_pct)
-                    .bind(1)
-                    .bind(1)
-                    .bind(0)
-                    .bind(0)
-                    .bind(100.0)
-                    .bind(window_start)
-                    .bind(now)
-                    .execute(&db_pool)
-                    .await;
-                    
-                    // Check if should auto-promote
-                    let should_promote: Option<bool> = sqlx::query_scalar(
-                        "SELECT should_promote_canary($1)"
-                    )
-                    .bind(pv_id)
-                    .fetch_optional(&db_pool)
-                    .await
-                    .ok()
-                    .flatten();
-                    
-                    if should_promote.unwrap_or(false) {
-                        // Auto-promote to next percentage tier
-                        let next_percentage = match rollout_pct {
-                            1 => 5,
-                            5 => 10,
-                            10 => 25,
-                            25 => 50,
-                            50 => 100,
-                            _ => 100,
-                        };
-                        
-                        let success_rate: Option<f64> = sqlx::query_scalar(
-                            "SELECT calculate_canary_success_rate($1, $2, 10)"
-                        )
-                        .bind(pv_id)
-                        .bind(rollout_pct)
-                        .fetch_optional(&db_pool)
-                        .await
-                        .ok()
-                        .flatten();
-                        
-                        let _ = sqlx::query(
-                            "UPDATE policy_versions 
-                             SET rollout_percentage = $1
-                             WHERE id = $2"
-                        )
-                        .bind(next_percentage)
-                        .bind(pv_id)
-                        .execute(&db_pool)
-                        .await;
-                        
-                        // Log promotion
-                        let _ = sqlx::query(
-                            "INSERT INTO canary_deployment_history (
-                                policy_version_id, action, from_percentage, to_percentage,
-                                success_rate, triggered_by, notes
-                            ) VALUES ($1, $2, $3, $4, $5, $6, $7)"
-                        )
-                        .bind(pv_id)
-                        .bind("PROMOTED")
-                        .bind(rollout_pct)
-                        .bind(next_percentage)
-                        .bind(success_rate.unwrap_or(0.0))
-                        .bind("AUTO")
-                        .bind(format!("Auto-promoted from {}% to {}% based on success rate", rollout_pct, next_percentage))
-                        .execute(&db_pool)
-                        .await;
-                        
-                        log::info!("CANARY AUTO-PROMOTED: Policy {} from {}% to {}%", pv_id, rollout_pct, next_percentage);
-                    }
-                }
-                
-                if let Err(e) = sqlx::query(
-                    "INSERT INTO compliance_records (
-                        id, seal_id, tx_id, agent_id, action_summary, status, 
-                        risk_level, timestamp, payload_hash
-                    ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"
-                )
-                .bind(record_id)
-                .bind(&seal_id)
-                .bind(&tx_id)
-                .bind(&agent_id)
-                .bind(&action_summary)
-                .bind("COMPLIANT")
-                .bind("LOW")
-                .bind(chrono::Utc::now())
-                .bind(&format!("region:{}", country_for_log))
-                .execute(&db_pool)
-                .await {
-                    log::error!("Failed to log proxy success: {}", e);
-                }
-            });
+            HttpResponse::Ok().json(serde_json::json!({
+                "status": "SUCCESS",
+                "message": format!("Policy rolled back to version {} in {:.2}s", target_version, rollback_duration_sec),
+                "new_policy_id": new_policy_id,
+                "previous_policy_id": policy_id,
+                "rollback_duration_ms": rollback_duration_ms,
+                "rollback_duration_sec": rollback_duration_sec,
+                "sla_met": sla_met,
+                "sla_threshold_sec": 30.0
+            }))
+        }
+        Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "TARGET_VERSION_NOT_FOUND",
+            "message": format!("Version {} not found for policy type {}", target_version, policy_type)
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": "ROLLBACK_FAILED",
+            "message": format!("Database error: {}", e)
+        }))
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct PublishJurisdictionPolicyRequest {
+    /// Tenant the policy applies to; omit for the global policy
+    pub company_id: Option<Uuid>,
+    #[schema(example = "EU + adequacy countries")]
+    pub policy_name: Option<String>,
+    pub policy: crate::core::jurisdiction_policy::JurisdictionPolicy,
+}
 
-            // Build response with original headers and body
-            let mut http_response = HttpResponse::build(status);
-            
-            // Copy relevant headers (exclude connection, content-encoding, etc.)
-            for (key, value) in headers.iter() {
-                let key_str = key.as_str();
-                if !matches!(key_str, "connection" | "content-encoding" | "transfer-encoding" | "content-length") {
-                    if let Ok(val_str) = value.to_str() {
-                        http_response.append_header((key_str, val_str));
-                    }
-                }
-            }
+/// List jurisdiction policy versions
+#[utoipa::path(
+    get,
+    path = "/jurisdiction_policies",
+    tag = "Policy Management",
+    params(
+        ("company_id" = Option<String>, Query, description = "Tenant ID; global policy versions when omitted")
+    ),
+    responses(
+        (status = 200, description = "Policy versions, newest first"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+    ),
+    security(
+        ("bearer" = [])
+    )
+)]
+pub async fn list_jurisdiction_policies(
+    query: web::Query<HashMap<String, String>>,
+    data: web::Data<AppState>,
+    http_req: HttpRequest,
+) -> impl Responder {
+    // AUTHENTICATION & AUTHORIZATION
+    if let Err(resp) = authenticate_and_authorize(&http_req, &data.db_pool, "policy", "read").await {
+        return resp;
+    }
 
-            http_response.body(body_bytes.to_vec())
-        }
+    let company_id = match query.get("company_id").map(|s| Uuid::parse_str(s)) {
+        Some(Ok(id)) => Some(id),
+        Some(Err(_)) => return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Invalid input",
+            "message": "company_id must be a UUID"
+        })),
+        None => None,
+    };
+
+    #[derive(sqlx::FromRow, Serialize)]
+    struct JurisdictionPolicyVersion {
+        id: Uuid,
+        policy_name: Option<String>,
+        version_number: i32,
+        is_active: bool,
+        policy_config: serde_json::Value,
+        created_by: Option<String>,
+        created_at: chrono::NaiveDateTime,
+        activated_at: Option<chrono::NaiveDateTime>,
+    }
+
+    let versions: Result<Vec<JurisdictionPolicyVersion>, _> = sqlx::query_as(
+        "SELECT id, policy_name, version_number, is_active, policy_config,
+                created_by, created_at, activated_at
+         FROM policy_versions
+         WHERE policy_type = $1 AND company_id IS NOT DISTINCT FROM $2
+         ORDER BY version_number DESC"
+    )
+    .bind(crate::core::jurisdiction_policy::JURISDICTION_POLICY_TYPE)
+    .bind(company_id)
+    .fetch_all(&data.db_pool)
+    .await;
+
+    match versions {
+        Ok(versions) => HttpResponse::Ok().json(serde_json::json!({
+            "company_id": company_id,
+            "versions": versions
+        })),
         Err(e) => {
             let request_id = generate_request_id();
-            log_error_safely("forwarding proxy request", &e, &request_id);
-            HttpResponse::BadGateway().json(serde_json::json!({
-                "error": "PROXY_ERROR",
-                "message": "Failed to forward request to target server",
-                "request_id": request_id
-            }))
+            log_error_safely("listing jurisdiction policies", &e, &request_id);
+            create_error_response(&request_id)
         }
     }
 }
 
-// ========== POLICY SIMULATION & OPERATIONAL SAFETY ==========
-
-/// Simulate policy change impact before enforcement
+/// Publish a new jurisdiction policy version
+/// 
+/// Stores the policy as the next version for the tenant and activates it.
+/// Earlier versions stay available for `/jurisdiction_policies/{policy_id}/activate`.
 #[utoipa::path(
     post,
-    path = "/policies/simulate",
+    path = "/jurisdiction_policies",
     tag = "Policy Management",
-    request_body = SimulationRequest,
+    request_body = PublishJurisdictionPolicyRequest,
     responses(
-        (status = 200, description = "Simulation result", body = SimulationResult),
-        (status = 400, description = "Invalid request"),
+        (status = 201, description = "Policy version published and active"),
+        (status = 400, description = "Invalid policy"),
         (status = 401, description = "Unauthorized"),
         (status = 403, description = "Forbidden"),
     ),
@@ -6863,275 +9371,314 @@ pub async fn proxy_request(
         ("bearer" = [])
     )
 )]
-pub async fn simulate_policy(
-    req: web::Json<crate::core::policy_simulator::SimulationRequest>,
+pub async fn publish_jurisdiction_policy(
+    req: web::Json<PublishJurisdictionPolicyRequest>,
     data: web::Data<AppState>,
     http_req: HttpRequest,
 ) -> impl Responder {
     // AUTHENTICATION & AUTHORIZATION
-    let claims = match authenticate_and_authorize(&http_req, &data.db_pool, "policy", "read").await {
+    let claims = match authenticate_and_authorize(&http_req, &data.db_pool, "policy", "write").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
 
-    // Run simulation
-    match crate::core::policy_simulator::PolicySimulator::simulate(
-        &data.db_pool,
-        req.into_inner(),
-    )
-    .await
-    {
-        Ok(result) => {
-            // Cache the result
+    if let Err(message) = req.policy.validate() {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "INVALID_POLICY",
+            "message": message
+        }));
+    }
+
+    let policy_name = req.policy_name.clone().unwrap_or_else(|| "Jurisdiction policy".to_string());
+    match req.policy.publish(&data.db_pool, req.company_id, &policy_name, &claims.sub).await {
+        Ok((policy_id, version_number)) => {
             let _ = sqlx::query(
-                "INSERT INTO policy_simulation_results (policy_type, policy_config, simulation_result, time_range_days, simulated_by)
-                 VALUES ($1, $2, $3, $4, $5)"
+                "INSERT INTO policy_activation_history (policy_version_id, action, performed_by, notes)
+                 VALUES ($1, 'ACTIVATED', $2, $3)"
             )
-            .bind(format!("{:?}", result.policy_type))
-            .bind(serde_json::json!({}))
-            .bind(serde_json::to_value(&result).unwrap_or(serde_json::Value::Null))
-            .bind(result.total_requests)
+            .bind(policy_id)
             .bind(&claims.sub)
+            .bind(format!("Jurisdiction policy version {} published", version_number))
             .execute(&data.db_pool)
             .await;
 
-            HttpResponse::Ok().json(result)
+            HttpResponse::Created().json(serde_json::json!({
+                "status": "ACTIVE",
+                "policy_id": policy_id,
+                "version_number": version_number,
+                "company_id": req.company_id
+            }))
+        }
+        Err(e) => {
+            let request_id = generate_request_id();
+            log_error_safely("publishing jurisdiction policy", &e, &request_id);
+            create_error_response(&request_id)
         }
-        Err(e) => HttpResponse::BadRequest().json(serde_json::json!({
-            "error": "SIMULATION_FAILED",
-            "message": e
-        }))
     }
 }
 
-/// Export simulation report
+/// Activate an earlier jurisdiction policy version
 #[utoipa::path(
     post,
-    path = "/policies/simulate/export",
-    tag = "Policy Simulator",
-    request_body = crate::core::policy_simulator::SimulationRequest,
+    path = "/jurisdiction_policies/{policy_id}/activate",
+    tag = "Policy Management",
     params(
-        ("format" = Option<String>, Query, description = "Export format: json, csv, pdf, or text (default: json)"),
+        ("policy_id" = String, Path, description = "Jurisdiction policy version ID")
     ),
     responses(
-        (status = 200, description = "Simulation report exported"),
+        (status = 200, description = "Version activated"),
+        (status = 404, description = "Jurisdiction policy version not found"),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
     ),
     security(
         ("bearer" = [])
     )
 )]
-pub async fn export_simulation_report(
-    req: web::Json<crate::core::policy_simulator::SimulationRequest>,
-    query: web::Query<std::collections::HashMap<String, String>>,
+pub async fn activate_jurisdiction_policy(
+    path: web::Path<String>,
     data: web::Data<AppState>,
     http_req: HttpRequest,
 ) -> impl Responder {
     // AUTHENTICATION & AUTHORIZATION
-    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "policy", "read").await {
+    let claims = match authenticate_and_authorize(&http_req, &data.db_pool, "policy", "write").await {
         Ok(c) => c,
         Err(resp) => return resp,
     };
 
-    let format = query.get("format")
-        .map(|s| s.to_lowercase())
-        .unwrap_or_else(|| "json".to_string());
-
-    // Run simulation
-    let result = match crate::core::policy_simulator::PolicySimulator::simulate(
-        &data.db_pool,
-        req.into_inner(),
-    )
-    .await
-    {
-        Ok(r) => r,
-        Err(e) => return HttpResponse::BadRequest().json(serde_json::json!({
-            "error": "SIMULATION_FAILED",
-            "message": e
-        }))
+    let policy_id = match Uuid::parse_str(&path.into_inner()) {
+        Ok(id) => id,
+        Err(_) => return HttpResponse::NotFound().json(serde_json::json!({
+            "error": "POLICY_NOT_FOUND",
+            "message": "Jurisdiction policy version not found"
+        })),
     };
 
-    match format.as_str() {
-        "json" => {
-            HttpResponse::Ok()
-                .content_type("application/json")
-                .append_header(("Content-Disposition", format!("attachment; filename=\"simulation_report_{}.json\"", chrono::Utc::now().format("%Y%m%d_%H%M%S"))))
-                .json(&result)
+    match crate::core::jurisdiction_policy::JurisdictionPolicy::activate_version(&data.db_pool, policy_id).await {
+        Ok(true) => {
+            let _ = sqlx::query(
+                "INSERT INTO policy_activation_history (policy_version_id, action, performed_by, notes)
+                 VALUES ($1, 'ACTIVATED', $2, 'Jurisdiction policy version re-activated')"
+            )
+            .bind(policy_id)
+            .bind(&claims.sub)
+            .execute(&data.db_pool)
+            .await;
+
+            HttpResponse::Ok().json(serde_json::json!({
+                "status": "ACTIVE",
+                "policy_id": policy_id
+            }))
         }
-        "csv" => {
-            let mut csv = String::from("policy_type,total_requests,would_block,would_allow,impact_level,simulation_timestamp\n");
-            csv.push_str(&format!(
-                "{:?},{},{},{},{:?},{}\n",
-                result.policy_type,
-                result.total_requests,
-                result.would_block,
-                result.would_allow,
-                result.estimated_impact,
-                result.simulation_timestamp.to_rfc3339(),
-            ));
-            csv.push_str("\n# Affected Agents\nagent_id,total_requests,would_block,would_allow\n");
-            for agent in &result.affected_agents {
-                csv.push_str(&format!(
-                    "{},{},{},{}\n",
-                    escape_csv(&agent.agent_id),
-                    agent.total_requests,
-                    agent.would_block,
-                    agent.would_allow,
-                ));
-            }
-            csv.push_str("\n# Affected Endpoints\nendpoint,count\n");
-            for (endpoint, count) in &result.affected_endpoints {
-                csv.push_str(&format!("{},{}\n", escape_csv(endpoint), count));
-            }
-            csv.push_str("\n# Requests by Country\ncountry,count\n");
-            for (country, count) in &result.requests_by_country {
-                csv.push_str(&format!("{},{}\n", escape_csv(country), count));
-            }
-            HttpResponse::Ok()
-                .content_type("text/csv")
-                .append_header(("Content-Disposition", format!("attachment; filename=\"simulation_report_{}.csv\"", chrono::Utc::now().format("%Y%m%d_%H%M%S"))))
-                .body(csv)
+        Ok(false) => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "POLICY_NOT_FOUND",
+            "message": "Jurisdiction policy version not found"
+        })),
+        Err(e) => {
+            let request_id = generate_request_id();
+            log_error_safely("activating jurisdiction policy", &e, &request_id);
+            create_error_response(&request_id)
         }
-        "text" => {
-            let mut text = String::new();
-            text.push_str(&format!("POLICY SIMULATION REPORT\n"));
-            text.push_str(&format!("Generated: {}\n\n", chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC")));
-            text.push_str(&format!("Policy Type: {:?}\n", result.policy_type));
-            text.push_str(&format!("Total Requests: {}\n", result.total_requests));
-            text.push_str(&format!("Would Block: {}\n", result.would_block));
-            text.push_str(&format!("Would Allow: {}\n", result.would_allow));
-            text.push_str(&format!("Impact Level: {:?}\n\n", result.estimated_impact));
-            text.push_str(&format!("Affected Agents: {}\n", result.affected_agents.len()));
-            for agent in &result.affected_agents {
-                text.push_str(&format!("  - {}: {} blocked / {} total\n", agent.agent_id, agent.would_block, agent.total_requests));
-            }
-            text.push_str(&format!("\nAffected Endpoints: {}\n", result.affected_endpoints.len()));
-            for (endpoint, count) in &result.affected_endpoints {
-                text.push_str(&format!("  - {}: {} requests\n", endpoint, count));
-            }
-            text.push_str(&format!("\nRequests by Country: {}\n", result.requests_by_country.len()));
-            for (country, count) in &result.requests_by_country {
-                text.push_str(&format!("  - {}: {} requests\n", country, count));
-            }
-            HttpResponse::Ok()
-                .content_type("text/plain")
-                .append_header(("Content-Disposition", format!("attachment; filename=\"simulation_report_{}.txt\"", chrono::Utc::now().format("%Y%m%d_%H%M%S"))))
-                .body(text)
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct ImportPolicyBundleRequest {
+    /// Bundle files by relative path, e.g. `policies/sovereign-lock/global.json`
+    pub files: std::collections::BTreeMap<String, String>,
+    /// Only validate and diff; nothing is stored
+    #[serde(default)]
+    pub dry_run: bool,
+    pub notes: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct RollbackPolicyBundleRequest {
+    pub reason: Option<String>,
+}
+
+fn policy_bundle_error_response(context: &str, error: crate::core::policy_bundle::BundleError) -> HttpResponse {
+    use crate::core::policy_bundle::BundleError;
+    let message = error.to_string();
+    match error {
+        BundleError::NotFound => HttpResponse::NotFound().json(serde_json::json!({
+            "error": "IMPORT_NOT_FOUND",
+            "message": message
+        })),
+        BundleError::NotApproved { .. } => HttpResponse::Conflict().json(serde_json::json!({
+            "error": "APPROVAL_REQUIRED",
+            "message": message
+        })),
+        BundleError::Rejected | BundleError::WrongStatus(_) => HttpResponse::Conflict().json(serde_json::json!({
+            "error": "INVALID_IMPORT_STATUS",
+            "message": message
+        })),
+        BundleError::StateChanged => HttpResponse::Conflict().json(serde_json::json!({
+            "error": "POLICY_STATE_CHANGED",
+            "message": message
+        })),
+        BundleError::Invalid(_) => HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Invalid input",
+            "message": message
+        })),
+        BundleError::Database(e) => {
+            let request_id = generate_request_id();
+            log_error_safely(context, &e, &request_id);
+            create_error_response(&request_id)
         }
-        "pdf" => {
-            let temp_file = format!("temp_simulation_export_{}.pdf", uuid::Uuid::new_v4().to_string().replace("-", ""));
-            match generate_simulation_pdf(&result, &temp_file) {
-                Ok(_) => {
-                    match std::fs::read(&temp_file) {
-                        Ok(pdf_bytes) => {
-                            let _ = std::fs::remove_file(&temp_file);
-                            HttpResponse::Ok()
-                                .content_type("application/pdf")
-                                .append_header(("Content-Disposition", format!("attachment; filename=\"simulation_report_{}.pdf\"", chrono::Utc::now().format("%Y%m%d_%H%M%S"))))
-                                .body(pdf_bytes)
-                        }
-                        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
-                            "error": "PDF_READ_FAILED",
-                            "message": format!("Failed to read PDF file: {}", e)
-                        }))
-                    }
-                }
-                Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
-                    "error": "PDF_GENERATION_FAILED",
-                    "message": format!("Failed to generate PDF: {}", e)
-                }))
-            }
+    }
+}
+
+/// Export the live policies as a bundle
+///
+/// Active policy versions, asset policies, jurisdiction policies and the
+/// enforcement mode as deterministic JSON files, keyed by relative path.
+#[utoipa::path(
+    get,
+    path = "/policy_bundle",
+    tag = "Policy Management",
+    responses(
+        (status = 200, description = "Bundle digest and files"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+    ),
+    security(
+        ("bearer" = [])
+    )
+)]
+pub async fn export_policy_bundle(
+    data: web::Data<AppState>,
+    http_req: HttpRequest,
+) -> impl Responder {
+    // AUTHENTICATION & AUTHORIZATION
+    if let Err(resp) = authenticate_and_authorize(&http_req, &data.db_pool, "policy", "read").await {
+        return resp;
+    }
+
+    match crate::core::policy_bundle::PolicyBundles::export(&data.db_pool).await {
+        Ok((digest, files)) => HttpResponse::Ok().json(serde_json::json!({
+            "digest": digest,
+            "files": files
+        })),
+        Err(e) => {
+            let request_id = generate_request_id();
+            log_error_safely("exporting policy bundle", &e, &request_id);
+            create_error_response(&request_id)
         }
-        _ => HttpResponse::BadRequest().json(serde_json::json!({
-            "error": "INVALID_FORMAT",
-            "message": "Format must be json, csv, pdf, or text"
-        }))
     }
 }
 
-/// Generate PDF report for simulation results
-fn generate_simulation_pdf(result: &crate::core::policy_simulator::SimulationResult, output_path: &str) -> Result<(), String> {
-    use printpdf::*;
-    use std::fs::File;
-    use std::io::BufWriter;
-
-    let (doc, page1, layer1) = PdfDocument::new("Policy Simulation Report", Mm(210.0), Mm(297.0), "Layer 1");
-    let current_layer = doc.get_page(page1).get_layer(layer1);
-
-    let font_bold = doc.add_builtin_font(BuiltinFont::HelveticaBold)
-        .map_err(|e| format!("Failed to add font: {:?}", e))?;
-    let font_reg = doc.add_builtin_font(BuiltinFont::Helvetica)
-        .map_err(|e| format!("Failed to add font: {:?}", e))?;
-
-    // Header
-    current_layer.set_fill_color(Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None)));
-    current_layer.use_text("VERIDION NEXUS | POLICY SIMULATION REPORT", 18.0, Mm(10.0), Mm(280.0), &font_bold);
-    current_layer.use_text(&format!("Generated: {}", Utc::now().format("%Y-%m-%d %H:%M:%S UTC")), 10.0, Mm(10.0), Mm(270.0), &font_reg);
-
-    // Summary
-    let mut y_pos = 250.0;
-    current_layer.use_text(&format!("Policy Type: {:?}", result.policy_type), 10.0, Mm(10.0), Mm(y_pos), &font_reg);
-    y_pos -= 10.0;
-    current_layer.use_text(&format!("Total Requests: {}", result.total_requests), 10.0, Mm(10.0), Mm(y_pos), &font_reg);
-    y_pos -= 10.0;
-    current_layer.use_text(&format!("Would Block: {}", result.would_block), 10.0, Mm(10.0), Mm(y_pos), &font_reg);
-    y_pos -= 10.0;
-    current_layer.use_text(&format!("Would Allow: {}", result.would_allow), 10.0, Mm(10.0), Mm(y_pos), &font_reg);
-    y_pos -= 10.0;
-    current_layer.use_text(&format!("Impact Level: {:?}", result.estimated_impact), 10.0, Mm(10.0), Mm(y_pos), &font_reg);
-    y_pos -= 20.0;
+/// Import a policy bundle
+///
+/// Validates the bundle and diffs it against the live state. Unless `dry_run`
+/// is set, the import is stored pending approval: approve it with
+/// `/policies/{import_id}/approve`, then apply it with
+/// `/policy_bundle/imports/{import_id}/apply`.
+#[utoipa::path(
+    post,
+    path = "/policy_bundle/import",
+    tag = "Policy Management",
+    request_body = ImportPolicyBundleRequest,
+    responses(
+        (status = 200, description = "Dry run, or bundle matches the live state"),
+        (status = 202, description = "Import stored pending approval", body = crate::core::policy_bundle::PolicyBundleImport),
+        (status = 400, description = "Invalid bundle"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+    ),
+    security(
+        ("bearer" = [])
+    )
+)]
+pub async fn import_policy_bundle(
+    req: web::Json<ImportPolicyBundleRequest>,
+    data: web::Data<AppState>,
+    http_req: HttpRequest,
+) -> impl Responder {
+    // AUTHENTICATION & AUTHORIZATION
+    let claims = match authenticate_and_authorize(&http_req, &data.db_pool, "policy", "write").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
 
-    // Affected Agents (top 20)
-    current_layer.use_text("Top Affected Agents:", 10.0, Mm(10.0), Mm(y_pos), &font_bold);
-    y_pos -= 10.0;
-    for agent in result.affected_agents.iter().take(20) {
-        if y_pos < 50.0 { break; }
-        current_layer.use_text(&format!("  {}: {} blocked / {} total", agent.agent_id, agent.would_block, agent.total_requests), 9.0, Mm(10.0), Mm(y_pos), &font_reg);
-        y_pos -= 8.0;
+    let plan = match crate::core::policy_bundle::PolicyBundles::plan(&data.db_pool, &req.files).await {
+        Ok(plan) => plan,
+        Err(e) => return policy_bundle_error_response("planning policy bundle import", e),
+    };
+    if req.dry_run || plan.changes.is_empty() {
+        return HttpResponse::Ok().json(serde_json::json!({
+            "status": if req.dry_run { "DRY_RUN" } else { "NO_CHANGES" },
+            "plan": plan
+        }));
     }
 
-    // Footer
-    current_layer.set_fill_color(Color::Rgb(Rgb::new(0.5, 0.5, 0.5, None)));
-    current_layer.use_text("Generated automatically by Veridion Nexus - Policy Simulator", 8.0, Mm(10.0), Mm(10.0), &font_reg);
-
-    doc.save(&mut BufWriter::new(File::create(output_path).map_err(|e| e.to_string())?))
-        .map_err(|e| e.to_string())?;
-    Ok(())
+    match crate::core::policy_bundle::PolicyBundles::submit(&data.db_pool, &plan, &claims.sub, req.notes.as_deref()).await {
+        Ok(import) => HttpResponse::Accepted().json(import),
+        Err(e) => {
+            let request_id = generate_request_id();
+            log_error_safely("storing policy bundle import", &e, &request_id);
+            create_error_response(&request_id)
+        }
+    }
 }
 
-#[derive(Deserialize, ToSchema)]
-pub struct RollbackRequest {
-    #[schema(example = 3)]
-    pub target_version: Option<i32>,
-    #[schema(example = "Reverting due to production issues")]
-    pub notes: Option<String>,
-    #[schema(example = false)]
-    pub dry_run: Option<bool>, // If true, simulate rollback without executing
+/// List policy bundle imports
+#[utoipa::path(
+    get,
+    path = "/policy_bundle/imports",
+    tag = "Policy Management",
+    responses(
+        (status = 200, description = "Latest 100 imports, newest first"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+    ),
+    security(
+        ("bearer" = [])
+    )
+)]
+pub async fn list_policy_bundle_imports(
+    data: web::Data<AppState>,
+    http_req: HttpRequest,
+) -> impl Responder {
+    // AUTHENTICATION & AUTHORIZATION
+    if let Err(resp) = authenticate_and_authorize(&http_req, &data.db_pool, "policy", "read").await {
+        return resp;
+    }
+
+    match crate::core::policy_bundle::PolicyBundles::list(&data.db_pool, 100).await {
+        Ok(imports) => HttpResponse::Ok().json(serde_json::json!({ "imports": imports })),
+        Err(e) => {
+            let request_id = generate_request_id();
+            log_error_safely("listing policy bundle imports", &e, &request_id);
+            create_error_response(&request_id)
+        }
+    }
 }
 
-/// Rollback a policy to a previous version
+/// Apply an approved policy bundle import
+///
+/// Publishes the changed policies as new versions. Fails with 409 if the
+/// import lacks approvals or the live policies changed since it was diffed.
 #[utoipa::path(
     post,
-    path = "/policies/{policy_id}/rollback",
+    path = "/policy_bundle/imports/{import_id}/apply",
     tag = "Policy Management",
     params(
-        ("policy_id" = String, Path, description = "Policy version ID to rollback to")
+        ("import_id" = String, Path, description = "Import ID")
     ),
-    request_body = RollbackRequest,
     responses(
-        (status = 200, description = "Policy rolled back successfully"),
-        (status = 400, description = "Invalid request"),
+        (status = 200, description = "Import applied"),
+        (status = 404, description = "Import not found"),
+        (status = 409, description = "Not approved, not pending, or policies changed"),
         (status = 401, description = "Unauthorized"),
         (status = 403, description = "Forbidden"),
-        (status = 404, description = "Policy not found"),
     ),
     security(
         ("bearer" = [])
     )
 )]
-pub async fn rollback_policy(
+pub async fn apply_policy_bundle_import(
     path: web::Path<String>,
-    req: web::Json<RollbackRequest>,
     data: web::Data<AppState>,
     http_req: HttpRequest,
 ) -> impl Responder {
@@ -7141,195 +9688,340 @@ pub async fn rollback_policy(
         Err(resp) => return resp,
     };
 
-    let policy_id = path.into_inner();
-    let rollback_req = req.into_inner();
+    let import_id = match Uuid::parse_str(&path.into_inner()) {
+        Ok(id) => id,
+        Err(_) => return policy_bundle_error_response("applying policy bundle import", crate::core::policy_bundle::BundleError::NotFound),
+    };
+    match crate::core::policy_bundle::PolicyBundles::apply(&data.db_pool, import_id, &claims.sub).await {
+        Ok((import, summary)) => HttpResponse::Ok().json(serde_json::json!({
+            "import": import,
+            "summary": summary
+        })),
+        Err(e) => policy_bundle_error_response("applying policy bundle import", e),
+    }
+}
 
-    // Get current active policy
-    let current_policy: Option<(String, i32)> = sqlx::query_as(
-        "SELECT policy_type, version_number FROM policy_versions WHERE id = $1 AND is_active = true"
+/// Roll back an applied policy bundle import
+///
+/// Restores the policies the import replaced, as long as they have not been
+/// changed since. Listed in `/analytics/rollback-history`.
+#[utoipa::path(
+    post,
+    path = "/policy_bundle/imports/{import_id}/rollback",
+    tag = "Policy Management",
+    params(
+        ("import_id" = String, Path, description = "Import ID")
+    ),
+    request_body = Option<RollbackPolicyBundleRequest>,
+    responses(
+        (status = 200, description = "Import rolled back"),
+        (status = 404, description = "Import not found"),
+        (status = 409, description = "Not applied, or policies changed since"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+    ),
+    security(
+        ("bearer" = [])
     )
-    .bind(&policy_id)
-    .fetch_optional(&data.db_pool)
-    .await
-    .ok()
-    .flatten();
+)]
+pub async fn rollback_policy_bundle_import(
+    path: web::Path<String>,
+    req: Option<web::Json<RollbackPolicyBundleRequest>>,
+    data: web::Data<AppState>,
+    http_req: HttpRequest,
+) -> impl Responder {
+    // AUTHENTICATION & AUTHORIZATION
+    let claims = match authenticate_and_authorize(&http_req, &data.db_pool, "policy", "write").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
 
-    if current_policy.is_none() {
-        return HttpResponse::NotFound().json(serde_json::json!({
-            "error": "POLICY_NOT_FOUND",
-            "message": "Active policy not found"
-        }));
+    let import_id = match Uuid::parse_str(&path.into_inner()) {
+        Ok(id) => id,
+        Err(_) => return policy_bundle_error_response("rolling back policy bundle import", crate::core::policy_bundle::BundleError::NotFound),
+    };
+    let reason = req.as_ref().and_then(|r| r.reason.as_deref());
+    match crate::core::policy_bundle::PolicyBundles::rollback(&data.db_pool, import_id, &claims.sub, reason).await {
+        Ok((import, summary)) => HttpResponse::Ok().json(serde_json::json!({
+            "import": import,
+            "summary": summary
+        })),
+        Err(e) => policy_bundle_error_response("rolling back policy bundle import", e),
     }
+}
 
-    let (policy_type, _current_version) = current_policy.unwrap();
-    let is_dry_run = rollback_req.dry_run.unwrap_or(false);
-
-    // Get target version (or previous version if not specified)
-    let target_version = if let Some(version) = rollback_req.target_version {
-        version
-    } else {
-        // Get previous version
-        sqlx::query_scalar::<_, i32>(
-            "SELECT MAX(version_number) FROM policy_versions 
-             WHERE policy_type = $1 AND version_number < (SELECT version_number FROM policy_versions WHERE id = $2)"
-        )
-        .bind(&policy_type)
-        .bind(&policy_id)
-        .fetch_optional(&data.db_pool)
-        .await
-        .ok()
-        .flatten()
-        .unwrap_or(1)
-    };
+#[derive(Deserialize, ToSchema)]
+pub struct UpsertCloudRegionRequest {
+    #[schema(example = "AWS")]
+    pub provider: String,
+    #[schema(example = "eu-central-1")]
+    pub region_code: String,
+    /// ISO 3166-1 alpha-2 country code
+    #[schema(example = "DE")]
+    pub country: String,
+    /// EU/EEA or NON_EU; derived from the country when omitted
+    pub jurisdiction: Option<String>,
+    #[schema(example = "Frankfurt")]
+    pub location: Option<String>,
+}
 
-    // Check if target version exists
-    let target_policy: Option<(Uuid, String, i32)> = sqlx::query_as(
-        "SELECT id, policy_name, version_number FROM policy_versions 
-         WHERE policy_type = $1 AND version_number = $2"
+/// List the cloud region catalog
+/// 
+/// Built-in AWS, Azure and GCP regions plus the entries added through `PUT /region_catalog`.
+#[utoipa::path(
+    get,
+    path = "/region_catalog",
+    tag = "Policy Management",
+    responses(
+        (status = 200, description = "Cloud regions with their country and jurisdiction", body = Vec<crate::core::region_catalog::CloudRegion>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+    ),
+    security(
+        ("bearer" = [])
     )
-    .bind(&policy_type)
-    .bind(target_version)
-    .fetch_optional(&data.db_pool)
-    .await
-    .ok()
-    .flatten();
-
-    if target_policy.is_none() {
-        return HttpResponse::NotFound().json(serde_json::json!({
-            "error": "TARGET_VERSION_NOT_FOUND",
-            "message": format!("Version {} not found for policy type {}", target_version, policy_type)
-        }));
+)]
+pub async fn list_region_catalog(
+    data: web::Data<AppState>,
+    http_req: HttpRequest,
+) -> impl Responder {
+    // AUTHENTICATION & AUTHORIZATION
+    if let Err(resp) = authenticate_and_authorize(&http_req, &data.db_pool, "policy", "read").await {
+        return resp;
     }
 
-    let (target_policy_id, target_policy_name, _) = target_policy.unwrap();
-
-    // If dry run, return simulation without executing
-    if is_dry_run {
-        // Get current policy metrics
-        let current_metrics: Option<(i64, i64, f64)> = sqlx::query_as(
-            "SELECT 
-                COUNT(*)::bigint as total,
-                COUNT(*) FILTER (WHERE status LIKE '%BLOCKED%')::bigint as blocked,
-                AVG(inference_time_ms) as avg_latency
-             FROM compliance_records 
-             WHERE agent_id IN (
-                 SELECT DISTINCT agent_id FROM compliance_records 
-                 WHERE timestamp >= CURRENT_TIMESTAMP - INTERVAL '7 days'
-             )
-             AND timestamp >= CURRENT_TIMESTAMP - INTERVAL '7 days'"
-        )
-        .fetch_optional(&data.db_pool)
-        .await
-        .ok()
-        .flatten();
+    HttpResponse::Ok().json(data.sovereignty.region_catalog().list())
+}
 
-        let (total_requests, blocked_requests, avg_latency) = current_metrics.unwrap_or((0, 0, 100.0));
-        let success_rate = if total_requests > 0 {
-            ((total_requests - blocked_requests) as f64 / total_requests as f64) * 100.0
-        } else {
-            100.0
-        };
+/// Add or correct a cloud region
+/// 
+/// The entry is stored in `cloud_region_catalog` and applies immediately to the
+/// sovereign lock, policy simulations and the DORA register. Other nodes pick it
+/// up on restart.
+#[utoipa::path(
+    put,
+    path = "/region_catalog",
+    tag = "Policy Management",
+    request_body = UpsertCloudRegionRequest,
+    responses(
+        (status = 200, description = "Region stored", body = crate::core::region_catalog::CloudRegion),
+        (status = 400, description = "Invalid region"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+    ),
+    security(
+        ("bearer" = [])
+    )
+)]
+pub async fn upsert_region_catalog_entry(
+    req: web::Json<UpsertCloudRegionRequest>,
+    data: web::Data<AppState>,
+    http_req: HttpRequest,
+) -> impl Responder {
+    // AUTHENTICATION & AUTHORIZATION
+    let claims = match authenticate_and_authorize(&http_req, &data.db_pool, "policy", "write").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
 
-        return HttpResponse::Ok().json(serde_json::json!({
-            "status": "DRY_RUN",
-            "message": format!("Rollback simulation: Would rollback to version {} ({})", target_version, target_policy_name),
-            "target_policy_id": target_policy_id,
-            "target_policy_name": target_policy_name,
-            "target_version": target_version,
-            "current_policy_id": policy_id,
-            "estimated_rollback_time_ms": 50.0, // Estimated based on typical rollback
-            "estimated_rollback_time_sec": 0.05,
-            "estimated_sla_met": true,
-            "current_metrics": {
-                "total_requests": total_requests,
-                "blocked_requests": blocked_requests,
-                "success_rate": success_rate,
-                "avg_latency_ms": avg_latency
-            },
-            "note": "This is a simulation. No changes were made. Use dry_run=false to execute the rollback."
+    let country = req.country.trim();
+    if req.region_code.trim().is_empty() || req.provider.trim().is_empty() {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Invalid input",
+            "message": "provider and region_code are required"
+        }));
+    }
+    if country.len() != 2 || !country.chars().all(|c| c.is_ascii_alphabetic()) {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "Invalid input",
+            "message": "country must be an ISO 3166-1 alpha-2 code"
         }));
     }
 
-    // Track rollback start time for speed measurement
-    let rollback_start = std::time::Instant::now();
+    let mut region = crate::core::region_catalog::CloudRegion::new(
+        req.provider.trim(),
+        req.region_code.trim(),
+        country,
+        req.location.as_deref(),
+    );
+    if let Some(jurisdiction) = &req.jurisdiction {
+        let jurisdiction = jurisdiction.trim().to_uppercase();
+        if jurisdiction != crate::core::region_catalog::JURISDICTION_EU_EEA
+            && jurisdiction != crate::core::region_catalog::JURISDICTION_NON_EU {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "Invalid input",
+                "message": "jurisdiction must be EU/EEA or NON_EU"
+            }));
+        }
+        region.jurisdiction = jurisdiction;
+    }
 
-    // Deactivate current policy
-    let _ = sqlx::query(
-        "UPDATE policy_versions SET is_active = false, deactivated_at = CURRENT_TIMESTAMP WHERE id = $1"
-    )
-    .bind(&policy_id)
-    .execute(&data.db_pool)
-    .await;
+    match data.sovereignty.region_catalog().upsert(&data.db_pool, region.clone(), &claims.sub).await {
+        Ok(()) => HttpResponse::Ok().json(region),
+        Err(e) => {
+            let request_id = generate_request_id();
+            log_error_safely("updating region catalog", &e, &request_id);
+            create_error_response(&request_id)
+        }
+    }
+}
 
-    // Activate target version
-    let result = sqlx::query(
-        "UPDATE policy_versions 
-         SET is_active = true, activated_at = CURRENT_TIMESTAMP 
-         WHERE policy_type = $1 AND version_number = $2
-         RETURNING id"
+#[derive(Deserialize, ToSchema)]
+pub struct UpsertUsageBudgetRequest {
+    /// AGENT or BUSINESS_FUNCTION
+    #[schema(example = "BUSINESS_FUNCTION")]
+    pub scope_type: String,
+    /// agent_id or business function
+    #[schema(example = "CUSTOMER_SERVICE")]
+    pub scope_value: String,
+    /// DAILY or MONTHLY
+    #[schema(example = "MONTHLY")]
+    pub period: String,
+    pub max_tokens: Option<i64>,
+    pub max_requests: Option<i64>,
+    #[schema(example = 500.0)]
+    pub max_cost_eur: Option<f64>,
+    /// Utilization (percent) that triggers a soft alert (default: 80)
+    pub soft_limit_percent: Option<i32>,
+    /// HARD (block with HTTP 429, default) or SOFT (alert only)
+    pub enforcement: Option<String>,
+    pub is_active: Option<bool>,
+}
+
+/// List usage budgets
+/// 
+/// Every budget with its consumption in the current period.
+#[utoipa::path(
+    get,
+    path = "/usage_budgets",
+    tag = "Policy Management",
+    responses(
+        (status = 200, description = "Usage budgets and their current period usage", body = Vec<crate::core::usage_budget::BudgetStatus>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+    ),
+    security(
+        ("bearer" = [])
     )
-    .bind(&policy_type)
-    .bind(target_version)
-    .fetch_optional(&data.db_pool)
-    .await;
+)]
+pub async fn list_usage_budgets(
+    data: web::Data<AppState>,
+    http_req: HttpRequest,
+) -> impl Responder {
+    use crate::core::usage_budget::UsageBudgets;
 
-    // Calculate rollback duration
-    let rollback_duration_ms = rollback_start.elapsed().as_millis() as f64;
-    let rollback_duration_sec = rollback_duration_ms / 1000.0;
-    let sla_met = rollback_duration_sec < 30.0; // < 30 seconds SLA
+    // AUTHENTICATION & AUTHORIZATION
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "policy", "read").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
 
-    match result {
-        Ok(Some(row)) => {
-            let new_policy_id: Uuid = row.get(0);
+    let budgets = match UsageBudgets::list(&data.db_pool).await {
+        Ok(budgets) => budgets,
+        Err(e) => {
+            let request_id = generate_request_id();
+            log_error_safely("listing usage budgets", &e, &request_id);
+            return create_error_response(&request_id);
+        }
+    };
 
-            // Log rollback in history with speed metrics
-            let _ = sqlx::query(
-                "INSERT INTO policy_activation_history (policy_version_id, action, performed_by, previous_version_id, notes)
-                 VALUES ($1, 'ROLLED_BACK', $2, $3, $4)"
-            )
-            .bind(new_policy_id)
-            .bind(&claims.sub)
-            .bind(Uuid::parse_str(&policy_id).ok())
-            .bind(rollback_req.notes)
-            .execute(&data.db_pool)
-            .await;
+    let now = chrono::Utc::now();
+    let mut statuses = Vec::with_capacity(budgets.len());
+    for budget in budgets {
+        match UsageBudgets::status(&data.db_pool, budget, now).await {
+            Ok(status) => statuses.push(status),
+            Err(e) => {
+                let request_id = generate_request_id();
+                log_error_safely("computing usage budget status", &e, &request_id);
+                return create_error_response(&request_id);
+            }
+        }
+    }
 
-            // Log rollback speed metrics
-            let _ = sqlx::query(
-                "INSERT INTO policy_rollback_metrics (policy_version_id, rollback_duration_ms, sla_met, performed_by)
-                 VALUES ($1, $2, $3, $4)
-                 ON CONFLICT (policy_version_id) DO UPDATE SET
-                   rollback_duration_ms = EXCLUDED.rollback_duration_ms,
-                   sla_met = EXCLUDED.sla_met,
-                   performed_by = EXCLUDED.performed_by,
-                   updated_at = CURRENT_TIMESTAMP"
-            )
-            .bind(new_policy_id)
-            .bind(rollback_duration_ms)
-            .bind(sla_met)
-            .bind(&claims.sub)
-            .execute(&data.db_pool)
-            .await;
+    HttpResponse::Ok().json(statuses)
+}
 
-            HttpResponse::Ok().json(serde_json::json!({
-                "status": "SUCCESS",
-                "message": format!("Policy rolled back to version {} in {:.2}s", target_version, rollback_duration_sec),
-                "new_policy_id": new_policy_id,
-                "previous_policy_id": policy_id,
-                "rollback_duration_ms": rollback_duration_ms,
-                "rollback_duration_sec": rollback_duration_sec,
-                "sla_met": sla_met,
-                "sla_threshold_sec": 30.0
-            }))
+/// Create or update a usage budget
+/// 
+/// One budget per scope and period; posting the same scope_type, scope_value and period replaces its limits.
+/// Limits left out are not enforced.
+#[utoipa::path(
+    post,
+    path = "/usage_budgets",
+    tag = "Policy Management",
+    request_body = UpsertUsageBudgetRequest,
+    responses(
+        (status = 200, description = "Budget stored", body = crate::core::usage_budget::UsageBudget),
+        (status = 400, description = "Invalid budget"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+    ),
+    security(
+        ("bearer" = [])
+    )
+)]
+pub async fn upsert_usage_budget(
+    req: web::Json<UpsertUsageBudgetRequest>,
+    data: web::Data<AppState>,
+    http_req: HttpRequest,
+) -> impl Responder {
+    use crate::core::usage_budget::{UsageBudgets, ENFORCEMENT_HARD, ENFORCEMENT_SOFT, SCOPE_AGENT, SCOPE_BUSINESS_FUNCTION};
+
+    // AUTHENTICATION & AUTHORIZATION
+    let claims = match authenticate_and_authorize(&http_req, &data.db_pool, "policy", "write").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
+
+    let invalid = |message: &str| HttpResponse::BadRequest().json(serde_json::json!({
+        "error": "Invalid input",
+        "message": message
+    }));
+    let scope_type = req.scope_type.trim().to_uppercase();
+    let period = req.period.trim().to_uppercase();
+    let enforcement = req.enforcement.as_deref().unwrap_or(ENFORCEMENT_HARD).trim().to_uppercase();
+    let soft_limit_percent = req.soft_limit_percent.unwrap_or(80);
+    if scope_type != SCOPE_AGENT && scope_type != SCOPE_BUSINESS_FUNCTION {
+        return invalid("scope_type must be AGENT or BUSINESS_FUNCTION");
+    }
+    if req.scope_value.trim().is_empty() {
+        return invalid("scope_value is required");
+    }
+    if period != "DAILY" && period != "MONTHLY" {
+        return invalid("period must be DAILY or MONTHLY");
+    }
+    if enforcement != ENFORCEMENT_HARD && enforcement != ENFORCEMENT_SOFT {
+        return invalid("enforcement must be HARD or SOFT");
+    }
+    if !(1..=100).contains(&soft_limit_percent) {
+        return invalid("soft_limit_percent must be between 1 and 100");
+    }
+    if req.max_tokens.is_none() && req.max_requests.is_none() && req.max_cost_eur.is_none() {
+        return invalid("at least one of max_tokens, max_requests and max_cost_eur is required");
+    }
+    if req.max_tokens.unwrap_or(0) < 0 || req.max_requests.unwrap_or(0) < 0 || req.max_cost_eur.unwrap_or(0.0) < 0.0 {
+        return invalid("limits must not be negative");
+    }
+
+    match UsageBudgets::upsert(
+        &data.db_pool,
+        &scope_type,
+        req.scope_value.trim(),
+        &period,
+        req.max_tokens,
+        req.max_requests,
+        req.max_cost_eur,
+        soft_limit_percent,
+        &enforcement,
+        req.is_active.unwrap_or(true),
+        Some(&claims.sub),
+    ).await {
+        Ok(budget) => HttpResponse::Ok().json(budget),
+        Err(e) => {
+            let request_id = generate_request_id();
+            log_error_safely("storing usage budget", &e, &request_id);
+            create_error_response(&request_id)
         }
-        Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
-            "error": "TARGET_VERSION_NOT_FOUND",
-            "message": format!("Version {} not found for policy type {}", target_version, policy_type)
-        })),
-        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
-            "error": "ROLLBACK_FAILED",
-            "message": format!("Database error: {}", e)
-        }))
     }
 }
 
@@ -7392,7 +10084,7 @@ pub async fn get_policy_impact_analytics(
     let records: Vec<Record> = sqlx::query_as(
         "SELECT agent_id, action_summary, payload_hash 
          FROM compliance_records 
-         WHERE timestamp >= $1"
+         WHERE entry_type = 'ACTION' AND timestamp >= $1"
     )
     .bind(start_time)
     .fetch_all(&data.db_pool)
@@ -7858,6 +10550,115 @@ pub async fn get_asset_by_agent(
     }
 }
 
+#[derive(Deserialize)]
+pub struct AssetUsageQuery {
+    pub days: Option<i64>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct AssetUsageResponse {
+    pub asset_id: String,
+    pub business_function: Option<String>,
+    pub agent_ids: Vec<String>,
+    pub since: chrono::DateTime<chrono::Utc>,
+    pub requests: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub estimated_cost_eur: f64,
+    pub by_model: Vec<crate::core::usage_budget::ModelUsage>,
+    /// Budgets of the asset's agents and business function in their current period
+    pub budgets: Vec<crate::core::usage_budget::BudgetStatus>,
+}
+
+/// Get AI vendor usage of an asset
+/// 
+/// Tokens, requests and estimated cost of the agents mapped to the asset, with the
+/// usage budgets that apply to them.
+#[utoipa::path(
+    get,
+    path = "/assets/{asset_id}/usage",
+    tag = "Asset Management",
+    params(
+        ("asset_id" = String, Path, description = "Asset ID"),
+        ("days" = Option<i64>, Query, description = "Days of usage to report (default: 30, max: 366)")
+    ),
+    responses(
+        (status = 200, description = "Asset usage", body = AssetUsageResponse),
+        (status = 404, description = "Asset not found"),
+        (status = 401, description = "Unauthorized"),
+    ),
+    security(
+        ("bearer" = [])
+    )
+)]
+pub async fn get_asset_usage(
+    path: web::Path<String>,
+    query: web::Query<AssetUsageQuery>,
+    data: web::Data<AppState>,
+    http_req: HttpRequest,
+) -> impl Responder {
+    use crate::core::usage_budget::UsageBudgets;
+
+    // AUTHENTICATION & AUTHORIZATION
+    let _claims = match authenticate_and_authorize(&http_req, &data.db_pool, "asset", "read").await {
+        Ok(c) => c,
+        Err(resp) => return resp,
+    };
+
+    let asset_id = path.into_inner();
+    let (context, agent_ids) = match crate::core::asset_policy_engine::AssetPolicyEngine::get_asset_agents(&data.db_pool, &asset_id).await {
+        Ok(Some(asset)) => asset,
+        Ok(None) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "ASSET_NOT_FOUND",
+                "message": format!("No asset found for asset_id: {}", asset_id)
+            }));
+        }
+        Err(e) => {
+            let request_id = generate_request_id();
+            log::error!("Error in loading asset for usage: {} (Request ID: {})", e, request_id);
+            return create_error_response(&request_id);
+        }
+    };
+
+    let since = chrono::Utc::now() - chrono::Duration::days(query.days.unwrap_or(30).clamp(1, 366));
+    let by_model = match UsageBudgets::usage_by_model(&data.db_pool, &agent_ids, since).await {
+        Ok(by_model) => by_model,
+        Err(e) => {
+            let request_id = generate_request_id();
+            log_error_safely("loading asset usage", &e, &request_id);
+            return create_error_response(&request_id);
+        }
+    };
+
+    // Agent budgets of every mapped agent; the business function budget only once
+    let mut budgets: Vec<crate::core::usage_budget::BudgetStatus> = Vec::new();
+    for (i, agent_id) in agent_ids.iter().enumerate() {
+        let business_function = if i == 0 { context.business_function.as_deref() } else { None };
+        match UsageBudgets::check(&data.db_pool, agent_id, business_function).await {
+            Ok(statuses) => budgets.extend(statuses),
+            Err(e) => {
+                let request_id = generate_request_id();
+                log_error_safely("checking asset usage budgets", &e, &request_id);
+                return create_error_response(&request_id);
+            }
+        }
+    }
+
+    HttpResponse::Ok().json(AssetUsageResponse {
+        asset_id,
+        business_function: context.business_function,
+        agent_ids,
+        since,
+        requests: by_model.iter().map(|m| m.requests).sum(),
+        input_tokens: by_model.iter().map(|m| m.input_tokens).sum(),
+        output_tokens: by_model.iter().map(|m| m.output_tokens).sum(),
+        estimated_cost_eur: by_model.iter().map(|m| m.cost_eur).sum(),
+        by_model,
+        budgets,
+    })
+}
+
 /// Get business functions
 #[utoipa::path(
     get,
@@ -7926,6 +10727,7 @@ pub struct AssetPolicyResponse {
     request_body = AssetPolicyRequest,
     responses(
         (status = 200, description = "Asset policy created", body = AssetPolicyResponse),
+        (status = 400, description = "Invalid rules in a RULES policy"),
         (status = 401, description = "Unauthorized"),
     ),
     security(
@@ -7944,6 +10746,14 @@ pub async fn create_asset_policy(
     };
 
     let policy_req = req.into_inner();
+    if policy_req.policy_type == crate::core::asset_policy_engine::RULES_POLICY_TYPE {
+        if let Err(e) = crate::core::policy_rules::RuleSet::parse(&policy_req.policy_config) {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "Invalid input",
+                "message": format!("Invalid rules: {}", e)
+            }));
+        }
+    }
     let now = Utc::now();
     let policy_id = Uuid::new_v4();
 
@@ -8867,6 +11677,11 @@ pub struct CanaryConfigRequest {
     pub min_requests_for_promotion: Option<i64>,
     #[schema(example = 10)]
     pub evaluation_window_minutes: Option<i32>,
+    /// Cohort subject: AGENT (default), TENANT (X-Company-ID), BUSINESS_FUNCTION or USER
+    #[schema(example = "AGENT")]
+    pub bucket_by: Option<String>,
+    /// Salt of the rollout hash; a new salt only affects subjects not seen before
+    pub rollout_salt: Option<String>,
 }
 
 #[derive(Serialize, ToSchema)]
@@ -8879,6 +11694,7 @@ pub struct CanaryConfigResponse {
     pub rollback_threshold: f64,
     pub min_requests_for_promotion: i64,
     pub evaluation_window_minutes: i32,
+    pub bucket_by: String,
 }
 
 /// Configure canary deployment for a policy
@@ -8924,6 +11740,16 @@ pub async fn configure_canary(
             }));
         }
     }
+    let bucket_by = match config.bucket_by.as_deref().map(crate::core::rollout::BucketBy::parse) {
+        Some(None) => {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "INVALID_BUCKET_BY",
+                "message": "bucket_by must be AGENT, TENANT, BUSINESS_FUNCTION or USER"
+            }));
+        }
+        Some(Some(bucket_by)) => Some(bucket_by),
+        None => None,
+    };
     
     // Get current policy to preserve existing values
     #[derive(sqlx::FromRow)]
@@ -8934,11 +11760,13 @@ pub async fn configure_canary(
         canary_failure_threshold: Option<f64>,
         canary_min_requests: Option<i64>,
         canary_evaluation_window_minutes: Option<i32>,
+        rollout_bucket_by: String,
     }
     
     let current: Option<PolicyRow> = sqlx::query_as::<_, PolicyRow>(
         "SELECT rollout_percentage, canary_auto_promote_enabled, canary_success_threshold,
-                canary_failure_threshold, canary_min_requests, canary_evaluation_window_minutes
+                canary_failure_threshold, canary_min_requests, canary_evaluation_window_minutes,
+                rollout_bucket_by
          FROM policy_versions WHERE id = $1"
     )
     .bind(policy_id)
@@ -8963,6 +11791,7 @@ pub async fn configure_canary(
     let rollback_threshold = config.rollback_threshold.unwrap_or(current.canary_failure_threshold.unwrap_or(5.0));
     let min_requests = config.min_requests_for_promotion.unwrap_or(current.canary_min_requests.unwrap_or(100));
     let window_minutes = config.evaluation_window_minutes.unwrap_or(current.canary_evaluation_window_minutes.unwrap_or(10));
+    let bucket_by = bucket_by.map(|b| b.as_str().to_string()).unwrap_or(current.rollout_bucket_by);
     
     let result = sqlx::query(
         "UPDATE policy_versions 
@@ -8971,8 +11800,10 @@ pub async fn configure_canary(
              canary_success_threshold = $3,
              canary_failure_threshold = $4,
              canary_min_requests = $5,
-             canary_evaluation_window_minutes = $6
-         WHERE id = $7
+             canary_evaluation_window_minutes = $6,
+             rollout_bucket_by = $7,
+             rollout_salt = COALESCE($8, rollout_salt)
+         WHERE id = $9
          RETURNING rollout_percentage"
     )
     .bind(traffic_percentage)
@@ -8981,6 +11812,8 @@ pub async fn configure_canary(
     .bind(rollback_threshold)
     .bind(min_requests)
     .bind(window_minutes)
+    .bind(&bucket_by)
+    .bind(config.rollout_salt.as_deref())
     .bind(policy_id)
     .fetch_optional(&data.db_pool)
     .await;
@@ -8996,6 +11829,7 @@ pub async fn configure_canary(
                 rollback_threshold,
                 min_requests_for_promotion: min_requests,
                 evaluation_window_minutes: window_minutes,
+                bucket_by,
             })
         }
         Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
@@ -9236,6 +12070,12 @@ pub struct PolicyCanaryStatus {
     pub min_requests_for_promotion: i64,
     pub evaluation_window_minutes: i32,
     pub last_evaluated_at: Option<DateTime<Utc>>,
+    /// Cohort subject: AGENT, TENANT, BUSINESS_FUNCTION or USER
+    pub bucket_by: String,
+    /// Subjects seen since the rollout started, in or out of the cohort
+    pub subjects_seen: i64,
+    /// Exact cohort membership: the subjects the policy currently applies to
+    pub cohort: Vec<crate::core::rollout::RolloutAssignment>,
 }
 
 #[derive(Serialize, ToSchema)]
@@ -9285,12 +12125,14 @@ pub async fn get_canary_analytics(
         rollback_threshold: Option<f64>,
         min_requests_for_promotion: Option<i64>,
         evaluation_window_minutes: Option<i32>,
+        rollout_bucket_by: String,
     }
 
     let policies: Vec<PolicyCanaryRow> = sqlx::query_as::<_, PolicyCanaryRow>(
         "SELECT 
             id, policy_type,
             rollout_percentage,
+            rollout_bucket_by,
             auto_promote_enabled,
             auto_rollback_enabled,
             promotion_threshold,
@@ -9347,6 +12189,12 @@ pub async fn get_canary_analytics(
                 (0, 0, 0, 0, 100.0)
             };
 
+        let assignments = crate::core::rollout::RolloutEngine::assignments(&data.db_pool, p.id)
+            .await
+            .unwrap_or_default();
+        let subjects_seen = assignments.len() as i64;
+        let cohort = assignments.into_iter().filter(|a| a.in_cohort).collect();
+
         policies_status.push(PolicyCanaryStatus {
             policy_id: p.id.to_string(),
             policy_type: p.policy_type,
@@ -9363,6 +12211,9 @@ pub async fn get_canary_analytics(
             min_requests_for_promotion: p.min_requests_for_promotion.unwrap_or(100),
             evaluation_window_minutes: p.evaluation_window_minutes.unwrap_or(10),
             last_evaluated_at: None, // TODO: Add last_evaluated_at to policy_versions
+            bucket_by: p.rollout_bucket_by,
+            subjects_seen,
+            cohort,
         });
     }
 
@@ -9468,6 +12319,7 @@ pub async fn get_canary_analytics(
         ("policy_type" = String, Query, description = "Policy type: SOVEREIGN_LOCK, AGENT_REVOCATION, etc."),
         ("time_range_days" = Option<i64>, Query, description = "Time range in days (default: 7)"),
         ("agent_filter" = Option<String>, Query, description = "Comma-separated agent IDs to filter"),
+        ("sample_rate" = Option<f64>, Query, description = "Share of records to replay, e.g. 0.1"),
     ),
     responses(
         (status = 200, description = "Impact preview", body = SimulationResult),
@@ -9532,10 +12384,12 @@ pub async fn preview_policy_impact(
         business_function_filter: query.get("business_function").map(|s| s.split(',').map(|id| id.trim().to_string()).collect()),
         location_filter: query.get("location").map(|s| s.split(',').map(|id| id.trim().to_string()).collect()),
         time_offset_days: query.get("time_offset_days").and_then(|s| s.parse::<i64>().ok()),
+        sample_rate: query.get("sample_rate").and_then(|s| s.parse::<f64>().ok()),
     };
     
     match crate::core::policy_simulator::PolicySimulator::simulate(
         &data.db_pool,
+        &data.sovereignty,
         simulation_request.clone(),
     ).await {
         Ok(result) => {
@@ -9595,6 +12449,7 @@ pub async fn preview_policy_impact(
                 "SELECT AVG(inference_time_ms) 
                  FROM compliance_records 
                  WHERE agent_id = ANY($1) 
+                   AND entry_type = 'ACTION'
                    AND timestamp >= $2 
                    AND inference_time_ms IS NOT NULL"
             )
@@ -9656,7 +12511,7 @@ pub async fn preview_policy_impact(
                 let prev_end = Utc::now() - chrono::Duration::days(time_range_days);
                 
                 let prev_total: i64 = sqlx::query_scalar(
-                    "SELECT COUNT(*) FROM compliance_records WHERE timestamp >= $1 AND timestamp < $2"
+                    "SELECT COUNT(*) FROM compliance_records WHERE entry_type = 'ACTION' AND timestamp >= $1 AND timestamp < $2"
                 )
                 .bind(prev_start)
                 .bind(prev_end)
@@ -9829,6 +12684,8 @@ pub async fn compare_policies(
     let time_range_days = body.get("time_range_days")
         .and_then(|v| v.as_i64())
         .unwrap_or(7);
+    // Sampling picks the same records for both policies
+    let sample_rate = body.get("sample_rate").and_then(|v| v.as_f64());
 
     // Parse policy types
     let policy_type_a_enum = match policy_type_a {
@@ -9836,6 +12693,8 @@ pub async fn compare_policies(
         "AGENT_REVOCATION" => crate::core::policy_simulator::PolicyType::AgentRevocation,
         "CONSENT_REQUIREMENT" => crate::core::policy_simulator::PolicyType::ConsentRequirement,
         "PROCESSING_RESTRICTION" => crate::core::policy_simulator::PolicyType::ProcessingRestriction,
+        "RULES" => crate::core::policy_simulator::PolicyType::Rules,
+        "POLICY_SET" => crate::core::policy_simulator::PolicyType::PolicySet,
         _ => return HttpResponse::BadRequest().json(serde_json::json!({
             "error": "INVALID_POLICY_TYPE_A",
             "message": "Invalid policy_type_a"
@@ -9847,6 +12706,8 @@ pub async fn compare_policies(
         "AGENT_REVOCATION" => crate::core::policy_simulator::PolicyType::AgentRevocation,
         "CONSENT_REQUIREMENT" => crate::core::policy_simulator::PolicyType::ConsentRequirement,
         "PROCESSING_RESTRICTION" => crate::core::policy_simulator::PolicyType::ProcessingRestriction,
+        "RULES" => crate::core::policy_simulator::PolicyType::Rules,
+        "POLICY_SET" => crate::core::policy_simulator::PolicyType::PolicySet,
         _ => return HttpResponse::BadRequest().json(serde_json::json!({
             "error": "INVALID_POLICY_TYPE_B",
             "message": "Invalid policy_type_b"
@@ -9856,6 +12717,7 @@ pub async fn compare_policies(
     // Run both simulations
     let sim_a = crate::core::policy_simulator::PolicySimulator::simulate(
         &data.db_pool,
+        &data.sovereignty,
         crate::core::policy_simulator::SimulationRequest {
             policy_type: policy_type_a_enum.clone(),
             policy_config: policy_config_a,
@@ -9864,11 +12726,13 @@ pub async fn compare_policies(
             business_function_filter: None,
             location_filter: None,
             time_offset_days: None,
+            sample_rate,
         },
     ).await;
 
     let sim_b = crate::core::policy_simulator::PolicySimulator::simulate(
         &data.db_pool,
+        &data.sovereignty,
         crate::core::policy_simulator::SimulationRequest {
             policy_type: policy_type_b_enum.clone(),
             policy_config: policy_config_b,
@@ -9877,6 +12741,7 @@ pub async fn compare_policies(
             business_function_filter: None,
             location_filter: None,
             time_offset_days: None,
+            sample_rate,
         },
     ).await;
 
@@ -10051,6 +12916,7 @@ pub async fn get_policy_health(
                     PERCENTILE_CONT(0.99) WITHIN GROUP (ORDER BY EXTRACT(EPOCH FROM (completed_at - timestamp)) * 1000) as p99_latency
                  FROM compliance_records
                  WHERE policy_version_id = $1
+                   AND entry_type = 'ACTION'
                    AND timestamp >= $2
                    AND completed_at IS NOT NULL"
             )
@@ -11049,6 +13915,7 @@ pub async fn get_policy_health_dashboard(
             "SELECT AVG(EXTRACT(EPOCH FROM (completed_at - timestamp)) * 1000)
              FROM compliance_records
              WHERE policy_version_id = $1
+               AND entry_type = 'ACTION'
                AND timestamp >= $2
                AND completed_at IS NOT NULL"
         )
@@ -13155,7 +16022,7 @@ pub async fn get_nis2_compliance_report(
             "BASIC_HYGIENE" => {
                 // Check for compliance records (indicates security monitoring)
                 sqlx::query_scalar::<_, i64>(
-                    "SELECT COUNT(*) FROM compliance_records WHERE timestamp > CURRENT_TIMESTAMP - INTERVAL '30 days'"
+                    "SELECT COUNT(*) FROM compliance_records WHERE entry_type = 'ACTION' AND timestamp > CURRENT_TIMESTAMP - INTERVAL '30 days'"
                 )
                 .fetch_one(&data.db_pool)
                 .await
@@ -13425,7 +16292,7 @@ pub async fn get_monthly_compliance_summary(
 
     // Get total requests
     let total_requests: i64 = sqlx::query_scalar(
-        "SELECT COUNT(*) FROM compliance_records WHERE timestamp >= $1 AND timestamp < $2"
+        "SELECT COUNT(*) FROM compliance_records WHERE entry_type = 'ACTION' AND timestamp >= $1 AND timestamp < $2"
     )
     .bind(start_time)
     .bind(end_time)
@@ -13434,7 +16301,7 @@ pub async fn get_monthly_compliance_summary(
     .unwrap_or(0);
 
     let blocked_requests: i64 = sqlx::query_scalar(
-        "SELECT COUNT(*) FROM compliance_records WHERE timestamp >= $1 AND timestamp < $2 AND status LIKE '%BLOCKED%'"
+        "SELECT COUNT(*) FROM compliance_records WHERE entry_type = 'ACTION' AND timestamp >= $1 AND timestamp < $2 AND status LIKE '%BLOCKED%'"
     )
     .bind(start_time)
     .bind(end_time)
@@ -13502,7 +16369,7 @@ pub async fn get_monthly_compliance_summary(
     // Calculate trends (simplified - compare with previous month)
     let prev_start = start_time - chrono::Duration::days(30);
     let prev_blocked: i64 = sqlx::query_scalar(
-        "SELECT COUNT(*) FROM compliance_records WHERE timestamp >= $1 AND timestamp < $2 AND status LIKE '%BLOCKED%'"
+        "SELECT COUNT(*) FROM compliance_records WHERE entry_type = 'ACTION' AND timestamp >= $1 AND timestamp < $2 AND status LIKE '%BLOCKED%'"
     )
     .bind(prev_start)
     .bind(start_time)
@@ -13598,7 +16465,7 @@ pub async fn export_monthly_compliance_summary(
     .unwrap_or_else(|| Utc::now());
 
     let total_requests: i64 = sqlx::query_scalar(
-        "SELECT COUNT(*) FROM compliance_records WHERE timestamp >= $1 AND timestamp < $2"
+        "SELECT COUNT(*) FROM compliance_records WHERE entry_type = 'ACTION' AND timestamp >= $1 AND timestamp < $2"
     )
     .bind(start_time)
     .bind(end_time)
@@ -13607,7 +16474,7 @@ pub async fn export_monthly_compliance_summary(
     .unwrap_or(0);
 
     let blocked_requests: i64 = sqlx::query_scalar(
-        "SELECT COUNT(*) FROM compliance_records WHERE timestamp >= $1 AND timestamp < $2 AND status LIKE '%BLOCKED%'"
+        "SELECT COUNT(*) FROM compliance_records WHERE entry_type = 'ACTION' AND timestamp >= $1 AND timestamp < $2 AND status LIKE '%BLOCKED%'"
     )
     .bind(start_time)
     .bind(end_time)
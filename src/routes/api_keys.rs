@@ -11,6 +11,8 @@ use chrono::Utc;
 pub struct CreateApiKeyRequest {
     pub name: String,
     pub description: Option<String>,
+    /// Tenant the key belongs to; the forward proxy attributes its traffic to this tenant
+    pub company_id: Option<Uuid>,
     pub permissions: Vec<String>,
     pub expires_at: Option<chrono::DateTime<Utc>>,
 }
@@ -28,6 +30,7 @@ pub struct ApiKeyInfoResponse {
     pub name: String,
     pub description: Option<String>,
     pub user_id: Option<Uuid>,
+    pub company_id: Option<Uuid>,
     pub permissions: Vec<String>,
     pub expires_at: Option<chrono::DateTime<Utc>>,
     pub last_used_at: Option<chrono::DateTime<Utc>>,
@@ -94,6 +97,7 @@ pub async fn create_api_key(
         req.name.clone(),
         req.description.clone(),
         user_id,
+        req.company_id,
         req.permissions.clone(),
         req.expires_at,
     ).await {
@@ -119,6 +123,7 @@ pub async fn create_api_key(
                     name: info.name,
                     description: info.description,
                     user_id: info.user_id,
+                    company_id: info.company_id,
                     permissions: info.permissions,
                     expires_at: info.expires_at,
                     last_used_at: info.last_used_at,
@@ -179,6 +184,7 @@ pub async fn list_api_keys(
                     name: info.name,
                     description: info.description,
                     user_id: info.user_id,
+                    company_id: info.company_id,
                     permissions: info.permissions,
                     expires_at: info.expires_at,
                     last_used_at: info.last_used_at,
@@ -261,6 +267,7 @@ pub async fn get_api_key(
                         name: info.name,
                         description: info.description,
                         user_id: info.user_id,
+                        company_id: info.company_id,
                         permissions: info.permissions,
                         expires_at: info.expires_at,
                         last_used_at: info.last_used_at,
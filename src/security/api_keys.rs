@@ -12,6 +12,8 @@ pub struct ApiKeyInfo {
     pub name: String,
     pub description: Option<String>,
     pub user_id: Option<Uuid>,
+    /// Tenant the key belongs to
+    pub company_id: Option<Uuid>,
     pub permissions: Vec<String>,
     pub expires_at: Option<chrono::DateTime<Utc>>,
     pub last_used_at: Option<chrono::DateTime<Utc>>,
@@ -19,6 +21,40 @@ pub struct ApiKeyInfo {
     pub created_at: chrono::DateTime<Utc>,
 }
 
+/// Agent and tenant a request acts for
+#[derive(Debug, Clone, PartialEq)]
+pub struct AgentIdentity {
+    pub agent_id: String,
+    pub user_id: Option<String>,
+    pub company_id: Option<String>,
+}
+
+impl AgentIdentity {
+    /// Identity vouched for by an API key: the agent is the key's name
+    pub fn from_key(info: ApiKeyInfo) -> Self {
+        Self {
+            agent_id: info.name,
+            user_id: info.user_id.map(|id| id.to_string()),
+            company_id: info.company_id.map(|id| id.to_string()),
+        }
+    }
+
+    /// Why agent or tenant claims disagree with this identity, if they do
+    pub fn conflict(&self, claimed_agents: &[Option<String>], claimed_company: Option<&str>) -> Option<String> {
+        if let Some(agent) = claimed_agents.iter().flatten().find(|agent| **agent != self.agent_id) {
+            return Some(format!("The API key belongs to agent '{}', not '{}'", self.agent_id, agent));
+        }
+        match claimed_company {
+            Some(company) if self.company_id.as_deref() != Some(company) => Some(format!(
+                "The API key belongs to tenant '{}', not '{}'",
+                self.company_id.as_deref().unwrap_or("none"),
+                company
+            )),
+            _ => None,
+        }
+    }
+}
+
 /// API Key Service
 pub struct ApiKeyService {
     db_pool: PgPool,
@@ -50,6 +86,7 @@ impl ApiKeyService {
         name: String,
         description: Option<String>,
         user_id: Option<Uuid>,
+        company_id: Option<Uuid>,
         permissions: Vec<String>,
         expires_at: Option<chrono::DateTime<Utc>>,
     ) -> Result<(String, ApiKeyInfo), sqlx::Error> {
@@ -61,8 +98,8 @@ impl ApiKeyService {
 
         sqlx::query(
             r#"
-            INSERT INTO api_keys (id, key_hash, name, description, user_id, permissions, expires_at, active, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $9)
+            INSERT INTO api_keys (id, key_hash, name, description, user_id, company_id, permissions, expires_at, active, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $10)
             "#,
         )
         .bind(&id)
@@ -70,6 +107,7 @@ impl ApiKeyService {
         .bind(&name)
         .bind(&description)
         .bind(&user_id)
+        .bind(&company_id)
         .bind(&permissions)
         .bind(&expires_at)
         .bind(true)
@@ -82,6 +120,7 @@ impl ApiKeyService {
             name,
             description,
             user_id,
+            company_id,
             permissions,
             expires_at,
             last_used_at: None,
@@ -100,9 +139,9 @@ impl ApiKeyService {
     ) -> Result<Option<ApiKeyInfo>, sqlx::Error> {
         let key_hash = Self::hash_key(key);
 
-        let result: Option<(Uuid, String, Option<String>, Option<Uuid>, Vec<String>, Option<chrono::DateTime<Utc>>, Option<chrono::DateTime<Utc>>, bool, chrono::DateTime<Utc>)> = sqlx::query_as(
+        let result: Option<(Uuid, String, Option<String>, Option<Uuid>, Option<Uuid>, Vec<String>, Option<chrono::DateTime<Utc>>, Option<chrono::DateTime<Utc>>, bool, chrono::DateTime<Utc>)> = sqlx::query_as(
             r#"
-            SELECT id, name, description, user_id, permissions, expires_at, last_used_at, active, created_at
+            SELECT id, name, description, user_id, company_id, permissions, expires_at, last_used_at, active, created_at
             FROM api_keys
             WHERE key_hash = $1 AND active = true
             "#,
@@ -111,7 +150,7 @@ impl ApiKeyService {
         .fetch_optional(&self.db_pool)
         .await?;
 
-        if let Some((id, name, description, user_id, permissions, expires_at, _last_used_at, active, created_at)) = result {
+        if let Some((id, name, description, user_id, company_id, permissions, expires_at, _last_used_at, active, created_at)) = result {
             // Check expiration
             if let Some(exp) = expires_at {
                 if exp < Utc::now() {
@@ -133,6 +172,7 @@ impl ApiKeyService {
                 name,
                 description,
                 user_id,
+                company_id,
                 permissions,
                 expires_at,
                 last_used_at: Some(Utc::now()),
@@ -158,10 +198,10 @@ impl ApiKeyService {
         &self,
         user_id: Option<Uuid>,
     ) -> Result<Vec<ApiKeyInfo>, sqlx::Error> {
-        let keys: Vec<(Uuid, String, Option<String>, Option<Uuid>, Vec<String>, Option<chrono::DateTime<Utc>>, Option<chrono::DateTime<Utc>>, bool, chrono::DateTime<Utc>)> = if let Some(uid) = user_id {
+        let keys: Vec<(Uuid, String, Option<String>, Option<Uuid>, Option<Uuid>, Vec<String>, Option<chrono::DateTime<Utc>>, Option<chrono::DateTime<Utc>>, bool, chrono::DateTime<Utc>)> = if let Some(uid) = user_id {
             sqlx::query_as(
                 r#"
-                SELECT id, name, description, user_id, permissions, expires_at, last_used_at, active, created_at
+                SELECT id, name, description, user_id, company_id, permissions, expires_at, last_used_at, active, created_at
                 FROM api_keys
                 WHERE user_id = $1
                 ORDER BY created_at DESC
@@ -173,7 +213,7 @@ impl ApiKeyService {
         } else {
             sqlx::query_as(
                 r#"
-                SELECT id, name, description, user_id, permissions, expires_at, last_used_at, active, created_at
+                SELECT id, name, description, user_id, company_id, permissions, expires_at, last_used_at, active, created_at
                 FROM api_keys
                 ORDER BY created_at DESC
                 "#,
@@ -182,12 +222,13 @@ impl ApiKeyService {
             .await?
         };
 
-        Ok(keys.into_iter().map(|(id, name, description, user_id, permissions, expires_at, last_used_at, active, created_at)| {
+        Ok(keys.into_iter().map(|(id, name, description, user_id, company_id, permissions, expires_at, last_used_at, active, created_at)| {
             ApiKeyInfo {
                 id,
                 name,
                 description,
                 user_id,
+                company_id,
                 permissions,
                 expires_at,
                 last_used_at,
@@ -198,3 +239,27 @@ impl ApiKeyService {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identity_conflict() {
+        let identity = AgentIdentity {
+            agent_id: "credit-agent".to_string(),
+            user_id: None,
+            company_id: Some("tenant-a".to_string()),
+        };
+        let agent = |s: &str| Some(s.to_string());
+
+        assert_eq!(identity.conflict(&[agent("credit-agent"), None], Some("tenant-a")), None);
+        assert_eq!(identity.conflict(&[None, None], None), None);
+        assert!(identity.conflict(&[agent("hr-agent"), None], None).is_some());
+        assert!(identity.conflict(&[None, agent("hr-agent")], None).is_some());
+        assert!(identity.conflict(&[None, None], Some("tenant-b")).is_some());
+
+        // A key without a tenant cannot be used to claim one
+        let untenanted = AgentIdentity { company_id: None, ..identity };
+        assert!(untenanted.conflict(&[None, None], Some("tenant-a")).is_some());
+    }
+}
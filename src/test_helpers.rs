@@ -35,3 +35,134 @@ pub async fn create_test_db_pool() -> PgPool {
         .expect("Failed to create test database pool")
 }
 
+
+#[cfg(test)]
+/// Start a local mock KMS speaking the `HttpKmsProvider` protocol (`/wrap`, `/unwrap`)
+///
+/// Each key id gets a random 256-bit key held in memory. Returns the base URL.
+pub async fn spawn_mock_kms(key_ids: &[&str]) -> String {
+    use actix_web::{web, App, HttpResponse, HttpServer};
+    use base64::Engine;
+    use crate::core::key_provider::{
+        aes_unwrap, aes_wrap, KmsUnwrapRequest, KmsUnwrapResponse, KmsWrapRequest, KmsWrapResponse,
+    };
+    use rand::RngCore;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    let mut keys = HashMap::new();
+    for key_id in key_ids {
+        let mut key = vec![0u8; 32];
+        rand::thread_rng().fill_bytes(&mut key);
+        keys.insert(key_id.to_string(), key);
+    }
+    let keys = web::Data::new(Arc::new(keys));
+
+    async fn wrap(
+        keys: web::Data<Arc<HashMap<String, Vec<u8>>>>,
+        req: web::Json<KmsWrapRequest>,
+    ) -> HttpResponse {
+        let engine = base64::engine::general_purpose::STANDARD;
+        let (Some(key), Ok(plaintext)) = (keys.get(&req.key_id), engine.decode(&req.plaintext)) else {
+            return HttpResponse::BadRequest().finish();
+        };
+        match aes_wrap(key, &plaintext) {
+            Ok(ciphertext) => HttpResponse::Ok().json(KmsWrapResponse { ciphertext: engine.encode(ciphertext) }),
+            Err(_) => HttpResponse::BadRequest().finish(),
+        }
+    }
+
+    async fn unwrap(
+        keys: web::Data<Arc<HashMap<String, Vec<u8>>>>,
+        req: web::Json<KmsUnwrapRequest>,
+    ) -> HttpResponse {
+        let engine = base64::engine::general_purpose::STANDARD;
+        let (Some(key), Ok(ciphertext)) = (keys.get(&req.key_id), engine.decode(&req.ciphertext)) else {
+            return HttpResponse::BadRequest().finish();
+        };
+        match aes_unwrap(key, &ciphertext) {
+            Ok(plaintext) => HttpResponse::Ok().json(KmsUnwrapResponse { plaintext: engine.encode(plaintext) }),
+            Err(_) => HttpResponse::BadRequest().finish(),
+        }
+    }
+
+    let server = HttpServer::new(move || {
+        App::new()
+            .app_data(keys.clone())
+            .route("/wrap", web::post().to(wrap))
+            .route("/unwrap", web::post().to(unwrap))
+    })
+    .workers(1)
+    .bind(("127.0.0.1", 0))
+    .expect("Failed to bind mock KMS");
+    let addr = server.addrs()[0];
+    actix_web::rt::spawn(server.run());
+
+    format!("http://{}", addr)
+}
+
+#[cfg(test)]
+/// Start a local mock RFC 3161 TSA at `/tsa`
+///
+/// Answers every TimeStampReq with a granted TimeStampResp whose TSTInfo
+/// carries the request's message imprint and nonce. `/tsa/replay` answers with
+/// a fixed nonce instead, like a replayed response. The SignedData has no
+/// signer, so the token only decodes; it cannot be verified cryptographically.
+pub async fn spawn_mock_tsa() -> String {
+    use actix_web::{web, App, HttpResponse, HttpServer};
+    use crate::core::seal_provider::der;
+    const SET: u8 = 0x31;
+
+    async fn timestamp(req: actix_web::HttpRequest, body: web::Bytes) -> HttpResponse {
+        let fields = der::expect(&body, der::SEQUENCE, "TimeStampReq")
+            .and_then(|req| der::children(req.content))
+            .unwrap_or_default();
+        let Some(imprint) = fields.get(1).map(|imprint| imprint.raw.to_vec()) else {
+            return HttpResponse::BadRequest().finish();
+        };
+        let nonce = match fields.iter().skip(2).find(|f| f.tag == der::INTEGER) {
+            Some(_) if req.path().ends_with("/replay") => Some(der::unsigned_integer(&[0x42; 8])),
+            Some(nonce) => Some(nonce.raw.to_vec()),
+            None => None,
+        };
+
+        let gen_time = chrono::Utc::now().format("%Y%m%d%H%M%SZ").to_string();
+        let mut tst_fields = vec![
+            der::unsigned_integer(&[1]),
+            der::oid("1.2.3.4.1").unwrap(),
+            imprint,
+            der::unsigned_integer(&rand::random::<[u8; 8]>()),
+            der::tlv(der::GENERALIZED_TIME, gen_time.as_bytes()),
+        ];
+        tst_fields.extend(nonce);
+        let tst_info = der::sequence(&tst_fields);
+        let sha256 = der::sequence(&[der::oid("2.16.840.1.101.3.4.2.1").unwrap(), der::tlv(der::NULL, &[])]);
+        let encap = der::sequence(&[
+            der::oid("1.2.840.113549.1.9.16.1.4").unwrap(),
+            der::tlv(der::CONTEXT_0, &der::tlv(der::OCTET_STRING, &tst_info)),
+        ]);
+        let signed_data = der::sequence(&[
+            der::unsigned_integer(&[3]),
+            der::tlv(SET, &sha256),
+            encap,
+            der::tlv(SET, &[]),
+        ]);
+        let token = der::sequence(&[
+            der::oid("1.2.840.113549.1.7.2").unwrap(),
+            der::tlv(der::CONTEXT_0, &signed_data),
+        ]);
+        let response = der::sequence(&[der::sequence(&[der::unsigned_integer(&[0])]), token]);
+        HttpResponse::Ok().content_type("application/timestamp-reply").body(response)
+    }
+
+    let server = HttpServer::new(|| App::new()
+            .route("/tsa", web::post().to(timestamp))
+            .route("/tsa/replay", web::post().to(timestamp)))
+        .workers(1)
+        .bind(("127.0.0.1", 0))
+        .expect("Failed to bind mock TSA");
+    let addr = server.addrs()[0];
+    actix_web::rt::spawn(server.run());
+
+    format!("http://{}/tsa", addr)
+}
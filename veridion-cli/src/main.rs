@@ -2,7 +2,10 @@ use clap::{Parser, Subcommand};
 use colored::*;
 use reqwest::Client;
 use serde_json::json;
+use std::collections::BTreeMap;
 use std::env;
+use std::fs;
+use std::path::Path;
 
 const DEFAULT_API_URL: &str = "http://127.0.0.1:8080/api/v1";
 
@@ -37,6 +40,12 @@ enum Commands {
         config: String,
         #[arg(short, long, default_value = "7")]
         days: u32,
+        /// Share of records to replay (0 < rate <= 1)
+        #[arg(long)]
+        sample_rate: Option<f64>,
+        /// Run as a background job and poll its progress
+        #[arg(long)]
+        background: bool,
     },
     /// Rollback a policy to a previous version
     Rollback {
@@ -59,6 +68,56 @@ enum Commands {
         #[arg(short, long, default_value = "7")]
         days: u32,
     },
+    /// Verify the compliance record hash chain between two points in time
+    VerifyChain {
+        /// Start of the window (RFC 3339)
+        #[arg(short, long)]
+        from: Option<String>,
+        /// End of the window (RFC 3339)
+        #[arg(short, long)]
+        to: Option<String>,
+    },
+    /// Export the live policies as a bundle directory
+    PolicyExport {
+        #[arg(short, long)]
+        dir: String,
+    },
+    /// Import a bundle directory for approval
+    PolicyImport {
+        #[arg(short, long)]
+        dir: String,
+        /// Only show the changes
+        #[arg(long)]
+        dry_run: bool,
+        #[arg(short, long)]
+        notes: Option<String>,
+    },
+    /// Apply an approved bundle import
+    PolicyApply {
+        #[arg(short, long)]
+        import_id: String,
+    },
+}
+
+/// Directories and files of a bundle that `policy-export` manages
+const BUNDLE_ENTRIES: &[&str] = &["bundle.json", "enforcement.json", "policies", "jurisdiction", "asset_policies"];
+
+/// JSON files under `dir`, keyed by path relative to `root` with `/` separators
+fn read_bundle_files(root: &Path, dir: &Path, files: &mut BTreeMap<String, String>) -> anyhow::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            read_bundle_files(root, &path, files)?;
+        } else if path.extension().is_some_and(|e| e == "json") {
+            let relative = path.strip_prefix(root)?
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            files.insert(relative, fs::read_to_string(&path)?);
+        }
+    }
+    Ok(())
 }
 
 #[tokio::main]
@@ -111,36 +170,80 @@ async fn main() -> anyhow::Result<()> {
                 eprintln!("  Error: {}", error.get("message").unwrap_or(&json!("Unknown error")));
             }
         }
-        Commands::Simulate { policy_type, config, days } => {
+        Commands::Simulate { policy_type, config, days, sample_rate, background } => {
             println!("{}", format!("Simulating policy impact over {} days...", days).cyan());
             let config_json: serde_json::Value = serde_json::from_str(&config)?;
-            
+            let request = json!({
+                "policy_type": policy_type,
+                "policy_config": config_json,
+                "time_range_days": days,
+                "sample_rate": sample_rate
+            });
+
+            let url = if background {
+                format!("{}/policies/simulate/jobs", base_url)
+            } else {
+                format!("{}/policies/simulate", base_url)
+            };
             let response = client
-                .post(&format!("{}/policies/simulate", base_url))
+                .post(&url)
                 .header("Authorization", format!("Bearer {}", api_key))
                 .header("Content-Type", "application/json")
-                .json(&json!({
-                    "policy_type": policy_type,
-                    "policy_config": config_json,
-                    "time_range_days": days
-                }))
+                .json(&request)
                 .send()
                 .await?;
-            
-            if response.status().is_success() {
-                let result: serde_json::Value = response.json().await?;
-                println!("{}", "✓ Simulation completed".green());
-                println!("\n{}", "Results:".bold());
-                println!("  Total Requests: {}", result.get("total_requests").unwrap_or(&json!(0)));
-                println!("  Would Block: {}", result.get("would_block").unwrap_or(&json!(0)));
-                println!("  Would Allow: {}", result.get("would_allow").unwrap_or(&json!(0)));
-                if let Some(impact) = result.get("estimated_impact") {
-                    println!("  Estimated Impact: {}", impact);
-                }
-            } else {
+
+            if !response.status().is_success() {
                 let error: serde_json::Value = response.json().await?;
                 eprintln!("{}", "✗ Simulation failed".red());
                 eprintln!("  Error: {}", error.get("message").unwrap_or(&json!("Unknown error")));
+                return Ok(());
+            }
+
+            let mut result: serde_json::Value = response.json().await?;
+            if background {
+                let job_id = result.get("id").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+                println!("  Job: {}", job_id);
+                loop {
+                    tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+                    let job: serde_json::Value = client
+                        .get(&format!("{}/policies/simulate/jobs/{}", base_url, job_id))
+                        .header("Authorization", format!("Bearer {}", api_key))
+                        .send()
+                        .await?
+                        .json()
+                        .await?;
+                    match job.get("status").and_then(|v| v.as_str()).unwrap_or_default() {
+                        "COMPLETED" => {
+                            result = job.get("result").cloned().unwrap_or(json!({}));
+                            break;
+                        }
+                        "FAILED" => {
+                            eprintln!("{}", "✗ Simulation failed".red());
+                            eprintln!("  Error: {}", job.get("error").unwrap_or(&json!("Unknown error")));
+                            return Ok(());
+                        }
+                        status => println!(
+                            "  {}: {}/{} records ({:.1}%)",
+                            status,
+                            job.get("records_processed").unwrap_or(&json!(0)),
+                            job.get("records_total").filter(|v| !v.is_null()).unwrap_or(&json!("?")),
+                            job.get("progress_percent").and_then(|v| v.as_f64()).unwrap_or(0.0)
+                        ),
+                    }
+                }
+            }
+
+            println!("{}", "✓ Simulation completed".green());
+            println!("\n{}", "Results:".bold());
+            println!("  Total Requests: {}", result.get("total_requests").unwrap_or(&json!(0)));
+            println!("  Would Block: {}", result.get("would_block").unwrap_or(&json!(0)));
+            println!("  Would Allow: {}", result.get("would_allow").unwrap_or(&json!(0)));
+            if let Some(impact) = result.get("estimated_impact") {
+                println!("  Estimated Impact: {}", impact);
+            }
+            if let Some(rate) = result.get("sample_rate") {
+                println!("  Sample Rate: {}", rate);
             }
         }
         Commands::Rollback { policy_id, version, dry_run, notes } => {
@@ -243,8 +346,178 @@ async fn main() -> anyhow::Result<()> {
                 eprintln!("  Error: {}", error.get("message").unwrap_or(&json!("Unknown error")));
             }
         }
+        Commands::VerifyChain { from, to } => {
+            println!("{}", "Verifying compliance record chain...".cyan());
+            
+            let mut query = Vec::new();
+            if let Some(from) = &from {
+                query.push(("from", from));
+            }
+            if let Some(to) = &to {
+                query.push(("to", to));
+            }
+            
+            let response = client
+                .get(&format!("{}/verify_chain", base_url))
+                .header("Authorization", format!("Bearer {}", api_key))
+                .query(&query)
+                .send()
+                .await?;
+            
+            if response.status().is_success() {
+                let result: serde_json::Value = response.json().await?;
+                let valid = result.get("valid").and_then(|v| v.as_bool()).unwrap_or(false);
+                if valid {
+                    println!("{}", "✓ Chain intact".green());
+                } else {
+                    println!("{}", "✗ Chain verification failed".red());
+                }
+                println!("\n{}", "Chain:".bold());
+                println!("  Records Checked: {}", result.get("records_checked").unwrap_or(&json!(0)));
+                println!("  Sequence: {} - {}",
+                    result.get("first_seq").unwrap_or(&json!(null)),
+                    result.get("last_seq").unwrap_or(&json!(null)));
+                println!("  Head Hash: {}", result.get("head_hash").and_then(|v| v.as_str()).unwrap_or("N/A"));
+                if let Some(issues) = result.get("issues").and_then(|v| v.as_array()) {
+                    if !issues.is_empty() {
+                        println!("\n{}", "Issues:".bold());
+                        for issue in issues {
+                            println!("  [{}] seq {} ({}): {}",
+                                issue.get("kind").and_then(|v| v.as_str()).unwrap_or("?"),
+                                issue.get("chain_seq").unwrap_or(&json!(null)),
+                                issue.get("seal_id").and_then(|v| v.as_str()).unwrap_or("-"),
+                                issue.get("detail").and_then(|v| v.as_str()).unwrap_or(""));
+                        }
+                    }
+                }
+                if !valid {
+                    std::process::exit(1);
+                }
+            } else {
+                let error: serde_json::Value = response.json().await?;
+                eprintln!("{}", "✗ Chain verification failed".red());
+                eprintln!("  Error: {}", error.get("message").unwrap_or(&json!("Unknown error")));
+                std::process::exit(1);
+            }
+        }
+        Commands::PolicyExport { dir } => {
+            println!("{}", "Exporting policy bundle...".cyan());
+            
+            let response = client
+                .get(&format!("{}/policy_bundle", base_url))
+                .header("Authorization", format!("Bearer {}", api_key))
+                .send()
+                .await?;
+            
+            if response.status().is_success() {
+                let result: serde_json::Value = response.json().await?;
+                let files: BTreeMap<String, String> = serde_json::from_value(result["files"].clone())?;
+                let root = Path::new(&dir);
+                // Files of policies that no longer exist must not linger in the directory
+                let mut existing = BTreeMap::new();
+                for entry in BUNDLE_ENTRIES {
+                    let path = root.join(entry);
+                    if path.is_dir() {
+                        read_bundle_files(root, &path, &mut existing)?;
+                    }
+                }
+                for stale in existing.keys().filter(|path| !files.contains_key(*path)) {
+                    fs::remove_file(root.join(stale))?;
+                }
+                for (path, content) in &files {
+                    let target = root.join(path);
+                    if let Some(parent) = target.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    fs::write(target, content)?;
+                }
+                println!("{}", format!("✓ Wrote {} files to {}", files.len(), dir).green());
+                println!("  Digest: {}", result.get("digest").and_then(|v| v.as_str()).unwrap_or("N/A"));
+            } else {
+                let error: serde_json::Value = response.json().await?;
+                eprintln!("{}", "✗ Export failed".red());
+                eprintln!("  Error: {}", error.get("message").unwrap_or(&json!("Unknown error")));
+                std::process::exit(1);
+            }
+        }
+        Commands::PolicyImport { dir, dry_run, notes } => {
+            println!("{}", "Importing policy bundle...".cyan());
+            
+            let mut files = BTreeMap::new();
+            read_bundle_files(Path::new(&dir), Path::new(&dir), &mut files)?;
+            
+            let response = client
+                .post(&format!("{}/policy_bundle/import", base_url))
+                .header("Authorization", format!("Bearer {}", api_key))
+                .header("Content-Type", "application/json")
+                .json(&json!({
+                    "files": files,
+                    "dry_run": dry_run,
+                    "notes": notes
+                }))
+                .send()
+                .await?;
+            
+            if response.status().is_success() {
+                let result: serde_json::Value = response.json().await?;
+                let changes = result.get("diff")
+                    .or_else(|| result.get("plan").and_then(|p| p.get("changes")))
+                    .and_then(|v| v.as_array())
+                    .cloned()
+                    .unwrap_or_default();
+                match result.get("status").and_then(|v| v.as_str()) {
+                    Some("NO_CHANGES") => println!("{}", "✓ Bundle matches the live policies".green()),
+                    Some("DRY_RUN") => println!("{}", "✓ Bundle is valid (dry-run)".green()),
+                    _ => println!("{}", "✓ Import submitted for approval".green()),
+                }
+                if !changes.is_empty() {
+                    println!("\n{}", "Changes:".bold());
+                    for change in &changes {
+                        let path = change.get("path").and_then(|v| v.as_str()).unwrap_or("?");
+                        match change.get("change").and_then(|v| v.as_str()) {
+                            Some("ADDED") => println!("  {} {}", "+".green(), path),
+                            Some("REMOVED") => println!("  {} {}", "-".red(), path),
+                            _ => println!("  {} {}", "~".yellow(), path),
+                        }
+                    }
+                }
+                if let Some(id) = result.get("id").and_then(|v| v.as_str()) {
+                    println!("\n  Import ID: {}", id);
+                    println!("  Approve with POST /policies/{}/approve, then run: veridion policy-apply --import-id {}", id, id);
+                }
+            } else {
+                let error: serde_json::Value = response.json().await?;
+                eprintln!("{}", "✗ Import failed".red());
+                eprintln!("  Error: {}", error.get("message").unwrap_or(&json!("Unknown error")));
+                std::process::exit(1);
+            }
+        }
+        Commands::PolicyApply { import_id } => {
+            println!("{}", "Applying policy bundle import...".cyan());
+            
+            let response = client
+                .post(&format!("{}/policy_bundle/imports/{}/apply", base_url, import_id))
+                .header("Authorization", format!("Bearer {}", api_key))
+                .send()
+                .await?;
+            
+            if response.status().is_success() {
+                let result: serde_json::Value = response.json().await?;
+                let summary = &result["summary"];
+                println!("{}", "✓ Policy bundle applied".green());
+                println!("\n{}", "Summary:".bold());
+                println!("  Policies Activated: {}", summary.get("policies_activated").unwrap_or(&json!(0)));
+                println!("  Policies Deactivated: {}", summary.get("policies_deactivated").unwrap_or(&json!(0)));
+                println!("  Asset Policies Changed: {}", summary.get("asset_policies_changed").unwrap_or(&json!(0)));
+                println!("  Enforcement Mode Changed: {}", summary.get("enforcement_mode_changed").unwrap_or(&json!(false)));
+            } else {
+                let error: serde_json::Value = response.json().await?;
+                eprintln!("{}", "✗ Apply failed".red());
+                eprintln!("  Error: {}", error.get("message").unwrap_or(&json!("Unknown error")));
+                std::process::exit(1);
+            }
+        }
     }
     
     Ok(())
 }
-